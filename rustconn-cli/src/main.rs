@@ -3,19 +3,27 @@
 //! Provides commands for listing, adding, exporting, importing, testing connections,
 //! managing snippets, groups, templates, clusters, variables, and Wake-on-LAN functionality.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::{Parser, Subcommand, ValueEnum};
 use rustconn_core::cluster::Cluster;
 use rustconn_core::config::ConfigManager;
+use rustconn_core::connection::{host_for_uri, unbracketed_host};
+use rustconn_core::import::{
+    AnsibleInventoryImporter, BatchCancelHandle, BatchImporter, ImportStream, SshConfigImporter,
+    STREAMING_IMPORT_THRESHOLD,
+};
 use rustconn_core::models::{
-    Connection, ConnectionGroup, ConnectionTemplate, ProtocolType, Snippet,
+    Connection, ConnectionGroup, ConnectionTemplate, ProtocolType, Snippet, TemplateOverrides,
 };
+use rustconn_core::progress::CallbackProgressReporter;
+use rustconn_core::protocol::ProtocolRegistry;
+use rustconn_core::search::{SearchEngine, UsageSort};
 use rustconn_core::snippet::SnippetManager;
-use rustconn_core::variables::Variable;
-use rustconn_core::wol::{MacAddress, WolConfig};
+use rustconn_core::variables::{Variable, VariableType};
+use rustconn_core::wol::{MacAddress, SecureOnPassword, WolConfig};
 
 /// `RustConn` command-line interface for managing remote connections
 #[derive(Parser)]
@@ -27,6 +35,10 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub config: Option<PathBuf>,
 
+    /// Show what import or export would change without writing anything
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -52,13 +64,35 @@ pub enum Commands {
         /// Filter connections by tag
         #[arg(short, long)]
         tag: Option<String>,
+
+        /// Sort connections (insertion order if omitted)
+        #[arg(short, long, value_enum)]
+        sort: Option<ListSort>,
+
+        /// Comma-separated fields to show (e.g. name,host,tags)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        fields: Option<Vec<ListField>>,
+
+        /// Include archived connections
+        #[arg(long)]
+        archived: bool,
     },
 
     /// Connect to a server by name or ID
     #[command(about = "Initiate a connection to a remote server")]
     Connect {
-        /// Connection name or UUID
-        name: String,
+        /// Connection name or UUID (optional with --interactive)
+        #[arg(required_unless_present = "interactive")]
+        name: Option<String>,
+
+        /// Pick from ranked matches when the name is ambiguous or omitted
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Fingerprint of the SSH agent key to authenticate with (overrides
+        /// the connection's stored agent key preference)
+        #[arg(long, value_name = "FINGERPRINT")]
+        agent_key: Option<String>,
     },
 
     /// Add a new connection
@@ -68,17 +102,17 @@ pub enum Commands {
         #[arg(short, long)]
         name: String,
 
-        /// Host address (hostname or IP)
-        #[arg(short = 'H', long)]
-        host: String,
+        /// Host address (hostname or IP); optional when the template sets one
+        #[arg(short = 'H', long, required_unless_present = "template")]
+        host: Option<String>,
 
         /// Port number (defaults to protocol default: SSH=22, RDP=3389, VNC=5900)
         #[arg(short, long)]
         port: Option<u16>,
 
-        /// Protocol type (ssh, rdp, vnc)
-        #[arg(short = 'P', long, default_value = "ssh")]
-        protocol: String,
+        /// Protocol type (ssh, rdp, vnc) [default: ssh]
+        #[arg(short = 'P', long, conflicts_with = "template")]
+        protocol: Option<String>,
 
         /// Username for authentication
         #[arg(short, long)]
@@ -87,6 +121,10 @@ pub enum Commands {
         /// Path to SSH private key file
         #[arg(short, long)]
         key: Option<PathBuf>,
+
+        /// Template name or ID to create the connection from
+        #[arg(long)]
+        template: Option<String>,
     },
 
     /// Export connections to external format
@@ -96,7 +134,7 @@ pub enum Commands {
         #[arg(short, long, value_enum)]
         format: ExportFormatArg,
 
-        /// Output file or directory path
+        /// Output file or directory path (`-` writes single-file formats to stdout)
         #[arg(short, long)]
         output: PathBuf,
     },
@@ -108,7 +146,7 @@ pub enum Commands {
         #[arg(short, long, value_enum)]
         format: ImportFormatArg,
 
-        /// Input file path
+        /// Input file path (`-` reads single-file formats from stdin)
         file: PathBuf,
     },
 
@@ -121,6 +159,10 @@ pub enum Commands {
         /// Connection timeout in seconds
         #[arg(short, long, default_value = "10")]
         timeout: u64,
+
+        /// Also test archived connections when testing all
+        #[arg(long)]
+        include_archived: bool,
     },
 
     /// Delete a connection
@@ -130,6 +172,20 @@ pub enum Commands {
         name: String,
     },
 
+    /// Archive a connection
+    #[command(about = "Hide a connection from listings without deleting it")]
+    Archive {
+        /// Connection name or UUID
+        name: String,
+    },
+
+    /// Restore an archived connection
+    #[command(about = "Restore an archived connection")]
+    Unarchive {
+        /// Connection name or UUID
+        name: String,
+    },
+
     /// Show connection details
     #[command(about = "Show connection details")]
     Show {
@@ -173,6 +229,10 @@ pub enum Commands {
         /// UDP port (default: 9)
         #[arg(short, long, default_value = "9")]
         port: u16,
+
+        /// SecureOn password appended to the packet (format: 01:02:03:04:05:06)
+        #[arg(long, value_name = "PASSWORD")]
+        secure_on: Option<String>,
     },
 
     /// Manage command snippets
@@ -195,6 +255,10 @@ pub enum Commands {
     #[command(subcommand, about = "Manage global variables")]
     Var(VariableCommands),
 
+    /// Share per-connection automation
+    #[command(subcommand, about = "Export and import connection automation packs")]
+    Automation(AutomationCommands),
+
     /// Duplicate a connection
     #[command(about = "Duplicate an existing connection")]
     Duplicate {
@@ -222,6 +286,138 @@ pub enum OutputFormat {
     Csv,
 }
 
+/// Sort order for the list command
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    /// By name (case-insensitive)
+    Name,
+    /// By host (case-insensitive)
+    Host,
+    /// By protocol
+    Protocol,
+    /// Most recently connected first, never-connected last
+    LastConnected,
+}
+
+impl ListSort {
+    /// Sorts connections in place; the sort is stable
+    pub fn sort(self, connections: &mut [&Connection]) {
+        match self {
+            Self::Name => connections.sort_by_cached_key(|c| c.name.to_lowercase()),
+            Self::Host => connections.sort_by_cached_key(|c| c.host.to_lowercase()),
+            Self::Protocol => connections.sort_by_key(|c| c.protocol.as_str()),
+            Self::LastConnected => {
+                connections.sort_by_key(|c| std::cmp::Reverse(c.last_connected));
+            }
+        }
+    }
+}
+
+/// Field shown by the list command
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListField {
+    /// Connection UUID
+    Id,
+    /// Connection name
+    Name,
+    /// Host address
+    Host,
+    /// Port number
+    Port,
+    /// Protocol
+    Protocol,
+    /// Username
+    Username,
+    /// Tags
+    Tags,
+    /// Time of the last successful connection
+    LastConnected,
+}
+
+/// Fields shown by the list command when `--fields` is not given
+pub const DEFAULT_LIST_FIELDS: &[ListField] = &[
+    ListField::Name,
+    ListField::Host,
+    ListField::Port,
+    ListField::Protocol,
+];
+
+impl ListField {
+    /// Column header for table output
+    #[must_use]
+    pub const fn header(self) -> &'static str {
+        match self {
+            Self::Id => "ID",
+            Self::Name => "NAME",
+            Self::Host => "HOST",
+            Self::Port => "PORT",
+            Self::Protocol => "PROTOCOL",
+            Self::Username => "USERNAME",
+            Self::Tags => "TAGS",
+            Self::LastConnected => "LAST CONNECTED",
+        }
+    }
+
+    /// Key for CSV headers and JSON objects
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Host => "host",
+            Self::Port => "port",
+            Self::Protocol => "protocol",
+            Self::Username => "username",
+            Self::Tags => "tags",
+            Self::LastConnected => "last_connected",
+        }
+    }
+
+    /// Machine-readable value for CSV output
+    #[must_use]
+    pub fn value(self, conn: &Connection) -> String {
+        match self {
+            Self::Id => conn.id.to_string(),
+            Self::Name => conn.name.clone(),
+            Self::Host => conn.host.clone(),
+            Self::Port => conn.port.to_string(),
+            Self::Protocol => conn.protocol.as_str().to_string(),
+            Self::Username => conn.username.clone().unwrap_or_default(),
+            Self::Tags => conn.tags.join(";"),
+            Self::LastConnected => conn
+                .last_connected
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Human-readable value for table output
+    #[must_use]
+    pub fn display_value(self, conn: &Connection) -> String {
+        match self {
+            Self::Protocol => conn.protocol.to_string(),
+            Self::Tags => conn.tags.join(", "),
+            Self::LastConnected => conn.last_connected.map_or_else(
+                || "never".to_string(),
+                |t| t.format("%Y-%m-%d %H:%M").to_string(),
+            ),
+            _ => self.value(conn),
+        }
+    }
+
+    /// Typed value for JSON output
+    #[must_use]
+    pub fn json_value(self, conn: &Connection) -> serde_json::Value {
+        match self {
+            Self::Port => conn.port.into(),
+            Self::Username => conn.username.clone().into(),
+            Self::Tags => conn.tags.clone().into(),
+            Self::LastConnected => conn.last_connected.map(|t| t.to_rfc3339()).into(),
+            _ => self.value(conn).into(),
+        }
+    }
+}
+
 /// Export format options
 #[derive(Clone, Copy, Debug, ValueEnum)]
 pub enum ExportFormatArg {
@@ -239,6 +435,8 @@ pub enum ExportFormatArg {
     RoyalTs,
     /// MobaXterm session format (.mxtsessions)
     MobaXterm,
+    /// Termius import JSON
+    Termius,
 }
 
 /// Import format options
@@ -258,6 +456,8 @@ pub enum ImportFormatArg {
     RoyalTs,
     /// MobaXterm session format (.mxtsessions)
     MobaXterm,
+    /// PuTTY registry export (.reg) or sessions directory
+    Putty,
 }
 
 /// Snippet subcommands
@@ -352,7 +552,7 @@ pub enum GroupCommands {
     },
 
     /// Create a new group
-    #[command(about = "Create a new connection group")]
+    #[command(about = "Create a new connection group", visible_alias = "add")]
     Create {
         /// Group name
         #[arg(short, long)]
@@ -367,11 +567,26 @@ pub enum GroupCommands {
         description: Option<String>,
     },
 
+    /// Rename a group
+    #[command(about = "Rename a connection group")]
+    Rename {
+        /// Group name or ID
+        name: String,
+
+        /// New group name
+        new_name: String,
+    },
+
     /// Delete a group
     #[command(about = "Delete a connection group")]
     Delete {
         /// Group name or ID
         name: String,
+
+        /// Delete a non-empty group; its connections and subgroups move to
+        /// the deleted group's parent (or to the top level)
+        #[arg(long)]
+        force: bool,
     },
 
     /// Add a connection to a group
@@ -580,6 +795,10 @@ pub enum VariableCommands {
         /// Description
         #[arg(short, long)]
         description: Option<String>,
+
+        /// Value type: string, int, bool, secret (value is a secret backend key) or enum:a,b,c (keeps the existing type if omitted)
+        #[arg(long = "type")]
+        value_type: Option<VariableType>,
     },
 
     /// Delete a global variable
@@ -590,6 +809,31 @@ pub enum VariableCommands {
     },
 }
 
+/// Automation subcommands
+#[derive(Subcommand)]
+pub enum AutomationCommands {
+    /// Export a connection's automation to a pack file
+    #[command(about = "Export expect rules, key sequence and tasks to a pack file")]
+    Export {
+        /// Connection name or UUID
+        name: String,
+
+        /// Output pack file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Apply an automation pack to a connection
+    #[command(about = "Replace a connection's automation with a pack file")]
+    Import {
+        /// Pack file path
+        file: PathBuf,
+
+        /// Connection name or UUID
+        name: String,
+    },
+}
+
 /// Parse a key=value pair for variable substitution
 fn parse_key_val(s: &str) -> Result<(String, String), String> {
     let pos = s
@@ -607,13 +851,23 @@ fn main() {
             protocol,
             group,
             tag,
+            sort,
+            fields,
+            archived,
         } => cmd_list(
             format,
             protocol.as_deref(),
             group.as_deref(),
             tag.as_deref(),
+            sort,
+            fields.as_deref(),
+            archived,
         ),
-        Commands::Connect { name } => cmd_connect(&name),
+        Commands::Connect {
+            name,
+            interactive,
+            agent_key,
+        } => cmd_connect(name.as_deref(), interactive, agent_key.as_deref()),
         Commands::Add {
             name,
             host,
@@ -621,18 +875,26 @@ fn main() {
             protocol,
             user,
             key,
+            template,
         } => cmd_add(
             &name,
-            &host,
+            host.as_deref(),
             port,
-            &protocol,
+            protocol.as_deref(),
             user.as_deref(),
             key.as_deref(),
+            template.as_deref(),
         ),
-        Commands::Export { format, output } => cmd_export(format, &output),
-        Commands::Import { format, file } => cmd_import(format, &file),
-        Commands::Test { name, timeout } => cmd_test(&name, timeout),
+        Commands::Export { format, output } => cmd_export(format, &output, cli.dry_run),
+        Commands::Import { format, file } => cmd_import(format, &file, cli.dry_run),
+        Commands::Test {
+            name,
+            timeout,
+            include_archived,
+        } => cmd_test(&name, timeout, include_archived),
         Commands::Delete { name } => cmd_delete(&name),
+        Commands::Archive { name } => cmd_archive(&name, true),
+        Commands::Unarchive { name } => cmd_archive(&name, false),
         Commands::Show { name } => cmd_show(&name),
         Commands::Update {
             name,
@@ -651,12 +913,14 @@ fn main() {
             target,
             broadcast,
             port,
-        } => cmd_wol(&target, &broadcast, port),
+            secure_on,
+        } => cmd_wol(&target, &broadcast, port, secure_on.as_deref()),
         Commands::Snippet(subcmd) => cmd_snippet(subcmd),
         Commands::Group(subcmd) => cmd_group(subcmd),
         Commands::Template(subcmd) => cmd_template(subcmd),
         Commands::Cluster(subcmd) => cmd_cluster(subcmd),
         Commands::Var(subcmd) => cmd_var(subcmd),
+        Commands::Automation(subcmd) => cmd_automation(subcmd),
         Commands::Duplicate { name, new_name } => cmd_duplicate(&name, new_name.as_deref()),
         Commands::Stats => cmd_stats(),
     };
@@ -673,6 +937,9 @@ fn cmd_list(
     protocol: Option<&str>,
     group: Option<&str>,
     tag: Option<&str>,
+    sort: Option<ListSort>,
+    fields: Option<&[ListField]>,
+    include_archived: bool,
 ) -> Result<(), CliError> {
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
@@ -698,9 +965,14 @@ fn cmd_list(
         .transpose()?;
 
     // Filter connections
-    let filtered: Vec<&Connection> = connections
+    let mut filtered: Vec<&Connection> = connections
         .iter()
         .filter(|c| {
+            // Archived connections are hidden unless asked for
+            if c.archived && !include_archived {
+                return false;
+            }
+
             // Filter by protocol
             if let Some(proto_filter) = protocol {
                 if c.protocol.as_str() != proto_filter.to_lowercase() {
@@ -727,63 +999,81 @@ fn cmd_list(
         })
         .collect();
 
+    if let Some(sort) = sort {
+        sort.sort(&mut filtered);
+    }
+
     match format {
-        OutputFormat::Table => print_table(&filtered),
-        OutputFormat::Json => print_json(&filtered)?,
-        OutputFormat::Csv => print_csv(&filtered),
+        OutputFormat::Table => print_table(&filtered, fields.unwrap_or(DEFAULT_LIST_FIELDS)),
+        OutputFormat::Json => print_json(&filtered, fields)?,
+        OutputFormat::Csv => print_csv(&filtered, fields.unwrap_or(DEFAULT_LIST_FIELDS)),
     }
 
     Ok(())
 }
 
 /// Print connections as a formatted table
-fn print_table(connections: &[&Connection]) {
-    println!("{}", format_table(connections));
+fn print_table(connections: &[&Connection], fields: &[ListField]) {
+    println!("{}", format_table(connections, fields));
 }
 
-/// Format connections as a table string
+/// Format the selected fields of connections as a table string
 #[must_use]
-pub fn format_table(connections: &[&Connection]) -> String {
+pub fn format_table(connections: &[&Connection], fields: &[ListField]) -> String {
     if connections.is_empty() {
         return "No connections found.".to_string();
     }
 
     let mut output = String::new();
 
-    // Calculate column widths
-    let name_width = connections
+    let rows: Vec<Vec<String>> = connections
         .iter()
-        .map(|c| c.name.len())
-        .max()
-        .unwrap_or(4)
-        .max(4);
-    let host_width = connections
+        .map(|c| fields.iter().map(|f| f.display_value(c)).collect())
+        .collect();
+    let widths: Vec<usize> = fields
         .iter()
-        .map(|c| c.host.len())
-        .max()
-        .unwrap_or(4)
-        .max(4);
-    let protocol_width = 8; // "PROTOCOL" or "SSH/RDP/VNC/SPICE"
-    let port_width = 5; // "PORT" or max 5 digits
+        .enumerate()
+        .map(|(i, f)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .max()
+                .unwrap_or(0)
+                .max(f.header().len())
+        })
+        .collect();
+
+    let format_row = |cells: &mut dyn Iterator<Item = &str>| {
+        cells
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
 
     // Print header
     let _ = writeln!(
         output,
-        "{:<name_width$}  {:<host_width$}  {:<port_width$}  {:<protocol_width$}",
-        "NAME", "HOST", "PORT", "PROTOCOL"
+        "{}",
+        format_row(&mut fields.iter().map(|f| f.header()))
     );
     let _ = writeln!(
         output,
-        "{:-<name_width$}  {:-<host_width$}  {:-<port_width$}  {:-<protocol_width$}",
-        "", "", "", ""
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("  ")
     );
 
     // Print rows
-    for conn in connections {
+    for row in &rows {
         let _ = writeln!(
             output,
-            "{:<name_width$}  {:<host_width$}  {:<port_width$}  {:<protocol_width$}",
-            conn.name, conn.host, conn.port, conn.protocol
+            "{}",
+            format_row(&mut row.iter().map(String::as_str))
         );
     }
 
@@ -791,49 +1081,80 @@ pub fn format_table(connections: &[&Connection]) -> String {
 }
 
 /// Print connections as JSON
-fn print_json(connections: &[&Connection]) -> Result<(), CliError> {
-    let json = format_json(connections)?;
+fn print_json(connections: &[&Connection], fields: Option<&[ListField]>) -> Result<(), CliError> {
+    let json = format_json(connections, fields)?;
     println!("{json}");
     Ok(())
 }
 
 /// Format connections as JSON string
 ///
+/// Without selected fields each connection is written as a
+/// [`ConnectionOutput`]; otherwise only the selected fields appear.
+///
 /// # Errors
 ///
 /// Returns `CliError::Config` if JSON serialization fails.
-pub fn format_json(connections: &[&Connection]) -> Result<String, CliError> {
-    let output: Vec<ConnectionOutput> = connections.iter().map(|c| (*c).into()).collect();
-    serde_json::to_string_pretty(&output)
-        .map_err(|e| CliError::Config(format!("Failed to serialize to JSON: {e}")))
+pub fn format_json(
+    connections: &[&Connection],
+    fields: Option<&[ListField]>,
+) -> Result<String, CliError> {
+    let result = match fields {
+        None => {
+            let output: Vec<ConnectionOutput> = connections.iter().map(|c| (*c).into()).collect();
+            serde_json::to_string_pretty(&output)
+        }
+        Some(fields) => {
+            let output: Vec<SelectedFields<'_>> = connections
+                .iter()
+                .map(|conn| SelectedFields { conn, fields })
+                .collect();
+            serde_json::to_string_pretty(&output)
+        }
+    };
+    result.map_err(|e| CliError::Config(format!("Failed to serialize to JSON: {e}")))
+}
+
+/// Serializes the selected fields of a connection, in selection order
+struct SelectedFields<'a> {
+    conn: &'a Connection,
+    fields: &'a [ListField],
+}
+
+impl serde::Serialize for SelectedFields<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for field in self.fields {
+            map.serialize_entry(field.key(), &field.json_value(self.conn))?;
+        }
+        map.end()
+    }
 }
 
 /// Print connections as CSV
-fn print_csv(connections: &[&Connection]) {
-    println!("{}", format_csv(connections));
+fn print_csv(connections: &[&Connection], fields: &[ListField]) {
+    println!("{}", format_csv(connections, fields));
 }
 
-/// Format connections as CSV string
+/// Format the selected fields of connections as a CSV string
 #[must_use]
-pub fn format_csv(connections: &[&Connection]) -> String {
+pub fn format_csv(connections: &[&Connection], fields: &[ListField]) -> String {
     let mut output = String::new();
 
     // Print header
-    output.push_str("name,host,port,protocol\n");
+    let header: Vec<&str> = fields.iter().map(|f| f.key()).collect();
+    let _ = writeln!(output, "{}", header.join(","));
 
     // Print rows
     for conn in connections {
         // Escape fields that might contain commas or quotes
-        let name = escape_csv_field(&conn.name);
-        let host = escape_csv_field(&conn.host);
-        let _ = writeln!(
-            output,
-            "{},{},{},{}",
-            name,
-            host,
-            conn.port,
-            conn.protocol.as_str()
-        );
+        let row: Vec<String> = fields
+            .iter()
+            .map(|f| escape_csv_field(&f.value(conn)))
+            .collect();
+        let _ = writeln!(output, "{}", row.join(","));
     }
 
     output.trim_end().to_string()
@@ -874,7 +1195,11 @@ impl From<&Connection> for ConnectionOutput {
 }
 
 /// Connect command handler
-fn cmd_connect(name: &str) -> Result<(), CliError> {
+fn cmd_connect(
+    name: Option<&str>,
+    interactive: bool,
+    agent_key: Option<&str>,
+) -> Result<(), CliError> {
     // Load connections
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
@@ -890,22 +1215,222 @@ fn cmd_connect(name: &str) -> Result<(), CliError> {
     }
 
     // Find the connection
-    let connection = find_connection(&connections, name)?;
+    let connection = match (name, interactive) {
+        (Some(name), false) => find_connection(&connections, name)?,
+        (Some(name), true) => {
+            if let Ok(connection) = find_connection(&connections, name) {
+                connection
+            } else {
+                let groups = config_manager
+                    .load_groups()
+                    .map_err(|e| CliError::Config(format!("Failed to load groups: {e}")))?;
+                let candidates = rank_connections(&connections, &groups, name)?;
+                pick_connection(&candidates, &mut std::io::stdin().lock())?
+            }
+        }
+        (None, false) => {
+            return Err(CliError::Config(
+                "A connection name is required without --interactive".into(),
+            ))
+        }
+        (None, true) => {
+            let mut candidates: Vec<&Connection> =
+                connections.iter().filter(|c| !c.archived).collect();
+            candidates.sort_by(|a, b| UsageSort::RecentlyUsed.compare(a, b));
+            pick_connection(&candidates, &mut std::io::stdin().lock())?
+        }
+    };
 
     println!(
         "Connecting to '{}' ({} {}:{})...",
         connection.name, connection.protocol, connection.host, connection.port
     );
 
+    let agent_identity = resolve_agent_identity(connection, agent_key)?;
+
+    let registry = load_protocol_registry(&config_manager)?;
+    if let Some(protocol) = registry.get_custom_for(connection) {
+        protocol.validate_connection(connection).map_err(|e| {
+            CliError::Config(format!(
+                "Invalid '{}' connection: {e}",
+                protocol.display_name()
+            ))
+        })?;
+    }
+
+    // A missing jump host is an error rather than a silent direct connection
+    let find = |id: uuid::Uuid| connections.iter().find(|c| c.id == id);
+    let jump_hosts: Vec<&Connection> = rustconn_core::resolve_jump_chain(connection.id, find)
+        .map_err(|e| CliError::Config(format!("Cannot reach '{}': {e}", connection.name)))?
+        .into_iter()
+        .filter_map(find)
+        .collect();
+
+    // Malformed algorithm names are left for ssh to reject
+    if let rustconn_core::models::ProtocolConfig::Ssh(ref ssh_config) = connection.protocol_config {
+        for warning in ssh_config.algorithms.warnings() {
+            eprintln!("Warning: {warning}");
+        }
+    }
+
     // Build and execute the connection command
-    let command = build_connection_command(connection);
+    let command = build_connection_command(
+        connection,
+        &jump_hosts,
+        agent_identity.as_deref(),
+        &registry,
+    );
     execute_connection_command(&command)
 }
 
+/// Maximum number of connections offered by the interactive picker
+const PICKER_LIMIT: usize = 20;
+
+/// Ranks connections matching a query with the search engine
+fn rank_connections<'a>(
+    connections: &'a [Connection],
+    groups: &[ConnectionGroup],
+    query: &str,
+) -> Result<Vec<&'a Connection>, CliError> {
+    let parsed = SearchEngine::parse_query(query)
+        .map_err(|e| CliError::Config(format!("Invalid search query: {e}")))?;
+    let candidates: Vec<&Connection> = SearchEngine::new()
+        .search(&parsed, connections, groups)
+        .iter()
+        .filter_map(|result| connections.iter().find(|c| c.id == result.connection_id))
+        .collect();
+    if candidates.is_empty() {
+        return Err(CliError::ConnectionNotFound(query.to_string()));
+    }
+    Ok(candidates)
+}
+
+/// Lists candidates on stderr and reads the chosen number from `input`
+///
+/// A single candidate is chosen without prompting. Invalid numbers prompt
+/// again; an empty line or end of input cancels.
+fn pick_connection<'a>(
+    candidates: &[&'a Connection],
+    input: &mut impl std::io::BufRead,
+) -> Result<&'a Connection, CliError> {
+    let candidates = &candidates[..candidates.len().min(PICKER_LIMIT)];
+    match candidates {
+        [] => {
+            return Err(CliError::ConnectionNotFound(
+                "No connections configured".to_string(),
+            ))
+        }
+        [only] => return Ok(only),
+        _ => {}
+    }
+
+    for (index, conn) in candidates.iter().enumerate() {
+        eprintln!(
+            "{:>3}) {} ({} {}:{})",
+            index + 1,
+            conn.name,
+            conn.protocol,
+            conn.host,
+            conn.port
+        );
+    }
+
+    loop {
+        eprint!("Select a connection [1-{}]: ", candidates.len());
+        let mut line = String::new();
+        let read = input
+            .read_line(&mut line)
+            .map_err(|e| CliError::Config(format!("Failed to read selection: {e}")))?;
+        let line = line.trim();
+        if read == 0 || line.is_empty() {
+            return Err(CliError::Config("No connection selected".to_string()));
+        }
+        match line.parse::<usize>() {
+            Ok(choice) if (1..=candidates.len()).contains(&choice) => {
+                return Ok(candidates[choice - 1]);
+            }
+            _ => eprintln!("Invalid selection '{line}'"),
+        }
+    }
+}
+
+/// Creates a protocol registry including the user-defined protocols from settings
+fn load_protocol_registry(config_manager: &ConfigManager) -> Result<ProtocolRegistry, CliError> {
+    let settings = config_manager
+        .load_settings()
+        .map_err(|e| CliError::Config(format!("Failed to load settings: {e}")))?;
+
+    let mut registry = ProtocolRegistry::new();
+    for custom in &settings.connection.custom_protocols {
+        registry
+            .register_custom(&custom.name, &custom.command_template)
+            .map_err(|e| {
+                CliError::Config(format!("Invalid custom protocol '{}': {e}", custom.name))
+            })?;
+    }
+    Ok(registry)
+}
+
+/// Resolves SSH arguments restricting authentication to the selected agent key
+///
+/// The key is taken from `--agent-key` or, failing that, from the
+/// connection's stored agent key preference. Only an explicit `--agent-key`
+/// is required to resolve; a stored preference that cannot be honored (no
+/// agent, key not loaded) is reported as a warning and ssh runs unrestricted.
+fn resolve_agent_identity(
+    connection: &Connection,
+    agent_key: Option<&str>,
+) -> Result<Option<Vec<String>>, CliError> {
+    let rustconn_core::models::ProtocolConfig::Ssh(ref ssh_config) = connection.protocol_config
+    else {
+        return match agent_key {
+            Some(_) => Err(CliError::Config(
+                "--agent-key is only supported for SSH connections".to_string(),
+            )),
+            None => Ok(None),
+        };
+    };
+
+    let Some(fingerprint) = ssh_config.effective_agent_fingerprint(agent_key) else {
+        return Ok(None);
+    };
+
+    match rustconn_core::ssh_agent::SshAgentManager::from_env().identity_args_for_key(fingerprint) {
+        Ok(args) => Ok(Some(args)),
+        Err(e) if agent_key.is_some() => Err(CliError::Config(format!(
+            "Failed to select agent key {fingerprint}: {e}"
+        ))),
+        Err(e) => {
+            eprintln!("Warning: ignoring stored agent key {fingerprint}: {e}");
+            Ok(None)
+        }
+    }
+}
+
 /// Builds the command arguments for a connection based on its protocol
-fn build_connection_command(connection: &Connection) -> ConnectionCommand {
+///
+/// A user-defined protocol selected by the connection takes precedence over
+/// the built-in command for its protocol type.
+fn build_connection_command(
+    connection: &Connection,
+    jump_hosts: &[&Connection],
+    agent_identity: Option<&[String]>,
+    registry: &ProtocolRegistry,
+) -> ConnectionCommand {
+    if let Some(mut argv) = registry
+        .get_custom_for(connection)
+        .and_then(|protocol| protocol.build_command(connection))
+    {
+        let program = argv.remove(0);
+        return ConnectionCommand {
+            program,
+            args: argv,
+            env: Vec::new(),
+        };
+    }
+
     match connection.protocol {
-        ProtocolType::Ssh => build_ssh_command(connection),
+        ProtocolType::Ssh => build_ssh_command(connection, jump_hosts, agent_identity),
         ProtocolType::Rdp => build_rdp_command(connection),
         ProtocolType::Vnc => build_vnc_command(connection),
         ProtocolType::Spice => build_spice_command(connection),
@@ -919,10 +1444,21 @@ struct ConnectionCommand {
     program: String,
     /// Command-line arguments
     args: Vec<String>,
+    /// Extra environment variables for the program
+    env: Vec<(String, String)>,
 }
 
 /// Builds SSH command arguments
-fn build_ssh_command(connection: &Connection) -> ConnectionCommand {
+///
+/// `jump_hosts` is the resolved jump host chain, first hop first; when it is
+/// not empty it replaces the connection's manual proxy jump. When
+/// `agent_identity` is set, it replaces the configured key file so that
+/// only the selected agent key is offered.
+fn build_ssh_command(
+    connection: &Connection,
+    jump_hosts: &[&Connection],
+    agent_identity: Option<&[String]>,
+) -> ConnectionCommand {
     let mut args = Vec::new();
 
     // Add port if not default
@@ -933,14 +1469,19 @@ fn build_ssh_command(connection: &Connection) -> ConnectionCommand {
 
     // Get SSH-specific config
     if let rustconn_core::models::ProtocolConfig::Ssh(ref ssh_config) = connection.protocol_config {
-        // Add identity file if specified
-        if let Some(ref key_path) = ssh_config.key_path {
+        // Add identity restriction for the selected agent key, or the key file
+        if let Some(identity_args) = agent_identity {
+            args.extend_from_slice(identity_args);
+        } else if let Some(ref key_path) = ssh_config.key_path {
             args.push("-i".to_string());
             args.push(key_path.display().to_string());
         }
 
-        // Add proxy jump if specified
-        if let Some(ref proxy_jump) = ssh_config.proxy_jump {
+        // Add proxy jump through saved jump hosts, or as specified
+        if let Some(jump) = rustconn_core::proxy_jump_arg(jump_hosts) {
+            args.push("-J".to_string());
+            args.push(jump);
+        } else if let Some(ref proxy_jump) = ssh_config.proxy_jump {
             args.push("-J".to_string());
             args.push(proxy_jump.clone());
         }
@@ -968,18 +1509,30 @@ fn build_ssh_command(connection: &Connection) -> ConnectionCommand {
             args.push("-C".to_string());
         }
 
+        // Add port forwarding tunnels
+        for forward in &ssh_config.forwards {
+            args.extend(forward.command_args());
+        }
+
+        // Add algorithm preferences (before custom options, so they win)
+        args.extend(ssh_config.algorithms.command_args());
+
         // Add custom options
         for (key, value) in &ssh_config.custom_options {
             args.push("-o".to_string());
             args.push(format!("{key}={value}"));
         }
+
+        // Forward the session environment
+        args.extend(ssh_config.send_env_args());
     }
 
-    // Build the destination (user@host or just host)
-    let destination = connection.username.as_ref().map_or_else(
-        || connection.host.clone(),
-        |u| format!("{u}@{}", connection.host),
-    );
+    // Build the destination (user@host or just host); ssh takes IPv6 unbracketed
+    let host = unbracketed_host(&connection.host);
+    let destination = connection
+        .username
+        .as_ref()
+        .map_or_else(|| host.to_string(), |u| format!("{u}@{host}"));
     args.push(destination);
 
     // Add startup command if specified
@@ -989,9 +1542,15 @@ fn build_ssh_command(connection: &Connection) -> ConnectionCommand {
         }
     }
 
+    let env = match &connection.protocol_config {
+        rustconn_core::models::ProtocolConfig::Ssh(ssh_config) => ssh_config.environment.clone(),
+        _ => Vec::new(),
+    };
+
     ConnectionCommand {
         program: "ssh".to_string(),
         args,
+        env,
     }
 }
 
@@ -1000,7 +1559,11 @@ fn build_rdp_command(connection: &Connection) -> ConnectionCommand {
     let mut args = Vec::new();
 
     // Server address with port
-    args.push(format!("/v:{}:{}", connection.host, connection.port));
+    args.push(format!(
+        "/v:{}:{}",
+        host_for_uri(&connection.host),
+        connection.port
+    ));
 
     // Username
     if let Some(ref username) = connection.username {
@@ -1054,6 +1617,8 @@ fn build_rdp_command(connection: &Connection) -> ConnectionCommand {
     ConnectionCommand {
         program: "xfreerdp".to_string(),
         args,
+
+        env: Vec::new(),
     }
 }
 
@@ -1092,11 +1657,13 @@ fn build_vnc_command(connection: &Connection) -> ConnectionCommand {
     } else {
         connection.port
     };
-    args.push(format!("{}:{display}", connection.host));
+    args.push(format!("{}:{display}", host_for_uri(&connection.host)));
 
     ConnectionCommand {
         program: "vncviewer".to_string(),
         args,
+
+        env: Vec::new(),
     }
 }
 
@@ -1117,7 +1684,11 @@ fn build_spice_command(connection: &Connection) -> ConnectionCommand {
         "spice"
     };
 
-    let uri = format!("{scheme}://{}:{}", connection.host, connection.port);
+    let uri = format!(
+        "{scheme}://{}:{}",
+        host_for_uri(&connection.host),
+        connection.port
+    );
     args.push(uri);
 
     // Get SPICE-specific config
@@ -1146,6 +1717,8 @@ fn build_spice_command(connection: &Connection) -> ConnectionCommand {
     ConnectionCommand {
         program: "remote-viewer".to_string(),
         args,
+
+        env: Vec::new(),
     }
 }
 
@@ -1163,13 +1736,18 @@ fn build_zerotrust_command(connection: &Connection) -> ConnectionCommand {
         // Add any custom arguments
         args.extend(zt_config.custom_args.clone());
 
-        ConnectionCommand { program, args }
+        ConnectionCommand {
+            program,
+            args,
+            env: Vec::new(),
+        }
     } else {
         // Fallback - should not happen if protocol type matches
         eprintln!("Warning: ZeroTrust protocol type but no ZeroTrust config");
         ConnectionCommand {
             program: "echo".to_string(),
             args: vec!["Invalid Zero Trust configuration".to_string()],
+            env: Vec::new(),
         }
     }
 }
@@ -1198,7 +1776,7 @@ fn execute_connection_command(command: &ConnectionCommand) -> Result<(), CliErro
         use std::os::unix::process::CommandExt;
 
         let mut cmd = Command::new(&command.program);
-        cmd.args(&command.args);
+        cmd.args(&command.args).envs(command.env.iter().cloned());
 
         // Print the command being executed
         eprintln!("Executing: {} {}", command.program, command.args.join(" "));
@@ -1215,7 +1793,7 @@ fn execute_connection_command(command: &ConnectionCommand) -> Result<(), CliErro
     {
         // On non-Unix systems, spawn the process and wait
         let mut cmd = Command::new(&command.program);
-        cmd.args(&command.args);
+        cmd.args(&command.args).envs(command.env.iter().cloned());
 
         // Print the command being executed
         eprintln!("Executing: {} {}", command.program, command.args.join(" "));
@@ -1239,28 +1817,51 @@ fn execute_connection_command(command: &ConnectionCommand) -> Result<(), CliErro
 /// Add connection command handler
 fn cmd_add(
     name: &str,
-    host: &str,
+    host: Option<&str>,
     port: Option<u16>,
-    protocol: &str,
+    protocol: Option<&str>,
     user: Option<&str>,
     key: Option<&std::path::Path>,
+    template: Option<&str>,
 ) -> Result<(), CliError> {
-    // Parse protocol and determine default port
-    let (protocol_type, default_port) = parse_protocol(protocol)?;
-    let port = port.unwrap_or(default_port);
-
-    // Create the connection based on protocol
-    let mut connection = create_connection(name, host, port, protocol_type, key);
-
-    // Set username if provided
-    if let Some(username) = user {
-        connection.username = Some(username.to_string());
-    }
-
-    // Load existing connections
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
 
+    let connection = if let Some(template_name) = template {
+        let templates = config_manager
+            .load_templates()
+            .map_err(|e| CliError::Template(format!("Failed to load templates: {e}")))?;
+        let overrides = TemplateOverrides {
+            port,
+            username: user.map(String::from),
+        };
+        let mut connection = find_template(&templates, template_name)?
+            .instantiate(name, host, &overrides)
+            .map_err(|e| CliError::Template(e.to_string()))?;
+        if let Some(key_path) = key {
+            if !set_ssh_key(&mut connection, key_path) {
+                eprintln!("Warning: --key option is ignored for non-SSH templates");
+            }
+        }
+        connection
+    } else {
+        let host =
+            host.ok_or_else(|| CliError::Config("--host is required without --template".into()))?;
+        // Parse protocol and determine default port
+        let (protocol_type, default_port) = parse_protocol(protocol.unwrap_or("ssh"))?;
+        let port = port.unwrap_or(default_port);
+
+        // Create the connection based on protocol
+        let mut connection = create_connection(name, host, port, protocol_type, key);
+
+        // Set username if provided
+        if let Some(username) = user {
+            connection.username = Some(username.to_string());
+        }
+        connection
+    };
+
+    // Load existing connections
     let mut connections = config_manager
         .load_connections()
         .map_err(|e| CliError::Config(format!("Failed to load connections: {e}")))?;
@@ -1285,6 +1886,19 @@ fn cmd_add(
     Ok(())
 }
 
+/// Sets public key authentication with the given key; returns false for non-SSH connections
+fn set_ssh_key(connection: &mut Connection, key_path: &std::path::Path) -> bool {
+    if let rustconn_core::models::ProtocolConfig::Ssh(ref mut ssh_config) =
+        connection.protocol_config
+    {
+        ssh_config.key_path = Some(key_path.to_path_buf());
+        ssh_config.auth_method = rustconn_core::models::SshAuthMethod::PublicKey;
+        true
+    } else {
+        false
+    }
+}
+
 /// Parse protocol string and return protocol type with default port
 fn parse_protocol(protocol: &str) -> Result<(ProtocolType, u16), CliError> {
     match protocol.to_lowercase().as_str() {
@@ -1311,12 +1925,7 @@ fn create_connection(
             let mut conn = Connection::new_ssh(name.to_string(), host.to_string(), port);
             // Set key path if provided
             if let Some(key_path) = key {
-                if let rustconn_core::models::ProtocolConfig::Ssh(ref mut ssh_config) =
-                    conn.protocol_config
-                {
-                    ssh_config.key_path = Some(key_path.to_path_buf());
-                    ssh_config.auth_method = rustconn_core::models::SshAuthMethod::PublicKey;
-                }
+                set_ssh_key(&mut conn, key_path);
             }
             conn
         }
@@ -1347,8 +1956,20 @@ fn create_connection(
     }
 }
 
+/// Path argument meaning stdin or stdout
+const STDIO_PATH: &str = "-";
+
+/// Returns true if a path argument means stdin or stdout
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO_PATH
+}
+
 /// Export connections command handler
-fn cmd_export(format: ExportFormatArg, output: &std::path::Path) -> Result<(), CliError> {
+fn cmd_export(
+    format: ExportFormatArg,
+    output: &std::path::Path,
+    dry_run: bool,
+) -> Result<(), CliError> {
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
 
@@ -1369,13 +1990,22 @@ fn cmd_export(format: ExportFormatArg, output: &std::path::Path) -> Result<(), C
         ExportFormatArg::Native => rustconn_core::export::ExportFormat::Native,
         ExportFormatArg::RoyalTs => rustconn_core::export::ExportFormat::RoyalTs,
         ExportFormatArg::MobaXterm => rustconn_core::export::ExportFormat::MobaXterm,
+        ExportFormatArg::Termius => rustconn_core::export::ExportFormat::Termius,
     };
 
+    if is_stdio(output) {
+        return export_to_stdout(&connections, &groups, export_format, dry_run);
+    }
+
     // Create export options
     let options = rustconn_core::export::ExportOptions::new(export_format, output.to_path_buf());
 
     // Call the appropriate exporter
-    let result = export_connections(&connections, &groups, &options)?;
+    let result = if dry_run {
+        dry_run_export(&connections, &groups, &options)?
+    } else {
+        export_connections(&connections, &groups, &options)?
+    };
 
     // Display results
     println!(
@@ -1397,6 +2027,128 @@ fn cmd_export(format: ExportFormatArg, output: &std::path::Path) -> Result<(), C
         }
     }
 
+    if dry_run {
+        eprintln!("\nDry run: no files were written");
+    }
+
+    Ok(())
+}
+
+/// Runs an export into a temporary location and reports the files it would
+/// have written under the real output path
+fn dry_run_export(
+    connections: &[Connection],
+    groups: &[ConnectionGroup],
+    options: &rustconn_core::export::ExportOptions,
+) -> Result<rustconn_core::export::ExportResult, CliError> {
+    let staging_root =
+        std::env::temp_dir().join(format!("rustconn-dry-run-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&staging_root)
+        .map_err(|e| CliError::Export(format!("Failed to create staging directory: {e}")))?;
+    let staging = if options.format.exports_to_directory() {
+        staging_root.join("export")
+    } else {
+        staging_root.join(format!("export.{}", options.format.file_extension()))
+    };
+
+    let mut staged_options = options.clone();
+    staged_options.output_path.clone_from(&staging);
+    let result = export_connections(connections, groups, &staged_options);
+    let _ = std::fs::remove_dir_all(&staging_root);
+    let mut result = result?;
+
+    for file in &mut result.output_files {
+        if let Ok(relative) = file.strip_prefix(&staging) {
+            *file = if relative.as_os_str().is_empty() {
+                options.output_path.clone()
+            } else {
+                options.output_path.join(relative)
+            };
+        }
+    }
+
+    Ok(result)
+}
+
+/// Exports a single-file format to stdout, with the summary on stderr
+fn export_to_stdout(
+    connections: &[Connection],
+    groups: &[ConnectionGroup],
+    format: rustconn_core::export::ExportFormat,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    use rustconn_core::export::{
+        AnsibleExporter, AsbruExporter, ExportFormat, ExportOptions, ExportTarget,
+        MobaXtermExporter, NativeExport, RoyalTsExporter, SshConfigExporter, TermiusExporter,
+    };
+    use std::io::Write as _;
+
+    // No file is written, the path only tells the exporters which flavour
+    // of the format to render
+    let options = ExportOptions::new(
+        format,
+        std::path::PathBuf::from(format!("-.{}", format.file_extension())),
+    );
+    let rendered = match format {
+        ExportFormat::Ansible => {
+            AnsibleExporter::new().export_to_string(connections, groups, &options)
+        }
+        ExportFormat::SshConfig => {
+            SshConfigExporter::new().export_to_string(connections, groups, &options)
+        }
+        ExportFormat::Asbru => AsbruExporter::new().export_to_string(connections, groups, &options),
+        ExportFormat::RoyalTs => {
+            RoyalTsExporter::new().export_to_string(connections, groups, &options)
+        }
+        ExportFormat::MobaXterm => {
+            MobaXtermExporter::new().export_to_string(connections, groups, &options)
+        }
+        ExportFormat::Termius => {
+            TermiusExporter::new().export_to_string(connections, groups, &options)
+        }
+        ExportFormat::Native => NativeExport::with_data(
+            connections.to_vec(),
+            groups.to_vec(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .to_json()
+        .map(|content| {
+            let mut result = rustconn_core::export::ExportResult::new();
+            result.exported_count = connections.len();
+            (content, result)
+        }),
+        ExportFormat::Remmina => {
+            return Err(CliError::Export(format!(
+                "{format} exports one file per connection and cannot be written to stdout; \
+                 pass a directory to --output"
+            )));
+        }
+    };
+    let (content, result) = rendered.map_err(|e| CliError::Export(e.to_string()))?;
+
+    if !dry_run {
+        std::io::stdout()
+            .lock()
+            .write_all(content.as_bytes())
+            .map_err(|e| CliError::Export(format!("Failed to write to stdout: {e}")))?;
+    }
+
+    eprintln!(
+        "Export complete: {} connections exported, {} skipped",
+        result.exported_count, result.skipped_count
+    );
+    if !result.warnings.is_empty() {
+        eprintln!("\nWarnings:");
+        for warning in &result.warnings {
+            eprintln!("  - {warning}");
+        }
+    }
+    if dry_run {
+        eprintln!("\nDry run: nothing was written to stdout");
+    }
+
     Ok(())
 }
 
@@ -1408,7 +2160,7 @@ fn export_connections(
 ) -> Result<rustconn_core::export::ExportResult, CliError> {
     use rustconn_core::export::{
         AnsibleExporter, AsbruExporter, ExportFormat, ExportTarget, MobaXtermExporter,
-        NativeExport, RemminaExporter, RoyalTsExporter, SshConfigExporter,
+        NativeExport, RemminaExporter, RoyalTsExporter, SshConfigExporter, TermiusExporter,
     };
 
     let result = match options.format {
@@ -1469,15 +2221,31 @@ fn export_connections(
                 .export(connections, groups, options)
                 .map_err(|e| CliError::Export(e.to_string()))?
         }
+        ExportFormat::Termius => {
+            let exporter = TermiusExporter::new();
+            exporter
+                .export(connections, groups, options)
+                .map_err(|e| CliError::Export(e.to_string()))?
+        }
     };
 
     Ok(result)
 }
 
 /// Import connections command handler
-fn cmd_import(format: ImportFormatArg, file: &std::path::Path) -> Result<(), CliError> {
+fn cmd_import(
+    format: ImportFormatArg,
+    file: &std::path::Path,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let stdin_content = if is_stdio(file) {
+        Some(read_import_stdin(format)?)
+    } else {
+        None
+    };
+
     // Check if file exists
-    if !file.exists() {
+    if stdin_content.is_none() && !file.exists() {
         return Err(CliError::Import(format!(
             "File not found: {}",
             file.display()
@@ -1496,23 +2264,233 @@ fn cmd_import(format: ImportFormatArg, file: &std::path::Path) -> Result<(), Cli
         .load_groups()
         .map_err(|e| CliError::Config(format!("Failed to load existing groups: {e}")))?;
 
+    // Large SSH configs and INI inventories are merged and saved batch by
+    // batch; other large files are read whole, so warn before loading them
+    if stdin_content.is_none() && BatchImporter::should_stream(file) {
+        match format {
+            ImportFormatArg::SshConfig => {
+                let importer = SshConfigImporter::new();
+                let stream = importer
+                    .stream_from_path(file)
+                    .map_err(|e| CliError::Import(e.to_string()))?;
+                return stream_import(
+                    stream,
+                    &config_manager,
+                    existing_connections,
+                    existing_groups,
+                    dry_run,
+                );
+            }
+            ImportFormatArg::Ansible
+                if AnsibleInventoryImporter::is_streamable(file)
+                    .map_err(|e| CliError::Import(e.to_string()))? =>
+            {
+                let importer = AnsibleInventoryImporter::new();
+                let stream = importer
+                    .stream_from_path(file)
+                    .map_err(|e| CliError::Import(e.to_string()))?;
+                return stream_import(
+                    stream,
+                    &config_manager,
+                    existing_connections,
+                    existing_groups,
+                    dry_run,
+                );
+            }
+            _ => eprintln!(
+                "Warning: {} is larger than {} MiB and is read into memory whole",
+                file.display(),
+                STREAMING_IMPORT_THRESHOLD / (1024 * 1024)
+            ),
+        }
+    }
+
+    // Native bundles carry stable IDs, so reconcile by ID instead of name
+    if matches!(format, ImportFormatArg::Native) {
+        let native = match stdin_content {
+            Some(ref content) => rustconn_core::export::NativeExport::from_json(content),
+            None => rustconn_core::export::NativeExport::from_file(file),
+        }
+        .map_err(|e| CliError::Import(e.to_string()))?;
+        return merge_native_import(
+            &config_manager,
+            native,
+            &mut existing_connections,
+            &mut existing_groups,
+            dry_run,
+        );
+    }
+
     // Import connections using the appropriate importer
-    let import_result = import_connections(format, file)?;
+    let import_result = match stdin_content {
+        Some(ref content) => import_content(format, content)?,
+        None => import_connections(format, file)?,
+    };
 
-    // Display import summary
-    println!("Import Summary:");
-    println!(
-        "  Connections imported: {}",
-        import_result.connections.len()
+    print_import_summary(&import_result, import_result.connections.len());
+
+    // Merge imported connections with existing
+    let initial_count = existing_connections.len();
+    let initial_group_count = existing_groups.len();
+
+    merge_imported_groups(&mut existing_groups, import_result.groups);
+    merge_imported_connections(&mut existing_connections, import_result.connections);
+
+    let new_connections = existing_connections.len() - initial_count;
+    let new_groups = existing_groups.len() - initial_group_count;
+
+    // Save merged connections
+    if !dry_run {
+        save_import(&config_manager, &existing_connections, &existing_groups)?;
+    }
+
+    print_merge_results(
+        new_connections,
+        new_groups,
+        &existing_connections,
+        &existing_groups,
+        dry_run,
     );
-    println!("  Groups imported: {}", import_result.groups.len());
-    println!("  Entries skipped: {}", import_result.skipped.len());
-    println!("  Errors: {}", import_result.errors.len());
+
+    Ok(())
+}
+
+/// Connections merged at a time when a large file is streamed
+const STREAMED_IMPORT_BATCH_SIZE: usize = 5000;
+
+/// Imports a large file from a stream, merging each batch of connections
+/// as it is parsed
+///
+/// The connections are saved after a batch once they have grown by half
+/// since the last save, so the whole file is rewritten only a logarithmic
+/// number of times. Progress is shown on stderr and Ctrl+C stops the
+/// import after the current batch; the batches merged until then are
+/// saved.
+fn stream_import<S: ImportStream>(
+    stream: S,
+    config_manager: &ConfigManager,
+    mut existing_connections: Vec<Connection>,
+    mut existing_groups: Vec<ConnectionGroup>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let batches = BatchImporter::new(STREAMED_IMPORT_BATCH_SIZE);
+    let cancel = batches.cancel_handle();
+    cancel_on_interrupt(batches.cancel_handle());
+
+    let progress = CallbackProgressReporter::new(|current, total, message| {
+        let percent = current
+            .saturating_mul(100)
+            .checked_div(total)
+            .unwrap_or(100);
+        eprint!("\r{message} ({percent}%)");
+    });
+
+    // Only the first save rotates backups, so they keep the configuration
+    // from before the import
+    let batch_saver = config_manager.clone().with_backup_count(0);
+    let initial_count = existing_connections.len();
+    let initial_group_count = existing_groups.len();
+    let mut seen: HashSet<(String, String)> = existing_connections
+        .iter()
+        .map(|c| (c.name.clone(), c.host.clone()))
+        .collect();
+    let mut imported = 0;
+    let mut saved_len = None;
+    let mut save_error = None;
+
+    let streamed = batches.process_stream(stream, Some(&progress), |batch| {
+        imported += batch.len();
+        // Avoid duplicates by name and host
+        existing_connections.extend(
+            batch
+                .into_iter()
+                .filter(|c| seen.insert((c.name.clone(), c.host.clone()))),
+        );
+        let len = existing_connections.len();
+        if dry_run || saved_len.is_some_and(|saved| len < saved + saved / 2) {
+            return;
+        }
+        let saver = if saved_len.is_some() {
+            &batch_saver
+        } else {
+            config_manager
+        };
+        match saver.save_connections(&existing_connections) {
+            Ok(()) => saved_len = Some(len),
+            Err(e) => {
+                save_error = Some(CliError::Config(format!("Failed to save connections: {e}")));
+                cancel.cancel();
+            }
+        }
+    });
+    eprintln!();
+
+    if let Some(error) = save_error {
+        return Err(error);
+    }
+
+    let mut result = streamed.result;
+    result.apply_group_memberships(&mut existing_connections);
+    print_import_summary(&result, imported);
+
+    merge_imported_groups(&mut existing_groups, std::mem::take(&mut result.groups));
+    let new_connections = existing_connections.len() - initial_count;
+    let new_groups = existing_groups.len() - initial_group_count;
+
+    if !dry_run {
+        let saver = if saved_len.is_some() {
+            &batch_saver
+        } else {
+            config_manager
+        };
+        save_import(saver, &existing_connections, &existing_groups)?;
+    }
+
+    print_merge_results(
+        new_connections,
+        new_groups,
+        &existing_connections,
+        &existing_groups,
+        dry_run,
+    );
+
+    if streamed.was_cancelled {
+        return Err(CliError::Import(format!(
+            "Import cancelled after {imported} connections"
+        )));
+    }
+    Ok(())
+}
+
+/// Cancels an import when the user presses Ctrl+C
+fn cancel_on_interrupt(cancel: BatchCancelHandle) {
+    std::thread::spawn(move || {
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        else {
+            return;
+        };
+        if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+            eprintln!("\nCancelling import after the current batch...");
+            cancel.cancel();
+        }
+    });
+}
+
+/// Prints the counts and issues of an import
+fn print_import_summary(result: &rustconn_core::import::ImportResult, imported: usize) {
+    println!("Import Summary:");
+    println!("  Connections imported: {imported}");
+    println!("  Groups imported: {}", result.groups.len());
+    println!("  Duplicates merged: {}", result.merged_duplicates);
+    println!("  Entries skipped: {}", result.skipped.len());
+    println!("  Errors: {}", result.errors.len());
 
     // Display skipped entries if any
-    if !import_result.skipped.is_empty() {
+    if !result.skipped.is_empty() {
         eprintln!("\nSkipped entries:");
-        for skipped in &import_result.skipped {
+        for skipped in &result.skipped {
             if let Some(ref location) = skipped.location {
                 eprintln!(
                     "  - {} ({}): {}",
@@ -1524,55 +2502,174 @@ fn cmd_import(format: ImportFormatArg, file: &std::path::Path) -> Result<(), Cli
         }
     }
 
-    // Display errors if any
-    if !import_result.errors.is_empty() {
-        eprintln!("\nErrors:");
-        for error in &import_result.errors {
-            eprintln!("  - {error}");
-        }
+    // Display errors if any
+    if !result.errors.is_empty() {
+        eprintln!("\nErrors:");
+        for error in &result.errors {
+            eprintln!("  - {error}");
+        }
+    }
+}
+
+/// Adds imported groups, avoiding duplicates by name
+fn merge_imported_groups(existing: &mut Vec<ConnectionGroup>, groups: Vec<ConnectionGroup>) {
+    for group in groups {
+        if !existing.iter().any(|g| g.name == group.name) {
+            existing.push(group);
+        }
+    }
+}
+
+/// Adds imported connections, avoiding duplicates by name and host
+fn merge_imported_connections(existing: &mut Vec<Connection>, connections: Vec<Connection>) {
+    for conn in connections {
+        let is_duplicate = existing
+            .iter()
+            .any(|c| c.name == conn.name && c.host == conn.host);
+
+        if !is_duplicate {
+            existing.push(conn);
+        }
+    }
+}
+
+/// Saves the merged connections and groups of an import
+fn save_import(
+    config_manager: &ConfigManager,
+    connections: &[Connection],
+    groups: &[ConnectionGroup],
+) -> Result<(), CliError> {
+    config_manager
+        .save_connections(connections)
+        .map_err(|e| CliError::Config(format!("Failed to save connections: {e}")))?;
+
+    config_manager
+        .save_groups(groups)
+        .map_err(|e| CliError::Config(format!("Failed to save groups: {e}")))
+}
+
+/// Prints the outcome of merging an import into the configuration
+fn print_merge_results(
+    new_connections: usize,
+    new_groups: usize,
+    connections: &[Connection],
+    groups: &[ConnectionGroup],
+    dry_run: bool,
+) {
+    println!("\nMerge results:");
+    println!("  New connections added: {new_connections}");
+    println!("  New groups added: {new_groups}");
+    println!("  Total connections: {}", connections.len());
+    println!("  Total groups: {}", groups.len());
+
+    if dry_run {
+        eprintln!("\nDry run: no changes were saved");
+    }
+}
+
+/// Merges a native `.rcn` bundle into the existing configuration by ID
+fn merge_native_import(
+    config_manager: &ConfigManager,
+    native: rustconn_core::export::NativeExport,
+    existing_connections: &mut Vec<Connection>,
+    existing_groups: &mut Vec<ConnectionGroup>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let report = native.merge_into(existing_connections, existing_groups);
+
+    println!("Merge results:");
+    println!(
+        "  Connections: {} added, {} updated, {} skipped",
+        report.connections.added, report.connections.updated, report.connections.skipped
+    );
+    println!(
+        "  Groups: {} added, {} updated, {} skipped",
+        report.groups.added, report.groups.updated, report.groups.skipped
+    );
+
+    if !report.conflicts.is_empty() {
+        eprintln!("\nRenamed to avoid duplicate names:");
+        for conflict in &report.conflicts {
+            eprintln!(
+                "  - '{}' -> '{}'",
+                conflict.original_name, conflict.renamed_to
+            );
+        }
+    }
+
+    if report.is_unchanged() {
+        return Ok(());
     }
 
-    // Merge imported connections with existing
-    let initial_count = existing_connections.len();
-    let initial_group_count = existing_groups.len();
+    if !dry_run {
+        config_manager
+            .save_connections(existing_connections)
+            .map_err(|e| CliError::Config(format!("Failed to save connections: {e}")))?;
 
-    // Add imported groups (avoiding duplicates by name)
-    for group in import_result.groups {
-        if !existing_groups.iter().any(|g| g.name == group.name) {
-            existing_groups.push(group);
-        }
+        config_manager
+            .save_groups(existing_groups)
+            .map_err(|e| CliError::Config(format!("Failed to save groups: {e}")))?;
     }
 
-    // Add imported connections (avoiding duplicates by name and host)
-    for conn in import_result.connections {
-        let is_duplicate = existing_connections
-            .iter()
-            .any(|c| c.name == conn.name && c.host == conn.host);
+    println!("  Total connections: {}", existing_connections.len());
+    println!("  Total groups: {}", existing_groups.len());
 
-        if !is_duplicate {
-            existing_connections.push(conn);
-        }
+    if dry_run {
+        eprintln!("\nDry run: no changes were saved");
     }
 
-    let new_connections = existing_connections.len() - initial_count;
-    let new_groups = existing_groups.len() - initial_group_count;
+    Ok(())
+}
 
-    // Save merged connections
-    config_manager
-        .save_connections(&existing_connections)
-        .map_err(|e| CliError::Config(format!("Failed to save connections: {e}")))?;
+/// Reads a single-file import format from stdin
+fn read_import_stdin(format: ImportFormatArg) -> Result<String, CliError> {
+    use std::io::Read as _;
 
-    config_manager
-        .save_groups(&existing_groups)
-        .map_err(|e| CliError::Config(format!("Failed to save groups: {e}")))?;
+    if matches!(format, ImportFormatArg::Remmina) {
+        return Err(CliError::Import(
+            "Remmina imports a directory of .remmina files and cannot read from stdin; \
+             pass the directory path instead"
+                .to_string(),
+        ));
+    }
 
-    println!("\nMerge results:");
-    println!("  New connections added: {new_connections}");
-    println!("  New groups added: {new_groups}");
-    println!("  Total connections: {}", existing_connections.len());
-    println!("  Total groups: {}", existing_groups.len());
+    let mut bytes = Vec::new();
+    std::io::stdin()
+        .lock()
+        .read_to_end(&mut bytes)
+        .map_err(|e| CliError::Import(format!("Failed to read stdin: {e}")))?;
+    if matches!(format, ImportFormatArg::Putty) {
+        return Ok(rustconn_core::import::PuttyImporter::decode_reg(&bytes));
+    }
+    // MobaXterm files may be Windows-1252; keep what decodes
+    Ok(String::from_utf8(bytes)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+}
 
-    Ok(())
+/// Parses connections read from stdin using the importer for the format
+fn import_content(
+    format: ImportFormatArg,
+    content: &str,
+) -> Result<rustconn_core::import::ImportResult, CliError> {
+    use rustconn_core::import::{
+        AnsibleInventoryImporter, AsbruImporter, MobaXtermImporter, PuttyImporter, RoyalTsImporter,
+        SshConfigImporter,
+    };
+
+    const SOURCE: &str = "<stdin>";
+    Ok(match format {
+        ImportFormatArg::Ansible => {
+            AnsibleInventoryImporter::new().parse_inventory(content, SOURCE)
+        }
+        ImportFormatArg::SshConfig => SshConfigImporter::new().parse_config(content, SOURCE),
+        ImportFormatArg::Asbru => AsbruImporter::new().parse_config(content, SOURCE),
+        ImportFormatArg::RoyalTs => RoyalTsImporter::new().parse_xml(content, SOURCE),
+        ImportFormatArg::MobaXterm => MobaXtermImporter::new().parse_content(content, SOURCE),
+        ImportFormatArg::Putty => PuttyImporter::new().parse_reg(content, SOURCE),
+        ImportFormatArg::Remmina | ImportFormatArg::Native => {
+            unreachable!("Remmina is rejected and native bundles are merged before parsing")
+        }
+    })
 }
 
 /// Imports connections using the appropriate importer based on format
@@ -1582,7 +2679,7 @@ fn import_connections(
 ) -> Result<rustconn_core::import::ImportResult, CliError> {
     use rustconn_core::import::{
         AnsibleInventoryImporter, AsbruImporter, ImportResult, ImportSource, MobaXtermImporter,
-        RemminaImporter, RoyalTsImporter, SshConfigImporter,
+        PuttyImporter, RemminaImporter, RoyalTsImporter, SshConfigImporter,
     };
 
     let result = match format {
@@ -1618,8 +2715,7 @@ fn import_connections(
             ImportResult {
                 connections: native.connections,
                 groups: native.groups,
-                skipped: Vec::new(),
-                errors: Vec::new(),
+                ..ImportResult::default()
             }
         }
         ImportFormatArg::RoyalTs => {
@@ -1634,13 +2730,22 @@ fn import_connections(
                 .import_from_path(file)
                 .map_err(|e| CliError::Import(e.to_string()))?
         }
+        ImportFormatArg::Putty => {
+            let importer = PuttyImporter::with_path(file.to_path_buf());
+            importer
+                .import_from_path(file)
+                .map_err(|e| CliError::Import(e.to_string()))?
+        }
     };
 
     Ok(result)
 }
 
 /// Test connection command handler
-fn cmd_test(name: &str, timeout: u64) -> Result<(), CliError> {
+///
+/// Testing "all" skips archived connections unless `include_archived` is
+/// set; an archived connection named explicitly is always tested.
+fn cmd_test(name: &str, timeout: u64, include_archived: bool) -> Result<(), CliError> {
     // Load connections
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
@@ -1670,9 +2775,17 @@ fn cmd_test(name: &str, timeout: u64) -> Result<(), CliError> {
     // Determine which connections to test
     if name.eq_ignore_ascii_case("all") {
         // Test all connections
-        println!("Testing {} connections...\n", connections.len());
+        let targets: Vec<Connection> = connections
+            .into_iter()
+            .filter(|c| include_archived || !c.archived)
+            .collect();
+        if targets.is_empty() {
+            println!("No active connections to test.");
+            return Ok(());
+        }
+        println!("Testing {} connections...\n", targets.len());
 
-        let summary = runtime.block_on(tester.test_batch(&connections));
+        let summary = runtime.block_on(tester.test_batch(&targets));
 
         // Display individual results
         for result in &summary.results {
@@ -1782,6 +2895,10 @@ fn print_test_result(result: &rustconn_core::testing::TestResult) {
             print!(" [{protocol}]");
         }
 
+        if let Some(version) = result.details.get("server_version") {
+            print!(" {version}");
+        }
+
         println!();
     } else {
         // Failure: red X
@@ -1835,6 +2952,14 @@ fn print_test_summary(summary: &rustconn_core::testing::TestSummary) {
     } else {
         println!("  {RED}Pass rate: {pass_rate:.1}%{RESET}");
     }
+
+    if let (Some(p50), Some(p95), Some(jitter)) = (
+        summary.latency_percentile(50.0),
+        summary.latency_percentile(95.0),
+        summary.jitter_ms,
+    ) {
+        println!("  Latency: p50 {p50}ms, p95 {p95}ms, jitter {jitter:.1}ms");
+    }
 }
 
 /// Delete connection command handler
@@ -1866,6 +2991,43 @@ fn cmd_delete(name: &str) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Archive or unarchive command handler
+fn cmd_archive(name: &str, archived: bool) -> Result<(), CliError> {
+    let config_manager = ConfigManager::new()
+        .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
+
+    let mut connections = config_manager
+        .load_connections()
+        .map_err(|e| CliError::Config(format!("Failed to load connections: {e}")))?;
+
+    let id = find_connection(&connections, name)?.id;
+    let connection = connections
+        .iter_mut()
+        .find(|c| c.id == id)
+        .ok_or_else(|| CliError::ConnectionNotFound(name.to_string()))?;
+
+    let action = if archived { "Archived" } else { "Unarchived" };
+    if connection.archived == archived {
+        println!(
+            "Connection '{}' is already {}",
+            connection.name,
+            action.to_lowercase()
+        );
+        return Ok(());
+    }
+    connection.archived = archived;
+    connection.touch();
+    let conn_name = connection.name.clone();
+
+    config_manager
+        .save_connections(&connections)
+        .map_err(|e| CliError::Config(format!("Failed to save connections: {e}")))?;
+
+    println!("{action} connection '{conn_name}' (ID: {id})");
+
+    Ok(())
+}
+
 /// Show connection details command handler
 fn cmd_show(name: &str) -> Result<(), CliError> {
     // Load connections
@@ -2071,10 +3233,20 @@ impl CliError {
 // ============================================================================
 
 /// Wake-on-LAN command handler
-fn cmd_wol(target: &str, broadcast: &str, port: u16) -> Result<(), CliError> {
+fn cmd_wol(
+    target: &str,
+    broadcast: &str,
+    port: u16,
+    secure_on: Option<&str>,
+) -> Result<(), CliError> {
+    let secure_on = secure_on
+        .map(str::parse::<SecureOnPassword>)
+        .transpose()
+        .map_err(|e| CliError::Wol(e.to_string()))?;
+
     // Try to parse target as MAC address first
-    let mac = if let Ok(mac) = target.parse::<MacAddress>() {
-        mac
+    let (mac, configured_secure_on) = if let Ok(mac) = target.parse::<MacAddress>() {
+        (mac, None)
     } else {
         // Try to find connection by name and get its WOL config
         let config_manager = ConfigManager::new()
@@ -2089,7 +3261,7 @@ fn cmd_wol(target: &str, broadcast: &str, port: u16) -> Result<(), CliError> {
         connection
             .wol_config
             .as_ref()
-            .map(|wol| wol.mac_address)
+            .map(|wol| (wol.mac_address, wol.secure_on_password))
             .ok_or_else(|| {
                 CliError::Wol(format!(
                     "Connection '{}' does not have Wake-on-LAN configured",
@@ -2098,13 +3270,17 @@ fn cmd_wol(target: &str, broadcast: &str, port: u16) -> Result<(), CliError> {
             })?
     };
 
-    let config = WolConfig::new(mac)
+    let mut config = WolConfig::new(mac)
         .with_broadcast_address(broadcast)
         .with_port(port);
+    config.secure_on_password = secure_on.or(configured_secure_on);
 
     println!("Sending Wake-on-LAN magic packet...");
     println!("  MAC Address: {mac}");
     println!("  Broadcast:   {broadcast}:{port}");
+    if config.secure_on_password.is_some() {
+        println!("  SecureOn:    yes");
+    }
 
     rustconn_core::wol::send_wol(&config).map_err(|e| CliError::Wol(e.to_string()))?;
 
@@ -2433,7 +3609,8 @@ fn cmd_group(subcmd: GroupCommands) -> Result<(), CliError> {
             parent,
             description,
         } => cmd_group_create(&name, parent.as_deref(), description.as_deref()),
-        GroupCommands::Delete { name } => cmd_group_delete(&name),
+        GroupCommands::Rename { name, new_name } => cmd_group_rename(&name, &new_name),
+        GroupCommands::Delete { name, force } => cmd_group_delete(&name, force),
         GroupCommands::AddConnection { group, connection } => {
             cmd_group_add_connection(&group, &connection)
         }
@@ -2545,7 +3722,7 @@ fn cmd_group_show(name: &str) -> Result<(), CliError> {
     // Find connections in this group
     let group_connections: Vec<_> = connections
         .iter()
-        .filter(|c| c.group_id == Some(group.id))
+        .filter(|c| c.is_in_group(group.id))
         .collect();
 
     println!("\nConnections ({}):", group_connections.len());
@@ -2595,8 +3772,49 @@ fn cmd_group_create(
     Ok(())
 }
 
+/// Rename a group
+fn cmd_group_rename(name: &str, new_name: &str) -> Result<(), CliError> {
+    let config_manager = ConfigManager::new()
+        .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
+
+    let mut groups = config_manager
+        .load_groups()
+        .map_err(|e| CliError::Group(format!("Failed to load groups: {e}")))?;
+
+    let id = find_group(&groups, name)?.id;
+
+    if new_name.trim().is_empty() {
+        return Err(CliError::Group("Group name cannot be empty".to_string()));
+    }
+    if groups
+        .iter()
+        .any(|g| g.id != id && g.name.eq_ignore_ascii_case(new_name))
+    {
+        return Err(CliError::Group(format!(
+            "Group with name '{new_name}' already exists"
+        )));
+    }
+
+    let group = groups
+        .iter_mut()
+        .find(|g| g.id == id)
+        .ok_or_else(|| CliError::Group(format!("Group not found: {name}")))?;
+    let old_name = std::mem::replace(&mut group.name, new_name.to_string());
+
+    config_manager
+        .save_groups(&groups)
+        .map_err(|e| CliError::Group(format!("Failed to save groups: {e}")))?;
+
+    println!("Renamed group '{old_name}' to '{new_name}'");
+
+    Ok(())
+}
+
 /// Delete a group
-fn cmd_group_delete(name: &str) -> Result<(), CliError> {
+///
+/// A group that still holds connections or subgroups is only deleted with
+/// `force`; its contents then move to the deleted group's parent.
+fn cmd_group_delete(name: &str, force: bool) -> Result<(), CliError> {
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
 
@@ -2604,17 +3822,54 @@ fn cmd_group_delete(name: &str) -> Result<(), CliError> {
         .load_groups()
         .map_err(|e| CliError::Group(format!("Failed to load groups: {e}")))?;
 
+    let mut connections = config_manager
+        .load_connections()
+        .map_err(|e| CliError::Config(format!("Failed to load connections: {e}")))?;
+
     let group = find_group(&groups, name)?;
     let id = group.id;
+    let parent_id = group.parent_id;
     let group_name = group.name.clone();
 
+    let connection_count = connections.iter().filter(|c| c.is_in_group(id)).count();
+    let subgroup_count = groups.iter().filter(|g| g.parent_id == Some(id)).count();
+    if connection_count + subgroup_count > 0 && !force {
+        return Err(CliError::Group(format!(
+            "Group '{group_name}' contains {connection_count} connection(s) and \
+             {subgroup_count} subgroup(s); use --force to move them to the parent group"
+        )));
+    }
+
     groups.retain(|g| g.id != id);
+    for child in groups.iter_mut().filter(|g| g.parent_id == Some(id)) {
+        child.parent_id = parent_id;
+    }
+    for connection in &mut connections {
+        if connection.remove_from_group(id) {
+            if let Some(parent_id) = parent_id {
+                connection.add_to_group(parent_id);
+            }
+        }
+    }
 
+    if connection_count > 0 {
+        config_manager
+            .save_connections(&connections)
+            .map_err(|e| CliError::Config(format!("Failed to save connections: {e}")))?;
+    }
     config_manager
         .save_groups(&groups)
         .map_err(|e| CliError::Group(format!("Failed to save groups: {e}")))?;
 
     println!("Deleted group '{group_name}' (ID: {id})");
+    if connection_count + subgroup_count > 0 {
+        let destination = parent_id
+            .and_then(|pid| groups.iter().find(|g| g.id == pid))
+            .map_or_else(|| "the top level".to_string(), |g| format!("'{}'", g.name));
+        println!(
+            "Moved {connection_count} connection(s) and {subgroup_count} subgroup(s) to {destination}"
+        );
+    }
 
     Ok(())
 }
@@ -2992,19 +4247,14 @@ fn cmd_template_apply(
 
     let template = find_template(&templates, template_name)?;
 
-    // Apply template to create connection
-    let mut connection = template.apply(conn_name.map(String::from));
-
-    // Override with provided values
-    if let Some(h) = host {
-        connection.host = h.to_string();
-    }
-    if let Some(p) = port {
-        connection.port = p;
-    }
-    if let Some(u) = user {
-        connection.username = Some(u.to_string());
-    }
+    // Apply template and overrides to create connection
+    let overrides = TemplateOverrides {
+        port,
+        username: user.map(String::from),
+    };
+    let connection = template
+        .instantiate(conn_name.unwrap_or(&template.name), host, &overrides)
+        .map_err(|e| CliError::Template(e.to_string()))?;
 
     // Load and save connections
     let mut connections = config_manager
@@ -3382,7 +4632,8 @@ fn cmd_var(subcmd: VariableCommands) -> Result<(), CliError> {
             value,
             secret,
             description,
-        } => cmd_var_set(&name, &value, secret, description.as_deref()),
+            value_type,
+        } => cmd_var_set(&name, &value, secret, description.as_deref(), value_type),
         VariableCommands::Delete { name } => cmd_var_delete(&name),
     }
 }
@@ -3488,6 +4739,7 @@ fn cmd_var_show(name: &str) -> Result<(), CliError> {
     println!("  Name:   {}", var.name);
     println!("  Value:  {}", var.display_value());
     println!("  Secret: {}", if var.is_secret { "Yes" } else { "No" });
+    println!("  Type:   {}", var.value_type);
 
     if let Some(ref desc) = var.description {
         println!("  Description: {desc}");
@@ -3502,6 +4754,7 @@ fn cmd_var_set(
     value: &str,
     secret: bool,
     description: Option<&str>,
+    value_type: Option<VariableType>,
 ) -> Result<(), CliError> {
     let config_manager = ConfigManager::new()
         .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
@@ -3525,6 +4778,13 @@ fn cmd_var_set(
         var
     };
 
+    let value_type = value_type
+        .or_else(|| existing_idx.map(|idx| variables[idx].value_type.clone()))
+        .unwrap_or_default();
+    let mut var = var.with_type(value_type);
+    var.validate()
+        .map_err(|e| CliError::Variable(e.to_string()))?;
+
     let action = if let Some(idx) = existing_idx {
         variables[idx] = var;
         "Updated"
@@ -3567,6 +4827,108 @@ fn cmd_var_delete(name: &str) -> Result<(), CliError> {
     Ok(())
 }
 
+// ============================================================================
+// Automation commands
+// ============================================================================
+
+/// Automation command handler
+fn cmd_automation(subcmd: AutomationCommands) -> Result<(), CliError> {
+    match subcmd {
+        AutomationCommands::Export { name, output } => cmd_automation_export(&name, &output),
+        AutomationCommands::Import { file, name } => cmd_automation_import(&file, &name),
+    }
+}
+
+/// Export a connection's automation to a pack file
+fn cmd_automation_export(name: &str, output: &Path) -> Result<(), CliError> {
+    let config_manager = ConfigManager::new()
+        .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
+
+    let connections = config_manager
+        .load_connections()
+        .map_err(|e| CliError::Config(format!("Failed to load connections: {e}")))?;
+
+    let global_variables = config_manager
+        .load_variables()
+        .map_err(|e| CliError::Config(format!("Failed to load variables: {e}")))?;
+
+    let connection = find_connection(&connections, name)?;
+    let pack = rustconn_core::AutomationPack::from_connection(connection, &global_variables);
+
+    if pack.is_empty() {
+        return Err(CliError::Export(format!(
+            "Connection '{}' has no automation configured",
+            connection.name
+        )));
+    }
+
+    pack.to_file(output)
+        .map_err(|e| CliError::Export(e.to_string()))?;
+
+    println!(
+        "Exported automation of '{}' ({} expect rule(s)) to {}",
+        connection.name,
+        pack.expect_rules.len(),
+        output.display()
+    );
+    if !pack.secret_references.is_empty() {
+        println!(
+            "Secrets referenced (not exported): {}",
+            pack.secret_references.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Apply an automation pack to a connection
+fn cmd_automation_import(file: &Path, name: &str) -> Result<(), CliError> {
+    let pack = rustconn_core::AutomationPack::from_file(file)
+        .map_err(|e| CliError::Import(e.to_string()))?;
+
+    let config_manager = ConfigManager::new()
+        .map_err(|e| CliError::Config(format!("Failed to initialize config: {e}")))?;
+
+    let mut connections = config_manager
+        .load_connections()
+        .map_err(|e| CliError::Config(format!("Failed to load connections: {e}")))?;
+
+    let global_variables = config_manager
+        .load_variables()
+        .map_err(|e| CliError::Config(format!("Failed to load variables: {e}")))?;
+
+    let id = find_connection(&connections, name)?.id;
+    let connection = connections
+        .iter_mut()
+        .find(|c| c.id == id)
+        .ok_or_else(|| CliError::ConnectionNotFound(name.to_string()))?;
+
+    pack.apply_to(connection);
+    let connection_name = connection.name.clone();
+    let missing: Vec<String> = pack
+        .missing_secrets(connection, &global_variables)
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    config_manager
+        .save_connections(&connections)
+        .map_err(|e| CliError::Config(format!("Failed to save connections: {e}")))?;
+
+    println!(
+        "Applied automation pack to '{connection_name}' ({} expect rule(s))",
+        pack.expect_rules.len()
+    );
+    if !missing.is_empty() {
+        println!(
+            "Define these secret variables before connecting: {}",
+            missing.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Duplicate and Stats commands
 // ============================================================================
@@ -3591,6 +4953,7 @@ fn cmd_duplicate(name: &str, new_name: Option<&str>) -> Result<(), CliError> {
     duplicate.created_at = chrono::Utc::now();
     duplicate.updated_at = chrono::Utc::now();
     duplicate.last_connected = None;
+    duplicate.connect_count = 0;
 
     let id = duplicate.id;
     let dup_name = duplicate.name.clone();
@@ -3673,3 +5036,185 @@ fn cmd_stats() -> Result<(), CliError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_ssh_command_ipv6_host() {
+        let mut connection = Connection::new_ssh("v6".to_string(), "[fe80::1]".to_string(), 2222);
+        connection.username = Some("admin".to_string());
+        let command = build_ssh_command(&connection, &[], None);
+
+        assert_eq!(command.program, "ssh");
+        assert!(command.args.contains(&"admin@fe80::1".to_string()));
+    }
+
+    #[test]
+    fn test_build_ssh_command_environment() {
+        let mut connection = Connection::new_ssh("env".to_string(), "host".to_string(), 22);
+        if let rustconn_core::models::ProtocolConfig::Ssh(ssh) = &mut connection.protocol_config {
+            ssh.environment = vec![("LANG".to_string(), "C.UTF-8".to_string())];
+        }
+        let command = build_ssh_command(&connection, &[], None);
+
+        assert!(command.args.windows(2).any(|w| w == ["-o", "SendEnv=LANG"]));
+        assert!(!command.args.iter().any(|arg| arg.contains("C.UTF-8")));
+        assert_eq!(
+            command.env,
+            vec![("LANG".to_string(), "C.UTF-8".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unavailable_stored_agent_key_is_not_fatal() {
+        let mut connection = Connection::new_ssh("agent".to_string(), "host".to_string(), 22);
+        if let rustconn_core::models::ProtocolConfig::Ssh(ssh) = &mut connection.protocol_config {
+            ssh.agent_key_fingerprint = Some("SHA256:not-loaded-anywhere".to_string());
+        }
+
+        assert!(matches!(
+            resolve_agent_identity(&connection, None),
+            Ok(None)
+        ));
+        assert!(matches!(
+            resolve_agent_identity(&connection, Some("SHA256:not-loaded-anywhere")),
+            Err(CliError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_ssh_command_jump_hosts() {
+        let mut bastion = Connection::new_ssh(
+            "bastion".to_string(),
+            "bastion.example.com".to_string(),
+            2222,
+        );
+        bastion.username = Some("ops".to_string());
+        let inner = Connection::new_ssh("inner".to_string(), "10.0.0.2".to_string(), 22);
+        let mut connection = Connection::new_ssh("db".to_string(), "10.0.0.3".to_string(), 22);
+        if let rustconn_core::models::ProtocolConfig::Ssh(ssh) = &mut connection.protocol_config {
+            ssh.proxy_jump = Some("ignored.example.com".to_string());
+        }
+
+        let command = build_ssh_command(&connection, &[&bastion, &inner], None);
+        assert!(command
+            .args
+            .windows(2)
+            .any(|w| w == ["-J", "ops@bastion.example.com:2222,10.0.0.2"]));
+        assert!(!command.args.iter().any(|a| a.contains("ignored")));
+    }
+
+    #[test]
+    fn test_build_ssh_command_algorithms() {
+        let mut connection = Connection::new_ssh("switch".to_string(), "10.0.0.2".to_string(), 22);
+        if let rustconn_core::models::ProtocolConfig::Ssh(ssh) = &mut connection.protocol_config {
+            ssh.algorithms.ciphers =
+                rustconn_core::models::SshAlgorithmList::parse("+aes128-cbc,3des-cbc");
+            ssh.algorithms.macs = rustconn_core::models::SshAlgorithmList::parse("-hmac-md5");
+        }
+
+        let command = build_ssh_command(&connection, &[], None);
+        assert!(command
+            .args
+            .windows(2)
+            .any(|w| w == ["-o", "Ciphers=+aes128-cbc,3des-cbc"]));
+        assert!(command
+            .args
+            .windows(2)
+            .any(|w| w == ["-o", "MACs=-hmac-md5"]));
+        assert!(!command.args.iter().any(|a| a.starts_with("KexAlgorithms")));
+    }
+
+    #[test]
+    fn test_build_rdp_command_ipv6_host() {
+        let connection = Connection::new_rdp("v6".to_string(), "fe80::1".to_string(), 3389);
+        let command = build_rdp_command(&connection);
+
+        assert!(command.args.contains(&"/v:[fe80::1]:3389".to_string()));
+    }
+
+    #[test]
+    fn test_build_vnc_command_ipv6_host() {
+        let connection = Connection::new_vnc("v6".to_string(), "fe80::1".to_string(), 5901);
+        let command = build_vnc_command(&connection);
+
+        assert_eq!(command.args.last().map(String::as_str), Some("[fe80::1]:1"));
+    }
+
+    #[test]
+    fn test_build_spice_command_ipv6_host() {
+        let connection = Connection::new_spice("v6".to_string(), "fe80::1".to_string(), 5900);
+        let command = build_spice_command(&connection);
+
+        assert!(command.args.contains(&"spice://[fe80::1]:5900".to_string()));
+    }
+
+    #[test]
+    fn test_build_commands_leave_ipv4_and_hostnames_untouched() {
+        let connection = Connection::new_rdp("v4".to_string(), "10.0.0.5".to_string(), 3389);
+        assert!(build_rdp_command(&connection)
+            .args
+            .contains(&"/v:10.0.0.5:3389".to_string()));
+
+        let connection = Connection::new_ssh("dns".to_string(), "web-01".to_string(), 22);
+        assert!(build_ssh_command(&connection, &[], None)
+            .args
+            .contains(&"web-01".to_string()));
+    }
+
+    fn picker_connections() -> Vec<Connection> {
+        vec![
+            Connection::new_ssh("web-01".to_string(), "10.0.0.1".to_string(), 22),
+            Connection::new_ssh("web-02".to_string(), "10.0.0.2".to_string(), 22),
+            Connection::new_rdp("db".to_string(), "10.0.0.3".to_string(), 3389),
+        ]
+    }
+
+    #[test]
+    fn test_pick_connection_reprompts_until_valid() {
+        let connections = picker_connections();
+        let candidates: Vec<&Connection> = connections.iter().collect();
+        let mut input = std::io::Cursor::new("abc\n9\n2\n");
+
+        let picked = pick_connection(&candidates, &mut input).unwrap();
+        assert_eq!(picked.name, "web-02");
+    }
+
+    #[test]
+    fn test_pick_connection_single_candidate_skips_prompt() {
+        let connections = picker_connections();
+        let mut input = std::io::Cursor::new("");
+
+        let picked = pick_connection(&[&connections[2]], &mut input).unwrap();
+        assert_eq!(picked.name, "db");
+    }
+
+    #[test]
+    fn test_pick_connection_errors() {
+        let connections = picker_connections();
+        let candidates: Vec<&Connection> = connections.iter().collect();
+
+        // Empty line and end of input both cancel
+        for input in ["\n", ""] {
+            let result = pick_connection(&candidates, &mut std::io::Cursor::new(input));
+            assert!(matches!(result, Err(CliError::Config(_))));
+        }
+
+        let result = pick_connection(&[], &mut std::io::Cursor::new("1\n"));
+        assert!(matches!(result, Err(CliError::ConnectionNotFound(_))));
+    }
+
+    #[test]
+    fn test_rank_connections() {
+        let connections = picker_connections();
+
+        let ranked = rank_connections(&connections, &[], "web").unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked.iter().all(|c| c.name.starts_with("web")));
+
+        let result = rank_connections(&connections, &[], "nomatch");
+        assert!(matches!(result, Err(CliError::ConnectionNotFound(q)) if q == "nomatch"));
+    }
+}
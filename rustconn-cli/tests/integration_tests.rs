@@ -12,13 +12,38 @@ use tempfile::TempDir;
 fn run_cli(args: &[&str], config_dir: Option<&std::path::Path>) -> Output {
     let mut cmd = Command::new(env!("CARGO_BIN_EXE_rustconn-cli"));
 
+    // The configuration lives under $XDG_CONFIG_HOME/rustconn
     if let Some(dir) = config_dir {
-        cmd.env("RUSTCONN_CONFIG_DIR", dir);
+        cmd.env("RUSTCONN_CONFIG_DIR", dir)
+            .env("XDG_CONFIG_HOME", dir);
     }
 
     cmd.args(args).output().expect("Failed to execute CLI")
 }
 
+/// Helper to run the CLI with data piped to stdin
+fn run_cli_with_stdin(args: &[&str], config_dir: &std::path::Path, stdin: &[u8]) -> Output {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustconn-cli"))
+        .env("RUSTCONN_CONFIG_DIR", config_dir)
+        .env("XDG_CONFIG_HOME", config_dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute CLI");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin)
+        .expect("Failed to write stdin");
+    child.wait_with_output().expect("Failed to wait for CLI")
+}
+
 /// Helper to get stdout as string
 fn stdout_str(output: &Output) -> String {
     String::from_utf8_lossy(&output.stdout).to_string()
@@ -101,6 +126,36 @@ fn test_add_help() {
         stdout.contains("protocol"),
         "Add help should mention protocol option"
     );
+    assert!(
+        stdout.contains("--template"),
+        "Add help should mention template option"
+    );
+}
+
+#[test]
+fn test_connect_help() {
+    let output = run_cli(&["connect", "--help"], None);
+
+    assert!(output.status.success(), "Connect help should succeed");
+
+    let stdout = stdout_str(&output);
+    assert!(
+        stdout.contains("--agent-key"),
+        "Connect help should mention agent key option"
+    );
+}
+
+#[test]
+fn test_automation_help() {
+    let output = run_cli(&["automation", "--help"], None);
+
+    assert!(output.status.success(), "Automation help should succeed");
+
+    let stdout = stdout_str(&output);
+    assert!(
+        stdout.contains("export") && stdout.contains("import"),
+        "Automation help should mention export and import subcommands"
+    );
 }
 
 #[test]
@@ -190,6 +245,56 @@ fn test_list_csv_format() {
     }
 }
 
+#[test]
+fn test_list_fields_and_sort() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(
+        &[
+            "list",
+            "--format",
+            "csv",
+            "--fields",
+            "name,tags,last-connected",
+            "--sort",
+            "last-connected",
+        ],
+        Some(temp_dir.path()),
+    );
+
+    assert!(output.status.success(), "List with fields should succeed");
+    let stdout = stdout_str(&output);
+    assert!(
+        stdout.starts_with("name,tags,last_connected"),
+        "CSV header should list only the selected fields. Got: {stdout}"
+    );
+
+    let output = run_cli(&["list", "--fields", "name,colour"], Some(temp_dir.path()));
+    assert!(!output.status.success(), "Unknown field should be rejected");
+    assert!(stderr_str(&output).contains("colour"));
+}
+
+#[test]
+fn test_archive_commands() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(&["list", "--help"], None);
+    assert!(stdout_str(&output).contains("--archived"));
+    let output = run_cli(&["test", "--help"], None);
+    assert!(stdout_str(&output).contains("--include-archived"));
+
+    for command in ["archive", "unarchive"] {
+        let output = run_cli(
+            &[command, "nonexistent-connection-xyz"],
+            Some(temp_dir.path()),
+        );
+        assert!(
+            !output.status.success(),
+            "{command} of a missing connection should fail"
+        );
+    }
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
@@ -221,6 +326,59 @@ fn test_connect_nonexistent() {
     );
 }
 
+#[test]
+fn test_connect_requires_name_unless_interactive() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(&["connect"], Some(temp_dir.path()));
+    assert!(
+        !output.status.success(),
+        "Connect without a name should fail"
+    );
+    assert!(stderr_str(&output).contains("required"));
+
+    let output = run_cli_with_stdin(&["connect", "--interactive"], temp_dir.path(), b"\n");
+    assert!(
+        !output.status.success(),
+        "Interactive connect without a selection should fail"
+    );
+
+    // Archived connections are not offered by the picker
+    let output = run_cli(
+        &["add", "--name", "retired", "--host", "192.0.2.20"],
+        Some(temp_dir.path()),
+    );
+    assert!(output.status.success(), "Add should succeed");
+    let output = run_cli(&["archive", "retired"], Some(temp_dir.path()));
+    assert!(output.status.success(), "Archive should succeed");
+    let output = run_cli_with_stdin(&["connect", "--interactive"], temp_dir.path(), b"1\n");
+    assert!(
+        !output.status.success(),
+        "Interactive connect should not pick an archived connection"
+    );
+    assert!(!stdout_str(&output).contains("Connecting to"));
+}
+
+#[test]
+fn test_import_warns_before_reading_large_file_whole() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    // Native bundles cannot be streamed; 17 MiB is above the threshold
+    let bundle = temp_dir.path().join("large.rcn");
+    std::fs::write(&bundle, " ".repeat(17 * 1024 * 1024)).expect("Failed to write bundle");
+    let bundle_arg = bundle.to_str().expect("Temp path is UTF-8");
+
+    let output = run_cli(
+        &["import", "--format", "native", bundle_arg],
+        Some(temp_dir.path()),
+    );
+    assert!(
+        !output.status.success(),
+        "A blank bundle should fail to import"
+    );
+    assert!(stderr_str(&output).contains("read into memory whole"));
+}
+
 #[test]
 fn test_import_nonexistent_file() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -276,6 +434,103 @@ fn test_export_invalid_format() {
     );
 }
 
+// ============================================================================
+// Stdin/Stdout Piping Tests
+// ============================================================================
+
+#[test]
+fn test_export_to_stdout() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(
+        &["export", "--format", "ssh-config", "--output", "-"],
+        Some(temp_dir.path()),
+    );
+
+    assert!(output.status.success(), "Export to stdout should succeed");
+    let stdout = stdout_str(&output);
+    assert!(
+        stdout.starts_with("# SSH config exported from RustConn"),
+        "Stdout should carry only the exported config. Got: {stdout}"
+    );
+    assert!(!stdout.contains("Export complete"));
+    assert!(stderr_str(&output).contains("Export complete"));
+}
+
+#[test]
+fn test_import_reads_stdin() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli_with_stdin(
+        &["import", "--format", "native", "-"],
+        temp_dir.path(),
+        b"not a native bundle",
+    );
+
+    // The content is parsed instead of being treated as a missing file
+    assert!(!output.status.success());
+    let stderr = stderr_str(&output);
+    assert!(
+        stderr.contains("Import error") && !stderr.contains("File not found"),
+        "Should report a parse error. Got: {stderr}"
+    );
+}
+
+#[test]
+fn test_dry_run_import_and_export() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let target = temp_dir.path().join("inventory.ini");
+    let target_arg = target.to_str().expect("Temp path is UTF-8");
+    let output = run_cli(
+        &[
+            "export",
+            "--format",
+            "ansible",
+            "--output",
+            target_arg,
+            "--dry-run",
+        ],
+        Some(temp_dir.path()),
+    );
+    assert!(output.status.success(), "Dry-run export should succeed");
+    assert!(stdout_str(&output).contains(target_arg));
+    assert!(stderr_str(&output).contains("Dry run"));
+    assert!(!target.exists(), "Dry-run export must not write files");
+
+    let output = run_cli_with_stdin(
+        &["--dry-run", "import", "--format", "ssh-config", "-"],
+        temp_dir.path(),
+        b"Host dry-run-only-host\n    HostName 192.0.2.10\n",
+    );
+    assert!(output.status.success(), "Dry-run import should succeed");
+    assert!(stdout_str(&output).contains("New connections added"));
+    assert!(stderr_str(&output).contains("Dry run"));
+
+    let output = run_cli(&["list", "--format", "json"], Some(temp_dir.path()));
+    assert!(!stdout_str(&output).contains("dry-run-only-host"));
+}
+
+#[test]
+fn test_stdio_rejected_for_directory_formats() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(
+        &["export", "--format", "remmina", "--output", "-"],
+        Some(temp_dir.path()),
+    );
+    assert!(!output.status.success());
+    assert!(stderr_str(&output).contains("cannot be written to stdout"));
+
+    let output = run_cli_with_stdin(
+        &["import", "--format", "remmina", "-"],
+        temp_dir.path(),
+        b"",
+    );
+    assert!(!output.status.success());
+    assert!(stderr_str(&output).contains("cannot read from stdin"));
+}
+
 // ============================================================================
 // Add Command Tests
 // ============================================================================
@@ -297,6 +552,36 @@ fn test_add_missing_required_args() {
     );
 }
 
+#[test]
+fn test_add_template_conflicts_with_protocol() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(
+        &[
+            "add",
+            "--name",
+            "test",
+            "--template",
+            "linux",
+            "--protocol",
+            "rdp",
+        ],
+        Some(temp_dir.path()),
+    );
+
+    assert!(
+        !output.status.success(),
+        "Add with both --template and --protocol should fail"
+    );
+
+    let stderr = stderr_str(&output);
+    assert!(
+        stderr.contains("cannot be used with"),
+        "Should report conflicting arguments. Got: {}",
+        stderr
+    );
+}
+
 #[test]
 fn test_add_invalid_protocol() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -327,6 +612,61 @@ fn test_add_invalid_protocol() {
     );
 }
 
+// ============================================================================
+// Group Command Tests
+// ============================================================================
+
+#[test]
+fn test_group_rename_and_delete() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = run_cli(&["group", "delete", "--help"], None);
+    assert!(output.status.success(), "Group delete help should succeed");
+    assert!(stdout_str(&output).contains("--force"));
+
+    let output = run_cli(&["group", "add", "--help"], None);
+    assert!(output.status.success(), "'add' should alias 'create'");
+
+    let output = run_cli(
+        &["group", "rename", "nonexistent-group-xyz", "Renamed"],
+        Some(temp_dir.path()),
+    );
+    assert!(
+        !output.status.success(),
+        "Renaming a missing group should fail"
+    );
+    assert!(stderr_str(&output).contains("not found"));
+}
+
+// ============================================================================
+// Variable Command Tests
+// ============================================================================
+
+#[test]
+fn test_var_set_validates_type() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = Some(temp_dir.path());
+
+    let output = run_cli(&["var", "set", "port", "abc", "--type", "int"], dir);
+    assert!(
+        !output.status.success(),
+        "Non-integer port should be rejected"
+    );
+    assert!(stderr_str(&output).contains("not an integer"));
+
+    let output = run_cli(&["var", "set", "port", "2222", "--type", "int"], dir);
+    assert!(output.status.success(), "Integer port should be accepted");
+
+    // Updating keeps the existing type
+    let output = run_cli(&["var", "set", "port", "ssh"], dir);
+    assert!(!output.status.success(), "Existing type should be kept");
+
+    let output = run_cli(&["var", "show", "port"], dir);
+    let stdout = stdout_str(&output);
+    assert!(stdout.contains("2222"));
+    assert!(stdout.contains("Type:   int"));
+}
+
 // ============================================================================
 // Test Command Tests
 // ============================================================================
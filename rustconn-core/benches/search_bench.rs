@@ -9,7 +9,8 @@
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use rustconn_core::models::{Connection, ConnectionGroup, ProtocolConfig, SshConfig};
-use rustconn_core::search::{SearchEngine, SearchQuery};
+use rustconn_core::search::{SearchEngine, SearchIndex, SearchQuery};
+use std::collections::HashMap;
 use std::hint::black_box;
 use uuid::Uuid;
 
@@ -173,12 +174,48 @@ fn bench_search_with_filters(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_search_indexed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search_indexed");
+    let connections = create_test_connections(10_000);
+    let groups = create_test_groups(100);
+    let by_id: HashMap<Uuid, Connection> = connections.iter().map(|c| (c.id, c.clone())).collect();
+    let index = SearchIndex::build(&connections);
+    let engine = SearchEngine::new();
+
+    for text in ["server-0042", "us-west", "prod"] {
+        let query = SearchQuery::with_text(text);
+
+        group.bench_with_input(BenchmarkId::new("linear", text), &query, |b, query| {
+            b.iter(|| {
+                engine.search(
+                    black_box(query),
+                    black_box(&connections),
+                    black_box(&groups),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("indexed", text), &query, |b, query| {
+            b.iter(|| {
+                engine.search_indexed(
+                    black_box(query),
+                    |id| by_id.get(&id),
+                    black_box(&groups),
+                    black_box(&index),
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_search_scaling,
     bench_fuzzy_score,
     bench_query_parsing,
     bench_search_with_filters,
+    bench_search_indexed,
 );
 
 criterion_main!(benches);
@@ -6,14 +6,19 @@
 //! - Automatic response sending when patterns match
 //! - Priority-based rule ordering
 //! - Timeout handling for patterns
+//! - Wait-for-prompt steps that block until a pattern appears
+//! - `$1`, `$2`, ... in responses, replaced by the pattern's capture groups
 
 use std::sync::Arc;
+use std::time::Duration;
 
-use regex::Regex;
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
+use crate::session::SecretWindow;
 use crate::variables::{VariableManager, VariableScope};
 
 /// Errors that can occur during expect operations
@@ -47,22 +52,74 @@ pub enum ExpectError {
     /// Duplicate rule ID
     #[error("Duplicate rule ID: {0}")]
     DuplicateRuleId(Uuid),
+
+    /// A wait-for-prompt rule's pattern did not appear in time
+    #[error("Timed out after {timeout_ms}ms waiting for '{pattern}' (rule {rule_id})")]
+    Timeout {
+        /// The rule that timed out
+        rule_id: Uuid,
+        /// The pattern that was awaited
+        pattern: String,
+        /// The timeout that elapsed
+        timeout_ms: u32,
+    },
+
+    /// The output stream ended while waiting for a prompt
+    #[error("Output closed while waiting for rule {0}")]
+    OutputClosed(Uuid),
+
+    /// The response references a capture group the pattern does not have
+    #[error("Response references ${group} but pattern '{pattern}' has {available} capture groups")]
+    InvalidBackreference {
+        /// The referenced group number
+        group: usize,
+        /// The rule's pattern
+        pattern: String,
+        /// Number of capture groups in the pattern
+        available: usize,
+    },
 }
 
 /// Result type for expect operations
 pub type ExpectResult<T> = std::result::Result<T, ExpectError>;
 
+/// Timeout used by wait-for-prompt rules that do not set `timeout_ms`
+pub const DEFAULT_PROMPT_TIMEOUT_MS: u32 = 30_000;
+
+/// Bytes of unconsumed output a [`PromptWaiter`] keeps; older output is
+/// dropped, so a prompt must appear within this much of the latest output
+pub const MAX_PROMPT_BUFFER: usize = 64 * 1024;
+
+/// When an expect rule fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectTiming {
+    /// Respond whenever the pattern appears in the output
+    #[default]
+    Immediate,
+    /// Act as a sequential step: block until the pattern appears (or the
+    /// rule's timeout elapses) before responding and moving to the next step
+    WaitForPrompt,
+}
+
 /// An expect rule with pattern and response
 ///
 /// Expect rules define patterns to match against terminal output and
 /// responses to send when a match is found.
+///
+/// Rules saved before responses supported capture groups are migrated on
+/// load: their `$` signs are escaped so they are still sent literally.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredExpectRule", into = "StoredExpectRule")]
 pub struct ExpectRule {
     /// Unique identifier for this rule
     pub id: Uuid,
     /// Regex pattern to match against terminal output
     pub pattern: String,
-    /// Response to send when pattern matches (supports variables)
+    /// Response to send when pattern matches
+    ///
+    /// Supports `${variable}` references and `$1`, `$2`, ... for the
+    /// pattern's capture groups; `$$` is a literal `$`.
     pub response: String,
     /// Priority for rule ordering (higher = checked first)
     pub priority: i32,
@@ -70,6 +127,9 @@ pub struct ExpectRule {
     pub timeout_ms: Option<u32>,
     /// Whether this rule is enabled
     pub enabled: bool,
+    /// When the rule fires
+    #[serde(default)]
+    pub timing: ExpectTiming,
 }
 
 impl ExpectRule {
@@ -88,6 +148,7 @@ impl ExpectRule {
             priority: 0,
             timeout_ms: None,
             enabled: true,
+            timing: ExpectTiming::Immediate,
         }
     }
 
@@ -101,6 +162,7 @@ impl ExpectRule {
             priority: 0,
             timeout_ms: None,
             enabled: true,
+            timing: ExpectTiming::Immediate,
         }
     }
 
@@ -125,6 +187,56 @@ impl ExpectRule {
         self
     }
 
+    /// Sets when this rule fires
+    #[must_use]
+    pub const fn with_timing(mut self, timing: ExpectTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Returns true if this rule is a wait-for-prompt step
+    #[must_use]
+    pub const fn is_wait_for_prompt(&self) -> bool {
+        matches!(self.timing, ExpectTiming::WaitForPrompt)
+    }
+
+    /// Returns true if this rule answers a password or passphrase prompt
+    ///
+    /// Such rules either reference a password variable in their response or
+    /// match a password prompt. Session loggers suppress logging while the
+    /// response is sent (see
+    /// [`SessionLogger::begin_secret_window`](crate::session::SessionLogger::begin_secret_window)).
+    #[must_use]
+    pub fn answers_secret(&self) -> bool {
+        // Stems, so that patterns like `[Pp]assword:` are recognised too
+        const STEMS: [&str; 3] = ["assword", "assphrase", "asscode"];
+        let pattern = self.pattern.to_lowercase();
+        let response = self.response.to_lowercase();
+        STEMS
+            .iter()
+            .any(|stem| pattern.contains(stem) || response.contains(&format!("${{p{stem}")))
+    }
+
+    /// Sends this rule's response through `send`
+    ///
+    /// When the rule [answers a secret](Self::answers_secret), `window` is
+    /// open while `send` runs, so session logs skip the echoed response.
+    pub fn send_response<R>(&self, window: &SecretWindow, send: impl FnOnce() -> R) -> R {
+        if !self.answers_secret() {
+            return send();
+        }
+        window.begin();
+        let result = send();
+        window.end();
+        result
+    }
+
+    /// Returns the timeout applied when waiting for this rule's prompt
+    #[must_use]
+    pub fn prompt_timeout_ms(&self) -> u32 {
+        self.timeout_ms.unwrap_or(DEFAULT_PROMPT_TIMEOUT_MS)
+    }
+
     /// Validates the regex pattern
     ///
     /// # Errors
@@ -159,11 +271,135 @@ impl PartialEq for ExpectRule {
             && self.priority == other.priority
             && self.timeout_ms == other.timeout_ms
             && self.enabled == other.enabled
+            && self.timing == other.timing
     }
 }
 
 impl Eq for ExpectRule {}
 
+/// Serialized form of [`ExpectRule`]
+#[derive(Serialize, Deserialize)]
+struct StoredExpectRule {
+    id: Uuid,
+    pattern: String,
+    response: String,
+    priority: i32,
+    timeout_ms: Option<u32>,
+    enabled: bool,
+    #[serde(default)]
+    timing: ExpectTiming,
+    /// Whether `$1` and `$$` in the response are capture groups and escapes;
+    /// missing in rules saved before they were supported
+    #[serde(default)]
+    backreferences: bool,
+}
+
+impl From<StoredExpectRule> for ExpectRule {
+    fn from(stored: StoredExpectRule) -> Self {
+        let response = if stored.backreferences {
+            stored.response
+        } else {
+            escape_response_dollars(&stored.response)
+        };
+        Self {
+            id: stored.id,
+            pattern: stored.pattern,
+            response,
+            priority: stored.priority,
+            timeout_ms: stored.timeout_ms,
+            enabled: stored.enabled,
+            timing: stored.timing,
+        }
+    }
+}
+
+impl From<ExpectRule> for StoredExpectRule {
+    fn from(rule: ExpectRule) -> Self {
+        Self {
+            id: rule.id,
+            pattern: rule.pattern,
+            response: rule.response,
+            priority: rule.priority,
+            timeout_ms: rule.timeout_ms,
+            enabled: rule.enabled,
+            timing: rule.timing,
+            backreferences: true,
+        }
+    }
+}
+
+/// Escapes the `$` signs of a response written before capture groups were
+/// supported that would now start a reference or an escape, keeping
+/// `${variable}` references
+fn escape_response_dollars(response: &str) -> String {
+    let mut escaped = String::with_capacity(response.len());
+    let mut chars = response.chars().peekable();
+    while let Some(c) = chars.next() {
+        escaped.push(c);
+        if c == '$'
+            && chars
+                .peek()
+                .is_some_and(|n| *n == '$' || n.is_ascii_digit())
+        {
+            escaped.push('$');
+        }
+    }
+    escaped
+}
+
+/// Part of a parsed response
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ResponsePart {
+    /// Text sent as written, after variable substitution
+    Literal(String),
+    /// Text of a capture group of the match
+    Group(usize),
+}
+
+/// Splits a response into literal text and `$N` capture group references
+fn parse_response(response: &str) -> Vec<ResponsePart> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = response.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c != '$' {
+            literal.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some((_, '$')) => {
+                literal.push('$');
+                chars.next();
+            }
+            Some((_, d)) if d.is_ascii_digit() => {
+                let start = i + 1;
+                let mut end = start;
+                while let Some((j, d)) = chars.peek().copied() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    end = j + 1;
+                    chars.next();
+                }
+                if !literal.is_empty() {
+                    parts.push(ResponsePart::Literal(std::mem::take(&mut literal)));
+                }
+                // Digits only, so this fails only on overflow
+                parts.push(ResponsePart::Group(
+                    response[start..end].parse().unwrap_or(usize::MAX),
+                ));
+            }
+            // `${variable}` and other text stay for variable substitution
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(ResponsePart::Literal(literal));
+    }
+    parts
+}
+
 /// A compiled expect rule with pre-compiled regex pattern
 #[derive(Debug, Clone)]
 pub struct CompiledRule {
@@ -171,6 +407,8 @@ pub struct CompiledRule {
     pub rule: ExpectRule,
     /// The compiled regex pattern
     pub regex: Regex,
+    /// The response split into text and capture group references
+    response: Vec<ResponsePart>,
 }
 
 impl CompiledRule {
@@ -178,10 +416,68 @@ impl CompiledRule {
     ///
     /// # Errors
     ///
-    /// Returns an error if the pattern fails to compile.
+    /// Returns an error if the pattern fails to compile or the response
+    /// references a capture group the pattern does not have.
     pub fn new(rule: ExpectRule) -> ExpectResult<Self> {
         let regex = rule.compile_pattern()?;
-        Ok(Self { rule, regex })
+        let response = parse_response(&rule.response);
+
+        // Group 0 is the whole match
+        let available = regex.captures_len() - 1;
+        if let Some(group) = response.iter().find_map(|part| match part {
+            ResponsePart::Group(n) if *n > available => Some(*n),
+            _ => None,
+        }) {
+            return Err(ExpectError::InvalidBackreference {
+                group,
+                pattern: rule.pattern,
+                available,
+            });
+        }
+
+        Ok(Self {
+            rule,
+            regex,
+            response,
+        })
+    }
+
+    /// Returns the response for the first match in the output
+    ///
+    /// Capture group references are replaced by the matched text, which is
+    /// inserted as-is; `literal` transforms the rest of the response, for
+    /// example to substitute variables. Captured text never goes through
+    /// `literal`, so output cannot inject variable references.
+    ///
+    /// Returns `Ok(None)` if the output does not match.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from `literal`.
+    pub fn response_with<F>(&self, output: &str, mut literal: F) -> ExpectResult<Option<String>>
+    where
+        F: FnMut(&str) -> ExpectResult<String>,
+    {
+        let Some(captures) = self.regex.captures(output) else {
+            return Ok(None);
+        };
+        let mut response = String::new();
+        for part in &self.response {
+            match part {
+                ResponsePart::Literal(text) => response.push_str(&literal(text)?),
+                ResponsePart::Group(n) => response.push_str(group_text(&captures, *n)),
+            }
+        }
+        Ok(Some(response))
+    }
+
+    /// Returns the response for the first match in the output, with capture
+    /// groups substituted and variables left unresolved
+    #[must_use]
+    pub fn response_for(&self, output: &str) -> Option<String> {
+        self.response_with(output, |text| Ok(text.to_string()))
+            .ok()
+            .flatten()
     }
 
     /// Checks if the output matches this rule's pattern
@@ -197,6 +493,11 @@ impl CompiledRule {
     }
 }
 
+/// Returns the text of a capture group, empty if it did not participate
+fn group_text<'a>(captures: &Captures<'a>, group: usize) -> &'a str {
+    captures.get(group).map_or("", |m| m.as_str())
+}
+
 /// Expect engine for pattern matching
 ///
 /// The expect engine manages a collection of expect rules and matches
@@ -305,7 +606,7 @@ impl ExpectEngine {
     /// Sorts rules by priority (highest first)
     fn sort_by_priority(&mut self) {
         self.rules
-            .sort_by(|a, b| b.rule.priority.cmp(&a.rule.priority));
+            .sort_by_key(|compiled| std::cmp::Reverse(compiled.rule.priority));
     }
 
     /// Validates all patterns in the engine
@@ -323,17 +624,111 @@ impl ExpectEngine {
     /// Matches output against all enabled rules, returning the highest priority match
     ///
     /// Rules are checked in priority order (highest first). The first matching
-    /// enabled rule is returned.
+    /// enabled rule is returned. Wait-for-prompt rules are skipped; they only
+    /// fire as steps of [`Self::run_prompt_sequence`].
     #[must_use]
     pub fn match_output(&self, output: &str) -> Option<&ExpectRule> {
         self.rules
             .iter()
-            .filter(|r| r.rule.enabled)
+            .filter(|r| r.rule.enabled && !r.rule.is_wait_for_prompt())
             .find(|r| r.matches(output))
             .map(|r| &r.rule)
     }
 
-    /// Matches output and returns the response with variables substituted
+    /// Returns enabled wait-for-prompt rules in step order (highest priority first)
+    #[must_use]
+    pub fn prompt_steps(&self) -> Vec<&ExpectRule> {
+        self.rules
+            .iter()
+            .filter(|r| r.rule.enabled && r.rule.is_wait_for_prompt())
+            .map(|r| &r.rule)
+            .collect()
+    }
+
+    /// Waits until a rule's pattern appears in the output
+    ///
+    /// Output consumed up to the end of the match is removed from the
+    /// waiter's buffer so the next step only sees newer output. Returns the
+    /// rule together with the matched text, for substituting capture groups
+    /// with [`CompiledRule::response_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExpectError::Timeout` if the pattern does not appear within
+    /// the rule's timeout, `ExpectError::OutputClosed` if the output ends
+    /// first, or `ExpectError::RuleNotFound` for an unknown rule.
+    pub async fn wait_for_prompt(
+        &self,
+        waiter: &mut PromptWaiter,
+        rule_id: Uuid,
+    ) -> ExpectResult<(&CompiledRule, String)> {
+        let compiled = self
+            .rules
+            .iter()
+            .find(|r| r.rule.id == rule_id)
+            .ok_or(ExpectError::RuleNotFound(rule_id))?;
+
+        let timeout_ms = compiled.rule.prompt_timeout_ms();
+        let wait = async {
+            loop {
+                if let Some(found) = compiled.find(&waiter.buffer) {
+                    let (start, end) = (found.start(), found.end());
+                    let matched = waiter.buffer[start..end].to_string();
+                    waiter.buffer.drain(..end);
+                    return Ok(matched);
+                }
+                match waiter.output.recv().await {
+                    Some(chunk) => waiter.push(&chunk),
+                    None => return Err(ExpectError::OutputClosed(rule_id)),
+                }
+            }
+        };
+
+        match tokio::time::timeout(Duration::from_millis(u64::from(timeout_ms)), wait).await {
+            Ok(result) => result.map(|matched| (compiled, matched)),
+            Err(_) => Err(ExpectError::Timeout {
+                rule_id,
+                pattern: compiled.rule.pattern.clone(),
+                timeout_ms,
+            }),
+        }
+    }
+
+    /// Runs all wait-for-prompt steps in order
+    ///
+    /// For each step, waits for its prompt and then passes the rule and the
+    /// matched text to `respond`, which is expected to send the
+    /// (substituted) response.
+    ///
+    /// # Returns
+    ///
+    /// The number of completed steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first step failure; `ExpectError::Timeout` identifies the
+    /// rule whose prompt did not appear.
+    pub async fn run_prompt_sequence<F>(
+        &self,
+        waiter: &mut PromptWaiter,
+        mut respond: F,
+    ) -> ExpectResult<usize>
+    where
+        F: FnMut(&CompiledRule, &str),
+    {
+        let steps: Vec<Uuid> = self.prompt_steps().iter().map(|r| r.id).collect();
+        for id in &steps {
+            let (rule, matched) = self.wait_for_prompt(waiter, *id).await?;
+            respond(rule, &matched);
+        }
+        Ok(steps.len())
+    }
+
+    /// Matches output and returns the response with variables and capture
+    /// groups substituted
+    ///
+    /// Secret variables resolve once loaded with
+    /// [`VariableManager::load_secrets`].
     ///
     /// # Errors
     ///
@@ -344,14 +739,19 @@ impl ExpectEngine {
         variable_manager: &VariableManager,
         scope: VariableScope,
     ) -> ExpectResult<Option<String>> {
-        if let Some(rule) = self.match_output(output) {
-            let response = variable_manager
-                .substitute(&rule.response, scope)
-                .map_err(|e| ExpectError::VariableError(e.to_string()))?;
-            Ok(Some(response))
-        } else {
-            Ok(None)
-        }
+        let Some(compiled) = self
+            .rules
+            .iter()
+            .filter(|r| r.rule.enabled && !r.rule.is_wait_for_prompt())
+            .find(|r| r.matches(output))
+        else {
+            return Ok(None);
+        };
+        compiled.response_with(output, |text| {
+            variable_manager
+                .substitute(text, scope)
+                .map_err(|e| ExpectError::VariableError(e.to_string()))
+        })
     }
 
     /// Matches output and returns the response with variables substituted (Arc version)
@@ -374,6 +774,76 @@ impl ExpectEngine {
     }
 }
 
+/// Buffered terminal output consumed by wait-for-prompt steps
+///
+/// The terminal side sends output chunks through the channel returned by
+/// [`PromptWaiter::channel`]; the waiter accumulates them until a step's
+/// pattern matches. At most [`MAX_PROMPT_BUFFER`] bytes are kept.
+#[derive(Debug)]
+pub struct PromptWaiter {
+    /// Incoming terminal output
+    output: mpsc::UnboundedReceiver<String>,
+    /// Output received but not yet consumed by a matched step
+    buffer: String,
+}
+
+impl PromptWaiter {
+    /// Creates a waiter reading from the given output channel
+    #[must_use]
+    pub const fn new(output: mpsc::UnboundedReceiver<String>) -> Self {
+        Self {
+            output,
+            buffer: String::new(),
+        }
+    }
+
+    /// Creates a waiter together with the sender used to feed it output
+    #[must_use]
+    pub fn channel() -> (mpsc::UnboundedSender<String>, Self) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (sender, Self::new(receiver))
+    }
+
+    /// Returns output received but not yet consumed by a matched step
+    #[must_use]
+    pub fn pending_output(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Returns the part of a screen snapshot that was not in the previous one
+    ///
+    /// Terminals expose their screen rather than a byte stream. Output
+    /// appended below the previous snapshot, including after the screen
+    /// scrolled, is returned on its own; a redrawn screen is returned whole.
+    /// Trailing empty rows are ignored, since they fill up as output arrives.
+    #[must_use]
+    pub fn unseen_output<'a>(previous: &str, current: &'a str) -> &'a str {
+        let previous = previous.trim_end_matches(['\n', '\r']);
+        let current = current.trim_end_matches(['\n', '\r']);
+        let line_starts =
+            std::iter::once(0).chain(previous.match_indices('\n').map(|(i, _)| i + 1));
+        for start in line_starts {
+            let kept = &previous[start..];
+            if !kept.is_empty() && current.starts_with(kept) {
+                return &current[kept.len()..];
+            }
+        }
+        current
+    }
+
+    /// Appends output, dropping the oldest bytes beyond [`MAX_PROMPT_BUFFER`]
+    fn push(&mut self, chunk: &str) {
+        self.buffer.push_str(chunk);
+        if self.buffer.len() > MAX_PROMPT_BUFFER {
+            let mut cut = self.buffer.len() - MAX_PROMPT_BUFFER;
+            while !self.buffer.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.buffer.drain(..cut);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,6 +870,41 @@ mod tests {
         assert_eq!(rule.timeout_ms, Some(5000));
     }
 
+    #[test]
+    fn test_expect_rule_answers_secret() {
+        assert!(ExpectRule::new(r"[Pp]assword:", "hunter2").answers_secret());
+        assert!(ExpectRule::new(r"token>", "${password}").answers_secret());
+        assert!(!ExpectRule::new(r"\$ $", "uptime").answers_secret());
+    }
+
+    #[test]
+    fn test_secret_response_is_not_logged() {
+        use crate::session::{LogConfig, LogContext, LogDirection, SessionLogger};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("session.log");
+        let config = LogConfig::new(log_path.to_string_lossy().to_string()).with_log_input(true);
+        let mut logger = SessionLogger::new(config, &LogContext::new("test", "ssh"), None).unwrap();
+        let window = logger.secret_window();
+
+        // The terminal echoes what automation sends into the input log
+        let sudo = ExpectRule::new(r"\[sudo\] password", "autopass\n");
+        let confirm = ExpectRule::new(r"Continue\?", "yes-please\n");
+        for rule in [&sudo, &confirm] {
+            rule.send_response(&window, || {
+                logger
+                    .write_event(LogDirection::Input, rule.response.as_bytes())
+                    .unwrap();
+            });
+        }
+        assert!(!window.is_open());
+        logger.flush().unwrap();
+
+        let content = std::fs::read_to_string(&log_path).unwrap();
+        assert!(!content.contains("autopass"));
+        assert!(content.contains("yes-please"));
+    }
+
     #[test]
     fn test_expect_rule_validate_valid_pattern() {
         let rule = ExpectRule::new(r"password:\s*$", "secret");
@@ -539,6 +1044,52 @@ mod tests {
         assert!(engine.match_output("no match").is_none());
     }
 
+    #[test]
+    fn test_response_backreferences() {
+        let rule = ExpectRule::new(r"Challenge: (\w+)-(\d+)", "answer $2 $1 $$1 ${user}\n");
+        let compiled = CompiledRule::new(rule).unwrap();
+
+        assert_eq!(
+            compiled.response_for("Challenge: abc-42").as_deref(),
+            Some("answer 42 abc $1 ${user}\n")
+        );
+        assert_eq!(compiled.response_for("no challenge"), None);
+
+        // Optional groups that did not match are empty
+        let rule = ExpectRule::new(r"token(=(\w+))?", "[$2]");
+        let compiled = CompiledRule::new(rule).unwrap();
+        assert_eq!(compiled.response_for("token").as_deref(), Some("[]"));
+    }
+
+    #[test]
+    fn test_invalid_backreference_rejected_at_compile() {
+        let rule = ExpectRule::new(r"Challenge: (\w+)", "$2");
+        let err = CompiledRule::new(rule.clone()).unwrap_err();
+        assert!(matches!(
+            err,
+            ExpectError::InvalidBackreference {
+                group: 2,
+                available: 1,
+                ..
+            }
+        ));
+        assert!(ExpectEngine::new().add_rule(rule).is_err());
+    }
+
+    #[test]
+    fn test_match_and_substitute_keeps_captures_verbatim() {
+        let mut variables = VariableManager::new();
+        variables.set_global(crate::variables::Variable::new("user", "admin"));
+        let engine =
+            ExpectEngine::from_rules(vec![ExpectRule::new(r"Token: (\S+)", "${user} $1")]).unwrap();
+
+        // A captured variable reference is sent as text, not resolved
+        let response = engine
+            .match_and_substitute("Token: ${user}", &variables, VariableScope::Global)
+            .unwrap();
+        assert_eq!(response.as_deref(), Some("admin ${user}"));
+    }
+
     #[test]
     fn test_expect_engine_from_rules() {
         let rules = vec![
@@ -564,15 +1115,173 @@ mod tests {
         ));
     }
 
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    fn router_login_engine() -> (ExpectEngine, Uuid, Uuid) {
+        let login = ExpectRule::new(r"Username:", "admin\n")
+            .with_priority(20)
+            .with_timing(ExpectTiming::WaitForPrompt);
+        let enable = ExpectRule::new(r"router>", "enable\n")
+            .with_priority(10)
+            .with_timeout(50)
+            .with_timing(ExpectTiming::WaitForPrompt);
+        let ids = (login.id, enable.id);
+        let engine = ExpectEngine::from_rules(vec![login, enable]).unwrap();
+        (engine, ids.0, ids.1)
+    }
+
+    #[test]
+    fn test_wait_for_prompt_rules_skip_immediate_matching() {
+        let (engine, _, _) = router_login_engine();
+        assert!(engine.match_output("Username:").is_none());
+        assert_eq!(engine.prompt_steps().len(), 2);
+    }
+
+    #[test]
+    fn test_prompt_sequence_runs_steps_in_order() {
+        let (engine, login_id, enable_id) = router_login_engine();
+        let (sender, mut waiter) = PromptWaiter::channel();
+        sender.send("Welcome\nUser".to_string()).unwrap();
+        sender.send("name: ".to_string()).unwrap();
+        sender.send("\nrouter> extra".to_string()).unwrap();
+
+        let mut responded = Vec::new();
+        let steps = block_on(engine.run_prompt_sequence(&mut waiter, |rule, matched| {
+            responded.push((rule.rule.id, matched.to_string()));
+        }))
+        .unwrap();
+
+        assert_eq!(steps, 2);
+        assert_eq!(
+            responded,
+            vec![
+                (login_id, "Username:".to_string()),
+                (enable_id, "router>".to_string())
+            ]
+        );
+        assert_eq!(waiter.pending_output(), " extra");
+    }
+
+    #[test]
+    fn test_wait_for_prompt_reports_timed_out_rule() {
+        let (engine, _, enable_id) = router_login_engine();
+        let (sender, mut waiter) = PromptWaiter::channel();
+        sender.send("Username: ".to_string()).unwrap();
+
+        let mut responded = 0;
+        let result = block_on(engine.run_prompt_sequence(&mut waiter, |_, _| responded += 1));
+
+        // The first step completed, the second never saw its prompt
+        assert_eq!(responded, 1);
+        match result {
+            Err(ExpectError::Timeout {
+                rule_id,
+                pattern,
+                timeout_ms,
+            }) => {
+                assert_eq!(rule_id, enable_id);
+                assert_eq!(pattern, "router>");
+                assert_eq!(timeout_ms, 50);
+            }
+            other => panic!("Expected timeout, got {other:?}"),
+        }
+        drop(sender);
+    }
+
+    #[test]
+    fn test_prompt_step_substitutes_capture_groups() {
+        let rule = ExpectRule::new(r"Code (\d+):", "$1\n").with_timing(ExpectTiming::WaitForPrompt);
+        let engine = ExpectEngine::from_rules(vec![rule]).unwrap();
+        let (sender, mut waiter) = PromptWaiter::channel();
+        sender.send("Enter Code 4711: ".to_string()).unwrap();
+
+        let mut responses = Vec::new();
+        block_on(engine.run_prompt_sequence(&mut waiter, |rule, matched| {
+            responses.push(rule.response_for(matched).unwrap());
+        }))
+        .unwrap();
+        assert_eq!(responses, vec!["4711\n"]);
+    }
+
+    #[test]
+    fn test_prompt_waiter_buffer_is_capped() {
+        let (_sender, mut waiter) = PromptWaiter::channel();
+        waiter.push(&"é".repeat(MAX_PROMPT_BUFFER));
+        waiter.push("Username:");
+        assert!(waiter.pending_output().len() <= MAX_PROMPT_BUFFER);
+        assert!(waiter.pending_output().ends_with("Username:"));
+    }
+
+    #[test]
+    fn test_unseen_output() {
+        // Appended to the last line and below it
+        assert_eq!(
+            PromptWaiter::unseen_output("login: \n\n\n", "login: admin\nPassword:\n\n"),
+            "admin\nPassword:"
+        );
+        // The screen scrolled by one line
+        assert_eq!(
+            PromptWaiter::unseen_output("one\ntwo\nthree", "two\nthree\nrouter>"),
+            "\nrouter>"
+        );
+        // Redrawn screen
+        assert_eq!(
+            PromptWaiter::unseen_output("old", "new screen"),
+            "new screen"
+        );
+        assert_eq!(PromptWaiter::unseen_output("", "first"), "first");
+    }
+
+    #[test]
+    fn test_wait_for_prompt_output_closed() {
+        let (engine, login_id, _) = router_login_engine();
+        let (sender, mut waiter) = PromptWaiter::channel();
+        drop(sender);
+
+        let result = block_on(engine.wait_for_prompt(&mut waiter, login_id));
+        assert_eq!(result.unwrap_err(), ExpectError::OutputClosed(login_id));
+    }
+
+    #[test]
+    fn test_expect_rule_timing_defaults_to_immediate() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","pattern":"p","response":"r","priority":0,"timeout_ms":null,"enabled":true}"#;
+        let rule: ExpectRule = serde_json::from_str(json).unwrap();
+        assert_eq!(rule.timing, ExpectTiming::Immediate);
+        assert_eq!(rule.prompt_timeout_ms(), DEFAULT_PROMPT_TIMEOUT_MS);
+    }
+
     #[test]
     fn test_expect_rule_serialization() {
         let rule = ExpectRule::new("pattern", "response")
             .with_priority(5)
-            .with_timeout(1000);
+            .with_timeout(1000)
+            .with_timing(ExpectTiming::WaitForPrompt);
 
         let json = serde_json::to_string(&rule).unwrap();
         let deserialized: ExpectRule = serde_json::from_str(&json).unwrap();
 
         assert_eq!(rule, deserialized);
     }
+
+    #[test]
+    fn test_legacy_response_dollars_stay_literal() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","pattern":"Pass(word)","response":"pa$1 $$ ${user} $","priority":0,"timeout_ms":null,"enabled":true}"#;
+        let rule: ExpectRule = serde_json::from_str(json).unwrap();
+        assert_eq!(rule.response, "pa$$1 $$$ ${user} $");
+
+        let compiled = CompiledRule::new(rule.clone()).unwrap();
+        assert_eq!(
+            compiled.response_for("Password").as_deref(),
+            Some("pa$1 $$ ${user} $")
+        );
+
+        // Saved rules are marked as migrated and load unchanged
+        let json = serde_json::to_string(&rule).unwrap();
+        let reloaded: ExpectRule = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded, rule);
+    }
 }
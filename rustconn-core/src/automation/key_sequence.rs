@@ -6,8 +6,10 @@
 //! - Special keys (Enter, Tab, Escape, function keys, etc.)
 //! - Wait commands for timing
 //! - Variable references for dynamic content
+//! - A line-oriented macro script format for sharing sequences as files
 
 use std::fmt;
+use std::fmt::Write as _;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -481,6 +483,9 @@ impl KeySequence {
 
     /// Substitutes variables in the key sequence using the provided manager
     ///
+    /// Secret variables resolve once loaded with
+    /// [`VariableManager::load_secrets`].
+    ///
     /// # Errors
     ///
     /// Returns an error if variable substitution fails.
@@ -530,6 +535,155 @@ impl KeySequence {
         })
     }
 
+    /// Serializes the sequence into the macro script format
+    ///
+    /// Each element is written on its own line:
+    ///
+    /// ```text
+    /// "admin"
+    /// <TAB>
+    /// ${password}
+    /// <ENTER>
+    /// wait 500
+    /// <CTRL+C>
+    /// ```
+    ///
+    /// The output is parsed back to an identical sequence by
+    /// [`Self::from_script`].
+    #[must_use]
+    pub fn to_script(&self) -> String {
+        let mut script = String::new();
+        for element in &self.elements {
+            match element {
+                KeyElement::Text(text) => {
+                    script.push('"');
+                    for ch in text.chars() {
+                        match ch {
+                            '"' => script.push_str("\\\""),
+                            '\\' => script.push_str("\\\\"),
+                            '\n' => script.push_str("\\n"),
+                            '\r' => script.push_str("\\r"),
+                            '\t' => script.push_str("\\t"),
+                            _ => script.push(ch),
+                        }
+                    }
+                    script.push('"');
+                }
+                KeyElement::SpecialKey(key) => {
+                    let _ = write!(script, "<{}>", key.as_str());
+                }
+                KeyElement::Wait(ms) => {
+                    let _ = write!(script, "wait {ms}");
+                }
+                KeyElement::Variable(name) => {
+                    let _ = write!(script, "${{{name}}}");
+                }
+            }
+            script.push('\n');
+        }
+        script
+    }
+
+    /// Parses a macro script
+    ///
+    /// # Syntax
+    ///
+    /// Tokens are separated by whitespace and may share a line:
+    ///
+    /// - Quoted text: `"literal text"` (escapes: `\"`, `\\`, `\n`, `\r`, `\t`)
+    /// - Special keys: `<Enter>`, `<Tab>`, `<F5>`
+    /// - Modifier combinations: `Ctrl+C` (bare) or `<Ctrl+C>`
+    /// - Waits: `wait 500` (milliseconds)
+    /// - Variable references: `${username}`
+    /// - Comments: `#` to the end of the line
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeySequenceError::UnknownKey` for unrecognized key names and
+    /// `KeySequenceError::InvalidSyntax` (with the line number) for other
+    /// malformed input.
+    pub fn from_script(script: &str) -> KeySequenceResult<Self> {
+        let mut elements = Vec::new();
+
+        for (index, line) in script.lines().enumerate() {
+            let line_number = index + 1;
+            let syntax_error = |message: &str| {
+                KeySequenceError::InvalidSyntax(format!("line {line_number}: {message}"))
+            };
+
+            let mut rest = line.trim_start();
+            while !rest.is_empty() {
+                if rest.starts_with('#') {
+                    break;
+                }
+
+                if let Some(quoted) = rest.strip_prefix('"') {
+                    let (text, remaining) = Self::parse_script_string(quoted)
+                        .ok_or_else(|| syntax_error("unterminated string"))?;
+                    if !text.is_empty() {
+                        elements.push(KeyElement::Text(text));
+                    }
+                    rest = remaining;
+                } else if let Some(inner) = rest.strip_prefix('<') {
+                    let end = inner
+                        .find('>')
+                        .ok_or_else(|| syntax_error("unclosed '<'"))?;
+                    elements.push(KeyElement::SpecialKey(SpecialKey::parse(
+                        inner[..end].trim(),
+                    )?));
+                    rest = &inner[end + 1..];
+                } else if let Some(inner) = rest.strip_prefix("${") {
+                    let end = inner
+                        .find('}')
+                        .ok_or_else(|| syntax_error("unclosed '${'"))?;
+                    let name = &inner[..end];
+                    if !Self::is_valid_variable_name(name) {
+                        return Err(syntax_error(&format!("invalid variable name '{name}'")));
+                    }
+                    elements.push(KeyElement::Variable(name.to_string()));
+                    rest = &inner[end + 1..];
+                } else {
+                    let (word, remaining) = split_word(rest);
+                    if word.eq_ignore_ascii_case("wait") {
+                        let (duration, remaining) = split_word(remaining.trim_start());
+                        let ms = duration
+                            .parse()
+                            .map_err(|_| KeySequenceError::InvalidWaitDuration(duration.into()))?;
+                        elements.push(KeyElement::Wait(ms));
+                        rest = remaining;
+                    } else {
+                        elements.push(KeyElement::SpecialKey(SpecialKey::parse(word)?));
+                        rest = remaining;
+                    }
+                }
+                rest = rest.trim_start();
+            }
+        }
+
+        Ok(Self { elements })
+    }
+
+    /// Parses a quoted script string, returning the text and the remaining input
+    ///
+    /// `input` starts just after the opening quote.
+    fn parse_script_string(input: &str) -> Option<(String, &str)> {
+        let mut text = String::new();
+        let mut chars = input.char_indices();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '"' => return Some((text, &input[i + 1..])),
+                '\\' => match chars.next()?.1 {
+                    'n' => text.push('\n'),
+                    'r' => text.push('\r'),
+                    't' => text.push('\t'),
+                    other => text.push(other),
+                },
+                _ => text.push(ch),
+            }
+        }
+        None
+    }
+
     /// Substitutes variables using an Arc-wrapped manager (for async contexts)
     ///
     /// # Errors
@@ -544,6 +698,13 @@ impl KeySequence {
     }
 }
 
+/// Splits off the next whitespace-delimited word
+fn split_word(input: &str) -> (&str, &str) {
+    input
+        .find(char::is_whitespace)
+        .map_or((input, ""), |end| input.split_at(end))
+}
+
 impl fmt::Display for KeySequence {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for element in &self.elements {
@@ -807,6 +968,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_script_round_trip() {
+        let seq = KeySequence::from_elements(vec![
+            KeyElement::Text("say \"hi\"\\ now\n".to_string()),
+            KeyElement::SpecialKey(SpecialKey::Tab),
+            KeyElement::Variable("password".to_string()),
+            KeyElement::Wait(250),
+            KeyElement::SpecialKey(SpecialKey::CtrlC),
+        ]);
+        let script = seq.to_script();
+        assert_eq!(KeySequence::from_script(&script).unwrap(), seq);
+    }
+
+    #[test]
+    fn test_from_script_syntax() {
+        let script =
+            "# login macro\n\"admin\" <Tab> ${pass} <enter>\n\nwait 500\nCtrl+C  # interrupt\n";
+        let seq = KeySequence::from_script(script).unwrap();
+        assert_eq!(
+            seq.elements,
+            vec![
+                KeyElement::Text("admin".to_string()),
+                KeyElement::SpecialKey(SpecialKey::Tab),
+                KeyElement::Variable("pass".to_string()),
+                KeyElement::SpecialKey(SpecialKey::Enter),
+                KeyElement::Wait(500),
+                KeyElement::SpecialKey(SpecialKey::CtrlC),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_script_errors() {
+        assert_eq!(
+            KeySequence::from_script("<Hyper>"),
+            Err(KeySequenceError::UnknownKey("Hyper".to_string()))
+        );
+        assert_eq!(
+            KeySequence::from_script("Ctrl+Q"),
+            Err(KeySequenceError::UnknownKey("Ctrl+Q".to_string()))
+        );
+        assert!(matches!(
+            KeySequence::from_script("wait soon"),
+            Err(KeySequenceError::InvalidWaitDuration(_))
+        ));
+        assert!(matches!(
+            KeySequence::from_script("<Enter>\n\"open"),
+            Err(KeySequenceError::InvalidSyntax(msg)) if msg.starts_with("line 2:")
+        ));
+    }
+
     #[test]
     fn test_ctrl_key_variants() {
         // Test different Ctrl key formats
@@ -4,14 +4,23 @@
 //! - Key sequences for automated keystrokes after connection
 //! - Expect-style pattern matching for interactive prompts
 //! - Pre/post connection tasks
+//! - Shareable automation packs
 
 mod expect;
 mod key_sequence;
+mod pack;
 mod tasks;
 
-pub use expect::{CompiledRule, ExpectEngine, ExpectError, ExpectResult, ExpectRule};
+pub use expect::{
+    CompiledRule, ExpectEngine, ExpectError, ExpectResult, ExpectRule, ExpectTiming, PromptWaiter,
+    DEFAULT_PROMPT_TIMEOUT_MS,
+};
 pub use key_sequence::{KeyElement, KeySequence, KeySequenceError, KeySequenceResult, SpecialKey};
+pub use pack::{
+    AutomationPack, AutomationPackError, AutomationPackResult, AUTOMATION_PACK_EXTENSION,
+    AUTOMATION_PACK_VERSION,
+};
 pub use tasks::{
-    ConnectionTask, FolderConnectionTracker, TaskCondition, TaskError, TaskExecutor, TaskResult,
-    TaskTiming,
+    run_scheduled, ConnectionTask, FolderConnectionTracker, TaskCondition, TaskError, TaskExecutor,
+    TaskResult, TaskRunner, TaskTiming,
 };
@@ -0,0 +1,506 @@
+//! Shareable automation rule packs
+//!
+//! An automation pack bundles a connection's automation settings (expect
+//! rules, post-login scripts, key sequence and pre/post tasks) into a
+//! standalone JSON file so they can be shared between connections or
+//! teammates.
+//!
+//! Packs never carry secret values. When an exported field contains the value
+//! of a secret variable (connection-local or global), it is replaced with a
+//! `${name}` reference on export; the importing side must define the
+//! variable itself.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::Connection;
+use crate::variables::{Variable, VariableManager};
+
+use super::{ConnectionTask, ExpectRule, KeyElement, KeySequence};
+
+/// Current version of the automation pack format
+pub const AUTOMATION_PACK_VERSION: u32 = 1;
+
+/// File extension for automation pack files
+pub const AUTOMATION_PACK_EXTENSION: &str = "rcauto";
+
+/// Errors that can occur when reading or writing automation packs
+#[derive(Debug, Clone, Error)]
+pub enum AutomationPackError {
+    /// Failed to serialize the pack
+    #[error("Failed to serialize automation pack: {0}")]
+    Serialization(String),
+
+    /// Failed to parse JSON
+    #[error("Failed to parse automation pack: {0}")]
+    Parse(String),
+
+    /// Unsupported format version
+    #[error("Unsupported automation pack version: {0} (current: {AUTOMATION_PACK_VERSION})")]
+    UnsupportedVersion(u32),
+
+    /// Failed to read or write a file
+    #[error("I/O error: {0}")]
+    Io(String),
+}
+
+/// Result type for automation pack operations
+pub type AutomationPackResult<T> = std::result::Result<T, AutomationPackError>;
+
+/// A portable bundle of per-connection automation settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutomationPack {
+    /// Format version for migrations
+    pub version: u32,
+    /// Export timestamp
+    pub exported_at: DateTime<Utc>,
+    /// Optional human-readable pack name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Expect rules for interactive prompts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expect_rules: Vec<ExpectRule>,
+    /// Post-login scripts to execute
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_login_scripts: Vec<String>,
+    /// Key sequence to send after connection is established
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_sequence: Option<KeySequence>,
+    /// Task to execute before connecting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_connect_task: Option<ConnectionTask>,
+    /// Task to execute after disconnecting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_disconnect_task: Option<ConnectionTask>,
+    /// Names of secret variables referenced by the pack (values are never exported)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_references: Vec<String>,
+}
+
+impl AutomationPack {
+    /// Creates a pack from a connection's automation settings
+    ///
+    /// Literal occurrences of secret variable values, from the connection's
+    /// local variables and from `global_variables`, are replaced with
+    /// `${name}` references in every exported field.
+    #[must_use]
+    pub fn from_connection(connection: &Connection, global_variables: &[Variable]) -> Self {
+        let scrubber = SecretScrubber::new(connection, global_variables);
+        let mut references = BTreeSet::new();
+
+        let expect_rules = connection
+            .automation
+            .expect_rules
+            .iter()
+            .map(|rule| ExpectRule {
+                response: scrubber.scrub(&rule.response, &mut references),
+                ..rule.clone()
+            })
+            .collect();
+        let post_login_scripts = connection
+            .automation
+            .post_login_scripts
+            .iter()
+            .map(|script| scrubber.scrub(script, &mut references))
+            .collect();
+        let key_sequence = connection
+            .key_sequence
+            .as_ref()
+            .map(|sequence| scrubber.scrub_key_sequence(sequence, &mut references));
+        let mut scrub_task = |task: &ConnectionTask| ConnectionTask {
+            command: scrubber.scrub(&task.command, &mut references),
+            description: task
+                .description
+                .as_ref()
+                .map(|description| scrubber.scrub(description, &mut references)),
+            ..task.clone()
+        };
+        let pre_connect_task = connection.pre_connect_task.as_ref().map(&mut scrub_task);
+        let post_disconnect_task = connection
+            .post_disconnect_task
+            .as_ref()
+            .map(&mut scrub_task);
+
+        Self {
+            version: AUTOMATION_PACK_VERSION,
+            exported_at: Utc::now(),
+            name: Some(connection.name.clone()),
+            expect_rules,
+            post_login_scripts,
+            key_sequence,
+            pre_connect_task,
+            post_disconnect_task,
+            secret_references: references.into_iter().collect(),
+        }
+    }
+
+    /// Applies the pack to a connection, replacing its automation settings
+    ///
+    /// Rules and tasks receive fresh IDs so the same pack can be applied to
+    /// several connections without ID collisions.
+    pub fn apply_to(&self, connection: &mut Connection) {
+        connection.automation.expect_rules = self
+            .expect_rules
+            .iter()
+            .map(|rule| ExpectRule {
+                id: Uuid::new_v4(),
+                ..rule.clone()
+            })
+            .collect();
+        connection
+            .automation
+            .post_login_scripts
+            .clone_from(&self.post_login_scripts);
+        connection.key_sequence.clone_from(&self.key_sequence);
+        connection.pre_connect_task = self.pre_connect_task.as_ref().map(|task| ConnectionTask {
+            id: Uuid::new_v4(),
+            ..task.clone()
+        });
+        connection.post_disconnect_task =
+            self.post_disconnect_task
+                .as_ref()
+                .map(|task| ConnectionTask {
+                    id: Uuid::new_v4(),
+                    ..task.clone()
+                });
+        connection.touch();
+    }
+
+    /// Returns secret references that neither the connection nor the
+    /// global variables define
+    #[must_use]
+    pub fn missing_secrets(
+        &self,
+        connection: &Connection,
+        global_variables: &[Variable],
+    ) -> Vec<&str> {
+        self.secret_references
+            .iter()
+            .filter(|name| {
+                !connection.local_variables.contains_key(name.as_str())
+                    && !global_variables.iter().any(|v| &v.name == *name)
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns true if the pack contains no automation
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.expect_rules.is_empty()
+            && self.post_login_scripts.is_empty()
+            && self.key_sequence.is_none()
+            && self.pre_connect_task.is_none()
+            && self.post_disconnect_task.is_none()
+    }
+
+    /// Export to JSON string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> AutomationPackResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| AutomationPackError::Serialization(e.to_string()))
+    }
+
+    /// Export to a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or file writing fails.
+    pub fn to_file(&self, path: &Path) -> AutomationPackResult<()> {
+        let json = self.to_json()?;
+        fs::write(path, json)
+            .map_err(|e| AutomationPackError::Io(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Import from JSON string with version validation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing fails or the version is unsupported.
+    pub fn from_json(json: &str) -> AutomationPackResult<Self> {
+        let pack: Self =
+            serde_json::from_str(json).map_err(|e| AutomationPackError::Parse(e.to_string()))?;
+
+        if pack.version > AUTOMATION_PACK_VERSION {
+            return Err(AutomationPackError::UnsupportedVersion(pack.version));
+        }
+
+        Ok(pack)
+    }
+
+    /// Import from a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if file reading, parsing, or version validation fails.
+    pub fn from_file(path: &Path) -> AutomationPackResult<Self> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| AutomationPackError::Io(format!("{}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+}
+
+/// Replaces secret variable values with `${name}` references
+struct SecretScrubber {
+    /// Secret `(name, value)` pairs, longest value first so that a secret
+    /// containing another is replaced as a whole
+    secrets: Vec<(String, String)>,
+    /// Names that resolve to a secret variable for this connection
+    secret_names: BTreeSet<String>,
+}
+
+impl SecretScrubber {
+    /// Collects the secrets of a connection and the global variables
+    ///
+    /// A local variable shadows a global one of the same name, but the
+    /// global's value is still scrubbed since it is a secret literal.
+    fn new(connection: &Connection, global_variables: &[Variable]) -> Self {
+        let all = connection.local_variables.values().chain(global_variables);
+        let mut secrets: Vec<(String, String)> = all
+            .filter(|v| v.is_secret && !v.value.is_empty())
+            .map(|v| (v.name.clone(), v.value.clone()))
+            .collect();
+        secrets.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+
+        let secret_names = global_variables
+            .iter()
+            .filter(|v| v.is_secret && !connection.local_variables.contains_key(&v.name))
+            .chain(connection.local_variables.values().filter(|v| v.is_secret))
+            .map(|v| v.name.clone())
+            .collect();
+
+        Self {
+            secrets,
+            secret_names,
+        }
+    }
+
+    /// Scrubs `text` and records the secret variables it refers to
+    fn scrub(&self, text: &str, references: &mut BTreeSet<String>) -> String {
+        let mut text = text.to_string();
+        for (name, value) in &self.secrets {
+            if text.contains(value.as_str()) {
+                text = text.replace(value.as_str(), &format!("${{{name}}}"));
+            }
+        }
+        if let Ok(names) = VariableManager::parse_references(&text) {
+            references.extend(
+                names
+                    .into_iter()
+                    .filter(|name| self.secret_names.contains(name)),
+            );
+        }
+        text
+    }
+
+    /// Scrubs the text elements of a key sequence
+    ///
+    /// Secret values inside typed text become variable elements, since a
+    /// `${name}` in a text element would be typed literally.
+    fn scrub_key_sequence(
+        &self,
+        sequence: &KeySequence,
+        references: &mut BTreeSet<String>,
+    ) -> KeySequence {
+        let mut elements = Vec::with_capacity(sequence.elements.len());
+        for element in &sequence.elements {
+            match element {
+                KeyElement::Text(text) => self.split_text(text, &mut elements, references),
+                KeyElement::Variable(name) => {
+                    if self.secret_names.contains(name) {
+                        references.insert(name.clone());
+                    }
+                    elements.push(element.clone());
+                }
+                _ => elements.push(element.clone()),
+            }
+        }
+        KeySequence::from_elements(elements)
+    }
+
+    /// Pushes `text` as text and variable elements, splitting at secret values
+    fn split_text(
+        &self,
+        text: &str,
+        elements: &mut Vec<KeyElement>,
+        references: &mut BTreeSet<String>,
+    ) {
+        let found = self
+            .secrets
+            .iter()
+            .filter_map(|(name, value)| text.find(value.as_str()).map(|at| (at, name, value)))
+            .min_by_key(|(at, _, _)| *at);
+        let Some((at, name, value)) = found else {
+            if !text.is_empty() {
+                elements.push(KeyElement::Text(text.to_string()));
+            }
+            return;
+        };
+        if at > 0 {
+            elements.push(KeyElement::Text(text[..at].to_string()));
+        }
+        elements.push(KeyElement::Variable(name.clone()));
+        references.insert(name.clone());
+        self.split_text(&text[at + value.len()..], elements, references);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automation::TaskCondition;
+    use crate::models::ProtocolConfig;
+    use crate::variables::Variable;
+
+    fn connection_with_automation() -> Connection {
+        let mut conn = Connection::new(
+            "db-primary".to_string(),
+            "db.example.com".to_string(),
+            22,
+            ProtocolConfig::Ssh(crate::models::SshConfig::default()),
+        );
+        conn.local_variables.insert(
+            "sudo_pass".to_string(),
+            Variable::new_secret("sudo_pass", "hunter2"),
+        );
+        conn.automation.expect_rules = vec![
+            ExpectRule::new(r"\[sudo\] password", "hunter2\n")
+                .with_priority(10)
+                .with_timeout(5000),
+            ExpectRule::new(r"Continue\?", "y\n"),
+        ];
+        conn.automation.post_login_scripts = vec!["uptime".to_string()];
+        conn.pre_connect_task = Some(
+            ConnectionTask::new_pre_connect("vpn up ${vpn}")
+                .with_condition(TaskCondition::first_in_folder())
+                .with_timeout(10_000),
+        );
+        conn
+    }
+
+    #[test]
+    fn test_secret_values_are_referenced_not_embedded() {
+        let conn = connection_with_automation();
+        let pack = AutomationPack::from_connection(&conn, &[]);
+        let json = pack.to_json().unwrap();
+
+        assert!(!json.contains("hunter2"));
+        assert_eq!(pack.expect_rules[0].response, "${sudo_pass}\n");
+        assert_eq!(pack.expect_rules[1].response, "y\n");
+        assert_eq!(pack.secret_references, vec!["sudo_pass".to_string()]);
+    }
+
+    #[test]
+    fn test_secrets_are_scrubbed_from_every_field() {
+        let mut conn = connection_with_automation();
+        conn.automation.post_login_scripts = vec!["echo hunter2 | sudo -S true".to_string()];
+        conn.key_sequence = Some(KeySequence::from_elements(vec![
+            KeyElement::Text("login s3cr3t-token now".to_string()),
+            KeyElement::Variable("sudo_pass".to_string()),
+        ]));
+        conn.pre_connect_task = Some(ConnectionTask::new_pre_connect("vpn up --pass hunter2"));
+        conn.post_disconnect_task = Some(
+            ConnectionTask::new_post_disconnect("curl -H 'X-Token: s3cr3t-token' hook")
+                .with_description("token s3cr3t-token"),
+        );
+        let globals = [
+            Variable::new_secret("api_token", "s3cr3t-token"),
+            Variable::new("region", "eu"),
+        ];
+
+        let pack = AutomationPack::from_connection(&conn, &globals);
+        let json = pack.to_json().unwrap();
+
+        assert!(!json.contains("hunter2"));
+        assert!(!json.contains("s3cr3t-token"));
+        assert_eq!(
+            pack.post_login_scripts,
+            vec!["echo ${sudo_pass} | sudo -S true"]
+        );
+        assert_eq!(
+            pack.key_sequence.as_ref().unwrap().elements,
+            vec![
+                KeyElement::Text("login ".to_string()),
+                KeyElement::Variable("api_token".to_string()),
+                KeyElement::Text(" now".to_string()),
+                KeyElement::Variable("sudo_pass".to_string()),
+            ]
+        );
+        assert_eq!(
+            pack.pre_connect_task.as_ref().unwrap().command,
+            "vpn up --pass ${sudo_pass}"
+        );
+        let post = pack.post_disconnect_task.as_ref().unwrap();
+        assert_eq!(post.command, "curl -H 'X-Token: ${api_token}' hook");
+        assert_eq!(post.description.as_deref(), Some("token ${api_token}"));
+        assert_eq!(pack.secret_references, vec!["api_token", "sudo_pass"]);
+    }
+
+    #[test]
+    fn test_global_secret_definitions_satisfy_references() {
+        let pack = AutomationPack::from_connection(&connection_with_automation(), &[]);
+        let target = Connection::new(
+            "other".to_string(),
+            "other.example.com".to_string(),
+            22,
+            ProtocolConfig::Ssh(crate::models::SshConfig::default()),
+        );
+        let globals = [Variable::new_secret("sudo_pass", "")];
+        assert!(pack.missing_secrets(&target, &globals).is_empty());
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        let pack = AutomationPack::from_connection(&connection_with_automation(), &[]);
+        let imported = AutomationPack::from_json(&pack.to_json().unwrap()).unwrap();
+        assert_eq!(imported, pack);
+    }
+
+    #[test]
+    fn test_apply_to_other_connection() {
+        let source = connection_with_automation();
+        let pack = AutomationPack::from_connection(&source, &[]);
+
+        let mut target = Connection::new(
+            "db-replica".to_string(),
+            "replica.example.com".to_string(),
+            22,
+            ProtocolConfig::Ssh(crate::models::SshConfig::default()),
+        );
+        pack.apply_to(&mut target);
+
+        assert_eq!(target.automation.expect_rules.len(), 2);
+        assert_ne!(
+            target.automation.expect_rules[0].id,
+            source.automation.expect_rules[0].id
+        );
+        assert_eq!(target.automation.expect_rules[0].priority, 10);
+        assert_eq!(target.automation.expect_rules[0].timeout_ms, Some(5000));
+        assert_eq!(target.automation.post_login_scripts, vec!["uptime"]);
+
+        let task = target.pre_connect_task.as_ref().unwrap();
+        assert_eq!(task.command, "vpn up ${vpn}");
+        assert!(task.condition.only_first_in_folder);
+        assert_eq!(pack.missing_secrets(&target, &[]), vec!["sudo_pass"]);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut pack = AutomationPack::from_connection(&connection_with_automation(), &[]);
+        pack.version = AUTOMATION_PACK_VERSION + 1;
+        let result = AutomationPack::from_json(&pack.to_json().unwrap());
+        assert!(matches!(
+            result,
+            Err(AutomationPackError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -4,18 +4,24 @@
 //! before connecting and after disconnecting. It supports:
 //! - Pre-connect tasks (e.g., VPN setup, tunnel creation)
 //! - Post-disconnect tasks (e.g., cleanup, logging)
+//! - Recurring tasks on a fixed interval (e.g., keepalive commands)
 //! - Conditional execution based on folder connection state
 //! - Variable substitution in command strings
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::process::ExitStatus;
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::process::Command;
+use tokio::time::Instant;
 use uuid::Uuid;
 
+use crate::models::Connection;
+use crate::secret::CancellationToken;
 use crate::variables::{VariableManager, VariableScope};
 
 /// Errors that can occur during task operations
@@ -64,6 +70,10 @@ pub enum TaskTiming {
     PreConnect,
     /// Execute after the connection is terminated
     PostDisconnect,
+    /// Execute repeatedly while the connection is active
+    ///
+    /// Serialized as the interval in milliseconds.
+    Interval(#[serde(with = "interval_millis")] Duration),
 }
 
 impl TaskTiming {
@@ -73,10 +83,36 @@ impl TaskTiming {
         match self {
             Self::PreConnect => "Pre-connect",
             Self::PostDisconnect => "Post-disconnect",
+            Self::Interval(_) => "Recurring",
+        }
+    }
+
+    /// Returns the repeat interval for scheduled timings
+    #[must_use]
+    pub const fn interval(&self) -> Option<Duration> {
+        match self {
+            Self::Interval(interval) => Some(*interval),
+            Self::PreConnect | Self::PostDisconnect => None,
         }
     }
 }
 
+/// Serde helper storing interval durations as whole milliseconds
+mod interval_millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn serialize<S: Serializer>(interval: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(interval.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
 /// Task execution condition
 ///
 /// Defines conditions for when a task should be executed, particularly
@@ -213,6 +249,23 @@ impl ConnectionTask {
         }
     }
 
+    /// Creates a new recurring task that runs every `interval` while connected
+    ///
+    /// Failures of recurring tasks are reported as errors for each run but
+    /// never stop the schedule.
+    #[must_use]
+    pub fn new_scheduled(command: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            timing: TaskTiming::Interval(interval),
+            command: command.into(),
+            condition: TaskCondition::new(),
+            timeout_ms: None,
+            abort_on_failure: true,
+            description: None,
+        }
+    }
+
     /// Creates a new task with a specific ID
     #[must_use]
     pub fn with_id(id: Uuid, timing: TaskTiming, command: impl Into<String>) -> Self {
@@ -222,7 +275,7 @@ impl ConnectionTask {
             command: command.into(),
             condition: TaskCondition::new(),
             timeout_ms: None,
-            abort_on_failure: matches!(timing, TaskTiming::PreConnect),
+            abort_on_failure: !matches!(timing, TaskTiming::PostDisconnect),
             description: None,
         }
     }
@@ -267,8 +320,18 @@ impl ConnectionTask {
         matches!(self.timing, TaskTiming::PostDisconnect)
     }
 
+    /// Returns true if this is a recurring task
+    #[must_use]
+    pub const fn is_scheduled(&self) -> bool {
+        matches!(self.timing, TaskTiming::Interval(_))
+    }
+
     /// Substitutes variables in the command string
     ///
+    /// Secret variables resolve only once loaded with
+    /// [`VariableManager::load_secrets`]; [`TaskExecutor`] fetches them
+    /// itself.
+    ///
     /// # Errors
     ///
     /// Returns an error if variable substitution fails.
@@ -301,6 +364,8 @@ impl ConnectionTask {
 pub struct FolderConnectionTracker {
     /// Map of folder ID to count of active connections
     active_counts: HashMap<Uuid, usize>,
+    /// IDs of connections opened through [`Self::track_opened`]
+    open_connections: HashSet<Uuid>,
 }
 
 impl FolderConnectionTracker {
@@ -333,6 +398,56 @@ impl FolderConnectionTracker {
         *count == 0
     }
 
+    /// Records a specific connection being opened in a folder
+    ///
+    /// Opening an already tracked connection does not change the folder
+    /// count. Returns true if this is the first connection in the folder.
+    pub fn track_opened(&mut self, connection_id: Uuid, folder_id: Option<Uuid>) -> bool {
+        if self.open_connections.insert(connection_id) {
+            self.connection_opened(folder_id)
+        } else {
+            false
+        }
+    }
+
+    /// Records a specific connection being closed in a folder
+    ///
+    /// Returns true if this was the last connection in the folder.
+    pub fn track_closed(&mut self, connection_id: Uuid, folder_id: Option<Uuid>) -> bool {
+        if self.open_connections.remove(&connection_id) {
+            self.connection_closed(folder_id)
+        } else {
+            !self.has_active_connections(folder_id)
+        }
+    }
+
+    /// Returns true if the connection was opened through [`Self::track_opened`]
+    /// and has not been closed since
+    #[must_use]
+    pub fn is_open(&self, connection_id: Uuid) -> bool {
+        self.open_connections.contains(&connection_id)
+    }
+
+    /// Returns the connections of a group that still need to be launched
+    ///
+    /// Only direct members of the group are considered. Connections that are
+    /// already tracked as open are excluded, and the rest are ordered by
+    /// `launch_order`, then `sort_order`, then name.
+    #[must_use]
+    pub fn pending_for_group(&self, group_id: Uuid, connections: &[Connection]) -> Vec<Uuid> {
+        let mut pending: Vec<&Connection> = connections
+            .iter()
+            .filter(|c| c.is_in_group(group_id) && !self.is_open(c.id))
+            .collect();
+        pending.sort_by(|a, b| {
+            a.launch_order
+                .cmp(&b.launch_order)
+                .then(a.sort_order.cmp(&b.sort_order))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        pending.into_iter().map(|c| c.id).collect()
+    }
+
     /// Returns the number of active connections in a folder
     #[must_use]
     pub fn active_count(&self, folder_id: Option<Uuid>) -> usize {
@@ -349,6 +464,7 @@ impl FolderConnectionTracker {
     /// Clears all tracking data
     pub fn clear(&mut self) {
         self.active_counts.clear();
+        self.open_connections.clear();
     }
 }
 
@@ -445,8 +561,12 @@ impl TaskExecutor {
             return Ok(0); // Skip execution, return success
         }
 
-        // Substitute variables in command
-        let command = task.substitute_command_arc(&self.variable_manager, scope)?;
+        // Substitute variables in command, fetching secret variables
+        let command = self
+            .variable_manager
+            .substitute_with_secrets(&task.command, scope)
+            .await
+            .map_err(|e| TaskError::VariableError(e.to_string()))?;
 
         // Execute the command
         self.execute_command(&command, task.timeout_ms, task.abort_on_failure)
@@ -545,6 +665,139 @@ impl TaskExecutor {
 
         self.execute(task, scope, folder_id, false, is_last).await
     }
+
+    /// Runs recurring tasks on their intervals until `cancel` is triggered
+    ///
+    /// See [`run_scheduled`] for the scheduling rules.
+    pub async fn run_scheduled<F>(
+        &self,
+        tasks: &[ConnectionTask],
+        scope: VariableScope,
+        folder_id: Option<Uuid>,
+        cancel: &CancellationToken,
+        on_result: F,
+    ) -> usize
+    where
+        F: FnMut(&ConnectionTask, TaskResult<i32>),
+    {
+        run_scheduled(self, tasks, scope, folder_id, cancel, on_result).await
+    }
+}
+
+/// Runs single tasks on behalf of [`run_scheduled`]
+///
+/// [`TaskExecutor`] runs commands through the shell and tracks folders with
+/// its [`FolderConnectionTracker`].
+#[async_trait]
+pub trait TaskRunner: Send + Sync {
+    /// Runs one task and returns its exit code
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task fails; see [`TaskExecutor::execute`].
+    async fn run_task(
+        &self,
+        task: &ConnectionTask,
+        scope: VariableScope,
+        folder_id: Option<Uuid>,
+        is_first: bool,
+        is_last: bool,
+    ) -> TaskResult<i32>;
+
+    /// Returns the number of active connections in a folder
+    fn active_in_folder(&self, folder_id: Option<Uuid>) -> usize;
+}
+
+#[async_trait]
+impl TaskRunner for TaskExecutor {
+    async fn run_task(
+        &self,
+        task: &ConnectionTask,
+        scope: VariableScope,
+        folder_id: Option<Uuid>,
+        is_first: bool,
+        is_last: bool,
+    ) -> TaskResult<i32> {
+        self.execute(task, scope, folder_id, is_first, is_last)
+            .await
+    }
+
+    fn active_in_folder(&self, folder_id: Option<Uuid>) -> usize {
+        self.folder_tracker
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .active_count(folder_id)
+    }
+}
+
+/// Runs recurring tasks on their intervals until `cancel` is triggered
+///
+/// Only tasks with [`TaskTiming::Interval`] are scheduled; others are
+/// ignored. Each task first runs one interval after the loop starts.
+/// Folder restrictions in a task's [`TaskCondition`] are satisfied while
+/// the connection is the only active one in its folder.
+///
+/// Every run's outcome is passed to `on_result`. A failing run produces a
+/// `TaskError` there but does not stop the schedule. Intervals follow the
+/// tokio clock, so a paused runtime advances them instantly.
+///
+/// # Returns
+///
+/// The number of task runs performed before cancellation.
+pub async fn run_scheduled<R, F>(
+    runner: &R,
+    tasks: &[ConnectionTask],
+    scope: VariableScope,
+    folder_id: Option<Uuid>,
+    cancel: &CancellationToken,
+    mut on_result: F,
+) -> usize
+where
+    R: TaskRunner + ?Sized,
+    F: FnMut(&ConnectionTask, TaskResult<i32>),
+{
+    let start = Instant::now();
+    let mut schedule: Vec<(&ConnectionTask, Duration, Instant)> = tasks
+        .iter()
+        .filter_map(|task| task.timing.interval().map(|interval| (task, interval)))
+        .filter(|(_, interval)| !interval.is_zero())
+        .map(|(task, interval)| (task, interval, start + interval))
+        .collect();
+
+    let mut runs = 0;
+    while !schedule.is_empty() && !cancel.is_cancelled() {
+        let Some(next_due) = schedule.iter().map(|(_, _, due)| *due).min() else {
+            break;
+        };
+
+        tokio::select! {
+            () = cancel.cancelled() => break,
+            () = tokio::time::sleep_until(next_due) => {}
+        }
+
+        let now = Instant::now();
+        for (task, interval, due) in &mut schedule {
+            if *due > now {
+                continue;
+            }
+            *due = now + *interval;
+
+            let sole = runner.active_in_folder(folder_id) <= 1;
+            if !task.condition.should_execute(sole, sole) {
+                continue;
+            }
+
+            let result = runner.run_task(task, scope, folder_id, sole, sole).await;
+            runs += 1;
+            on_result(task, result);
+
+            if cancel.is_cancelled() {
+                return runs;
+            }
+        }
+    }
+
+    runs
 }
 
 #[cfg(test)]
@@ -555,6 +808,164 @@ mod tests {
     fn test_task_timing_description() {
         assert_eq!(TaskTiming::PreConnect.description(), "Pre-connect");
         assert_eq!(TaskTiming::PostDisconnect.description(), "Post-disconnect");
+        assert_eq!(
+            TaskTiming::Interval(Duration::from_secs(300)).description(),
+            "Recurring"
+        );
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_scheduled_task_serialization() {
+        let task = ConnectionTask::new_scheduled("echo keepalive", Duration::from_secs(300));
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(json.contains(r#""timing":{"interval":300000}"#));
+
+        let parsed: ConnectionTask = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, task);
+        assert!(parsed.is_scheduled());
+        assert_eq!(parsed.timing.interval(), Some(Duration::from_secs(300)));
+    }
+
+    /// Runner that fails `exit 3` and counts runs without spawning processes
+    #[derive(Default)]
+    struct FakeRunner {
+        active: usize,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TaskRunner for FakeRunner {
+        async fn run_task(
+            &self,
+            task: &ConnectionTask,
+            _scope: VariableScope,
+            _folder_id: Option<Uuid>,
+            _is_first: bool,
+            _is_last: bool,
+        ) -> TaskResult<i32> {
+            self.calls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            if task.command == "exit 3" {
+                Err(TaskError::NonZeroExit(3))
+            } else {
+                Ok(0)
+            }
+        }
+
+        fn active_in_folder(&self, _folder_id: Option<Uuid>) -> usize {
+            self.active
+        }
+    }
+
+    /// Runs `future` on a runtime whose clock advances only while idle
+    fn block_on_paused<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_run_scheduled_continues_after_failures() {
+        let runner = FakeRunner::default();
+        let tasks = vec![
+            ConnectionTask::new_scheduled("true", Duration::from_secs(300)),
+            ConnectionTask::new_scheduled("exit 3", Duration::from_secs(300)),
+            ConnectionTask::new_pre_connect("false"),
+        ];
+        let cancel = CancellationToken::new();
+
+        let mut successes = 0;
+        let mut failures = Vec::new();
+        let (runs, elapsed) = block_on_paused(async {
+            let start = Instant::now();
+            let runs = run_scheduled(
+                &runner,
+                &tasks,
+                VariableScope::Global,
+                None,
+                &cancel,
+                |task, result| {
+                    match result {
+                        Ok(_) => successes += 1,
+                        Err(e) => failures.push((task.command.clone(), e)),
+                    }
+                    if successes + failures.len() == 6 {
+                        cancel.cancel();
+                    }
+                },
+            )
+            .await;
+            (runs, start.elapsed())
+        });
+
+        // Three ticks of both recurring tasks; the pre-connect task never runs
+        assert_eq!(runs, 6);
+        assert_eq!(successes, 3);
+        assert_eq!(failures.len(), 3);
+        assert!(failures
+            .iter()
+            .all(|(cmd, e)| cmd == "exit 3" && *e == TaskError::NonZeroExit(3)));
+        assert_eq!(elapsed, Duration::from_secs(900));
+    }
+
+    #[test]
+    fn test_run_scheduled_stops_when_cancelled() {
+        let executor = TaskExecutor::new(Arc::new(VariableManager::new()));
+        let tasks = vec![ConnectionTask::new_scheduled(
+            "true",
+            Duration::from_secs(3600),
+        )];
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let runs = block_on(executor.run_scheduled(
+            &tasks,
+            VariableScope::Global,
+            None,
+            &cancel,
+            |_, _| panic!("cancelled schedule must not run tasks"),
+        ));
+        assert_eq!(runs, 0);
+    }
+
+    #[test]
+    fn test_run_scheduled_respects_folder_condition() {
+        let runner = FakeRunner {
+            active: 2,
+            ..FakeRunner::default()
+        };
+        let tasks = vec![
+            ConnectionTask::new_scheduled("true", Duration::from_secs(60))
+                .with_condition(TaskCondition::first_in_folder()),
+        ];
+        let cancel = CancellationToken::new();
+
+        let runs = block_on_paused(async {
+            let stopper = async {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                cancel.cancel();
+            };
+            let schedule = run_scheduled(
+                &runner,
+                &tasks,
+                VariableScope::Global,
+                Some(Uuid::new_v4()),
+                &cancel,
+                |_, _| {},
+            );
+            tokio::join!(schedule, stopper).0
+        });
+        assert_eq!(runs, 0);
+        assert_eq!(runner.calls.load(std::sync::atomic::Ordering::Relaxed), 0);
     }
 
     #[test]
@@ -689,6 +1100,41 @@ mod tests {
         assert_eq!(tracker.active_count(None), 0);
     }
 
+    #[test]
+    fn test_pending_for_group_orders_and_skips_open() {
+        let group_id = Uuid::new_v4();
+        let make = |name: &str, launch_order: i32| {
+            let mut conn = Connection::new_ssh(name.to_string(), "host".to_string(), 22);
+            conn.group_id = Some(group_id);
+            conn.launch_order = launch_order;
+            conn
+        };
+        let db = make("db", 0);
+        let web = make("web", 2);
+        let cache = make("cache", 1);
+        let api = make("api", 2);
+        let outside = Connection::new_ssh("other".to_string(), "host".to_string(), 22);
+        let connections = vec![web.clone(), db.clone(), outside, api.clone(), cache.clone()];
+
+        let mut tracker = FolderConnectionTracker::new();
+        assert_eq!(
+            tracker.pending_for_group(group_id, &connections),
+            vec![db.id, cache.id, api.id, web.id]
+        );
+
+        assert!(tracker.track_opened(cache.id, Some(group_id)));
+        assert!(!tracker.track_opened(cache.id, Some(group_id)));
+        assert_eq!(tracker.active_count(Some(group_id)), 1);
+        assert_eq!(
+            tracker.pending_for_group(group_id, &connections),
+            vec![db.id, api.id, web.id]
+        );
+
+        assert!(tracker.track_closed(cache.id, Some(group_id)));
+        assert!(!tracker.is_open(cache.id));
+        assert_eq!(tracker.pending_for_group(group_id, &connections).len(), 4);
+    }
+
     #[test]
     fn test_task_substitute_command() {
         let mut manager = VariableManager::new();
@@ -1,13 +1,26 @@
 //! Cluster management for `RustConn`
 //!
 //! This module provides cluster functionality for managing multiple connections
-//! as a group, including broadcast mode for sending input to all sessions simultaneously.
+//! as a group, including broadcast mode for sending input to all sessions simultaneously
+//! and running a single command on every member with per-host results.
+
+mod ssh;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::models::{Connection, ConnectionGroup};
+use crate::search::{SearchEngine, SearchQuery};
+
+pub use ssh::{SshCommandRunner, DEFAULT_COMMAND_TIMEOUT, DEFAULT_CONNECT_TIMEOUT};
+
+/// Maximum number of members a cluster command runs on at the same time
+pub const MAX_CONCURRENT_COMMANDS: usize = 8;
+
 /// Errors related to cluster operations
 #[derive(Debug, Error)]
 pub enum ClusterError {
@@ -65,6 +78,11 @@ pub struct ClusterMemberState {
     pub status: ClusterSessionStatus,
     /// Error message if status is Error
     pub error_message: Option<String>,
+    /// Whether this member receives broadcast input
+    ///
+    /// Members can be temporarily excluded from broadcast (e.g. a hung node)
+    /// without leaving the cluster session.
+    pub broadcast_active: bool,
 }
 
 impl ClusterMemberState {
@@ -75,6 +93,7 @@ impl ClusterMemberState {
             connection_id,
             status: ClusterSessionStatus::Pending,
             error_message: None,
+            broadcast_active: true,
         }
     }
 
@@ -110,6 +129,86 @@ impl ClusterMemberState {
             ClusterSessionStatus::Connecting | ClusterSessionStatus::Connected
         )
     }
+
+    /// Returns true if the member is connected and not excluded from broadcast
+    #[must_use]
+    pub fn is_broadcast_target(&self) -> bool {
+        self.broadcast_active && self.status == ClusterSessionStatus::Connected
+    }
+}
+
+/// Outcome of a command run on one cluster member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClusterCommandStatus {
+    /// The command ran and exited with the given code
+    Exited(i32),
+    /// The command could not be run on the host
+    Failed(String),
+    /// The member was not connected or is excluded from broadcast
+    Skipped,
+}
+
+/// Output and status of a command run on one cluster member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterCommandResult {
+    /// Standard output captured from the command
+    pub output: String,
+    /// How the command ended
+    pub status: ClusterCommandStatus,
+}
+
+impl ClusterCommandResult {
+    /// Creates a result for a command that exited with `exit_code`
+    #[must_use]
+    pub fn exited(output: impl Into<String>, exit_code: i32) -> Self {
+        Self {
+            output: output.into(),
+            status: ClusterCommandStatus::Exited(exit_code),
+        }
+    }
+
+    /// Creates a result for a command that could not be run
+    #[must_use]
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self {
+            output: String::new(),
+            status: ClusterCommandStatus::Failed(message.into()),
+        }
+    }
+
+    /// Creates a result for a member the command was not sent to
+    #[must_use]
+    pub const fn skipped() -> Self {
+        Self {
+            output: String::new(),
+            status: ClusterCommandStatus::Skipped,
+        }
+    }
+
+    /// Returns true if the command exited with code 0
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        matches!(self.status, ClusterCommandStatus::Exited(0))
+    }
+
+    /// Returns the exit code if the command ran
+    #[must_use]
+    pub const fn exit_code(&self) -> Option<i32> {
+        match self.status {
+            ClusterCommandStatus::Exited(code) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Runs a command on the host behind a cluster member
+///
+/// [`SshCommandRunner`] executes the command over a separate SSH
+/// connection. Implementations are called from several threads at once and
+/// should block until the command finishes.
+pub trait ClusterCommandRunner: Send + Sync {
+    /// Runs `command` on the host of `connection_id` and collects its output
+    fn run(&self, connection_id: Uuid, command: &str) -> ClusterCommandResult;
 }
 
 /// A cluster of connections that can be managed together
@@ -188,6 +287,8 @@ pub struct ClusterSession {
     pub cluster_name: String,
     /// State of each member session, keyed by connection ID
     sessions: HashMap<Uuid, ClusterMemberState>,
+    /// Member connection IDs in cluster order
+    members: Vec<Uuid>,
     /// Whether broadcast mode is currently enabled
     broadcast_mode: bool,
 }
@@ -206,6 +307,7 @@ impl ClusterSession {
             cluster_id: cluster.id,
             cluster_name: cluster.name.clone(),
             sessions,
+            members: cluster.connection_ids.clone(),
             broadcast_mode: cluster.broadcast_enabled,
         }
     }
@@ -279,6 +381,25 @@ impl ClusterSession {
         self.sessions.len()
     }
 
+    /// Includes or excludes a member from broadcast input
+    ///
+    /// Returns false if the connection is not a member of this session.
+    pub fn set_member_active(&mut self, connection_id: Uuid, active: bool) -> bool {
+        self.sessions
+            .get_mut(&connection_id)
+            .map(|state| state.broadcast_active = active)
+            .is_some()
+    }
+
+    /// Returns the number of members that receive broadcast input
+    #[must_use]
+    pub fn active_member_count(&self) -> usize {
+        self.sessions
+            .values()
+            .filter(|s| s.broadcast_active)
+            .count()
+    }
+
     /// Returns the number of connected sessions
     #[must_use]
     pub fn connected_count(&self) -> usize {
@@ -334,16 +455,16 @@ impl ClusterSession {
             return Vec::new();
         }
 
-        // Return IDs of all connected sessions
+        // Return IDs of all connected sessions not excluded from broadcast
         self.sessions
             .iter()
-            .filter(|(_, state)| state.status == ClusterSessionStatus::Connected)
+            .filter(|(_, state)| state.is_broadcast_target())
             .map(|(id, _)| *id)
             .collect()
     }
 
     /// Returns the IDs of all sessions that should receive input
-    /// In broadcast mode, returns all connected sessions
+    /// In broadcast mode, returns all connected, non-excluded sessions
     /// Otherwise, returns an empty vec (caller should handle single session focus)
     #[must_use]
     pub fn get_input_targets(&self) -> Vec<Uuid> {
@@ -354,6 +475,71 @@ impl ClusterSession {
         }
     }
 
+    /// Runs one command on every member and collects the per-host results
+    ///
+    /// The command is sent to all connected members not excluded from
+    /// broadcast, whether or not broadcast mode is on, with at most
+    /// [`MAX_CONCURRENT_COMMANDS`] hosts running at once. This returns once
+    /// every host has finished. Results are in cluster order and include
+    /// every member; members the command was not sent to are reported as
+    /// [`ClusterCommandStatus::Skipped`].
+    #[must_use]
+    pub fn run_command(
+        &self,
+        command: &str,
+        runner: &dyn ClusterCommandRunner,
+    ) -> Vec<(Uuid, ClusterCommandResult)> {
+        self.run_command_with_limit(command, runner, MAX_CONCURRENT_COMMANDS)
+    }
+
+    /// Like [`Self::run_command`], with at most `limit` hosts at once
+    #[must_use]
+    pub fn run_command_with_limit(
+        &self,
+        command: &str,
+        runner: &dyn ClusterCommandRunner,
+        limit: usize,
+    ) -> Vec<(Uuid, ClusterCommandResult)> {
+        let targets: Vec<usize> = self
+            .members
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| {
+                self.sessions
+                    .get(id)
+                    .is_some_and(ClusterMemberState::is_broadcast_target)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let results: Mutex<Vec<ClusterCommandResult>> =
+            Mutex::new(vec![ClusterCommandResult::skipped(); self.members.len()]);
+        let next = AtomicUsize::new(0);
+
+        // Each worker takes the next pending member until none are left
+        std::thread::scope(|scope| {
+            for _ in 0..limit.clamp(1, targets.len().max(1)) {
+                scope.spawn(|| {
+                    while let Some(&index) = targets.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let id = self.members[index];
+                        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                            runner.run(id, command)
+                        }))
+                        .unwrap_or_else(|_| {
+                            ClusterCommandResult::failed("command runner panicked")
+                        });
+                        results.lock().unwrap_or_else(PoisonError::into_inner)[index] = result;
+                    }
+                });
+            }
+        });
+
+        self.members
+            .iter()
+            .copied()
+            .zip(results.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .collect()
+    }
+
     /// Returns connection IDs of sessions that failed
     #[must_use]
     pub fn get_failed_sessions(&self) -> Vec<(Uuid, Option<String>)> {
@@ -499,6 +685,90 @@ mod tests {
         let targets = session.broadcast_input("test");
         assert_eq!(targets.len(), 2);
     }
+
+    struct EchoRunner {
+        unreachable: Uuid,
+    }
+
+    impl ClusterCommandRunner for EchoRunner {
+        fn run(&self, connection_id: Uuid, command: &str) -> ClusterCommandResult {
+            if connection_id == self.unreachable {
+                ClusterCommandResult::failed("connection reset")
+            } else {
+                ClusterCommandResult::exited(format!("{connection_id}: {command}\n"), 0)
+            }
+        }
+    }
+
+    #[test]
+    fn test_cluster_session_run_command() {
+        let mut cluster = Cluster::new("Test".to_string());
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            cluster.add_connection(*id);
+        }
+
+        let mut session = ClusterSession::new(&cluster);
+        for id in &ids[..3] {
+            session.update_session_status(*id, ClusterSessionStatus::Connected);
+        }
+        session.set_member_active(ids[2], false);
+
+        let runner = EchoRunner {
+            unreachable: ids[1],
+        };
+        let results = session.run_command("uptime", &runner);
+
+        let order: Vec<Uuid> = results.iter().map(|(id, _)| *id).collect();
+        assert_eq!(order, ids);
+        assert!(results[0].1.is_success());
+        assert_eq!(results[0].1.output, format!("{}: uptime\n", ids[0]));
+        assert_eq!(results[0].1.exit_code(), Some(0));
+        assert_eq!(
+            results[1].1.status,
+            ClusterCommandStatus::Failed("connection reset".to_string())
+        );
+        assert_eq!(results[1].1.exit_code(), None);
+        // Excluded and disconnected members are skipped
+        assert_eq!(results[2].1, ClusterCommandResult::skipped());
+        assert_eq!(results[3].1, ClusterCommandResult::skipped());
+    }
+
+    /// Runner recording how many commands run at the same time
+    #[derive(Default)]
+    struct CountingRunner {
+        active: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl ClusterCommandRunner for CountingRunner {
+        fn run(&self, _connection_id: Uuid, _command: &str) -> ClusterCommandResult {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            ClusterCommandResult::exited("", 0)
+        }
+    }
+
+    #[test]
+    fn test_cluster_session_run_command_is_bounded() {
+        let mut cluster = Cluster::new("Test".to_string());
+        let ids: Vec<Uuid> = (0..12).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            cluster.add_connection(*id);
+        }
+        let mut session = ClusterSession::new(&cluster);
+        for id in &ids {
+            session.update_session_status(*id, ClusterSessionStatus::Connected);
+        }
+
+        let runner = CountingRunner::default();
+        let results = session.run_command_with_limit("true", &runner, 3);
+        assert_eq!(results.len(), ids.len());
+        assert!(results.iter().all(|(_, r)| r.is_success()));
+        assert!(runner.peak.load(Ordering::SeqCst) <= 3);
+    }
 }
 
 /// Manager for active cluster sessions
@@ -543,6 +813,41 @@ impl ClusterManager {
         self.clusters.get_mut(&cluster_id)
     }
 
+    /// Creates a cluster from the connections matching a search query
+    ///
+    /// Matches are added in relevance order; a connection is only added
+    /// once. Archived connections are left out unless the query has a
+    /// `status:` filter selecting them. The new cluster is stored in the
+    /// manager.
+    ///
+    /// # Errors
+    /// Returns `ClusterError::EmptyCluster` if the query matches no connections
+    ///
+    /// # Panics
+    /// This function will not panic as the cluster is inserted before retrieval.
+    pub fn create_from_filter(
+        &mut self,
+        name: String,
+        query: &SearchQuery,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+    ) -> ClusterResult<&Cluster> {
+        let mut cluster = Cluster::new(name);
+        for result in SearchEngine::new().search(query, connections, groups) {
+            cluster.add_connection(result.connection_id);
+        }
+
+        if cluster.is_empty() {
+            return Err(ClusterError::EmptyCluster);
+        }
+
+        let cluster_id = cluster.id;
+        self.clusters.insert(cluster_id, cluster);
+
+        // Safe to unwrap: we just inserted the cluster above
+        Ok(self.clusters.get(&cluster_id).unwrap())
+    }
+
     /// Updates an existing cluster
     ///
     /// # Errors
@@ -659,6 +964,31 @@ impl ClusterManager {
         }
     }
 
+    /// Includes or excludes a member of an active cluster session from broadcast
+    ///
+    /// # Errors
+    /// Returns an error if the cluster has no active session or the
+    /// connection is not a member of it
+    pub fn set_member_active(
+        &mut self,
+        cluster_id: Uuid,
+        connection_id: Uuid,
+        active: bool,
+    ) -> ClusterResult<()> {
+        let session = self
+            .active_sessions
+            .get_mut(&cluster_id)
+            .ok_or(ClusterError::NotFound(cluster_id))?;
+        if session.set_member_active(connection_id, active) {
+            Ok(())
+        } else {
+            Err(ClusterError::SessionError {
+                connection_id,
+                message: "not a member of this cluster session".to_string(),
+            })
+        }
+    }
+
     /// Gets the broadcast targets for a cluster (if in broadcast mode)
     #[must_use]
     pub fn get_broadcast_targets(&self, cluster_id: Uuid) -> Vec<Uuid> {
@@ -685,6 +1015,7 @@ impl ClusterManager {
                 cluster_id,
                 cluster_name: session.cluster_name.clone(),
                 total_sessions: session.session_count(),
+                active_members: session.active_member_count(),
                 connected_count: session.connected_count(),
                 error_count: session.error_count(),
                 broadcast_mode: session.is_broadcast_mode(),
@@ -701,6 +1032,8 @@ pub struct ClusterSessionSummary {
     pub cluster_name: String,
     /// Total number of sessions
     pub total_sessions: usize,
+    /// Number of members receiving broadcast input
+    pub active_members: usize,
     /// Number of connected sessions
     pub connected_count: usize,
     /// Number of sessions with errors
@@ -805,7 +1138,91 @@ mod manager_tests {
         let summary = manager.get_session_summary(cluster_id).unwrap();
         assert_eq!(summary.cluster_name, "Test Cluster");
         assert_eq!(summary.total_sessions, 2);
+        assert_eq!(summary.active_members, 2);
         assert_eq!(summary.connected_count, 0);
         assert!(summary.broadcast_mode);
     }
+
+    #[test]
+    fn test_cluster_manager_create_from_filter() {
+        let tagged = |name: &str, tags: &[&str]| {
+            Connection::new_ssh(name.to_string(), format!("{name}.example.com"), 22)
+                .with_tags(tags.iter().map(ToString::to_string).collect())
+        };
+        let web1 = tagged("web1", &["web"]);
+        let web2 = tagged("web2", &["web", "prod"]);
+        let db = tagged("db", &["db"]);
+        let rdp_web = Connection::new_rdp("web-rdp".to_string(), "win".to_string(), 3389)
+            .with_tags(vec!["web".to_string()]);
+        let mut retired = tagged("web-old", &["web"]);
+        retired.archived = true;
+        // The same connection listed twice must only be added once
+        let connections = vec![
+            web1.clone(),
+            web2.clone(),
+            db,
+            rdp_web,
+            web1.clone(),
+            retired.clone(),
+        ];
+
+        let mut manager = ClusterManager::new();
+        let query = SearchEngine::parse_query("protocol:ssh tag:web").unwrap();
+        let cluster = manager
+            .create_from_filter("Web".to_string(), &query, &connections, &[])
+            .unwrap();
+
+        assert_eq!(cluster.name, "Web");
+        assert_eq!(cluster.connection_count(), 2);
+        assert!(cluster.contains_connection(web1.id));
+        assert!(cluster.contains_connection(web2.id));
+        assert_eq!(manager.cluster_count(), 1);
+
+        let query = SearchEngine::parse_query("protocol:ssh tag:web status:any").unwrap();
+        let cluster = manager
+            .create_from_filter("All web".to_string(), &query, &connections, &[])
+            .unwrap();
+        assert_eq!(cluster.connection_count(), 3);
+        assert!(cluster.contains_connection(retired.id));
+
+        let query = SearchEngine::parse_query("tag:nothing").unwrap();
+        let result = manager.create_from_filter("None".to_string(), &query, &connections, &[]);
+        assert!(matches!(result, Err(ClusterError::EmptyCluster)));
+        assert_eq!(manager.cluster_count(), 2);
+    }
+
+    #[test]
+    fn test_cluster_manager_exclude_member_from_broadcast() {
+        let mut manager = ClusterManager::new();
+        let mut cluster = Cluster::new("Test".to_string());
+        let conn1 = Uuid::new_v4();
+        let conn2 = Uuid::new_v4();
+        cluster.add_connection(conn1);
+        cluster.add_connection(conn2);
+        cluster.broadcast_enabled = true;
+        let cluster_id = cluster.id;
+
+        manager.add_cluster(cluster);
+        manager.start_session(cluster_id).unwrap();
+        manager.update_connection_status(cluster_id, conn1, ClusterSessionStatus::Connected);
+        manager.update_connection_status(cluster_id, conn2, ClusterSessionStatus::Connected);
+
+        manager.set_member_active(cluster_id, conn1, false).unwrap();
+        assert_eq!(manager.get_broadcast_targets(cluster_id), vec![conn2]);
+
+        let session = manager.get_session(cluster_id).unwrap();
+        assert!(!session.get_session_state(conn1).unwrap().broadcast_active);
+        let summary = manager.get_session_summary(cluster_id).unwrap();
+        assert_eq!(summary.active_members, 1);
+        assert_eq!(summary.total_sessions, 2);
+        assert_eq!(summary.connected_count, 2);
+
+        manager.set_member_active(cluster_id, conn1, true).unwrap();
+        assert_eq!(manager.get_broadcast_targets(cluster_id).len(), 2);
+
+        assert!(matches!(
+            manager.set_member_active(cluster_id, Uuid::new_v4(), false),
+            Err(ClusterError::SessionError { .. })
+        ));
+    }
 }
@@ -0,0 +1,327 @@
+//! SSH command runner for cluster members
+//!
+//! Runs a cluster command through a separate, non-interactive `ssh`
+//! process per member, so the member's terminal session is left alone.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use super::{ClusterCommandResult, ClusterCommandRunner, ClusterCommandStatus};
+use crate::connection::{proxy_jump_arg, resolve_jump_chain, unbracketed_host};
+use crate::models::{Connection, ProtocolConfig};
+use crate::protocol::SSH_ERROR_EXIT_STATUS;
+
+/// Default time allowed for establishing each SSH connection
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time a command may run on a member before `ssh` is killed
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a running `ssh` process is checked for exit
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Runs cluster commands with `ssh` in batch mode
+///
+/// Batch mode never prompts, so members need key or agent authentication;
+/// members that would ask for a password fail with the `ssh` error.
+/// Commands still running after the command timeout are killed, so one
+/// hung host cannot hold up the whole cluster run.
+#[derive(Debug, Clone)]
+pub struct SshCommandRunner {
+    /// `ssh` arguments up to and including the destination, per member
+    targets: HashMap<Uuid, Vec<String>>,
+    /// Time each command may run before its `ssh` process is killed
+    command_timeout: Duration,
+}
+
+impl Default for SshCommandRunner {
+    fn default() -> Self {
+        Self {
+            targets: HashMap::new(),
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+        }
+    }
+}
+
+impl SshCommandRunner {
+    /// Prepares the `ssh` arguments for every SSH connection in `connections`
+    ///
+    /// Jump hosts are resolved against the same list. Members that are not
+    /// SSH connections, or whose jump chain cannot be resolved, fail when a
+    /// command is run on them.
+    #[must_use]
+    pub fn new(connections: &[Connection]) -> Self {
+        Self::with_connect_timeout(connections, DEFAULT_CONNECT_TIMEOUT)
+    }
+
+    /// Like [`Self::new`], with a custom connection timeout
+    #[must_use]
+    pub fn with_connect_timeout(connections: &[Connection], timeout: Duration) -> Self {
+        let by_id: HashMap<Uuid, &Connection> = connections.iter().map(|c| (c.id, c)).collect();
+        let lookup = |id: Uuid| by_id.get(&id).copied();
+
+        let targets = connections
+            .iter()
+            .filter_map(|connection| {
+                let chain = resolve_jump_chain(connection.id, lookup).ok()?;
+                let hops: Vec<&Connection> = chain.into_iter().filter_map(lookup).collect();
+                let args = Self::build_args(connection, proxy_jump_arg(&hops), timeout)?;
+                Some((connection.id, args))
+            })
+            .collect();
+        Self {
+            targets,
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+        }
+    }
+
+    /// Sets the time each command may run before it is killed
+    #[must_use]
+    pub const fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// Returns the `ssh` arguments used for a member, without the command
+    #[must_use]
+    pub fn args(&self, connection_id: Uuid) -> Option<&[String]> {
+        self.targets.get(&connection_id).map(Vec::as_slice)
+    }
+
+    /// Builds the arguments for one connection, or `None` if it is not SSH
+    fn build_args(
+        connection: &Connection,
+        jump: Option<String>,
+        timeout: Duration,
+    ) -> Option<Vec<String>> {
+        let ProtocolConfig::Ssh(ref ssh) = connection.protocol_config else {
+            return None;
+        };
+
+        let mut args = vec![
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            "-o".to_string(),
+            format!("ConnectTimeout={}", timeout.as_secs().max(1)),
+        ];
+        if connection.port != 22 {
+            args.push("-p".to_string());
+            args.push(connection.port.to_string());
+        }
+        if let Some(key) = ssh.key_path.as_ref().filter(|p| !p.as_os_str().is_empty()) {
+            args.push("-i".to_string());
+            args.push(key.display().to_string());
+            args.push("-o".to_string());
+            args.push("IdentitiesOnly=yes".to_string());
+        }
+        // Saved jump hosts take precedence over a manual proxy jump
+        if let Some(jump) = jump.or_else(|| ssh.proxy_jump.clone()) {
+            args.push("-J".to_string());
+            args.push(jump);
+        }
+        args.extend(ssh.algorithms.command_args());
+        for (key, value) in &ssh.custom_options {
+            args.push("-o".to_string());
+            args.push(format!("{key}={value}"));
+        }
+
+        let host = unbracketed_host(&connection.host);
+        args.push(
+            connection
+                .username
+                .as_deref()
+                .filter(|u| !u.is_empty())
+                .map_or_else(|| host.to_string(), |user| format!("{user}@{host}")),
+        );
+        Some(args)
+    }
+}
+
+impl ClusterCommandRunner for SshCommandRunner {
+    fn run(&self, connection_id: Uuid, command: &str) -> ClusterCommandResult {
+        let Some(args) = self.targets.get(&connection_id) else {
+            return ClusterCommandResult::failed("Not a reachable SSH connection");
+        };
+
+        let mut ssh = Command::new("ssh");
+        ssh.args(args).arg(command);
+        let output = match run_with_timeout(&mut ssh, self.command_timeout) {
+            Ok(output) => output,
+            Err(e) => return ClusterCommandResult::failed(format!("Failed to run ssh: {e}")),
+        };
+
+        let Some(status) = output.status else {
+            return ClusterCommandResult {
+                output: output.stdout,
+                status: ClusterCommandStatus::Failed(format!(
+                    "Timed out after {} s",
+                    self.command_timeout.as_secs()
+                )),
+            };
+        };
+        match status.code() {
+            // ssh reports its own failures with 255, but so does a remote
+            // command that exits 255; only the latter can have printed output
+            Some(SSH_ERROR_EXIT_STATUS) if output.stdout.is_empty() => {
+                let message = output.stderr.trim();
+                ClusterCommandResult::failed(if message.is_empty() {
+                    format!("ssh exited with status {SSH_ERROR_EXIT_STATUS}")
+                } else {
+                    message.to_string()
+                })
+            }
+            Some(code) => ClusterCommandResult::exited(output.stdout, code),
+            None => ClusterCommandResult {
+                output: output.stdout,
+                status: ClusterCommandStatus::Failed("ssh was terminated by a signal".to_string()),
+            },
+        }
+    }
+}
+
+/// Output of a process run by [`run_with_timeout`]
+struct ProcessOutput {
+    /// Exit status, or `None` if the process was killed after the timeout
+    status: Option<ExitStatus>,
+    stdout: String,
+    stderr: String,
+}
+
+/// Runs `command` with null stdin, killing it if it outlives `timeout`
+///
+/// Output written before the kill is kept.
+fn run_with_timeout(command: &mut Command, timeout: Duration) -> std::io::Result<ProcessOutput> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if Instant::now() >= deadline {
+            // The process may exit on its own between the check and the kill
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    // A killed ssh can leave a jump host process holding stderr open, so
+    // its stderr is not waited for
+    let stderr = if status.is_some() {
+        stderr.join().unwrap_or_default()
+    } else {
+        String::new()
+    };
+    Ok(ProcessOutput {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Reads a child pipe to the end on a separate thread
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SshConfig;
+
+    #[test]
+    fn test_ssh_runner_args() {
+        let mut bastion = Connection::new_ssh("bastion".to_string(), "gw.example".to_string(), 22);
+        bastion.username = Some("ops".to_string());
+
+        let mut config = SshConfig {
+            key_path: Some("/keys/id_ed25519".into()),
+            jump_host_id: Some(bastion.id),
+            ..SshConfig::default()
+        };
+        config
+            .custom_options
+            .insert("ServerAliveInterval".to_string(), "30".to_string());
+        let mut web = Connection::new(
+            "web".to_string(),
+            "fd00::1".to_string(),
+            2222,
+            ProtocolConfig::Ssh(config),
+        );
+        web.username = Some("deploy".to_string());
+        let rdp = Connection::new_rdp("desk".to_string(), "desk.example".to_string(), 3389);
+
+        let runner = SshCommandRunner::new(&[bastion.clone(), web.clone(), rdp.clone()]);
+        assert_eq!(
+            runner.args(web.id).unwrap(),
+            [
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ConnectTimeout=10",
+                "-p",
+                "2222",
+                "-i",
+                "/keys/id_ed25519",
+                "-o",
+                "IdentitiesOnly=yes",
+                "-J",
+                "ops@gw.example",
+                "-o",
+                "ServerAliveInterval=30",
+                "deploy@fd00::1",
+            ]
+        );
+        assert!(runner.args(rdp.id).is_none());
+        assert_eq!(
+            runner.run(rdp.id, "uptime").status,
+            ClusterCommandStatus::Failed("Not a reachable SSH connection".to_string())
+        );
+
+        // A missing jump host leaves the member unreachable
+        let runner = SshCommandRunner::new(&[web.clone()]);
+        assert!(runner.args(web.id).is_none());
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        let output = run_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"]),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert_eq!(output.status.and_then(|s| s.code()), Some(3));
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+
+        let started = Instant::now();
+        let output = run_with_timeout(
+            Command::new("sh").args(["-c", "echo partial; exec sleep 30"]),
+            Duration::from_millis(300),
+        )
+        .unwrap();
+        assert!(output.status.is_none());
+        assert_eq!(output.stdout, "partial\n");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,299 @@
+//! Passphrase encryption for configuration files
+//!
+//! Encrypted files start with a fixed header that records the format version
+//! and the Argon2id parameters, followed by the salt and the AEAD nonce:
+//!
+//! ```text
+//! RCCFGENC | version (1) | memory KiB (u32 LE) | iterations (u32 LE) |
+//! parallelism (u32 LE) | salt (32 bytes) | nonce (12 bytes) | ciphertext + tag
+//! ```
+//!
+//! The key is derived from the passphrase with Argon2id and the content is
+//! sealed with ChaCha20-Poly1305, with everything before the ciphertext as
+//! associated data. A fresh salt and nonce are generated on every save.
+//! Because the parameters are stored per file, they can be raised later
+//! without breaking existing files.
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use secrecy::{ExposeSecret, SecretString};
+
+use crate::error::{ConfigError, ConfigResult};
+
+/// Magic bytes at the start of an encrypted configuration file
+pub const ENCRYPTED_CONFIG_MAGIC: &[u8] = b"RCCFGENC";
+
+/// Version of the encrypted file layout
+const FORMAT_VERSION: u8 = 1;
+
+/// Length of the Argon2id salt
+const SALT_LEN: usize = 32;
+
+/// Length of the header before the salt: magic, version and three `u32`s
+const PARAMS_END: usize = ENCRYPTED_CONFIG_MAGIC.len() + 1 + 3 * 4;
+
+/// Length of everything before the ciphertext
+const HEADER_LEN: usize = PARAMS_END + SALT_LEN + NONCE_LEN;
+
+/// Largest Argon2 memory cost accepted from a file header (1 GiB)
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+
+/// Largest Argon2 iteration count accepted from a file header
+const MAX_ITERATIONS: u32 = 64;
+
+/// Largest Argon2 lane count accepted from a file header
+const MAX_PARALLELISM: u32 = 64;
+
+/// Argon2id cost parameters used to derive the file key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes over the memory
+    pub iterations: u32,
+    /// Number of lanes
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// Cheap parameters for tests and throwaway files
+    ///
+    /// These offer little resistance to brute force and must not be used
+    /// for real configuration files.
+    #[must_use]
+    pub const fn fast() -> Self {
+        Self {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    /// Rejects parameters outside the accepted range
+    fn validate(self) -> ConfigResult<Self> {
+        if self.memory_kib > MAX_MEMORY_KIB
+            || self.iterations > MAX_ITERATIONS
+            || self.parallelism > MAX_PARALLELISM
+        {
+            return Err(ConfigError::Encryption(format!(
+                "Unsupported key derivation parameters: m={} t={} p={}",
+                self.memory_kib, self.iterations, self.parallelism
+            )));
+        }
+        Ok(self)
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: 65536,
+            iterations: 3,
+            parallelism: 4,
+        }
+    }
+}
+
+/// Returns true if the data starts with the encrypted file header
+#[must_use]
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_CONFIG_MAGIC)
+}
+
+/// Encrypts file content with a passphrase
+///
+/// The key derivation parameters are written into the header, so
+/// [`decrypt`] needs only the passphrase.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Encryption` if the parameters are out of range, or
+/// random generation, key derivation, or sealing fails.
+pub fn encrypt(
+    plaintext: &[u8],
+    passphrase: &SecretString,
+    params: &KdfParams,
+) -> ConfigResult<Vec<u8>> {
+    let params = params.validate()?;
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .map_err(|_| ConfigError::Encryption("Failed to generate salt".to_string()))?;
+    rng.fill(&mut nonce_bytes)
+        .map_err(|_| ConfigError::Encryption("Failed to generate nonce".to_string()))?;
+
+    let mut output = Vec::with_capacity(HEADER_LEN + plaintext.len() + CHACHA20_POLY1305.tag_len());
+    output.extend_from_slice(ENCRYPTED_CONFIG_MAGIC);
+    output.push(FORMAT_VERSION);
+    output.extend_from_slice(&params.memory_kib.to_le_bytes());
+    output.extend_from_slice(&params.iterations.to_le_bytes());
+    output.extend_from_slice(&params.parallelism.to_le_bytes());
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce_bytes);
+
+    let key = sealing_key(passphrase, &salt, params)?;
+    let mut ciphertext = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce_bytes),
+        Aad::from(&output[..HEADER_LEN]),
+        &mut ciphertext,
+    )
+    .map_err(|_| ConfigError::Encryption("Encryption failed".to_string()))?;
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Decrypts file content written by [`encrypt`]
+///
+/// # Errors
+///
+/// Returns `ConfigError::InvalidPassphrase` if the passphrase is wrong or the
+/// content was modified, and `ConfigError::Encryption` if the header is
+/// malformed or names an unsupported version or parameters.
+pub fn decrypt(data: &[u8], passphrase: &SecretString) -> ConfigResult<Vec<u8>> {
+    if !is_encrypted(data) {
+        return Err(ConfigError::Encryption(
+            "Missing encrypted file header".to_string(),
+        ));
+    }
+    if data.len() < HEADER_LEN + CHACHA20_POLY1305.tag_len() {
+        return Err(ConfigError::Encryption(
+            "Encrypted file is truncated".to_string(),
+        ));
+    }
+
+    let version = data[ENCRYPTED_CONFIG_MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(ConfigError::Encryption(format!(
+            "Unsupported encrypted file version {version}"
+        )));
+    }
+    let read_u32 = |offset: usize| {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&data[offset..offset + 4]);
+        u32::from_le_bytes(bytes)
+    };
+    let params_start = ENCRYPTED_CONFIG_MAGIC.len() + 1;
+    let params = KdfParams {
+        memory_kib: read_u32(params_start),
+        iterations: read_u32(params_start + 4),
+        parallelism: read_u32(params_start + 8),
+    }
+    .validate()?;
+
+    let salt = &data[PARAMS_END..PARAMS_END + SALT_LEN];
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    nonce_bytes.copy_from_slice(&data[PARAMS_END + SALT_LEN..HEADER_LEN]);
+
+    let key = sealing_key(passphrase, salt, params)?;
+    let mut plaintext = data[HEADER_LEN..].to_vec();
+    let len = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce_bytes),
+            Aad::from(&data[..HEADER_LEN]),
+            &mut plaintext,
+        )
+        .map_err(|_| ConfigError::InvalidPassphrase)?
+        .len();
+    plaintext.truncate(len);
+    Ok(plaintext)
+}
+
+/// Derives the AEAD key from a passphrase using Argon2id
+fn sealing_key(
+    passphrase: &SecretString,
+    salt: &[u8],
+    params: KdfParams,
+) -> ConfigResult<LessSafeKey> {
+    use argon2::{Algorithm, Argon2, Params, Version};
+
+    let params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(32),
+    )
+    .map_err(|e| ConfigError::Encryption(format!("Invalid Argon2 params: {e}")))?;
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.expose_secret().as_bytes(), salt, &mut key)
+        .map_err(|e| ConfigError::Encryption(format!("Key derivation failed: {e}")))?;
+
+    UnboundKey::new(&CHACHA20_POLY1305, &key)
+        .map(LessSafeKey::new)
+        .map_err(|_| ConfigError::Encryption("Failed to create key".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let passphrase = SecretString::from("correct horse");
+        let data = encrypt(b"[[connections]]", &passphrase, &KdfParams::default()).unwrap();
+
+        assert!(is_encrypted(&data));
+        assert!(!data.windows(13).any(|w| w == b"[[connections"));
+        assert_eq!(decrypt(&data, &passphrase).unwrap(), b"[[connections]]");
+    }
+
+    #[test]
+    fn test_params_are_read_from_header() {
+        let passphrase = SecretString::from("correct horse");
+        let params = KdfParams {
+            memory_kib: 256,
+            iterations: 2,
+            parallelism: 2,
+        };
+        let data = encrypt(b"secret", &passphrase, &params).unwrap();
+        assert_eq!(decrypt(&data, &passphrase).unwrap(), b"secret");
+
+        // The parameters are authenticated
+        let mut tampered = data.clone();
+        tampered[ENCRYPTED_CONFIG_MAGIC.len() + 5] = 3;
+        assert!(matches!(
+            decrypt(&tampered, &passphrase),
+            Err(ConfigError::InvalidPassphrase)
+        ));
+
+        // Oversized costs are rejected before deriving a key
+        let mut huge = data;
+        huge[ENCRYPTED_CONFIG_MAGIC.len() + 1..ENCRYPTED_CONFIG_MAGIC.len() + 5]
+            .copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            decrypt(&huge, &passphrase),
+            Err(ConfigError::Encryption(_))
+        ));
+    }
+
+    #[test]
+    fn test_wrong_passphrase_and_tampering() {
+        let passphrase = SecretString::from("correct horse");
+        let mut data = encrypt(b"secret", &passphrase, &KdfParams::fast()).unwrap();
+
+        assert!(matches!(
+            decrypt(&data, &SecretString::from("wrong")),
+            Err(ConfigError::InvalidPassphrase)
+        ));
+
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        assert!(matches!(
+            decrypt(&data, &passphrase),
+            Err(ConfigError::InvalidPassphrase)
+        ));
+        assert!(matches!(
+            decrypt(ENCRYPTED_CONFIG_MAGIC, &passphrase),
+            Err(ConfigError::Encryption(_))
+        ));
+
+        data[ENCRYPTED_CONFIG_MAGIC.len()] = FORMAT_VERSION + 1;
+        assert!(matches!(
+            decrypt(&data, &passphrase),
+            Err(ConfigError::Encryption(_))
+        ));
+    }
+}
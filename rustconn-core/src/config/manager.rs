@@ -4,14 +4,22 @@
 //! configuration files for connections, groups, snippets, and application settings.
 
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use secrecy::SecretString;
 
 use crate::cluster::Cluster;
 use crate::error::{ConfigError, ConfigResult};
 use crate::models::{
     Connection, ConnectionGroup, ConnectionHistoryEntry, ConnectionTemplate, Snippet,
 };
+use crate::split::NamedLayout;
 
+use super::encryption::{self, KdfParams};
+use super::migrations::{self, Migration, CURRENT_SCHEMA_VERSION};
+use super::passphrase::{ChainPassphraseProvider, PassphraseProvider};
 use super::settings::AppSettings;
 
 /// File names for configuration files
@@ -21,11 +29,17 @@ const SNIPPETS_FILE: &str = "snippets.toml";
 const CLUSTERS_FILE: &str = "clusters.toml";
 const TEMPLATES_FILE: &str = "templates.toml";
 const HISTORY_FILE: &str = "history.toml";
+const LAYOUTS_FILE: &str = "layouts.toml";
 const CONFIG_FILE: &str = "config.toml";
 
+/// Default number of rotating connection backups
+pub const DEFAULT_BACKUP_COUNT: usize = 3;
+
 /// Wrapper for serializing a list of connections
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 struct ConnectionsFile {
+    #[serde(default = "current_schema_version")]
+    schema_version: u32,
     #[serde(default)]
     connections: Vec<Connection>,
 }
@@ -33,10 +47,16 @@ struct ConnectionsFile {
 /// Wrapper for serializing a list of groups
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 struct GroupsFile {
+    #[serde(default = "current_schema_version")]
+    schema_version: u32,
     #[serde(default)]
     groups: Vec<ConnectionGroup>,
 }
 
+const fn current_schema_version() -> u32 {
+    CURRENT_SCHEMA_VERSION
+}
+
 /// Wrapper for serializing a list of snippets
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 struct SnippetsFile {
@@ -65,6 +85,22 @@ struct HistoryFile {
     entries: Vec<ConnectionHistoryEntry>,
 }
 
+/// Wrapper for serializing saved split layouts
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct LayoutsFile {
+    #[serde(default)]
+    layouts: Vec<NamedLayout>,
+}
+
+/// Encryption state shared between clones of a `ConfigManager`
+#[derive(Debug, Default)]
+struct EncryptionCache {
+    /// The `encrypt_connections` setting, read once and updated on save
+    enabled: Option<bool>,
+    /// Passphrase obtained from the provider
+    passphrase: Option<SecretString>,
+}
+
 /// Configuration manager for `RustConn`
 ///
 /// Handles loading and saving configuration files in TOML format.
@@ -73,12 +109,25 @@ struct HistoryFile {
 pub struct ConfigManager {
     /// Base directory for configuration files
     config_dir: PathBuf,
+    /// Number of rotating `.bak` copies kept for the connections file
+    backup_count: usize,
+    /// Passphrase for the encrypted connections file (runtime only)
+    passphrase: Option<SecretString>,
+    /// Source of the passphrase when none was set explicitly
+    passphrase_provider: Option<Arc<dyn PassphraseProvider>>,
+    /// Argon2id parameters for newly encrypted files
+    kdf_params: KdfParams,
+    /// Cached encryption setting and provider passphrase
+    encryption: Arc<Mutex<EncryptionCache>>,
 }
 
 impl ConfigManager {
     /// Creates a new `ConfigManager` with the default configuration directory
     ///
-    /// The default directory is `~/.config/rustconn/`
+    /// The default directory is `~/.config/rustconn/`. The passphrase for
+    /// an encrypted connections file is taken from the
+    /// `RUSTCONN_CONFIG_PASSPHRASE` environment variable or the desktop
+    /// keyring (see [`ChainPassphraseProvider::system`]).
     ///
     /// # Errors
     ///
@@ -87,15 +136,74 @@ impl ConfigManager {
         let config_dir = dirs::config_dir()
             .ok_or_else(|| ConfigError::NotFound(PathBuf::from("~/.config")))?
             .join("rustconn");
-        Ok(Self { config_dir })
+        Ok(Self::with_config_dir(config_dir)
+            .with_passphrase_provider(ChainPassphraseProvider::system()))
     }
 
     /// Creates a new `ConfigManager` with a custom configuration directory
     ///
-    /// This is useful for testing or non-standard configurations.
+    /// This is useful for testing or non-standard configurations. No
+    /// passphrase provider is installed.
+    #[must_use]
+    pub fn with_config_dir(config_dir: PathBuf) -> Self {
+        Self {
+            config_dir,
+            backup_count: DEFAULT_BACKUP_COUNT,
+            passphrase: None,
+            passphrase_provider: None,
+            kdf_params: KdfParams::default(),
+            encryption: Arc::default(),
+        }
+    }
+
+    /// Sets the passphrase used for the encrypted connections file
+    ///
+    /// An explicit passphrase takes precedence over the provider.
+    #[must_use]
+    pub fn with_passphrase(mut self, passphrase: SecretString) -> Self {
+        self.passphrase = Some(passphrase);
+        self
+    }
+
+    /// Sets or clears the passphrase used for the encrypted connections file
+    pub fn set_passphrase(&mut self, passphrase: Option<SecretString>) {
+        self.passphrase = passphrase;
+    }
+
+    /// Sets the source asked for the passphrase when none was set explicitly
+    #[must_use]
+    pub fn with_passphrase_provider(mut self, provider: impl PassphraseProvider + 'static) -> Self {
+        self.passphrase_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Sets the Argon2id parameters used when encrypting the connections file
+    ///
+    /// Files record their own parameters, so this only affects new writes.
+    #[must_use]
+    pub const fn with_kdf_params(mut self, params: KdfParams) -> Self {
+        self.kdf_params = params;
+        self
+    }
+
+    /// Returns true if the connections file on disk is encrypted
     #[must_use]
-    pub const fn with_config_dir(config_dir: PathBuf) -> Self {
-        Self { config_dir }
+    pub fn connections_encrypted(&self) -> bool {
+        fs::read(self.config_dir.join(CONNECTIONS_FILE))
+            .is_ok_and(|data| encryption::is_encrypted(&data))
+    }
+
+    /// Sets the number of connection backups to keep (0 disables backups)
+    #[must_use]
+    pub const fn with_backup_count(mut self, backup_count: usize) -> Self {
+        self.backup_count = backup_count;
+        self
+    }
+
+    /// Returns the number of connection backups kept
+    #[must_use]
+    pub const fn backup_count(&self) -> usize {
+        self.backup_count
     }
 
     /// Returns the configuration directory path
@@ -147,44 +255,234 @@ impl ConfigManager {
 
     /// Loads connections from the configuration file
     ///
-    /// Returns an empty vector if the file doesn't exist.
+    /// Returns an empty vector if the file doesn't exist. Encrypted files are
+    /// decrypted with the configured passphrase; plain files load as before.
+    /// Files written with an older schema are migrated and saved back in the
+    /// current schema. If the file cannot be parsed, the newest backup that
+    /// parses is used instead and a warning is logged.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file exists but cannot be parsed.
+    /// Returns `ConfigError::PassphraseRequired` or
+    /// `ConfigError::InvalidPassphrase` if the file is encrypted and the
+    /// passphrase is missing or wrong, `ConfigError::NewerSchema` if it was
+    /// written by a newer release, or an error if the file exists but
+    /// neither it nor any backup can be parsed.
     pub fn load_connections(&self) -> ConfigResult<Vec<Connection>> {
         let path = self.config_dir.join(CONNECTIONS_FILE);
-        Self::load_toml_file::<ConnectionsFile>(&path).map(|f| f.connections)
+        let error = match self
+            .load_migrated_file::<ConnectionsFile>(&path, migrations::CONNECTION_MIGRATIONS)
+        {
+            Ok((file, migrated)) => {
+                if migrated {
+                    if let Err(e) = self.save_connections(&file.connections) {
+                        tracing::warn!(error = %e, "Failed to save migrated connections");
+                    }
+                }
+                return Ok(file.connections);
+            }
+            Err(
+                e @ (ConfigError::PassphraseRequired
+                | ConfigError::InvalidPassphrase
+                | ConfigError::NewerSchema { .. }),
+            ) => {
+                return Err(e);
+            }
+            Err(e) => e,
+        };
+
+        for backup in self.connection_backups() {
+            if let Ok((file, _)) = self
+                .load_migrated_file::<ConnectionsFile>(&backup, migrations::CONNECTION_MIGRATIONS)
+            {
+                tracing::warn!(
+                    error = %error,
+                    backup = %backup.display(),
+                    "Connections file is corrupt, loaded backup instead"
+                );
+                return Ok(file.connections);
+            }
+        }
+        Err(error)
     }
 
     /// Saves connections to the configuration file
     ///
+    /// The previous file is rotated into the `.bak` copies and the new
+    /// content is written atomically, so a crash mid-save leaves either the
+    /// old or the new file in place. When `encrypt_connections` is enabled in
+    /// the settings, the file is encrypted with the configured passphrase and
+    /// unencrypted backups are removed.
+    ///
     /// Creates the configuration directory if it doesn't exist.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written.
+    /// Returns `ConfigError::PassphraseRequired` if encryption is enabled and
+    /// no passphrase is set, `ConfigError::NewerSchema` if the existing file
+    /// was written by a newer release, or an error if the file cannot be
+    /// written.
     pub fn save_connections(&self, connections: &[Connection]) -> ConfigResult<()> {
         self.ensure_config_dir()?;
         let path = self.config_dir.join(CONNECTIONS_FILE);
+        self.ensure_schema_not_newer(&path)?;
         let file = ConnectionsFile {
+            schema_version: CURRENT_SCHEMA_VERSION,
             connections: connections.to_vec(),
         };
-        Self::save_toml_file(&path, &file)
+        self.rotate_backups(&path)?;
+        self.write_connections_file(&path, &file)
+    }
+
+    /// Writes the connections file, encrypting it if enabled in the settings
+    fn write_connections_file(&self, path: &Path, file: &ConnectionsFile) -> ConfigResult<()> {
+        if !self.encryption_enabled()? {
+            return Self::save_toml_file(path, file);
+        }
+
+        let content = toml::to_string_pretty(file)
+            .map_err(|e| ConfigError::Serialize(format!("Failed to serialize: {e}")))?;
+        let data = self.use_passphrase(|passphrase| {
+            encryption::encrypt(content.as_bytes(), passphrase, &self.kdf_params)
+        })?;
+        Self::write_atomic(path, &data)?;
+
+        for backup in self.connection_backups() {
+            if !fs::read(&backup).is_ok_and(|data| encryption::is_encrypted(&data)) {
+                fs::remove_file(&backup).map_err(|e| {
+                    ConfigError::Write(format!("Failed to remove {}: {e}", backup.display()))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the cached `encrypt_connections` setting
+    fn encryption_enabled(&self) -> ConfigResult<bool> {
+        let cached = self.lock_encryption().enabled;
+        if let Some(enabled) = cached {
+            return Ok(enabled);
+        }
+        let enabled = self.load_settings()?.connection.encrypt_connections;
+        self.lock_encryption().enabled = Some(enabled);
+        Ok(enabled)
+    }
+
+    /// Runs `f` with the passphrase, asking the provider the first time
+    ///
+    /// A provider passphrase that turns out to be wrong is forgotten, so the
+    /// next call asks again.
+    fn use_passphrase<R>(
+        &self,
+        f: impl FnOnce(&SecretString) -> ConfigResult<R>,
+    ) -> ConfigResult<R> {
+        if let Some(passphrase) = &self.passphrase {
+            return f(passphrase);
+        }
+        let mut cache = self.lock_encryption();
+        if cache.passphrase.is_none() {
+            if let Some(provider) = &self.passphrase_provider {
+                cache.passphrase = provider.passphrase()?;
+            }
+        }
+        let passphrase = cache
+            .passphrase
+            .as_ref()
+            .ok_or(ConfigError::PassphraseRequired)?;
+        let result = f(passphrase);
+        if matches!(result, Err(ConfigError::InvalidPassphrase)) {
+            cache.passphrase = None;
+        }
+        result
+    }
+
+    fn lock_encryption(&self) -> MutexGuard<'_, EncryptionCache> {
+        self.encryption
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the existing connection backups, newest first
+    #[must_use]
+    pub fn connection_backups(&self) -> Vec<PathBuf> {
+        let path = self.config_dir.join(CONNECTIONS_FILE);
+        (1..=self.backup_count)
+            .map(|index| Self::backup_path(&path, index))
+            .filter(|backup| backup.exists())
+            .collect()
+    }
+
+    /// Restores the connections file from a backup
+    ///
+    /// `index` 1 is the newest backup. The current file is replaced without
+    /// being rotated, so the remaining backups are kept as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::NotFound` if the backup does not exist, or an
+    /// error if it cannot be parsed or written.
+    pub fn restore_backup(&self, index: usize) -> ConfigResult<Vec<Connection>> {
+        let path = self.config_dir.join(CONNECTIONS_FILE);
+        let backup = Self::backup_path(&path, index);
+        if index == 0 || !backup.exists() {
+            return Err(ConfigError::NotFound(backup));
+        }
+
+        let (file, _) =
+            self.load_migrated_file::<ConnectionsFile>(&backup, migrations::CONNECTION_MIGRATIONS)?;
+        self.write_connections_file(&path, &file)?;
+        Ok(file.connections)
+    }
+
+    /// Returns the path of the backup with the given index
+    fn backup_path(path: &Path, index: usize) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{index}.bak"));
+        path.with_file_name(name)
+    }
+
+    /// Shifts existing backups by one and copies the current file to backup 1
+    fn rotate_backups(&self, path: &Path) -> ConfigResult<()> {
+        if self.backup_count == 0 || !path.exists() {
+            return Ok(());
+        }
+
+        let rotate_err = |e: std::io::Error| {
+            ConfigError::Write(format!(
+                "Failed to rotate backups of {}: {e}",
+                path.display()
+            ))
+        };
+        for index in (1..self.backup_count).rev() {
+            let from = Self::backup_path(path, index);
+            if from.exists() {
+                fs::rename(&from, Self::backup_path(path, index + 1)).map_err(rotate_err)?;
+            }
+        }
+        fs::copy(path, Self::backup_path(path, 1)).map_err(rotate_err)?;
+        Ok(())
     }
 
     // ========== Groups ==========
 
     /// Loads connection groups from the configuration file
     ///
-    /// Returns an empty vector if the file doesn't exist.
+    /// Returns an empty vector if the file doesn't exist. Files written with
+    /// an older schema are migrated and saved back in the current schema.
     ///
     /// # Errors
     ///
     /// Returns an error if the file exists but cannot be parsed.
     pub fn load_groups(&self) -> ConfigResult<Vec<ConnectionGroup>> {
         let path = self.config_dir.join(GROUPS_FILE);
-        Self::load_toml_file::<GroupsFile>(&path).map(|f| f.groups)
+        let (file, migrated) =
+            self.load_migrated_file::<GroupsFile>(&path, migrations::GROUP_MIGRATIONS)?;
+        if migrated {
+            if let Err(e) = self.save_groups(&file.groups) {
+                tracing::warn!(error = %e, "Failed to save migrated groups");
+            }
+        }
+        Ok(file.groups)
     }
 
     /// Saves connection groups to the configuration file
@@ -193,11 +491,14 @@ impl ConfigManager {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written.
+    /// Returns `ConfigError::NewerSchema` if the existing file was written by
+    /// a newer release, or an error if the file cannot be written.
     pub fn save_groups(&self, groups: &[ConnectionGroup]) -> ConfigResult<()> {
         self.ensure_config_dir()?;
         let path = self.config_dir.join(GROUPS_FILE);
+        self.ensure_schema_not_newer(&path)?;
         let file = GroupsFile {
+            schema_version: CURRENT_SCHEMA_VERSION,
             groups: groups.to_vec(),
         };
         Self::save_toml_file(&path, &file)
@@ -323,6 +624,36 @@ impl ConfigManager {
         Self::save_toml_file(&path, &file)
     }
 
+    // ========== Split Layouts ==========
+
+    /// Loads saved split layouts from the configuration file
+    ///
+    /// Returns an empty list if the file doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be parsed.
+    pub fn load_layouts(&self) -> ConfigResult<Vec<NamedLayout>> {
+        let path = self.config_dir.join(LAYOUTS_FILE);
+        Self::load_toml_file::<LayoutsFile>(&path).map(|f| f.layouts)
+    }
+
+    /// Saves split layouts to the configuration file
+    ///
+    /// Creates the configuration directory if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_layouts(&self, layouts: &[NamedLayout]) -> ConfigResult<()> {
+        self.ensure_config_dir()?;
+        let path = self.config_dir.join(LAYOUTS_FILE);
+        let file = LayoutsFile {
+            layouts: layouts.to_vec(),
+        };
+        Self::save_toml_file(&path, &file)
+    }
+
     // ========== Application Settings ==========
 
     /// Loads application settings from the configuration file
@@ -350,7 +681,9 @@ impl ConfigManager {
     pub fn save_settings(&self, settings: &AppSettings) -> ConfigResult<()> {
         self.ensure_config_dir()?;
         let path = self.config_dir.join(CONFIG_FILE);
-        Self::save_toml_file(&path, settings)
+        Self::save_toml_file(&path, settings)?;
+        self.lock_encryption().enabled = Some(settings.connection.encrypt_connections);
+        Ok(())
     }
 
     // ========== Global Variables ==========
@@ -397,6 +730,64 @@ impl ConfigManager {
         Self::parse_toml(&content, path)
     }
 
+    /// Loads a versioned TOML file, migrating it to the current schema
+    ///
+    /// Returns the default value if the file doesn't exist, and whether any
+    /// migration ran.
+    fn load_migrated_file<T>(
+        &self,
+        path: &Path,
+        migrations: &[Migration],
+    ) -> ConfigResult<(T, bool)>
+    where
+        T: serde::de::DeserializeOwned + Default,
+    {
+        if !path.exists() {
+            return Ok((T::default(), false));
+        }
+
+        let content = self.read_config_file(path)?;
+        let mut table: toml::Table = Self::parse_toml(&content, path)?;
+        let migrated = migrations::migrate(&mut table, migrations, path)?;
+        let data = T::deserialize(table).map_err(|e| {
+            ConfigError::Deserialize(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        Ok((data, migrated))
+    }
+
+    /// Refuses to overwrite a versioned file written by a newer release
+    ///
+    /// Missing or unreadable files are not checked; saving replaces them as
+    /// before.
+    fn ensure_schema_not_newer(&self, path: &Path) -> ConfigResult<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let table = match self.read_config_file(path) {
+            Ok(content) => toml::from_str::<toml::Table>(&content).ok(),
+            Err(_) => None,
+        };
+        if let Some(table) = table {
+            if let Err(e @ ConfigError::NewerSchema { .. }) =
+                migrations::supported_schema_version(&table, path)
+            {
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a configuration file, decrypting it if it is encrypted
+    fn read_config_file(&self, path: &Path) -> ConfigResult<String> {
+        let mut data = fs::read(path)
+            .map_err(|e| ConfigError::Parse(format!("Failed to read {}: {}", path.display(), e)))?;
+        if encryption::is_encrypted(&data) {
+            data = self.use_passphrase(|passphrase| encryption::decrypt(&data, passphrase))?;
+        }
+        String::from_utf8(data)
+            .map_err(|e| ConfigError::Parse(format!("Failed to read {}: {}", path.display(), e)))
+    }
+
     /// Parses TOML content with validation
     fn parse_toml<T>(content: &str, path: &Path) -> ConfigResult<T>
     where
@@ -408,15 +799,39 @@ impl ConfigManager {
     }
 
     /// Saves data to a TOML file
+    ///
+    /// The content is written to a temporary file next to the target and
+    /// renamed over it, so the target is never left half-written.
     fn save_toml_file<T>(path: &Path, data: &T) -> ConfigResult<()>
     where
         T: serde::Serialize,
     {
         let content = toml::to_string_pretty(data)
             .map_err(|e| ConfigError::Serialize(format!("Failed to serialize: {e}")))?;
+        Self::write_atomic(path, content.as_bytes())
+    }
 
-        fs::write(path, content)
-            .map_err(|e| ConfigError::Write(format!("Failed to write {}: {}", path.display(), e)))
+    /// Writes a file through a temporary file renamed over the target
+    fn write_atomic(path: &Path, content: &[u8]) -> ConfigResult<()> {
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let result = fs::File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(content)?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&tmp_path, path));
+        if let Err(e) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(ConfigError::Write(format!(
+                "Failed to write {}: {}",
+                path.display(),
+                e
+            )));
+        }
+        Ok(())
     }
 
     // ========== Validation ==========
@@ -440,11 +855,13 @@ impl ConfigManager {
         // (the target is defined in the provider config)
         let is_zerotrust = matches!(connection.protocol_config, ProtocolConfig::ZeroTrust(_));
 
-        if !is_zerotrust && connection.host.trim().is_empty() {
-            return Err(ConfigError::Validation {
-                field: "host".to_string(),
-                reason: "Host cannot be empty".to_string(),
-            });
+        if !is_zerotrust {
+            crate::connection::validate_host(&connection.host).map_err(|e| {
+                ConfigError::Validation {
+                    field: "host".to_string(),
+                    reason: e.to_string(),
+                }
+            })?;
         }
 
         if !is_zerotrust && connection.port == 0 {
@@ -454,6 +871,24 @@ impl ConfigManager {
             });
         }
 
+        if let ProtocolConfig::Ssh(ssh) = &connection.protocol_config {
+            if let Some(name) = ssh.invalid_environment_name() {
+                return Err(ConfigError::Validation {
+                    field: "environment".to_string(),
+                    reason: format!("'{name}' is not a valid environment variable name"),
+                });
+            }
+        }
+
+        if let Some(color) = &connection.color {
+            if !is_hex_color(color) {
+                return Err(ConfigError::Validation {
+                    field: "color".to_string(),
+                    reason: format!("'{color}' is not a hex color (#RGB or #RRGGBB)"),
+                });
+            }
+        }
+
         Ok(())
     }
 
@@ -579,6 +1014,13 @@ impl ConfigManager {
     }
 }
 
+/// Returns true for `#RGB` and `#RRGGBB` hex colors
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -587,7 +1029,8 @@ mod tests {
 
     fn create_test_manager() -> (ConfigManager, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let manager = ConfigManager::with_config_dir(temp_dir.path().to_path_buf());
+        let manager = ConfigManager::with_config_dir(temp_dir.path().to_path_buf())
+            .with_kdf_params(KdfParams::fast());
         (manager, temp_dir)
     }
 
@@ -627,6 +1070,109 @@ mod tests {
         assert_eq!(loaded[0].port, conn.port);
     }
 
+    #[test]
+    fn test_save_connections_rotates_backups() {
+        let (manager, temp) = create_test_manager();
+        let manager = manager.with_backup_count(2);
+
+        for name in ["one", "two", "three", "four"] {
+            let conn = Connection::new_ssh(name.to_string(), "example.com".to_string(), 22);
+            manager.save_connections(&[conn]).unwrap();
+        }
+
+        let backups = manager.connection_backups();
+        assert_eq!(backups.len(), 2);
+        assert!(!temp.path().join("connections.toml.3.bak").exists());
+        assert!(!temp.path().join("connections.toml.tmp").exists());
+
+        let names = |path: &Path| {
+            ConfigManager::load_toml_file::<ConnectionsFile>(path)
+                .unwrap()
+                .connections[0]
+                .name
+                .clone()
+        };
+        assert_eq!(names(&backups[0]), "three");
+        assert_eq!(names(&backups[1]), "two");
+    }
+
+    #[test]
+    fn test_corrupt_connections_fall_back_to_backup() {
+        let (manager, temp) = create_test_manager();
+        for name in ["old", "new"] {
+            let conn = Connection::new_ssh(name.to_string(), "example.com".to_string(), 22);
+            manager.save_connections(&[conn]).unwrap();
+        }
+
+        fs::write(
+            temp.path().join(CONNECTIONS_FILE),
+            "[[connections]\nname = ",
+        )
+        .unwrap();
+        assert_eq!(manager.load_connections().unwrap()[0].name, "old");
+
+        // Restoring repairs the primary file
+        assert!(matches!(
+            manager.restore_backup(2),
+            Err(ConfigError::NotFound(_))
+        ));
+        assert_eq!(manager.restore_backup(1).unwrap()[0].name, "old");
+        let primary =
+            ConfigManager::load_toml_file::<ConnectionsFile>(&temp.path().join(CONNECTIONS_FILE));
+        assert_eq!(primary.unwrap().connections[0].name, "old");
+    }
+
+    #[derive(Debug)]
+    struct FixedPassphrase(&'static str);
+
+    impl PassphraseProvider for FixedPassphrase {
+        fn passphrase(&self) -> ConfigResult<Option<SecretString>> {
+            Ok(Some(SecretString::from(self.0)))
+        }
+    }
+
+    #[test]
+    fn test_encrypted_connections_store() {
+        let (manager, temp) = create_test_manager();
+        let conn = Connection::new_ssh("web".to_string(), "example.com".to_string(), 22);
+
+        // Plain files written before encryption was enabled still load
+        manager
+            .save_connections(std::slice::from_ref(&conn))
+            .unwrap();
+        let manager = manager.with_passphrase(SecretString::from("hunter2"));
+        assert_eq!(manager.load_connections().unwrap(), vec![conn.clone()]);
+
+        let mut settings = AppSettings::default();
+        settings.connection.encrypt_connections = true;
+        manager.save_settings(&settings).unwrap();
+        manager
+            .save_connections(std::slice::from_ref(&conn))
+            .unwrap();
+
+        let raw = fs::read(temp.path().join(CONNECTIONS_FILE)).unwrap();
+        assert!(encryption::is_encrypted(&raw));
+        assert!(manager.connections_encrypted());
+        // The plain copy rotated into the backups is removed
+        assert!(manager.connection_backups().is_empty());
+        assert_eq!(manager.load_connections().unwrap(), vec![conn.clone()]);
+
+        let provided = ConfigManager::with_config_dir(temp.path().to_path_buf())
+            .with_passphrase_provider(FixedPassphrase("hunter2"));
+        assert_eq!(provided.load_connections().unwrap(), vec![conn]);
+
+        let locked = ConfigManager::with_config_dir(temp.path().to_path_buf());
+        assert!(matches!(
+            locked.load_connections(),
+            Err(ConfigError::PassphraseRequired)
+        ));
+        let wrong = locked.with_passphrase(SecretString::from("wrong"));
+        assert!(matches!(
+            wrong.load_connections(),
+            Err(ConfigError::InvalidPassphrase)
+        ));
+    }
+
     #[test]
     fn test_save_and_load_groups() {
         let (manager, _temp) = create_test_manager();
@@ -697,6 +1243,57 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_connection_host() {
+        for host in ["web-01.example.com", "10.0.0.5", "::1", "[2001:db8::1]"] {
+            let conn = Connection::new_ssh("Test".to_string(), host.to_string(), 22);
+            assert!(ConfigManager::validate_connection(&conn).is_ok(), "{host}");
+        }
+        for host in ["http://foo", "foo/bar", "foo:22", " ", "300.1.1.1"] {
+            let conn = Connection::new_ssh("Test".to_string(), host.to_string(), 22);
+            assert!(matches!(
+                ConfigManager::validate_connection(&conn),
+                Err(ConfigError::Validation { field, .. }) if field == "host"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_connection_environment() {
+        let mut conn = Connection::new_ssh("Test".to_string(), "example.com".to_string(), 22);
+        let ProtocolConfig::Ssh(ssh) = &mut conn.protocol_config else {
+            unreachable!("new_ssh creates an SSH config");
+        };
+        ssh.environment = vec![("LANG".to_string(), "C".to_string())];
+        assert!(ConfigManager::validate_connection(&conn).is_ok());
+
+        if let ProtocolConfig::Ssh(ssh) = &mut conn.protocol_config {
+            ssh.environment
+                .push(("MY-VAR".to_string(), "1".to_string()));
+        }
+        assert!(matches!(
+            ConfigManager::validate_connection(&conn),
+            Err(ConfigError::Validation { field, .. }) if field == "environment"
+        ));
+    }
+
+    #[test]
+    fn test_validate_connection_color() {
+        let conn = Connection::new_ssh("Test".to_string(), "example.com".to_string(), 22);
+
+        for valid in ["#3584e4", "#FFF"] {
+            let conn = conn.clone().with_color(valid);
+            assert!(ConfigManager::validate_connection(&conn).is_ok());
+        }
+        for invalid in ["3584e4", "#3584e", "#zzzzzz", "red"] {
+            let conn = conn.clone().with_color(invalid);
+            assert!(matches!(
+                ConfigManager::validate_connection(&conn),
+                Err(ConfigError::Validation { field, .. }) if field == "color"
+            ));
+        }
+    }
+
     #[test]
     fn test_validate_group_empty_name() {
         let mut group = ConnectionGroup::new("Test".to_string());
@@ -739,6 +1336,31 @@ mod tests {
         assert!(loaded[0].broadcast_enabled);
     }
 
+    #[test]
+    fn test_save_and_load_layouts() {
+        use crate::split::{LayoutNode, SplitDirection};
+        use uuid::Uuid;
+
+        let (manager, _temp) = create_test_manager();
+
+        let layout = NamedLayout::new(
+            "monitoring grid",
+            LayoutNode::Split {
+                direction: SplitDirection::Horizontal,
+                position: 0.25,
+                first: Box::new(LayoutNode::Pane {
+                    connection_id: Some(Uuid::new_v4()),
+                }),
+                second: Box::new(LayoutNode::Pane {
+                    connection_id: None,
+                }),
+            },
+        );
+
+        manager.save_layouts(std::slice::from_ref(&layout)).unwrap();
+        assert_eq!(manager.load_layouts().unwrap(), vec![layout]);
+    }
+
     #[test]
     fn test_validate_cluster_empty_name() {
         use crate::cluster::Cluster;
@@ -0,0 +1,172 @@
+//! Schema migrations for persisted configuration files
+//!
+//! The connections and groups files carry a `schema_version`. Files written
+//! before versioning was introduced have no version and are treated as
+//! version 1. On load, the raw TOML table is upgraded one version at a time
+//! until it matches [`CURRENT_SCHEMA_VERSION`], before it is deserialized
+//! into the models.
+
+use std::path::Path;
+
+use toml::{Table, Value};
+
+use crate::error::{ConfigError, ConfigResult};
+
+/// Schema version written by this release
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Key holding the schema version at the top level of a file
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Upgrades a file table from one schema version to the next
+pub type Migration = fn(&mut Table);
+
+/// Migrations for the connections file; entry `i` upgrades version `i + 1`
+pub const CONNECTION_MIGRATIONS: &[Migration] = &[connections_v1_to_v2];
+
+/// Migrations for the groups file; entry `i` upgrades version `i + 1`
+pub const GROUP_MIGRATIONS: &[Migration] = &[groups_v1_to_v2];
+
+/// Returns the schema version of a file table
+///
+/// Files without a version predate versioning and are version 1.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Validation` if the version is not a positive integer.
+pub fn schema_version(table: &Table) -> ConfigResult<u32> {
+    match table.get(SCHEMA_VERSION_KEY) {
+        None => Ok(1),
+        Some(Value::Integer(version)) => u32::try_from(*version)
+            .ok()
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| invalid_version(&version.to_string())),
+        Some(other) => Err(invalid_version(&other.to_string())),
+    }
+}
+
+/// Returns the schema version of a file table, refusing newer versions
+///
+/// # Errors
+///
+/// Returns `ConfigError::NewerSchema` if `path` was written by a newer
+/// release, or `ConfigError::Validation` if the version is invalid.
+pub fn supported_schema_version(table: &Table, path: &Path) -> ConfigResult<u32> {
+    let version = schema_version(table)?;
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(ConfigError::NewerSchema {
+            path: path.to_path_buf(),
+            version,
+            supported: CURRENT_SCHEMA_VERSION,
+        });
+    }
+    Ok(version)
+}
+
+/// Upgrades the table read from `path` to the current schema version
+///
+/// Returns `true` if any migration ran, meaning the file should be rewritten.
+///
+/// # Errors
+///
+/// Returns `ConfigError::NewerSchema` if the file was written by a newer
+/// release, or `ConfigError::Validation` if its version is invalid.
+pub fn migrate(table: &mut Table, migrations: &[Migration], path: &Path) -> ConfigResult<bool> {
+    let version = supported_schema_version(table, path)?;
+    for migration in migrations.iter().skip(version as usize - 1) {
+        migration(table);
+    }
+    table.insert(
+        SCHEMA_VERSION_KEY.to_string(),
+        Value::Integer(i64::from(CURRENT_SCHEMA_VERSION)),
+    );
+    Ok(version < CURRENT_SCHEMA_VERSION)
+}
+
+fn invalid_version(value: &str) -> ConfigError {
+    ConfigError::Validation {
+        field: SCHEMA_VERSION_KEY.to_string(),
+        reason: format!("'{value}' is not a valid schema version"),
+    }
+}
+
+/// Returns the tables of an array-of-tables entry such as `[[connections]]`
+fn entries_mut<'a>(table: &'a mut Table, key: &str) -> impl Iterator<Item = &'a mut Table> {
+    table
+        .get_mut(key)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_table_mut)
+}
+
+/// v2 added `launch_order`; older connections launch in sort order
+fn connections_v1_to_v2(table: &mut Table) {
+    for connection in entries_mut(table, "connections") {
+        connection
+            .entry("launch_order")
+            .or_insert(Value::Integer(0));
+    }
+}
+
+/// v2 introduced versioning for groups; the layout is unchanged
+const fn groups_v1_to_v2(_table: &mut Table) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_unversioned_connections() {
+        let mut table: Table = toml::from_str(
+            r#"
+            [[connections]]
+            name = "web"
+            sort_order = 3
+
+            [[connections]]
+            name = "db"
+            launch_order = 5
+            "#,
+        )
+        .unwrap();
+
+        assert!(migrate(
+            &mut table,
+            CONNECTION_MIGRATIONS,
+            Path::new("connections.toml")
+        )
+        .unwrap());
+        assert_eq!(schema_version(&table).unwrap(), CURRENT_SCHEMA_VERSION);
+        let connections: Vec<_> = entries_mut(&mut table, "connections").collect();
+        assert_eq!(connections[0]["launch_order"].as_integer(), Some(0));
+        assert_eq!(connections[1]["launch_order"].as_integer(), Some(5));
+
+        // Already current
+        assert!(!migrate(
+            &mut table,
+            CONNECTION_MIGRATIONS,
+            Path::new("connections.toml")
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_rejects_newer_or_invalid_version() {
+        let path = Path::new("groups.toml");
+        let mut newer: Table = toml::from_str("schema_version = 99").unwrap();
+        assert!(matches!(
+            migrate(&mut newer, CONNECTION_MIGRATIONS, path),
+            Err(ConfigError::NewerSchema { version: 99, .. })
+        ));
+
+        let mut invalid: Table = toml::from_str("schema_version = \"two\"").unwrap();
+        assert!(matches!(
+            migrate(&mut invalid, GROUP_MIGRATIONS, path),
+            Err(ConfigError::Validation { .. })
+        ));
+
+        let zero: Table = toml::from_str("schema_version = 0").unwrap();
+        assert!(schema_version(&zero).is_err());
+    }
+}
@@ -3,11 +3,16 @@
 //! This module provides the `ConfigManager` for loading and saving
 //! configuration files in TOML format.
 
+pub mod encryption;
 mod manager;
+pub mod migrations;
+pub mod passphrase;
 pub mod settings;
 
-pub use manager::ConfigManager;
+pub use manager::{ConfigManager, DEFAULT_BACKUP_COUNT};
+pub use migrations::CURRENT_SCHEMA_VERSION;
 pub use settings::{
-    AppSettings, ColorScheme, ConnectionSettings, LoggingSettings, SavedSession, SecretBackendType,
-    SecretSettings, SessionRestoreSettings, TerminalSettings, UiSettings,
+    AppSettings, ColorScheme, ConnectionSettings, CustomProtocolSettings, LoggingSettings,
+    SavedSession, SecretBackendType, SecretSettings, SessionRestoreSettings, TerminalSettings,
+    UiSettings,
 };
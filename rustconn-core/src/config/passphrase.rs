@@ -0,0 +1,134 @@
+//! Passphrase sources for the encrypted connections file
+//!
+//! `ConfigManager` asks a [`PassphraseProvider`] for the passphrase the first
+//! time it reads or writes an encrypted file and keeps the answer for the
+//! rest of the session. The default chain checks the
+//! `RUSTCONN_CONFIG_PASSPHRASE` environment variable and then the desktop
+//! keyring.
+
+use std::fmt;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+use secrecy::SecretString;
+
+use crate::error::ConfigResult;
+
+/// Environment variable read by [`EnvPassphraseProvider`]
+pub const PASSPHRASE_ENV_VAR: &str = "RUSTCONN_CONFIG_PASSPHRASE";
+
+/// Source of the passphrase for encrypted configuration files
+pub trait PassphraseProvider: Send + Sync + fmt::Debug {
+    /// Returns the passphrase, or `None` if this source has none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source exists but cannot be queried.
+    fn passphrase(&self) -> ConfigResult<Option<SecretString>>;
+}
+
+/// Reads the passphrase from an environment variable
+#[derive(Debug, Clone)]
+pub struct EnvPassphraseProvider {
+    var: String,
+}
+
+impl EnvPassphraseProvider {
+    /// Creates a provider reading `var`
+    #[must_use]
+    pub fn new(var: impl Into<String>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+impl Default for EnvPassphraseProvider {
+    fn default() -> Self {
+        Self::new(PASSPHRASE_ENV_VAR)
+    }
+}
+
+impl PassphraseProvider for EnvPassphraseProvider {
+    fn passphrase(&self) -> ConfigResult<Option<SecretString>> {
+        Ok(std::env::var(&self.var)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(SecretString::from))
+    }
+}
+
+/// Looks the passphrase up in the desktop keyring with `secret-tool`
+///
+/// The entry is stored with the attributes `application rustconn` and
+/// `kind config-passphrase`:
+///
+/// ```text
+/// secret-tool store --label="RustConn configuration" application rustconn kind config-passphrase
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyringPassphraseProvider;
+
+impl PassphraseProvider for KeyringPassphraseProvider {
+    fn passphrase(&self) -> ConfigResult<Option<SecretString>> {
+        // A missing secret-tool or a locked keyring means no passphrase here
+        let Ok(output) = Command::new("secret-tool")
+            .args([
+                "lookup",
+                "application",
+                "rustconn",
+                "kind",
+                "config-passphrase",
+            ])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        else {
+            return Ok(None);
+        };
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let value = String::from_utf8_lossy(&output.stdout);
+        let value = value.trim_end_matches('\n');
+        Ok((!value.is_empty()).then(|| SecretString::from(value)))
+    }
+}
+
+/// Asks each provider in turn and returns the first passphrase found
+#[derive(Debug, Clone, Default)]
+pub struct ChainPassphraseProvider {
+    providers: Vec<Arc<dyn PassphraseProvider>>,
+}
+
+impl ChainPassphraseProvider {
+    /// Creates an empty chain
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a provider to the chain
+    #[must_use]
+    pub fn with(mut self, provider: impl PassphraseProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// The chain `ConfigManager::new` uses: environment, then keyring
+    #[must_use]
+    pub fn system() -> Self {
+        Self::new()
+            .with(EnvPassphraseProvider::default())
+            .with(KeyringPassphraseProvider)
+    }
+}
+
+impl PassphraseProvider for ChainPassphraseProvider {
+    fn passphrase(&self) -> ConfigResult<Option<SecretString>> {
+        for provider in &self.providers {
+            if let Some(passphrase) = provider.passphrase()? {
+                return Ok(Some(passphrase));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -271,6 +271,8 @@ pub enum SecretBackendType {
     LibSecret,
     /// Bitwarden CLI
     Bitwarden,
+    /// pass(1) password store
+    Pass,
 }
 
 /// Color scheme preference
@@ -425,6 +427,24 @@ pub struct ConnectionSettings {
     /// Timeout in seconds for port check (default: 3)
     #[serde(default = "default_port_check_timeout")]
     pub port_check_timeout_secs: u32,
+    /// User-defined protocol handlers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_protocols: Vec<CustomProtocolSettings>,
+    /// Encrypt the connections file with a passphrase
+    #[serde(default)]
+    pub encrypt_connections: bool,
+    /// Disconnect terminal sessions idle for this many minutes (0 = never)
+    #[serde(default)]
+    pub idle_timeout_minutes: u32,
+}
+
+/// A user-defined protocol handler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomProtocolSettings {
+    /// Protocol name, referenced by a connection's `protocol` custom property
+    pub name: String,
+    /// Command template, e.g. `mosh {user}@{host} -p {port}`
+    pub command_template: String,
 }
 
 const fn default_port_check_timeout() -> u32 {
@@ -436,6 +456,9 @@ impl Default for ConnectionSettings {
         Self {
             pre_connect_port_check: true,
             port_check_timeout_secs: default_port_check_timeout(),
+            custom_protocols: Vec::new(),
+            encrypt_connections: false,
+            idle_timeout_minutes: 0,
         }
     }
 }
@@ -0,0 +1,233 @@
+//! Host address validation and formatting
+//!
+//! A connection host is a DNS name, an IPv4 literal, or an IPv6 literal.
+//! IPv6 literals are stored either bare (`::1`) or bracketed (`[::1]`);
+//! command builders call [`unbracketed_host`] or [`host_for_uri`] to get the
+//! form each client expects.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use thiserror::Error;
+
+/// Maximum length of a DNS name
+const MAX_HOSTNAME_LEN: usize = 253;
+
+/// Maximum length of a single DNS label
+const MAX_LABEL_LEN: usize = 63;
+
+/// Reasons a connection host is rejected
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum HostError {
+    /// The host is empty or whitespace
+    #[error("Host cannot be empty")]
+    Empty,
+
+    /// The host contains inner whitespace
+    #[error("Host '{0}' cannot contain whitespace")]
+    Whitespace(String),
+
+    /// The host was entered as a URL
+    #[error("Host '{0}' includes a URL scheme; enter only the host name")]
+    Scheme(String),
+
+    /// The host includes a path
+    #[error("Host '{0}' includes a path; enter only the host name")]
+    Path(String),
+
+    /// The host includes a port
+    #[error("Host '{0}' includes a port; use the port field instead")]
+    Port(String),
+
+    /// The host looks like an IPv4 address but is not one
+    #[error("'{0}' is not a valid IPv4 address")]
+    InvalidIpv4(String),
+
+    /// The host looks like an IPv6 address but is not one
+    #[error("'{0}' is not a valid IPv6 address")]
+    InvalidIpv6(String),
+
+    /// The host is not a valid DNS name
+    #[error("'{0}' is not a valid host name")]
+    InvalidHostname(String),
+}
+
+/// Checks that a host is a plausible hostname, IPv4, or IPv6 literal
+///
+/// Surrounding whitespace is ignored. Hosts containing variable references
+/// (`$VAR` or `${VAR}`) are accepted as-is since they are only resolved at
+/// connect time. IPv6 literals may be bare or bracketed and may carry a zone
+/// index (`fe80::1%eth0`).
+///
+/// # Errors
+///
+/// Returns the [`HostError`] describing the first problem found.
+pub fn validate_host(host: &str) -> Result<(), HostError> {
+    let host = host.trim();
+    if host.is_empty() {
+        return Err(HostError::Empty);
+    }
+    if host.contains('$') {
+        return Ok(());
+    }
+    if host.chars().any(char::is_whitespace) {
+        return Err(HostError::Whitespace(host.to_string()));
+    }
+    if host.contains("://") {
+        return Err(HostError::Scheme(host.to_string()));
+    }
+    if host.contains('/') {
+        return Err(HostError::Path(host.to_string()));
+    }
+
+    if let Some(inner) = host.strip_prefix('[') {
+        return match inner.strip_suffix(']') {
+            Some(address) if is_ipv6_literal(address) => Ok(()),
+            _ if inner.contains("]:") => Err(HostError::Port(host.to_string())),
+            _ => Err(HostError::InvalidIpv6(host.to_string())),
+        };
+    }
+
+    if host.contains(':') {
+        if is_ipv6_literal(host) {
+            return Ok(());
+        }
+        // A single colon followed by digits is `host:port`
+        return match host.split_once(':') {
+            Some((name, port))
+                if !name.is_empty()
+                    && !port.is_empty()
+                    && port.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                Err(HostError::Port(host.to_string()))
+            }
+            _ => Err(HostError::InvalidIpv6(host.to_string())),
+        };
+    }
+
+    validate_hostname(host)
+}
+
+/// Returns the host with surrounding whitespace and IPv6 brackets removed
+///
+/// This is the form expected by clients that take the host as a separate
+/// argument, such as `ssh`.
+#[must_use]
+pub fn unbracketed_host(host: &str) -> &str {
+    let host = host.trim();
+    host.strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+/// Returns the host in the form used before a `:port` suffix or inside a URI
+///
+/// IPv6 literals are bracketed; other hosts are returned trimmed.
+#[must_use]
+pub fn host_for_uri(host: &str) -> String {
+    let bare = unbracketed_host(host);
+    if bare.contains(':') {
+        format!("[{bare}]")
+    } else {
+        bare.to_string()
+    }
+}
+
+/// Returns true for an IPv6 literal with an optional zone index
+fn is_ipv6_literal(value: &str) -> bool {
+    let (address, zone) = value.split_once('%').unwrap_or((value, "zone"));
+    !zone.is_empty() && address.parse::<Ipv6Addr>().is_ok()
+}
+
+/// Validates an IPv4 literal or DNS name
+fn validate_hostname(host: &str) -> Result<(), HostError> {
+    let name = host.strip_suffix('.').unwrap_or(host);
+    let labels: Vec<&str> = name.split('.').collect();
+
+    // All-numeric names can only be IPv4 addresses
+    if labels
+        .iter()
+        .all(|label| !label.is_empty() && label.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return name
+            .parse::<Ipv4Addr>()
+            .map(|_| ())
+            .map_err(|_| HostError::InvalidIpv4(host.to_string()));
+    }
+
+    let valid = name.len() <= MAX_HOSTNAME_LEN
+        && labels.iter().all(|label| {
+            (1..=MAX_LABEL_LEN).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(HostError::InvalidHostname(host.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_host_accepts_plausible_hosts() {
+        for host in [
+            "example.com",
+            " server01 ",
+            "db_1.internal.",
+            "192.168.1.10",
+            "::1",
+            "[2001:db8::1]",
+            "fe80::1%eth0",
+            "${DB_HOST}",
+        ] {
+            assert_eq!(validate_host(host), Ok(()), "{host}");
+        }
+    }
+
+    #[test]
+    fn test_validate_host_reports_specific_problems() {
+        assert_eq!(validate_host("  "), Err(HostError::Empty));
+        assert!(matches!(
+            validate_host("my host"),
+            Err(HostError::Whitespace(_))
+        ));
+        assert!(matches!(
+            validate_host("http://foo"),
+            Err(HostError::Scheme(_))
+        ));
+        assert!(matches!(validate_host("foo/bar"), Err(HostError::Path(_))));
+        assert!(matches!(validate_host("foo:22"), Err(HostError::Port(_))));
+        assert!(matches!(validate_host("[::1]:22"), Err(HostError::Port(_))));
+        assert!(matches!(
+            validate_host("999.1.1.1"),
+            Err(HostError::InvalidIpv4(_))
+        ));
+        assert!(matches!(
+            validate_host("[::g]"),
+            Err(HostError::InvalidIpv6(_))
+        ));
+        assert!(matches!(
+            validate_host("-bad.com"),
+            Err(HostError::InvalidHostname(_))
+        ));
+        assert!(matches!(
+            validate_host("a..b"),
+            Err(HostError::InvalidHostname(_))
+        ));
+    }
+
+    #[test]
+    fn test_bracket_normalization() {
+        assert_eq!(unbracketed_host(" [::1] "), "::1");
+        assert_eq!(unbracketed_host("example.com"), "example.com");
+        assert_eq!(host_for_uri("::1"), "[::1]");
+        assert_eq!(host_for_uri("[::1]"), "[::1]");
+        assert_eq!(host_for_uri("10.0.0.1"), "10.0.0.1");
+    }
+}
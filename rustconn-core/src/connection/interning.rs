@@ -8,6 +8,9 @@ use std::sync::Arc;
 
 use crate::performance::memory_optimizer;
 
+/// Eviction churn above which the interner capacity is considered too small
+pub const HIGH_EVICTION_CHURN: f64 = 0.5;
+
 /// Interns a protocol name string for memory efficiency
 ///
 /// Protocol names are frequently repeated across many connections,
@@ -91,6 +94,8 @@ pub fn intern_connection_strings(
 ///
 /// This function checks the current interning statistics and logs them.
 /// If the hit rate falls below the threshold (30%), it returns a warning message.
+/// A warning is also returned when eviction churn exceeds
+/// [`HIGH_EVICTION_CHURN`], meaning the interner capacity is too small.
 ///
 /// # Arguments
 ///
@@ -108,12 +113,16 @@ pub fn check_interning_stats(threshold: f64) -> Option<String> {
         .load(std::sync::atomic::Ordering::Relaxed);
     let hit_count = stats.hit_count.load(std::sync::atomic::Ordering::Relaxed);
     let bytes_saved = stats.bytes_saved.load(std::sync::atomic::Ordering::Relaxed);
+    let eviction_count = stats
+        .eviction_count
+        .load(std::sync::atomic::Ordering::Relaxed);
 
     if intern_count == 0 {
         return None;
     }
 
     let hit_rate = hit_count as f64 / intern_count as f64;
+    let churn = stats.eviction_churn();
 
     // Log statistics
     tracing::debug!(
@@ -121,10 +130,18 @@ pub fn check_interning_stats(threshold: f64) -> Option<String> {
         hit_count = hit_count,
         hit_rate = format!("{:.1}%", hit_rate * 100.0),
         bytes_saved = bytes_saved,
+        eviction_count = eviction_count,
         "String interning statistics"
     );
 
-    if hit_rate < threshold && intern_count > 100 {
+    if churn > HIGH_EVICTION_CHURN && eviction_count > 100 {
+        Some(format!(
+            "String interner evicts {:.1}% of new strings (capacity {}). \
+             Consider raising the interner capacity.",
+            churn * 100.0,
+            memory_optimizer().interner().capacity()
+        ))
+    } else if hit_rate < threshold && intern_count > 100 {
         Some(format!(
             "String interner hit rate ({:.1}%) is below recommended threshold ({:.1}%). \
              Consider reviewing which strings are being interned.",
@@ -190,8 +207,9 @@ pub fn log_interning_stats() {
 /// Logs interning statistics and emits a warning if hit rate is below threshold
 ///
 /// This function logs the current interning statistics and checks if the
-/// hit rate is below the recommended threshold (30%). If so, it logs a
-/// warning suggesting configuration review.
+/// hit rate is below the recommended threshold (30%) or eviction churn is
+/// above [`HIGH_EVICTION_CHURN`]. If so, it logs a warning suggesting
+/// configuration review.
 ///
 /// # Arguments
 ///
@@ -199,9 +217,15 @@ pub fn log_interning_stats() {
 ///
 /// # Returns
 ///
-/// `true` if hit rate is acceptable, `false` if below threshold
+/// `true` if hit rate and churn are acceptable, `false` otherwise
 pub fn log_interning_stats_with_warning(threshold: f64) -> bool {
     let (intern_count, hit_count, hit_rate, bytes_saved) = get_interning_stats();
+    let interner = memory_optimizer().interner();
+    let eviction_count = interner
+        .stats()
+        .eviction_count
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let churn = interner.stats().eviction_churn();
 
     if intern_count == 0 {
         tracing::debug!("String interning: no strings interned yet");
@@ -214,9 +238,19 @@ pub fn log_interning_stats_with_warning(threshold: f64) -> bool {
         hit_count = hit_count,
         hit_rate_percent = format!("{:.1}", hit_rate * 100.0),
         bytes_saved = bytes_saved,
+        eviction_count = eviction_count,
         "String interning statistics"
     );
 
+    if churn > HIGH_EVICTION_CHURN && eviction_count > 100 {
+        tracing::warn!(
+            eviction_churn_percent = format!("{:.1}", churn * 100.0),
+            capacity = interner.capacity(),
+            "String interner eviction churn is high. Consider raising the interner capacity."
+        );
+        return false;
+    }
+
     // Check if hit rate is below threshold (only warn if we have enough samples)
     if hit_rate < threshold && intern_count > 100 {
         tracing::warn!(
@@ -0,0 +1,182 @@
+//! SSH jump host resolution
+//!
+//! An SSH connection reaches its host through another saved connection when
+//! `SshConfig::jump_host_id` is set. Jump hosts may themselves use jump
+//! hosts, forming a chain that is resolved here into the `-J` argument.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use super::host_for_uri;
+use crate::error::{ConfigError, ConfigResult};
+use crate::models::{Connection, ProtocolConfig};
+
+/// Resolves the jump hosts needed to reach a connection
+///
+/// Walks the `jump_host_id` references starting at the connection `id`
+/// and returns the jump host IDs in the order SSH connects through them,
+/// first hop first. The connection itself is not included, and a
+/// connection without a jump host yields an empty chain.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Validation` if a connection in the chain is
+/// missing (for example a deleted jump host), is not an SSH connection,
+/// or if the references form a cycle.
+pub fn resolve_jump_chain<'a, F>(id: Uuid, lookup: F) -> ConfigResult<Vec<Uuid>>
+where
+    F: Fn(Uuid) -> Option<&'a Connection>,
+{
+    let target = lookup(id).ok_or_else(|| ConfigError::Validation {
+        field: "id".to_string(),
+        reason: format!("Connection with ID {id} not found"),
+    })?;
+
+    let mut chain = Vec::new();
+    let mut visited = HashSet::from([id]);
+    let mut current = target;
+    while let Some(next_id) = jump_host_id(current) {
+        if !visited.insert(next_id) {
+            return Err(ConfigError::Validation {
+                field: "jump_host_id".to_string(),
+                reason: format!(
+                    "Jump host chain of '{}' loops back through '{}'",
+                    target.name, current.name
+                ),
+            });
+        }
+        let next = lookup(next_id).ok_or_else(|| ConfigError::Validation {
+            field: "jump_host_id".to_string(),
+            reason: format!(
+                "Jump host of '{}' no longer exists (ID {next_id})",
+                current.name
+            ),
+        })?;
+        if !matches!(next.protocol_config, ProtocolConfig::Ssh(_)) {
+            return Err(ConfigError::Validation {
+                field: "jump_host_id".to_string(),
+                reason: format!(
+                    "Jump host '{}' of '{}' is not an SSH connection",
+                    next.name, current.name
+                ),
+            });
+        }
+        chain.push(next_id);
+        current = next;
+    }
+
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Formats a jump host as `[user@]host[:port]` for the SSH `-J` option
+#[must_use]
+pub fn jump_host_spec(connection: &Connection) -> String {
+    let mut spec = host_for_uri(&connection.host);
+    if let Some(user) = connection.username.as_deref().filter(|u| !u.is_empty()) {
+        spec = format!("{user}@{spec}");
+    }
+    if connection.port != 22 {
+        spec = format!("{spec}:{}", connection.port);
+    }
+    spec
+}
+
+/// Builds the SSH `-J` value for a resolved jump chain, first hop first
+///
+/// A manual `proxy_jump` on the first hop is kept in front of the chain,
+/// since that is how the first hop itself is reached. Returns `None` for
+/// an empty chain.
+#[must_use]
+pub fn proxy_jump_arg(chain: &[&Connection]) -> Option<String> {
+    let first = chain.first()?;
+    let mut hops = Vec::with_capacity(chain.len() + 1);
+    if let ProtocolConfig::Ssh(ref ssh) = first.protocol_config {
+        if let Some(proxy) = ssh.proxy_jump.as_deref().filter(|p| !p.is_empty()) {
+            hops.push(proxy.to_string());
+        }
+    }
+    hops.extend(chain.iter().map(|c| jump_host_spec(c)));
+    Some(hops.join(","))
+}
+
+fn jump_host_id(connection: &Connection) -> Option<Uuid> {
+    match connection.protocol_config {
+        ProtocolConfig::Ssh(ref ssh) => ssh.jump_host_id,
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn ssh(name: &str, jump: Option<&Connection>) -> Connection {
+        let mut conn = Connection::new_ssh(name.to_string(), format!("{name}.example.com"), 22);
+        if let ProtocolConfig::Ssh(ref mut config) = conn.protocol_config {
+            config.jump_host_id = jump.map(|j| j.id);
+        }
+        conn
+    }
+
+    fn index(connections: &[Connection]) -> HashMap<Uuid, &Connection> {
+        connections.iter().map(|c| (c.id, c)).collect()
+    }
+
+    #[test]
+    fn test_resolve_chain_first_hop_first() {
+        let outer = ssh("outer", None).with_username("ops");
+        let mut inner = ssh("inner", Some(&outer));
+        inner.port = 2222;
+        let target = ssh("db", Some(&inner));
+        let connections = vec![outer.clone(), inner.clone(), target.clone()];
+        let map = index(&connections);
+
+        let chain = resolve_jump_chain(target.id, |id| map.get(&id).copied()).unwrap();
+        assert_eq!(chain, vec![outer.id, inner.id]);
+        assert!(resolve_jump_chain(outer.id, |id| map.get(&id).copied())
+            .unwrap()
+            .is_empty());
+
+        let hops: Vec<&Connection> = chain.iter().map(|id| map[id]).collect();
+        assert_eq!(
+            proxy_jump_arg(&hops).as_deref(),
+            Some("ops@outer.example.com,inner.example.com:2222")
+        );
+        assert_eq!(proxy_jump_arg(&[]), None);
+    }
+
+    #[test]
+    fn test_resolve_chain_errors() {
+        let deleted = ssh("deleted", None);
+        let orphan = ssh("orphan", Some(&deleted));
+        let mut a = ssh("a", None);
+        let b = ssh("b", Some(&a));
+        if let ProtocolConfig::Ssh(ref mut config) = a.protocol_config {
+            config.jump_host_id = Some(b.id);
+        }
+        let rdp = Connection::new_rdp("desktop".to_string(), "10.0.0.5".to_string(), 3389);
+        let via_rdp = ssh("via-rdp", Some(&rdp));
+        let connections = vec![orphan.clone(), a, b.clone(), rdp, via_rdp.clone()];
+        let map = index(&connections);
+        let lookup = |id| map.get(&id).copied();
+
+        let err = resolve_jump_chain(orphan.id, lookup).unwrap_err();
+        assert!(err.to_string().contains("no longer exists"), "{err}");
+        let err = resolve_jump_chain(b.id, lookup).unwrap_err();
+        assert!(err.to_string().contains("loops back"), "{err}");
+        let err = resolve_jump_chain(via_rdp.id, lookup).unwrap_err();
+        assert!(err.to_string().contains("not an SSH connection"), "{err}");
+        assert!(resolve_jump_chain(Uuid::new_v4(), lookup).is_err());
+    }
+
+    #[test]
+    fn test_jump_host_spec_brackets_ipv6() {
+        let mut conn = ssh("v6", None);
+        conn.host = "2001:db8::1".to_string();
+        conn.port = 2200;
+        assert_eq!(jump_host_spec(&conn), "[2001:db8::1]:2200");
+    }
+}
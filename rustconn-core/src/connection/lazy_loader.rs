@@ -4,23 +4,89 @@
 //! loaded and provides methods to load children on demand. This improves startup
 //! performance for large connection databases by only loading root-level items
 //! initially and loading children when groups are expanded.
+//!
+//! Expanding a group can also prefetch the children of its neighbouring
+//! sibling groups on a background worker, so that expanding those next does
+//! not stall.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, PoisonError};
 use uuid::Uuid;
 
+use super::virtual_scroll::VirtualScrollConfig;
 use crate::models::{Connection, ConnectionGroup};
 
+/// Default number of sibling groups prefetched on each side of an expanded group
+pub const DEFAULT_PREFETCH_WINDOW: usize = 1;
+
+/// Maximum number of prefetches waiting for the worker; further requests
+/// are dropped rather than blocking the caller
+const PREFETCH_QUEUE_CAPACITY: usize = 64;
+
+/// IDs of a group's direct children, computed ahead of time by a prefetch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefetchedChildren {
+    /// Child group IDs
+    pub group_ids: Vec<Uuid>,
+    /// Child connection IDs
+    pub connection_ids: Vec<Uuid>,
+}
+
+/// Shared snapshot of the groups and connections that prefetches read from
+type PrefetchSource = (Arc<[ConnectionGroup]>, Arc<[Connection]>);
+
+/// Work item for the prefetch worker
+enum PrefetchJob {
+    /// Compute the children of `target`, queued under `ticket`
+    Fetch {
+        target: Uuid,
+        ticket: u64,
+        source: PrefetchSource,
+    },
+    /// Signal the sender once every earlier job has been processed
+    Flush(mpsc::Sender<()>),
+}
+
+/// Prefetch bookkeeping shared with the worker, keyed by group ID
+#[derive(Debug, Default)]
+struct PrefetchState {
+    /// Groups waiting for the worker: expanded group that queued them and
+    /// the ticket of the queued job
+    queued: HashMap<Uuid, (Uuid, u64)>,
+    /// Children computed by the worker, with the expanded group that queued them
+    done: HashMap<Uuid, (Uuid, PrefetchedChildren)>,
+}
+
 /// Tracks which groups have been loaded for lazy loading
 ///
 /// The `LazyGroupLoader` maintains state about which groups have had their
 /// children loaded. This allows the sidebar to load only root-level items
 /// initially and load children on demand when groups are expanded.
-#[derive(Debug, Clone, Default)]
+///
+/// Not `Clone`: a loader owns its prefetch worker and results.
+#[derive(Debug)]
 pub struct LazyGroupLoader {
     /// Set of group IDs that have been loaded
     loaded_groups: HashSet<Uuid>,
     /// Whether root-level items have been loaded
     root_loaded: bool,
+    /// Groups and connections that prefetches read from
+    source: Option<PrefetchSource>,
+    /// Number of siblings prefetched on each side of an expanded group
+    prefetch_window: usize,
+    /// Queued and finished prefetches
+    prefetch_state: Arc<Mutex<PrefetchState>>,
+    /// Queue of the prefetch worker, started on the first prefetch
+    worker: Option<SyncSender<PrefetchJob>>,
+    /// Ticket of the most recently queued prefetch
+    last_ticket: u64,
+}
+
+impl Default for LazyGroupLoader {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LazyGroupLoader {
@@ -30,7 +96,193 @@ impl LazyGroupLoader {
         Self {
             loaded_groups: HashSet::new(),
             root_loaded: false,
+            source: None,
+            prefetch_window: DEFAULT_PREFETCH_WINDOW,
+            prefetch_state: Arc::new(Mutex::new(PrefetchState::default())),
+            worker: None,
+            last_ticket: 0,
+        }
+    }
+
+    /// Sets how many sibling groups are prefetched on each side of an
+    /// expanded group; 0 disables prefetching
+    #[must_use]
+    pub const fn with_prefetch_window(mut self, window: usize) -> Self {
+        self.prefetch_window = window;
+        self
+    }
+
+    /// Returns the prefetch window
+    #[must_use]
+    pub const fn prefetch_window(&self) -> usize {
+        self.prefetch_window
+    }
+
+    /// Sets the groups and connections that prefetches read from
+    ///
+    /// Cancels queued prefetches and drops prefetched children, since they
+    /// were computed from the previous data.
+    pub fn set_source(&mut self, groups: Arc<[ConnectionGroup]>, connections: Arc<[Connection]>) {
+        self.clear_prefetches();
+        self.source = Some((groups, connections));
+    }
+
+    /// Prefetches the children of the sibling groups next to `group_id`
+    ///
+    /// Call this when `group_id` is expanded. Up to the prefetch window of
+    /// siblings before and after it (by sort order) that are neither loaded
+    /// nor already prefetched are queued for a single background worker;
+    /// the results are picked up with [`Self::take_prefetched`]. Never
+    /// blocks: when the worker's queue is full the remaining siblings are
+    /// skipped. Returns the number of groups queued, which is 0 if there is
+    /// nothing to prefetch or no source has been set.
+    ///
+    /// A previous prefetch for the same group is cancelled.
+    pub fn prefetch(&mut self, group_id: Uuid) -> usize {
+        self.cancel_prefetch(group_id);
+        let Some(source) = self.source.clone() else {
+            return 0;
+        };
+        let targets: Vec<Uuid> = {
+            let state = self
+                .prefetch_state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            self.prefetch_targets(group_id, &source.0)
+                .into_iter()
+                .filter(|id| !state.done.contains_key(id))
+                .collect()
+        };
+        if targets.is_empty() {
+            return 0;
         }
+        let Some(worker) = self.worker() else {
+            return 0;
+        };
+
+        // Held while queueing so the worker never sees a job before its entry
+        let mut state = self
+            .prefetch_state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut queued = 0;
+        for target in targets {
+            self.last_ticket += 1;
+            let ticket = self.last_ticket;
+            let job = PrefetchJob::Fetch {
+                target,
+                ticket,
+                source: source.clone(),
+            };
+            match worker.try_send(job) {
+                Ok(()) => {
+                    state.queued.insert(target, (group_id, ticket));
+                    queued += 1;
+                }
+                Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => break,
+            }
+        }
+        drop(state);
+        queued
+    }
+
+    /// Cancels the prefetch started for `group_id`, e.g. when it is collapsed
+    ///
+    /// Siblings it queued are skipped by the worker and children it already
+    /// prefetched are discarded. Prefetches started for other groups are
+    /// not affected.
+    pub fn cancel_prefetch(&mut self, group_id: Uuid) {
+        let mut state = self
+            .prefetch_state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        state.queued.retain(|_, (origin, _)| *origin != group_id);
+        state.done.retain(|_, (origin, _)| *origin != group_id);
+    }
+
+    /// Takes the prefetched children of a group, if a prefetch computed them
+    pub fn take_prefetched(&self, group_id: Uuid) -> Option<PrefetchedChildren> {
+        self.prefetch_state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .done
+            .remove(&group_id)
+            .map(|(_, children)| children)
+    }
+
+    /// Returns whether a group's children have been prefetched
+    #[must_use]
+    pub fn is_prefetched(&self, group_id: Uuid) -> bool {
+        self.prefetch_state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .done
+            .contains_key(&group_id)
+    }
+
+    /// Blocks until the worker has processed every prefetch queued so far
+    pub fn wait_for_prefetches(&self) {
+        let Some(worker) = &self.worker else {
+            return;
+        };
+        let (done_tx, done_rx) = mpsc::channel();
+        if worker.send(PrefetchJob::Flush(done_tx)).is_ok() {
+            let _ = done_rx.recv();
+        }
+    }
+
+    fn clear_prefetches(&self) {
+        let mut state = self
+            .prefetch_state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        state.queued.clear();
+        state.done.clear();
+    }
+
+    /// Returns the prefetch worker's queue, starting the worker if needed
+    ///
+    /// The worker exits once the loader, and with it the queue, is dropped.
+    fn worker(&mut self) -> Option<SyncSender<PrefetchJob>> {
+        if self.worker.is_none() {
+            let (sender, receiver) = mpsc::sync_channel(PREFETCH_QUEUE_CAPACITY);
+            let state = Arc::clone(&self.prefetch_state);
+            std::thread::Builder::new()
+                .name("group-prefetch".to_string())
+                .spawn(move || {
+                    for job in receiver {
+                        run_prefetch_job(job, &state);
+                    }
+                })
+                .ok()?;
+            self.worker = Some(sender);
+        }
+        self.worker.clone()
+    }
+
+    /// Siblings within the prefetch window that still need their children
+    fn prefetch_targets(&self, group_id: Uuid, groups: &[ConnectionGroup]) -> Vec<Uuid> {
+        let Some(group) = groups.iter().find(|g| g.id == group_id) else {
+            return Vec::new();
+        };
+        let mut siblings: Vec<&ConnectionGroup> = groups
+            .iter()
+            .filter(|g| g.parent_id == group.parent_id)
+            .collect();
+        siblings.sort_by_key(|g| g.sort_order);
+        let Some(position) = siblings.iter().position(|g| g.id == group_id) else {
+            return Vec::new();
+        };
+
+        let before = siblings[position.saturating_sub(self.prefetch_window)..position]
+            .iter()
+            .rev();
+        let after = siblings[position + 1..].iter().take(self.prefetch_window);
+        after
+            .chain(before)
+            .map(|g| g.id)
+            .filter(|id| !self.loaded_groups.contains(id))
+            .collect()
     }
 
     /// Returns whether root-level items have been loaded
@@ -65,20 +317,24 @@ impl LazyGroupLoader {
 
     /// Marks a group as unloaded (for re-loading)
     ///
+    /// Also cancels a prefetch started when the group was expanded.
+    ///
     /// # Arguments
     ///
     /// * `group_id` - The UUID of the group to mark as unloaded
     pub fn mark_group_unloaded(&mut self, group_id: Uuid) {
         self.loaded_groups.remove(&group_id);
+        self.cancel_prefetch(group_id);
     }
 
     /// Resets all loading state
     ///
-    /// This clears all loaded groups and resets `root_loaded` to false.
-    /// Useful when the connection database is reloaded.
+    /// This clears all loaded groups, resets `root_loaded` to false and
+    /// cancels prefetches. Useful when the connection database is reloaded.
     pub fn reset(&mut self) {
         self.loaded_groups.clear();
         self.root_loaded = false;
+        self.clear_prefetches();
     }
 
     /// Returns the number of groups that have been loaded
@@ -115,12 +371,41 @@ impl LazyGroupLoader {
 
         let child_connections: Vec<&'a Connection> = all_connections
             .iter()
-            .filter(|c| c.group_id == Some(group_id))
+            .filter(|c| c.is_in_group(group_id))
             .collect();
 
         (child_groups, child_connections)
     }
 
+    /// Gets the row heights of a group's children for virtual scrolling
+    ///
+    /// Heights follow the order of [`Self::get_children_to_load`]: child
+    /// groups first, then connections. When the group is expanded, pass them
+    /// to [`VirtualScroller::insert_items`] after the group's row; when it is
+    /// collapsed, remove the same number of rows with
+    /// [`VirtualScroller::remove_items`]. Rows of other groups keep their
+    /// cached heights.
+    ///
+    /// [`VirtualScroller::insert_items`]: crate::VirtualScroller::insert_items
+    /// [`VirtualScroller::remove_items`]: crate::VirtualScroller::remove_items
+    #[must_use]
+    pub fn child_row_heights(
+        &self,
+        group_id: Uuid,
+        all_groups: &[ConnectionGroup],
+        all_connections: &[Connection],
+        config: &VirtualScrollConfig,
+    ) -> Vec<f64> {
+        let (groups, connections) =
+            self.get_children_to_load(group_id, all_groups, all_connections);
+        std::iter::repeat_n(config.row_height(true), groups.len())
+            .chain(std::iter::repeat_n(
+                config.row_height(false),
+                connections.len(),
+            ))
+            .collect()
+    }
+
     /// Gets root-level items to load
     ///
     /// Returns root-level groups (those with no parent) and ungrouped connections.
@@ -165,6 +450,54 @@ impl LazyGroupLoader {
     }
 }
 
+/// Processes one job on the prefetch worker
+///
+/// A fetch whose ticket is no longer queued was cancelled, or superseded by
+/// a newer prefetch of the same group, and is skipped.
+fn run_prefetch_job(job: PrefetchJob, state: &Mutex<PrefetchState>) {
+    let is_current = |state: &PrefetchState, target: &Uuid, ticket: u64| {
+        state.queued.get(target).is_some_and(|(_, t)| *t == ticket)
+    };
+    match job {
+        PrefetchJob::Flush(done) => {
+            let _ = done.send(());
+        }
+        PrefetchJob::Fetch {
+            target,
+            ticket,
+            source: (groups, connections),
+        } => {
+            if !is_current(
+                &state.lock().unwrap_or_else(PoisonError::into_inner),
+                &target,
+                ticket,
+            ) {
+                return;
+            }
+            let children = PrefetchedChildren {
+                group_ids: groups
+                    .iter()
+                    .filter(|g| g.parent_id == Some(target))
+                    .map(|g| g.id)
+                    .collect(),
+                connection_ids: connections
+                    .iter()
+                    .filter(|c| c.is_in_group(target))
+                    .map(|c| c.id)
+                    .collect(),
+            };
+            // Checked again under the lock so a concurrent cancel sees either
+            // a queued entry it can drop or a result it can remove
+            let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            if is_current(&state, &target, ticket) {
+                if let Some((origin, _)) = state.queued.remove(&target) {
+                    state.done.insert(target, (origin, children));
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::similar_names)]
 mod tests {
@@ -304,6 +637,129 @@ mod tests {
         assert!(child_connections.iter().any(|c| c.id == conn2.id));
     }
 
+    #[test]
+    fn test_child_row_heights() {
+        let loader = LazyGroupLoader::new();
+        let config = VirtualScrollConfig::default()
+            .with_item_height(24.0)
+            .with_group_header_height(40.0);
+
+        let root_group = create_test_group("Root", None);
+        let child_group = create_test_group("Child", Some(root_group.id));
+        let conn1 = create_test_connection("Conn1", Some(root_group.id));
+        let conn2 = create_test_connection("Conn2", Some(child_group.id));
+        let all_groups = vec![root_group.clone(), child_group];
+        let all_connections = vec![conn1, conn2];
+
+        let heights =
+            loader.child_row_heights(root_group.id, &all_groups, &all_connections, &config);
+        assert_eq!(heights, vec![40.0, 24.0]);
+
+        let mut scroller = crate::VirtualScroller::new(1, 40.0, 200.0);
+        scroller.insert_items(1, &heights);
+        assert!((scroller.total_height() - 104.0).abs() < f64::EPSILON);
+        scroller.remove_items(1, heights.len());
+        assert!((scroller.total_height() - 40.0).abs() < f64::EPSILON);
+    }
+
+    fn prefetch_fixture() -> (LazyGroupLoader, Vec<ConnectionGroup>, Vec<Connection>) {
+        let mut groups: Vec<_> = (0..5)
+            .map(|i| create_test_group(&format!("Group{i}"), None))
+            .collect();
+        for (i, group) in groups.iter_mut().enumerate() {
+            group.sort_order = i32::try_from(i).unwrap();
+        }
+        let nested = create_test_group("Nested", Some(groups[1].id));
+        let connections = vec![
+            create_test_connection("Conn1", Some(groups[1].id)),
+            create_test_connection("Conn3", Some(groups[3].id)),
+        ];
+        groups.push(nested);
+
+        let mut loader = LazyGroupLoader::new();
+        loader.set_source(groups.clone().into(), connections.clone().into());
+        (loader, groups, connections)
+    }
+
+    #[test]
+    fn test_prefetch_adjacent_siblings() {
+        let (mut loader, groups, connections) = prefetch_fixture();
+        assert_eq!(loader.prefetch_window(), DEFAULT_PREFETCH_WINDOW);
+
+        assert_eq!(loader.prefetch(groups[2].id), 2);
+        loader.wait_for_prefetches();
+
+        let previous = loader.take_prefetched(groups[1].id).unwrap();
+        assert_eq!(previous.group_ids, vec![groups[5].id]);
+        assert_eq!(previous.connection_ids, vec![connections[0].id]);
+        let next = loader.take_prefetched(groups[3].id).unwrap();
+        assert!(next.group_ids.is_empty());
+        assert_eq!(next.connection_ids, vec![connections[1].id]);
+
+        // Outside the window, and taken entries are gone
+        assert!(!loader.is_prefetched(groups[0].id));
+        assert!(!loader.is_prefetched(groups[4].id));
+        assert!(!loader.is_prefetched(groups[1].id));
+    }
+
+    #[test]
+    fn test_prefetch_window_and_loaded_groups() {
+        let (loader, groups, _) = prefetch_fixture();
+        let mut loader = loader.with_prefetch_window(2);
+        loader.mark_group_loaded(groups[1].id);
+
+        assert_eq!(loader.prefetch(groups[2].id), 3);
+        loader.wait_for_prefetches();
+        assert!(loader.is_prefetched(groups[0].id));
+        assert!(!loader.is_prefetched(groups[1].id));
+        assert!(loader.is_prefetched(groups[3].id));
+        assert!(loader.is_prefetched(groups[4].id));
+
+        let mut disabled = LazyGroupLoader::new().with_prefetch_window(0);
+        assert_eq!(disabled.prefetch(groups[2].id), 0);
+        disabled.set_source(groups.clone().into(), Vec::new().into());
+        assert_eq!(disabled.prefetch(groups[2].id), 0);
+    }
+
+    #[test]
+    fn test_prefetch_cancelled_on_collapse() {
+        let (mut loader, groups, _) = prefetch_fixture();
+
+        assert_eq!(loader.prefetch(groups[2].id), 2);
+        loader.mark_group_loaded(groups[2].id);
+        loader.mark_group_unloaded(groups[2].id);
+        loader.wait_for_prefetches();
+
+        // Whatever finished before the cancel was discarded
+        assert!(!loader.is_prefetched(groups[1].id));
+        assert!(!loader.is_prefetched(groups[3].id));
+    }
+
+    #[test]
+    fn test_prefetch_cancel_is_per_group() {
+        let (mut loader, groups, _) = prefetch_fixture();
+
+        assert_eq!(loader.prefetch(groups[0].id), 1);
+        assert_eq!(loader.prefetch(groups[4].id), 1);
+        loader.cancel_prefetch(groups[0].id);
+        loader.wait_for_prefetches();
+
+        assert!(!loader.is_prefetched(groups[1].id));
+        assert!(loader.is_prefetched(groups[3].id));
+
+        // Already prefetched siblings are not queued again, and many
+        // expansions share the one worker
+        assert_eq!(loader.prefetch(groups[2].id), 1);
+        for _ in 0..20 {
+            loader.prefetch(groups[0].id);
+        }
+        loader.wait_for_prefetches();
+        assert!(loader.is_prefetched(groups[1].id));
+
+        loader.reset();
+        assert!(!loader.is_prefetched(groups[3].id));
+    }
+
     #[test]
     fn test_multiple_groups_loaded() {
         let mut loader = LazyGroupLoader::new();
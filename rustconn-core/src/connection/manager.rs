@@ -5,13 +5,14 @@
 
 use std::collections::HashMap;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::config::ConfigManager;
 use crate::error::{ConfigError, ConfigResult};
 use crate::models::{Connection, ConnectionGroup, ProtocolConfig};
 use crate::performance::memory_optimizer;
+use crate::search::{SearchIndex, UsageSort};
 
 /// Manager for connection CRUD operations
 ///
@@ -25,6 +26,8 @@ pub struct ConnectionManager {
     groups: HashMap<Uuid, ConnectionGroup>,
     /// Configuration manager for persistence
     config_manager: ConfigManager,
+    /// Search index kept in step with `connections`
+    search_index: SearchIndex,
 }
 
 impl ConnectionManager {
@@ -46,6 +49,7 @@ impl ConnectionManager {
             Self::intern_connection_strings(conn);
         }
 
+        let search_index = SearchIndex::build(&connections_vec);
         let connections = connections_vec.into_iter().map(|c| (c.id, c)).collect();
 
         let groups = groups_vec.into_iter().map(|g| (g.id, g)).collect();
@@ -54,6 +58,7 @@ impl ConnectionManager {
             connections,
             groups,
             config_manager,
+            search_index,
         })
     }
 
@@ -65,6 +70,7 @@ impl ConnectionManager {
             connections: HashMap::new(),
             groups: HashMap::new(),
             config_manager,
+            search_index: SearchIndex::new(),
         }
     }
 
@@ -100,6 +106,7 @@ impl ConnectionManager {
         Self::intern_connection_strings(&connection);
 
         let id = connection.id;
+        self.search_index.insert(&connection);
         self.connections.insert(id, connection);
         self.persist_connections()?;
 
@@ -120,6 +127,7 @@ impl ConnectionManager {
         Self::intern_connection_strings(&connection);
 
         let id = connection.id;
+        self.search_index.insert(&connection);
         self.connections.insert(id, connection);
         self.persist_connections()?;
 
@@ -157,6 +165,7 @@ impl ConnectionManager {
         // Intern strings for memory efficiency
         Self::intern_connection_strings(&updated);
 
+        self.search_index.update(&updated);
         self.connections.insert(id, updated);
         self.persist_connections()?;
 
@@ -175,6 +184,48 @@ impl ConnectionManager {
                 reason: format!("Connection with ID {id} not found"),
             });
         }
+        self.search_index.remove(id);
+
+        self.persist_connections()?;
+        Ok(())
+    }
+
+    /// Archives a connection
+    ///
+    /// Archived connections are kept with all their settings but are left
+    /// out of [`Self::list_active_connections`], searches without a
+    /// `status:` filter, batch tests and filter-built clusters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection doesn't exist or persistence fails.
+    pub fn archive(&mut self, id: Uuid) -> ConfigResult<()> {
+        self.set_archived(id, true)
+    }
+
+    /// Restores an archived connection to the default listings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection doesn't exist or persistence fails.
+    pub fn unarchive(&mut self, id: Uuid) -> ConfigResult<()> {
+        self.set_archived(id, false)
+    }
+
+    fn set_archived(&mut self, id: Uuid, archived: bool) -> ConfigResult<()> {
+        let conn = self
+            .connections
+            .get_mut(&id)
+            .ok_or_else(|| ConfigError::Validation {
+                field: "id".to_string(),
+                reason: format!("Connection with ID {id} not found"),
+            })?;
+        if conn.archived == archived {
+            return Ok(());
+        }
+
+        conn.archived = archived;
+        conn.touch();
 
         self.persist_connections()?;
         Ok(())
@@ -187,7 +238,13 @@ impl ConnectionManager {
     }
 
     /// Gets a mutable reference to a connection by ID
+    ///
+    /// The connection is marked stale in the search index, since changes
+    /// made through the reference are not seen by it; searches score it
+    /// unconditionally until it is next updated with
+    /// [`Self::update_connection`].
     pub fn get_connection_mut(&mut self, id: Uuid) -> Option<&mut Connection> {
+        self.search_index.mark_stale(id);
         self.connections.get_mut(&id)
     }
 
@@ -197,12 +254,20 @@ impl ConnectionManager {
         self.connections.values().collect()
     }
 
+    /// Lists connections that are not archived
+    #[must_use]
+    pub fn list_active_connections(&self) -> Vec<&Connection> {
+        self.connections.values().filter(|c| !c.archived).collect()
+    }
+
     /// Gets all connections in a specific group
+    ///
+    /// Includes connections listed in the group as an additional group.
     #[must_use]
     pub fn get_by_group(&self, group_id: Uuid) -> Vec<&Connection> {
         self.connections
             .values()
-            .filter(|c| c.group_id == Some(group_id))
+            .filter(|c| c.is_in_group(group_id))
             .collect()
     }
 
@@ -316,10 +381,9 @@ impl ConnectionManager {
 
         let parent_id = group.parent_id;
 
-        // Move connections in this group to ungrouped
+        // Move connections in this group to their next group, or to ungrouped
         for conn in self.connections.values_mut() {
-            if conn.group_id == Some(id) {
-                conn.group_id = None;
+            if conn.remove_from_group(id) {
                 conn.touch();
             }
         }
@@ -371,6 +435,7 @@ impl ConnectionManager {
 
         for conn_id in connections_to_delete {
             self.connections.remove(&conn_id);
+            self.search_index.remove(conn_id);
         }
 
         // Delete all the groups
@@ -407,7 +472,7 @@ impl ConnectionManager {
         let groups = self.collect_descendant_groups(group_id);
         self.connections
             .values()
-            .filter(|conn| conn.group_id.is_some_and(|gid| groups.contains(&gid)))
+            .filter(|conn| groups.iter().any(|&gid| conn.is_in_group(gid)))
             .count()
     }
 
@@ -501,6 +566,8 @@ impl ConnectionManager {
                 })?;
 
         conn.group_id = group_id;
+        conn.additional_group_ids
+            .retain(|&gid| Some(gid) != group_id);
         conn.sort_order = new_sort_order;
         conn.touch();
 
@@ -508,6 +575,63 @@ impl ConnectionManager {
         Ok(())
     }
 
+    /// Moves several connections to a group at once
+    ///
+    /// The connections are appended to the target group in the given order
+    /// and saved once. Nothing is moved if any ID is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_ids` - The connections to move
+    /// * `target_group` - The target group (None for ungrouped)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target group or any connection doesn't exist.
+    pub fn move_to_group(
+        &mut self,
+        connection_ids: &[Uuid],
+        target_group: Option<Uuid>,
+    ) -> ConfigResult<()> {
+        if let Some(gid) = target_group {
+            if !self.groups.contains_key(&gid) {
+                return Err(ConfigError::Validation {
+                    field: "target_group".to_string(),
+                    reason: format!("Group with ID {gid} not found"),
+                });
+            }
+        }
+        if let Some(missing) = connection_ids
+            .iter()
+            .find(|id| !self.connections.contains_key(id))
+        {
+            return Err(ConfigError::Validation {
+                field: "connection_ids".to_string(),
+                reason: format!("Connection with ID {missing} not found"),
+            });
+        }
+
+        let mut next_sort_order = self
+            .connections
+            .values()
+            .filter(|c| c.group_id == target_group && !connection_ids.contains(&c.id))
+            .map(|c| c.sort_order)
+            .max()
+            .map_or(0, |max| max + 1);
+
+        for id in connection_ids {
+            if let Some(conn) = self.connections.get_mut(id) {
+                conn.group_id = target_group;
+                conn.sort_order = next_sort_order;
+                conn.touch();
+                next_sort_order += 1;
+            }
+        }
+
+        self.persist_connections()?;
+        Ok(())
+    }
+
     /// Gets a group by ID
     #[must_use]
     pub fn get_group(&self, id: Uuid) -> Option<&ConnectionGroup> {
@@ -643,6 +767,15 @@ impl ConnectionManager {
         tags
     }
 
+    /// Returns the search index over all connections
+    ///
+    /// The index is updated as connections are created, updated and
+    /// deleted; pass it to [`crate::search::SearchEngine::search_indexed`].
+    #[must_use]
+    pub const fn search_index(&self) -> &SearchIndex {
+        &self.search_index
+    }
+
     // ========== Group Path Utilities ==========
 
     /// Gets the full path of a group (e.g., "Production/Web Servers")
@@ -669,6 +802,19 @@ impl ConnectionManager {
         }
     }
 
+    /// Resolves the jump hosts needed to reach an SSH connection
+    ///
+    /// Returns the connection IDs referenced through `jump_host_id`, first
+    /// hop first. See [`super::resolve_jump_chain`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection or a jump host in its chain does
+    /// not exist, a jump host is not an SSH connection, or the chain loops.
+    pub fn resolve_jump_chain(&self, id: Uuid) -> ConfigResult<Vec<Uuid>> {
+        super::resolve_jump_chain(id, |id| self.connections.get(&id))
+    }
+
     /// Checks if moving a group would create a cycle
     fn would_create_cycle(&self, group_id: Uuid, new_parent_id: Uuid) -> bool {
         // A cycle would be created if new_parent_id is a descendant of group_id
@@ -677,11 +823,16 @@ impl ConnectionManager {
             return true;
         }
 
+        // The visited set stops the walk if the stored hierarchy already loops
+        let mut visited = std::collections::HashSet::new();
         let mut current_id = Some(new_parent_id);
         while let Some(id) = current_id {
             if id == group_id {
                 return true;
             }
+            if !visited.insert(id) {
+                return false;
+            }
             current_id = self.groups.get(&id).and_then(|g| g.parent_id);
         }
 
@@ -737,6 +888,7 @@ impl ConnectionManager {
             Self::intern_connection_strings(conn);
         }
 
+        self.search_index = SearchIndex::build(&connections_vec);
         self.connections = connections_vec.into_iter().map(|c| (c.id, c)).collect();
 
         self.groups = groups_vec.into_iter().map(|g| (g.id, g)).collect();
@@ -1000,6 +1152,52 @@ impl ConnectionManager {
         Ok(())
     }
 
+    /// Records a successful connection
+    ///
+    /// Sets `last_connected` to the current time and increments
+    /// `connect_count`. Call this once the session has actually connected,
+    /// never for attempts that failed. How the session ends is recorded in
+    /// the connection history, not here.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection doesn't exist or persistence fails.
+    pub fn record_connect(&mut self, connection_id: Uuid) -> ConfigResult<()> {
+        self.record_connect_at(connection_id, Utc::now())
+    }
+
+    /// Records a successful connection that was established at `connected_at`
+    ///
+    /// Used when success is only known later, e.g. when a terminal session's
+    /// process exits. Usage is telemetry rather than an edit, so
+    /// `updated_at` is left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection doesn't exist or persistence fails.
+    pub fn record_connect_at(
+        &mut self,
+        connection_id: Uuid,
+        connected_at: DateTime<Utc>,
+    ) -> ConfigResult<()> {
+        let conn =
+            self.connections
+                .get_mut(&connection_id)
+                .ok_or_else(|| ConfigError::Validation {
+                    field: "connection_id".to_string(),
+                    reason: format!("Connection with ID {connection_id} not found"),
+                })?;
+
+        conn.last_connected = Some(
+            conn.last_connected
+                .map_or(connected_at, |last| last.max(connected_at)),
+        );
+        conn.connect_count = conn.connect_count.saturating_add(1);
+
+        self.persist_connections()?;
+        Ok(())
+    }
+
     /// Sorts all connections by `last_connected` timestamp (most recent first)
     ///
     /// Connections with a `last_connected` timestamp are sorted in descending order
@@ -1010,32 +1208,25 @@ impl ConnectionManager {
     ///
     /// Returns an error if persistence fails.
     pub fn sort_by_recent(&mut self) -> ConfigResult<()> {
-        // Get all connection IDs
-        let mut conn_ids: Vec<Uuid> = self.connections.keys().copied().collect();
+        self.sort_by_usage(UsageSort::RecentlyUsed)
+    }
 
-        // Sort by last_connected descending, then by name for those without timestamp
-        conn_ids.sort_by(|a, b| {
-            let conn_a = self.connections.get(a);
-            let conn_b = self.connections.get(b);
-
-            match (
-                conn_a.and_then(|c| c.last_connected),
-                conn_b.and_then(|c| c.last_connected),
-            ) {
-                // Both have timestamps - sort descending (most recent first)
-                (Some(time_a), Some(time_b)) => time_b.cmp(&time_a),
-                // Only a has timestamp - a comes first
-                (Some(_), None) => std::cmp::Ordering::Less,
-                // Only b has timestamp - b comes first
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                // Neither has timestamp - sort by name
-                (None, None) => {
-                    let name_a = conn_a.map(|c| c.name.to_lowercase()).unwrap_or_default();
-                    let name_b = conn_b.map(|c| c.name.to_lowercase()).unwrap_or_default();
-                    name_a.cmp(&name_b)
-                }
-            }
-        });
+    /// Sorts all connections by `connect_count` (most used first)
+    ///
+    /// Ties are ordered by `last_connected`, then by name. Connections that
+    /// were never used are placed at the end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if persistence fails.
+    pub fn sort_by_most_used(&mut self) -> ConfigResult<()> {
+        self.sort_by_usage(UsageSort::MostUsed)
+    }
+
+    /// Rewrites `sort_order` for all connections using a usage ordering
+    fn sort_by_usage(&mut self, sort: UsageSort) -> ConfigResult<()> {
+        let mut conn_ids: Vec<Uuid> = self.connections.keys().copied().collect();
+        conn_ids.sort_by(|a, b| sort.compare(&self.connections[a], &self.connections[b]));
 
         // Update sort_order for all connections
         for (idx, conn_id) in conn_ids.iter().enumerate() {
@@ -1385,6 +1576,53 @@ mod tests {
         assert_eq!(conn.port, 22);
     }
 
+    #[test]
+    fn test_record_connect_and_sort_by_most_used() {
+        let (mut manager, _temp) = create_test_manager();
+        let ids: Vec<Uuid> = ["alpha", "beta", "gamma"]
+            .iter()
+            .map(|name| {
+                manager
+                    .create_connection(
+                        (*name).to_string(),
+                        "example.com".to_string(),
+                        22,
+                        ProtocolConfig::Ssh(SshConfig::default()),
+                    )
+                    .unwrap()
+            })
+            .collect();
+
+        let updated_at = manager.get_connection(ids[2]).unwrap().updated_at;
+        manager.record_connect(ids[2]).unwrap();
+        manager.record_connect(ids[2]).unwrap();
+        manager.record_connect(ids[1]).unwrap();
+        assert!(manager.record_connect(Uuid::new_v4()).is_err());
+
+        let gamma = manager.get_connection(ids[2]).unwrap();
+        assert_eq!(gamma.connect_count, 2);
+        assert!(gamma.last_connected.is_some());
+        // Usage is not an edit
+        assert_eq!(gamma.updated_at, updated_at);
+
+        // A session that started earlier doesn't move last_connected back
+        let latest = gamma.last_connected;
+        manager
+            .record_connect_at(ids[2], Utc::now() - chrono::Duration::hours(1))
+            .unwrap();
+        let gamma = manager.get_connection(ids[2]).unwrap();
+        assert_eq!(gamma.connect_count, 3);
+        assert_eq!(gamma.last_connected, latest);
+        assert_eq!(manager.get_connection(ids[0]).unwrap().connect_count, 0);
+
+        manager.sort_by_most_used().unwrap();
+        let order: Vec<i32> = ids
+            .iter()
+            .map(|id| manager.get_connection(*id).unwrap().sort_order)
+            .collect();
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+
     #[test]
     fn test_update_connection() {
         let (mut manager, _temp) = create_test_manager();
@@ -1428,6 +1666,72 @@ mod tests {
         assert!(manager.get_connection(id).is_none());
     }
 
+    #[test]
+    fn test_search_index_follows_changes() {
+        use crate::search::{SearchEngine, SearchQuery};
+
+        let (mut manager, _temp) = create_test_manager();
+        let id = manager
+            .create_connection(
+                "Test Server".to_string(),
+                "example.com".to_string(),
+                22,
+                ProtocolConfig::Ssh(SshConfig::default()),
+            )
+            .unwrap();
+        assert!(manager.search_index().contains(id));
+
+        let mut updated = manager.get_connection(id).unwrap().clone();
+        updated.name = "walrus".to_string();
+        manager.update_connection(id, updated).unwrap();
+
+        let search = |manager: &ConnectionManager, text: &str| {
+            SearchEngine::new().search_indexed(
+                &SearchQuery::with_text(text),
+                |id| manager.get_connection(id),
+                &[],
+                manager.search_index(),
+            )
+        };
+        let results = search(&manager, "walrus");
+        assert_eq!(results.len(), 1);
+        assert!((results[0].score - 1.0).abs() < f32::EPSILON);
+
+        // Connections changed in place are scored until re-indexed
+        manager.get_connection_mut(id).unwrap().name = "otter".to_string();
+        assert!(search(&manager, "walrus").is_empty());
+        assert_eq!(search(&manager, "otter").len(), 1);
+
+        manager.delete_connection(id).unwrap();
+        assert!(!manager.search_index().contains(id));
+        assert!(manager.search_index().is_empty());
+    }
+
+    #[test]
+    fn test_archive_and_unarchive() {
+        let (mut manager, _temp) = create_test_manager();
+        let id = manager
+            .create_connection(
+                "Old Server".to_string(),
+                "old.example.com".to_string(),
+                22,
+                ProtocolConfig::Ssh(SshConfig::default()),
+            )
+            .unwrap();
+
+        manager.archive(id).unwrap();
+        assert!(manager.get_connection(id).unwrap().archived);
+        assert!(manager.list_active_connections().is_empty());
+        assert_eq!(manager.list_connections().len(), 1);
+        assert!(manager.archive(Uuid::new_v4()).is_err());
+
+        let reloaded = manager.config_manager.load_connections().unwrap();
+        assert!(reloaded[0].archived);
+
+        manager.unarchive(id).unwrap();
+        assert_eq!(manager.list_active_connections().len(), 1);
+    }
+
     #[test]
     fn test_create_group() {
         let (mut manager, _temp) = create_test_manager();
@@ -1479,6 +1783,28 @@ mod tests {
         assert!(manager.get_connection(conn_id).unwrap().group_id.is_none());
     }
 
+    #[test]
+    fn test_additional_group_membership() {
+        let (mut manager, _temp) = create_test_manager();
+
+        let web = manager.create_group("Web".to_string()).unwrap();
+        let monitoring = manager.create_group("Monitoring".to_string()).unwrap();
+        let mut conn = Connection::new_ssh("shared".to_string(), "shared.example".to_string(), 22);
+        conn.group_id = Some(web);
+        conn.additional_group_ids = vec![monitoring];
+        let conn_id = manager.create_connection_from(conn).unwrap();
+
+        assert_eq!(manager.get_by_group(web).len(), 1);
+        assert_eq!(manager.get_by_group(monitoring).len(), 1);
+        assert_eq!(manager.count_connections_in_group(monitoring), 1);
+
+        // Deleting the primary group promotes the additional one
+        manager.delete_group(web).unwrap();
+        let conn = manager.get_connection(conn_id).unwrap();
+        assert_eq!(conn.group_id, Some(monitoring));
+        assert!(conn.additional_group_ids.is_empty());
+    }
+
     #[test]
     fn test_move_group_prevents_cycle() {
         let (mut manager, _temp) = create_test_manager();
@@ -1493,6 +1819,73 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_move_group_rejects_deep_cycle() {
+        let (mut manager, _temp) = create_test_manager();
+
+        let root_id = manager.create_group("Root".to_string()).unwrap();
+        let child_id = manager
+            .create_group_with_parent("Child".to_string(), root_id)
+            .unwrap();
+        let grandchild_id = manager
+            .create_group_with_parent("Grandchild".to_string(), child_id)
+            .unwrap();
+        let other_id = manager.create_group("Other".to_string()).unwrap();
+
+        assert!(manager.move_group(root_id, Some(grandchild_id)).is_err());
+        assert!(manager.move_group(root_id, Some(root_id)).is_err());
+        assert_eq!(manager.get_group(root_id).unwrap().parent_id, None);
+
+        manager.move_group(child_id, Some(other_id)).unwrap();
+        assert_eq!(
+            manager.get_group(child_id).unwrap().parent_id,
+            Some(other_id)
+        );
+        assert!(manager.validate_hierarchy());
+
+        manager.move_group(child_id, None).unwrap();
+        assert_eq!(manager.get_group(child_id).unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn test_move_to_group_moves_all_or_nothing() {
+        let (mut manager, _temp) = create_test_manager();
+
+        let group_id = manager.create_group("Production".to_string()).unwrap();
+        let ids: Vec<Uuid> = (0..3)
+            .map(|i| {
+                manager
+                    .create_connection(
+                        format!("Server {i}"),
+                        format!("host{i}.example.com"),
+                        22,
+                        ProtocolConfig::Ssh(SshConfig::default()),
+                    )
+                    .unwrap()
+            })
+            .collect();
+
+        // An unknown ID rejects the whole move
+        let result = manager.move_to_group(&[ids[0], Uuid::new_v4()], Some(group_id));
+        assert!(result.is_err());
+        assert!(manager.get_connection(ids[0]).unwrap().group_id.is_none());
+        assert!(manager.move_to_group(&ids, Some(Uuid::new_v4())).is_err());
+
+        manager
+            .move_to_group(&[ids[2], ids[0]], Some(group_id))
+            .unwrap();
+        let moved: Vec<_> = [ids[2], ids[0]]
+            .iter()
+            .map(|id| manager.get_connection(*id).unwrap())
+            .collect();
+        assert!(moved.iter().all(|c| c.group_id == Some(group_id)));
+        assert!(moved[0].sort_order < moved[1].sort_order);
+        assert_eq!(manager.get_by_group(group_id).len(), 2);
+
+        manager.move_to_group(&ids, None).unwrap();
+        assert_eq!(manager.get_ungrouped().len(), 3);
+    }
+
     #[test]
     fn test_search_by_name() {
         let (mut manager, _temp) = create_test_manager();
@@ -8,18 +8,25 @@
 //! The module also includes string interning utilities for memory optimization
 //! when dealing with large numbers of connections, and virtual scrolling helpers
 //! for efficient rendering of large connection lists.
+//!
+//! SSH jump host chains are resolved by [`resolve_jump_chain`].
 
+mod host;
 mod interning;
+mod jump;
 mod lazy_loader;
 mod manager;
 mod port_check;
 mod virtual_scroll;
 
+pub use host::{host_for_uri, unbracketed_host, validate_host, HostError};
 pub use interning::{
     check_interning_stats, get_interning_stats, intern_connection_strings, intern_hostname,
     intern_protocol_name, intern_username, log_interning_stats, log_interning_stats_with_warning,
+    HIGH_EVICTION_CHURN,
 };
-pub use lazy_loader::LazyGroupLoader;
+pub use jump::{jump_host_spec, proxy_jump_arg, resolve_jump_chain};
+pub use lazy_loader::{LazyGroupLoader, PrefetchedChildren, DEFAULT_PREFETCH_WINDOW};
 pub use manager::ConnectionManager;
 pub use port_check::{check_port, check_port_async, PortCheckError, PortCheckResult};
 pub use virtual_scroll::{SelectionState, VirtualScrollConfig};
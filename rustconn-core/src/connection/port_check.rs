@@ -58,7 +58,7 @@ pub fn check_port(
     timeout_secs: u32,
 ) -> Result<PortCheckResult, PortCheckError> {
     let timeout = Duration::from_secs(u64::from(timeout_secs));
-    let addr_str = format!("{host}:{port}");
+    let addr_str = format!("{}:{port}", super::host_for_uri(host));
 
     // Resolve hostname to socket addresses
     let addrs: Vec<SocketAddr> = addr_str
@@ -117,7 +117,7 @@ pub async fn check_port_async(
     timeout_secs: u32,
 ) -> Result<PortCheckResult, PortCheckError> {
     let timeout = Duration::from_secs(u64::from(timeout_secs));
-    let addr_str = format!("{host}:{port}");
+    let addr_str = format!("{}:{port}", super::host_for_uri(host));
 
     // Resolve hostname (blocking, but fast)
     let addrs: Vec<SocketAddr> = addr_str
@@ -113,6 +113,8 @@ pub struct VirtualScrollConfig {
     pub overscan: usize,
     /// Estimated height of each item in pixels
     pub item_height: f64,
+    /// Estimated height of group header rows in pixels
+    pub group_header_height: f64,
 }
 
 impl Default for VirtualScrollConfig {
@@ -121,6 +123,7 @@ impl Default for VirtualScrollConfig {
             threshold: 100,
             overscan: 5,
             item_height: 30.0,
+            group_header_height: 30.0,
         }
     }
 }
@@ -147,6 +150,23 @@ impl VirtualScrollConfig {
         self
     }
 
+    /// Creates a new configuration with custom group header height
+    #[must_use]
+    pub const fn with_group_header_height(mut self, height: f64) -> Self {
+        self.group_header_height = height;
+        self
+    }
+
+    /// Returns the estimated height of a group header or connection row
+    #[must_use]
+    pub const fn row_height(&self, is_group: bool) -> f64 {
+        if is_group {
+            self.group_header_height
+        } else {
+            self.item_height
+        }
+    }
+
     /// Returns whether virtual scrolling should be enabled for the given item count
     #[must_use]
     pub const fn should_enable(&self, item_count: usize) -> bool {
@@ -1,7 +1,8 @@
 //! Dashboard data types for session monitoring
 //!
 //! This module provides core data types for the connection dashboard,
-//! including session statistics and filtering capabilities.
+//! including session statistics and filtering capabilities, and renders
+//! them as Prometheus metrics.
 //!
 //! **Validates: Requirements 13.1, 13.2, 13.5**
 
@@ -11,6 +12,10 @@ use uuid::Uuid;
 
 use crate::session::SessionState;
 
+mod prometheus;
+
+pub use prometheus::{prometheus_export, write_prometheus};
+
 /// Session statistics for dashboard display
 /// **Validates: Requirements 13.2**
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,7 +125,7 @@ impl SessionStats {
             SessionState::Starting => "Starting",
             SessionState::Active => "Connected",
             SessionState::Disconnecting => "Disconnecting",
-            SessionState::Terminated => "Disconnected",
+            SessionState::Terminated | SessionState::Disconnected => "Disconnected",
             SessionState::Error => "Error",
         }
     }
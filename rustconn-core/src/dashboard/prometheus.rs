@@ -0,0 +1,294 @@
+//! Prometheus text exposition of dashboard and performance metrics
+//!
+//! Renders session counts, per-protocol traffic and operation timings in the
+//! Prometheus text format (version 0.0.4), so that an instance running on a
+//! bastion host can be scraped. All metric names start with `rustconn_`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use super::SessionStats;
+use crate::performance::{metrics, PerformanceMetrics};
+use crate::session::SessionState;
+
+/// Session states in the order their series are written
+const STATES: [SessionState; 6] = [
+    SessionState::Starting,
+    SessionState::Active,
+    SessionState::Disconnecting,
+    SessionState::Terminated,
+    SessionState::Disconnected,
+    SessionState::Error,
+];
+
+/// Per-protocol totals over the current sessions
+#[derive(Default)]
+struct ProtocolTotals {
+    /// Session count per entry of [`STATES`]
+    states: [u64; STATES.len()],
+    bytes_sent: u64,
+    bytes_received: u64,
+}
+
+/// Renders session and operation metrics in Prometheus text format
+///
+/// Operation timings come from the global [`metrics()`] collector, which only
+/// records while profiling is enabled.
+#[must_use]
+pub fn prometheus_export(sessions: &[SessionStats]) -> String {
+    let mut out = String::with_capacity(2048);
+    write_prometheus(&mut out, sessions, metrics());
+    out
+}
+
+/// Appends session and operation metrics in Prometheus text format to `out`
+///
+/// Reusing `out` across scrapes avoids reallocating the buffer; besides that
+/// only one small entry per protocol is allocated.
+pub fn write_prometheus(out: &mut String, sessions: &[SessionStats], metrics: &PerformanceMetrics) {
+    write_session_metrics(out, sessions);
+    write_operation_metrics(out, metrics);
+}
+
+fn write_session_metrics(out: &mut String, sessions: &[SessionStats]) {
+    let mut protocols: BTreeMap<&str, ProtocolTotals> = BTreeMap::new();
+    for session in sessions {
+        let totals = protocols.entry(session.protocol.as_str()).or_default();
+        totals.states[state_index(session.state)] += 1;
+        totals.bytes_sent = totals.bytes_sent.saturating_add(session.bytes_sent);
+        totals.bytes_received = totals.bytes_received.saturating_add(session.bytes_received);
+    }
+
+    header(
+        out,
+        "rustconn_sessions_active",
+        "gauge",
+        "Sessions currently connected",
+    );
+    for (protocol, totals) in &protocols {
+        sample(
+            out,
+            "rustconn_sessions_active",
+            &[("protocol", protocol)],
+            totals.states[state_index(SessionState::Active)],
+        );
+    }
+
+    header(
+        out,
+        "rustconn_sessions",
+        "gauge",
+        "Sessions by protocol and state",
+    );
+    for (protocol, totals) in &protocols {
+        for (state, count) in STATES.iter().zip(totals.states) {
+            if count > 0 {
+                sample(
+                    out,
+                    "rustconn_sessions",
+                    &[("protocol", protocol), ("state", state_label(*state))],
+                    count,
+                );
+            }
+        }
+    }
+
+    header(
+        out,
+        "rustconn_session_sent_bytes",
+        "gauge",
+        "Bytes sent by current sessions",
+    );
+    for (protocol, totals) in &protocols {
+        sample(
+            out,
+            "rustconn_session_sent_bytes",
+            &[("protocol", protocol)],
+            totals.bytes_sent,
+        );
+    }
+
+    header(
+        out,
+        "rustconn_session_received_bytes",
+        "gauge",
+        "Bytes received by current sessions",
+    );
+    for (protocol, totals) in &protocols {
+        sample(
+            out,
+            "rustconn_session_received_bytes",
+            &[("protocol", protocol)],
+            totals.bytes_received,
+        );
+    }
+}
+
+fn write_operation_metrics(out: &mut String, metrics: &PerformanceMetrics) {
+    header(
+        out,
+        "rustconn_operation_duration_seconds",
+        "summary",
+        "Duration of timed operations such as search",
+    );
+    metrics.for_each_operation(|operation, durations| {
+        let labels = [("operation", operation)];
+        let total: Duration = durations.iter().sum();
+        sample(
+            out,
+            "rustconn_operation_duration_seconds_sum",
+            &labels,
+            total.as_secs_f64(),
+        );
+        sample(
+            out,
+            "rustconn_operation_duration_seconds_count",
+            &labels,
+            durations.len(),
+        );
+    });
+
+    header(
+        out,
+        "rustconn_operation_duration_max_seconds",
+        "gauge",
+        "Longest recorded duration per operation",
+    );
+    metrics.for_each_operation(|operation, durations| {
+        let max = durations.iter().max().copied().unwrap_or_default();
+        sample(
+            out,
+            "rustconn_operation_duration_max_seconds",
+            &[("operation", operation)],
+            max.as_secs_f64(),
+        );
+    });
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: impl std::fmt::Display) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (i, (key, label)) in labels.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(key);
+            out.push_str("=\"");
+            push_escaped(out, label);
+            out.push('"');
+        }
+        out.push('}');
+    }
+    let _ = writeln!(out, " {value}");
+}
+
+/// Escapes a label value as required by the exposition format
+fn push_escaped(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+}
+
+const fn state_index(state: SessionState) -> usize {
+    match state {
+        SessionState::Starting => 0,
+        SessionState::Active => 1,
+        SessionState::Disconnecting => 2,
+        SessionState::Terminated => 3,
+        SessionState::Disconnected => 4,
+        SessionState::Error => 5,
+    }
+}
+
+const fn state_label(state: SessionState) -> &'static str {
+    match state {
+        SessionState::Starting => "starting",
+        SessionState::Active => "active",
+        SessionState::Disconnecting => "disconnecting",
+        SessionState::Terminated => "terminated",
+        SessionState::Disconnected => "disconnected",
+        SessionState::Error => "error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn session(protocol: &str, state: SessionState, sent: u64, received: u64) -> SessionStats {
+        let mut session = SessionStats::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Test".to_string(),
+            protocol.to_string(),
+            "localhost".to_string(),
+        );
+        session.state = state;
+        session.bytes_sent = sent;
+        session.bytes_received = received;
+        session
+    }
+
+    #[test]
+    fn test_session_metrics() {
+        let sessions = [
+            session("ssh", SessionState::Active, 100, 1000),
+            session("ssh", SessionState::Active, 50, 500),
+            session("ssh", SessionState::Error, 0, 0),
+            session("rdp", SessionState::Starting, 10, 20),
+        ];
+        let mut out = String::new();
+        write_prometheus(&mut out, &sessions, &PerformanceMetrics::new());
+
+        let lines: Vec<&str> = out.lines().collect();
+        for expected in [
+            "# TYPE rustconn_sessions_active gauge",
+            "rustconn_sessions_active{protocol=\"rdp\"} 0",
+            "rustconn_sessions_active{protocol=\"ssh\"} 2",
+            "rustconn_sessions{protocol=\"ssh\",state=\"active\"} 2",
+            "rustconn_sessions{protocol=\"ssh\",state=\"error\"} 1",
+            "rustconn_sessions{protocol=\"rdp\",state=\"starting\"} 1",
+            "rustconn_session_sent_bytes{protocol=\"ssh\"} 150",
+            "rustconn_session_received_bytes{protocol=\"rdp\"} 20",
+        ] {
+            assert!(lines.contains(&expected), "missing {expected:?} in\n{out}");
+        }
+        assert!(!out.contains("state=\"terminated\""));
+    }
+
+    #[test]
+    fn test_operation_metrics_and_escaping() {
+        let metrics = PerformanceMetrics::new();
+        metrics.set_profiling_enabled(true);
+        metrics.record_operation("search", Duration::from_millis(250));
+        metrics.record_operation("search", Duration::from_millis(750));
+        metrics.record_operation("say \"hi\"\\", Duration::from_secs(2));
+
+        let mut out = String::new();
+        write_prometheus(&mut out, &[], &metrics);
+
+        let lines: Vec<&str> = out.lines().collect();
+        for expected in [
+            "# TYPE rustconn_operation_duration_seconds summary",
+            "rustconn_operation_duration_seconds_sum{operation=\"search\"} 1",
+            "rustconn_operation_duration_seconds_count{operation=\"search\"} 2",
+            "rustconn_operation_duration_max_seconds{operation=\"search\"} 0.75",
+            "rustconn_operation_duration_seconds_count{operation=\"say \\\"hi\\\"\\\\\"} 1",
+        ] {
+            assert!(lines.contains(&expected), "missing {expected:?} in\n{out}");
+        }
+    }
+}
@@ -11,6 +11,8 @@
 //! - Password-based encryption for document protection
 //! - Export/import for portable sharing
 //! - Dirty state tracking for unsaved changes
+//! - Markdown notes attached to a specific connection
+//! - Full-text search across document names, descriptions and notes
 //!
 //! # Example
 //!
@@ -37,6 +39,10 @@ use uuid::Uuid;
 use crate::models::{Connection, ConnectionGroup, ConnectionTemplate};
 use crate::variables::Variable;
 
+mod search;
+
+pub use search::DocumentMatch;
+
 /// Errors that can occur during document operations
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum DocumentError {
@@ -77,7 +83,11 @@ pub enum DocumentError {
 pub type DocumentResult<T> = std::result::Result<T, DocumentError>;
 
 /// Document format version for compatibility
-pub const DOCUMENT_FORMAT_VERSION: u32 = 1;
+///
+/// Version history:
+/// - 1: initial format
+/// - 2: optional `connection_id` and `notes` for connection-attached notes
+pub const DOCUMENT_FORMAT_VERSION: u32 = 2;
 
 /// Magic bytes for identifying encrypted documents
 const ENCRYPTED_MAGIC: &[u8] = b"RCDB_ENC";
@@ -95,6 +105,12 @@ pub struct Document {
     /// Optional description
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Connection this document is attached to (e.g. runbook notes for a host)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<Uuid>,
+    /// Markdown notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
     /// Connections contained in this document
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub connections: Vec<Connection>,
@@ -129,6 +145,8 @@ impl Document {
             id: Uuid::new_v4(),
             name: name.into(),
             description: None,
+            connection_id: None,
+            notes: None,
             connections: Vec::new(),
             groups: Vec::new(),
             variables: HashMap::new(),
@@ -146,6 +164,20 @@ impl Document {
         self
     }
 
+    /// Attaches this document to a connection
+    #[must_use]
+    pub const fn with_connection(mut self, connection_id: Uuid) -> Self {
+        self.connection_id = Some(connection_id);
+        self
+    }
+
+    /// Sets the markdown notes for this document
+    #[must_use]
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
     /// Adds a connection to this document
     pub fn add_connection(&mut self, connection: Connection) {
         self.connections.push(connection);
@@ -290,11 +322,15 @@ impl Document {
 
     /// Deserializes a document from JSON
     ///
+    /// Documents written by older versions are migrated to the current format.
+    ///
     /// # Errors
     ///
     /// Returns `DocumentError::ParseError` if parsing fails
     pub fn from_json(json: &str) -> DocumentResult<Self> {
-        serde_json::from_str(json).map_err(|e| DocumentError::ParseError(e.to_string()))
+        serde_json::from_str(json)
+            .map(Self::migrate)
+            .map_err(|e| DocumentError::ParseError(e.to_string()))
     }
 
     /// Serializes the document to YAML
@@ -312,7 +348,19 @@ impl Document {
     ///
     /// Returns `DocumentError::ParseError` if parsing fails
     pub fn from_yaml(yaml: &str) -> DocumentResult<Self> {
-        serde_yaml::from_str(yaml).map_err(|e| DocumentError::ParseError(e.to_string()))
+        serde_yaml::from_str(yaml)
+            .map(Self::migrate)
+            .map_err(|e| DocumentError::ParseError(e.to_string()))
+    }
+
+    /// Migrates a document from an older format version to the current one
+    const fn migrate(mut self) -> Self {
+        // v1 -> v2: `connection_id` and `notes` were added; serde already
+        // defaults them to `None`, so only the version needs updating
+        if self.format_version < DOCUMENT_FORMAT_VERSION {
+            self.format_version = DOCUMENT_FORMAT_VERSION;
+        }
+        self
     }
 }
 
@@ -531,6 +579,27 @@ impl DocumentManager {
             .collect()
     }
 
+    /// Returns documents attached to the given connection, sorted by name
+    #[must_use]
+    pub fn documents_for_connection(&self, connection_id: Uuid) -> Vec<&Document> {
+        let mut documents: Vec<&Document> = self
+            .documents
+            .values()
+            .filter(|d| d.connection_id == Some(connection_id))
+            .collect();
+        documents.sort_by(|a, b| a.name.cmp(&b.name));
+        documents
+    }
+
+    /// Searches the names, descriptions and notes of all documents
+    ///
+    /// Returns at most `max_results` matches sorted by relevance, highest
+    /// first. Matching notes yield the best matching line as a snippet.
+    #[must_use]
+    pub fn search(&self, query: &str, max_results: usize) -> Vec<DocumentMatch> {
+        search::search(self.documents.values(), query, max_results)
+    }
+
     /// Inserts a document directly into the manager
     ///
     /// This is primarily useful for testing. The document is marked as dirty.
@@ -767,6 +836,52 @@ mod tests {
         assert_eq!(doc.name, parsed.name);
     }
 
+    #[test]
+    fn test_document_v1_migration() {
+        let json = r#"{
+            "id": "3f1c9a52-7d0e-4b8a-9c61-2f4e5d6a7b80",
+            "name": "Legacy",
+            "created_at": "2024-01-01T00:00:00Z",
+            "modified_at": "2024-01-01T00:00:00Z",
+            "format_version": 1
+        }"#;
+
+        let doc = Document::from_json(json).unwrap();
+        assert_eq!(doc.format_version, DOCUMENT_FORMAT_VERSION);
+        assert!(doc.connection_id.is_none());
+        assert!(doc.notes.is_none());
+    }
+
+    #[test]
+    fn test_document_manager_documents_for_connection() {
+        let mut manager = DocumentManager::new();
+        let host_id = Uuid::new_v4();
+        let runbook = manager.insert(
+            Document::new("Runbook")
+                .with_connection(host_id)
+                .with_notes("# Restart\n`systemctl restart app`"),
+        );
+        let contacts = manager.insert(Document::new("Contacts").with_connection(host_id));
+        manager.insert(Document::new("Other").with_connection(Uuid::new_v4()));
+        manager.insert(Document::new("Unattached"));
+
+        let ids: Vec<Uuid> = manager
+            .documents_for_connection(host_id)
+            .iter()
+            .map(|d| d.id)
+            .collect();
+        assert_eq!(ids, vec![contacts, runbook]);
+        assert!(manager.documents_for_connection(Uuid::new_v4()).is_empty());
+
+        let parsed =
+            Document::from_json(&manager.get(runbook).unwrap().to_json().unwrap()).unwrap();
+        assert_eq!(parsed.connection_id, Some(host_id));
+        assert_eq!(
+            parsed.notes.as_deref(),
+            Some("# Restart\n`systemctl restart app`")
+        );
+    }
+
     #[test]
     fn test_document_manager_create() {
         let mut manager = DocumentManager::new();
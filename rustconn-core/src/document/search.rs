@@ -0,0 +1,285 @@
+//! Full-text search across documents
+//!
+//! Queries are split into terms and each term is fuzzy-matched against the
+//! words of a document's name, description and notes with the connection
+//! search matcher. A field scores by how many terms it matches and how
+//! well, so a question like "how do I rotate the TLS cert" finds the note
+//! line that mentions rotating the certificate. Notes are scored line by
+//! line and the best line is returned as a snippet.
+
+use uuid::Uuid;
+
+use super::Document;
+use crate::search::{MatchHighlight, SearchEngine};
+
+/// Lowest fuzzy score for a term to count as matching a word
+///
+/// Character-level fuzzy matches score below 0.5, so this accepts words
+/// containing most of the term's characters in order but not ones sharing
+/// a single letter.
+const MIN_TERM_SCORE: f32 = 0.4;
+
+/// Maximum length of a snippet in bytes
+const MAX_SNIPPET_LEN: usize = 160;
+
+/// Bytes of context kept before the first highlight of a shortened snippet
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Byte ranges of matched words in a text
+type Ranges = Vec<(usize, usize)>;
+
+/// A document matching a full-text search
+#[derive(Debug, Clone)]
+pub struct DocumentMatch {
+    /// The ID of the matching document
+    pub document_id: Uuid,
+    /// Relevance score (0.0 to 1.0, higher is more relevant)
+    pub score: f32,
+    /// The best matching line of the notes, if the notes matched
+    pub snippet: Option<String>,
+    /// Highlight positions for matched text
+    ///
+    /// Positions of `name` and `description` highlights index the field
+    /// value; positions of `notes` highlights index [`Self::snippet`].
+    pub highlights: Vec<MatchHighlight>,
+}
+
+/// Searches documents and returns at most `max_results` matches, most
+/// relevant first
+pub(super) fn search<'a>(
+    documents: impl Iterator<Item = &'a Document>,
+    query: &str,
+    max_results: usize,
+) -> Vec<DocumentMatch> {
+    let terms = query_terms(query);
+    if terms.is_empty() || max_results == 0 {
+        return Vec::new();
+    }
+
+    let engine = SearchEngine::new();
+    let mut matches: Vec<(&Document, DocumentMatch)> = documents
+        .filter_map(|doc| score_document(&engine, &terms, doc).map(|m| (doc, m)))
+        .collect();
+
+    // Sort by score descending; documents are unordered, so break ties by name
+    matches.sort_by(|(a_doc, a), (b_doc, b)| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a_doc.name.cmp(&b_doc.name))
+            .then_with(|| a_doc.id.cmp(&b_doc.id))
+    });
+    matches.truncate(max_results);
+    matches.into_iter().map(|(_, m)| m).collect()
+}
+
+/// Splits a query into search terms
+///
+/// Single-character terms are dropped unless nothing else is left, since
+/// they match nearly every text.
+fn query_terms(query: &str) -> Vec<&str> {
+    let words: Vec<&str> = words(query).into_iter().map(|(_, w)| w).collect();
+    if words.iter().any(|w| w.chars().nth(1).is_some()) {
+        words
+            .into_iter()
+            .filter(|w| w.chars().nth(1).is_some())
+            .collect()
+    } else {
+        words
+    }
+}
+
+/// Splits text into alphanumeric words with their byte offsets
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s, &text[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, &text[s..]));
+    }
+    words
+}
+
+fn score_document(engine: &SearchEngine, terms: &[&str], doc: &Document) -> Option<DocumentMatch> {
+    let mut result = DocumentMatch {
+        document_id: doc.id,
+        score: 0.0,
+        snippet: None,
+        highlights: Vec::new(),
+    };
+
+    // Score against name (highest weight)
+    if let Some((score, highlights)) = score_text(engine, terms, &doc.name) {
+        result.score = result.score.max(score);
+        result.highlights.extend(
+            highlights
+                .into_iter()
+                .map(|(s, e)| MatchHighlight::new("name", s, e)),
+        );
+    }
+
+    if let Some(description) = doc.description.as_deref() {
+        if let Some((score, highlights)) = score_text(engine, terms, description) {
+            result.score = result.score.max(score * 0.8);
+            result.highlights.extend(
+                highlights
+                    .into_iter()
+                    .map(|(s, e)| MatchHighlight::new("description", s, e)),
+            );
+        }
+    }
+
+    // Score notes line by line and keep the best line as the snippet
+    let mut best_line: Option<(&str, f32, Ranges)> = None;
+    for line in doc.notes.as_deref().unwrap_or_default().lines() {
+        if let Some((score, highlights)) = score_text(engine, terms, line) {
+            // Keep the first of equally good lines
+            if best_line.as_ref().is_none_or(|(_, best, _)| score > *best) {
+                best_line = Some((line, score, highlights));
+            }
+        }
+    }
+    if let Some((line, score, highlights)) = best_line {
+        result.score = result.score.max(score * 0.7);
+        let (snippet, highlights) = snippet(line, &highlights);
+        result.snippet = Some(snippet);
+        result.highlights.extend(
+            highlights
+                .into_iter()
+                .map(|(s, e)| MatchHighlight::new("notes", s, e)),
+        );
+    }
+
+    (result.score > 0.0).then_some(result)
+}
+
+/// Scores `text` against the query terms
+///
+/// Each term takes the best fuzzy score among the words of the text, and
+/// the text scores the mean over all terms. Returns the score and the
+/// byte ranges of the matched words, or `None` if no term matches.
+fn score_text(engine: &SearchEngine, terms: &[&str], text: &str) -> Option<(f32, Ranges)> {
+    let text_words = words(text);
+    let mut total = 0.0;
+    let mut highlights = Vec::new();
+    for term in terms {
+        let best = text_words
+            .iter()
+            .map(|&(start, word)| (engine.fuzzy_score(term, word), start, word.len()))
+            .filter(|(score, _, _)| *score >= MIN_TERM_SCORE)
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some((score, start, len)) = best {
+            total += score;
+            highlights.push((start, start + len));
+        }
+    }
+    if highlights.is_empty() {
+        return None;
+    }
+
+    highlights.sort_unstable();
+    highlights.dedup();
+    #[allow(clippy::cast_precision_loss)]
+    let score = total / terms.len() as f32;
+    Some((score, highlights))
+}
+
+/// Trims a line to at most [`MAX_SNIPPET_LEN`] bytes around its first
+/// highlight and shifts the highlights to match
+fn snippet(line: &str, highlights: &[(usize, usize)]) -> (String, Ranges) {
+    let trimmed = line.trim_start();
+    let offset = line.len() - trimmed.len();
+    let line = trimmed.trim_end();
+
+    let first = highlights
+        .first()
+        .map_or(0, |(s, _)| s.saturating_sub(offset));
+    let mut start = if line.len() > MAX_SNIPPET_LEN {
+        first.saturating_sub(SNIPPET_CONTEXT)
+    } else {
+        0
+    };
+    while !line.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (start + MAX_SNIPPET_LEN).min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let base = offset + start;
+    let shifted = highlights
+        .iter()
+        .filter(|(s, e)| *s >= base && *e <= offset + end)
+        .map(|(s, e)| (s - base, e - base))
+        .collect();
+    (line[start..end].to_string(), shifted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runbooks() -> Vec<Document> {
+        vec![
+            Document::new("TLS").with_notes(
+                "# Certificates\n\nRenew the Let's Encrypt cert with certbot.\n\
+                 To rotate the TLS cert, run `rotate-certs.sh` on each web node.",
+            ),
+            Document::new("Database failover")
+                .with_description("Postgres primary switch")
+                .with_notes("Promote the replica with pg_ctl promote."),
+            Document::new("Certificate inventory"),
+        ]
+    }
+
+    #[test]
+    fn test_search_ranks_and_snippets() {
+        let docs = runbooks();
+        let matches = search(docs.iter(), "how do I rotate the TLS cert", 10);
+
+        assert_eq!(matches[0].document_id, docs[0].id);
+        let snippet = matches[0].snippet.as_deref().unwrap();
+        assert!(snippet.starts_with("To rotate the TLS cert"), "{snippet}");
+        let notes: Vec<&str> = matches[0]
+            .highlights
+            .iter()
+            .filter(|h| h.field == "notes")
+            .map(|h| &snippet[h.start..h.end])
+            .collect();
+        assert!(notes.contains(&"rotate"));
+        assert!(notes.contains(&"TLS"));
+
+        let matches = search(docs.iter(), "postgres", 10);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].highlights[0].field, "description");
+    }
+
+    #[test]
+    fn test_search_respects_max_results() {
+        let docs = runbooks();
+        assert_eq!(search(docs.iter(), "cert", 10).len(), 2);
+        assert_eq!(search(docs.iter(), "cert", 1).len(), 1);
+        assert!(search(docs.iter(), "cert", 0).is_empty());
+        assert!(search(docs.iter(), "  ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_snippet_shortens_long_lines() {
+        let line = format!("{} rotate {}", "x".repeat(100), "ü".repeat(100));
+        let start = line.find("rotate").unwrap();
+        let (snippet, highlights) = snippet(&line, &[(start, start + 6)]);
+        assert!(snippet.len() <= MAX_SNIPPET_LEN);
+        let (s, e) = highlights[0];
+        assert_eq!(&snippet[s..e], "rotate");
+    }
+}
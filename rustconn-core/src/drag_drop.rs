@@ -2,6 +2,13 @@
 //!
 //! This module provides a pure data model for drag-and-drop operations,
 //! allowing property-based testing without GTK dependencies.
+//!
+//! Several selected items can be dragged at once. The drop position is
+//! still computed once for the target row; the caller then moves every
+//! dragged item to that position in selection order.
+
+use thiserror::Error;
+use uuid::Uuid;
 
 /// Drop position relative to a target item
 ///
@@ -27,6 +34,48 @@ pub enum ItemType {
     Document,
 }
 
+/// An item taking part in a drag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DraggedItem {
+    /// ID of the connection, group or document
+    pub id: Uuid,
+    /// Type of the item
+    pub item_type: ItemType,
+}
+
+impl DraggedItem {
+    /// Creates a dragged item
+    #[must_use]
+    pub const fn new(id: Uuid, item_type: ItemType) -> Self {
+        Self { id, item_type }
+    }
+}
+
+/// Reasons a batch of dragged items cannot be dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum DropError {
+    /// Nothing is being dragged
+    #[error("No items to drop")]
+    EmptyBatch,
+
+    /// The position is not valid for the target item
+    #[error("Cannot drop {position:?} a {target_type:?}")]
+    InvalidPosition {
+        /// Requested position
+        position: DropPosition,
+        /// Type of the target item
+        target_type: ItemType,
+    },
+
+    /// One of the dragged items is the drop target
+    #[error("Cannot drop item {0} onto itself")]
+    OntoSelf(Uuid),
+
+    /// Moving a dragged group would place it inside itself
+    #[error("Moving group {0} there would create a cycle")]
+    GroupCycle(Uuid),
+}
+
 /// Configuration for drop position calculation
 #[derive(Debug, Clone, Copy)]
 pub struct DropConfig {
@@ -154,9 +203,167 @@ pub const fn is_valid_drop_position(position: DropPosition, item_type: ItemType)
     }
 }
 
+/// Validates that a drop position is valid for a batch of dragged items
+///
+/// A batch is valid when it is not empty and the position is valid for
+/// the target item (see [`is_valid_drop_position`]).
+///
+/// # Arguments
+/// * `position` - The drop position to validate
+/// * `target_type` - Type of the target item
+/// * `dragged` - Types of all dragged items
+#[must_use]
+pub const fn is_valid_batch_drop_position(
+    position: DropPosition,
+    target_type: ItemType,
+    dragged: &[ItemType],
+) -> bool {
+    !dragged.is_empty() && is_valid_drop_position(position, target_type)
+}
+
+/// Validates dropping a batch of items relative to a target item
+///
+/// Rejects the whole batch if the position is invalid, if the target is
+/// one of the dragged items, or if any dragged group would end up inside
+/// itself or one of its descendants.
+///
+/// # Arguments
+/// * `dragged` - Dragged items, in the order they will be moved
+/// * `target` - Item under the pointer
+/// * `position` - Drop position relative to the target
+/// * `parent_of` - Returns the group containing an item (None = root)
+///
+/// # Errors
+/// Returns the first reason the batch cannot be dropped.
+pub fn validate_batch_drop<F>(
+    dragged: &[DraggedItem],
+    target: DraggedItem,
+    position: DropPosition,
+    parent_of: F,
+) -> Result<(), DropError>
+where
+    F: Fn(Uuid) -> Option<Uuid>,
+{
+    let types: Vec<ItemType> = dragged.iter().map(|item| item.item_type).collect();
+    if types.is_empty() {
+        return Err(DropError::EmptyBatch);
+    }
+    if !is_valid_batch_drop_position(position, target.item_type, &types) {
+        return Err(DropError::InvalidPosition {
+            position,
+            target_type: target.item_type,
+        });
+    }
+    if let Some(item) = dragged.iter().find(|item| item.id == target.id) {
+        return Err(DropError::OntoSelf(item.id));
+    }
+
+    // Group that will contain the dropped items
+    let destination = if position == DropPosition::Into && target.item_type == ItemType::Group {
+        Some(target.id)
+    } else {
+        parent_of(target.id)
+    };
+
+    for group in dragged.iter().filter(|i| i.item_type == ItemType::Group) {
+        let mut current = destination;
+        while let Some(id) = current {
+            if id == group.id {
+                return Err(DropError::GroupCycle(group.id));
+            }
+            current = parent_of(id);
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+
+    /// Builds a tree: `root_group > child_group > conn`, plus `other` at the root
+    fn hierarchy() -> (HashMap<Uuid, Uuid>, [Uuid; 4]) {
+        let root_group = Uuid::new_v4();
+        let child_group = Uuid::new_v4();
+        let conn = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let parents = HashMap::from([(child_group, root_group), (conn, child_group)]);
+        (parents, [root_group, child_group, conn, other])
+    }
+
+    #[test]
+    fn test_batch_drop_into_group() {
+        let (parents, [root_group, _, conn, other]) = hierarchy();
+        let dragged = [
+            DraggedItem::new(conn, ItemType::Connection),
+            DraggedItem::new(other, ItemType::Connection),
+        ];
+        let target = DraggedItem::new(root_group, ItemType::Group);
+
+        let result = validate_batch_drop(&dragged, target, DropPosition::Into, |id| {
+            parents.get(&id).copied()
+        });
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_batch_drop_rejects_group_cycle() {
+        let (parents, [root_group, child_group, conn, other]) = hierarchy();
+        let parent_of = |id| parents.get(&id).copied();
+        let dragged = [
+            DraggedItem::new(other, ItemType::Connection),
+            DraggedItem::new(root_group, ItemType::Group),
+        ];
+
+        // Into a descendant group
+        let target = DraggedItem::new(child_group, ItemType::Group);
+        assert_eq!(
+            validate_batch_drop(&dragged, target, DropPosition::Into, parent_of),
+            Err(DropError::GroupCycle(root_group))
+        );
+
+        // Next to a connection that lives inside the dragged group
+        let target = DraggedItem::new(conn, ItemType::Connection);
+        assert_eq!(
+            validate_batch_drop(&dragged, target, DropPosition::After, parent_of),
+            Err(DropError::GroupCycle(root_group))
+        );
+    }
+
+    #[test]
+    fn test_batch_drop_rejects_invalid_batches() {
+        let (parents, [_, child_group, conn, _]) = hierarchy();
+        let parent_of = |id| parents.get(&id).copied();
+        let target = DraggedItem::new(conn, ItemType::Connection);
+
+        assert_eq!(
+            validate_batch_drop(&[], target, DropPosition::Before, parent_of),
+            Err(DropError::EmptyBatch)
+        );
+        assert_eq!(
+            validate_batch_drop(
+                &[DraggedItem::new(child_group, ItemType::Group)],
+                target,
+                DropPosition::Into,
+                parent_of
+            ),
+            Err(DropError::InvalidPosition {
+                position: DropPosition::Into,
+                target_type: ItemType::Connection
+            })
+        );
+        assert_eq!(
+            validate_batch_drop(&[target], target, DropPosition::Before, parent_of),
+            Err(DropError::OntoSelf(conn))
+        );
+        assert!(!is_valid_batch_drop_position(
+            DropPosition::Before,
+            ItemType::Group,
+            &[]
+        ));
+    }
 
     #[test]
     fn test_connection_only_before_or_after() {
@@ -66,6 +66,32 @@ pub enum ConfigError {
     /// Failed to deserialize configuration
     #[error("Failed to deserialize configuration: {0}")]
     Deserialize(String),
+
+    /// Failed to encrypt or decrypt a configuration file
+    #[error("Configuration encryption error: {0}")]
+    Encryption(String),
+
+    /// The configuration file is encrypted and no passphrase was provided
+    #[error("Configuration file is encrypted; a passphrase is required")]
+    PassphraseRequired,
+
+    /// The passphrase does not decrypt the configuration file
+    #[error("Wrong passphrase for the encrypted configuration file")]
+    InvalidPassphrase,
+
+    /// The configuration file was written by a newer release
+    #[error(
+        "{} uses schema version {version}, but this version of RustConn supports up to {supported}; update RustConn to open it",
+        path.display()
+    )]
+    NewerSchema {
+        /// The configuration file
+        path: PathBuf,
+        /// Schema version found in the file
+        version: u32,
+        /// Newest schema version this release understands
+        supported: u32,
+    },
 }
 
 /// Errors related to protocol operations (SSH, RDP, VNC)
@@ -119,6 +145,10 @@ pub enum SecretError {
     #[error("Secret backend not available: {0}")]
     BackendUnavailable(String),
 
+    /// Secret backend is running but its vault is locked
+    #[error("Secret backend is locked: {0}")]
+    BackendLocked(String),
+
     /// KeePassXC-specific error
     #[error("KeePassXC error: {0}")]
     KeePassXC(String),
@@ -130,6 +160,18 @@ pub enum SecretError {
     /// Bitwarden-specific error
     #[error("Bitwarden error: {0}")]
     Bitwarden(String),
+
+    /// pass(1)-specific error
+    #[error("pass error: {0}")]
+    Pass(String),
+
+    /// An entry already exists at the given path
+    #[error("Secret entry already exists: {0}")]
+    EntryExists(String),
+
+    /// The backend cannot create entries
+    #[error("{0} does not support creating entries")]
+    EntryCreationUnsupported(String),
 }
 
 /// Errors related to configuration import operations
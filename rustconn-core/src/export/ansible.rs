@@ -251,7 +251,30 @@ impl ExportTarget for AnsibleExporter {
         groups: &[ConnectionGroup],
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult> {
+        let (content, mut result) = self.export_to_string(connections, groups, options)?;
+
+        // Write to file
+        fs::write(&options.output_path, &content).map_err(|e| {
+            ExportError::WriteError(format!(
+                "Failed to write to {}: {}",
+                options.output_path.display(),
+                e
+            ))
+        })?;
+
+        result.add_output_file(options.output_path.clone());
+
+        Ok(result)
+    }
+
+    fn export_to_string(
+        &self,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
         let mut result = ExportResult::new();
+        options.effective_password_mode(&mut result);
 
         // Filter SSH connections and count skipped
         let ssh_connections: Vec<&Connection> = connections
@@ -290,19 +313,9 @@ impl ExportTarget for AnsibleExporter {
             Self::export_ini(&connections_vec, &filtered_groups)
         };
 
-        // Write to file
-        fs::write(&options.output_path, &content).map_err(|e| {
-            ExportError::WriteError(format!(
-                "Failed to write to {}: {}",
-                options.output_path.display(),
-                e
-            ))
-        })?;
-
         result.exported_count = ssh_connections.len();
-        result.add_output_file(options.output_path.clone());
 
-        Ok(result)
+        Ok((content, result))
     }
 
     fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
@@ -9,7 +9,10 @@ use uuid::Uuid;
 
 use crate::models::{Connection, ConnectionGroup, ProtocolConfig, ProtocolType};
 
-use super::{ExportError, ExportFormat, ExportOptions, ExportResult, ExportResult2, ExportTarget};
+use super::{
+    secret_reference, ExportError, ExportFormat, ExportOptions, ExportResult, ExportResult2,
+    ExportTarget, PasswordMode,
+};
 
 /// Asbru-CM YAML exporter.
 ///
@@ -36,6 +39,16 @@ impl AsbruExporter {
     /// A string containing the YAML-formatted Asbru configuration.
     #[must_use]
     pub fn export(connections: &[Connection], groups: &[ConnectionGroup]) -> String {
+        Self::export_with_passwords(connections, groups, |_| None)
+    }
+
+    /// Exports connections to Asbru YAML, using `password_for` to fill in
+    /// each connection's `pass` field.
+    fn export_with_passwords(
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        password_for: impl Fn(&Connection) -> Option<String>,
+    ) -> String {
         use std::fmt::Write;
 
         let mut output = String::new();
@@ -58,7 +71,9 @@ impl AsbruExporter {
         // Export connections
         for conn in connections {
             let asbru_uuid = generate_asbru_uuid();
-            let entry = Self::connection_to_entry(conn, &group_uuid_map);
+            let password = password_for(conn);
+            let entry =
+                Self::connection_to_entry_with_password(conn, &group_uuid_map, password.as_deref());
             let _ = writeln!(output, "{asbru_uuid}:");
             output.push_str(&entry);
             output.push('\n');
@@ -81,6 +96,19 @@ impl AsbruExporter {
     pub fn connection_to_entry(
         connection: &Connection,
         group_uuid_map: &HashMap<Uuid, String>,
+    ) -> String {
+        Self::connection_to_entry_with_password(connection, group_uuid_map, None)
+    }
+
+    /// Converts a connection to an Asbru YAML entry with a password value.
+    ///
+    /// The value is written verbatim as the `pass` field, so it can be a
+    /// secret backend reference rather than the secret itself.
+    #[must_use]
+    pub fn connection_to_entry_with_password(
+        connection: &Connection,
+        group_uuid_map: &HashMap<Uuid, String>,
+        password: Option<&str>,
     ) -> String {
         let mut lines = Vec::new();
         let name = escape_yaml_string(&connection.name);
@@ -108,6 +136,12 @@ impl AsbruExporter {
             lines.push(format!("  user: \"{user}\""));
         }
 
+        // pass
+        if let Some(password) = password {
+            let password = escape_yaml_string(password);
+            lines.push(format!("  pass: \"{password}\""));
+        }
+
         // method (protocol type)
         let method = match connection.protocol {
             ProtocolType::Ssh | ProtocolType::ZeroTrust => "SSH", // ZeroTrust exported as SSH
@@ -170,14 +204,18 @@ impl AsbruExporter {
             }
         }
 
-        // description from tags
+        // description, falling back to legacy "desc:" tags
         let desc_tags: Vec<&str> = connection
             .tags
             .iter()
             .filter_map(|t| t.strip_prefix("desc:"))
             .collect();
-        if !desc_tags.is_empty() {
-            let desc = escape_yaml_string(&desc_tags.join(", "));
+        let desc = match connection.description.as_deref() {
+            Some(desc) if !desc.is_empty() => desc.to_string(),
+            _ => desc_tags.join(", "),
+        };
+        if !desc.is_empty() {
+            let desc = escape_yaml_string(&desc);
             lines.push(format!("  description: \"{desc}\""));
         }
 
@@ -246,17 +284,7 @@ impl ExportTarget for AsbruExporter {
         groups: &[ConnectionGroup],
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult> {
-        let mut result = ExportResult::new();
-
-        // Filter groups if not including them
-        let filtered_groups = if options.include_groups {
-            groups.to_vec()
-        } else {
-            Vec::new()
-        };
-
-        // Generate content
-        let content = Self::export(connections, &filtered_groups);
+        let (content, mut result) = self.export_to_string(connections, groups, options)?;
 
         // Write to file
         fs::write(&options.output_path, &content).map_err(|e| {
@@ -267,12 +295,42 @@ impl ExportTarget for AsbruExporter {
             ))
         })?;
 
-        result.exported_count = connections.len();
         result.add_output_file(options.output_path.clone());
 
         Ok(result)
     }
 
+    fn export_to_string(
+        &self,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
+        let mut result = ExportResult::new();
+        let mode = options.effective_password_mode(&mut result);
+
+        // Filter groups if not including them
+        let filtered_groups = if options.include_groups {
+            groups.to_vec()
+        } else {
+            Vec::new()
+        };
+
+        // Generate content; references use the full hierarchy even when
+        // groups are left out of the file
+        let content = Self::export_with_passwords(connections, &filtered_groups, |connection| {
+            if mode == PasswordMode::SecretRef {
+                secret_reference(connection, groups)
+            } else {
+                None
+            }
+        });
+
+        result.exported_count = connections.len();
+
+        Ok((content, result))
+    }
+
     fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
         let group_uuid_map = HashMap::new();
         let asbru_uuid = generate_asbru_uuid();
@@ -451,6 +509,22 @@ mod tests {
         assert!(output.contains("parent:"));
     }
 
+    #[test]
+    fn test_connection_to_entry_with_password() {
+        let conn = create_ssh_connection("Server", "host.example.com", 22);
+        let group_map = HashMap::new();
+
+        let entry = AsbruExporter::connection_to_entry(&conn, &group_map);
+        assert!(!entry.contains("pass:"));
+
+        let entry = AsbruExporter::connection_to_entry_with_password(
+            &conn,
+            &group_map,
+            Some("keepassxc://RustConn/Server"),
+        );
+        assert!(entry.contains("  pass: \"keepassxc://RustConn/Server\""));
+    }
+
     #[test]
     fn test_escape_yaml_string() {
         assert_eq!(escape_yaml_string("simple"), "simple");
@@ -2,10 +2,17 @@
 //!
 //! This module provides `BatchExporter` for processing large numbers of connections
 //! efficiently using configurable batch sizes and progress reporting.
+//!
+//! Each result carries a `BatchExportCheckpoint` so a cancelled export can be
+//! continued with `BatchExporter::resume_from` instead of starting over.
 
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
+
 use crate::models::Connection;
 use crate::progress::ProgressReporter;
 
@@ -17,6 +24,51 @@ pub const DEFAULT_EXPORT_BATCH_SIZE: usize = 50;
 /// Threshold for using batch processing (connections count).
 pub const BATCH_EXPORT_THRESHOLD: usize = 10;
 
+/// Position of a batch export, used to resume it later.
+///
+/// Batches are fixed-size chunks of the connection list, so a checkpoint is
+/// only meaningful together with the batch size and connection count it was
+/// taken with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchExportCheckpoint {
+    /// Batch size the export was running with.
+    pub batch_size: usize,
+    /// Index of the last batch that was fully processed.
+    pub last_completed_batch: usize,
+    /// Total number of connections in the export.
+    pub total: usize,
+}
+
+impl BatchExportCheckpoint {
+    /// Creates a new checkpoint.
+    #[must_use]
+    pub const fn new(batch_size: usize, last_completed_batch: usize, total: usize) -> Self {
+        Self {
+            batch_size,
+            last_completed_batch,
+            total,
+        }
+    }
+
+    /// Returns the index of the first batch still to be processed.
+    #[must_use]
+    pub const fn next_batch(&self) -> usize {
+        self.last_completed_batch + 1
+    }
+
+    /// Returns the index of the first connection still to be processed.
+    #[must_use]
+    pub const fn next_index(&self) -> usize {
+        self.next_batch() * self.batch_size
+    }
+
+    /// Returns true if every batch has been processed.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.next_index() >= self.total
+    }
+}
+
 /// Result of a batch export operation.
 #[derive(Debug)]
 pub struct BatchExportResult {
@@ -26,6 +78,8 @@ pub struct BatchExportResult {
     pub was_cancelled: bool,
     /// Number of batches processed.
     pub batches_processed: usize,
+    /// Checkpoint after the last completed batch, if any batch completed.
+    pub checkpoint: Option<BatchExportCheckpoint>,
 }
 
 impl BatchExportResult {
@@ -36,14 +90,31 @@ impl BatchExportResult {
             result,
             was_cancelled,
             batches_processed,
+            checkpoint: None,
         }
     }
 
+    /// Sets the checkpoint for resuming the export.
+    #[must_use]
+    pub const fn with_checkpoint(mut self, checkpoint: Option<BatchExportCheckpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
     /// Returns true if the export completed without cancellation.
     #[must_use]
     pub const fn is_complete(&self) -> bool {
         !self.was_cancelled
     }
+
+    /// Returns the index of the last completed batch.
+    #[must_use]
+    pub const fn last_completed_batch(&self) -> Option<usize> {
+        match self.checkpoint {
+            Some(checkpoint) => Some(checkpoint.last_completed_batch),
+            None => None,
+        }
+    }
 }
 
 /// Batch exporter for efficient bulk connection exports.
@@ -124,65 +195,9 @@ impl BatchExporter {
     where
         F: Fn(&Connection) -> Result<String, ExportError>,
     {
-        let total = connections.len();
-        let mut result = ExportResult::new();
-        let mut batches_processed = 0;
-        let mut exported_data = Vec::with_capacity(total);
-
-        // Check for cancellation from progress reporter or internal flag
-        let check_cancelled =
-            || self.is_cancelled() || progress.is_some_and(ProgressReporter::is_cancelled);
-
-        if check_cancelled() {
-            return BatchExportResult::new(result, true, 0);
-        }
-
-        // Process in batches
-        for (batch_idx, chunk) in connections.chunks(self.batch_size).enumerate() {
-            // Check cancellation between batches
-            if check_cancelled() {
-                // Return partial results
-                result.exported_count = exported_data.len();
-                return BatchExportResult::new(result, true, batches_processed);
-            }
-
-            let batch_start = batch_idx * self.batch_size;
-
-            // Process each connection in the batch
-            for (idx, conn) in chunk.iter().enumerate() {
-                let current = batch_start + idx;
-
-                // Report progress
-                if let Some(reporter) = progress {
-                    reporter.report(
-                        current,
-                        total,
-                        &format!("Exporting connection {} of {}", current + 1, total),
-                    );
-                }
-
-                // Process the connection
-                match processor(conn) {
-                    Ok(data) => {
-                        exported_data.push(data);
-                        result.increment_exported();
-                    }
-                    Err(e) => {
-                        result.add_warning(format!("Failed to export '{}': {}", conn.name, e));
-                        result.increment_skipped();
-                    }
-                }
-            }
-
-            batches_processed += 1;
-        }
-
-        // Report completion
-        if let Some(reporter) = progress {
-            reporter.report(total, total, "Export complete");
-        }
-
-        BatchExportResult::new(result, false, batches_processed)
+        self.run_batches(connections, self.batch_size, 0, progress, |conn| {
+            processor(conn).map(drop)
+        })
     }
 
     /// Processes connections for export in batches, collecting the exported data.
@@ -205,32 +220,158 @@ impl BatchExporter {
     ) -> (BatchExportResult, Vec<String>)
     where
         F: Fn(&Connection) -> Result<String, ExportError>,
+    {
+        let mut exported_data = Vec::with_capacity(connections.len());
+        let result = self.run_batches(connections, self.batch_size, 0, progress, |conn| {
+            exported_data.push(processor(conn)?);
+            Ok(())
+        });
+        (result, exported_data)
+    }
+
+    /// Continues an export from a checkpoint of an earlier run.
+    ///
+    /// Processing restarts at the batch after `checkpoint.last_completed_batch`
+    /// using the checkpoint's batch size, so batch boundaries line up with the
+    /// earlier run and no connection is exported twice. If the connection
+    /// count no longer matches the checkpoint, the export starts over and a
+    /// warning is recorded.
+    ///
+    /// Counts in the returned result cover only the resumed part.
+    pub fn resume_from<F>(
+        &self,
+        checkpoint: &BatchExportCheckpoint,
+        connections: &[Connection],
+        progress: Option<&dyn ProgressReporter>,
+        processor: F,
+    ) -> BatchExportResult
+    where
+        F: Fn(&Connection) -> Result<String, ExportError>,
+    {
+        let (batch_size, start_batch, warning) = Self::resume_position(checkpoint, connections);
+        let mut result = self.run_batches(connections, batch_size, start_batch, progress, |conn| {
+            processor(conn).map(drop)
+        });
+        if let Some(warning) = warning {
+            result.result.add_warning(warning);
+        }
+        result
+    }
+
+    /// Exports each connection to its own file in `output_dir`.
+    ///
+    /// `file_name` names the file for a connection and `processor` produces
+    /// its content. When `checkpoint` is given the export resumes after it as
+    /// in [`Self::resume_from`], and files that already exist are kept rather
+    /// than rewritten, so a batch interrupted halfway is not duplicated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output directory cannot be created.
+    pub fn export_to_directory<N, F>(
+        &self,
+        connections: &[Connection],
+        output_dir: &Path,
+        checkpoint: Option<&BatchExportCheckpoint>,
+        progress: Option<&dyn ProgressReporter>,
+        file_name: N,
+        processor: F,
+    ) -> Result<BatchExportResult, ExportError>
+    where
+        N: Fn(&Connection) -> String,
+        F: Fn(&Connection) -> Result<String, ExportError>,
+    {
+        fs::create_dir_all(output_dir).map_err(|e| {
+            ExportError::WriteError(format!(
+                "Failed to create directory {}: {}",
+                output_dir.display(),
+                e
+            ))
+        })?;
+
+        let (batch_size, start_batch, warning) = match checkpoint {
+            Some(checkpoint) => Self::resume_position(checkpoint, connections),
+            None => (self.batch_size, 0, None),
+        };
+        let resuming = checkpoint.is_some();
+
+        let mut output_files: Vec<PathBuf> = Vec::new();
+        let mut result = self.run_batches(connections, batch_size, start_batch, progress, |conn| {
+            let path = output_dir.join(file_name(conn));
+            if !(resuming && path.exists()) {
+                let content = processor(conn)?;
+                fs::write(&path, content).map_err(|e| {
+                    ExportError::WriteError(format!("Failed to write {}: {}", path.display(), e))
+                })?;
+            }
+            output_files.push(path);
+            Ok(())
+        });
+
+        result.result.output_files = output_files;
+        if let Some(warning) = warning {
+            result.result.add_warning(warning);
+        }
+        Ok(result)
+    }
+
+    /// Returns the batch size and start batch for resuming from a checkpoint,
+    /// plus a warning if the checkpoint does not fit the connections.
+    fn resume_position(
+        checkpoint: &BatchExportCheckpoint,
+        connections: &[Connection],
+    ) -> (usize, usize, Option<String>) {
+        if checkpoint.total == connections.len() && checkpoint.batch_size > 0 {
+            (checkpoint.batch_size, checkpoint.next_batch(), None)
+        } else {
+            (
+                checkpoint.batch_size.max(1),
+                0,
+                Some(format!(
+                    "Checkpoint was taken for {} connections but {} were given; restarting export",
+                    checkpoint.total,
+                    connections.len()
+                )),
+            )
+        }
+    }
+
+    /// Runs `processor` over the batches starting at `start_batch`.
+    fn run_batches<F>(
+        &self,
+        connections: &[Connection],
+        batch_size: usize,
+        start_batch: usize,
+        progress: Option<&dyn ProgressReporter>,
+        mut processor: F,
+    ) -> BatchExportResult
+    where
+        F: FnMut(&Connection) -> Result<(), ExportError>,
     {
         let total = connections.len();
         let mut result = ExportResult::new();
         let mut batches_processed = 0;
-        let mut exported_data = Vec::with_capacity(total);
+        let mut checkpoint = start_batch
+            .checked_sub(1)
+            .map(|last| BatchExportCheckpoint::new(batch_size, last, total));
 
         // Check for cancellation from progress reporter or internal flag
         let check_cancelled =
             || self.is_cancelled() || progress.is_some_and(ProgressReporter::is_cancelled);
 
         if check_cancelled() {
-            return (BatchExportResult::new(result, true, 0), exported_data);
+            return BatchExportResult::new(result, true, 0).with_checkpoint(checkpoint);
         }
 
         // Process in batches
-        for (batch_idx, chunk) in connections.chunks(self.batch_size).enumerate() {
-            // Check cancellation between batches
+        for (batch_idx, chunk) in connections.chunks(batch_size).enumerate().skip(start_batch) {
+            // Check cancellation between batches, returning partial results
             if check_cancelled() {
-                result.exported_count = exported_data.len();
-                return (
-                    BatchExportResult::new(result, true, batches_processed),
-                    exported_data,
-                );
+                return BatchExportResult::new(result, true, batches_processed)
+                    .with_checkpoint(checkpoint);
             }
 
-            let batch_start = batch_idx * self.batch_size;
+            let batch_start = batch_idx * batch_size;
 
             // Process each connection in the batch
             for (idx, conn) in chunk.iter().enumerate() {
@@ -247,10 +388,7 @@ impl BatchExporter {
 
                 // Process the connection
                 match processor(conn) {
-                    Ok(data) => {
-                        exported_data.push(data);
-                        result.increment_exported();
-                    }
+                    Ok(()) => result.increment_exported(),
                     Err(e) => {
                         result.add_warning(format!("Failed to export '{}': {}", conn.name, e));
                         result.increment_skipped();
@@ -259,6 +397,7 @@ impl BatchExporter {
             }
 
             batches_processed += 1;
+            checkpoint = Some(BatchExportCheckpoint::new(batch_size, batch_idx, total));
         }
 
         // Report completion
@@ -266,10 +405,7 @@ impl BatchExporter {
             reporter.report(total, total, "Export complete");
         }
 
-        (
-            BatchExportResult::new(result, false, batches_processed),
-            exported_data,
-        )
+        BatchExportResult::new(result, false, batches_processed).with_checkpoint(checkpoint)
     }
 
     /// Returns true if batch processing should be used for the given count.
@@ -457,4 +593,140 @@ mod tests {
         assert!(data.contains(&"conn0".to_string()));
         assert!(data.contains(&"conn4".to_string()));
     }
+
+    #[test]
+    fn test_checkpoint_positions() {
+        let checkpoint = BatchExportCheckpoint::new(DEFAULT_EXPORT_BATCH_SIZE, 1, 120);
+        assert_eq!(checkpoint.next_batch(), 2);
+        assert_eq!(checkpoint.next_index(), 2 * DEFAULT_EXPORT_BATCH_SIZE);
+        assert!(!checkpoint.is_finished());
+        assert!(BatchExportCheckpoint::new(DEFAULT_EXPORT_BATCH_SIZE, 2, 120).is_finished());
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let exporter = BatchExporter::new(3);
+        let connections: Vec<_> = (0..10)
+            .map(|i| create_test_connection(&format!("conn{i}")))
+            .collect();
+
+        let result = exporter.process_connections(&connections, None, |c| Ok(c.name.clone()));
+        assert_eq!(result.last_completed_batch(), Some(3));
+        assert!(result.checkpoint.unwrap().is_finished());
+
+        // Resume after the first two batches with a different configured size
+        let checkpoint = BatchExportCheckpoint::new(3, 1, 10);
+        let resumer = BatchExporter::new(4);
+        let seen = std::sync::Mutex::new(Vec::new());
+        let result = resumer.resume_from(&checkpoint, &connections, None, |c| {
+            seen.lock().unwrap().push(c.name.clone());
+            Ok(c.name.clone())
+        });
+
+        assert!(result.is_complete());
+        assert_eq!(result.result.exported_count, 4);
+        assert_eq!(result.batches_processed, 2);
+        assert_eq!(result.last_completed_batch(), Some(3));
+        assert_eq!(
+            seen.into_inner().unwrap(),
+            vec!["conn6", "conn7", "conn8", "conn9"]
+        );
+    }
+
+    #[test]
+    fn test_resume_from_mismatched_checkpoint_restarts() {
+        let exporter = BatchExporter::new(3);
+        let connections: Vec<_> = (0..5)
+            .map(|i| create_test_connection(&format!("conn{i}")))
+            .collect();
+
+        let checkpoint = BatchExportCheckpoint::new(3, 0, 10);
+        let result = exporter.resume_from(&checkpoint, &connections, None, |c| Ok(c.name.clone()));
+
+        assert_eq!(result.result.exported_count, 5);
+        assert_eq!(result.result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_cancelled_export_keeps_checkpoint() {
+        let exporter = BatchExporter::new(2);
+        let handle = exporter.cancel_handle();
+        let connections: Vec<_> = (0..10)
+            .map(|i| create_test_connection(&format!("conn{i}")))
+            .collect();
+
+        let result = exporter.process_connections(&connections, None, |c| {
+            if c.name == "conn3" {
+                handle.cancel();
+            }
+            Ok(c.name.clone())
+        });
+
+        assert!(result.was_cancelled);
+        assert_eq!(result.last_completed_batch(), Some(1));
+        assert_eq!(result.checkpoint.unwrap().next_index(), 4);
+    }
+
+    #[test]
+    fn test_export_to_directory_resume_skips_written_files() {
+        use crate::export::RemminaExporter;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let connections: Vec<_> = (0..6)
+            .map(|i| create_test_connection(&format!("conn{i}")))
+            .collect();
+
+        let exporter = BatchExporter::new(2);
+        let handle = exporter.cancel_handle();
+        let result = exporter
+            .export_to_directory(
+                &connections,
+                temp_dir.path(),
+                None,
+                None,
+                RemminaExporter::generate_filename,
+                |c| {
+                    if c.name == "conn1" {
+                        handle.cancel();
+                    }
+                    RemminaExporter::export_connection(c)
+                },
+            )
+            .unwrap();
+        assert!(result.was_cancelled);
+        assert_eq!(result.result.output_files.len(), 2);
+        let checkpoint = result.checkpoint.unwrap();
+
+        // A file from the next batch that was written before the interruption
+        let partial = temp_dir
+            .path()
+            .join(RemminaExporter::generate_filename(&connections[2]));
+        fs::write(&partial, "existing").unwrap();
+
+        exporter.reset();
+        let written = std::sync::Mutex::new(Vec::new());
+        let result = exporter
+            .export_to_directory(
+                &connections,
+                temp_dir.path(),
+                Some(&checkpoint),
+                None,
+                RemminaExporter::generate_filename,
+                |c| {
+                    written.lock().unwrap().push(c.name.clone());
+                    RemminaExporter::export_connection(c)
+                },
+            )
+            .unwrap();
+
+        assert!(result.is_complete());
+        assert_eq!(result.result.exported_count, 4);
+        assert_eq!(result.result.output_files.len(), 4);
+        assert_eq!(
+            written.into_inner().unwrap(),
+            vec!["conn3", "conn4", "conn5"]
+        );
+        assert_eq!(fs::read_to_string(&partial).unwrap(), "existing");
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 6);
+    }
 }
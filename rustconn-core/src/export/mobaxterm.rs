@@ -276,6 +276,24 @@ impl MobaXtermExporter {
         groups: &[ConnectionGroup],
         output_path: &Path,
     ) -> ExportResult2<ExportResult> {
+        let (content, mut result) = Self::export_sessions(connections, groups);
+
+        // Write to file
+        fs::write(output_path, content).map_err(|e| {
+            ExportError::WriteError(format!("Failed to write {}: {}", output_path.display(), e))
+        })?;
+
+        result.add_output_file(output_path.to_path_buf());
+        Ok(result)
+    }
+
+    /// Exports all connections to a MobaXterm sessions document with CRLF
+    /// line endings.
+    #[must_use]
+    pub fn export_sessions(
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+    ) -> (String, ExportResult) {
         let mut result = ExportResult::new();
         let mut output = String::new();
 
@@ -328,7 +346,7 @@ impl MobaXtermExporter {
             .iter()
             .filter(|(k, _)| k.is_some())
             .collect();
-        sorted_groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+        sorted_groups.sort_by_key(|(path, _)| *path);
 
         for (group_path, group_connections) in sorted_groups {
             if let Some(path) = group_path {
@@ -362,15 +380,7 @@ impl MobaXtermExporter {
         }
 
         // Convert to Windows line endings (CRLF)
-        let output_crlf = output.replace('\n', "\r\n");
-
-        // Write to file
-        fs::write(output_path, output_crlf).map_err(|e| {
-            ExportError::WriteError(format!("Failed to write {}: {}", output_path.display(), e))
-        })?;
-
-        result.add_output_file(output_path.to_path_buf());
-        Ok(result)
+        (output.replace('\n', "\r\n"), result)
     }
 
     /// Exports a single connection to a session line.
@@ -408,7 +418,20 @@ impl ExportTarget for MobaXtermExporter {
         groups: &[ConnectionGroup],
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult> {
-        Self::export_to_file(connections, groups, &options.output_path)
+        let mut result = Self::export_to_file(connections, groups, &options.output_path)?;
+        options.effective_password_mode(&mut result);
+        Ok(result)
+    }
+
+    fn export_to_string(
+        &self,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
+        let (content, mut result) = Self::export_sessions(connections, groups);
+        options.effective_password_mode(&mut result);
+        Ok((content, result))
     }
 
     fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
@@ -2,7 +2,7 @@
 //!
 //! This module provides functionality to export connections to various formats
 //! including Ansible inventory, SSH config, Remmina, Asbru-CM, MobaXterm,
-//! and `RustConn` native format.
+//! Termius and `RustConn` native format.
 //!
 //! For large exports (more than 10 connections), use `BatchExporter` for
 //! efficient batch processing with progress reporting and cancellation support.
@@ -15,26 +15,32 @@ pub mod native;
 pub mod remmina;
 pub mod royalts;
 pub mod ssh_config;
+pub mod termius;
 
 use std::path::PathBuf;
 
 pub use ansible::AnsibleExporter;
 pub use asbru::AsbruExporter;
 pub use batch::{
-    BatchExportCancelHandle, BatchExportResult, BatchExporter, BATCH_EXPORT_THRESHOLD,
-    DEFAULT_EXPORT_BATCH_SIZE,
+    BatchExportCancelHandle, BatchExportCheckpoint, BatchExportResult, BatchExporter,
+    BATCH_EXPORT_THRESHOLD, DEFAULT_EXPORT_BATCH_SIZE,
 };
 pub use mobaxterm::MobaXtermExporter;
-pub use native::{NativeExport, NativeImportError, NATIVE_FILE_EXTENSION, NATIVE_FORMAT_VERSION};
+pub use native::{
+    MergeConflict, MergeCounts, MergeItemKind, MergeReport, NativeExport, NativeImportError,
+    NATIVE_FILE_EXTENSION, NATIVE_FORMAT_VERSION,
+};
 pub use remmina::RemminaExporter;
 pub use royalts::RoyalTsExporter;
 pub use ssh_config::SshConfigExporter;
+pub use termius::TermiusExporter;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::models::{Connection, ConnectionGroup};
+use crate::models::{Connection, ConnectionGroup, PasswordSource};
 use crate::progress::ProgressReporter;
+use crate::secret::{CredentialResolver, KeePassHierarchy};
 
 /// Export format types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -54,6 +60,8 @@ pub enum ExportFormat {
     RoyalTs,
     /// MobaXterm session format (.mxtsessions)
     MobaXterm,
+    /// Termius import JSON
+    Termius,
 }
 
 impl ExportFormat {
@@ -68,6 +76,7 @@ impl ExportFormat {
             Self::Native,
             Self::RoyalTs,
             Self::MobaXterm,
+            Self::Termius,
         ]
     }
 
@@ -82,6 +91,7 @@ impl ExportFormat {
             Self::Native => "RustConn Native",
             Self::RoyalTs => "Royal TS",
             Self::MobaXterm => "MobaXterm",
+            Self::Termius => "Termius",
         }
     }
 
@@ -96,6 +106,7 @@ impl ExportFormat {
             Self::Native => NATIVE_FILE_EXTENSION,
             Self::RoyalTs => "rtsz",
             Self::MobaXterm => "mxtsessions",
+            Self::Termius => "json",
         }
     }
 
@@ -104,6 +115,14 @@ impl ExportFormat {
     pub const fn exports_to_directory(&self) -> bool {
         matches!(self, Self::Remmina)
     }
+
+    /// Returns true if this format can carry a secret backend reference
+    ///
+    /// The native format keeps each connection's password source as-is.
+    #[must_use]
+    pub const fn supports_secret_refs(&self) -> bool {
+        matches!(self, Self::Remmina | Self::Asbru | Self::Native)
+    }
 }
 
 impl std::fmt::Display for ExportFormat {
@@ -112,6 +131,44 @@ impl std::fmt::Display for ExportFormat {
     }
 }
 
+/// How passwords are written to exported files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasswordMode {
+    /// Leave passwords out of the export
+    #[default]
+    Omit,
+    /// Embed passwords in plaintext (if supported)
+    Plaintext,
+    /// Write a reference to the secret backend entry instead of the secret
+    SecretRef,
+}
+
+/// Returns the secret backend reference for a connection's password
+///
+/// References have the form `<backend>://<entry>`, where the entry is the key
+/// `RustConn` uses to look the password up. `KeePass` entries use the
+/// hierarchical path built from `groups`. Returns `None` when the password
+/// does not live in a secret backend.
+#[must_use]
+pub fn secret_reference(connection: &Connection, groups: &[ConnectionGroup]) -> Option<String> {
+    match connection.password_source {
+        PasswordSource::KeePass => Some(format!(
+            "keepassxc://{}",
+            KeePassHierarchy::build_entry_path(connection, groups)
+        )),
+        PasswordSource::Keyring => Some(format!(
+            "keyring://{}",
+            CredentialResolver::generate_lookup_key(connection)
+        )),
+        PasswordSource::Bitwarden => Some(format!(
+            "bitwarden://{}",
+            CredentialResolver::generate_lookup_key(connection)
+        )),
+        PasswordSource::None | PasswordSource::Prompt | PasswordSource::Inherit => None,
+    }
+}
+
 /// Options for export operations
 #[derive(Debug, Clone)]
 pub struct ExportOptions {
@@ -119,6 +176,8 @@ pub struct ExportOptions {
     pub format: ExportFormat,
     /// Whether to include passwords in the export (if supported)
     pub include_passwords: bool,
+    /// How passwords are written to the export
+    pub password_mode: PasswordMode,
     /// Whether to include group hierarchy in the export
     pub include_groups: bool,
     /// Output path (file or directory depending on format)
@@ -132,18 +191,50 @@ impl ExportOptions {
         Self {
             format,
             include_passwords: false,
+            password_mode: PasswordMode::Omit,
             include_groups: true,
             output_path,
         }
     }
 
     /// Sets whether to include passwords
+    ///
+    /// This is shorthand for [`PasswordMode::Plaintext`] or [`PasswordMode::Omit`].
     #[must_use]
     pub const fn with_passwords(mut self, include: bool) -> Self {
         self.include_passwords = include;
+        self.password_mode = if include {
+            PasswordMode::Plaintext
+        } else {
+            PasswordMode::Omit
+        };
+        self
+    }
+
+    /// Sets how passwords are written to the export
+    #[must_use]
+    pub const fn with_password_mode(mut self, mode: PasswordMode) -> Self {
+        self.include_passwords = matches!(mode, PasswordMode::Plaintext);
+        self.password_mode = mode;
         self
     }
 
+    /// Returns the password mode the export format can honor
+    ///
+    /// Formats that cannot represent [`PasswordMode::SecretRef`] fall back to
+    /// [`PasswordMode::Omit`], which is recorded as a warning in `result`.
+    pub fn effective_password_mode(&self, result: &mut ExportResult) -> PasswordMode {
+        if self.password_mode == PasswordMode::SecretRef && !self.format.supports_secret_refs() {
+            result.add_warning(format!(
+                "{} cannot represent secret references; passwords were omitted",
+                self.format
+            ));
+            PasswordMode::Omit
+        } else {
+            self.password_mode
+        }
+    }
+
     /// Sets whether to include groups
     #[must_use]
     pub const fn with_groups(mut self, include: bool) -> Self {
@@ -286,6 +377,28 @@ pub trait ExportTarget: Send + Sync {
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult>;
 
+    /// Renders the export as a single document instead of writing a file
+    ///
+    /// The returned result carries the counts and warnings but no output
+    /// files. Formats that write one file per connection cannot be rendered
+    /// and return an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format has no single-document form or the
+    /// document cannot be generated.
+    fn export_to_string(
+        &self,
+        _connections: &[Connection],
+        _groups: &[ConnectionGroup],
+        _options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
+        Err(ExportError::InvalidPath(format!(
+            "{} exports one file per connection",
+            self.display_name()
+        )))
+    }
+
     /// Exports a single connection to a string representation
     ///
     /// # Arguments
@@ -344,7 +457,7 @@ mod tests {
     #[test]
     fn test_export_format_all() {
         let formats = ExportFormat::all();
-        assert_eq!(formats.len(), 7);
+        assert_eq!(formats.len(), 8);
         assert!(formats.contains(&ExportFormat::Ansible));
         assert!(formats.contains(&ExportFormat::SshConfig));
         assert!(formats.contains(&ExportFormat::Remmina));
@@ -352,6 +465,7 @@ mod tests {
         assert!(formats.contains(&ExportFormat::Native));
         assert!(formats.contains(&ExportFormat::RoyalTs));
         assert!(formats.contains(&ExportFormat::MobaXterm));
+        assert!(formats.contains(&ExportFormat::Termius));
     }
 
     #[test]
@@ -363,6 +477,7 @@ mod tests {
         assert_eq!(ExportFormat::Native.display_name(), "RustConn Native");
         assert_eq!(ExportFormat::RoyalTs.display_name(), "Royal TS");
         assert_eq!(ExportFormat::MobaXterm.display_name(), "MobaXterm");
+        assert_eq!(ExportFormat::Termius.display_name(), "Termius");
     }
 
     #[test]
@@ -374,6 +489,7 @@ mod tests {
         assert_eq!(ExportFormat::Native.file_extension(), "rcn");
         assert_eq!(ExportFormat::RoyalTs.file_extension(), "rtsz");
         assert_eq!(ExportFormat::MobaXterm.file_extension(), "mxtsessions");
+        assert_eq!(ExportFormat::Termius.file_extension(), "json");
     }
 
     #[test]
@@ -403,9 +519,60 @@ mod tests {
             .with_groups(false);
 
         assert!(options.include_passwords);
+        assert_eq!(options.password_mode, PasswordMode::Plaintext);
         assert!(!options.include_groups);
     }
 
+    #[test]
+    fn test_effective_password_mode_falls_back() {
+        let options = ExportOptions::new(ExportFormat::SshConfig, PathBuf::from("/tmp/config"))
+            .with_password_mode(PasswordMode::SecretRef);
+        assert!(!options.include_passwords);
+
+        let mut result = ExportResult::new();
+        assert_eq!(
+            options.effective_password_mode(&mut result),
+            PasswordMode::Omit
+        );
+        assert_eq!(result.warnings.len(), 1);
+
+        let options = ExportOptions::new(ExportFormat::Remmina, PathBuf::from("/tmp/out"))
+            .with_password_mode(PasswordMode::SecretRef);
+        let mut result = ExportResult::new();
+        assert_eq!(
+            options.effective_password_mode(&mut result),
+            PasswordMode::SecretRef
+        );
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_secret_reference() {
+        use crate::models::{ProtocolConfig, SshConfig};
+
+        let group = ConnectionGroup::new("Prod".to_string());
+        let mut conn = Connection::new(
+            "web".to_string(),
+            "10.0.0.1".to_string(),
+            22,
+            ProtocolConfig::Ssh(SshConfig::default()),
+        );
+        conn.group_id = Some(group.id);
+        assert_eq!(secret_reference(&conn, std::slice::from_ref(&group)), None);
+
+        conn.password_source = PasswordSource::KeePass;
+        assert_eq!(
+            secret_reference(&conn, &[group]).as_deref(),
+            Some("keepassxc://RustConn/Prod/web")
+        );
+
+        conn.password_source = PasswordSource::Keyring;
+        assert_eq!(
+            secret_reference(&conn, &[]).as_deref(),
+            Some("keyring://rustconn/web")
+        );
+    }
+
     #[test]
     fn test_export_result_new() {
         let result = ExportResult::new();
@@ -10,9 +10,12 @@ use std::path::Path;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::cluster::Cluster;
-use crate::models::{Connection, ConnectionGroup, ConnectionTemplate};
+use crate::models::{
+    AutomationConfig, Connection, ConnectionGroup, ConnectionTemplate, PasswordSource,
+};
 use crate::variables::Variable;
 
 use super::ExportError;
@@ -43,6 +46,61 @@ pub enum NativeImportError {
     Migration(String),
 }
 
+/// Kind of item affected by a merge conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeItemKind {
+    /// A connection
+    Connection,
+    /// A connection group
+    Group,
+}
+
+/// An imported item that was renamed because its name was already taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// Kind of the renamed item
+    pub kind: MergeItemKind,
+    /// ID of the renamed item
+    pub id: Uuid,
+    /// Name in the imported bundle
+    pub original_name: String,
+    /// Name the item was stored under
+    pub renamed_to: String,
+}
+
+/// Added/updated/skipped counts for one kind of item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeCounts {
+    /// Items with new IDs that were appended
+    pub added: usize,
+    /// Existing items replaced by the imported version
+    pub updated: usize,
+    /// Imported items left out because the local copy is identical or newer
+    pub skipped: usize,
+}
+
+/// Outcome of [`NativeExport::merge_into`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Connection counts
+    pub connections: MergeCounts,
+    /// Group counts
+    pub groups: MergeCounts,
+    /// Items renamed to avoid duplicate names
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeReport {
+    /// Returns true if the merge changed nothing
+    #[must_use]
+    pub const fn is_unchanged(&self) -> bool {
+        self.connections.added == 0
+            && self.connections.updated == 0
+            && self.groups.added == 0
+            && self.groups.updated == 0
+    }
+}
+
 /// `RustConn` native export format (.rcn)
 ///
 /// This struct represents the complete export of a `RustConn` configuration,
@@ -195,6 +253,107 @@ impl NativeExport {
         export
     }
 
+    /// Merges the exported connections and groups into existing ones
+    ///
+    /// Items are reconciled by UUID:
+    /// - a new ID is appended
+    /// - a known group ID is updated in place, keeping its local expanded state
+    /// - a known connection ID is updated only if the imported copy is newer
+    ///   (`updated_at`), so local edits are never overwritten by older data
+    ///
+    /// Updates are applied field by field: optional fields that are unset in
+    /// the bundle (`None`, empty lists, no password source) keep their local
+    /// value instead of clearing it.
+    ///
+    /// An added or updated item whose name is already used by a different
+    /// item in the same parent group gets a numeric suffix (`"web (2)"`) and
+    /// is listed in [`MergeReport::conflicts`]. Templates, clusters and
+    /// variables are not merged.
+    pub fn merge_into(
+        &self,
+        connections: &mut Vec<Connection>,
+        groups: &mut Vec<ConnectionGroup>,
+    ) -> MergeReport {
+        let mut report = MergeReport::default();
+
+        for incoming in &self.groups {
+            let existing = groups.iter().position(|g| g.id == incoming.id);
+            let mut group = match existing {
+                Some(index) => {
+                    let mut merged = groups[index].clone();
+                    merge_group(&mut merged, incoming);
+                    if groups[index] == merged {
+                        report.groups.skipped += 1;
+                        continue;
+                    }
+                    merged
+                }
+                None => incoming.clone(),
+            };
+
+            let taken = |name: &str| {
+                groups
+                    .iter()
+                    .any(|g| g.id != group.id && g.parent_id == group.parent_id && g.name == name)
+            };
+            if let Some(renamed) = unique_name(&group.name, taken) {
+                report.conflicts.push(MergeConflict {
+                    kind: MergeItemKind::Group,
+                    id: group.id,
+                    original_name: std::mem::replace(&mut group.name, renamed.clone()),
+                    renamed_to: renamed,
+                });
+            }
+
+            if let Some(index) = existing {
+                groups[index] = group;
+                report.groups.updated += 1;
+            } else {
+                groups.push(group);
+                report.groups.added += 1;
+            }
+        }
+
+        for incoming in &self.connections {
+            let existing = connections.iter().position(|c| c.id == incoming.id);
+            let mut connection = match existing {
+                Some(index) if connections[index].updated_at >= incoming.updated_at => {
+                    report.connections.skipped += 1;
+                    continue;
+                }
+                Some(index) => {
+                    let mut merged = connections[index].clone();
+                    merge_connection(&mut merged, incoming);
+                    merged
+                }
+                None => incoming.clone(),
+            };
+            let taken = |name: &str| {
+                connections.iter().any(|c| {
+                    c.id != connection.id && c.group_id == connection.group_id && c.name == name
+                })
+            };
+            if let Some(renamed) = unique_name(&connection.name, taken) {
+                report.conflicts.push(MergeConflict {
+                    kind: MergeItemKind::Connection,
+                    id: connection.id,
+                    original_name: std::mem::replace(&mut connection.name, renamed.clone()),
+                    renamed_to: renamed,
+                });
+            }
+
+            if let Some(index) = existing {
+                connections[index] = connection;
+                report.connections.updated += 1;
+            } else {
+                connections.push(connection);
+                report.connections.added += 1;
+            }
+        }
+
+        report
+    }
+
     /// Returns the total number of items in this export
     #[must_use]
     pub fn total_items(&self) -> usize {
@@ -235,9 +394,155 @@ impl Default for NativeExport {
     }
 }
 
+/// Replaces `target` with `source` only if `source` is set
+fn merge_option<T: Clone>(target: &mut Option<T>, source: Option<&T>) {
+    if let Some(value) = source {
+        *target = Some(value.clone());
+    }
+}
+
+/// Applies the fields set in an imported group onto a local one
+///
+/// The local expanded state and creation time are kept.
+fn merge_group(target: &mut ConnectionGroup, source: &ConnectionGroup) {
+    let ConnectionGroup {
+        id: _,
+        name,
+        parent_id,
+        expanded: _,
+        created_at: _,
+        sort_order,
+        username,
+        domain,
+        password_source,
+    } = source;
+
+    target.name.clone_from(name);
+    target.parent_id = *parent_id;
+    target.sort_order = *sort_order;
+    merge_option(&mut target.username, username.as_ref());
+    merge_option(&mut target.domain, domain.as_ref());
+    merge_option(&mut target.password_source, password_source.as_ref());
+}
+
+/// Applies the fields set in an imported connection onto a local one
+///
+/// Required fields and flags are always taken from the import, including the
+/// parent group. Unset optional fields, empty lists and a `None` password
+/// source keep the local value; local variables are merged by name and usage
+/// statistics keep the larger value. The local creation time is kept.
+fn merge_connection(target: &mut Connection, source: &Connection) {
+    let Connection {
+        id: _,
+        name,
+        description,
+        protocol,
+        host,
+        port,
+        username,
+        group_id,
+        additional_group_ids,
+        tags,
+        created_at: _,
+        updated_at,
+        protocol_config,
+        automation,
+        sort_order,
+        launch_order,
+        last_connected,
+        connect_count,
+        password_source,
+        domain,
+        custom_properties,
+        pre_connect_task,
+        post_disconnect_task,
+        wol_config,
+        local_variables,
+        log_config,
+        key_sequence,
+        window_mode,
+        remember_window_position,
+        window_geometry,
+        skip_port_check,
+        color,
+        icon,
+        archived,
+    } = source;
+
+    target.name.clone_from(name);
+    target.protocol = *protocol;
+    target.host.clone_from(host);
+    target.port = *port;
+    target.group_id = *group_id;
+    target.updated_at = *updated_at;
+    target.protocol_config.clone_from(protocol_config);
+    target.sort_order = *sort_order;
+    target.launch_order = *launch_order;
+    target.window_mode = *window_mode;
+    target.remember_window_position = *remember_window_position;
+    target.skip_port_check = *skip_port_check;
+    target.archived = *archived;
+
+    merge_option(&mut target.description, description.as_ref());
+    merge_option(&mut target.username, username.as_ref());
+    merge_option(&mut target.domain, domain.as_ref());
+    merge_option(&mut target.pre_connect_task, pre_connect_task.as_ref());
+    merge_option(
+        &mut target.post_disconnect_task,
+        post_disconnect_task.as_ref(),
+    );
+    merge_option(&mut target.wol_config, wol_config.as_ref());
+    merge_option(&mut target.log_config, log_config.as_ref());
+    merge_option(&mut target.key_sequence, key_sequence.as_ref());
+    merge_option(&mut target.window_geometry, window_geometry.as_ref());
+    merge_option(&mut target.color, color.as_ref());
+    merge_option(&mut target.icon, icon.as_ref());
+
+    if !additional_group_ids.is_empty() {
+        target.additional_group_ids.clone_from(additional_group_ids);
+    }
+    if !tags.is_empty() {
+        target.tags.clone_from(tags);
+    }
+    if !custom_properties.is_empty() {
+        target.custom_properties.clone_from(custom_properties);
+    }
+    if *automation != AutomationConfig::default() {
+        target.automation.clone_from(automation);
+    }
+    if *password_source != PasswordSource::None {
+        target.password_source = *password_source;
+    }
+    target.local_variables.extend(
+        local_variables
+            .iter()
+            .map(|(name, variable)| (name.clone(), variable.clone())),
+    );
+    if last_connected.is_some() && *last_connected > target.last_connected {
+        target.last_connected = *last_connected;
+    }
+    target.connect_count = target.connect_count.max(*connect_count);
+}
+
+/// Returns a suffixed variant of `name` if it is taken, `None` if it is free
+fn unique_name(name: &str, taken: impl Fn(&str) -> bool) -> Option<String> {
+    if !taken(name) {
+        return None;
+    }
+    let mut counter = 2u32;
+    loop {
+        let candidate = format!("{name} ({counter})");
+        if !taken(&candidate) {
+            return Some(candidate);
+        }
+        counter += 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use uuid::Uuid;
 
     #[test]
     fn test_native_export_new() {
@@ -277,6 +582,199 @@ mod tests {
         assert!(!export.is_empty());
     }
 
+    #[test]
+    fn test_native_round_trip_preserves_automation() {
+        use crate::automation::{
+            ConnectionTask, ExpectRule, KeySequence, TaskCondition, TaskTiming,
+        };
+
+        let mut conn = Connection::new_ssh("Automated".to_string(), "host.com".to_string(), 22);
+        conn.automation.expect_rules = vec![ExpectRule::new(r"password:", "${db_pass}\n")
+            .with_priority(5)
+            .with_timeout(3000)
+            .with_enabled(false)];
+        conn.automation.post_login_scripts = vec!["cd /srv".to_string()];
+        conn.key_sequence = Some(KeySequence::parse("sudo -i{ENTER}").unwrap());
+        conn.pre_connect_task = Some(
+            ConnectionTask::new_pre_connect("wg-quick up office")
+                .with_condition(TaskCondition::first_in_folder())
+                .with_timeout(15_000)
+                .with_description("Bring up VPN"),
+        );
+        conn.post_disconnect_task = Some(
+            ConnectionTask::with_id(
+                Uuid::new_v4(),
+                TaskTiming::PostDisconnect,
+                "wg-quick down office",
+            )
+            .with_condition(TaskCondition::last_in_folder()),
+        );
+
+        let export = NativeExport::with_data(vec![conn.clone()], vec![], vec![], vec![], vec![]);
+        let imported = NativeExport::from_json(&export.to_json().unwrap()).unwrap();
+        let restored = &imported.connections[0];
+
+        assert_eq!(restored.automation, conn.automation);
+        assert_eq!(restored.key_sequence, conn.key_sequence);
+        assert_eq!(restored.pre_connect_task, conn.pre_connect_task);
+        assert_eq!(restored.post_disconnect_task, conn.post_disconnect_task);
+    }
+
+    #[test]
+    fn test_native_round_trip_preserves_color_and_icon() {
+        let conn = Connection::new_ssh("Tagged".to_string(), "host.com".to_string(), 22)
+            .with_color("#e01b24")
+            .with_icon("network-server-symbolic");
+        let plain = Connection::new_ssh("Plain".to_string(), "host.com".to_string(), 22);
+
+        let export = NativeExport::with_data(vec![conn, plain], vec![], vec![], vec![], vec![]);
+        let json = export.to_json().unwrap();
+        let imported = NativeExport::from_json(&json).unwrap();
+
+        assert_eq!(imported.connections[0].color.as_deref(), Some("#e01b24"));
+        assert_eq!(
+            imported.connections[0].icon.as_deref(),
+            Some("network-server-symbolic")
+        );
+        assert_eq!(imported.connections[1].color, None);
+        assert_eq!(json.matches("\"color\"").count(), 1);
+    }
+
+    #[test]
+    fn test_merge_into_reconciles_by_id() {
+        let group = ConnectionGroup::new("Prod".to_string());
+        let mut local_group = group.clone();
+        local_group.expanded = false;
+
+        let mut stale = Connection::new_ssh("db".to_string(), "db.old".to_string(), 22);
+        let mut fresh = Connection::new_ssh("web".to_string(), "web.old".to_string(), 22);
+        let local_newer = Connection::new_ssh("cache".to_string(), "cache.local".to_string(), 22);
+
+        let mut incoming_stale = stale.clone();
+        incoming_stale.host = "db.shared".to_string();
+        stale.touch();
+        let mut incoming_fresh = fresh.clone();
+        incoming_fresh.host = "web.shared".to_string();
+        incoming_fresh.updated_at = fresh.updated_at + chrono::Duration::seconds(5);
+        fresh.name = "web-local".to_string();
+        let added = Connection::new_ssh("mail".to_string(), "mail.shared".to_string(), 25);
+
+        let export = NativeExport::with_data(
+            vec![
+                incoming_stale,
+                incoming_fresh,
+                local_newer.clone(),
+                added.clone(),
+            ],
+            vec![group.clone()],
+            vec![],
+            vec![],
+            vec![],
+        );
+
+        let mut connections = vec![stale, fresh, local_newer];
+        let mut groups = vec![local_group];
+        let report = export.merge_into(&mut connections, &mut groups);
+
+        assert_eq!(report.connections.added, 1);
+        assert_eq!(report.connections.updated, 1);
+        assert_eq!(report.connections.skipped, 2);
+        assert_eq!(report.groups.skipped, 1);
+        assert!(report.conflicts.is_empty());
+
+        assert_eq!(connections.len(), 4);
+        assert_eq!(connections[0].host, "db.old");
+        assert_eq!(connections[1].host, "web.shared");
+        assert_eq!(connections[1].name, "web");
+        assert_eq!(connections[3].id, added.id);
+        assert!(!groups[0].expanded);
+    }
+
+    #[test]
+    fn test_merge_into_keeps_local_fields_unset_in_import() {
+        let mut group = ConnectionGroup::new("Prod".to_string());
+        let incoming_group = group.clone();
+        group.username = Some("admin".to_string());
+        group.domain = Some("CORP".to_string());
+
+        let mut local =
+            Connection::new_ssh("web".to_string(), "web.old".to_string(), 22).with_color("#e01b24");
+        let mut incoming = local.clone();
+        incoming.color = None;
+        incoming.host = "web.shared".to_string();
+        incoming.updated_at = local.updated_at + chrono::Duration::seconds(5);
+        local.username = Some("alice".to_string());
+        local.description = Some("Local notes".to_string());
+        local.tags = vec!["local".to_string()];
+        local.password_source = PasswordSource::Keyring;
+        local.connect_count = 7;
+
+        let export =
+            NativeExport::with_data(vec![incoming], vec![incoming_group], vec![], vec![], vec![]);
+        let mut connections = vec![local];
+        let mut groups = vec![group];
+        let report = export.merge_into(&mut connections, &mut groups);
+
+        assert_eq!(report.connections.updated, 1);
+        assert_eq!(report.groups.skipped, 1);
+        let merged = &connections[0];
+        assert_eq!(merged.host, "web.shared");
+        assert_eq!(merged.username.as_deref(), Some("alice"));
+        assert_eq!(merged.description.as_deref(), Some("Local notes"));
+        assert_eq!(merged.color.as_deref(), Some("#e01b24"));
+        assert_eq!(merged.tags, vec!["local".to_string()]);
+        assert_eq!(merged.password_source, PasswordSource::Keyring);
+        assert_eq!(merged.connect_count, 7);
+        assert_eq!(groups[0].username.as_deref(), Some("admin"));
+        assert_eq!(groups[0].domain.as_deref(), Some("CORP"));
+    }
+
+    #[test]
+    fn test_merge_into_suffixes_conflicting_names() {
+        let local_group = ConnectionGroup::new("Prod".to_string());
+        let local_conn = Connection::new_ssh("web".to_string(), "a.example".to_string(), 22);
+        let local_conn_2 = Connection::new_ssh("web (2)".to_string(), "b.example".to_string(), 22);
+
+        let shared_group = ConnectionGroup::new("Prod".to_string());
+        let shared_conn = Connection::new_ssh("web".to_string(), "c.example".to_string(), 22);
+        let mut other_folder = Connection::new_ssh("web".to_string(), "d.example".to_string(), 22);
+        other_folder.group_id = Some(shared_group.id);
+
+        let export = NativeExport::with_data(
+            vec![shared_conn.clone(), other_folder],
+            vec![shared_group.clone()],
+            vec![],
+            vec![],
+            vec![],
+        );
+        let mut connections = vec![local_conn, local_conn_2];
+        let mut groups = vec![local_group];
+        let report = export.merge_into(&mut connections, &mut groups);
+
+        assert_eq!(report.groups.added, 1);
+        assert_eq!(report.connections.added, 2);
+        assert_eq!(report.conflicts.len(), 2);
+        assert_eq!(report.conflicts[0].kind, MergeItemKind::Group);
+        assert_eq!(groups[1].name, "Prod (2)");
+        assert_eq!(
+            report.conflicts[1],
+            MergeConflict {
+                kind: MergeItemKind::Connection,
+                id: shared_conn.id,
+                original_name: "web".to_string(),
+                renamed_to: "web (3)".to_string(),
+            }
+        );
+        // Same name in a different folder is not a conflict
+        assert_eq!(connections[3].name, "web");
+
+        // Merging the same bundle again changes nothing
+        let mut groups_again = groups.clone();
+        let report = NativeExport::with_data(vec![], groups, vec![], vec![], vec![])
+            .merge_into(&mut connections, &mut groups_again);
+        assert!(report.is_unchanged());
+    }
+
     #[test]
     fn test_native_export_metadata() {
         let mut export = NativeExport::new();
@@ -8,7 +8,10 @@ use std::path::Path;
 
 use crate::models::{Connection, ConnectionGroup, ProtocolConfig, ProtocolType};
 
-use super::{ExportError, ExportFormat, ExportOptions, ExportResult, ExportResult2, ExportTarget};
+use super::{
+    secret_reference, ExportError, ExportFormat, ExportOptions, ExportResult, ExportResult2,
+    ExportTarget, PasswordMode,
+};
 
 /// Remmina connection file exporter.
 ///
@@ -37,6 +40,21 @@ impl RemminaExporter {
     ///
     /// Returns an error if the protocol is not supported.
     pub fn export_connection(connection: &Connection) -> Result<String, ExportError> {
+        Self::export_connection_with_password(connection, None)
+    }
+
+    /// Exports a connection to .remmina file content with a password value.
+    ///
+    /// The value is written verbatim as the `password` key, so it can be a
+    /// secret backend reference rather than the secret itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the protocol is not supported.
+    pub fn export_connection_with_password(
+        connection: &Connection,
+        password: Option<&str>,
+    ) -> Result<String, ExportError> {
         let mut output = String::new();
         output.push_str("[remmina]\n");
 
@@ -55,6 +73,10 @@ impl RemminaExporter {
             }
         }
 
+        if let Some(password) = password {
+            let _ = writeln!(output, "password={password}");
+        }
+
         Ok(output)
     }
 
@@ -213,8 +235,17 @@ impl RemminaExporter {
         connections: &[Connection],
         output_dir: &Path,
     ) -> ExportResult2<ExportResult> {
-        let mut result = ExportResult::new();
+        Self::write_directory(connections, output_dir, ExportResult::new(), |_| None)
+    }
 
+    /// Writes one .remmina file per connection, using `password_for` to fill
+    /// in the `password` key.
+    fn write_directory(
+        connections: &[Connection],
+        output_dir: &Path,
+        mut result: ExportResult,
+        password_for: impl Fn(&Connection) -> Option<String>,
+    ) -> ExportResult2<ExportResult> {
         // Create output directory if it doesn't exist
         if !output_dir.exists() {
             fs::create_dir_all(output_dir).map_err(|e| {
@@ -227,7 +258,8 @@ impl RemminaExporter {
         }
 
         for connection in connections {
-            match Self::export_connection(connection) {
+            let password = password_for(connection);
+            match Self::export_connection_with_password(connection, password.as_deref()) {
                 Ok(content) => {
                     let filename = Self::generate_filename(connection);
                     let file_path = output_dir.join(&filename);
@@ -282,10 +314,18 @@ impl ExportTarget for RemminaExporter {
     fn export(
         &self,
         connections: &[Connection],
-        _groups: &[ConnectionGroup],
+        groups: &[ConnectionGroup],
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult> {
-        Self::export_to_directory(connections, &options.output_path)
+        let mut result = ExportResult::new();
+        let mode = options.effective_password_mode(&mut result);
+        Self::write_directory(connections, &options.output_path, result, |connection| {
+            if mode == PasswordMode::SecretRef {
+                secret_reference(connection, groups)
+            } else {
+                None
+            }
+        })
     }
 
     fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
@@ -487,4 +527,36 @@ mod tests {
         assert_eq!(result.skipped_count, 1);
         assert!(result.has_warnings());
     }
+
+    #[test]
+    fn test_export_with_secret_ref() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut conn = create_ssh_connection("Server", "host.example.com", 22);
+        conn.password_source = crate::models::PasswordSource::Keyring;
+        let plain = create_ssh_connection("Plain", "plain.example.com", 22);
+
+        let options = ExportOptions::new(ExportFormat::Remmina, temp_dir.path().to_path_buf())
+            .with_password_mode(PasswordMode::SecretRef);
+        let result = RemminaExporter::new()
+            .export(&[conn.clone(), plain.clone()], &[], &options)
+            .unwrap();
+        assert_eq!(result.exported_count, 2);
+        assert!(result.warnings.is_empty());
+
+        let content = fs::read_to_string(
+            temp_dir
+                .path()
+                .join(RemminaExporter::generate_filename(&conn)),
+        )
+        .unwrap();
+        assert!(content.contains("password=keyring://rustconn/Server\n"));
+
+        let content = fs::read_to_string(
+            temp_dir
+                .path()
+                .join(RemminaExporter::generate_filename(&plain)),
+        )
+        .unwrap();
+        assert!(!content.contains("password="));
+    }
 }
@@ -264,7 +264,30 @@ impl ExportTarget for RoyalTsExporter {
         groups: &[ConnectionGroup],
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult> {
+        let (content, mut result) = self.export_to_string(connections, groups, options)?;
+
+        // Write to file
+        fs::write(&options.output_path, &content).map_err(|e| {
+            ExportError::WriteError(format!(
+                "Failed to write to {}: {}",
+                options.output_path.display(),
+                e
+            ))
+        })?;
+
+        result.add_output_file(options.output_path.clone());
+
+        Ok(result)
+    }
+
+    fn export_to_string(
+        &self,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
         let mut result = ExportResult::new();
+        options.effective_password_mode(&mut result);
 
         // Filter supported connections and count skipped
         let supported_connections: Vec<&Connection> = connections
@@ -296,19 +319,9 @@ impl ExportTarget for RoyalTsExporter {
             groups,
         );
 
-        // Write to file
-        fs::write(&options.output_path, &content).map_err(|e| {
-            ExportError::WriteError(format!(
-                "Failed to write to {}: {}",
-                options.output_path.display(),
-                e
-            ))
-        })?;
-
         result.exported_count = supported_connections.len();
-        result.add_output_file(options.output_path.clone());
 
-        Ok(result)
+        Ok((content, result))
     }
 
     fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
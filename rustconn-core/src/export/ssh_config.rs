@@ -116,6 +116,30 @@ impl SshConfigExporter {
                 let _ = writeln!(output, "    ForwardAgent yes");
             }
 
+            // Port forwarding tunnels
+            for forward in &ssh_config.forwards {
+                let _ = writeln!(
+                    output,
+                    "    {} {}",
+                    forward.config_keyword(),
+                    forward.config_value()
+                );
+            }
+
+            // Session environment
+            for (name, value) in &ssh_config.environment {
+                let _ = writeln!(
+                    output,
+                    "    SetEnv {}",
+                    escape_value(&format!("{name}={value}"))
+                );
+            }
+
+            // Algorithm preferences
+            for (keyword, list) in ssh_config.algorithms.iter() {
+                let _ = writeln!(output, "    {keyword} {}", list.to_value());
+            }
+
             // Custom options
             for (key, value) in &ssh_config.custom_options {
                 let escaped_value = escape_value(value);
@@ -145,10 +169,33 @@ impl ExportTarget for SshConfigExporter {
     fn export(
         &self,
         connections: &[Connection],
-        _groups: &[ConnectionGroup],
+        groups: &[ConnectionGroup],
         options: &ExportOptions,
     ) -> ExportResult2<ExportResult> {
+        let (content, mut result) = self.export_to_string(connections, groups, options)?;
+
+        // Write to file
+        fs::write(&options.output_path, &content).map_err(|e| {
+            ExportError::WriteError(format!(
+                "Failed to write to {}: {}",
+                options.output_path.display(),
+                e
+            ))
+        })?;
+
+        result.add_output_file(options.output_path.clone());
+
+        Ok(result)
+    }
+
+    fn export_to_string(
+        &self,
+        connections: &[Connection],
+        _groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
         let mut result = ExportResult::new();
+        options.effective_password_mode(&mut result);
 
         // Filter SSH connections and count skipped
         let ssh_connections: Vec<&Connection> = connections
@@ -170,19 +217,9 @@ impl ExportTarget for SshConfigExporter {
         // Generate content
         let content = Self::export(&ssh_connections.iter().copied().cloned().collect::<Vec<_>>());
 
-        // Write to file
-        fs::write(&options.output_path, &content).map_err(|e| {
-            ExportError::WriteError(format!(
-                "Failed to write to {}: {}",
-                options.output_path.display(),
-                e
-            ))
-        })?;
-
         result.exported_count = ssh_connections.len();
-        result.add_output_file(options.output_path.clone());
 
-        Ok(result)
+        Ok((content, result))
     }
 
     fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
@@ -223,6 +260,7 @@ fn escape_value(value: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{SshAlgorithmList, SshForward};
     use std::path::PathBuf;
 
     fn create_ssh_connection(name: &str, host: &str, port: u16) -> Connection {
@@ -239,6 +277,25 @@ mod tests {
         assert!(!entry.contains("Port")); // Default port should not be included
     }
 
+    #[test]
+    fn test_export_to_string_counts_skipped() {
+        let connections = vec![
+            create_ssh_connection("myserver", "192.168.1.100", 22),
+            Connection::new_rdp("desktop".to_string(), "192.168.1.200".to_string(), 3389),
+        ];
+        let options = ExportOptions::new(ExportFormat::SshConfig, PathBuf::from("-"));
+
+        let (content, result) = SshConfigExporter::new()
+            .export_to_string(&connections, &[], &options)
+            .unwrap();
+
+        assert!(content.contains("Host myserver"));
+        assert!(!content.contains("desktop"));
+        assert_eq!(result.exported_count, 1);
+        assert_eq!(result.skipped_count, 1);
+        assert!(result.output_files.is_empty());
+    }
+
     #[test]
     fn test_format_host_entry_with_custom_port() {
         let conn = create_ssh_connection("myserver", "192.168.1.100", 2222);
@@ -316,6 +373,71 @@ mod tests {
         assert!(entry.contains("ForwardAgent yes"));
     }
 
+    #[test]
+    fn test_format_host_entry_with_forwards() {
+        let mut conn = create_ssh_connection("tunnel", "bastion.example.com", 22);
+        if let ProtocolConfig::Ssh(ref mut ssh_config) = conn.protocol_config {
+            ssh_config.forwards = vec![
+                SshForward::from_config("LocalForward", "8080 db.internal:5432").unwrap(),
+                SshForward::from_config("LocalForward", "8443 web.internal:443").unwrap(),
+                SshForward::from_config("DynamicForward", "1080").unwrap(),
+            ];
+        }
+        let entry = SshConfigExporter::format_host_entry(&conn);
+
+        assert!(entry.contains("    LocalForward 8080 db.internal:5432\n"));
+        assert!(entry.contains("    LocalForward 8443 web.internal:443\n"));
+        assert!(entry.contains("    DynamicForward 1080\n"));
+    }
+
+    #[test]
+    fn test_format_host_entry_with_environment() {
+        let mut conn = create_ssh_connection("app", "app.example.com", 22);
+        if let ProtocolConfig::Ssh(ref mut ssh_config) = conn.protocol_config {
+            ssh_config.environment = vec![
+                ("LANG".to_string(), "C.UTF-8".to_string()),
+                ("GREETING".to_string(), "hello \"world\"".to_string()),
+            ];
+        }
+        let entry = SshConfigExporter::format_host_entry(&conn);
+
+        assert!(entry.contains("    SetEnv \"LANG=C.UTF-8\"\n"));
+        assert!(entry.contains("    SetEnv \"GREETING=hello \\\"world\\\"\"\n"));
+
+        let imported = crate::import::SshConfigImporter::new().parse_config(&entry, "test");
+        let ProtocolConfig::Ssh(ssh_config) = &imported.connections[0].protocol_config else {
+            panic!("Expected SSH config");
+        };
+        let ProtocolConfig::Ssh(original) = &conn.protocol_config else {
+            unreachable!();
+        };
+        assert_eq!(ssh_config.environment, original.environment);
+    }
+
+    #[test]
+    fn test_format_host_entry_with_algorithms() {
+        let mut conn = create_ssh_connection("switch", "10.0.0.2", 22);
+        if let ProtocolConfig::Ssh(ref mut ssh_config) = conn.protocol_config {
+            ssh_config.algorithms.ciphers = SshAlgorithmList::parse("+aes128-cbc,3des-cbc");
+            ssh_config.algorithms.kex_algorithms =
+                SshAlgorithmList::parse("^diffie-hellman-group14-sha1");
+        }
+        let entry = SshConfigExporter::format_host_entry(&conn);
+
+        assert!(entry.contains("    Ciphers +aes128-cbc,3des-cbc\n"));
+        assert!(entry.contains("    KexAlgorithms ^diffie-hellman-group14-sha1\n"));
+        assert!(!entry.contains("MACs"));
+
+        let imported = crate::import::SshConfigImporter::new().parse_config(&entry, "test");
+        let ProtocolConfig::Ssh(ssh_config) = &imported.connections[0].protocol_config else {
+            panic!("Expected SSH config");
+        };
+        let ProtocolConfig::Ssh(original) = &conn.protocol_config else {
+            unreachable!();
+        };
+        assert_eq!(ssh_config.algorithms, original.algorithms);
+    }
+
     #[test]
     fn test_export_multiple_connections() {
         let connections = vec![
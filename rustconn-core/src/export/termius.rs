@@ -0,0 +1,316 @@
+//! Termius JSON exporter.
+//!
+//! Exports `RustConn` connections to the JSON document Termius imports:
+//! groups, hosts and the SSH keys they reference. Termius only speaks SSH,
+//! so connections using other protocols are skipped.
+//!
+//! Objects reference each other by numeric IDs local to the document.
+//! SSH keys are exported as references to the key file; key material is
+//! never written.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::{debug, info_span};
+use uuid::Uuid;
+
+use crate::models::{Connection, ConnectionGroup, ProtocolConfig, ProtocolType, SshKeySource};
+use crate::tracing::span_names;
+
+use super::{ExportError, ExportFormat, ExportOptions, ExportResult, ExportResult2, ExportTarget};
+
+/// Termius import document
+#[derive(Debug, Default, Serialize)]
+struct TermiusDocument {
+    groups: Vec<TermiusGroup>,
+    hosts: Vec<TermiusHost>,
+    ssh_keys: Vec<TermiusSshKey>,
+}
+
+/// Folder in the Termius host tree
+#[derive(Debug, Serialize)]
+struct TermiusGroup {
+    id: u32,
+    label: String,
+    parent_group: Option<u32>,
+}
+
+/// SSH host entry
+#[derive(Debug, Serialize)]
+struct TermiusHost {
+    id: u32,
+    label: String,
+    address: String,
+    group: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    ssh_config: TermiusSshConfig,
+}
+
+/// Per-host SSH settings
+#[derive(Debug, Serialize)]
+struct TermiusSshConfig {
+    port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssh_key: Option<u32>,
+    agent_forwarding: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    startup_command: Option<String>,
+}
+
+/// Reference to a private key file on the local system
+#[derive(Debug, Serialize)]
+struct TermiusSshKey {
+    id: u32,
+    label: String,
+    path: PathBuf,
+}
+
+/// Termius JSON exporter.
+///
+/// Exports SSH connections with their group hierarchy and key references.
+pub struct TermiusExporter;
+
+impl TermiusExporter {
+    /// Creates a new Termius exporter
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Exports connections to a Termius JSON document.
+    ///
+    /// Connections with protocols Termius does not support are counted as
+    /// skipped in `result` with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExportError::Serialization` if the document cannot be encoded.
+    pub fn export_to_json(
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        result: &mut ExportResult,
+    ) -> ExportResult2<String> {
+        let _span = info_span!(
+            span_names::EXPORT_EXECUTE,
+            format = "termius",
+            connection_count = connections.len()
+        )
+        .entered();
+
+        let mut document = TermiusDocument::default();
+
+        let group_ids: HashMap<Uuid, u32> =
+            groups.iter().zip(1..).map(|(g, id)| (g.id, id)).collect();
+        for group in groups {
+            document.groups.push(TermiusGroup {
+                id: group_ids[&group.id],
+                label: group.name.clone(),
+                parent_group: group.parent_id.and_then(|p| group_ids.get(&p).copied()),
+            });
+        }
+
+        let mut key_ids: HashMap<PathBuf, u32> = HashMap::new();
+        for conn in connections {
+            if conn.protocol != ProtocolType::Ssh {
+                result.increment_skipped();
+                result.add_warning(format!(
+                    "Skipped unsupported connection '{}' (protocol: {})",
+                    conn.name, conn.protocol
+                ));
+                continue;
+            }
+
+            let ssh_key = Self::key_path(conn).map(|path| {
+                let next_id = u32::try_from(key_ids.len())
+                    .unwrap_or(u32::MAX)
+                    .saturating_add(1);
+                *key_ids.entry(path.to_path_buf()).or_insert_with(|| {
+                    document.ssh_keys.push(TermiusSshKey {
+                        id: next_id,
+                        label: path.file_name().map_or_else(
+                            || path.display().to_string(),
+                            |name| name.to_string_lossy().into_owned(),
+                        ),
+                        path: path.to_path_buf(),
+                    });
+                    next_id
+                })
+            });
+
+            let (agent_forwarding, startup_command) = match conn.protocol_config {
+                ProtocolConfig::Ssh(ref ssh) => (ssh.agent_forwarding, ssh.startup_command.clone()),
+                _ => (false, None),
+            };
+
+            let id = u32::try_from(document.hosts.len())
+                .unwrap_or(u32::MAX)
+                .saturating_add(1);
+            document.hosts.push(TermiusHost {
+                id,
+                label: conn.name.clone(),
+                address: conn.host.clone(),
+                group: conn.group_id.and_then(|g| group_ids.get(&g).copied()),
+                tags: conn.tags.clone(),
+                ssh_config: TermiusSshConfig {
+                    port: conn.port,
+                    username: conn.username.clone(),
+                    ssh_key,
+                    agent_forwarding,
+                    startup_command,
+                },
+            });
+            result.increment_exported();
+        }
+
+        debug!(
+            exported = document.hosts.len(),
+            keys = document.ssh_keys.len(),
+            "Termius export completed"
+        );
+        serde_json::to_string_pretty(&document)
+            .map_err(|e| ExportError::Serialization(e.to_string()))
+    }
+
+    /// Returns the private key file a connection authenticates with
+    fn key_path(conn: &Connection) -> Option<&Path> {
+        let ProtocolConfig::Ssh(ref ssh) = conn.protocol_config else {
+            return None;
+        };
+        match ssh.key_source {
+            SshKeySource::File { ref path } => Some(path.as_path()),
+            SshKeySource::Agent { .. } => None,
+            SshKeySource::Default => ssh.key_path.as_deref(),
+        }
+    }
+}
+
+impl Default for TermiusExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExportTarget for TermiusExporter {
+    fn format_id(&self) -> ExportFormat {
+        ExportFormat::Termius
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Termius"
+    }
+
+    fn export(
+        &self,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<ExportResult> {
+        let (content, mut result) = self.export_to_string(connections, groups, options)?;
+
+        fs::write(&options.output_path, &content).map_err(|e| {
+            ExportError::WriteError(format!(
+                "Failed to write to {}: {}",
+                options.output_path.display(),
+                e
+            ))
+        })?;
+
+        result.add_output_file(options.output_path.clone());
+        Ok(result)
+    }
+
+    fn export_to_string(
+        &self,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        options: &ExportOptions,
+    ) -> ExportResult2<(String, ExportResult)> {
+        let mut result = ExportResult::new();
+        options.effective_password_mode(&mut result);
+
+        let content = Self::export_to_json(connections, groups, &mut result)?;
+        Ok((content, result))
+    }
+
+    fn export_connection(&self, connection: &Connection) -> ExportResult2<String> {
+        if connection.protocol != ProtocolType::Ssh {
+            return Err(ExportError::UnsupportedProtocol(format!(
+                "{}",
+                connection.protocol
+            )));
+        }
+        let mut result = ExportResult::new();
+        Self::export_to_json(std::slice::from_ref(connection), &[], &mut result)
+    }
+
+    fn supports_protocol(&self, protocol: &ProtocolType) -> bool {
+        matches!(protocol, ProtocolType::Ssh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SshConfig;
+
+    fn ssh_connection(name: &str, key: Option<&str>) -> Connection {
+        let ssh = SshConfig {
+            key_source: key.map_or(SshKeySource::Default, |k| SshKeySource::File {
+                path: PathBuf::from(k),
+            }),
+            ..SshConfig::default()
+        };
+        Connection::new(
+            name.to_string(),
+            format!("{name}.example.com"),
+            22,
+            ProtocolConfig::Ssh(ssh),
+        )
+        .with_username("admin")
+    }
+
+    #[test]
+    fn test_export_hosts_groups_and_keys() {
+        let parent = ConnectionGroup::new("Prod".to_string());
+        let child = ConnectionGroup::with_parent("Web".to_string(), parent.id);
+        let mut web = ssh_connection("web", Some("/home/user/.ssh/id_ed25519"));
+        web.group_id = Some(child.id);
+        let db = ssh_connection("db", Some("/home/user/.ssh/id_ed25519"));
+
+        let mut result = ExportResult::new();
+        let json =
+            TermiusExporter::export_to_json(&[web, db], &[parent, child], &mut result).unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(result.exported_count, 2);
+        assert_eq!(doc["groups"][1]["label"], "Web");
+        assert_eq!(doc["groups"][1]["parent_group"], 1);
+        assert_eq!(doc["hosts"][0]["group"], 2);
+        assert_eq!(doc["hosts"][0]["address"], "web.example.com");
+        assert_eq!(doc["hosts"][0]["ssh_config"]["username"], "admin");
+        assert_eq!(doc["ssh_keys"].as_array().unwrap().len(), 1);
+        assert_eq!(doc["ssh_keys"][0]["label"], "id_ed25519");
+        assert_eq!(doc["hosts"][1]["ssh_config"]["ssh_key"], 1);
+    }
+
+    #[test]
+    fn test_unsupported_protocols_are_skipped() {
+        let rdp = Connection::new_rdp("desktop".to_string(), "10.0.0.5".to_string(), 3389);
+        let mut result = ExportResult::new();
+        let json =
+            TermiusExporter::export_to_json(&[rdp, ssh_connection("web", None)], &[], &mut result)
+                .unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(result.exported_count, 1);
+        assert_eq!(result.skipped_count, 1);
+        assert!(result.warnings[0].contains("desktop"));
+        assert_eq!(doc["hosts"].as_array().unwrap().len(), 1);
+        assert!(doc["hosts"][0]["ssh_config"].get("ssh_key").is_none());
+    }
+}
@@ -0,0 +1,73 @@
+//! Screenshot encoding for remote displays
+//!
+//! All embedded displays keep their framebuffer as 32-bit BGRA pixels, the
+//! layout Cairo uses on little-endian hosts. Captures are converted to RGBA
+//! and encoded as PNG so they can be saved or copied to the clipboard as-is.
+
+use super::{FfiError, FfiResult};
+
+/// Bytes per BGRA pixel
+const BYTES_PER_PIXEL: usize = 4;
+
+/// Encodes a BGRA framebuffer as an opaque PNG image
+///
+/// The alpha channel is ignored because remote desktops are opaque and
+/// servers often leave it zeroed.
+///
+/// # Errors
+///
+/// Returns `FfiError::InvalidParameter` if the size is zero or does not match
+/// the pixel data, and `FfiError::CaptureFailed` if encoding fails.
+pub fn encode_bgra_png(width: u32, height: u32, bgra: &[u8]) -> FfiResult<Vec<u8>> {
+    let expected = width as usize * height as usize * BYTES_PER_PIXEL;
+    if expected == 0 || bgra.len() != expected {
+        return Err(FfiError::InvalidParameter(format!(
+            "Frame of {} bytes does not match {width}x{height} BGRA",
+            bgra.len()
+        )));
+    }
+
+    let rgba: Vec<u8> = bgra
+        .chunks_exact(BYTES_PER_PIXEL)
+        .flat_map(|px| [px[2], px[1], px[0], u8::MAX])
+        .collect();
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&rgba))
+        .map_err(|e| FfiError::CaptureFailed(e.to_string()))?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_bgra_png_swaps_channels() {
+        // One blue and one red pixel, alpha left at zero
+        let png_data = encode_bgra_png(2, 1, &[255, 0, 0, 0, 0, 0, 255, 0]).unwrap();
+        assert!(png_data.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut reader = png::Decoder::new(std::io::Cursor::new(png_data))
+            .read_info()
+            .unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(pixels, [0, 0, 255, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_encode_bgra_png_rejects_mismatched_size() {
+        assert!(matches!(
+            encode_bgra_png(2, 2, &[0; 4]),
+            Err(FfiError::InvalidParameter(_))
+        ));
+        assert!(encode_bgra_png(0, 0, &[]).is_err());
+    }
+}
@@ -26,9 +26,11 @@
 //! - Signal connection helpers
 //! - Widget accessor for GTK integration
 
+mod capture;
 pub mod vnc;
 
-pub use vnc::{VncCredentialType, VncDisplay, VncError};
+pub use capture::encode_bgra_png;
+pub use vnc::{VncCredentialType, VncDisplay, VncError, VncScalingMode};
 
 use thiserror::Error;
 
@@ -62,6 +64,18 @@ pub enum FfiError {
     /// Resource cleanup failed
     #[error("Cleanup failed: {0}")]
     CleanupFailed(String),
+
+    /// The widget has not been realized yet
+    #[error("Widget not realized: {0}")]
+    NotRealized(String),
+
+    /// The display is not connected to a remote host
+    #[error("Not connected: {0}")]
+    NotConnected(String),
+
+    /// Capturing or encoding a frame failed
+    #[error("Frame capture failed: {0}")]
+    CaptureFailed(String),
 }
 
 /// Result type for FFI operations
@@ -117,6 +131,29 @@ pub trait FfiDisplay {
 
     /// Closes the current connection
     fn close(&self);
+
+    /// Returns the remote framebuffer size in pixels
+    ///
+    /// Returns `Ok(None)` until the server has reported a size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FfiError::NotRealized` if the widget has not been realized.
+    fn framebuffer_size(&self) -> FfiResult<Option<(u32, u32)>>;
+
+    /// Captures the current remote frame as a PNG image
+    ///
+    /// Only [`VncDisplay`] implements this trait. Embedded RDP sessions
+    /// capture through `RdpClient::capture_frame` instead, and SPICE
+    /// sessions do not support frame capture.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FfiError::NotRealized` if the widget has not been realized,
+    /// `FfiError::NotConnected` if no session is active, and
+    /// `FfiError::CaptureFailed` if no frame has been received yet or
+    /// encoding fails.
+    fn capture_frame(&self) -> FfiResult<Vec<u8>>;
 }
 
 #[cfg(test)]
@@ -8,7 +8,8 @@
 //! The `VncDisplay` struct wraps the `GtkVncDisplay` widget and provides:
 //! - Connection management (`open_host`, `close`, `is_open`)
 //! - Authentication handling (`set_credential`)
-//! - Display configuration (`set_scaling`)
+//! - Display configuration (`set_scaling`, `framebuffer_size`)
+//! - Screenshots (`capture_frame`)
 //! - Signal connections for state changes
 //!
 //! # Requirements Coverage
@@ -44,7 +45,7 @@
 //! display.open_host("192.168.1.100", 5900)?;
 //! ```
 
-use super::{ConnectionState, FfiDisplay, FfiError};
+use super::{encode_bgra_png, ConnectionState, FfiDisplay, FfiError, FfiResult};
 use std::cell::RefCell;
 use std::rc::Rc;
 use thiserror::Error;
@@ -74,6 +75,18 @@ pub enum VncError {
     /// Widget not initialized
     #[error("VNC display widget not initialized")]
     NotInitialized,
+
+    /// Widget not realized yet
+    #[error("VNC display widget not realized")]
+    NotRealized,
+
+    /// No VNC session is active
+    #[error("VNC display is not connected")]
+    NotConnected,
+
+    /// The current frame could not be captured
+    #[error("VNC frame capture failed: {0}")]
+    CaptureFailed(String),
 }
 
 impl From<VncError> for FfiError {
@@ -88,6 +101,9 @@ impl From<VncError> for FfiError {
             }
             VncError::InvalidCredential(msg) => Self::InvalidParameter(msg),
             VncError::NotInitialized => Self::WidgetCreationFailed("Not initialized".to_string()),
+            VncError::NotRealized => Self::NotRealized("VNC display".to_string()),
+            VncError::NotConnected => Self::NotConnected("VNC display".to_string()),
+            VncError::CaptureFailed(msg) => Self::CaptureFailed(msg),
         }
     }
 }
@@ -116,6 +132,56 @@ impl std::fmt::Display for VncCredentialType {
     }
 }
 
+/// How the remote desktop is scaled into the widget
+///
+/// [`scaling`](Self::scaling) and [`keep_aspect_ratio`](Self::keep_aspect_ratio)
+/// give the matching values of the `GtkVncDisplay` properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VncScalingMode {
+    /// Native resolution, scrolled if larger than the widget
+    #[default]
+    None,
+    /// Scaled to fit the widget, preserving the aspect ratio
+    Fit,
+    /// Scaled to fill the widget, stretching if needed
+    Fill,
+}
+
+impl VncScalingMode {
+    /// Value for the `scaling` property
+    #[must_use]
+    pub const fn scaling(self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Value for the `keep-aspect-ratio` property
+    #[must_use]
+    pub const fn keep_aspect_ratio(self) -> bool {
+        !matches!(self, Self::Fill)
+    }
+}
+
+impl From<bool> for VncScalingMode {
+    /// Converts the legacy scaling toggle (`true` = fit to window)
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Self::Fit
+        } else {
+            Self::None
+        }
+    }
+}
+
+impl std::fmt::Display for VncScalingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Fit => write!(f, "Fit"),
+            Self::Fill => write!(f, "Fill"),
+        }
+    }
+}
+
 /// Internal state for VNC display
 #[derive(Debug, Default)]
 struct VncDisplayState {
@@ -125,8 +191,14 @@ struct VncDisplayState {
     host: Option<String>,
     /// Connected port (if any)
     port: Option<u16>,
-    /// Whether scaling is enabled
-    scaling_enabled: bool,
+    /// Scaling mode applied to the widget
+    scaling_mode: VncScalingMode,
+    /// Whether the widget has been realized
+    realized: bool,
+    /// Remote framebuffer size reported by the server
+    framebuffer_size: Option<(u32, u32)>,
+    /// Latest frame as BGRA pixels matching `framebuffer_size`
+    framebuffer: Option<Vec<u8>>,
     /// Stored credentials
     credentials: std::collections::HashMap<VncCredentialType, String>,
 }
@@ -254,6 +326,8 @@ impl VncDisplay {
         state.connection_state = ConnectionState::Disconnected;
         state.host = None;
         state.port = None;
+        state.framebuffer_size = None;
+        state.framebuffer = None;
         state.credentials.clear();
     }
 
@@ -296,24 +370,112 @@ impl VncDisplay {
         Ok(())
     }
 
-    /// Enables or disables display scaling
+    /// Sets how the remote display is scaled into the widget
     ///
-    /// When scaling is enabled, the remote display will be scaled to fit
-    /// the widget's allocated size. When disabled, the display will be
-    /// shown at its native resolution with scrollbars if necessary.
+    /// Only records the mode; the widget reads it back with
+    /// [`Self::scaling_mode`] when it lays out the remote display.
     ///
     /// # Arguments
     ///
-    /// * `enabled` - Whether to enable scaling
-    pub fn set_scaling(&self, enabled: bool) {
+    /// * `mode` - The scaling mode
+    pub fn set_scaling(&self, mode: VncScalingMode) {
         let mut state = self.state.borrow_mut();
-        state.scaling_enabled = enabled;
+        state.scaling_mode = mode;
+    }
+
+    /// Returns the current scaling mode
+    #[must_use]
+    pub fn scaling_mode(&self) -> VncScalingMode {
+        self.state.borrow().scaling_mode
     }
 
     /// Returns whether scaling is enabled
     #[must_use]
     pub fn scaling_enabled(&self) -> bool {
-        self.state.borrow().scaling_enabled
+        self.scaling_mode().scaling()
+    }
+
+    /// Records whether the underlying widget is realized
+    ///
+    /// Called from the widget's `realize` and `unrealize` handlers.
+    pub fn set_realized(&self, realized: bool) {
+        self.state.borrow_mut().realized = realized;
+    }
+
+    /// Returns whether the underlying widget is realized
+    #[must_use]
+    pub fn is_realized(&self) -> bool {
+        self.state.borrow().realized
+    }
+
+    /// Records the connection state reported by the widget
+    ///
+    /// Embedded viewers that drive the connection themselves call this so
+    /// that state-dependent calls such as [`Self::capture_frame`] see the
+    /// real session state. No signal callbacks are invoked.
+    pub fn set_connection_state(&self, connection_state: ConnectionState) {
+        self.state.borrow_mut().connection_state = connection_state;
+    }
+
+    /// Records the remote framebuffer size
+    ///
+    /// Call this whenever the server reports a new desktop size.
+    pub fn update_framebuffer_size(&self, width: u32, height: u32) {
+        let mut state = self.state.borrow_mut();
+        if state.framebuffer_size != Some((width, height)) {
+            state.framebuffer = None;
+        }
+        state.framebuffer_size = Some((width, height));
+    }
+
+    /// Records the latest frame as BGRA pixels
+    ///
+    /// Also records the framebuffer size. Called whenever the widget has
+    /// finished drawing a server update.
+    pub fn update_framebuffer(&self, width: u32, height: u32, pixels: Vec<u8>) {
+        let mut state = self.state.borrow_mut();
+        state.framebuffer_size = Some((width, height));
+        state.framebuffer = Some(pixels);
+    }
+
+    /// Returns the remote framebuffer size in pixels
+    ///
+    /// Returns `Ok(None)` until the server has reported a size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VncError::NotRealized` if the widget has not been realized.
+    pub fn framebuffer_size(&self) -> Result<Option<(u32, u32)>, VncError> {
+        let state = self.state.borrow();
+        if !state.realized {
+            return Err(VncError::NotRealized);
+        }
+        Ok(state.framebuffer_size)
+    }
+
+    /// Captures the latest frame as a PNG image
+    ///
+    /// # Errors
+    ///
+    /// Returns `VncError::NotRealized` if the widget has not been realized,
+    /// `VncError::NotConnected` if no session is active, and
+    /// `VncError::CaptureFailed` if no frame has been received yet or
+    /// encoding fails.
+    pub fn capture_frame(&self) -> Result<Vec<u8>, VncError> {
+        let state = self.state.borrow();
+        if !state.realized {
+            return Err(VncError::NotRealized);
+        }
+        if state.connection_state != ConnectionState::Connected {
+            return Err(VncError::NotConnected);
+        }
+        let (Some((width, height)), Some(pixels)) = (state.framebuffer_size, &state.framebuffer)
+        else {
+            return Err(VncError::CaptureFailed(
+                "no frame received from the server yet".to_string(),
+            ));
+        };
+        encode_bgra_png(width, height, pixels).map_err(|e| VncError::CaptureFailed(e.to_string()))
     }
 
     /// Returns the current connection state
@@ -447,6 +609,14 @@ impl FfiDisplay for VncDisplay {
     fn close(&self) {
         Self::close(self);
     }
+
+    fn framebuffer_size(&self) -> FfiResult<Option<(u32, u32)>> {
+        Ok(Self::framebuffer_size(self)?)
+    }
+
+    fn capture_frame(&self) -> FfiResult<Vec<u8>> {
+        Ok(Self::capture_frame(self)?)
+    }
 }
 
 impl Drop for VncDisplay {
@@ -543,13 +713,84 @@ mod tests {
         let display = VncDisplay::new();
         assert!(!display.scaling_enabled());
 
-        display.set_scaling(true);
+        display.set_scaling(true.into());
         assert!(display.scaling_enabled());
 
-        display.set_scaling(false);
+        display.set_scaling(false.into());
         assert!(!display.scaling_enabled());
     }
 
+    #[test]
+    fn test_vnc_scaling_mode_properties() {
+        let display = VncDisplay::new();
+        assert_eq!(display.scaling_mode(), VncScalingMode::None);
+
+        display.set_scaling(VncScalingMode::Fill);
+        assert_eq!(display.scaling_mode(), VncScalingMode::Fill);
+        assert!(display.scaling_enabled());
+
+        let props = |m: VncScalingMode| (m.scaling(), m.keep_aspect_ratio());
+        assert_eq!(props(VncScalingMode::None), (false, true));
+        assert_eq!(props(VncScalingMode::Fit), (true, true));
+        assert_eq!(props(VncScalingMode::Fill), (true, false));
+    }
+
+    #[test]
+    fn test_vnc_framebuffer_size_requires_realized_widget() {
+        let display = VncDisplay::new();
+        assert!(matches!(
+            display.framebuffer_size(),
+            Err(VncError::NotRealized)
+        ));
+        assert!(matches!(
+            FfiDisplay::framebuffer_size(&display),
+            Err(FfiError::NotRealized(_))
+        ));
+
+        display.set_realized(true);
+        assert_eq!(display.framebuffer_size().unwrap(), None);
+
+        display.open_host("localhost", 5900).unwrap();
+        display.update_framebuffer_size(3840, 2160);
+        assert_eq!(
+            FfiDisplay::framebuffer_size(&display).unwrap(),
+            Some((3840, 2160))
+        );
+
+        display.close();
+        assert_eq!(display.framebuffer_size().unwrap(), None);
+    }
+
+    #[test]
+    fn test_vnc_capture_frame() {
+        let display = VncDisplay::new();
+        assert!(matches!(
+            FfiDisplay::capture_frame(&display),
+            Err(FfiError::NotRealized(_))
+        ));
+
+        display.set_realized(true);
+        assert!(matches!(
+            FfiDisplay::capture_frame(&display),
+            Err(FfiError::NotConnected(_))
+        ));
+
+        display.open_host("localhost", 5900).unwrap();
+        display.set_connection_state(ConnectionState::Connected);
+        assert!(matches!(
+            display.capture_frame(),
+            Err(VncError::CaptureFailed(_))
+        ));
+
+        display.update_framebuffer(2, 2, vec![0x80; 16]);
+        let png = FfiDisplay::capture_frame(&display).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        // A resize invalidates the stored frame
+        display.update_framebuffer_size(4, 4);
+        assert!(display.capture_frame().is_err());
+    }
+
     #[test]
     fn test_vnc_display_connected_signal() {
         let display = VncDisplay::new();
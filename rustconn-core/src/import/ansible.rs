@@ -1,18 +1,55 @@
 //! Ansible inventory importer.
 //!
 //! Parses Ansible inventory files in INI and YAML formats.
+//!
+//! When importing from a path, the `group_vars/` and `host_vars/`
+//! directories next to the inventory are loaded as well and their variables
+//! are merged into the imported connections.
 
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use uuid::Uuid;
 
 use crate::error::ImportError;
-use crate::models::{Connection, ConnectionGroup, ProtocolConfig, SshConfig};
+use crate::models::{
+    Connection, ConnectionGroup, CustomProperty, ProtocolConfig, ProtocolType, SshConfig,
+};
 
+use super::batch::{ImportItem, ImportStream};
+use super::normalize::yaml_location;
 use super::traits::{ImportResult, ImportSource, SkippedEntry};
 
+/// File extensions ignored when importing an inventory directory
+const IGNORED_INVENTORY_EXTENSIONS: &[&str] = &["orig", "bak", "cfg", "retry", "md"];
+
+/// Variables loaded from `group_vars/` or `host_vars/`, keyed by group or host name
+type VarsByName = HashMap<String, serde_yaml::Mapping>;
+
+/// Parser state carried between the lines of an INI inventory
+#[derive(Default)]
+struct IniState {
+    current_group: Option<(String, Uuid)>,
+    group_vars: HashMap<String, HashMap<String, String>>,
+    in_vars_section: bool,
+    vars_group_name: Option<String>,
+}
+
+/// A YAML inventory being parsed, for locating skipped entries
+struct YamlSource<'a> {
+    content: &'a str,
+    path: &'a str,
+}
+
+impl YamlSource<'_> {
+    /// Returns the `file:line (node path)` location of a nested key
+    fn location(&self, node_path: &[&str]) -> String {
+        yaml_location(self.content, self.path, node_path)
+    }
+}
+
 /// Importer for Ansible inventory files.
 ///
 /// Supports both INI-style and YAML inventory formats.
@@ -39,8 +76,18 @@ impl AnsibleInventoryImporter {
     }
 
     /// Parses inventory content (auto-detects format)
+    ///
+    /// A host listed in several groups becomes a single connection; its
+    /// further groups are recorded in `Connection::additional_group_ids`.
     #[must_use]
     pub fn parse_inventory(&self, content: &str, source_path: &str) -> ImportResult {
+        let mut result = self.parse_inventory_unmerged(content, source_path);
+        result.merge_duplicate_connections();
+        result
+    }
+
+    /// Parses inventory content, keeping one connection per group entry
+    fn parse_inventory_unmerged(&self, content: &str, source_path: &str) -> ImportResult {
         // Try YAML first, then INI
         if content.trim().starts_with("---") || content.contains("hosts:") {
             self.parse_yaml_inventory(content, source_path)
@@ -53,75 +100,85 @@ impl AnsibleInventoryImporter {
     #[must_use]
     pub fn parse_ini_inventory(&self, content: &str, source_path: &str) -> ImportResult {
         let mut result = ImportResult::new();
-        let mut current_group: Option<(String, Uuid)> = None;
-        let mut group_vars: HashMap<String, HashMap<String, String>> = HashMap::new();
-        let mut in_vars_section = false;
-        let mut vars_group_name: Option<String> = None;
+        let mut state = IniState::default();
 
         for (line_num, line) in content.lines().enumerate() {
-            let line = line.trim();
-
-            // Skip empty lines and comments
-            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
-                continue;
-            }
+            self.parse_ini_line(&mut state, line, line_num + 1, source_path, &mut result);
+        }
 
-            // Check for group header
-            if line.starts_with('[') && line.ends_with(']') {
-                let section = &line[1..line.len() - 1];
+        result
+    }
 
-                // Check if this is a :vars section
-                if section.contains(":vars") {
-                    in_vars_section = true;
-                    vars_group_name = Some(section.split(':').next().unwrap_or("").to_string());
-                    continue;
-                }
+    /// Parses one line of an INI inventory
+    fn parse_ini_line(
+        &self,
+        state: &mut IniState,
+        line: &str,
+        line_num: usize,
+        source_path: &str,
+        result: &mut ImportResult,
+    ) {
+        let line = line.trim();
 
-                // Check if this is a :children section (skip for now)
-                if section.contains(":children") {
-                    in_vars_section = false;
-                    vars_group_name = None;
-                    current_group = None;
-                    continue;
-                }
+        // Skip empty lines and comments
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            return;
+        }
 
-                in_vars_section = false;
-                vars_group_name = None;
+        // Check for group header
+        if line.starts_with('[') && line.ends_with(']') {
+            let section = &line[1..line.len() - 1];
 
-                // Create new group
-                let group = ConnectionGroup::new(section.to_string());
-                let group_id = group.id;
-                result.add_group(group);
-                current_group = Some((section.to_string(), group_id));
-                continue;
+            // Check if this is a :vars section
+            if section.contains(":vars") {
+                state.in_vars_section = true;
+                state.vars_group_name = Some(section.split(':').next().unwrap_or("").to_string());
+                return;
             }
 
-            // Handle vars section
-            if in_vars_section {
-                if let Some(ref group_name) = vars_group_name {
-                    if let Some((key, value)) = Self::parse_var_line(line) {
-                        group_vars
-                            .entry(group_name.clone())
-                            .or_default()
-                            .insert(key, value);
-                    }
-                }
-                continue;
+            // Check if this is a :children section (skip for now)
+            if section.contains(":children") {
+                state.in_vars_section = false;
+                state.vars_group_name = None;
+                state.current_group = None;
+                return;
             }
 
-            // Parse host line
-            if let Some(connection) = self.parse_host_line(
-                line,
-                line_num + 1,
-                current_group.as_ref().map(|(_, id)| *id),
-                source_path,
-                &mut result,
-            ) {
-                result.add_connection(connection);
+            state.in_vars_section = false;
+            state.vars_group_name = None;
+
+            // Create new group
+            let group = ConnectionGroup::new(section.to_string());
+            let group_id = group.id;
+            result.add_group(group);
+            state.current_group = Some((section.to_string(), group_id));
+            return;
+        }
+
+        // Handle vars section
+        if state.in_vars_section {
+            if let Some(ref group_name) = state.vars_group_name {
+                if let Some((key, value)) = Self::parse_var_line(line) {
+                    state
+                        .group_vars
+                        .entry(group_name.clone())
+                        .or_default()
+                        .insert(key, value);
+                }
             }
+            return;
         }
 
-        result
+        // Parse host line
+        if let Some(connection) = self.parse_host_line(
+            line,
+            line_num,
+            state.current_group.as_ref().map(|(_, id)| *id),
+            source_path,
+            result,
+        ) {
+            result.add_connection(connection);
+        }
     }
 
     /// Parses a variable assignment line
@@ -237,6 +294,11 @@ impl AnsibleInventoryImporter {
             }
         };
 
+        let source = YamlSource {
+            content,
+            path: source_path,
+        };
+
         // Process top-level groups
         if let serde_yaml::Value::Mapping(map) = yaml {
             for (group_name, group_value) in map {
@@ -254,7 +316,8 @@ impl AnsibleInventoryImporter {
                                             child_name_str,
                                             child_value,
                                             None,
-                                            source_path,
+                                            &["all", "children"],
+                                            &source,
                                             &mut result,
                                         );
                                     }
@@ -264,7 +327,13 @@ impl AnsibleInventoryImporter {
                             if let Some(hosts) =
                                 all_map.get(serde_yaml::Value::String("hosts".to_string()))
                             {
-                                self.process_yaml_hosts(hosts, None, source_path, &mut result);
+                                self.process_yaml_hosts(
+                                    hosts,
+                                    None,
+                                    &["all", "hosts"],
+                                    &source,
+                                    &mut result,
+                                );
                             }
                         }
                     } else {
@@ -272,7 +341,8 @@ impl AnsibleInventoryImporter {
                             &name,
                             &group_value,
                             None,
-                            source_path,
+                            &[],
+                            &source,
                             &mut result,
                         );
                     }
@@ -284,14 +354,20 @@ impl AnsibleInventoryImporter {
     }
 
     /// Processes a YAML group
+    ///
+    /// `parent_path` is the chain of YAML keys leading to the group.
     fn process_yaml_group(
         &self,
         name: &str,
         value: &serde_yaml::Value,
         parent_id: Option<Uuid>,
-        source_path: &str,
+        parent_path: &[&str],
+        source: &YamlSource<'_>,
         result: &mut ImportResult,
     ) {
+        let mut node_path = parent_path.to_vec();
+        node_path.push(name);
+
         // Create group
         let group = parent_id.map_or_else(
             || ConnectionGroup::new(name.to_string()),
@@ -303,20 +379,23 @@ impl AnsibleInventoryImporter {
         if let serde_yaml::Value::Mapping(map) = value {
             // Process hosts
             if let Some(hosts) = map.get(serde_yaml::Value::String("hosts".to_string())) {
-                self.process_yaml_hosts(hosts, Some(group_id), source_path, result);
+                let hosts_path = [node_path.as_slice(), &["hosts"]].concat();
+                self.process_yaml_hosts(hosts, Some(group_id), &hosts_path, source, result);
             }
 
             // Process children groups
             if let Some(serde_yaml::Value::Mapping(children)) =
                 map.get(serde_yaml::Value::String("children".to_string()))
             {
+                let children_path = [node_path.as_slice(), &["children"]].concat();
                 for (child_name, child_value) in children {
                     if let serde_yaml::Value::String(child_name_str) = child_name {
                         self.process_yaml_group(
                             child_name_str,
                             child_value,
                             Some(group_id),
-                            source_path,
+                            &children_path,
+                            source,
                             result,
                         );
                     }
@@ -331,7 +410,8 @@ impl AnsibleInventoryImporter {
         &self,
         hosts: &serde_yaml::Value,
         group_id: Option<Uuid>,
-        source_path: &str,
+        hosts_path: &[&str],
+        source: &YamlSource<'_>,
         result: &mut ImportResult,
     ) {
         if let serde_yaml::Value::Mapping(hosts_map) = hosts {
@@ -342,7 +422,7 @@ impl AnsibleInventoryImporter {
                         result.add_skipped(SkippedEntry::with_location(
                             name,
                             "Patterns are not supported",
-                            source_path,
+                            source.location(&[hosts_path, &[name.as_str()]].concat()),
                         ));
                         continue;
                     }
@@ -400,6 +480,340 @@ impl AnsibleInventoryImporter {
     }
 }
 
+impl AnsibleInventoryImporter {
+    /// Lists the inventory files of an inventory directory, sorted by name
+    fn inventory_dir_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        let name = path.file_name().unwrap_or_default().to_string_lossy();
+                        let ignored_extension = path.extension().is_some_and(|ext| {
+                            IGNORED_INVENTORY_EXTENSIONS.contains(&&*ext.to_string_lossy())
+                        });
+                        path.is_file()
+                            && !name.starts_with('.')
+                            && !name.ends_with('~')
+                            && !ignored_extension
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+        files
+    }
+
+    /// Loads `<base>/<kind>/` variable files into a map keyed by file stem
+    ///
+    /// Each entry is either a YAML file (`web.yml`, `web.yaml` or `web`) or a
+    /// directory whose YAML files are merged in name order.
+    fn load_vars_dir(base: &Path, kind: &str, result: &mut ImportResult) -> VarsByName {
+        let mut vars = VarsByName::new();
+        let Ok(entries) = fs::read_dir(base.join(kind)) else {
+            return vars;
+        };
+
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+
+            let (key, files) = if path.is_dir() {
+                (name, Self::inventory_dir_files(&path))
+            } else {
+                let key = match path.extension().and_then(|e| e.to_str()) {
+                    Some("yml" | "yaml" | "json") => path
+                        .file_stem()
+                        .map_or_else(|| name.clone(), |stem| stem.to_string_lossy().into_owned()),
+                    Some(_) => continue,
+                    None => name,
+                };
+                (key, vec![path])
+            };
+
+            for file in files {
+                match Self::load_vars_file(&file) {
+                    Ok(mapping) => vars.entry(key.clone()).or_default().extend(mapping),
+                    Err(reason) => result.add_skipped(SkippedEntry::with_location(
+                        format!("{kind}/{key}"),
+                        reason,
+                        file.display().to_string(),
+                    )),
+                }
+            }
+        }
+
+        vars
+    }
+
+    /// Reads a single YAML variables file
+    fn load_vars_file(path: &Path) -> Result<serde_yaml::Mapping, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read: {e}"))?;
+        match serde_yaml::from_str::<serde_yaml::Value>(&content) {
+            Ok(serde_yaml::Value::Mapping(mapping)) => Ok(mapping),
+            Ok(serde_yaml::Value::Null) => Ok(serde_yaml::Mapping::new()),
+            Ok(_) => Err("Variables file is not a mapping".to_string()),
+            Err(e) => Err(format!("Failed to parse YAML: {e}")),
+        }
+    }
+
+    /// Merges `group_vars/` and `host_vars/` found in `base` into the connections
+    ///
+    /// Precedence follows Ansible: `all` group, then parent groups, then the
+    /// host's own group, then `host_vars`. Group variables only fill in
+    /// values the inventory left at their defaults, while `host_vars` always
+    /// win. `ansible_host`, `ansible_user`, `ansible_port` and
+    /// `ansible_ssh_private_key_file` map to connection fields; any other
+    /// variable becomes a custom property.
+    fn apply_vars_dirs(base: &Path, result: &mut ImportResult) {
+        let group_vars = Self::load_vars_dir(base, "group_vars", result);
+        let host_vars = Self::load_vars_dir(base, "host_vars", result);
+        if group_vars.is_empty() && host_vars.is_empty() {
+            return;
+        }
+
+        let groups: HashMap<Uuid, &ConnectionGroup> =
+            result.groups.iter().map(|g| (g.id, g)).collect();
+
+        for connection in &mut result.connections {
+            // Group chain from the root down to the connection's own group
+            let mut chain = Vec::new();
+            let mut next = connection.group_id;
+            while let Some(group) = next.and_then(|id| groups.get(&id)) {
+                if chain.len() > groups.len() {
+                    break;
+                }
+                chain.push(group.name.as_str());
+                next = group.parent_id;
+            }
+            chain.push("all");
+            chain.reverse();
+
+            Self::apply_chain_vars(connection, &chain, &group_vars, &host_vars);
+        }
+    }
+
+    /// Applies the variables of a root-first group chain, then `host_vars`
+    fn apply_chain_vars(
+        connection: &mut Connection,
+        chain: &[&str],
+        group_vars: &VarsByName,
+        host_vars: &VarsByName,
+    ) {
+        let mut merged = serde_yaml::Mapping::new();
+        for name in chain {
+            if let Some(vars) = group_vars.get(*name) {
+                merged.extend(vars.clone());
+            }
+        }
+        Self::apply_vars(connection, &merged, false);
+
+        if let Some(vars) = host_vars.get(&connection.name) {
+            Self::apply_vars(connection, vars, true);
+        }
+    }
+
+    /// Applies Ansible variables to a connection
+    ///
+    /// With `override_inventory` unset, mapped fields are only changed while
+    /// they still hold the values an inventory without variables produces.
+    fn apply_vars(
+        connection: &mut Connection,
+        vars: &serde_yaml::Mapping,
+        override_inventory: bool,
+    ) {
+        for (key, value) in vars {
+            let (Some(key), Some(value)) = (key.as_str(), Self::yaml_scalar_string(value)) else {
+                continue;
+            };
+
+            match key {
+                "ansible_host" | "ansible_ssh_host" => {
+                    if override_inventory || connection.host == connection.name {
+                        connection.host = value;
+                    }
+                }
+                "ansible_user" | "ansible_ssh_user" => {
+                    if override_inventory || connection.username.is_none() {
+                        connection.username = Some(value);
+                    }
+                }
+                "ansible_port" | "ansible_ssh_port" => {
+                    if let Ok(port) = value.parse() {
+                        if override_inventory || connection.port == 22 {
+                            connection.port = port;
+                        }
+                    }
+                }
+                "ansible_ssh_private_key_file" => {
+                    if let ProtocolConfig::Ssh(ssh) = &mut connection.protocol_config {
+                        if override_inventory || ssh.key_path.is_none() {
+                            ssh.key_path =
+                                Some(PathBuf::from(shellexpand::tilde(&value).into_owned()));
+                        }
+                    }
+                }
+                _ => {
+                    let property = if key.contains("pass") {
+                        CustomProperty::new_protected(key, value)
+                    } else {
+                        CustomProperty::new_text(key, value)
+                    };
+                    connection.custom_properties.retain(|p| p.name != key);
+                    connection.custom_properties.push(property);
+                }
+            }
+        }
+    }
+
+    /// Converts a YAML value to a string, serializing collections as JSON
+    ///
+    /// Tagged values such as `!vault` encrypted strings are skipped.
+    fn yaml_scalar_string(value: &serde_yaml::Value) -> Option<String> {
+        match value {
+            serde_yaml::Value::String(s) => Some(s.clone()),
+            serde_yaml::Value::Number(n) => Some(n.to_string()),
+            serde_yaml::Value::Bool(b) => Some(b.to_string()),
+            serde_yaml::Value::Sequence(_) | serde_yaml::Value::Mapping(_) => {
+                serde_json::to_string(value).ok()
+            }
+            serde_yaml::Value::Null | serde_yaml::Value::Tagged(_) => None,
+        }
+    }
+
+    /// Imports a file or inventory directory without merging repeated hosts
+    fn import_unmerged(&self, path: &Path) -> Result<ImportResult, ImportError> {
+        if !path.exists() {
+            return Err(ImportError::FileNotFound(path.to_path_buf()));
+        }
+
+        let (files, base) = if path.is_dir() {
+            (Self::inventory_dir_files(path), path)
+        } else {
+            (
+                vec![path.to_path_buf()],
+                path.parent().unwrap_or_else(|| Path::new(".")),
+            )
+        };
+
+        let mut result = ImportResult::new();
+        for file in files {
+            let content = fs::read_to_string(&file).map_err(|e| ImportError::ParseError {
+                source_name: "Ansible inventory".to_string(),
+                reason: format!("Failed to read {}: {}", file.display(), e),
+            })?;
+            result.merge(self.parse_inventory_unmerged(&content, &file.display().to_string()));
+        }
+
+        Self::apply_vars_dirs(base, &mut result);
+        Ok(result)
+    }
+
+    /// Opens an inventory for streaming import
+    ///
+    /// INI inventories are read one line at a time, so memory use does not
+    /// grow with the file size; use this with
+    /// [`BatchImporter::process_stream`] for files above
+    /// [`STREAMING_IMPORT_THRESHOLD`]. YAML inventories and inventory
+    /// directories are not streamed: they are read and parsed whole, and
+    /// the returned stream reports this through
+    /// [`ImportStream::is_incremental`].
+    ///
+    /// [`BatchImporter::process_stream`]: super::BatchImporter::process_stream
+    /// [`STREAMING_IMPORT_THRESHOLD`]: super::STREAMING_IMPORT_THRESHOLD
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the inventory cannot be opened or read.
+    pub fn stream_from_path(&self, path: &Path) -> Result<AnsibleInventoryStream<'_>, ImportError> {
+        if !path.exists() {
+            return Err(ImportError::FileNotFound(path.to_path_buf()));
+        }
+
+        let total_bytes = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+        let mut stream = AnsibleInventoryStream {
+            importer: self,
+            lines: None,
+            line_num: 0,
+            state: IniState::default(),
+            source_path: path.display().to_string(),
+            group_names: HashMap::new(),
+            group_vars: VarsByName::new(),
+            host_vars: VarsByName::new(),
+            seen: HashMap::new(),
+            queue: VecDeque::new(),
+            bytes_read: 0,
+            total_bytes,
+            incremental: true,
+        };
+
+        if !Self::is_streamable(path)? {
+            let result = self.import_unmerged(path)?;
+            stream.incremental = false;
+            stream.bytes_read = total_bytes;
+            stream.enqueue(result, false);
+            return Ok(stream);
+        }
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut issues = ImportResult::new();
+        stream.group_vars = Self::load_vars_dir(base, "group_vars", &mut issues);
+        stream.host_vars = Self::load_vars_dir(base, "host_vars", &mut issues);
+        stream.enqueue(issues, false);
+        stream.lines = Some(BufReader::new(Self::open_inventory(path)?).lines());
+        Ok(stream)
+    }
+
+    /// Returns whether [`Self::stream_from_path`] reads the inventory
+    /// incrementally, which is the case for INI files but not for YAML
+    /// inventories and inventory directories
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the inventory cannot be read.
+    pub fn is_streamable(path: &Path) -> Result<bool, ImportError> {
+        Ok(!path.is_dir() && !Self::is_yaml_inventory(path)?)
+    }
+
+    /// Detects a YAML inventory the same way as [`Self::parse_inventory`],
+    /// without reading the whole file into memory
+    fn is_yaml_inventory(path: &Path) -> Result<bool, ImportError> {
+        let mut first_content_line = true;
+        for line in BufReader::new(Self::open_inventory(path)?).lines() {
+            let line = line.map_err(|e| Self::read_error(path, &e))?;
+            if first_content_line && !line.trim().is_empty() {
+                if line.trim_start().starts_with("---") {
+                    return Ok(true);
+                }
+                first_content_line = false;
+            }
+            if line.contains("hosts:") {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn open_inventory(path: &Path) -> Result<File, ImportError> {
+        File::open(path).map_err(|e| Self::read_error(path, &e))
+    }
+
+    fn read_error(path: &Path, error: &io::Error) -> ImportError {
+        ImportError::ParseError {
+            source_name: "Ansible inventory".to_string(),
+            reason: format!("Failed to read {}: {}", path.display(), error),
+        }
+    }
+}
+
 impl Default for AnsibleInventoryImporter {
     fn default() -> Self {
         Self::new()
@@ -482,16 +896,132 @@ impl ImportSource for AnsibleInventoryImporter {
     }
 
     fn import_from_path(&self, path: &Path) -> Result<ImportResult, ImportError> {
-        if !path.exists() {
-            return Err(ImportError::FileNotFound(path.to_path_buf()));
+        // Merge after applying vars so every copy sees its own group's vars
+        let mut result = self.import_unmerged(path)?;
+        result.merge_duplicate_connections();
+        Ok(result)
+    }
+}
+
+/// Streaming Ansible inventory parser created by
+/// [`AnsibleInventoryImporter::stream_from_path`]
+///
+/// A host repeated in several groups is yielded once and reported as merged
+/// duplicates afterwards; only the host keys are kept in memory for this.
+/// Only INI inventories are parsed incrementally.
+pub struct AnsibleInventoryStream<'a> {
+    importer: &'a AnsibleInventoryImporter,
+    /// Unread lines of an INI inventory, `None` once exhausted
+    lines: Option<io::Lines<BufReader<File>>>,
+    line_num: usize,
+    state: IniState,
+    source_path: String,
+    /// Group names by ID, for applying `group_vars`
+    group_names: HashMap<Uuid, String>,
+    group_vars: VarsByName,
+    host_vars: VarsByName,
+    /// First connection for each host, port and protocol, with its group
+    seen: HashMap<(String, u16, ProtocolType), (Uuid, Option<Uuid>)>,
+    queue: VecDeque<ImportItem>,
+    bytes_read: u64,
+    total_bytes: u64,
+    /// False for YAML inventories and directories, which are read whole
+    incremental: bool,
+}
+
+impl AnsibleInventoryStream<'_> {
+    /// Queues the contents of `result`, folding repeated hosts into the first
+    fn enqueue(&mut self, mut result: ImportResult, apply_vars: bool) {
+        for group in &result.groups {
+            self.group_names.insert(group.id, group.name.clone());
         }
+        self.queue
+            .extend(result.groups.drain(..).map(ImportItem::Group));
+
+        let has_vars = !(self.group_vars.is_empty() && self.host_vars.is_empty());
+        for mut connection in result.connections.drain(..) {
+            if apply_vars && has_vars {
+                let group = connection
+                    .group_id
+                    .and_then(|id| self.group_names.get(&id))
+                    .map(String::as_str);
+                let chain: Vec<&str> = std::iter::once("all").chain(group).collect();
+                AnsibleInventoryImporter::apply_chain_vars(
+                    &mut connection,
+                    &chain,
+                    &self.group_vars,
+                    &self.host_vars,
+                );
+            }
 
-        let content = fs::read_to_string(path).map_err(|e| ImportError::ParseError {
-            source_name: "Ansible inventory".to_string(),
-            reason: format!("Failed to read {}: {}", path.display(), e),
-        })?;
+            let key = (
+                connection.host.to_ascii_lowercase(),
+                connection.port,
+                connection.protocol,
+            );
+            if let Some(&(connection_id, first_group)) = self.seen.get(&key) {
+                self.queue.push_back(ImportItem::MergedDuplicate {
+                    connection_id,
+                    group_id: connection.group_id.filter(|id| Some(*id) != first_group),
+                });
+            } else {
+                self.seen.insert(key, (connection.id, connection.group_id));
+                self.queue
+                    .push_back(ImportItem::Connection(Box::new(connection)));
+            }
+        }
+
+        ImportItem::drain_result(&mut result, &mut self.queue);
+    }
+}
+
+impl Iterator for AnsibleInventoryStream<'_> {
+    type Item = ImportItem;
+
+    fn next(&mut self) -> Option<ImportItem> {
+        loop {
+            if let Some(item) = self.queue.pop_front() {
+                return Some(item);
+            }
+
+            let next = self.lines.as_mut()?.next();
+            let mut result = ImportResult::new();
+            match next {
+                Some(Ok(line)) => {
+                    self.line_num += 1;
+                    self.bytes_read += line.len() as u64 + 1;
+                    self.importer.parse_ini_line(
+                        &mut self.state,
+                        &line,
+                        self.line_num,
+                        &self.source_path,
+                        &mut result,
+                    );
+                }
+                Some(Err(e)) => {
+                    result.add_error(AnsibleInventoryImporter::read_error(
+                        Path::new(&self.source_path),
+                        &e,
+                    ));
+                    self.lines = None;
+                }
+                None => {
+                    self.bytes_read = self.total_bytes;
+                    self.lines = None;
+                }
+            }
+            self.enqueue(result, true);
+        }
+    }
+}
 
-        Ok(self.parse_inventory(&content, &path.display().to_string()))
+impl ImportStream for AnsibleInventoryStream<'_> {
+    fn position(&self) -> (u64, u64) {
+        (self.bytes_read.min(self.total_bytes), self.total_bytes)
+    }
+
+    fn is_incremental(&self) -> bool {
+        self.incremental
     }
 }
 
@@ -533,6 +1063,64 @@ db1.example.com ansible_user=postgres
         assert_eq!(db1.username, Some("postgres".to_string()));
     }
 
+    #[test]
+    fn test_yaml_skipped_host_location() {
+        let importer = AnsibleInventoryImporter::new();
+        let content = r"
+all:
+  children:
+    web:
+      hosts:
+        web1.example.com:
+        web[01:10].example.com:
+    db:
+      hosts:
+        db1.example.com:
+";
+
+        let result = importer.parse_yaml_inventory(content, "hosts.yml");
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(
+            result.skipped[0].location.as_deref(),
+            Some("hosts.yml:7 (all/children/web/hosts/web[01:10].example.com)")
+        );
+    }
+
+    #[test]
+    fn test_host_in_several_groups_is_merged() {
+        let importer = AnsibleInventoryImporter::new();
+        let content = r"
+[webservers]
+shared.example.com
+web1.example.com
+
+[monitoring]
+shared.example.com
+";
+
+        let result = importer.parse_inventory(content, "test");
+        assert_eq!(result.connections.len(), 2);
+        assert_eq!(result.merged_duplicates, 1);
+
+        let web = result
+            .groups
+            .iter()
+            .find(|g| g.name == "webservers")
+            .unwrap();
+        let monitoring = result
+            .groups
+            .iter()
+            .find(|g| g.name == "monitoring")
+            .unwrap();
+        let shared = result
+            .connections
+            .iter()
+            .find(|c| c.host == "shared.example.com")
+            .unwrap();
+        assert_eq!(shared.group_id, Some(web.id));
+        assert_eq!(shared.additional_group_ids, vec![monitoring.id]);
+    }
+
     #[test]
     fn test_parse_yaml_simple() {
         let importer = AnsibleInventoryImporter::new();
@@ -584,4 +1172,155 @@ web[1:10].example.com
         let result = importer.parse_inventory(yaml_content, "test");
         assert_eq!(result.connections.len(), 1);
     }
+
+    #[test]
+    fn test_stream_matches_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let hosts = base.join("hosts");
+        fs::write(
+            &hosts,
+            "[web]\nweb1 ansible_host=10.0.0.1\nshared ansible_host=10.0.0.9\n\n\
+             [db]\ndb1 ansible_port=5022\nshared ansible_host=10.0.0.9\n\n\
+             [db:vars]\nansible_user=dba\n\n[broken\n",
+        )
+        .unwrap();
+        fs::create_dir(base.join("group_vars")).unwrap();
+        fs::write(base.join("group_vars/web.yml"), "ansible_user: deploy\n").unwrap();
+
+        let importer = AnsibleInventoryImporter::new();
+        let expected = importer.import_from_path(&hosts).unwrap();
+
+        assert!(AnsibleInventoryImporter::is_streamable(&hosts).unwrap());
+        let mut stream = importer.stream_from_path(&hosts).unwrap();
+        assert!(stream.is_incremental());
+        let mut connections = Vec::new();
+        let mut groups = Vec::new();
+        let mut merged = Vec::new();
+        for item in stream.by_ref() {
+            match item {
+                ImportItem::Connection(conn) => connections.push(*conn),
+                ImportItem::Group(group) => groups.push(group),
+                ImportItem::MergedDuplicate {
+                    connection_id,
+                    group_id,
+                } => merged.push((connection_id, group_id)),
+                _ => {}
+            }
+        }
+        let (read, total) = stream.position();
+        assert_eq!(read, total);
+
+        let summarize = |conns: &[Connection]| {
+            conns
+                .iter()
+                .map(|c| (c.name.clone(), c.host.clone(), c.port, c.username.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summarize(&connections), summarize(&expected.connections));
+        assert_eq!(groups.len(), expected.groups.len());
+        assert_eq!(merged.len(), expected.merged_duplicates);
+
+        let shared = connections.iter().find(|c| c.name == "shared").unwrap();
+        let db = groups.iter().find(|g| g.name == "db").unwrap();
+        assert_eq!(merged, vec![(shared.id, Some(db.id))]);
+    }
+
+    #[test]
+    fn test_stream_reads_yaml_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let hosts = dir.path().join("hosts.yml");
+        fs::write(
+            &hosts,
+            "all:\n  children:\n    web:\n      hosts:\n        web1:\n        web2:\n",
+        )
+        .unwrap();
+
+        let importer = AnsibleInventoryImporter::new();
+        assert!(!AnsibleInventoryImporter::is_streamable(&hosts).unwrap());
+        assert!(!AnsibleInventoryImporter::is_streamable(dir.path()).unwrap());
+        let stream = importer.stream_from_path(&hosts).unwrap();
+        assert!(!stream.is_incremental());
+        assert_eq!(stream.position().0, stream.position().1);
+        let connections = stream
+            .filter(|item| matches!(item, ImportItem::Connection(_)))
+            .count();
+        assert_eq!(connections, 2);
+    }
+
+    #[test]
+    fn test_import_merges_group_and_host_vars() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        fs::write(
+            base.join("hosts"),
+            "[web]\nweb1 ansible_user=deploy\nweb2\n\n[db]\ndb1 ansible_port=5022\n",
+        )
+        .unwrap();
+
+        fs::create_dir_all(base.join("group_vars/db")).unwrap();
+        fs::write(
+            base.join("group_vars/all.yml"),
+            "ansible_user: admin\nenvironment: prod\n",
+        )
+        .unwrap();
+        fs::write(
+            base.join("group_vars/web.yaml"),
+            "ansible_port: 2222\nansible_ssh_private_key_file: /keys/web\n",
+        )
+        .unwrap();
+        fs::write(
+            base.join("group_vars/db/main.yml"),
+            "ansible_port: 6022\nbackup_window: '02:00'\n",
+        )
+        .unwrap();
+
+        fs::create_dir(base.join("host_vars")).unwrap();
+        fs::write(
+            base.join("host_vars/web2"),
+            "ansible_host: 10.0.0.2\nansible_user: root\nansible_become_password: s3cret\n",
+        )
+        .unwrap();
+        fs::write(base.join("host_vars/broken.yml"), "- not\n- a mapping\n").unwrap();
+
+        let importer = AnsibleInventoryImporter::new();
+        for path in [base.join("hosts"), base.to_path_buf()] {
+            let result = importer.import_from_path(&path).unwrap();
+            assert_eq!(result.connections.len(), 3);
+            assert_eq!(result.skipped.len(), 1);
+
+            let find = |name: &str| result.connections.iter().find(|c| c.name == name).unwrap();
+
+            // Inventory values win over group_vars
+            let web1 = find("web1");
+            assert_eq!(web1.username.as_deref(), Some("deploy"));
+            assert_eq!(web1.port, 2222);
+            if let ProtocolConfig::Ssh(ssh) = &web1.protocol_config {
+                assert_eq!(ssh.key_path, Some(PathBuf::from("/keys/web")));
+            }
+            assert!(web1
+                .custom_properties
+                .iter()
+                .any(|p| p.name == "environment" && p.value == "prod"));
+
+            // host_vars win over everything
+            let web2 = find("web2");
+            assert_eq!(web2.host, "10.0.0.2");
+            assert_eq!(web2.username.as_deref(), Some("root"));
+            let password = web2
+                .custom_properties
+                .iter()
+                .find(|p| p.name == "ansible_become_password")
+                .unwrap();
+            assert!(password.is_protected());
+
+            let db1 = find("db1");
+            assert_eq!(db1.port, 5022);
+            assert_eq!(db1.username.as_deref(), Some("admin"));
+            assert!(db1
+                .custom_properties
+                .iter()
+                .any(|p| p.name == "backup_window"));
+        }
+    }
 }
@@ -10,7 +10,7 @@
 //! **Security Warning:** Imported passwords are stored in plain text in the YAML file.
 //! Consider using a secure credential backend after import.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -19,10 +19,11 @@ use uuid::Uuid;
 
 use crate::error::ImportError;
 use crate::models::{
-    Connection, ConnectionGroup, PasswordSource, ProtocolConfig, RdpConfig, SshAuthMethod,
-    SshConfig, SshKeySource, VncConfig,
+    Connection, ConnectionGroup, PasswordSource, ProtocolConfig, RdpConfig, SshAlgorithms,
+    SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 
+use super::normalize::yaml_location;
 use super::traits::{ImportResult, ImportSource, SkippedEntry};
 
 /// Importer for Asbru-CM configuration files.
@@ -234,6 +235,8 @@ impl AsbruImporter {
         // 1. Top-level (exported files) - entries are at root level
         // 2. Inside "environments" key (installed Asbru config)
         let mut config: HashMap<String, AsbruEntry> = HashMap::new();
+        // Entries read from "environments", for skipped-entry locations
+        let mut in_environments: HashSet<String> = HashSet::new();
 
         // Check if this is an installed Asbru config with "environments" key
         if let Some(environments) = raw_config.get("environments") {
@@ -247,6 +250,7 @@ impl AsbruImporter {
                         // Try to deserialize as AsbruEntry
                         if let Ok(entry) = serde_yaml::from_value(value.clone()) {
                             config.insert(key_str.to_string(), entry);
+                            in_environments.insert(key_str.to_string());
                         }
                     }
                 }
@@ -267,6 +271,7 @@ impl AsbruImporter {
             // Try to deserialize as AsbruEntry
             if let Ok(entry) = serde_yaml::from_value(value.clone()) {
                 config.insert(key.clone(), entry);
+                in_environments.remove(key);
             }
             // Skip entries that don't match the expected structure
         }
@@ -306,8 +311,13 @@ impl AsbruImporter {
         // Second pass: process connections
         for (key, entry) in &config {
             if entry.is_group != Some(1) {
+                let location = if in_environments.contains(key) {
+                    yaml_location(content, source_path, &["environments", key])
+                } else {
+                    yaml_location(content, source_path, &[key])
+                };
                 if let Some(connection) =
-                    self.convert_entry(key, entry, &uuid_map, source_path, &mut result)
+                    self.convert_entry(key, entry, &uuid_map, &location, &mut result)
                 {
                     result.add_connection(connection);
                 }
@@ -324,7 +334,7 @@ impl AsbruImporter {
         key: &str,
         entry: &AsbruEntry,
         uuid_map: &HashMap<String, Uuid>,
-        source_path: &str,
+        location: &str,
         result: &mut ImportResult,
     ) -> Option<Connection> {
         // Get connection name
@@ -340,7 +350,7 @@ impl AsbruImporter {
             result.add_skipped(SkippedEntry::with_location(
                 &name,
                 "No hostname specified in ip, host, name, or title fields",
-                source_path,
+                location,
             ));
             return None;
         };
@@ -391,14 +401,12 @@ impl AsbruImporter {
                             "-X" | "-x" => x11_forwarding = true,
                             "-C" => compression = true,
                             "-A" => agent_forwarding = true,
-                            "-o" => {
-                                // Next part is the option value
-                                if i + 1 < parts.len() {
-                                    i += 1;
-                                    let opt = parts[i].trim_matches('"');
-                                    if let Some((k, v)) = opt.split_once('=') {
-                                        custom_options.insert(k.to_string(), v.to_string());
-                                    }
+                            // Next part is the option value
+                            "-o" if i + 1 < parts.len() => {
+                                i += 1;
+                                let opt = parts[i].trim_matches('"');
+                                if let Some((k, v)) = opt.split_once('=') {
+                                    custom_options.insert(k.to_string(), v.to_string());
                                 }
                             }
                             _ if part.contains('=') => {
@@ -422,6 +430,9 @@ impl AsbruImporter {
                         agent_key_fingerprint: None,
                         identities_only: false,
                         jump_host_id: None,
+                        forwards: Vec::new(),
+                        environment: Vec::new(),
+                        algorithms: SshAlgorithms::default(),
                         proxy_jump: None,
                         use_control_master: false,
                         agent_forwarding,
@@ -458,7 +469,7 @@ impl AsbruImporter {
                 result.add_skipped(SkippedEntry::with_location(
                     &name,
                     format!("Unsupported protocol: {protocol_type}"),
-                    source_path,
+                    location,
                 ));
                 return None;
             }
@@ -809,6 +820,30 @@ invalid-uuid:
         let result = importer.parse_config(yaml, "test");
         assert_eq!(result.connections.len(), 1);
         assert_eq!(result.skipped.len(), 1);
+        assert_eq!(
+            result.skipped[0].location.as_deref(),
+            Some("test:7 (invalid-uuid)")
+        );
+    }
+
+    #[test]
+    fn test_skipped_environment_entry_location() {
+        let importer = AsbruImporter::new();
+        let yaml = r#"
+environments:
+  telnet-uuid:
+    _is_group: 0
+    name: "Old Switch"
+    ip: "10.0.0.1"
+    method: "Telnet"
+"#;
+
+        let result = importer.parse_config(yaml, "asbru.yml");
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(
+            result.skipped[0].location.as_deref(),
+            Some("asbru.yml:3 (environments/telnet-uuid)")
+        );
     }
 
     #[test]
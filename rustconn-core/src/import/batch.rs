@@ -2,15 +2,24 @@
 //!
 //! This module provides `BatchImporter` for processing large numbers of connections
 //! efficiently using configurable batch sizes and progress reporting.
+//!
+//! Files above [`STREAMING_IMPORT_THRESHOLD`] can be imported through an
+//! [`ImportStream`], which parses them incrementally so that memory use does
+//! not grow with the file size. Inputs a stream cannot parse incrementally
+//! are read whole, which [`ImportStream::is_incremental`] reports.
 
+use std::collections::VecDeque;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use uuid::Uuid;
+
 use crate::error::ImportError;
-use crate::models::Connection;
+use crate::models::{Connection, ConnectionGroup};
 use crate::progress::ProgressReporter;
 
-use super::traits::ImportResult;
+use super::traits::{ImportResult, SkippedEntry};
 
 /// Default batch size for import operations.
 pub const DEFAULT_IMPORT_BATCH_SIZE: usize = 50;
@@ -18,6 +27,61 @@ pub const DEFAULT_IMPORT_BATCH_SIZE: usize = 50;
 /// Threshold for using batch processing (connections count).
 pub const BATCH_IMPORT_THRESHOLD: usize = 10;
 
+/// File size above which imports should be streamed (bytes).
+pub const STREAMING_IMPORT_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+/// An entry produced by a streaming importer.
+#[derive(Debug)]
+pub enum ImportItem {
+    /// A parsed connection
+    Connection(Box<Connection>),
+    /// A group that later connections may belong to
+    Group(ConnectionGroup),
+    /// A connection that duplicates one already yielded
+    ///
+    /// The duplicate is not yielded; its group is recorded as a further
+    /// group membership of the earlier connection.
+    MergedDuplicate {
+        /// ID of the connection yielded earlier
+        connection_id: Uuid,
+        /// Group of the duplicate
+        group_id: Option<Uuid>,
+    },
+    /// An entry that was skipped
+    Skipped(SkippedEntry),
+    /// A non-fatal error
+    Error(ImportError),
+}
+
+impl ImportItem {
+    /// Moves the contents of `result` to the back of `queue`
+    ///
+    /// Groups come first so that they precede the connections that use them.
+    pub fn drain_result(result: &mut ImportResult, queue: &mut VecDeque<Self>) {
+        queue.extend(result.groups.drain(..).map(Self::Group));
+        queue.extend(
+            result
+                .connections
+                .drain(..)
+                .map(|conn| Self::Connection(Box::new(conn))),
+        );
+        queue.extend(result.skipped.drain(..).map(Self::Skipped));
+        queue.extend(result.errors.drain(..).map(Self::Error));
+    }
+}
+
+/// An import source that parses its input incrementally.
+pub trait ImportStream: Iterator<Item = ImportItem> {
+    /// Returns the bytes consumed so far and the total size of the input.
+    fn position(&self) -> (u64, u64);
+
+    /// Returns false if the input was read whole into memory because its
+    /// format cannot be parsed incrementally.
+    fn is_incremental(&self) -> bool {
+        true
+    }
+}
+
 /// Result of a batch import operation.
 #[derive(Debug)]
 pub struct BatchImportResult {
@@ -249,11 +313,138 @@ impl BatchImporter {
         BatchImportResult::new(result, false, batches_processed)
     }
 
+    /// Imports from a stream, handing connections to `sink` one batch at a time.
+    ///
+    /// Connections are not kept in the returned result, so memory use is
+    /// bounded by the batch size; groups, skipped entries, errors and group
+    /// memberships of merged duplicates are collected as usual. Progress is
+    /// reported in bytes of input consumed. On cancellation the partial batch
+    /// is dropped and no further batches are delivered.
+    pub fn process_stream<S, F>(
+        &self,
+        mut stream: S,
+        progress: Option<&dyn ProgressReporter>,
+        mut sink: F,
+    ) -> BatchImportResult
+    where
+        S: ImportStream,
+        F: FnMut(Vec<Connection>),
+    {
+        let mut result = ImportResult::new();
+        let mut batch = Vec::with_capacity(self.batch_size);
+        let mut batches_processed = 0;
+        let mut imported = 0usize;
+
+        let check_cancelled =
+            || self.is_cancelled() || progress.is_some_and(ProgressReporter::is_cancelled);
+
+        if check_cancelled() {
+            return BatchImportResult::new(result, true, 0);
+        }
+
+        while let Some(item) = stream.next() {
+            match item {
+                ImportItem::Connection(conn) => batch.push(*conn),
+                ImportItem::Group(group) => result.add_group(group),
+                ImportItem::MergedDuplicate {
+                    connection_id,
+                    group_id,
+                } => {
+                    result.merged_duplicates += 1;
+                    let Some(group_id) = group_id else {
+                        continue;
+                    };
+                    // Still in the pending batch, otherwise already handed out
+                    if let Some(conn) = batch.iter_mut().find(|c| c.id == connection_id) {
+                        conn.add_to_group(group_id);
+                    } else {
+                        let groups = result.group_memberships.entry(connection_id).or_default();
+                        if !groups.contains(&group_id) {
+                            groups.push(group_id);
+                        }
+                    }
+                }
+                ImportItem::Skipped(entry) => result.add_skipped(entry),
+                ImportItem::Error(error) => result.add_error(error),
+            }
+
+            if batch.len() < self.batch_size {
+                continue;
+            }
+            if check_cancelled() {
+                return BatchImportResult::new(result, true, batches_processed);
+            }
+            imported += batch.len();
+            sink(std::mem::replace(
+                &mut batch,
+                Vec::with_capacity(self.batch_size),
+            ));
+            batches_processed += 1;
+
+            if let Some(reporter) = progress {
+                let (read, total) = stream.position();
+                reporter.report(
+                    usize::try_from(read).unwrap_or(usize::MAX),
+                    usize::try_from(total).unwrap_or(usize::MAX),
+                    &format!("Imported {imported} connections"),
+                );
+            }
+        }
+
+        if check_cancelled() {
+            return BatchImportResult::new(result, true, batches_processed);
+        }
+        if !batch.is_empty() {
+            imported += batch.len();
+            sink(batch);
+            batches_processed += 1;
+        }
+
+        if let Some(reporter) = progress {
+            let (_, total) = stream.position();
+            let total = usize::try_from(total).unwrap_or(usize::MAX);
+            reporter.report(
+                total,
+                total,
+                &format!("Import complete: {imported} connections"),
+            );
+        }
+
+        BatchImportResult::new(result, false, batches_processed)
+    }
+
+    /// Imports a whole stream into a single result.
+    ///
+    /// Runs [`Self::process_stream`] and collects the delivered batches into
+    /// `result.connections`, then applies the group memberships recorded for
+    /// connections of earlier batches. The input is still parsed
+    /// incrementally, but every imported connection is kept in memory.
+    pub fn collect_stream<S: ImportStream>(
+        &self,
+        stream: S,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> BatchImportResult {
+        let mut connections = Vec::new();
+        let mut imported = self.process_stream(stream, progress, |batch| {
+            connections.extend(batch);
+        });
+        imported.result.apply_group_memberships(&mut connections);
+        imported.result.group_memberships.clear();
+        imported.result.connections = connections;
+        imported
+    }
+
     /// Returns true if batch processing should be used for the given count.
     #[must_use]
     pub const fn should_use_batch(count: usize) -> bool {
         count > BATCH_IMPORT_THRESHOLD
     }
+
+    /// Returns true if the file at `path` is large enough to be streamed.
+    #[must_use]
+    pub fn should_stream(path: &Path) -> bool {
+        std::fs::metadata(path).is_ok_and(|meta| meta.len() > STREAMING_IMPORT_THRESHOLD)
+    }
 }
 
 impl Default for BatchImporter {
@@ -419,4 +610,136 @@ mod tests {
         assert!(result.was_cancelled);
         assert_eq!(result.batches_processed, 0);
     }
+
+    /// Stream over prepared items that reports one byte per item consumed
+    struct VecStream {
+        items: std::vec::IntoIter<ImportItem>,
+        total: u64,
+    }
+
+    impl VecStream {
+        fn new(items: Vec<ImportItem>) -> Self {
+            let total = items.len() as u64;
+            Self {
+                items: items.into_iter(),
+                total,
+            }
+        }
+    }
+
+    impl Iterator for VecStream {
+        type Item = ImportItem;
+
+        fn next(&mut self) -> Option<ImportItem> {
+            self.items.next()
+        }
+    }
+
+    impl ImportStream for VecStream {
+        fn position(&self) -> (u64, u64) {
+            (self.total - self.items.len() as u64, self.total)
+        }
+    }
+
+    #[test]
+    fn test_process_stream_delivers_batches() {
+        let importer = BatchImporter::new(3);
+        let group = ConnectionGroup::new("web".to_string());
+        let first = create_test_connection("conn0");
+        let first_id = first.id;
+
+        let mut items = vec![
+            ImportItem::Group(group.clone()),
+            ImportItem::Connection(Box::new(first)),
+        ];
+        items.extend((1..7).map(|i| {
+            ImportItem::Connection(Box::new(create_test_connection(&format!("conn{i}"))))
+        }));
+        let last_id = match items.last() {
+            Some(ImportItem::Connection(conn)) => conn.id,
+            _ => unreachable!(),
+        };
+        items.push(ImportItem::MergedDuplicate {
+            connection_id: first_id,
+            group_id: Some(group.id),
+        });
+        items.push(ImportItem::MergedDuplicate {
+            connection_id: last_id,
+            group_id: Some(group.id),
+        });
+        items.push(ImportItem::MergedDuplicate {
+            connection_id: first_id,
+            group_id: None,
+        });
+
+        let reports = std::sync::Mutex::new(Vec::new());
+        let reporter = crate::progress::CallbackProgressReporter::new(|current, total, _: &str| {
+            reports.lock().unwrap().push((current, total));
+        });
+
+        let mut batches = Vec::new();
+        let mut last_batch = Vec::new();
+        let result = importer.process_stream(VecStream::new(items), Some(&reporter), |batch| {
+            batches.push(batch.len());
+            last_batch = batch;
+        });
+
+        assert!(result.is_complete());
+        assert_eq!(batches, vec![3, 3, 1]);
+        assert_eq!(result.batches_processed, 3);
+        assert!(result.result.connections.is_empty());
+        assert_eq!(result.result.groups.len(), 1);
+        assert_eq!(result.result.merged_duplicates, 3);
+        // Applied directly while still pending, recorded once handed out
+        assert!(last_batch[0].is_in_group(group.id));
+        assert!(!result.result.group_memberships.contains_key(&last_id));
+        assert_eq!(result.result.group_memberships[&first_id], vec![group.id]);
+
+        let reports = reports.into_inner().unwrap();
+        assert_eq!(reports.first(), Some(&(4, 11)));
+        assert_eq!(reports.last(), Some(&(11, 11)));
+    }
+
+    #[test]
+    fn test_collect_stream_applies_memberships() {
+        let importer = BatchImporter::new(2);
+        let group = ConnectionGroup::new("db".to_string());
+        let first = create_test_connection("conn0");
+        let first_id = first.id;
+
+        let mut items = vec![ImportItem::Connection(Box::new(first))];
+        items.extend((1..4).map(|i| {
+            ImportItem::Connection(Box::new(create_test_connection(&format!("conn{i}"))))
+        }));
+        items.push(ImportItem::MergedDuplicate {
+            connection_id: first_id,
+            group_id: Some(group.id),
+        });
+
+        let result = importer.collect_stream(VecStream::new(items), None);
+
+        assert!(result.is_complete());
+        assert_eq!(result.batches_processed, 2);
+        assert_eq!(result.result.connections.len(), 4);
+        assert!(result.result.connections[0].is_in_group(group.id));
+        assert!(result.result.group_memberships.is_empty());
+    }
+
+    #[test]
+    fn test_process_stream_cancelled() {
+        let importer = BatchImporter::new(2);
+        let items = (0..6)
+            .map(|i| ImportItem::Connection(Box::new(create_test_connection(&format!("conn{i}")))))
+            .collect();
+
+        let mut delivered = 0;
+        let result = importer.process_stream(VecStream::new(items), None, |batch| {
+            delivered += batch.len();
+            importer.cancel();
+        });
+
+        assert!(result.was_cancelled);
+        assert_eq!(delivered, 2);
+        assert_eq!(result.batches_processed, 1);
+    }
 }
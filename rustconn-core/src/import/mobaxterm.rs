@@ -13,8 +13,8 @@ use std::path::{Path, PathBuf};
 
 use crate::error::ImportError;
 use crate::models::{
-    Connection, ConnectionGroup, ProtocolConfig, RdpConfig, Resolution, SshAuthMethod, SshConfig,
-    SshKeySource, VncConfig,
+    Connection, ConnectionGroup, ProtocolConfig, RdpConfig, Resolution, SshAlgorithms,
+    SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 
 use super::traits::{ImportResult, ImportSource, SkippedEntry};
@@ -308,6 +308,9 @@ impl MobaXtermImporter {
             agent_key_fingerprint: None,
             identities_only: false,
             jump_host_id: None,
+            forwards: Vec::new(),
+            environment: Vec::new(),
+            algorithms: SshAlgorithms::default(),
             proxy_jump: None,
             use_control_master: false,
             agent_forwarding,
@@ -7,6 +7,7 @@
 //! - Ansible inventory files
 //! - Royal TS rJSON files
 //! - MobaXterm session files
+//! - PuTTY saved sessions (.reg export or sessions directory)
 //!
 //! For large imports (more than 10 connections), use `BatchImporter` for
 //! efficient batch processing with progress reporting and cancellation support.
@@ -43,25 +44,27 @@ pub mod batch;
 mod mobaxterm;
 mod normalize;
 mod preview;
+mod putty;
 mod rdm;
 mod remmina;
 mod royalts;
 mod ssh_config;
 mod traits;
 
-pub use ansible::AnsibleInventoryImporter;
+pub use ansible::{AnsibleInventoryImporter, AnsibleInventoryStream};
 pub use asbru::AsbruImporter;
 pub use batch::{
-    BatchCancelHandle, BatchImportResult, BatchImporter, BATCH_IMPORT_THRESHOLD,
-    DEFAULT_IMPORT_BATCH_SIZE,
+    BatchCancelHandle, BatchImportResult, BatchImporter, ImportItem, ImportStream,
+    BATCH_IMPORT_THRESHOLD, DEFAULT_IMPORT_BATCH_SIZE, STREAMING_IMPORT_THRESHOLD,
 };
 pub use mobaxterm::MobaXtermImporter;
 pub use normalize::{
     is_valid_hostname, looks_like_hostname, parse_host_port, ImportNormalizer, NormalizeOptions,
 };
 pub use preview::{DuplicateAction, ImportPreview, MergeStrategy, PreviewConnection, PreviewGroup};
+pub use putty::PuttyImporter;
 pub use rdm::RdmImporter;
 pub use remmina::RemminaImporter;
 pub use royalts::RoyalTsImporter;
-pub use ssh_config::SshConfigImporter;
+pub use ssh_config::{SshConfigImporter, SshConfigStream};
 pub use traits::{ImportResult, ImportSource, SkippedEntry};
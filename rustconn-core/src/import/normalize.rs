@@ -275,6 +275,36 @@ pub fn looks_like_hostname(s: &str) -> bool {
     false
 }
 
+/// Formats the location of a nested YAML mapping key as `file:line (a/b/c)`
+///
+/// Each segment of `path` is searched for as a `key:` line after the line of
+/// the previous one, which locates entries in block-style YAML without a
+/// span-aware parser. The line is omitted if a segment cannot be found.
+#[must_use]
+pub fn yaml_location(content: &str, source_path: &str, path: &[&str]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut line = None;
+    for segment in path {
+        let start = line.map_or(0, |index| index + 1);
+        line = lines.iter().skip(start).position(|text| {
+            let text = text.trim_start().trim_start_matches("- ");
+            let text = text.strip_prefix(['\'', '"']).unwrap_or(text);
+            text.strip_prefix(segment)
+                .is_some_and(|rest| rest.trim_start_matches(['\'', '"']).starts_with(':'))
+        });
+        match line {
+            Some(offset) => line = Some(start + offset),
+            None => break,
+        }
+    }
+
+    let node_path = path.join("/");
+    match line {
+        Some(index) => format!("{source_path}:{} ({node_path})", index + 1),
+        None => format!("{source_path} ({node_path})"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
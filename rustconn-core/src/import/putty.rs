@@ -0,0 +1,512 @@
+//! PuTTY session importer.
+//!
+//! Reads saved PuTTY sessions from either:
+//! - a registry export (`.reg`) of `HKCU\Software\SimonTatham\PuTTY\Sessions`,
+//!   as produced on Windows by `regedit /e`
+//! - a sessions directory (`~/.putty/sessions`), where Unix PuTTY stores one
+//!   `Key=Value` file per session
+//!
+//! Session names are `%`-escaped by PuTTY. After decoding, `/` and `\`
+//! separate folders (as written by PuTTY session managers) from the
+//! connection name. Only SSH sessions are imported; other session types
+//! such as serial or telnet are reported as skipped.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::error::ImportError;
+use crate::models::{
+    Connection, ConnectionGroup, ProtocolConfig, SshAuthMethod, SshConfig, SshKeySource,
+};
+
+use super::traits::{ImportResult, ImportSource, SkippedEntry};
+
+/// Registry key under which PuTTY stores its sessions
+const SESSIONS_KEY: &str = "\\Software\\SimonTatham\\PuTTY\\Sessions\\";
+
+/// Name of the session holding PuTTY's defaults rather than a host
+const DEFAULT_SETTINGS: &str = "Default Settings";
+
+/// Importer for PuTTY saved sessions.
+pub struct PuttyImporter {
+    /// Custom path to import from
+    custom_path: Option<PathBuf>,
+}
+
+impl PuttyImporter {
+    /// Creates a new PuTTY importer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { custom_path: None }
+    }
+
+    /// Creates a new importer with a custom `.reg` file or sessions directory.
+    #[must_use]
+    pub const fn with_path(path: PathBuf) -> Self {
+        Self {
+            custom_path: Some(path),
+        }
+    }
+
+    /// Decodes PuTTY's `%XX` escapes in a session name.
+    fn unescape_name(name: &str) -> String {
+        let bytes = name.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                if let Some(byte) = name
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+        String::from_utf8_lossy(&decoded).into_owned()
+    }
+
+    /// Parses a registry export of PuTTY sessions.
+    #[must_use]
+    pub fn parse_reg(&self, content: &str, source_path: &str) -> ImportResult {
+        let mut sessions: Vec<(String, HashMap<String, String>)> = Vec::new();
+        let mut in_session = false;
+        let mut pending = String::new();
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+
+            // Hex values wrap onto continuation lines ending with a backslash
+            if let Some(part) = line.strip_suffix('\\') {
+                pending.push_str(part);
+                continue;
+            }
+            let line = if pending.is_empty() {
+                line.to_string()
+            } else {
+                let joined = format!("{pending}{line}");
+                pending.clear();
+                joined
+            };
+
+            if line.starts_with('[') && line.ends_with(']') {
+                let key = &line[1..line.len() - 1];
+                in_session = false;
+                if key.starts_with('-') {
+                    continue; // Deletion of a key
+                }
+                if let Some(pos) = key.find(SESSIONS_KEY) {
+                    let name = &key[pos + SESSIONS_KEY.len()..];
+                    if !name.is_empty() && !name.contains('\\') {
+                        sessions.push((name.to_string(), HashMap::new()));
+                        in_session = true;
+                    }
+                }
+                continue;
+            }
+
+            if !in_session {
+                continue;
+            }
+            let Some((key, value)) = Self::parse_reg_value(&line) else {
+                continue;
+            };
+            if let Some((_, values)) = sessions.last_mut() {
+                values.insert(key, value);
+            }
+        }
+
+        Self::sessions_to_result(sessions, source_path)
+    }
+
+    /// Parses a `"Name"=value` registry line into a key and a string value.
+    ///
+    /// `DWORD` values are converted to decimal; binary values are ignored.
+    fn parse_reg_value(line: &str) -> Option<(String, String)> {
+        let rest = line.strip_prefix('"')?;
+        let end = rest.find("\"=")?;
+        let key = rest[..end].to_string();
+        let value = &rest[end + 2..];
+
+        if let Some(dword) = value.strip_prefix("dword:") {
+            let number = u32::from_str_radix(dword.trim(), 16).ok()?;
+            return Some((key, number.to_string()));
+        }
+
+        let quoted = value.strip_prefix('"')?.strip_suffix('"')?;
+        let mut unescaped = String::with_capacity(quoted.len());
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    unescaped.push(next);
+                }
+            } else {
+                unescaped.push(c);
+            }
+        }
+        Some((key, unescaped))
+    }
+
+    /// Parses the contents of a Unix PuTTY session file.
+    fn parse_session_file(content: &str) -> HashMap<String, String> {
+        content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim_end().to_string()))
+            .collect()
+    }
+
+    /// Imports every session file in a Unix PuTTY sessions directory.
+    fn import_directory(&self, dir: &Path) -> Result<ImportResult, ImportError> {
+        let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        entries.sort();
+
+        let mut sessions = Vec::with_capacity(entries.len());
+        for path in entries {
+            let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            let bytes = fs::read(&path)?;
+            let content = String::from_utf8_lossy(&bytes);
+            sessions.push((name, Self::parse_session_file(&content)));
+        }
+
+        Ok(Self::sessions_to_result(
+            sessions,
+            &dir.display().to_string(),
+        ))
+    }
+
+    /// Converts parsed sessions into connections and groups.
+    fn sessions_to_result(
+        sessions: Vec<(String, HashMap<String, String>)>,
+        source_path: &str,
+    ) -> ImportResult {
+        let mut result = ImportResult::new();
+        let mut group_map: HashMap<String, Uuid> = HashMap::new();
+
+        for (escaped_name, values) in sessions {
+            let full_name = Self::unescape_name(&escaped_name);
+            if full_name == DEFAULT_SETTINGS {
+                result.add_skipped(SkippedEntry::with_location(
+                    full_name,
+                    "PuTTY default settings are not a host",
+                    source_path,
+                ));
+                continue;
+            }
+
+            let (group_path, name) = match full_name.rfind(['/', '\\']) {
+                Some(pos) => (
+                    Some(full_name[..pos].replace('\\', "/")),
+                    full_name[pos + 1..].to_string(),
+                ),
+                None => (None, full_name.clone()),
+            };
+
+            match Self::build_connection(&name, &values) {
+                Ok(mut connection) => {
+                    connection.group_id = group_path
+                        .filter(|path| path.split('/').all(|part| !part.is_empty()))
+                        .map(|path| Self::get_or_create_group(&path, &mut group_map, &mut result));
+                    result.add_connection(connection);
+                }
+                Err(reason) => {
+                    result.add_skipped(SkippedEntry::with_location(full_name, reason, source_path));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Builds a connection from a session's settings.
+    fn build_connection(
+        name: &str,
+        values: &HashMap<String, String>,
+    ) -> Result<Connection, String> {
+        let get = |key: &str| {
+            values
+                .get(key)
+                .map(String::as_str)
+                .filter(|v| !v.is_empty())
+        };
+
+        let protocol = get("Protocol").unwrap_or("ssh");
+        if !protocol.eq_ignore_ascii_case("ssh") {
+            return Err(format!("Unsupported PuTTY session type: {protocol}"));
+        }
+
+        // PuTTY accepts "user@host" in the host name field
+        let (host_user, host) = match get("HostName") {
+            Some(host) => match host.rsplit_once('@') {
+                Some((user, host)) => (Some(user.to_string()), host.trim().to_string()),
+                None => (None, host.trim().to_string()),
+            },
+            None => (None, String::new()),
+        };
+        if host.is_empty() {
+            return Err("No host specified".to_string());
+        }
+
+        let port = get("PortNumber")
+            .and_then(|p| p.parse::<u16>().ok())
+            .filter(|p| *p != 0)
+            .unwrap_or(22);
+
+        let key_path = get("PublicKeyFile").map(PathBuf::from);
+        let flag = |key: &str| get(key).is_some_and(|v| v == "1");
+
+        let ssh_config = SshConfig {
+            auth_method: if key_path.is_some() {
+                SshAuthMethod::PublicKey
+            } else {
+                SshAuthMethod::Password
+            },
+            key_path,
+            key_source: SshKeySource::Default,
+            agent_forwarding: flag("AgentFwd"),
+            x11_forwarding: flag("X11Forward"),
+            compression: flag("Compression"),
+            ..SshConfig::default()
+        };
+
+        let mut connection = Connection::new(
+            name.to_string(),
+            host,
+            port,
+            ProtocolConfig::Ssh(ssh_config),
+        );
+        connection.username = get("UserName").map(str::to_string).or(host_user);
+        Ok(connection)
+    }
+
+    /// Gets or creates the groups for a `/`-separated folder path.
+    fn get_or_create_group(
+        group_path: &str,
+        group_map: &mut HashMap<String, Uuid>,
+        result: &mut ImportResult,
+    ) -> Uuid {
+        if let Some(&id) = group_map.get(group_path) {
+            return id;
+        }
+
+        let mut parent_id: Option<Uuid> = None;
+        let mut current_path = String::new();
+        for part in group_path.split('/') {
+            if !current_path.is_empty() {
+                current_path.push('/');
+            }
+            current_path.push_str(part);
+
+            let id = if let Some(&id) = group_map.get(&current_path) {
+                id
+            } else {
+                let group = match parent_id {
+                    Some(pid) => ConnectionGroup::with_parent(part.to_string(), pid),
+                    None => ConnectionGroup::new(part.to_string()),
+                };
+                let id = group.id;
+                group_map.insert(current_path.clone(), id);
+                result.add_group(group);
+                id
+            };
+            parent_id = Some(id);
+        }
+
+        parent_id.unwrap_or_else(Uuid::nil)
+    }
+
+    /// Decodes a registry export, which `regedit` writes as UTF-16LE.
+    ///
+    /// UTF-8 input, with or without a byte order mark, is accepted as well.
+    #[must_use]
+    pub fn decode_reg(bytes: &[u8]) -> String {
+        if let Some(utf16) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            return String::from_utf16_lossy(&units);
+        }
+        let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+impl Default for PuttyImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportSource for PuttyImporter {
+    fn source_id(&self) -> &'static str {
+        "putty"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "PuTTY"
+    }
+
+    fn is_available(&self) -> bool {
+        self.default_paths().iter().any(|p| p.exists())
+    }
+
+    fn default_paths(&self) -> Vec<PathBuf> {
+        if let Some(ref path) = self.custom_path {
+            return vec![path.clone()];
+        }
+        dirs::home_dir()
+            .map(|home| vec![home.join(".putty").join("sessions")])
+            .unwrap_or_default()
+    }
+
+    fn import(&self) -> Result<ImportResult, ImportError> {
+        let Some(path) = self.default_paths().into_iter().find(|p| p.exists()) else {
+            return Err(ImportError::FileNotFound(PathBuf::from(
+                "No PuTTY sessions found",
+            )));
+        };
+
+        self.import_from_path(&path)
+    }
+
+    fn import_from_path(&self, path: &Path) -> Result<ImportResult, ImportError> {
+        if !path.exists() {
+            return Err(ImportError::FileNotFound(path.to_path_buf()));
+        }
+
+        if path.is_dir() {
+            return self.import_directory(path);
+        }
+
+        let bytes = fs::read(path).map_err(|e| ImportError::ParseError {
+            source_name: "PuTTY".to_string(),
+            reason: format!("Failed to read {}: {}", path.display(), e),
+        })?;
+
+        Ok(self.parse_reg(&Self::decode_reg(&bytes), &path.display().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REG_EXPORT: &str = r#"Windows Registry Editor Version 5.00
+
+[HKEY_CURRENT_USER\Software\SimonTatham\PuTTY\Sessions]
+
+[HKEY_CURRENT_USER\Software\SimonTatham\PuTTY\Sessions\Default%20Settings]
+"Protocol"="ssh"
+
+[HKEY_CURRENT_USER\Software\SimonTatham\PuTTY\Sessions\Prod%2FWeb%20Server]
+"HostName"="web.example.com"
+"PortNumber"=dword:00000897
+"Protocol"="ssh"
+"UserName"="deploy"
+"PublicKeyFile"="C:\\Users\\me\\.ssh\\deploy.ppk"
+"AgentFwd"=dword:00000001
+"Colour0"=hex:bb,bb,\
+  bb
+
+[HKEY_CURRENT_USER\Software\SimonTatham\PuTTY\Sessions\Console]
+"HostName"=""
+"Protocol"="serial"
+"SerialLine"="COM1"
+"#;
+
+    #[test]
+    fn test_unescape_name() {
+        assert_eq!(PuttyImporter::unescape_name("My%20Server"), "My Server");
+        assert_eq!(PuttyImporter::unescape_name("a%2Fb"), "a/b");
+        assert_eq!(PuttyImporter::unescape_name("100%"), "100%");
+        assert_eq!(PuttyImporter::unescape_name("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_parse_reg_export() {
+        let result = PuttyImporter::new().parse_reg(REG_EXPORT, "putty.reg");
+
+        assert_eq!(result.connections.len(), 1);
+        let conn = &result.connections[0];
+        assert_eq!(conn.name, "Web Server");
+        assert_eq!(conn.host, "web.example.com");
+        assert_eq!(conn.port, 2199);
+        assert_eq!(conn.username.as_deref(), Some("deploy"));
+        let ProtocolConfig::Ssh(ref ssh) = conn.protocol_config else {
+            panic!("expected SSH config");
+        };
+        assert_eq!(
+            ssh.key_path.as_deref(),
+            Some(Path::new("C:\\Users\\me\\.ssh\\deploy.ppk"))
+        );
+        assert_eq!(ssh.auth_method, SshAuthMethod::PublicKey);
+        assert!(ssh.agent_forwarding);
+
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].name, "Prod");
+        assert_eq!(conn.group_id, Some(result.groups[0].id));
+
+        assert_eq!(result.skipped.len(), 2);
+        assert!(result
+            .skipped
+            .iter()
+            .any(|s| s.identifier == "Console" && s.reason.contains("serial")));
+    }
+
+    #[test]
+    fn test_import_utf16_reg_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("putty.reg");
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(REG_EXPORT.encode_utf16().flat_map(u16::to_le_bytes));
+        fs::write(&path, bytes).unwrap();
+
+        let result = PuttyImporter::new().import_from_path(&path).unwrap();
+        assert_eq!(result.connections.len(), 1);
+        assert_eq!(result.connections[0].host, "web.example.com");
+    }
+
+    #[test]
+    fn test_import_sessions_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("db%20primary"),
+            "HostName=admin@db.internal\nPortNumber=0\nProtocol=ssh\nUserName=\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("router"),
+            "HostName=10.0.0.1\nPortNumber=23\nProtocol=telnet\n",
+        )
+        .unwrap();
+
+        let result = PuttyImporter::with_path(dir.path().to_path_buf())
+            .import()
+            .unwrap();
+
+        assert_eq!(result.connections.len(), 1);
+        let conn = &result.connections[0];
+        assert_eq!(conn.name, "db primary");
+        assert_eq!(conn.host, "db.internal");
+        assert_eq!(conn.port, 22);
+        assert_eq!(conn.username.as_deref(), Some("admin"));
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].identifier, "router");
+    }
+}
@@ -334,13 +334,16 @@ impl RdmImporter {
             port,
             username: conn.username.clone(),
             group_id,
+            additional_group_ids: Vec::new(),
             tags: Vec::new(),
             created_at: now,
             updated_at: now,
             protocol_config,
             automation: AutomationConfig::default(),
             sort_order: 0,
+            launch_order: 0,
             last_connected: None,
+            connect_count: 0,
             password_source,
             domain: conn.domain.clone(),
             custom_properties: Vec::new(),
@@ -354,6 +357,9 @@ impl RdmImporter {
             remember_window_position: false,
             window_geometry: None,
             skip_port_check: false,
+            color: None,
+            icon: None,
+            archived: false,
         })
     }
 }
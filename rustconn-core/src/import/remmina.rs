@@ -14,12 +14,30 @@ use uuid::Uuid;
 use crate::error::ImportError;
 use crate::models::{
     Connection, ConnectionGroup, PasswordSource, ProtocolConfig, RdpConfig, Resolution,
-    SpiceConfig, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
+    SpiceConfig, SshAlgorithms, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 
 use super::normalize::parse_host_port;
 use super::traits::{ImportResult, ImportSource, SkippedEntry};
 
+/// A section of a `.remmina` file with the lines it was read from
+#[derive(Default)]
+struct IniSection {
+    /// Line number of the `[section]` header
+    line: usize,
+    values: HashMap<String, String>,
+    /// Line number of each key
+    key_lines: HashMap<String, usize>,
+}
+
+impl IniSection {
+    /// Returns the `file:line` of `key`, or of the section header if absent
+    fn location(&self, source_path: &str, key: &str) -> String {
+        let line = self.key_lines.get(key).copied().unwrap_or(self.line);
+        format!("{source_path}:{line}")
+    }
+}
+
 /// Importer for Remmina connection files.
 ///
 /// Remmina stores each connection in a separate .remmina file
@@ -157,11 +175,11 @@ impl RemminaImporter {
     }
 
     /// Parses INI-style content into sections
-    fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
-        let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    fn parse_ini(content: &str) -> HashMap<String, IniSection> {
+        let mut sections: HashMap<String, IniSection> = HashMap::new();
         let mut current_section: Option<String> = None;
 
-        for line in content.lines() {
+        for (index, line) in content.lines().enumerate() {
             let line = line.trim();
 
             // Skip empty lines and comments
@@ -173,7 +191,7 @@ impl RemminaImporter {
             if line.starts_with('[') && line.ends_with(']') {
                 let section_name = line[1..line.len() - 1].to_lowercase();
                 current_section = Some(section_name.clone());
-                sections.entry(section_name).or_default();
+                sections.entry(section_name).or_default().line = index + 1;
                 continue;
             }
 
@@ -183,10 +201,9 @@ impl RemminaImporter {
                 let value = line[eq_pos + 1..].trim().to_string();
 
                 if let Some(ref section) = current_section {
-                    sections
-                        .entry(section.clone())
-                        .or_default()
-                        .insert(key, value);
+                    let section = sections.entry(section.clone()).or_default();
+                    section.key_lines.insert(key.clone(), index + 1);
+                    section.values.insert(key, value);
                 }
             }
         }
@@ -199,10 +216,12 @@ impl RemminaImporter {
     #[allow(clippy::too_many_lines)]
     fn convert_to_connection(
         &self,
-        config: &HashMap<String, String>,
+        section: &IniSection,
         source_path: &str,
         result: &mut ImportResult,
     ) -> Option<(Connection, Option<String>)> {
+        let config = &section.values;
+
         // Get protocol
         let protocol = config.get("protocol").map(|s| s.to_uppercase());
 
@@ -217,7 +236,7 @@ impl RemminaImporter {
                 result.add_skipped(SkippedEntry::with_location(
                     source_path,
                     "No server specified",
-                    source_path,
+                    section.location(source_path, "server"),
                 ));
                 return None;
             }
@@ -267,6 +286,9 @@ impl RemminaImporter {
                         agent_key_fingerprint: None,
                         identities_only: false,
                         jump_host_id: None,
+                        forwards: Vec::new(),
+                        environment: Vec::new(),
+                        algorithms: SshAlgorithms::default(),
                         proxy_jump: None,
                         use_control_master: false,
                         agent_forwarding,
@@ -308,7 +330,7 @@ impl RemminaImporter {
                 result.add_skipped(SkippedEntry::with_location(
                     &name,
                     format!("Unsupported protocol: {p}"),
-                    source_path,
+                    section.location(source_path, "protocol"),
                 ));
                 return None;
             }
@@ -316,7 +338,7 @@ impl RemminaImporter {
                 result.add_skipped(SkippedEntry::with_location(
                     &name,
                     "No protocol specified",
-                    source_path,
+                    section.location(source_path, "protocol"),
                 ));
                 return None;
             }
@@ -335,6 +357,7 @@ impl RemminaImporter {
                 connection.username = Some(username.clone());
             }
         }
+        connection.domain = config.get("domain").filter(|d| !d.is_empty()).cloned();
 
         // Try to import password from GNOME Keyring if enabled
         if self.import_passwords {
@@ -540,6 +563,7 @@ name=Windows Server
 protocol=RDP
 server=192.168.1.50
 username=Administrator
+domain=CORP
 resolution=1920x1080
 colordepth=32
 ";
@@ -549,6 +573,7 @@ colordepth=32
 
         let conn = &result.connections[0];
         assert_eq!(conn.name, "Windows Server");
+        assert_eq!(conn.domain.as_deref(), Some("CORP"));
         assert!(matches!(conn.protocol_config, ProtocolConfig::Rdp(_)));
 
         if let ProtocolConfig::Rdp(rdp_config) = &conn.protocol_config {
@@ -661,6 +686,10 @@ server=192.168.1.100
         let result = importer.parse_remmina_file(content, "test.remmina", &mut group_map);
         assert_eq!(result.connections.len(), 0);
         assert_eq!(result.skipped.len(), 1);
+        assert_eq!(
+            result.skipped[0].location.as_deref(),
+            Some("test.remmina:4")
+        );
     }
 
     #[test]
@@ -676,6 +705,11 @@ protocol=SSH
         let result = importer.parse_remmina_file(content, "test.remmina", &mut group_map);
         assert_eq!(result.connections.len(), 0);
         assert_eq!(result.skipped.len(), 1);
+        // Without a server key the location points at the section header
+        assert_eq!(
+            result.skipped[0].location.as_deref(),
+            Some("test.remmina:2")
+        );
     }
 
     #[test]
@@ -28,6 +28,8 @@ struct SshConnectionData {
     port: Option<u16>,
     parent_id: Option<String>,
     credential_id: Option<String>,
+    /// Username given directly on the connection
+    username: Option<String>,
     /// Path to private key file
     private_key_path: Option<String>,
 }
@@ -41,6 +43,10 @@ struct RdpConnectionData {
     port: Option<u16>,
     parent_id: Option<String>,
     credential_id: Option<String>,
+    /// Username given directly on the connection
+    username: Option<String>,
+    /// Domain given directly on the connection
+    domain: Option<String>,
 }
 
 /// Royal TS VNC connection data
@@ -302,10 +308,9 @@ impl RoyalTsImporter {
             "Port" => conn.port = value.parse().ok(),
             "ParentID" => conn.parent_id = Some(value.to_string()),
             "CredentialId" => conn.credential_id = Some(value.to_string()),
-            "PrivateKeyFile" | "KeyFilePath" | "PrivateKeyPath" => {
-                if !value.is_empty() {
-                    conn.private_key_path = Some(value.to_string());
-                }
+            "CredentialUsername" if !value.is_empty() => conn.username = Some(value.to_string()),
+            "PrivateKeyFile" | "KeyFilePath" | "PrivateKeyPath" if !value.is_empty() => {
+                conn.private_key_path = Some(value.to_string());
             }
             _ => {}
         }
@@ -319,6 +324,8 @@ impl RoyalTsImporter {
             "Port" => conn.port = value.parse().ok(),
             "ParentID" => conn.parent_id = Some(value.to_string()),
             "CredentialId" => conn.credential_id = Some(value.to_string()),
+            "CredentialUsername" if !value.is_empty() => conn.username = Some(value.to_string()),
+            "CredentialDomain" if !value.is_empty() => conn.domain = Some(value.to_string()),
             _ => {}
         }
     }
@@ -428,6 +435,7 @@ impl RoyalTsImporter {
             port,
             ProtocolConfig::Ssh(ssh_config),
         );
+        connection.username.clone_from(&conn.username);
 
         if let Some(cred_id) = &conn.credential_id {
             if let Some(cred) = credentials.get(cred_id) {
@@ -459,6 +467,8 @@ impl RoyalTsImporter {
             port,
             ProtocolConfig::Rdp(RdpConfig::default()),
         );
+        connection.username.clone_from(&conn.username);
+        connection.domain.clone_from(&conn.domain);
 
         if let Some(cred_id) = &conn.credential_id {
             if let Some(cred) = credentials.get(cred_id) {
@@ -654,6 +664,26 @@ mod tests {
         assert_eq!(conn.password_source, PasswordSource::Prompt);
     }
 
+    #[test]
+    fn test_parse_inline_credentials() {
+        let importer = RoyalTsImporter::new();
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<RTSZDocument>
+  <RoyalRDPConnection>
+    <ID>conn1</ID>
+    <Name>Desktop</Name>
+    <URI>10.0.0.5</URI>
+    <CredentialUsername>admin</CredentialUsername>
+    <CredentialDomain>CORP</CredentialDomain>
+  </RoyalRDPConnection>
+</RTSZDocument>"#;
+
+        let result = importer.parse_xml(content, "test.rtsz");
+        let conn = &result.connections[0];
+        assert_eq!(conn.username.as_deref(), Some("admin"));
+        assert_eq!(conn.domain.as_deref(), Some("CORP"));
+    }
+
     #[test]
     fn test_parse_folder_hierarchy() {
         let importer = RoyalTsImporter::new();
@@ -1,19 +1,523 @@
 //! SSH config file importer.
 //!
 //! Parses ~/.ssh/config and ~/.ssh/config.d/* files to import SSH connections.
+//!
+//! `Include` directives are followed recursively (relative paths resolve
+//! against the directory of the imported file); each file is read at most
+//! once per import, so include cycles and repeated includes are skipped. Options from `Match` blocks
+//! with `host`, `originalhost` or `all` criteria are attached to matching
+//! hosts as custom options; blocks with other criteria are reported as
+//! skipped entries.
+//!
+//! Very large files can be read with [`SshConfigImporter::stream_from_path`],
+//! which yields connections as their `Host` blocks end.
 
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
-use tracing::{debug, info_span};
+use tracing::{debug, info_span, warn};
 
 use crate::error::ImportError;
-use crate::models::{Connection, ProtocolConfig, SshAuthMethod, SshConfig, SshKeySource};
+use crate::models::{
+    is_valid_env_name, Connection, ProtocolConfig, SshAlgorithmList, SshAlgorithms, SshAuthMethod,
+    SshConfig, SshForward, SshKeySource,
+};
 use crate::tracing::span_names;
 
+use super::batch::{ImportItem, ImportStream};
 use super::traits::{ImportResult, ImportSource, SkippedEntry};
 
+/// Maximum nesting depth of `Include` directives (same limit as OpenSSH)
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Records `path` as read, returning false if it was read before
+///
+/// Paths are compared after canonicalization so that symlinks and relative
+/// spellings of the same file are recognized.
+fn first_visit(visited: &mut HashSet<PathBuf>, path: &Path) -> bool {
+    visited.insert(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+/// Reports an `Include`d file that was already read during this import
+fn skip_repeated_include(result: &mut ImportResult, pattern: &str, path: &Path, location: &str) {
+    result.add_skipped(SkippedEntry::with_location(
+        format!("Include {pattern}"),
+        format!("{} is already included", path.display()),
+        location,
+    ));
+}
+
+/// A config line with its origin, after `Include` expansion
+struct ConfigLine {
+    number: usize,
+    text: String,
+    source: String,
+}
+
+impl ConfigLine {
+    /// Returns the `file:line` location for skipped-entry reports
+    fn location(&self) -> String {
+        format!("{}:{}", self.source, self.number)
+    }
+}
+
+/// A `Host` block and its options
+struct HostBlock {
+    pattern: String,
+    options: HashMap<String, String>,
+    /// `LocalForward`/`RemoteForward`/`DynamicForward` lines, which may repeat
+    forwards: Vec<SshForward>,
+    /// `file:line` of the `Host` line
+    location: String,
+}
+
+/// A `Match` block whose conditions can be evaluated at import time
+struct MatchBlock {
+    conditions: Vec<MatchCondition>,
+    options: HashMap<String, String>,
+    forwards: Vec<SshForward>,
+}
+
+impl MatchBlock {
+    /// Returns true if every condition holds for the host
+    fn applies_to(&self, alias: &str, hostname: &str) -> bool {
+        self.conditions.iter().all(|condition| match condition {
+            MatchCondition::All => true,
+            MatchCondition::Host(patterns) => match_pattern_list(patterns, hostname),
+            MatchCondition::OriginalHost(patterns) => match_pattern_list(patterns, alias),
+        })
+    }
+}
+
+/// A `Match` criterion supported by the importer
+enum MatchCondition {
+    /// `all`
+    All,
+    /// `host` patterns, matched against the target hostname
+    Host(String),
+    /// `originalhost` patterns, matched against the `Host` alias
+    OriginalHost(String),
+}
+
+/// The block that subsequent options belong to
+#[derive(Default)]
+enum Block {
+    #[default]
+    None,
+    /// The last `Host` block
+    Host,
+    /// The last `Match` block
+    Match,
+    /// A `Match` block with conditions that cannot be evaluated
+    Ignored,
+}
+
+/// Groups config lines into `Host` and `Match` blocks
+#[derive(Default)]
+struct BlockParser {
+    hosts: Vec<HostBlock>,
+    matches: Vec<MatchBlock>,
+    current: Block,
+}
+
+impl BlockParser {
+    /// Adds one line to the current block or starts a new one
+    fn feed(&mut self, line: &ConfigLine, result: &mut ImportResult) {
+        let text = line.text.trim();
+
+        // Skip empty lines and comments
+        if text.is_empty() || text.starts_with('#') {
+            return;
+        }
+
+        // Parse key-value pair
+        let Some((key, value)) = SshConfigImporter::parse_line(text) else {
+            result.add_skipped(SkippedEntry::with_location(
+                format!("line {}", line.number),
+                "Invalid syntax",
+                line.location(),
+            ));
+            return;
+        };
+
+        let key_lower = key.to_lowercase();
+
+        match key_lower.as_str() {
+            "host" => {
+                self.hosts.push(HostBlock {
+                    pattern: value.to_string(),
+                    options: HashMap::new(),
+                    forwards: Vec::new(),
+                    location: line.location(),
+                });
+                self.current = Block::Host;
+            }
+            "match" => match parse_match_conditions(value) {
+                Ok(conditions) => {
+                    self.matches.push(MatchBlock {
+                        conditions,
+                        options: HashMap::new(),
+                        forwards: Vec::new(),
+                    });
+                    self.current = Block::Match;
+                }
+                Err(condition) => {
+                    result.add_skipped(SkippedEntry::with_location(
+                        format!("Match {value}"),
+                        format!(
+                            "Match condition '{condition}' cannot be represented; \
+                             options in this block are not imported"
+                        ),
+                        line.location(),
+                    ));
+                    self.current = Block::Ignored;
+                }
+            },
+            "localforward" | "remoteforward" | "dynamicforward" => {
+                let Some(forward) = SshForward::from_config(key, value) else {
+                    result.add_skipped(SkippedEntry::with_location(
+                        format!("{key} {value}"),
+                        "Unsupported port forwarding specification",
+                        line.location(),
+                    ));
+                    return;
+                };
+                match self.current {
+                    Block::Host => self.last_host().forwards.push(forward),
+                    Block::Match => self.last_match().forwards.push(forward),
+                    Block::None | Block::Ignored => {}
+                }
+            }
+            _ => match self.current {
+                Block::Host => insert_option(&mut self.last_host().options, key_lower, value),
+                Block::Match => insert_option(&mut self.last_match().options, key_lower, value),
+                Block::None | Block::Ignored => {}
+            },
+        }
+    }
+
+    fn last_host(&mut self) -> &mut HostBlock {
+        self.hosts
+            .last_mut()
+            .expect("Block::Host implies a host block")
+    }
+
+    fn last_match(&mut self) -> &mut MatchBlock {
+        self.matches
+            .last_mut()
+            .expect("Block::Match implies a match block")
+    }
+
+    /// Removes the `Host` blocks that can no longer receive options
+    fn take_finished_hosts(&mut self) -> Vec<HostBlock> {
+        let open = usize::from(matches!(self.current, Block::Host));
+        let finished = self.hosts.len().saturating_sub(open);
+        self.hosts.drain(..finished).collect()
+    }
+}
+
+/// Stores a block option; `SetEnv` lines accumulate instead of replacing
+fn insert_option(options: &mut HashMap<String, String>, key: String, value: &str) {
+    match options.get_mut(&key) {
+        Some(existing) if key == "setenv" => {
+            existing.push(' ');
+            existing.push_str(value);
+        }
+        _ => {
+            options.insert(key, value.to_string());
+        }
+    }
+}
+
+/// Splits a `SetEnv` value into `NAME=value` pairs
+///
+/// Pairs are separated by whitespace; double quotes group a pair that
+/// contains whitespace, and a backslash escapes the next character inside
+/// quotes. Items without `=` are returned as `Err`.
+fn parse_set_env(value: &str) -> Vec<Result<(String, String), String>> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quoted = false;
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_token = true;
+            }
+            '\\' if quoted => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_token {
+                    items.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        items.push(current);
+    }
+
+    items
+        .into_iter()
+        .map(|item| match item.split_once('=') {
+            Some((name, value)) => Ok((name.to_string(), value.to_string())),
+            None => Err(item),
+        })
+        .collect()
+}
+
+/// Reads config lines from a file and its `Include`s one line at a time
+struct StreamingLines {
+    /// Open files, innermost last, with their path and current line number
+    stack: Vec<(io::Lines<BufReader<File>>, String, usize)>,
+    base_dir: PathBuf,
+    /// Canonical paths of the files opened so far
+    visited: HashSet<PathBuf>,
+    /// Bytes read from the top-level file
+    bytes_read: u64,
+    /// Problems with `Include`s, to be reported by the stream
+    issues: ImportResult,
+}
+
+impl StreamingLines {
+    fn open(path: &Path) -> Result<Self, ImportError> {
+        let file = File::open(path).map_err(|e| ImportError::ParseError {
+            source_name: "SSH config".to_string(),
+            reason: format!("Failed to read {}: {}", path.display(), e),
+        })?;
+        let mut visited = HashSet::new();
+        first_visit(&mut visited, path);
+        Ok(Self {
+            stack: vec![(BufReader::new(file).lines(), path.display().to_string(), 0)],
+            base_dir: path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf(),
+            visited,
+            bytes_read: 0,
+            issues: ImportResult::new(),
+        })
+    }
+
+    /// Returns the next line, descending into `Include`d files
+    fn next_line(&mut self) -> Option<ConfigLine> {
+        loop {
+            let depth = self.stack.len().checked_sub(1)?;
+            let (lines, source, number) = self.stack.last_mut()?;
+            let text = match lines.next() {
+                Some(Ok(text)) => text,
+                Some(Err(e)) => {
+                    self.issues.add_error(ImportError::ParseError {
+                        source_name: "SSH config".to_string(),
+                        reason: format!("Failed to read {source}: {e}"),
+                    });
+                    self.stack.pop();
+                    continue;
+                }
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            *number += 1;
+            if depth == 0 {
+                self.bytes_read += text.len() as u64 + 1;
+            }
+
+            let line = ConfigLine {
+                number: *number,
+                text,
+                source: source.clone(),
+            };
+            let include = SshConfigImporter::parse_line(line.text.trim())
+                .filter(|(key, _)| key.eq_ignore_ascii_case("include"))
+                .map(|(_, value)| value.to_string());
+            let Some(patterns) = include else {
+                return Some(line);
+            };
+
+            let location = line.location();
+            if depth >= MAX_INCLUDE_DEPTH {
+                self.issues.add_skipped(SkippedEntry::with_location(
+                    format!("Include {patterns}"),
+                    format!("Include nesting deeper than {MAX_INCLUDE_DEPTH} levels"),
+                    location,
+                ));
+                continue;
+            }
+
+            let mut included = Vec::new();
+            for pattern in patterns.split_whitespace() {
+                for path in SshConfigImporter::resolve_include(
+                    pattern,
+                    &self.base_dir,
+                    &location,
+                    &mut self.issues,
+                ) {
+                    if !first_visit(&mut self.visited, &path) {
+                        skip_repeated_include(&mut self.issues, pattern, &path, &location);
+                        continue;
+                    }
+                    match File::open(&path) {
+                        Ok(file) => included.push((
+                            BufReader::new(file).lines(),
+                            path.display().to_string(),
+                            0,
+                        )),
+                        Err(e) => self.issues.add_skipped(SkippedEntry::with_location(
+                            format!("Include {pattern}"),
+                            format!("Failed to read {}: {e}", path.display()),
+                            &location,
+                        )),
+                    }
+                }
+            }
+            // The first included file is read first
+            self.stack.extend(included.into_iter().rev());
+        }
+    }
+}
+
+/// Streaming SSH config parser created by [`SshConfigImporter::stream_from_path`]
+///
+/// The file is read twice: once to collect `Match` blocks, which may apply to
+/// hosts defined before them, and once to yield hosts as their blocks end.
+pub struct SshConfigStream<'a> {
+    importer: &'a SshConfigImporter,
+    lines: StreamingLines,
+    parser: BlockParser,
+    /// `Match` blocks of the whole file, from the first pass
+    matches: Vec<MatchBlock>,
+    queue: VecDeque<ImportItem>,
+    total_bytes: u64,
+    finished: bool,
+}
+
+impl Iterator for SshConfigStream<'_> {
+    type Item = ImportItem;
+
+    fn next(&mut self) -> Option<ImportItem> {
+        loop {
+            if let Some(item) = self.queue.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+
+            let mut result = ImportResult::new();
+            let hosts = if let Some(line) = self.lines.next_line() {
+                self.parser.feed(&line, &mut result);
+                self.parser.take_finished_hosts()
+            } else {
+                self.finished = true;
+                std::mem::take(&mut self.parser.hosts)
+            };
+            for host in &hosts {
+                self.importer.finish_host(host, &self.matches, &mut result);
+            }
+            result.merge(std::mem::take(&mut self.lines.issues));
+            ImportItem::drain_result(&mut result, &mut self.queue);
+        }
+    }
+}
+
+impl ImportStream for SshConfigStream<'_> {
+    fn position(&self) -> (u64, u64) {
+        (
+            self.lines.bytes_read.min(self.total_bytes),
+            self.total_bytes,
+        )
+    }
+}
+
+/// Parses `Match` criteria, returning the first unsupported one as an error
+///
+/// Only `all`, `host` and `originalhost` can be evaluated without a live
+/// connection; `exec`, `user`, `localuser`, `canonical` etc. cannot.
+fn parse_match_conditions(criteria: &str) -> Result<Vec<MatchCondition>, String> {
+    let mut conditions = Vec::new();
+    let mut tokens = criteria.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        let keyword = token.to_lowercase();
+        let condition = match keyword.as_str() {
+            "all" => MatchCondition::All,
+            "host" | "originalhost" => {
+                let patterns = tokens.next().ok_or_else(|| token.to_string())?.to_string();
+                if keyword == "host" {
+                    MatchCondition::Host(patterns)
+                } else {
+                    MatchCondition::OriginalHost(patterns)
+                }
+            }
+            _ => {
+                return Err(tokens
+                    .next()
+                    .map_or_else(|| token.to_string(), |arg| format!("{token} {arg}")));
+            }
+        };
+        conditions.push(condition);
+    }
+
+    Ok(conditions)
+}
+
+/// Matches a comma-separated OpenSSH pattern list, honouring `!` negation
+fn match_pattern_list(patterns: &str, value: &str) -> bool {
+    let value = value.to_lowercase();
+    let mut matched = false;
+    for pattern in patterns.split(',') {
+        let pattern = pattern.trim().to_lowercase();
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if wildcard_match(negated, &value) {
+                return false;
+            }
+        } else if wildcard_match(&pattern, &value) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// Matches `text` against a pattern with `*` and `?` wildcards
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Importer for SSH config files.
 ///
 /// Parses standard OpenSSH configuration files and extracts connection
@@ -41,82 +545,210 @@ impl SshConfigImporter {
     }
 
     /// Parses SSH config content and returns an import result
+    ///
+    /// `Include` directives are not resolved here since there is no file to
+    /// resolve them against; they are reported as skipped entries. Use
+    /// [`ImportSource::import_from_path`] to follow includes.
     #[must_use]
     pub fn parse_config(&self, content: &str, source_path: &str) -> ImportResult {
         let mut result = ImportResult::new();
-        let mut current_host: Option<String> = None;
-        let mut current_options: HashMap<String, String> = HashMap::new();
+        let lines = Self::collect_lines(
+            content,
+            source_path,
+            None,
+            0,
+            &mut HashSet::new(),
+            &mut result,
+        );
+        self.parse_lines(&lines, &mut result);
+        result
+    }
 
-        for (line_num, line) in content.lines().enumerate() {
-            let line = line.trim();
+    /// Splits content into lines, inlining `Include`d files when `base_dir` is set
+    fn collect_lines(
+        content: &str,
+        source_path: &str,
+        base_dir: Option<&Path>,
+        depth: usize,
+        visited: &mut HashSet<PathBuf>,
+        result: &mut ImportResult,
+    ) -> Vec<ConfigLine> {
+        let mut lines = Vec::new();
 
-            // Skip empty lines and comments
-            if line.is_empty() || line.starts_with('#') {
+        for (line_num, line) in content.lines().enumerate() {
+            let include = Self::parse_line(line.trim())
+                .filter(|(key, _)| key.eq_ignore_ascii_case("include"))
+                .map(|(_, value)| value);
+
+            let location = format!("{source_path}:{}", line_num + 1);
+            let Some(patterns) = include else {
+                lines.push(ConfigLine {
+                    number: line_num + 1,
+                    text: line.to_string(),
+                    source: source_path.to_string(),
+                });
                 continue;
-            }
+            };
 
-            // Parse key-value pair
-            let Some((key, value)) = Self::parse_line(line) else {
+            let Some(base_dir) = base_dir else {
                 result.add_skipped(SkippedEntry::with_location(
-                    format!("line {}", line_num + 1),
-                    "Invalid syntax",
-                    source_path,
+                    format!("Include {patterns}"),
+                    "Include directives are only resolved when importing from a file",
+                    &location,
                 ));
                 continue;
             };
 
-            let key_lower = key.to_lowercase();
-
-            if key_lower == "host" {
-                // Save previous host if any
-                if let Some(host_pattern) = current_host.take() {
-                    self.process_host_entry(
-                        &host_pattern,
-                        &current_options,
-                        source_path,
-                        &mut result,
-                    );
+            if depth >= MAX_INCLUDE_DEPTH {
+                result.add_skipped(SkippedEntry::with_location(
+                    format!("Include {patterns}"),
+                    format!("Include nesting deeper than {MAX_INCLUDE_DEPTH} levels"),
+                    &location,
+                ));
+                continue;
+            }
+
+            for pattern in patterns.split_whitespace() {
+                for path in Self::resolve_include(pattern, base_dir, &location, result) {
+                    if !first_visit(visited, &path) {
+                        skip_repeated_include(result, pattern, &path, &location);
+                        continue;
+                    }
+                    match fs::read_to_string(&path) {
+                        Ok(included) => lines.extend(Self::collect_lines(
+                            &included,
+                            &path.display().to_string(),
+                            Some(base_dir),
+                            depth + 1,
+                            visited,
+                            result,
+                        )),
+                        Err(e) => result.add_skipped(SkippedEntry::with_location(
+                            format!("Include {pattern}"),
+                            format!("Failed to read {}: {e}", path.display()),
+                            &location,
+                        )),
+                    }
                 }
+            }
+        }
+
+        lines
+    }
+
+    /// Resolves an `Include` pattern to the files it names, sorted by path
+    ///
+    /// Relative patterns are resolved against `base_dir`. Wildcards are
+    /// supported in the file name component only. Problems are reported as
+    /// skipped entries at `location`, the `file:line` of the `Include`.
+    fn resolve_include(
+        pattern: &str,
+        base_dir: &Path,
+        location: &str,
+        result: &mut ImportResult,
+    ) -> Vec<PathBuf> {
+        let expanded = PathBuf::from(shellexpand::tilde(pattern).into_owned());
+        let path = if expanded.is_absolute() {
+            expanded
+        } else {
+            base_dir.join(expanded)
+        };
 
-                // Start new host entry
-                current_host = Some(value.to_string());
-                current_options.clear();
-            } else if current_host.is_some() {
-                // Add option to current host
-                current_options.insert(key_lower, value.to_string());
+        let file_pattern = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if !file_pattern.contains(['*', '?']) {
+            if path.is_file() {
+                return vec![path];
             }
+            result.add_skipped(SkippedEntry::with_location(
+                format!("Include {pattern}"),
+                format!("Included file {} not found", path.display()),
+                location,
+            ));
+            return Vec::new();
         }
 
-        // Process last host entry
-        if let Some(host_pattern) = current_host {
-            self.process_host_entry(&host_pattern, &current_options, source_path, &mut result);
+        let dir = path.parent().unwrap_or(base_dir);
+        if dir.to_string_lossy().contains(['*', '?']) {
+            result.add_skipped(SkippedEntry::with_location(
+                format!("Include {pattern}"),
+                "Wildcards are only supported in the file name of an Include",
+                location,
+            ));
+            return Vec::new();
         }
 
-        result
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|p| {
+                        p.is_file()
+                            && p.file_name().is_some_and(|name| {
+                                wildcard_match(&file_pattern, &name.to_string_lossy())
+                            })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        paths
     }
 
-    /// Parses a single line into key-value pair
-    fn parse_line(line: &str) -> Option<(&str, &str)> {
-        // SSH config supports both "Key Value" and "Key=Value" formats
-        if let Some(eq_pos) = line.find('=') {
-            let key = line[..eq_pos].trim();
-            let value = line[eq_pos + 1..].trim();
-            if !key.is_empty() && !value.is_empty() {
-                return Some((key, value));
-            }
+    /// Parses config lines into host entries, applying `Match` block options
+    fn parse_lines(&self, lines: &[ConfigLine], result: &mut ImportResult) {
+        let mut parser = BlockParser::default();
+        for line in lines {
+            parser.feed(line, result);
+        }
+        for host in &parser.hosts {
+            self.finish_host(host, &parser.matches, result);
         }
+    }
 
-        // Space-separated format
-        let parts: Vec<&str> = line.splitn(2, char::is_whitespace).collect();
-        if parts.len() == 2 {
-            let key = parts[0].trim();
-            let value = parts[1].trim();
-            if !key.is_empty() && !value.is_empty() {
-                return Some((key, value));
+    /// Converts a complete `Host` block into a connection
+    fn finish_host(&self, host: &HostBlock, matches: &[MatchBlock], result: &mut ImportResult) {
+        let alias = host.pattern.as_str();
+        let hostname = host.options.get("hostname").map_or(alias, String::as_str);
+        let mut match_options = HashMap::new();
+        let mut forwards = host.forwards.clone();
+        for block in matches.iter().filter(|m| m.applies_to(alias, hostname)) {
+            for (key, value) in &block.options {
+                match_options
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
             }
+            forwards.extend(block.forwards.iter().cloned());
         }
+        self.process_host_entry(
+            alias,
+            &host.options,
+            match_options,
+            forwards,
+            &host.location,
+            result,
+        );
+    }
+
+    /// Parses a single line into key-value pair
+    ///
+    /// The keyword ends at the first whitespace or `=`; the rest of the line
+    /// is the value, kept verbatim (e.g. `%h`/`%p` tokens or `=` inside a
+    /// `ProxyCommand`).
+    fn parse_line(line: &str) -> Option<(&str, &str)> {
+        // SSH config supports both "Key Value" and "Key=Value" formats
+        let key_end = line.find(|c: char| c.is_whitespace() || c == '=')?;
+        let key = &line[..key_end];
+        let rest = line[key_end..].trim_start();
+        let value = rest.strip_prefix('=').unwrap_or(rest).trim();
 
-        None
+        if key.is_empty() || value.is_empty() {
+            return None;
+        }
+        Some((key, value))
     }
 
     /// Processes a host entry and adds it to the result
@@ -124,7 +756,9 @@ impl SshConfigImporter {
         &self,
         host_pattern: &str,
         options: &HashMap<String, String>,
-        source_path: &str,
+        match_options: HashMap<String, String>,
+        forwards: Vec<SshForward>,
+        location: &str,
         result: &mut ImportResult,
     ) {
         // Skip wildcard patterns and special entries
@@ -132,7 +766,7 @@ impl SshConfigImporter {
             result.add_skipped(SkippedEntry::with_location(
                 host_pattern,
                 "Wildcard patterns are not imported",
-                source_path,
+                location,
             ));
             return;
         }
@@ -145,7 +779,7 @@ impl SshConfigImporter {
             result.add_skipped(SkippedEntry::with_location(
                 host_pattern,
                 "No valid hostname",
-                source_path,
+                location,
             ));
             return;
         }
@@ -185,6 +819,20 @@ impl SshConfigImporter {
             .get("compression")
             .is_some_and(|v| v.to_lowercase() == "yes");
 
+        // Options from matching Match blocks apply unless the host sets them
+        let mut match_options = match_options;
+        let match_env = match_options.remove("setenv");
+        let environment = options
+            .get("setenv")
+            .or(match_env.as_ref())
+            .map(|value| Self::parse_environment(value, host_pattern, location, result))
+            .unwrap_or_default();
+        let algorithms = Self::parse_algorithms(options, &mut match_options, host_pattern);
+        let mut custom_options = self.extract_recognized_options(options);
+        for (key, value) in match_options {
+            custom_options.entry(key).or_insert(value);
+        }
+
         // Build SSH config
         let ssh_config = SshConfig {
             auth_method,
@@ -193,6 +841,9 @@ impl SshConfigImporter {
             agent_key_fingerprint: None,
             identities_only,
             jump_host_id: None,
+            forwards,
+            environment,
+            algorithms,
             proxy_jump: options.get("proxyjump").cloned(),
             use_control_master: options
                 .get("controlmaster")
@@ -200,7 +851,7 @@ impl SshConfigImporter {
             agent_forwarding,
             x11_forwarding,
             compression,
-            custom_options: self.extract_recognized_options(options),
+            custom_options,
             startup_command: None,
         };
 
@@ -219,6 +870,56 @@ impl SshConfigImporter {
 
         result.add_connection(connection);
     }
+
+    /// Parses a `SetEnv` value, reporting entries that cannot be imported
+    fn parse_environment(
+        value: &str,
+        host_pattern: &str,
+        location: &str,
+        result: &mut ImportResult,
+    ) -> Vec<(String, String)> {
+        let mut environment = Vec::new();
+        for item in parse_set_env(value) {
+            match item {
+                Ok((name, value)) if is_valid_env_name(&name) => {
+                    environment.push((name, value));
+                }
+                Ok((name, _)) | Err(name) => result.add_skipped(SkippedEntry::with_location(
+                    format!("{host_pattern}: SetEnv {name}"),
+                    "Not a valid NAME=value environment entry",
+                    location,
+                )),
+            }
+        }
+        environment
+    }
+
+    /// Reads the `Ciphers`, `MACs` and `KexAlgorithms` preferences
+    ///
+    /// Names that do not look like algorithm names are logged but kept,
+    /// since the supported algorithms differ between OpenSSH versions.
+    fn parse_algorithms(
+        options: &HashMap<String, String>,
+        match_options: &mut HashMap<String, String>,
+        host_pattern: &str,
+    ) -> SshAlgorithms {
+        let mut algorithms = SshAlgorithms::default();
+        for keyword in SshAlgorithms::KEYWORDS {
+            let key = keyword.to_lowercase();
+            let match_value = match_options.remove(&key);
+            if let (Some(value), Some(list)) = (
+                options.get(&key).or(match_value.as_ref()),
+                algorithms.get_mut(keyword),
+            ) {
+                *list = SshAlgorithmList::parse(value);
+            }
+        }
+        for warning in algorithms.warnings() {
+            warn!(host = host_pattern, "{warning}");
+        }
+        algorithms
+    }
+
     /// Extracts recognized SSH options as custom_options for the connection
     fn extract_recognized_options(
         &self,
@@ -234,6 +935,7 @@ impl SshConfigImporter {
             "loglevel",
             "connecttimeout",
             "connectionattempts",
+            "proxycommand",
         ];
 
         options
@@ -250,7 +952,59 @@ impl SshConfigImporter {
             reason: format!("Failed to read {}: {}", path.display(), e),
         })?;
 
-        Ok(self.parse_config(&content, &path.display().to_string()))
+        let source_path = path.display().to_string();
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut result = ImportResult::new();
+        let mut visited = HashSet::new();
+        first_visit(&mut visited, path);
+        let lines = Self::collect_lines(
+            &content,
+            &source_path,
+            Some(base_dir),
+            0,
+            &mut visited,
+            &mut result,
+        );
+        self.parse_lines(&lines, &mut result);
+        Ok(result)
+    }
+
+    /// Opens an SSH config file for streaming import
+    ///
+    /// Unlike [`ImportSource::import_from_path`] the file is never held in
+    /// memory as a whole; use this with [`BatchImporter::process_stream`]
+    /// for files above [`STREAMING_IMPORT_THRESHOLD`].
+    ///
+    /// [`BatchImporter::process_stream`]: super::BatchImporter::process_stream
+    /// [`STREAMING_IMPORT_THRESHOLD`]: super::STREAMING_IMPORT_THRESHOLD
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or read.
+    pub fn stream_from_path(&self, path: &Path) -> Result<SshConfigStream<'_>, ImportError> {
+        if !path.exists() {
+            return Err(ImportError::FileNotFound(path.to_path_buf()));
+        }
+        let total_bytes = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+
+        // First pass: only the Match blocks are kept
+        let mut lines = StreamingLines::open(path)?;
+        let mut parser = BlockParser::default();
+        let mut discarded = ImportResult::new();
+        while let Some(line) = lines.next_line() {
+            parser.feed(&line, &mut discarded);
+            parser.take_finished_hosts();
+        }
+
+        Ok(SshConfigStream {
+            importer: self,
+            lines: StreamingLines::open(path)?,
+            parser: BlockParser::default(),
+            matches: parser.matches,
+            queue: VecDeque::new(),
+            total_bytes,
+            finished: false,
+        })
     }
 }
 
@@ -351,6 +1105,7 @@ impl ImportSource for SshConfigImporter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::SshAlgorithmMode;
 
     #[test]
     fn test_parse_simple_host() {
@@ -408,6 +1163,36 @@ Host *.example.com
         let result = importer.parse_config(config, "test");
         assert_eq!(result.connections.len(), 0);
         assert_eq!(result.skipped.len(), 2);
+        assert_eq!(result.skipped[0].location.as_deref(), Some("test:2"));
+        assert_eq!(result.skipped[1].location.as_deref(), Some("test:5"));
+    }
+
+    #[test]
+    fn test_skipped_entries_report_line() {
+        let importer = SshConfigImporter::new();
+        let config = r"
+Host good
+    HostName good.example.com
+    LocalForward nonsense
+
+Host broken
+    HostName
+Include extra.conf
+";
+
+        let result = importer.parse_config(config, "ssh_config");
+        let locations: Vec<(&str, Option<&str>)> = result
+            .skipped
+            .iter()
+            .map(|s| (s.identifier.as_str(), s.location.as_deref()))
+            .collect();
+        assert!(locations.contains(&("LocalForward nonsense", Some("ssh_config:4"))));
+        assert!(locations.contains(&("line 7", Some("ssh_config:7"))));
+        assert!(locations.contains(&("Include extra.conf", Some("ssh_config:8"))));
+        assert!(result.skipped.iter().all(|s| s
+            .location
+            .as_deref()
+            .is_some_and(|l| l.starts_with("ssh_config:"))));
     }
 
     #[test]
@@ -514,4 +1299,318 @@ Host keepalive-server
             panic!("Expected SSH config");
         }
     }
+
+    fn custom_options(conn: &Connection) -> &HashMap<String, String> {
+        match &conn.protocol_config {
+            ProtocolConfig::Ssh(ssh_config) => &ssh_config.custom_options,
+            _ => panic!("Expected SSH config"),
+        }
+    }
+
+    #[test]
+    fn test_proxy_command_preserved_verbatim() {
+        let importer = SshConfigImporter::new();
+        let config = r"
+Host internal
+    HostName 10.0.0.5
+    ProxyCommand ssh -o StrictHostKeyChecking=no -W %h:%p bastion
+";
+
+        let result = importer.parse_config(config, "test");
+        assert_eq!(
+            custom_options(&result.connections[0]).get("proxycommand"),
+            Some(&"ssh -o StrictHostKeyChecking=no -W %h:%p bastion".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_block_options_applied() {
+        let importer = SshConfigImporter::new();
+        let config = r#"
+Host web
+    HostName web.prod.example.com
+    ServerAliveInterval 10
+
+Host db
+    HostName db.staging.example.com
+
+Match host *.prod.example.com,!db.*
+    ServerAliveInterval 60
+    ForwardX11Trusted no
+
+Match originalhost db
+    LogLevel DEBUG
+
+Match exec "test -f /tmp/vpn"
+    ProxyJump vpn-gw
+"#;
+
+        let result = importer.parse_config(config, "test");
+        assert_eq!(result.connections.len(), 2);
+
+        let web = custom_options(&result.connections[0]);
+        // Host-level values take precedence over Match-level values
+        assert_eq!(web.get("serveraliveinterval"), Some(&"10".to_string()));
+        assert_eq!(web.get("forwardx11trusted"), Some(&"no".to_string()));
+        assert!(!web.contains_key("proxyjump"));
+
+        let db = custom_options(&result.connections[1]);
+        assert_eq!(db.get("loglevel"), Some(&"DEBUG".to_string()));
+        assert!(!db.contains_key("forwardx11trusted"));
+
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.skipped[0].reason.contains("exec"));
+    }
+
+    #[test]
+    fn test_port_forwards_imported() {
+        let importer = SshConfigImporter::new();
+        let config = r"
+Host tunnel
+    HostName bastion.example.com
+    LocalForward 8080 db.internal:5432
+    LocalForward 127.0.0.1:6379 cache.internal:6379
+    RemoteForward 9000 localhost:3000
+    DynamicForward 1080
+    LocalForward /tmp/app.sock /run/app.sock
+";
+
+        let result = importer.parse_config(config, "test");
+        let ProtocolConfig::Ssh(ssh_config) = &result.connections[0].protocol_config else {
+            panic!("Expected SSH config");
+        };
+        let lines: Vec<String> = ssh_config
+            .forwards
+            .iter()
+            .map(|f| format!("{} {}", f.config_keyword(), f.config_value()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "LocalForward 8080 db.internal:5432",
+                "LocalForward 127.0.0.1:6379 cache.internal:6379",
+                "RemoteForward 9000 localhost:3000",
+                "DynamicForward 1080",
+            ]
+        );
+        assert!(ssh_config.custom_options.is_empty());
+        assert_eq!(result.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_algorithms_imported() {
+        let importer = SshConfigImporter::new();
+        let config = r"
+Host switch
+    HostName 10.0.0.2
+    Ciphers +aes128-cbc,3des-cbc
+    MACs=hmac-sha1
+
+Match host 10.0.0.2
+    KexAlgorithms -diffie-hellman-group1-sha1
+    MACs hmac-md5
+";
+
+        let result = importer.parse_config(config, "test");
+        let ProtocolConfig::Ssh(ssh_config) = &result.connections[0].protocol_config else {
+            panic!("Expected SSH config");
+        };
+        let algorithms = &ssh_config.algorithms;
+        assert_eq!(algorithms.ciphers.mode, SshAlgorithmMode::Append);
+        assert_eq!(
+            algorithms.ciphers.algorithms,
+            vec!["aes128-cbc", "3des-cbc"]
+        );
+        assert_eq!(algorithms.macs.to_value(), "hmac-sha1");
+        assert_eq!(
+            algorithms.kex_algorithms.to_value(),
+            "-diffie-hellman-group1-sha1"
+        );
+        assert!(ssh_config.custom_options.is_empty());
+    }
+
+    #[test]
+    fn test_set_env_imported() {
+        let importer = SshConfigImporter::new();
+        let config = r#"
+Host app
+    HostName app.example.com
+    SetEnv LANG=C.UTF-8 "GREETING=hello world"
+    SetEnv EMPTY= 2FA=on NOVALUE
+
+Match host app.example.com
+    SetEnv IGNORED=1
+"#;
+
+        let result = importer.parse_config(config, "test");
+        let ProtocolConfig::Ssh(ssh_config) = &result.connections[0].protocol_config else {
+            panic!("Expected SSH config");
+        };
+        assert_eq!(
+            ssh_config.environment,
+            vec![
+                ("LANG".to_string(), "C.UTF-8".to_string()),
+                ("GREETING".to_string(), "hello world".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+        assert!(ssh_config.custom_options.is_empty());
+        assert_eq!(result.skipped.len(), 2);
+    }
+
+    #[test]
+    fn test_include_resolved_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_d = dir.path().join("conf.d");
+        fs::create_dir(&conf_d).unwrap();
+        fs::write(
+            conf_d.join("10-web.conf"),
+            "Host web\n    HostName web.example.com\n",
+        )
+        .unwrap();
+        fs::write(
+            conf_d.join("20-db.conf"),
+            "Host db\n    HostName db.example.com\nInclude nested\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("nested"),
+            "Host cache\n    HostName cache.example.com\n",
+        )
+        .unwrap();
+        fs::write(conf_d.join("notes.txt"), "Host ignored\n").unwrap();
+
+        let config = dir.path().join("config");
+        fs::write(
+            &config,
+            "Include conf.d/*.conf missing.conf\n\nHost main\n    HostName main.example.com\n",
+        )
+        .unwrap();
+
+        let result = SshConfigImporter::new().import_from_path(&config).unwrap();
+        let names: Vec<_> = result.connections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["web", "db", "cache", "main"]);
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.skipped[0].reason.contains("not found"));
+
+        // Without a file there is nothing to resolve against
+        let result = SshConfigImporter::new().parse_config("Include conf.d/*.conf\n", "inline");
+        assert!(result.connections.is_empty());
+        assert_eq!(result.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_include_cycle_is_read_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        fs::write(
+            &config,
+            "Include config other\nHost loop\n    HostName loop.example.com\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("other"),
+            "Include ./config other\nHost other\n    HostName other.example.com\n",
+        )
+        .unwrap();
+
+        let importer = SshConfigImporter::new();
+        let result = importer.import_from_path(&config).unwrap();
+        let names: Vec<_> = result.connections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["other", "loop"]);
+        assert_eq!(result.skipped.len(), 3);
+        assert!(result
+            .skipped
+            .iter()
+            .all(|entry| entry.reason.contains("already included")));
+
+        let streamed = importer
+            .stream_from_path(&config)
+            .unwrap()
+            .filter(|item| matches!(item, ImportItem::Connection(_)))
+            .count();
+        assert_eq!(streamed, 2);
+    }
+
+    #[test]
+    fn test_include_depth_is_bounded() {
+        let dir = tempfile::tempdir().unwrap();
+        for level in 0..=MAX_INCLUDE_DEPTH + 1 {
+            fs::write(
+                dir.path().join(format!("level{level}")),
+                format!(
+                    "Include level{}\nHost host{level}\n    HostName host{level}.example.com\n",
+                    level + 1
+                ),
+            )
+            .unwrap();
+        }
+
+        let result = SshConfigImporter::new()
+            .import_from_path(&dir.path().join("level0"))
+            .unwrap();
+        assert_eq!(result.connections.len(), MAX_INCLUDE_DEPTH + 1);
+        assert!(result.skipped[0].reason.contains("nesting"));
+    }
+
+    #[test]
+    fn test_stream_matches_import() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("extra"),
+            "Host extra\n    HostName extra.example.com\n",
+        )
+        .unwrap();
+        let config = dir.path().join("config");
+        fs::write(
+            &config,
+            "Host web\n    HostName web.example.com\n\nInclude extra missing\n\n\
+             Host *\n    User fallback\n\n\
+             Host db\n    HostName db.example.com\n    Port 5022\n\n\
+             Match host db.example.com\n    LogLevel DEBUG\n",
+        )
+        .unwrap();
+
+        let importer = SshConfigImporter::new();
+        let expected = importer.import_from_path(&config).unwrap();
+
+        let mut stream = importer.stream_from_path(&config).unwrap();
+        let mut connections = Vec::new();
+        let mut skipped = 0;
+        for item in stream.by_ref() {
+            match item {
+                ImportItem::Connection(conn) => connections.push(*conn),
+                ImportItem::Skipped(_) => skipped += 1,
+                _ => {}
+            }
+        }
+        let (read, total) = stream.position();
+        assert_eq!(read, total);
+
+        let summarize = |conns: &[Connection]| {
+            conns
+                .iter()
+                .map(|c| (c.name.clone(), c.host.clone(), c.port, c.username.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summarize(&connections), summarize(&expected.connections));
+        assert_eq!(skipped, expected.skipped.len());
+
+        // The Match block at the end still applies to the earlier host
+        let db = connections.iter().find(|c| c.name == "db").unwrap();
+        assert_eq!(
+            custom_options(db).get("loglevel"),
+            Some(&"DEBUG".to_string())
+        );
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.example.com", "web.example.com"));
+        assert!(wildcard_match("web-?", "web-1"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("web-?", "web-10"));
+        assert!(match_pattern_list("*.com,!bad.com", "good.com"));
+        assert!(!match_pattern_list("*.com,!bad.com", "bad.com"));
+    }
 }
@@ -3,8 +3,12 @@
 //! This module defines the core abstractions for the import engine,
 //! allowing different import sources to be implemented uniformly.
 
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
 use crate::error::ImportError;
-use crate::models::{Connection, ConnectionGroup};
+use crate::models::{Connection, ConnectionGroup, ProtocolType};
 use crate::progress::ProgressReporter;
 
 /// Result of an import operation containing successful imports and any issues encountered.
@@ -18,6 +22,15 @@ pub struct ImportResult {
     pub skipped: Vec<SkippedEntry>,
     /// Errors encountered during import
     pub errors: Vec<ImportError>,
+    /// Groups of duplicates merged into connections not kept in `connections`
+    ///
+    /// Streaming imports hand connections out before later duplicates are
+    /// seen, so their further groups are collected here, keyed by connection
+    /// ID, and applied with [`Self::apply_group_memberships`]. Merges within
+    /// `connections` go straight to `Connection::additional_group_ids`.
+    pub group_memberships: HashMap<Uuid, Vec<Uuid>>,
+    /// Number of duplicate connections merged into an earlier one
+    pub merged_duplicates: usize,
 }
 
 impl ImportResult {
@@ -49,9 +62,10 @@ impl ImportResult {
     #[must_use]
     pub fn summary(&self) -> String {
         format!(
-            "Imported: {}, Groups: {}, Skipped: {}, Errors: {}",
+            "Imported: {}, Groups: {}, Merged: {}, Skipped: {}, Errors: {}",
             self.connections.len(),
             self.groups.len(),
+            self.merged_duplicates,
             self.skipped.len(),
             self.errors.len()
         )
@@ -83,6 +97,57 @@ impl ImportResult {
         self.groups.extend(other.groups);
         self.skipped.extend(other.skipped);
         self.errors.extend(other.errors);
+        self.group_memberships.extend(other.group_memberships);
+        self.merged_duplicates += other.merged_duplicates;
+    }
+
+    /// Adds the collected `group_memberships` to the given connections
+    pub fn apply_group_memberships<'a>(
+        &self,
+        connections: impl IntoIterator<Item = &'a mut Connection>,
+    ) {
+        for connection in connections {
+            if let Some(groups) = self.group_memberships.get(&connection.id) {
+                for &group_id in groups {
+                    connection.add_to_group(group_id);
+                }
+            }
+        }
+    }
+
+    /// Merges connections that share host, port and protocol
+    ///
+    /// The first occurrence is kept. The groups of later occurrences are
+    /// added to its `additional_group_ids` and their tags to its tags.
+    /// Returns the number of connections merged by this call.
+    pub fn merge_duplicate_connections(&mut self) -> usize {
+        let mut kept: HashMap<(String, u16, ProtocolType), usize> = HashMap::new();
+        let mut connections: Vec<Connection> = Vec::with_capacity(self.connections.len());
+        let mut merged = 0;
+
+        for conn in std::mem::take(&mut self.connections) {
+            let key = (conn.host.to_ascii_lowercase(), conn.port, conn.protocol);
+            let Some(&index) = kept.get(&key) else {
+                kept.insert(key, connections.len());
+                connections.push(conn);
+                continue;
+            };
+
+            merged += 1;
+            let target = &mut connections[index];
+            for group_id in conn.group_id.into_iter().chain(conn.additional_group_ids) {
+                target.add_to_group(group_id);
+            }
+            for tag in conn.tags {
+                if !target.tags.contains(&tag) {
+                    target.tags.push(tag);
+                }
+            }
+        }
+
+        self.connections = connections;
+        self.merged_duplicates += merged;
+        merged
     }
 }
 
@@ -236,3 +301,76 @@ pub trait ImportSource: Send + Sync {
         Ok(combined_result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProtocolConfig;
+
+    fn ssh(name: &str, host: &str, group_id: Option<Uuid>) -> Connection {
+        let mut conn = Connection::new(
+            name.to_string(),
+            host.to_string(),
+            22,
+            ProtocolConfig::Ssh(crate::models::SshConfig::default()),
+        );
+        conn.group_id = group_id;
+        conn
+    }
+
+    #[test]
+    fn test_merge_duplicate_connections() {
+        let web = Uuid::new_v4();
+        let db = Uuid::new_v4();
+        let mut result = ImportResult::new();
+        result.add_connection(ssh("app", "app.example.com", Some(web)));
+        result.add_connection(ssh("other", "other.example.com", Some(web)));
+        let mut dup = ssh("app", "APP.example.com", Some(db));
+        dup.tags.push("db".to_string());
+        result.add_connection(dup);
+        result.add_connection(ssh("app", "app.example.com", Some(db)));
+
+        assert_eq!(result.merge_duplicate_connections(), 2);
+        assert_eq!(result.merged_duplicates, 2);
+        assert_eq!(result.connections.len(), 2);
+
+        let app = &result.connections[0];
+        assert_eq!(app.group_id, Some(web));
+        assert_eq!(app.tags, vec!["db".to_string()]);
+        assert_eq!(app.additional_group_ids, vec![db]);
+        assert!(app.is_in_group(web) && app.is_in_group(db));
+        assert!(result.group_memberships.is_empty());
+        assert!(result.summary().contains("Merged: 2"));
+    }
+
+    #[test]
+    fn test_merge_keeps_different_ports_and_protocols() {
+        let mut result = ImportResult::new();
+        result.add_connection(ssh("a", "host", None));
+        let mut other_port = ssh("b", "host", None);
+        other_port.port = 2222;
+        result.add_connection(other_port);
+
+        assert_eq!(result.merge_duplicate_connections(), 0);
+        assert_eq!(result.connections.len(), 2);
+        assert!(result
+            .connections
+            .iter()
+            .all(|c| c.additional_group_ids.is_empty()));
+    }
+
+    #[test]
+    fn test_apply_group_memberships() {
+        let web = Uuid::new_v4();
+        let db = Uuid::new_v4();
+        let mut connections = vec![ssh("app", "app.example.com", Some(web))];
+        let mut result = ImportResult::new();
+        result
+            .group_memberships
+            .insert(connections[0].id, vec![db, web]);
+
+        result.apply_group_memberships(&mut connections);
+        assert_eq!(connections[0].group_id, Some(web));
+        assert_eq!(connections[0].additional_group_ids, vec![db]);
+    }
+}
@@ -37,6 +37,7 @@ pub mod export;
 pub mod ffi;
 pub mod import;
 pub mod models;
+pub mod notification;
 pub mod password_generator;
 pub mod performance;
 pub mod progress;
@@ -57,75 +58,92 @@ pub mod vnc_client;
 pub mod wol;
 
 pub use automation::{
-    CompiledRule, ConnectionTask, ExpectEngine, ExpectError, ExpectResult, ExpectRule,
-    FolderConnectionTracker, KeyElement, KeySequence, KeySequenceError, KeySequenceResult,
-    SpecialKey, TaskCondition, TaskError, TaskExecutor, TaskResult, TaskTiming,
+    AutomationPack, AutomationPackError, CompiledRule, ConnectionTask, ExpectEngine, ExpectError,
+    ExpectResult, ExpectRule, ExpectTiming, FolderConnectionTracker, KeyElement, KeySequence,
+    KeySequenceError, KeySequenceResult, PromptWaiter, SpecialKey, TaskCondition, TaskError,
+    TaskExecutor, TaskResult, TaskRunner, TaskTiming,
 };
 pub use cluster::{
-    Cluster, ClusterError, ClusterManager, ClusterMemberState, ClusterResult, ClusterSession,
-    ClusterSessionStatus, ClusterSessionSummary,
+    Cluster, ClusterCommandResult, ClusterCommandRunner, ClusterCommandStatus, ClusterError,
+    ClusterManager, ClusterMemberState, ClusterResult, ClusterSession, ClusterSessionStatus,
+    ClusterSessionSummary, SshCommandRunner,
+};
+pub use config::{
+    AppSettings, ConfigManager, ConnectionSettings, CustomProtocolSettings, SecretBackendType,
 };
-pub use config::{AppSettings, ConfigManager, ConnectionSettings, SecretBackendType};
 pub use connection::{
-    check_interning_stats, check_port, check_port_async, get_interning_stats,
+    check_interning_stats, check_port, check_port_async, get_interning_stats, host_for_uri,
     intern_connection_strings, intern_hostname, intern_protocol_name, intern_username,
-    log_interning_stats, log_interning_stats_with_warning, ConnectionManager, LazyGroupLoader,
-    PortCheckError, PortCheckResult, SelectionState, VirtualScrollConfig,
+    jump_host_spec, log_interning_stats, log_interning_stats_with_warning, proxy_jump_arg,
+    resolve_jump_chain, unbracketed_host, validate_host, ConnectionManager, HostError,
+    LazyGroupLoader, PortCheckError, PortCheckResult, PrefetchedChildren, SelectionState,
+    VirtualScrollConfig, DEFAULT_PREFETCH_WINDOW, HIGH_EVICTION_CHURN,
 };
-pub use dashboard::{DashboardFilter, SessionStats};
+pub use dashboard::{prometheus_export, write_prometheus, DashboardFilter, SessionStats};
 pub use document::{
-    Document, DocumentError, DocumentManager, DocumentResult, DOCUMENT_FORMAT_VERSION,
+    Document, DocumentError, DocumentManager, DocumentMatch, DocumentResult,
+    DOCUMENT_FORMAT_VERSION,
 };
 pub use drag_drop::{
-    calculate_drop_position, calculate_indicator_y, calculate_row_index, is_valid_drop_position,
-    DropConfig, DropPosition, ItemType,
+    calculate_drop_position, calculate_indicator_y, calculate_row_index,
+    is_valid_batch_drop_position, is_valid_drop_position, validate_batch_drop, DraggedItem,
+    DropConfig, DropError, DropPosition, ItemType,
 };
 pub use error::{
     ConfigError, ConfigResult, ImportError, ProtocolError, RustConnError, SecretError, SessionError,
 };
 pub use export::{
-    BatchExportCancelHandle, BatchExportResult, BatchExporter, ExportError, ExportFormat,
-    ExportOptions, ExportResult, ExportTarget, NativeExport, NativeImportError,
+    BatchExportCancelHandle, BatchExportCheckpoint, BatchExportResult, BatchExporter, ExportError,
+    ExportFormat, ExportOptions, ExportResult, ExportTarget, MergeConflict, MergeCounts,
+    MergeItemKind, MergeReport, NativeExport, NativeImportError, PasswordMode,
     BATCH_EXPORT_THRESHOLD, DEFAULT_EXPORT_BATCH_SIZE, NATIVE_FILE_EXTENSION,
     NATIVE_FORMAT_VERSION,
 };
 pub use ffi::{
-    ConnectionState, FfiDisplay, FfiError, FfiResult, VncCredentialType, VncDisplay, VncError,
+    encode_bgra_png, ConnectionState, FfiDisplay, FfiError, FfiResult, VncCredentialType,
+    VncDisplay, VncError, VncScalingMode,
 };
 pub use import::{
     AnsibleInventoryImporter, AsbruImporter, BatchCancelHandle, BatchImportResult, BatchImporter,
-    ImportResult, ImportSource, RemminaImporter, RoyalTsImporter, SkippedEntry, SshConfigImporter,
-    BATCH_IMPORT_THRESHOLD, DEFAULT_IMPORT_BATCH_SIZE,
+    ImportItem, ImportResult, ImportSource, ImportStream, RemminaImporter, RoyalTsImporter,
+    SkippedEntry, SshConfigImporter, BATCH_IMPORT_THRESHOLD, DEFAULT_IMPORT_BATCH_SIZE,
+    STREAMING_IMPORT_THRESHOLD,
 };
 pub use models::{
     group_templates_by_protocol, Connection, ConnectionGroup, ConnectionHistoryEntry,
     ConnectionStatistics, ConnectionTemplate, Credentials, CustomProperty, HistorySettings,
     PasswordSource, PropertyType, ProtocolConfig, ProtocolType, RdpConfig, RdpGateway, Resolution,
-    Snippet, SnippetVariable, SpiceConfig, SpiceImageCompression, SshAuthMethod, SshConfig,
-    SshKeySource, TemplateError, VncConfig, WindowGeometry, WindowMode,
+    Snippet, SnippetVariable, SpiceConfig, SpiceImageCompression, SshAlgorithmList,
+    SshAlgorithmMode, SshAlgorithms, SshAuthMethod, SshConfig, SshForward, SshKeySource,
+    TemplateError, TemplateOverrides, VncConfig, WindowGeometry, WindowMode,
 };
+pub use notification::{NoOpNotifier, Notification, NotificationUrgency, Notifier};
 pub use password_generator::{
     estimate_crack_time, CharacterSet, PasswordGenerator, PasswordGeneratorConfig,
     PasswordGeneratorError, PasswordGeneratorResult, PasswordStrength,
 };
 pub use performance::{
     format_bytes, memory_optimizer, metrics, AllocationStats, BatchProcessor, CompactString,
-    Debouncer, InternerStats, LazyInit, MemoryBreakdown, MemoryEstimate, MemoryOptimizer,
-    MemoryPressure, MemorySnapshot, MemoryTracker, ObjectPool, OperationStats,
-    OptimizationCategory, OptimizationRecommendation, PerformanceMetrics, PoolStats, ShrinkableVec,
-    StringInterner, TimingGuard, VirtualScroller,
+    Debouncer, InternerStats, LazyInit, MemoryBreakdown, MemoryDelta, MemoryEstimate,
+    MemoryOptimizer, MemoryPressure, MemorySnapshot, MemoryTracker, ObjectPool, OperationStats,
+    OptimizationCategory, OptimizationRecommendation, PerformanceMetrics, PoolStats, Pooled,
+    ShrinkableVec, StringInterner, TimingGuard, VirtualScroller, DEFAULT_INTERNER_CAPACITY,
+    SNAPSHOT_GROWTH_THRESHOLD,
 };
 pub use progress::{
-    CallbackProgressReporter, CancelHandle, LocalProgressReporter, NoOpProgressReporter,
-    ProgressReporter,
+    CallbackProgressReporter, CancelHandle, CompositeProgressReporter,
+    CompositeProgressReporterBuilder, EstimatingProgressReporter, LocalProgressReporter,
+    NoOpProgressReporter, ProgressReporter, StageProgressReporter,
 };
 pub use protocol::{
     build_freerdp_args, detect_aws_cli, detect_azure_cli, detect_boundary, detect_cloudflared,
-    detect_gcloud_cli, detect_oci_cli, detect_provider, detect_rdp_client, detect_ssh_client,
-    detect_tailscale, detect_teleport, detect_vnc_client, extract_geometry_from_args,
-    get_zero_trust_provider_icon, has_decorations_flag, ClientDetectionResult, ClientInfo,
-    CloudProvider, FreeRdpConfig, Protocol, ProtocolRegistry, ProviderIconCache, RdpProtocol,
-    SshProtocol, VncProtocol,
+    detect_freerdp_client, detect_gcloud_cli, detect_oci_cli, detect_provider,
+    detect_provider_client, detect_rdp_client, detect_ssh_client, detect_tailscale,
+    detect_teleport, detect_vnc_client, extract_geometry_from_args, get_zero_trust_provider_icon,
+    has_decorations_flag, is_wayland_session, refresh_detection, ssh_session_established,
+    ClientDetectionCache, ClientDetectionResult, ClientInfo, CloudProvider, CustomProtocol,
+    FreeRdpConfig, Protocol, ProtocolRegistry, ProviderIconCache, RdpProtocol, SshProtocol,
+    VncProtocol, SSH_ERROR_EXIT_STATUS,
 };
 pub use rdp_client::{
     convert_to_bgra, create_frame_update, create_frame_update_with_conversion,
@@ -150,55 +168,63 @@ pub use rdp_client::{
         SCANCODE_DELETE,
         STANDARD_RESOLUTIONS,
     },
-    is_embedded_rdp_available, keyval_to_unicode, ClipboardFormatInfo, PixelFormat,
-    RdpClientCommand, RdpClientConfig, RdpClientError, RdpClientEvent, RdpRect,
-    RdpSecurityProtocol,
+    is_embedded_rdp_available, keyval_to_unicode, ClipboardFormatInfo, ClipboardImage,
+    ClipboardTransferDirection, PixelFormat, RdpClientCommand, RdpClientConfig, RdpClientError,
+    RdpClientEvent, RdpRect, RdpSecurityProtocol,
 };
 #[cfg(feature = "rdp-embedded")]
 pub use rdp_client::{AudioFormatInfo, RdpClient, RdpCommandSender, RdpEventReceiver};
 pub use search::{
-    benchmark, cache::SearchCache, ConnectionSearchResult, DebouncedSearchEngine, MatchHighlight,
-    SearchEngine, SearchError, SearchFilter, SearchQuery, SearchResult,
+    benchmark, cache::SearchCache, ConnectionSearchResult, ConnectionStatus, DebouncedSearchEngine,
+    MatchHighlight, SearchEngine, SearchError, SearchFilter, SearchIndex, SearchQuery,
+    SearchResult, UsageSort,
 };
 pub use secret::{
     parse_keepassxc_version, resolve_with_callback, spawn_credential_resolution,
-    AsyncCredentialResolver, AsyncCredentialResult, CancellationToken, CredentialResolver,
-    CredentialStatus, CredentialVerificationManager, DialogPreFillData, GroupCreationResult,
-    KdbxExporter, KeePassHierarchy, KeePassStatus, KeePassXcBackend, LibSecretBackend,
-    PendingCredentialResolution, SecretBackend, SecretManager, VerifiedCredentials,
-    KEEPASS_ROOT_GROUP,
+    AsyncCredentialResolver, AsyncCredentialResult, CancellationToken, CredentialLookup,
+    CredentialResolver, CredentialStatus, CredentialVerificationManager, DialogPreFillData,
+    GroupCreationResult, KdbxExporter, KeePassHierarchy, KeePassStatus, KeePassXcBackend,
+    LibSecretBackend, PassBackend, PendingCredentialResolution, SecretBackend, SecretBackendEvent,
+    SecretEntryRef, SecretManager, VerifiedCredentials, KEEPASS_ROOT_GROUP,
 };
 pub use session::{
-    LogConfig, LogContext, LogError, LogResult, Session, SessionLogger, SessionManager,
-    SessionState, SessionType,
+    LogConfig, LogContext, LogDirection, LogError, LogFormat, LogResult, SecretWindow, Session,
+    SessionLogger, SessionManager, SessionState, SessionType,
 };
 pub use snippet::SnippetManager;
 pub use spice_client::{
     build_spice_viewer_args, detect_spice_viewer, is_embedded_spice_available, launch_spice_viewer,
-    SpiceClientCommand, SpiceClientConfig, SpiceClientError, SpiceClientEvent, SpiceCompression,
-    SpiceRect, SpiceSecurityProtocol, SpiceSharedFolder, SpiceViewerLaunchResult,
+    SpiceCertDecision, SpiceCertificateMismatch, SpiceClientCommand, SpiceClientConfig,
+    SpiceClientError, SpiceClientEvent, SpiceCompression, SpiceRect, SpiceSecurityProtocol,
+    SpiceSharedFolder, SpiceUsbDevice, SpiceUsbDeviceId, SpiceViewerLaunchResult,
 };
 #[cfg(feature = "spice-embedded")]
 pub use spice_client::{SpiceClient, SpiceClientState, SpiceCommandSender, SpiceEventReceiver};
 // Split view types (tab-scoped layouts)
 pub use split::SplitDirection;
 pub use split::{
-    ColorId, ColorPool, DropResult, LeafPanel, PanelId, PanelNode, SessionId as SplitSessionId,
-    SplitError, SplitLayoutModel, SplitNode, TabId, SPLIT_COLORS,
+    ColorId, ColorPool, DropResult, LayoutNode, LayoutRegistry, LeafPanel, NamedLayout,
+    PaneRestore, PanelId, PanelNode, RestoredLayout, SessionId as SplitSessionId, SplitError,
+    SplitLayoutModel, SplitNode, TabId, SPLIT_COLORS,
 };
 pub use ssh_agent::{
-    parse_agent_output, parse_key_list, AgentError, AgentKey, AgentResult, AgentStatus,
-    SshAgentManager,
+    agent_key_dir, build_agent_identity_args, is_known_key_type, parse_agent_output,
+    parse_key_list, write_agent_public_key, AddKeyOptions, AgentError, AgentKey, AgentResult,
+    AgentStatus, SshAgentManager,
 };
 pub use testing::{
     ConnectionTester, TestError, TestResult, TestSummary, DEFAULT_CONCURRENCY,
     DEFAULT_TEST_TIMEOUT_SECS,
 };
 pub use tracing::{
-    field_names, get_tracing_config, init_tracing, is_tracing_initialized, span_names,
+    field_names, get_recent_events, get_tracing_config, init_tracing, is_tracing_initialized,
+    ring_buffer, set_tracing_level, span_names, EventRingBuffer, RecordedEvent, RingBufferLayer,
     TracingConfig, TracingError, TracingLevel, TracingOutput, TracingResult,
+    DEFAULT_RING_BUFFER_CAPACITY,
+};
+pub use variables::{
+    Variable, VariableError, VariableManager, VariableResult, VariableScope, VariableType,
 };
-pub use variables::{Variable, VariableError, VariableManager, VariableResult, VariableScope};
 pub use vnc_client::is_embedded_vnc_available;
 #[cfg(feature = "vnc-embedded")]
 pub use vnc_client::{
@@ -206,6 +232,8 @@ pub use vnc_client::{
     VncEventReceiver, VncRect,
 };
 pub use wol::{
-    generate_magic_packet, send_magic_packet, send_wol, MacAddress, WolConfig, WolError, WolResult,
-    DEFAULT_BROADCAST_ADDRESS, DEFAULT_WOL_PORT, DEFAULT_WOL_WAIT_SECONDS, MAGIC_PACKET_SIZE,
+    generate_magic_packet, local_broadcast_addresses, send_magic_packet, send_wol,
+    send_wol_all_interfaces, InterfaceBroadcast, MacAddress, SecureOnPassword, WolConfig, WolError,
+    WolResult, DEFAULT_BROADCAST_ADDRESS, DEFAULT_WOL_PORT, DEFAULT_WOL_WAIT_SECONDS,
+    MAGIC_PACKET_SIZE, SECURE_ON_PASSWORD_SIZE,
 };
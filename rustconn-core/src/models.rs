@@ -17,14 +17,18 @@ pub use credentials::Credentials;
 pub use custom_property::{CustomProperty, PropertyType};
 pub use group::ConnectionGroup;
 pub use history::{ConnectionHistoryEntry, ConnectionStatistics, HistorySettings};
+pub use protocol::is_valid_env_name;
 pub use protocol::ProtocolType;
 pub use protocol::{
     AwsSsmConfig, AzureBastionConfig, AzureSshConfig, BoundaryConfig, CloudflareAccessConfig,
-    GcpIapConfig, GenericZeroTrustConfig, OciBastionConfig, ProtocolConfig, RdpClientMode,
-    RdpConfig, RdpGateway, RdpPerformanceMode, Resolution, SharedFolder, SpiceConfig,
-    SpiceImageCompression, SshAuthMethod, SshConfig, SshKeySource, TailscaleSshConfig,
-    TeleportConfig, VncClientMode, VncConfig, VncPerformanceMode, ZeroTrustConfig,
-    ZeroTrustProvider, ZeroTrustProviderConfig,
+    GcpIapConfig, GenericProxyConfig, GenericZeroTrustConfig, OciBastionConfig, ProtocolConfig,
+    RdpClientMode, RdpConfig, RdpGateway, RdpPerformanceMode, Resolution, SharedFolder,
+    SpiceConfig, SpiceImageCompression, SshAlgorithmList, SshAlgorithmMode, SshAlgorithms,
+    SshAuthMethod, SshConfig, SshForward, SshKeySource, TailscaleSshConfig, TeleportConfig,
+    VncClientMode, VncConfig, VncPerformanceMode, ZeroTrustConfig, ZeroTrustProvider,
+    ZeroTrustProviderConfig,
 };
 pub use snippet::{Snippet, SnippetVariable};
-pub use template::{group_templates_by_protocol, ConnectionTemplate, TemplateError};
+pub use template::{
+    group_templates_by_protocol, ConnectionTemplate, TemplateError, TemplateOverrides,
+};
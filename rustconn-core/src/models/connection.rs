@@ -159,6 +159,9 @@ pub struct Connection {
     /// Group this connection belongs to
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group_id: Option<Uuid>,
+    /// Further groups the connection is listed in besides `group_id`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_group_ids: Vec<Uuid>,
     /// Tags for organization and filtering
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
@@ -174,9 +177,16 @@ pub struct Connection {
     /// Sort order for manual ordering (lower values appear first)
     #[serde(default)]
     pub sort_order: i32,
+    /// Launch order when opening every connection in a group at once
+    /// (lower values launch first)
+    #[serde(default)]
+    pub launch_order: i32,
     /// Timestamp when the connection was last used
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_connected: Option<DateTime<Utc>>,
+    /// Number of successful connections made with this connection
+    #[serde(default)]
+    pub connect_count: u64,
     /// Source of password for this connection
     #[serde(default)]
     pub password_source: PasswordSource,
@@ -216,6 +226,18 @@ pub struct Connection {
     /// Skip pre-connect port check for this connection (overrides global setting)
     #[serde(default)]
     pub skip_port_check: bool,
+    /// Custom color tag as a hex string (e.g. `#3584e4`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Custom icon name shown instead of the protocol icon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Whether the connection is archived
+    ///
+    /// Archived connections are kept but hidden from default listings,
+    /// search results, batch tests and filter-built clusters.
+    #[serde(default)]
+    pub archived: bool,
 }
 
 impl Connection {
@@ -232,12 +254,15 @@ impl Connection {
             port,
             username: None,
             group_id: None,
+            additional_group_ids: Vec::new(),
             tags: Vec::new(),
             created_at: now,
             updated_at: now,
             protocol_config,
             sort_order: 0,
+            launch_order: 0,
             last_connected: None,
+            connect_count: 0,
             password_source: PasswordSource::None,
             domain: None,
             custom_properties: Vec::new(),
@@ -252,6 +277,9 @@ impl Connection {
             remember_window_position: false,
             window_geometry: None,
             skip_port_check: false,
+            color: None,
+            icon: None,
+            archived: false,
         }
     }
 
@@ -313,6 +341,44 @@ impl Connection {
         self
     }
 
+    /// Returns whether the connection is listed in a group
+    ///
+    /// Matches both the primary group and the additional groups.
+    #[must_use]
+    pub fn is_in_group(&self, group_id: Uuid) -> bool {
+        self.group_id == Some(group_id) || self.additional_group_ids.contains(&group_id)
+    }
+
+    /// Lists the connection in a further group
+    ///
+    /// Does nothing if the connection is already in that group.
+    pub fn add_to_group(&mut self, group_id: Uuid) {
+        if self.group_id.is_none() {
+            self.group_id = Some(group_id);
+        } else if !self.is_in_group(group_id) {
+            self.additional_group_ids.push(group_id);
+        }
+    }
+
+    /// Removes the connection from a group
+    ///
+    /// If that was the primary group, the first additional group takes its
+    /// place. Returns whether the connection was in the group.
+    pub fn remove_from_group(&mut self, group_id: Uuid) -> bool {
+        if !self.is_in_group(group_id) {
+            return false;
+        }
+        self.additional_group_ids.retain(|&gid| gid != group_id);
+        if self.group_id == Some(group_id) {
+            self.group_id = if self.additional_group_ids.is_empty() {
+                None
+            } else {
+                Some(self.additional_group_ids.remove(0))
+            };
+        }
+        true
+    }
+
     /// Adds tags to this connection
     #[must_use]
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
@@ -327,6 +393,20 @@ impl Connection {
         self
     }
 
+    /// Sets the color tag for this connection (hex, e.g. `#3584e4`)
+    #[must_use]
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets a custom icon name for this connection
+    #[must_use]
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
     /// Updates the `updated_at` timestamp to now
     pub fn touch(&mut self) {
         self.updated_at = Utc::now();
@@ -152,6 +152,397 @@ pub struct SshConfig {
     /// ID of another connection to use as a Jump Host
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jump_host_id: Option<uuid::Uuid>,
+    /// Port forwarding tunnels opened with the connection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forwards: Vec<SshForward>,
+    /// Environment variables for the session, in order (`SetEnv`)
+    ///
+    /// They are set in the environment of the local `ssh` process and sent
+    /// to the server with `SendEnv`, which must accept them (`AcceptEnv`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub environment: Vec<(String, String)>,
+    /// Cipher, MAC and key exchange algorithm preferences
+    #[serde(default, skip_serializing_if = "SshAlgorithms::is_empty")]
+    pub algorithms: SshAlgorithms,
+}
+
+/// How an algorithm list combines with the OpenSSH defaults
+///
+/// Corresponds to the optional prefix of the `ssh_config` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SshAlgorithmMode {
+    /// Use exactly the listed algorithms (no prefix)
+    #[default]
+    Replace,
+    /// Append the algorithms to the defaults (`+`)
+    Append,
+    /// Remove the algorithms from the defaults (`-`)
+    Remove,
+    /// Move the algorithms to the head of the defaults (`^`)
+    Prepend,
+}
+
+impl SshAlgorithmMode {
+    /// Returns the `ssh_config` value prefix
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Replace => "",
+            Self::Append => "+",
+            Self::Remove => "-",
+            Self::Prepend => "^",
+        }
+    }
+}
+
+/// An ordered list of SSH algorithms, e.g. for `Ciphers`
+///
+/// Serialized as the `ssh_config` value, e.g. `"+aes128-cbc,3des-cbc"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct SshAlgorithmList {
+    /// How the list combines with the defaults
+    pub mode: SshAlgorithmMode,
+    /// Algorithm names in order of preference
+    pub algorithms: Vec<String>,
+}
+
+impl SshAlgorithmList {
+    /// Creates a list from algorithm names
+    #[must_use]
+    pub fn new(mode: SshAlgorithmMode, algorithms: Vec<String>) -> Self {
+        Self { mode, algorithms }
+    }
+
+    /// Parses an `ssh_config` value such as `+aes128-cbc,3des-cbc`
+    ///
+    /// Names are trimmed and empty entries dropped; names are not checked
+    /// (see [`Self::suspicious_names`]).
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        let (mode, rest) = match value.chars().next() {
+            Some('+') => (SshAlgorithmMode::Append, &value[1..]),
+            Some('-') => (SshAlgorithmMode::Remove, &value[1..]),
+            Some('^') => (SshAlgorithmMode::Prepend, &value[1..]),
+            _ => (SshAlgorithmMode::Replace, value),
+        };
+        let algorithms = rest
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        Self { mode, algorithms }
+    }
+
+    /// Returns the `ssh_config` value, e.g. `+aes128-cbc,3des-cbc`
+    #[must_use]
+    pub fn to_value(&self) -> String {
+        format!("{}{}", self.mode.prefix(), self.algorithms.join(","))
+    }
+
+    /// Returns true if no algorithms are listed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.algorithms.is_empty()
+    }
+
+    /// Returns the names that do not look like algorithm names
+    ///
+    /// Only obviously malformed names are reported: the check accepts any
+    /// ASCII letters, digits and `-._@+`, since the supported algorithms
+    /// differ between OpenSSH versions.
+    pub fn suspicious_names(&self) -> impl Iterator<Item = &str> {
+        self.algorithms.iter().map(String::as_str).filter(|name| {
+            name.starts_with(['+', '-', '^'])
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '@' | '+'))
+        })
+    }
+}
+
+impl From<String> for SshAlgorithmList {
+    fn from(value: String) -> Self {
+        Self::parse(&value)
+    }
+}
+
+impl From<SshAlgorithmList> for String {
+    fn from(list: SshAlgorithmList) -> Self {
+        list.to_value()
+    }
+}
+
+/// Cipher, MAC and key exchange algorithm preferences of an SSH connection
+///
+/// Empty lists leave the OpenSSH defaults in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshAlgorithms {
+    /// Symmetric ciphers (`Ciphers`)
+    #[serde(default, skip_serializing_if = "SshAlgorithmList::is_empty")]
+    pub ciphers: SshAlgorithmList,
+    /// Message authentication codes (`MACs`)
+    #[serde(default, skip_serializing_if = "SshAlgorithmList::is_empty")]
+    pub macs: SshAlgorithmList,
+    /// Key exchange algorithms (`KexAlgorithms`)
+    #[serde(default, skip_serializing_if = "SshAlgorithmList::is_empty")]
+    pub kex_algorithms: SshAlgorithmList,
+}
+
+impl SshAlgorithms {
+    /// The `ssh_config` keywords of the lists, in the order they are emitted
+    pub const KEYWORDS: [&'static str; 3] = ["Ciphers", "MACs", "KexAlgorithms"];
+
+    /// Returns true if no list has algorithms
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ciphers.is_empty() && self.macs.is_empty() && self.kex_algorithms.is_empty()
+    }
+
+    /// Returns the list for an `ssh_config` keyword, matched case-insensitively
+    #[must_use]
+    pub fn get_mut(&mut self, keyword: &str) -> Option<&mut SshAlgorithmList> {
+        if keyword.eq_ignore_ascii_case("Ciphers") {
+            Some(&mut self.ciphers)
+        } else if keyword.eq_ignore_ascii_case("MACs") {
+            Some(&mut self.macs)
+        } else if keyword.eq_ignore_ascii_case("KexAlgorithms") {
+            Some(&mut self.kex_algorithms)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the non-empty lists with their `ssh_config` keywords
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &SshAlgorithmList)> {
+        Self::KEYWORDS
+            .into_iter()
+            .zip([&self.ciphers, &self.macs, &self.kex_algorithms])
+            .filter(|(_, list)| !list.is_empty())
+    }
+
+    /// Builds the `-o Keyword=value` arguments for the non-empty lists
+    #[must_use]
+    pub fn command_args(&self) -> Vec<String> {
+        self.iter()
+            .flat_map(|(keyword, list)| {
+                ["-o".to_string(), format!("{keyword}={}", list.to_value())]
+            })
+            .collect()
+    }
+
+    /// Returns a warning for each name that does not look like an algorithm
+    ///
+    /// These are warnings only; OpenSSH reports names it does not support
+    /// when connecting.
+    #[must_use]
+    pub fn warnings(&self) -> Vec<String> {
+        self.iter()
+            .flat_map(|(keyword, list)| {
+                list.suspicious_names().map(move |name| {
+                    format!("{keyword}: '{name}' does not look like an algorithm name")
+                })
+            })
+            .collect()
+    }
+}
+
+/// An SSH port forwarding tunnel
+///
+/// The bind address is optional; when unset, SSH uses its default
+/// (`localhost` unless `GatewayPorts` is enabled).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SshForward {
+    /// Local forwarding (`-L`): a local port connects to `host:port` via the server
+    Local {
+        /// Local address to listen on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bind_address: Option<String>,
+        /// Local port to listen on
+        bind_port: u16,
+        /// Destination host, resolved by the server
+        host: String,
+        /// Destination port
+        port: u16,
+    },
+    /// Remote forwarding (`-R`): a server port connects to `host:port` via the client
+    Remote {
+        /// Server address to listen on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bind_address: Option<String>,
+        /// Server port to listen on
+        bind_port: u16,
+        /// Destination host, resolved by the client
+        host: String,
+        /// Destination port
+        port: u16,
+    },
+    /// Dynamic forwarding (`-D`): a local SOCKS proxy
+    Dynamic {
+        /// Local address to listen on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bind_address: Option<String>,
+        /// Local port to listen on
+        bind_port: u16,
+    },
+}
+
+impl SshForward {
+    /// Returns the SSH command-line flag (`-L`, `-R` or `-D`)
+    #[must_use]
+    pub const fn flag(&self) -> &'static str {
+        match self {
+            Self::Local { .. } => "-L",
+            Self::Remote { .. } => "-R",
+            Self::Dynamic { .. } => "-D",
+        }
+    }
+
+    /// Returns the `ssh_config` keyword (`LocalForward`, `RemoteForward` or `DynamicForward`)
+    #[must_use]
+    pub const fn config_keyword(&self) -> &'static str {
+        match self {
+            Self::Local { .. } => "LocalForward",
+            Self::Remote { .. } => "RemoteForward",
+            Self::Dynamic { .. } => "DynamicForward",
+        }
+    }
+
+    /// Returns the command-line argument, e.g. `-L 8080:db.internal:5432`
+    #[must_use]
+    pub fn command_args(&self) -> [String; 2] {
+        let spec = match self {
+            Self::Local {
+                bind_address,
+                bind_port,
+                host,
+                port,
+            }
+            | Self::Remote {
+                bind_address,
+                bind_port,
+                host,
+                port,
+            } => format!(
+                "{}:{}:{port}",
+                format_bind(bind_address.as_deref(), *bind_port),
+                bracket_ipv6(host)
+            ),
+            Self::Dynamic {
+                bind_address,
+                bind_port,
+            } => format_bind(bind_address.as_deref(), *bind_port),
+        };
+        [self.flag().to_string(), spec]
+    }
+
+    /// Returns the `ssh_config` value, e.g. `8080 db.internal:5432`
+    #[must_use]
+    pub fn config_value(&self) -> String {
+        match self {
+            Self::Local {
+                bind_address,
+                bind_port,
+                host,
+                port,
+            }
+            | Self::Remote {
+                bind_address,
+                bind_port,
+                host,
+                port,
+            } => format!(
+                "{} {}:{port}",
+                format_bind(bind_address.as_deref(), *bind_port),
+                bracket_ipv6(host)
+            ),
+            Self::Dynamic {
+                bind_address,
+                bind_port,
+            } => format_bind(bind_address.as_deref(), *bind_port),
+        }
+    }
+
+    /// Parses an `ssh_config` forwarding line
+    ///
+    /// `keyword` is matched case-insensitively. Returns `None` if the keyword
+    /// is not a forwarding keyword or the value is malformed. Unix socket
+    /// forwards are not supported.
+    #[must_use]
+    pub fn from_config(keyword: &str, value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let (bind_address, bind_port) = parse_bind(parts.next()?)?;
+
+        if keyword.eq_ignore_ascii_case("DynamicForward") {
+            return parts.next().is_none().then_some(Self::Dynamic {
+                bind_address,
+                bind_port,
+            });
+        }
+
+        let (host, port) = split_host_port(parts.next()?)?;
+        if parts.next().is_some() {
+            return None;
+        }
+        let host = host.to_string();
+        let port = port.parse().ok()?;
+        if keyword.eq_ignore_ascii_case("LocalForward") {
+            Some(Self::Local {
+                bind_address,
+                bind_port,
+                host,
+                port,
+            })
+        } else if keyword.eq_ignore_ascii_case("RemoteForward") {
+            Some(Self::Remote {
+                bind_address,
+                bind_port,
+                host,
+                port,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Wraps IPv6 addresses in brackets so the port separator stays unambiguous
+fn bracket_ipv6(address: &str) -> String {
+    if address.contains(':') {
+        format!("[{address}]")
+    } else {
+        address.to_string()
+    }
+}
+
+/// Formats `[address:]port`
+fn format_bind(address: Option<&str>, port: u16) -> String {
+    address.map_or_else(
+        || port.to_string(),
+        |address| format!("{}:{port}", bracket_ipv6(address)),
+    )
+}
+
+/// Splits `host:port`, accepting a bracketed IPv6 host
+fn split_host_port(value: &str) -> Option<(&str, &str)> {
+    let (host, port) = value.rsplit_once(':')?;
+    let host = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+    (!host.is_empty()).then_some((host, port))
+}
+
+/// Parses `[address:]port`
+fn parse_bind(value: &str) -> Option<(Option<String>, u16)> {
+    match split_host_port(value) {
+        Some((address, port)) => Some((Some(address.to_string()), port.parse().ok()?)),
+        None => Some((None, value.parse().ok()?)),
+    }
 }
 
 impl SshConfig {
@@ -267,15 +658,78 @@ impl SshConfig {
             args.push("-C".to_string());
         }
 
+        // Add port forwarding tunnels
+        for forward in &self.forwards {
+            args.extend(forward.command_args());
+        }
+
+        // Add algorithm preferences before custom options, so they win over
+        // a stale `Ciphers` etc. in the custom options (ssh uses the first value)
+        args.extend(self.algorithms.command_args());
+
         // Add custom options
         for (key, value) in &self.custom_options {
             args.push("-o".to_string());
             args.push(format!("{key}={value}"));
         }
 
+        args.extend(self.send_env_args());
+
         args
     }
 
+    /// Builds the `-o SendEnv=NAME` arguments for the session environment
+    ///
+    /// Values are not part of the arguments; they are passed in the
+    /// environment of the `ssh` process (see [`Self::environment_strings`]).
+    #[must_use]
+    pub fn send_env_args(&self) -> Vec<String> {
+        self.environment
+            .iter()
+            .flat_map(|(name, _)| ["-o".to_string(), format!("SendEnv={name}")])
+            .collect()
+    }
+
+    /// Returns the session environment as `NAME=value` strings
+    #[must_use]
+    pub fn environment_strings(&self) -> Vec<String> {
+        self.environment
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect()
+    }
+
+    /// Returns the first environment variable name that is not a valid
+    /// POSIX name
+    #[must_use]
+    pub fn invalid_environment_name(&self) -> Option<&str> {
+        self.environment
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .find(|name| !is_valid_env_name(name))
+    }
+
+    /// Returns the agent key fingerprint to restrict authentication to
+    ///
+    /// An explicit `override_fingerprint` (e.g. from `connect --agent-key`)
+    /// wins over the stored `agent_key_fingerprint` preference, which in turn
+    /// wins over the fingerprint of an agent `key_source`.
+    #[must_use]
+    pub fn effective_agent_fingerprint<'a>(
+        &'a self,
+        override_fingerprint: Option<&'a str>,
+    ) -> Option<&'a str> {
+        override_fingerprint
+            .or(self.agent_key_fingerprint.as_deref())
+            .or(match &self.key_source {
+                SshKeySource::Agent { fingerprint, .. } if !fingerprint.is_empty() => {
+                    Some(fingerprint.as_str())
+                }
+                _ => None,
+            })
+            .filter(|fingerprint| !fingerprint.is_empty())
+    }
+
     /// Checks if this SSH config uses File authentication method
     ///
     /// Returns true if `key_source` is `SshKeySource::File` with a non-empty path.
@@ -314,6 +768,19 @@ pub enum SshKeySource {
     Default,
 }
 
+/// Checks that `name` is a POSIX environment variable name
+///
+/// Names consist of ASCII letters, digits and underscores and do not start
+/// with a digit.
+#[must_use]
+pub fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 /// Helper function for serde to skip serializing default key source
 const fn is_default_key_source(source: &SshKeySource) -> bool {
     matches!(source, SshKeySource::Default)
@@ -356,6 +823,9 @@ pub struct SharedFolder {
     pub local_path: PathBuf,
     /// Share name visible in the remote session
     pub share_name: String,
+    /// Expose the folder to the remote session as read-only
+    #[serde(default)]
+    pub read_only: bool,
 }
 
 const fn default_gateway_port() -> u16 {
@@ -487,6 +957,9 @@ pub struct RdpConfig {
     /// Enable audio redirection
     #[serde(default)]
     pub audio_redirect: bool,
+    /// Redirect the local microphone to the remote session
+    #[serde(default)]
+    pub microphone_redirect: bool,
     /// RDP gateway configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gateway: Option<RdpGateway>,
@@ -725,6 +1198,9 @@ pub struct SpiceConfig {
     /// Skip certificate verification (insecure)
     #[serde(default)]
     pub skip_cert_verify: bool,
+    /// SHA-256 fingerprint the server certificate is pinned to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_fingerprint: Option<String>,
     /// Enable USB redirection
     #[serde(default)]
     pub usb_redirection: bool,
@@ -745,6 +1221,7 @@ impl Default for SpiceConfig {
             tls_enabled: false,
             ca_cert_path: None,
             skip_cert_verify: false,
+            cert_fingerprint: None,
             usb_redirection: false,
             shared_folders: Vec::new(),
             clipboard_enabled: true, // Clipboard enabled by default
@@ -778,6 +1255,8 @@ pub enum ZeroTrustProvider {
     Boundary,
     /// Generic custom command
     Generic,
+    /// SSH through a user-supplied `ProxyCommand`
+    GenericProxy,
 }
 
 impl ZeroTrustProvider {
@@ -795,6 +1274,7 @@ impl ZeroTrustProvider {
             Self::TailscaleSsh => "Tailscale SSH",
             Self::Boundary => "HashiCorp Boundary",
             Self::Generic => "Generic Command",
+            Self::GenericProxy => "SSH via ProxyCommand",
         }
     }
 
@@ -817,6 +1297,7 @@ impl ZeroTrustProvider {
             Self::TailscaleSsh => "network-vpn-symbolic", // Tailscale - VPN
             Self::Boundary => "dialog-password-symbolic", // Boundary - password/lock
             Self::Generic => "system-run-symbolic",       // Generic - run command
+            Self::GenericProxy => "network-wired-symbolic", // ProxyCommand - wired link
         }
     }
 
@@ -833,6 +1314,7 @@ impl ZeroTrustProvider {
             Self::TailscaleSsh => "tailscale",
             Self::Boundary => "boundary",
             Self::Generic => "",
+            Self::GenericProxy => "ssh",
         }
     }
 
@@ -850,6 +1332,7 @@ impl ZeroTrustProvider {
             Self::TailscaleSsh,
             Self::Boundary,
             Self::Generic,
+            Self::GenericProxy,
         ]
     }
 }
@@ -1023,6 +1506,16 @@ impl ZeroTrustConfig {
                 let a = vec!["-c".to_string(), cmd];
                 ("sh".to_string(), a)
             }
+            ZeroTrustProviderConfig::GenericProxy(cfg) => {
+                let mut a = vec!["-o".to_string(), cfg.proxy_command_option()];
+                if cfg.port != 22 {
+                    a.push("-p".to_string());
+                    a.push(cfg.port.to_string());
+                }
+                let user = cfg.username.as_deref().or(username);
+                a.push(user.map_or_else(|| cfg.host.clone(), |u| format!("{u}@{}", cfg.host)));
+                ("ssh".to_string(), a)
+            }
         };
 
         // Append custom args
@@ -1056,6 +1549,8 @@ pub enum ZeroTrustProviderConfig {
     Boundary(BoundaryConfig),
     /// Generic custom command configuration
     Generic(GenericZeroTrustConfig),
+    /// SSH over a custom `ProxyCommand` configuration
+    GenericProxy(GenericProxyConfig),
 }
 
 /// AWS Systems Manager Session Manager configuration
@@ -1183,6 +1678,78 @@ pub struct GenericZeroTrustConfig {
     pub command_template: String,
 }
 
+/// SSH over a user-supplied `ProxyCommand` (e.g. a self-hosted IAP)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenericProxyConfig {
+    /// Target host as seen by the proxy
+    pub host: String,
+    /// Target SSH port (default: 22)
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    /// SSH username
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Proxy command; `{host}` and `{port}` expand to the target
+    pub proxy_command: String,
+}
+
+const fn default_ssh_port() -> u16 {
+    22
+}
+
+impl Default for GenericProxyConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: default_ssh_port(),
+            username: None,
+            proxy_command: String::new(),
+        }
+    }
+}
+
+impl GenericProxyConfig {
+    /// Creates a configuration from a `host` or `host:port` target
+    #[must_use]
+    pub fn from_target(target: &str, proxy_command: impl Into<String>) -> Self {
+        let target = target.trim();
+        let (host, port) = match target.rsplit_once(':') {
+            Some((host, port)) => match port.parse() {
+                Ok(port) => (host, port),
+                Err(_) => (target, default_ssh_port()),
+            },
+            None => (target, default_ssh_port()),
+        };
+        Self {
+            host: host.to_string(),
+            port,
+            username: None,
+            proxy_command: proxy_command.into(),
+        }
+    }
+
+    /// Returns the target as `host`, or `host:port` for a non-default port
+    #[must_use]
+    pub fn target(&self) -> String {
+        if self.port == default_ssh_port() {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    /// Returns the `ProxyCommand=...` option with placeholders expanded
+    #[must_use]
+    #[allow(clippy::literal_string_with_formatting_args)] // Template placeholders, not format args
+    pub fn proxy_command_option(&self) -> String {
+        let command = self
+            .proxy_command
+            .replace("{host}", &self.host)
+            .replace("{port}", &self.port.to_string());
+        format!("ProxyCommand={command}")
+    }
+}
+
 #[cfg(test)]
 mod zerotrust_tests {
     use super::*;
@@ -1293,6 +1860,46 @@ mod zerotrust_tests {
         assert!(args[1].contains("ProxyCommand"));
     }
 
+    #[test]
+    fn test_generic_proxy_build_command() {
+        let config = ZeroTrustConfig {
+            provider: ZeroTrustProvider::GenericProxy,
+            provider_config: ZeroTrustProviderConfig::GenericProxy(GenericProxyConfig {
+                host: "db.internal".to_string(),
+                port: 2222,
+                username: None,
+                proxy_command: "iap-tunnel --target {host}:{port}".to_string(),
+            }),
+            custom_args: vec![],
+            detected_provider: None,
+        };
+
+        let (program, args) = config.build_command(Some("admin"));
+        assert_eq!(program, "ssh");
+        assert_eq!(
+            args,
+            vec![
+                "-o",
+                "ProxyCommand=iap-tunnel --target db.internal:2222",
+                "-p",
+                "2222",
+                "admin@db.internal"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generic_proxy_target_round_trip() {
+        let config = GenericProxyConfig::from_target("bastion.internal:2200", "nc {host} {port}");
+        assert_eq!(config.host, "bastion.internal");
+        assert_eq!(config.port, 2200);
+        assert_eq!(config.target(), "bastion.internal:2200");
+
+        let config = GenericProxyConfig::from_target("bastion.internal", "nc {host} {port}");
+        assert_eq!(config.port, 22);
+        assert_eq!(config.target(), "bastion.internal");
+    }
+
     #[test]
     fn test_custom_args_appended() {
         let config = ZeroTrustConfig {
@@ -1348,3 +1955,162 @@ mod zerotrust_tests {
         assert_eq!(ZeroTrustProvider::Generic.cli_command(), "");
     }
 }
+
+#[cfg(test)]
+mod ssh_forward_tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_command_args() {
+        let config = SshConfig {
+            forwards: vec![
+                SshForward::Local {
+                    bind_address: None,
+                    bind_port: 8080,
+                    host: "db.internal".to_string(),
+                    port: 5432,
+                },
+                SshForward::Remote {
+                    bind_address: Some("0.0.0.0".to_string()),
+                    bind_port: 9000,
+                    host: "::1".to_string(),
+                    port: 3000,
+                },
+                SshForward::Dynamic {
+                    bind_address: Some("127.0.0.1".to_string()),
+                    bind_port: 1080,
+                },
+            ],
+            ..SshConfig::default()
+        };
+
+        assert_eq!(
+            config.build_command_args(),
+            vec![
+                "-L",
+                "8080:db.internal:5432",
+                "-R",
+                "0.0.0.0:9000:[::1]:3000",
+                "-D",
+                "127.0.0.1:1080"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forward_config_round_trip() {
+        let cases = [
+            ("LocalForward", "8080 db.internal:5432"),
+            ("RemoteForward", "[::1]:9000 localhost:3000"),
+            ("DynamicForward", "localhost:1080"),
+        ];
+        for (keyword, value) in cases {
+            let forward = SshForward::from_config(keyword, value).unwrap();
+            assert_eq!(forward.config_keyword(), keyword);
+            assert_eq!(forward.config_value(), value);
+        }
+
+        assert_eq!(
+            SshForward::from_config("localforward", "8080 db:5432"),
+            Some(SshForward::Local {
+                bind_address: None,
+                bind_port: 8080,
+                host: "db".to_string(),
+                port: 5432,
+            })
+        );
+        assert!(SshForward::from_config("LocalForward", "8080").is_none());
+        assert!(SshForward::from_config("LocalForward", "/tmp/sock /run/sock").is_none());
+        assert!(SshForward::from_config("DynamicForward", "1080 extra").is_none());
+        assert!(SshForward::from_config("ServerAliveInterval", "60").is_none());
+    }
+
+    #[test]
+    fn test_environment_args() {
+        let config = SshConfig {
+            environment: vec![
+                ("LANG".to_string(), "C.UTF-8".to_string()),
+                ("APP_ENV".to_string(), "a b=c".to_string()),
+            ],
+            ..SshConfig::default()
+        };
+
+        assert_eq!(
+            config.build_command_args(),
+            vec!["-o", "SendEnv=LANG", "-o", "SendEnv=APP_ENV"]
+        );
+        assert_eq!(
+            config.environment_strings(),
+            vec!["LANG=C.UTF-8", "APP_ENV=a b=c"]
+        );
+        assert_eq!(config.invalid_environment_name(), None);
+    }
+
+    #[test]
+    fn test_algorithm_list_parse_and_args() {
+        let list = SshAlgorithmList::parse(" +aes128-cbc, 3des-cbc,, ");
+        assert_eq!(list.mode, SshAlgorithmMode::Append);
+        assert_eq!(list.algorithms, vec!["aes128-cbc", "3des-cbc"]);
+        assert_eq!(list.to_value(), "+aes128-cbc,3des-cbc");
+        assert_eq!(
+            SshAlgorithmList::parse("-hmac-md5").mode,
+            SshAlgorithmMode::Remove
+        );
+        assert_eq!(
+            SshAlgorithmList::parse("^curve25519-sha256").mode,
+            SshAlgorithmMode::Prepend
+        );
+        assert!(SshAlgorithmList::parse("+").is_empty());
+
+        let mut config = SshConfig::default();
+        config.algorithms.kex_algorithms = SshAlgorithmList::parse("diffie-hellman-group14-sha1");
+        config.algorithms.ciphers = list;
+        config
+            .custom_options
+            .insert("Ciphers".to_string(), "aes256-ctr".to_string());
+        // Structured lists come first, so they win over custom options
+        assert_eq!(
+            config.build_command_args(),
+            vec![
+                "-o",
+                "Ciphers=+aes128-cbc,3des-cbc",
+                "-o",
+                "KexAlgorithms=diffie-hellman-group14-sha1",
+                "-o",
+                "Ciphers=aes256-ctr",
+            ]
+        );
+
+        let json = serde_json::to_value(&config.algorithms).unwrap();
+        assert_eq!(json["ciphers"], "+aes128-cbc,3des-cbc");
+        assert!(json.get("macs").is_none());
+        let parsed: SshAlgorithms = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, config.algorithms);
+    }
+
+    #[test]
+    fn test_algorithm_warnings_are_loose() {
+        let algorithms = SshAlgorithms {
+            ciphers: SshAlgorithmList::parse("aes128-gcm@openssh.com,chacha20 poly1305"),
+            macs: SshAlgorithmList::parse("+hmac-sha2-256,+hmac-sha1"),
+            kex_algorithms: SshAlgorithmList::parse("some-future-kex@example.org"),
+        };
+        assert_eq!(
+            algorithms.warnings(),
+            vec![
+                "Ciphers: 'chacha20 poly1305' does not look like an algorithm name",
+                "MACs: '+hmac-sha1' does not look like an algorithm name",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_valid_env_name() {
+        for name in ["LANG", "_", "app_env2", "X"] {
+            assert!(is_valid_env_name(name), "{name}");
+        }
+        for name in ["", "2FA", "APP-ENV", "A B", "A=B", "ÄPFEL"] {
+            assert!(!is_valid_env_name(name), "{name}");
+        }
+    }
+}
@@ -22,6 +22,46 @@ pub enum TemplateError {
     /// Invalid template configuration
     #[error("Invalid template: {0}")]
     Invalid(String),
+    /// A field required to create a connection is neither set by the
+    /// template nor provided by the caller
+    #[error("Template '{template}' requires a value for {field}")]
+    MissingField {
+        /// Template name
+        template: String,
+        /// Name of the missing field
+        field: &'static str,
+    },
+}
+
+/// Values that replace template defaults when instantiating a connection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateOverrides {
+    /// Port to use instead of the template port
+    pub port: Option<u16>,
+    /// Username to use instead of the template username
+    pub username: Option<String>,
+}
+
+impl TemplateOverrides {
+    /// Creates empty overrides
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the port
+    #[must_use]
+    pub const fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Overrides the username
+    #[must_use]
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
 }
 
 /// A connection template with default settings
@@ -234,13 +274,16 @@ impl ConnectionTemplate {
             port: self.port,
             username: self.username.clone(),
             group_id: None,
+            additional_group_ids: Vec::new(),
             tags: self.tags.clone(),
             created_at: now,
             updated_at: now,
             protocol_config: self.protocol_config.clone(),
             automation: AutomationConfig::default(),
             sort_order: 0,
+            launch_order: 0,
             last_connected: None,
+            connect_count: 0,
             password_source: self.password_source,
             domain: self.domain.clone(),
             custom_properties: self.custom_properties.clone(),
@@ -254,6 +297,54 @@ impl ConnectionTemplate {
             remember_window_position: false,
             window_geometry: None,
             skip_port_check: false,
+            color: None,
+            icon: None,
+            archived: false,
+        }
+    }
+
+    /// Creates a connection from this template
+    ///
+    /// Applies the template's protocol configuration and defaults, then the
+    /// given host (if any) and overrides. The template host is used when
+    /// `host` is `None` or empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::MissingField` if the name, host, or port is
+    /// still unset after applying the overrides.
+    pub fn instantiate(
+        &self,
+        name: impl Into<String>,
+        host: Option<&str>,
+        overrides: &TemplateOverrides,
+    ) -> Result<Connection, TemplateError> {
+        let mut connection = self.apply(Some(name.into()));
+        if let Some(host) = host.map(str::trim).filter(|h| !h.is_empty()) {
+            connection.host = host.to_string();
+        }
+        if let Some(port) = overrides.port {
+            connection.port = port;
+        }
+        if let Some(username) = &overrides.username {
+            connection.username = Some(username.clone());
+        }
+
+        let missing = if connection.name.trim().is_empty() {
+            Some("name")
+        } else if connection.host.trim().is_empty() {
+            Some("host")
+        } else if connection.port == 0 {
+            Some("port")
+        } else {
+            None
+        };
+        match missing {
+            Some(field) => Err(TemplateError::MissingField {
+                template: self.name.clone(),
+                field,
+            }),
+            None => Ok(connection),
         }
     }
 
@@ -367,6 +458,49 @@ mod tests {
         assert_eq!(connection.name, "VNC Template");
     }
 
+    #[test]
+    fn test_instantiate_with_overrides() {
+        let template = ConnectionTemplate::new_rdp("Windows".to_string())
+            .with_username("admin")
+            .with_domain("CORP");
+        let overrides = TemplateOverrides::new()
+            .with_port(3390)
+            .with_username("operator");
+
+        let connection = template
+            .instantiate("dc01", Some("dc01.corp.example"), &overrides)
+            .unwrap();
+
+        assert_eq!(connection.name, "dc01");
+        assert_eq!(connection.host, "dc01.corp.example");
+        assert_eq!(connection.port, 3390);
+        assert_eq!(connection.username.as_deref(), Some("operator"));
+        assert_eq!(connection.domain.as_deref(), Some("CORP"));
+        assert_eq!(connection.protocol_config, template.protocol_config);
+    }
+
+    #[test]
+    fn test_instantiate_requires_host_and_name() {
+        let template = ConnectionTemplate::new_ssh("Linux".to_string());
+        let overrides = TemplateOverrides::new();
+
+        assert!(matches!(
+            template.instantiate("web", None, &overrides),
+            Err(TemplateError::MissingField { field: "host", .. })
+        ));
+        assert!(matches!(
+            template.instantiate(" ", Some("web.example"), &overrides),
+            Err(TemplateError::MissingField { field: "name", .. })
+        ));
+
+        // The template host is used when none is given
+        let template = template.with_host("bastion.example");
+        assert_eq!(
+            template.instantiate("web", None, &overrides).unwrap().host,
+            "bastion.example"
+        );
+    }
+
     #[test]
     fn test_from_connection() {
         let connection = Connection::new_ssh("Original".to_string(), "host.com".to_string(), 22)
@@ -0,0 +1,135 @@
+//! User notifications emitted by core modules.
+//!
+//! This module defines the `Notifier` trait that core components use to
+//! alert the user (idle disconnects, reconnects, monitor alerts) without
+//! depending on a particular frontend. The GUI provides a desktop
+//! implementation; everything else defaults to [`NoOpNotifier`].
+
+use uuid::Uuid;
+
+/// Urgency of a notification, mirroring the desktop notification spec.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationUrgency {
+    /// Informational, may be shown unobtrusively
+    Low,
+    /// Regular notification
+    #[default]
+    Normal,
+    /// Requires the user's attention
+    Critical,
+}
+
+impl NotificationUrgency {
+    /// Returns the urgency name used by desktop notification servers.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// A notification to show to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Short summary line
+    pub title: String,
+    /// Detailed message
+    pub body: String,
+    /// Urgency level
+    pub urgency: NotificationUrgency,
+    /// Connection the notification relates to, if any
+    pub connection_id: Option<Uuid>,
+}
+
+impl Notification {
+    /// Creates a notification with normal urgency.
+    #[must_use]
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            urgency: NotificationUrgency::Normal,
+            connection_id: None,
+        }
+    }
+
+    /// Sets the urgency level.
+    #[must_use]
+    pub const fn with_urgency(mut self, urgency: NotificationUrgency) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    /// Associates the notification with a connection.
+    #[must_use]
+    pub const fn with_connection(mut self, connection_id: Uuid) -> Self {
+        self.connection_id = Some(connection_id);
+        self
+    }
+}
+
+/// Trait for delivering notifications to the user.
+///
+/// Implementations must not block; delivery failures should be logged
+/// rather than propagated, since notifications are best-effort.
+pub trait Notifier: Send + Sync {
+    /// Delivers a notification.
+    fn notify(&self, notification: &Notification);
+}
+
+/// A notifier that discards all notifications.
+///
+/// Useful as a default when no frontend is attached (CLI, tests).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoOpNotifier;
+
+impl NoOpNotifier {
+    /// Creates a new no-op notifier.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Notifier for NoOpNotifier {
+    fn notify(&self, _notification: &Notification) {
+        // No-op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_builder() {
+        let connection_id = Uuid::new_v4();
+        let notification = Notification::new("Title", "Body")
+            .with_urgency(NotificationUrgency::Critical)
+            .with_connection(connection_id);
+
+        assert_eq!(notification.title, "Title");
+        assert_eq!(notification.body, "Body");
+        assert_eq!(notification.urgency, NotificationUrgency::Critical);
+        assert_eq!(notification.connection_id, Some(connection_id));
+    }
+
+    #[test]
+    fn test_default_urgency() {
+        assert_eq!(
+            Notification::new("a", "b").urgency,
+            NotificationUrgency::Normal
+        );
+        assert_eq!(NotificationUrgency::Low.as_str(), "low");
+        assert_eq!(NotificationUrgency::Critical.as_str(), "critical");
+    }
+
+    #[test]
+    fn test_noop_notifier() {
+        // Should not panic
+        NoOpNotifier::new().notify(&Notification::new("a", "b"));
+    }
+}
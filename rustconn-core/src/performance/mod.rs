@@ -19,10 +19,10 @@
 #![allow(clippy::option_if_let_else)]
 #![allow(clippy::len_zero)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock};
 use std::time::{Duration, Instant};
 
 /// Global performance metrics instance
@@ -43,6 +43,8 @@ pub struct PerformanceMetrics {
     startup_timings: Mutex<HashMap<String, Duration>>,
     /// Operation timing measurements
     operation_timings: Mutex<HashMap<String, Vec<Duration>>>,
+    /// Peak number of concurrently running tasks per operation
+    operation_peaks: Mutex<HashMap<String, usize>>,
     /// Startup start time
     startup_start: Mutex<Option<Instant>>,
     /// Whether profiling is enabled
@@ -56,6 +58,7 @@ impl PerformanceMetrics {
         Self {
             startup_timings: Mutex::new(HashMap::new()),
             operation_timings: Mutex::new(HashMap::new()),
+            operation_peaks: Mutex::new(HashMap::new()),
             startup_start: Mutex::new(None),
             profiling_enabled: AtomicBool::new(cfg!(debug_assertions)),
         }
@@ -108,6 +111,19 @@ impl PerformanceMetrics {
             .push(duration);
     }
 
+    /// Records the peak number of tasks an operation ran at once
+    ///
+    /// Keeps the highest peak seen for the operation.
+    pub fn record_concurrency(&self, operation: &str, peak: usize) {
+        if !self.is_profiling_enabled() {
+            return;
+        }
+
+        let mut peaks = self.operation_peaks.lock().unwrap();
+        let entry = peaks.entry(operation.to_string()).or_default();
+        *entry = (*entry).max(peak);
+    }
+
     /// Gets the total startup time
     #[must_use]
     pub fn total_startup_time(&self) -> Option<Duration> {
@@ -157,6 +173,13 @@ impl PerformanceMetrics {
             let max = sorted[count - 1];
             let median = sorted[count / 2];
             let p95 = sorted[(count as f64 * 0.95) as usize];
+            let peak_concurrency = self
+                .operation_peaks
+                .lock()
+                .unwrap()
+                .get(operation)
+                .copied()
+                .unwrap_or(0);
 
             Some(OperationStats {
                 count,
@@ -166,14 +189,28 @@ impl PerformanceMetrics {
                 max,
                 median,
                 p95,
+                peak_concurrency,
             })
         })
     }
 
+    /// Calls `f` with each operation's recorded durations, sorted by name
+    ///
+    /// The timings stay locked while `f` runs, so no copies are made.
+    pub fn for_each_operation(&self, mut f: impl FnMut(&str, &[Duration])) {
+        let timings = self.operation_timings.lock().unwrap();
+        let mut names: Vec<&String> = timings.keys().collect();
+        names.sort_unstable();
+        for name in names {
+            f(name, &timings[name]);
+        }
+    }
+
     /// Clears all recorded metrics
     pub fn clear(&self) {
         self.startup_timings.lock().unwrap().clear();
         self.operation_timings.lock().unwrap().clear();
+        self.operation_peaks.lock().unwrap().clear();
         *self.startup_start.lock().unwrap() = None;
     }
 
@@ -207,6 +244,8 @@ pub struct OperationStats {
     pub median: Duration,
     /// 95th percentile time
     pub p95: Duration,
+    /// Most tasks running at once, 0 if the operation is not concurrent
+    pub peak_concurrency: usize,
 }
 
 /// RAII guard for timing operations
@@ -237,26 +276,78 @@ impl Drop for TimingGuard<'_> {
 ///
 /// Useful for search input and other high-frequency events where
 /// we want to wait for user input to settle before processing.
+///
+/// A debouncer created with [`new`](Self::new) lets an operation proceed at
+/// most once per `delay`. One created with [`with_max_wait`](Self::with_max_wait)
+/// treats calls closer than `delay` apart as a burst: the burst's first call
+/// proceeds only if leading-edge mode is enabled, and further calls proceed
+/// once every `max_wait` so that sustained input still produces results. The
+/// trailing call of a burst is left to the caller's timer, as before.
 pub struct Debouncer {
     /// Minimum delay between operations
     delay: Duration,
+    /// Upper bound on how long a burst of calls can be held back
+    max_wait: Option<Duration>,
+    /// Whether the first call of a burst proceeds immediately
+    leading: bool,
     /// Last operation instant
     last_operation: Mutex<Option<Instant>>,
+    /// Burst tracking for max-wait mode
+    burst: Mutex<BurstState>,
     /// Pending operation flag
     pending: AtomicBool,
 }
 
+/// Timing of the current burst of calls in max-wait mode
+#[derive(Debug, Default)]
+struct BurstState {
+    /// Time of the most recent call
+    last_call: Option<Instant>,
+    /// Start of the current max-wait window
+    window_start: Option<Instant>,
+}
+
 impl Debouncer {
     /// Creates a new debouncer with the specified delay
     #[must_use]
     pub const fn new(delay: Duration) -> Self {
         Self {
             delay,
+            max_wait: None,
+            leading: true,
             last_operation: Mutex::new(None),
+            burst: Mutex::new(BurstState {
+                last_call: None,
+                window_start: None,
+            }),
             pending: AtomicBool::new(false),
         }
     }
 
+    /// Creates a debouncer that holds back bursts for at most `max_wait`
+    ///
+    /// Calls less than `delay` apart form a burst. Within a burst, a call
+    /// proceeds once `max_wait` has elapsed since the burst started or since
+    /// the last call that proceeded. Leading-edge mode is off; enable it
+    /// with [`with_leading`](Self::with_leading).
+    #[must_use]
+    pub const fn with_max_wait(delay: Duration, max_wait: Duration) -> Self {
+        let mut debouncer = Self::new(delay);
+        debouncer.max_wait = Some(max_wait);
+        debouncer.leading = false;
+        debouncer
+    }
+
+    /// Sets whether the first call of a burst proceeds immediately
+    ///
+    /// Only affects max-wait mode; a debouncer created with
+    /// [`new`](Self::new) always lets the first call through.
+    #[must_use]
+    pub const fn with_leading(mut self, leading: bool) -> Self {
+        self.leading = leading;
+        self
+    }
+
     /// Creates a debouncer with a 100ms delay (good for search input)
     #[must_use]
     pub const fn for_search() -> Self {
@@ -274,7 +365,14 @@ impl Debouncer {
     /// Returns `true` if the operation should proceed, `false` if it should be skipped.
     #[must_use]
     pub fn should_proceed(&self) -> bool {
-        let now = Instant::now();
+        self.should_proceed_at(Instant::now())
+    }
+
+    fn should_proceed_at(&self, now: Instant) -> bool {
+        if let Some(max_wait) = self.max_wait {
+            return self.should_proceed_burst(now, max_wait);
+        }
+
         let mut last = self.last_operation.lock().unwrap();
 
         match *last {
@@ -295,6 +393,30 @@ impl Debouncer {
         }
     }
 
+    /// Max-wait mode: groups calls into bursts and releases one per window
+    fn should_proceed_burst(&self, now: Instant, max_wait: Duration) -> bool {
+        let mut burst = self.burst.lock().unwrap();
+        let previous_call = burst.last_call.replace(now);
+        let starts_burst =
+            previous_call.is_none_or(|previous| now.duration_since(previous) >= self.delay);
+
+        let proceed = if starts_burst {
+            burst.window_start = Some(now);
+            self.leading
+        } else {
+            burst
+                .window_start
+                .is_none_or(|start| now.duration_since(start) >= max_wait)
+        };
+
+        if proceed {
+            burst.window_start = Some(now);
+            *self.last_operation.lock().unwrap() = Some(now);
+        }
+        self.pending.store(!proceed, Ordering::SeqCst);
+        proceed
+    }
+
     /// Marks that there's a pending operation
     pub fn mark_pending(&self) {
         self.pending.store(true, Ordering::SeqCst);
@@ -309,6 +431,7 @@ impl Debouncer {
     /// Resets the debouncer state
     pub fn reset(&self) {
         *self.last_operation.lock().unwrap() = None;
+        *self.burst.lock().unwrap() = BurstState::default();
         self.pending.store(false, Ordering::SeqCst);
     }
 
@@ -317,6 +440,12 @@ impl Debouncer {
     pub const fn delay(&self) -> Duration {
         self.delay
     }
+
+    /// Gets the max-wait duration, if configured
+    #[must_use]
+    pub const fn max_wait(&self) -> Option<Duration> {
+        self.max_wait
+    }
 }
 
 /// Lazy initializer for deferred loading
@@ -466,6 +595,10 @@ pub struct AllocationStats {
 /// Batch processor for optimizing bulk operations
 ///
 /// Collects items and processes them in batches to reduce overhead.
+///
+/// [`Self::process_concurrent`] runs a task per item; with
+/// [`Self::with_concurrency`] at most that many tasks are in flight at a
+/// time, so large batches cannot open unbounded sockets or processes.
 pub struct BatchProcessor<T> {
     /// Items waiting to be processed
     items: Arc<Mutex<Vec<T>>>,
@@ -475,6 +608,14 @@ pub struct BatchProcessor<T> {
     max_wait: Duration,
     /// Last flush time
     last_flush: Arc<Mutex<Instant>>,
+    /// Maximum number of in-flight tasks, `None` for unbounded
+    concurrency: Option<usize>,
+    /// Permits bounding in-flight tasks
+    semaphore: Option<Arc<tokio::sync::Semaphore>>,
+    /// Number of tasks currently running
+    in_flight: Arc<AtomicUsize>,
+    /// Most tasks that ran at once
+    peak: Arc<AtomicUsize>,
 }
 
 impl<T> BatchProcessor<T> {
@@ -486,9 +627,71 @@ impl<T> BatchProcessor<T> {
             max_batch_size,
             max_wait,
             last_flush: Arc::new(Mutex::new(Instant::now())),
+            concurrency: None,
+            semaphore: None,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Limits [`Self::process_concurrent`] to `limit` tasks in flight
+    ///
+    /// A limit of 0 is treated as 1.
+    #[must_use]
+    pub fn with_concurrency(mut self, limit: usize) -> Self {
+        let limit = limit.max(1);
+        self.concurrency = Some(limit);
+        self.semaphore = Some(Arc::new(tokio::sync::Semaphore::new(limit)));
+        self
+    }
+
+    /// Returns the concurrency limit, `None` if unbounded
+    #[must_use]
+    pub const fn concurrency(&self) -> Option<usize> {
+        self.concurrency
+    }
+
+    /// Returns the most tasks that ran at once in [`Self::process_concurrent`]
+    #[must_use]
+    pub fn peak_concurrency(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+
+    /// Runs `f` on every item concurrently and returns the results in item
+    /// order
+    ///
+    /// Each task waits for a permit before `f` is called, so no more than
+    /// the [`Self::with_concurrency`] limit run at once. The future is
+    /// `Send` whenever the items, `f` and its futures are.
+    #[allow(clippy::future_not_send)]
+    pub async fn process_concurrent<I, F, Fut, R>(&self, items: I, f: F) -> Vec<R>
+    where
+        I: IntoIterator<Item = T>,
+        F: Fn(T) -> Fut,
+        Fut: std::future::Future<Output = R>,
+    {
+        let tasks = items.into_iter().map(|item| {
+            let f = &f;
+            async move {
+                let _permit = match self.semaphore {
+                    Some(ref semaphore) => Some(
+                        semaphore
+                            .acquire()
+                            .await
+                            .expect("batch semaphore is never closed"),
+                    ),
+                    None => None,
+                };
+                let running = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(running, Ordering::SeqCst);
+                let result = f(item).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                result
+            }
+        });
+        futures::future::join_all(tasks).await
+    }
+
     /// Adds an item to the batch
     ///
     /// Returns `Some(Vec<T>)` if the batch should be processed now.
@@ -540,6 +743,12 @@ impl<T> Default for BatchProcessor<T> {
 ///
 /// Calculates which items should be visible based on scroll position
 /// and viewport size, enabling efficient rendering of large lists.
+///
+/// All items share one height by default. Lists with rows of different
+/// heights, such as group headers taller than connection rows, switch to
+/// variable-height mode with [`Self::with_item_heights`] or
+/// [`Self::set_item_height_fn`]; the scroller then caches each item's height
+/// and start offset and finds the visible window by binary search.
 #[derive(Debug, Clone)]
 pub struct VirtualScroller {
     /// Total number of items
@@ -552,6 +761,11 @@ pub struct VirtualScroller {
     scroll_offset: f64,
     /// Number of items to render above/below visible area (buffer)
     overscan: usize,
+    /// Per-item heights, empty unless heights vary
+    heights: Vec<f64>,
+    /// Start offset of each item followed by the total height, empty unless
+    /// heights vary
+    offsets: Vec<f64>,
 }
 
 impl VirtualScroller {
@@ -564,6 +778,8 @@ impl VirtualScroller {
             viewport_height,
             scroll_offset: 0.0,
             overscan: 5,
+            heights: Vec::new(),
+            offsets: Vec::new(),
         }
     }
 
@@ -574,6 +790,15 @@ impl VirtualScroller {
         self
     }
 
+    /// Switches to variable-height mode with one height per item
+    ///
+    /// The item count becomes the number of heights given.
+    #[must_use]
+    pub fn with_item_heights(mut self, heights: impl IntoIterator<Item = f64>) -> Self {
+        self.set_item_heights(heights);
+        self
+    }
+
     /// Updates the scroll offset
     pub const fn set_scroll_offset(&mut self, offset: f64) {
         self.scroll_offset = offset.max(0.0);
@@ -585,14 +810,86 @@ impl VirtualScroller {
     }
 
     /// Updates the total item count
-    pub const fn set_total_items(&mut self, count: usize) {
+    ///
+    /// In variable-height mode, new items get the default item height.
+    pub fn set_total_items(&mut self, count: usize) {
+        let previous = self.total_items;
         self.total_items = count;
+        if self.has_variable_heights() {
+            self.heights.resize(count, self.item_height.max(0.0));
+            self.rebuild_offsets_from(previous.min(count));
+        }
+    }
+
+    /// Switches to variable-height mode with one height per item
+    ///
+    /// The item count becomes the number of heights given. Negative heights
+    /// are treated as zero.
+    pub fn set_item_heights(&mut self, heights: impl IntoIterator<Item = f64>) {
+        self.heights = heights.into_iter().map(|height| height.max(0.0)).collect();
+        self.total_items = self.heights.len();
+        self.offsets.clear();
+        self.rebuild_offsets_from(0);
+    }
+
+    /// Switches to variable-height mode, asking `height_of` for the height
+    /// of each of `count` items
+    pub fn set_item_height_fn(&mut self, count: usize, height_of: impl Fn(usize) -> f64) {
+        self.set_item_heights((0..count).map(height_of));
+    }
+
+    /// Updates the cached height of one item, e.g. after it was measured
+    ///
+    /// Switches to variable-height mode. Out-of-range indices are ignored.
+    pub fn set_item_height(&mut self, index: usize, height: f64) {
+        if index >= self.total_items {
+            return;
+        }
+        self.ensure_variable_heights();
+        self.heights[index] = height.max(0.0);
+        self.rebuild_offsets_from(index);
+    }
+
+    /// Inserts items before `index`, e.g. the rows of an expanded group
+    ///
+    /// Switches to variable-height mode. Only the offsets of items after
+    /// `index` are recomputed.
+    pub fn insert_items(&mut self, index: usize, heights: &[f64]) {
+        self.ensure_variable_heights();
+        let index = index.min(self.total_items);
+        self.heights
+            .splice(index..index, heights.iter().map(|height| height.max(0.0)));
+        self.total_items = self.heights.len();
+        self.rebuild_offsets_from(index);
+    }
+
+    /// Removes up to `count` items starting at `index`, e.g. the rows of a
+    /// collapsed group
+    ///
+    /// Switches to variable-height mode. Only the offsets of items after
+    /// `index` are recomputed.
+    pub fn remove_items(&mut self, index: usize, count: usize) {
+        self.ensure_variable_heights();
+        let index = index.min(self.total_items);
+        let end = index.saturating_add(count).min(self.total_items);
+        self.heights.drain(index..end);
+        self.total_items = self.heights.len();
+        self.rebuild_offsets_from(index);
+    }
+
+    /// Returns true if items have individual heights
+    #[must_use]
+    pub fn has_variable_heights(&self) -> bool {
+        !self.offsets.is_empty()
     }
 
     /// Gets the range of visible items (`start_index`, `end_index`)
     #[must_use]
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     pub fn visible_range(&self) -> (usize, usize) {
+        if self.has_variable_heights() {
+            return self.variable_visible_range();
+        }
         if self.total_items == 0 || self.item_height <= 0.0 {
             return (0, 0);
         }
@@ -606,16 +903,59 @@ impl VirtualScroller {
         (start, end)
     }
 
+    /// Visible range from the cached offsets
+    fn variable_visible_range(&self) -> (usize, usize) {
+        if self.total_items == 0 {
+            return (0, 0);
+        }
+
+        let top = self.scroll_offset;
+        let bottom = top + self.viewport_height;
+        // First item ending below the top edge, and the items starting above
+        // the bottom edge
+        let first_visible = self.offsets[1..].partition_point(|&end| end <= top);
+        let last_visible =
+            self.offsets[..self.total_items].partition_point(|&start| start < bottom);
+
+        let start = first_visible
+            .min(last_visible)
+            .saturating_sub(self.overscan);
+        let end = last_visible
+            .saturating_add(self.overscan)
+            .min(self.total_items);
+
+        (start, end)
+    }
+
     /// Gets the total scrollable height
     #[must_use]
     pub fn total_height(&self) -> f64 {
-        self.total_items as f64 * self.item_height
+        self.offsets
+            .last()
+            .copied()
+            .unwrap_or(self.total_items as f64 * self.item_height)
     }
 
     /// Gets the offset for a specific item index
     #[must_use]
     pub fn item_offset(&self, index: usize) -> f64 {
-        index as f64 * self.item_height
+        if self.has_variable_heights() {
+            self.offsets[index.min(self.total_items)]
+        } else {
+            index as f64 * self.item_height
+        }
+    }
+
+    /// Gets the height of a specific item, or 0 if out of range
+    #[must_use]
+    pub fn item_height_at(&self, index: usize) -> f64 {
+        if index >= self.total_items {
+            0.0
+        } else if self.has_variable_heights() {
+            self.heights[index]
+        } else {
+            self.item_height
+        }
     }
 
     /// Checks if an item is currently visible
@@ -624,19 +964,62 @@ impl VirtualScroller {
         let (start, end) = self.visible_range();
         index >= start && index < end
     }
+
+    fn ensure_variable_heights(&mut self) {
+        if !self.has_variable_heights() {
+            self.heights = vec![self.item_height.max(0.0); self.total_items];
+            self.rebuild_offsets_from(0);
+        }
+    }
+
+    /// Recomputes the offsets after `index`; earlier offsets are unchanged
+    fn rebuild_offsets_from(&mut self, index: usize) {
+        self.offsets.truncate(index + 1);
+        let mut offset = self.offsets.last().copied().unwrap_or(0.0);
+        if self.offsets.is_empty() {
+            self.offsets.push(offset);
+        }
+        for height in &self.heights[index..] {
+            offset += height;
+            self.offsets.push(offset);
+        }
+    }
 }
 
+/// Default maximum number of strings kept by a [`StringInterner`]
+pub const DEFAULT_INTERNER_CAPACITY: usize = 10_000;
+
 /// String interner for deduplicating repeated strings
 ///
 /// Reduces memory usage when the same strings appear multiple times
 /// (e.g., protocol names, common hostnames, usernames).
+///
+/// The interner holds at most `capacity` strings. When it is full, the least
+/// recently used strings that are no longer referenced outside the interner
+/// are evicted, so strings still held by callers keep their identity.
 pub struct StringInterner {
     /// Interned strings storage
-    strings: RwLock<HashMap<u64, Arc<str>>>,
+    strings: RwLock<HashMap<u64, InternedEntry>>,
+    /// Hashes keyed by last use time, oldest first
+    ///
+    /// Updated only while `strings` is write-locked. Hits under the read
+    /// lock just bump `InternedEntry::last_used`; eviction re-queues such
+    /// entries when it reaches their outdated key.
+    recency: Mutex<BTreeMap<u64, u64>>,
+    /// Maximum number of strings before eviction
+    capacity: usize,
+    /// Logical clock used to order entries by last use
+    clock: AtomicU64,
     /// Statistics
     stats: InternerStats,
 }
 
+/// An interned string with its last use time
+struct InternedEntry {
+    value: Arc<str>,
+    last_used: AtomicU64,
+}
+
 /// Statistics for the string interner
 #[derive(Debug, Default)]
 pub struct InternerStats {
@@ -648,18 +1031,54 @@ pub struct InternerStats {
     pub unique_count: AtomicUsize,
     /// Estimated bytes saved through deduplication
     pub bytes_saved: AtomicUsize,
+    /// Number of strings evicted to stay within capacity
+    pub eviction_count: AtomicUsize,
+}
+
+impl InternerStats {
+    /// Returns evictions per cache miss (0.0 to 1.0)
+    ///
+    /// Values close to 1.0 mean nearly every new string pushes another one
+    /// out, i.e. the capacity is too small for the working set.
+    #[must_use]
+    pub fn eviction_churn(&self) -> f64 {
+        let misses = self
+            .intern_count
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.hit_count.load(Ordering::Relaxed));
+        if misses == 0 {
+            0.0
+        } else {
+            (self.eviction_count.load(Ordering::Relaxed) as f64 / misses as f64).min(1.0)
+        }
+    }
 }
 
 impl StringInterner {
-    /// Creates a new string interner
+    /// Creates a new string interner with the default capacity
     #[must_use]
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_INTERNER_CAPACITY)
+    }
+
+    /// Creates a new string interner holding at most `capacity` strings
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             strings: RwLock::new(HashMap::new()),
+            recency: Mutex::new(BTreeMap::new()),
+            capacity: capacity.max(1),
+            clock: AtomicU64::new(0),
             stats: InternerStats::default(),
         }
     }
 
+    /// Returns the maximum number of strings kept before eviction
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Interns a string, returning a reference-counted pointer
     ///
     /// If the string was already interned, returns the existing Arc.
@@ -672,12 +1091,8 @@ impl StringInterner {
         // Try read lock first for cache hit
         {
             let strings = self.strings.read().unwrap();
-            if let Some(existing) = strings.get(&hash) {
-                if &**existing == s {
-                    self.stats.hit_count.fetch_add(1, Ordering::Relaxed);
-                    self.stats.bytes_saved.fetch_add(s.len(), Ordering::Relaxed);
-                    return Arc::clone(existing);
-                }
+            if let Some(existing) = self.lookup(&strings, hash, s) {
+                return existing;
             }
         }
 
@@ -685,21 +1100,98 @@ impl StringInterner {
         let mut strings = self.strings.write().unwrap();
 
         // Double-check after acquiring write lock
-        if let Some(existing) = strings.get(&hash) {
-            if &**existing == s {
-                self.stats.hit_count.fetch_add(1, Ordering::Relaxed);
-                self.stats.bytes_saved.fetch_add(s.len(), Ordering::Relaxed);
-                return Arc::clone(existing);
-            }
+        if let Some(existing) = self.lookup(&strings, hash, s) {
+            return existing;
+        }
+
+        if strings.len() >= self.capacity {
+            self.evict(&mut strings);
         }
 
         // Insert new string
         let arc: Arc<str> = Arc::from(s);
-        strings.insert(hash, Arc::clone(&arc));
+        let now = self.tick();
+        strings.insert(
+            hash,
+            InternedEntry {
+                value: Arc::clone(&arc),
+                last_used: AtomicU64::new(now),
+            },
+        );
+        self.lock_recency().insert(now, hash);
         self.stats.unique_count.fetch_add(1, Ordering::Relaxed);
         arc
     }
 
+    /// Returns the interned string on a hit, refreshing its last use time
+    fn lookup(
+        &self,
+        strings: &HashMap<u64, InternedEntry>,
+        hash: u64,
+        s: &str,
+    ) -> Option<Arc<str>> {
+        let entry = strings.get(&hash).filter(|entry| &*entry.value == s)?;
+        entry.last_used.store(self.tick(), Ordering::Relaxed);
+        self.stats.hit_count.fetch_add(1, Ordering::Relaxed);
+        self.stats.bytes_saved.fetch_add(s.len(), Ordering::Relaxed);
+        Some(Arc::clone(&entry.value))
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Locks the recency index, recovering from a poisoned lock
+    fn lock_recency(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, u64>> {
+        self.recency
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Evicts least recently used strings that only the interner references
+    ///
+    /// Frees a quarter of the capacity at once so that eviction is amortized
+    /// over subsequent inserts. Walks the recency index from the oldest
+    /// entry, so the cost depends on the entries evicted, refreshed by hits
+    /// or still live rather than on the interner size. Strings still held
+    /// elsewhere are never evicted; if all strings are live the interner
+    /// temporarily grows past its capacity.
+    fn evict(&self, strings: &mut HashMap<u64, InternedEntry>) {
+        let target = self.capacity - (self.capacity / 4).max(1);
+        let excess = strings.len().saturating_sub(target);
+
+        let mut recency = self.lock_recency();
+        let mut live = Vec::new();
+        let mut evicted = 0;
+        while evicted < excess {
+            let Some((used, hash)) = recency.pop_first() else {
+                break;
+            };
+            let Some(entry) = strings.get(&hash) else {
+                continue;
+            };
+            let last_used = entry.last_used.load(Ordering::Relaxed);
+            if last_used != used {
+                // Hit since it was queued; move it to its current position
+                recency.insert(last_used, hash);
+            } else if Arc::strong_count(&entry.value) > 1 {
+                live.push((used, hash));
+            } else {
+                strings.remove(&hash);
+                evicted += 1;
+            }
+        }
+        recency.extend(live);
+        drop(recency);
+
+        self.stats
+            .unique_count
+            .fetch_sub(evicted, Ordering::Relaxed);
+        self.stats
+            .eviction_count
+            .fetch_add(evicted, Ordering::Relaxed);
+    }
+
     /// Gets the interner statistics
     #[must_use]
     pub const fn stats(&self) -> &InternerStats {
@@ -720,7 +1212,9 @@ impl StringInterner {
 
     /// Clears all interned strings
     pub fn clear(&self) {
-        self.strings.write().unwrap().clear();
+        let mut strings = self.strings.write().unwrap();
+        strings.clear();
+        self.lock_recency().clear();
         self.stats.unique_count.store(0, Ordering::Relaxed);
     }
 
@@ -738,6 +1232,10 @@ impl Default for StringInterner {
     }
 }
 
+/// Growth of a single category between consecutive snapshots that triggers
+/// a recommendation (1 MiB)
+pub const SNAPSHOT_GROWTH_THRESHOLD: usize = 1024 * 1024;
+
 /// Memory optimizer providing utilities for reducing memory usage
 ///
 /// Provides methods for estimating memory usage, identifying optimization
@@ -766,6 +1264,19 @@ pub struct MemorySnapshot {
     pub group_count: usize,
     /// Number of sessions
     pub session_count: usize,
+    /// Per-category memory usage
+    pub breakdown: MemoryBreakdown,
+}
+
+impl MemorySnapshot {
+    /// Returns per-category growth from this snapshot to a later one
+    ///
+    /// Positive values mean the category grew since `self`, negative values
+    /// mean it shrank.
+    #[must_use]
+    pub fn diff(&self, later: &Self) -> MemoryDelta {
+        MemoryDelta::between(&self.breakdown, &later.breakdown)
+    }
 }
 
 /// Memory usage estimate for a data structure
@@ -880,6 +1391,30 @@ impl MemoryOptimizer {
         connection_count: usize,
         group_count: usize,
         session_count: usize,
+    ) {
+        let breakdown =
+            MemoryBreakdown::estimate(connection_count, group_count, session_count, 0, 0, 0);
+        self.take_snapshot_with_breakdown(
+            label,
+            connection_count,
+            group_count,
+            session_count,
+            breakdown,
+        );
+    }
+
+    /// Takes a memory snapshot with an explicit per-category breakdown
+    ///
+    /// Use this when caches, pooled buffers or UI state are measured rather
+    /// than estimated from counts, so that [`MemorySnapshot::diff`] can
+    /// attribute growth to them.
+    pub fn take_snapshot_with_breakdown(
+        &self,
+        label: &str,
+        connection_count: usize,
+        group_count: usize,
+        session_count: usize,
+        breakdown: MemoryBreakdown,
     ) {
         let snapshot = MemorySnapshot {
             timestamp: Instant::now(),
@@ -888,6 +1423,7 @@ impl MemoryOptimizer {
             connection_count,
             group_count,
             session_count,
+            breakdown,
         };
 
         let mut snapshots = self.snapshots.lock().unwrap();
@@ -908,6 +1444,48 @@ impl MemoryOptimizer {
         self.snapshots.lock().unwrap().clear();
     }
 
+    /// Returns per-category growth between the two most recent snapshots
+    #[must_use]
+    pub fn latest_diff(&self) -> Option<MemoryDelta> {
+        let snapshots = self.snapshots.lock().unwrap();
+        match snapshots.as_slice() {
+            [.., previous, latest] => Some(previous.diff(latest)),
+            _ => None,
+        }
+    }
+
+    /// Generates recommendations for categories that grew by more than
+    /// `threshold` bytes between two snapshots
+    #[must_use]
+    pub fn growth_recommendations(
+        from: &MemorySnapshot,
+        to: &MemorySnapshot,
+        threshold: usize,
+    ) -> Vec<OptimizationRecommendation> {
+        from.diff(to)
+            .grown_beyond(threshold)
+            .into_iter()
+            .map(|(name, growth)| {
+                let category = match name {
+                    "caches" => OptimizationCategory::CacheManagement,
+                    "sessions" | "overhead" => OptimizationCategory::UnusedData,
+                    _ => OptimizationCategory::DataStructure,
+                };
+                OptimizationRecommendation {
+                    category,
+                    description: format!(
+                        "Memory used by {name} grew by {} between snapshots '{}' and '{}'.",
+                        format_bytes(growth),
+                        from.label,
+                        to.label
+                    ),
+                    estimated_savings: growth / 2,
+                    priority: 4,
+                }
+            })
+            .collect()
+    }
+
     /// Estimates current heap usage based on data counts
     #[must_use]
     pub const fn estimate_current_heap(
@@ -1017,6 +1595,14 @@ impl MemoryOptimizer {
                 0.0
             };
 
+            // Attribute growth since the previous snapshot to categories
+            let previous = &snapshots[snapshots.len() - 2];
+            recommendations.extend(Self::growth_recommendations(
+                previous,
+                last,
+                SNAPSHOT_GROWTH_THRESHOLD,
+            ));
+
             if growth_rate > 0.5 && growth > 1024 * 1024 {
                 recommendations.push(OptimizationRecommendation {
                     category: OptimizationCategory::UnusedData,
@@ -1032,7 +1618,7 @@ impl MemoryOptimizer {
         }
 
         // Sort by priority (highest first)
-        recommendations.sort_by(|a, b| b.priority.cmp(&a.priority));
+        recommendations.sort_by_key(|rec| std::cmp::Reverse(rec.priority));
         recommendations
     }
 
@@ -1085,6 +1671,11 @@ impl MemoryOptimizer {
             "  Cache hits: {}\n",
             stats.hit_count.load(Ordering::Relaxed)
         ));
+        report.push_str(&format!(
+            "  Evictions: {} (capacity {})\n",
+            stats.eviction_count.load(Ordering::Relaxed),
+            self.interner.capacity()
+        ));
         report.push_str(&format!(
             "  Bytes saved: {}\n\n",
             format_bytes(stats.bytes_saved.load(Ordering::Relaxed))
@@ -1276,15 +1867,65 @@ pub fn memory_optimizer() -> &'static MemoryOptimizer {
 /// Memory-efficient pool for reusing allocations
 ///
 /// Reduces allocation overhead by reusing previously allocated objects.
+///
+/// [`acquire`](Self::acquire) always succeeds and allocates when the pool is
+/// empty. For backpressure, [`try_get`](Self::try_get) refuses to allocate
+/// once `max_size` objects are checked out, bounding the total number of
+/// live objects.
 pub struct ObjectPool<T> {
     /// Pool of available objects
     pool: Mutex<Vec<T>>,
-    /// Maximum pool size
+    /// Maximum pool size, also the checkout limit for `try_get`
     max_size: usize,
+    /// Number of objects currently checked out
+    outstanding: AtomicUsize,
     /// Statistics
     stats: PoolStats,
 }
 
+/// An object checked out with [`ObjectPool::try_get`]
+///
+/// The object is returned to the pool when the guard is dropped.
+pub struct Pooled<'a, T: Default> {
+    pool: &'a ObjectPool<T>,
+    value: Option<T>,
+}
+
+impl<T: Default> Pooled<'_, T> {
+    /// Detaches the object from the pool instead of returning it on drop
+    #[must_use]
+    pub fn into_inner(mut self) -> T {
+        self.pool.release_checkout();
+        self.value.take().expect("pooled value present until drop")
+    }
+}
+
+impl<T: Default> std::ops::Deref for Pooled<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_ref()
+            .expect("pooled value present until drop")
+    }
+}
+
+impl<T: Default> std::ops::DerefMut for Pooled<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+            .as_mut()
+            .expect("pooled value present until drop")
+    }
+}
+
+impl<T: Default> Drop for Pooled<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.pool.release(value);
+        }
+    }
+}
+
 /// Statistics for the object pool
 #[derive(Debug, Default)]
 pub struct PoolStats {
@@ -1296,6 +1937,8 @@ pub struct PoolStats {
     pub created: AtomicUsize,
     /// Number of objects dropped (pool full)
     pub dropped: AtomicUsize,
+    /// Number of `try_get` calls refused because the pool was exhausted
+    pub exhausted_count: AtomicUsize,
 }
 
 impl<T: Default> ObjectPool<T> {
@@ -1305,6 +1948,7 @@ impl<T: Default> ObjectPool<T> {
         Self {
             pool: Mutex::new(Vec::with_capacity(max_size)),
             max_size,
+            outstanding: AtomicUsize::new(0),
             stats: PoolStats::default(),
         }
     }
@@ -1312,8 +1956,9 @@ impl<T: Default> ObjectPool<T> {
     /// Acquires an object from the pool, or creates a new one if empty
     pub fn acquire(&self) -> T {
         self.stats.acquired.fetch_add(1, Ordering::Relaxed);
+        self.outstanding.fetch_add(1, Ordering::Relaxed);
 
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.lock_pool();
         if let Some(obj) = pool.pop() {
             obj
         } else {
@@ -1322,11 +1967,62 @@ impl<T: Default> ObjectPool<T> {
         }
     }
 
+    /// Acquires an object without exceeding `max_size` checked-out objects
+    ///
+    /// Reuses a pooled object if one is available and allocates otherwise,
+    /// unless `max_size` objects are already checked out; then returns
+    /// `None` and counts the refusal in `PoolStats::exhausted_count`.
+    /// Objects obtained with [`acquire`](Self::acquire) count towards the
+    /// limit as well.
+    pub fn try_get(&self) -> Option<Pooled<'_, T>> {
+        let mut pool = self.lock_pool();
+        let value = if let Some(obj) = pool.pop() {
+            obj
+        } else if self.outstanding.load(Ordering::Relaxed) < self.max_size {
+            self.stats.created.fetch_add(1, Ordering::Relaxed);
+            T::default()
+        } else {
+            self.stats.exhausted_count.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        self.outstanding.fetch_add(1, Ordering::Relaxed);
+        drop(pool);
+
+        self.stats.acquired.fetch_add(1, Ordering::Relaxed);
+        Some(Pooled {
+            pool: self,
+            value: Some(value),
+        })
+    }
+
+    /// Returns the number of objects currently checked out
+    #[must_use]
+    pub fn outstanding(&self) -> usize {
+        self.outstanding.load(Ordering::Relaxed)
+    }
+
+    /// Marks one checked-out object as no longer owned by a caller
+    fn release_checkout(&self) {
+        // Objects released without being acquired must not underflow
+        let _ = self
+            .outstanding
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+
+    /// Locks the pooled objects, recovering from a poisoned lock
+    ///
+    /// `Pooled` guards release from `Drop`, which may run while unwinding
+    /// from a panic that poisoned the lock; panicking there would abort.
+    fn lock_pool(&self) -> MutexGuard<'_, Vec<T>> {
+        self.pool.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Returns an object to the pool for reuse
     pub fn release(&self, obj: T) {
         self.stats.returned.fetch_add(1, Ordering::Relaxed);
+        self.release_checkout();
 
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.lock_pool();
         if pool.len() < self.max_size {
             pool.push(obj);
         } else {
@@ -1338,7 +2034,7 @@ impl<T: Default> ObjectPool<T> {
     /// Returns the current pool size
     #[must_use]
     pub fn size(&self) -> usize {
-        self.pool.lock().unwrap().len()
+        self.lock_pool().len()
     }
 
     /// Returns pool statistics
@@ -1349,12 +2045,12 @@ impl<T: Default> ObjectPool<T> {
 
     /// Clears the pool
     pub fn clear(&self) {
-        self.pool.lock().unwrap().clear();
+        self.lock_pool().clear();
     }
 
     /// Pre-populates the pool with objects
     pub fn warm(&self, count: usize) {
-        let mut pool = self.pool.lock().unwrap();
+        let mut pool = self.lock_pool();
         let to_add = count.min(self.max_size).saturating_sub(pool.len());
         for _ in 0..to_add {
             pool.push(T::default());
@@ -1671,6 +2367,88 @@ impl MemoryBreakdown {
     }
 }
 
+/// Signed per-category change between two [`MemoryBreakdown`]s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryDelta {
+    /// Change in memory used by connections
+    pub connections: isize,
+    /// Change in memory used by groups
+    pub groups: isize,
+    /// Change in memory used by sessions
+    pub sessions: isize,
+    /// Change in memory used by snippets
+    pub snippets: isize,
+    /// Change in memory used by templates
+    pub templates: isize,
+    /// Change in memory used by clusters
+    pub clusters: isize,
+    /// Change in memory used by caches
+    pub caches: isize,
+    /// Change in memory used by UI state
+    pub ui_state: isize,
+    /// Change in other/overhead memory
+    pub overhead: isize,
+}
+
+impl MemoryDelta {
+    /// Computes `after - before` for every category
+    #[must_use]
+    pub fn between(before: &MemoryBreakdown, after: &MemoryBreakdown) -> Self {
+        let delta = |a: usize, b: usize| b as isize - a as isize;
+        Self {
+            connections: delta(before.connections, after.connections),
+            groups: delta(before.groups, after.groups),
+            sessions: delta(before.sessions, after.sessions),
+            snippets: delta(before.snippets, after.snippets),
+            templates: delta(before.templates, after.templates),
+            clusters: delta(before.clusters, after.clusters),
+            caches: delta(before.caches, after.caches),
+            ui_state: delta(before.ui_state, after.ui_state),
+            overhead: delta(before.overhead, after.overhead),
+        }
+    }
+
+    /// Returns every category with its change, in breakdown order
+    #[must_use]
+    pub const fn categories(&self) -> [(&'static str, isize); 9] {
+        [
+            ("connections", self.connections),
+            ("groups", self.groups),
+            ("sessions", self.sessions),
+            ("snippets", self.snippets),
+            ("templates", self.templates),
+            ("clusters", self.clusters),
+            ("caches", self.caches),
+            ("ui_state", self.ui_state),
+            ("overhead", self.overhead),
+        ]
+    }
+
+    /// Returns the net change across all categories
+    #[must_use]
+    pub fn total(&self) -> isize {
+        self.categories().iter().map(|(_, delta)| delta).sum()
+    }
+
+    /// Returns categories that grew by more than `threshold` bytes,
+    /// largest growth first
+    #[must_use]
+    pub fn grown_beyond(&self, threshold: usize) -> Vec<(&'static str, usize)> {
+        let mut grown: Vec<_> = self
+            .categories()
+            .into_iter()
+            .filter_map(|(name, delta)| {
+                usize::try_from(delta)
+                    .ok()
+                    .filter(|growth| *growth > threshold)
+                    .map(|growth| (name, growth))
+            })
+            .collect();
+        grown.sort_by_key(|(_, growth)| std::cmp::Reverse(*growth));
+        grown
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1742,6 +2520,59 @@ mod tests {
         assert!(debouncer.should_proceed());
     }
 
+    #[test]
+    fn test_debouncer_max_wait_during_sustained_input() {
+        let debouncer =
+            Debouncer::with_max_wait(Duration::from_millis(100), Duration::from_millis(300));
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        // Keystrokes every 50ms never settle, but max_wait releases one every 300ms
+        let proceeded: Vec<u64> = (0..=16)
+            .map(|i| i * 50)
+            .filter(|&ms| debouncer.should_proceed_at(at(ms)))
+            .collect();
+        assert_eq!(proceeded, vec![300, 600]);
+        assert!(debouncer.has_pending());
+
+        // Continued typing crosses the next max-wait boundary at 900ms
+        assert!(!debouncer.should_proceed_at(at(850)));
+        assert!(debouncer.should_proceed_at(at(900)));
+    }
+
+    #[test]
+    fn test_debouncer_leading_edge() {
+        let debouncer =
+            Debouncer::with_max_wait(Duration::from_millis(100), Duration::from_millis(300))
+                .with_leading(true);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        // First keystroke fires immediately, the burst is then held back
+        let proceeded: Vec<u64> = (0..=6)
+            .map(|i| i * 50)
+            .filter(|&ms| debouncer.should_proceed_at(at(ms)))
+            .collect();
+        assert_eq!(proceeded, vec![0, 300]);
+
+        // After a pause longer than delay, a new burst fires immediately again
+        assert!(debouncer.should_proceed_at(at(500)));
+        assert!(!debouncer.should_proceed_at(at(520)));
+
+        debouncer.reset();
+        assert!(debouncer.should_proceed_at(at(530)));
+    }
+
+    #[test]
+    fn test_debouncer_max_wait_real_time() {
+        let debouncer = Debouncer::with_max_wait(Duration::from_secs(1), Duration::from_millis(30));
+        assert_eq!(debouncer.max_wait(), Some(Duration::from_millis(30)));
+
+        assert!(!debouncer.should_proceed());
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(debouncer.should_proceed());
+    }
+
     #[test]
     fn test_lazy_init() {
         let counter = std::sync::atomic::AtomicUsize::new(0);
@@ -1786,6 +2617,51 @@ mod tests {
         assert_eq!(remaining, vec![4]);
     }
 
+    #[test]
+    fn test_batch_processor_bounds_concurrency() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let processor: BatchProcessor<u64> = BatchProcessor::default().with_concurrency(3);
+        assert_eq!(processor.concurrency(), Some(3));
+
+        let results = runtime.block_on(processor.process_concurrent(0..10, |i| async move {
+            tokio::time::sleep(Duration::from_millis(10 - i)).await;
+            i * 2
+        }));
+        assert_eq!(results, (0..10).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(processor.peak_concurrency(), 3);
+
+        let unbounded: BatchProcessor<u64> = BatchProcessor::default();
+        runtime.block_on(unbounded.process_concurrent(0..5, |_| async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }));
+        assert_eq!(unbounded.concurrency(), None);
+        assert_eq!(unbounded.peak_concurrency(), 5);
+    }
+
+    #[test]
+    fn test_operation_stats_peak_concurrency() {
+        let metrics = PerformanceMetrics::new();
+        metrics.set_profiling_enabled(true);
+        metrics.record_operation("batch", Duration::from_millis(5));
+        assert_eq!(
+            metrics.operation_stats("batch").unwrap().peak_concurrency,
+            0
+        );
+
+        metrics.record_concurrency("batch", 4);
+        metrics.record_concurrency("batch", 2);
+        assert_eq!(
+            metrics.operation_stats("batch").unwrap().peak_concurrency,
+            4
+        );
+
+        metrics.clear();
+        assert!(metrics.operation_stats("batch").is_none());
+    }
+
     #[test]
     fn test_virtual_scroller() {
         let mut scroller = VirtualScroller::new(100, 30.0, 300.0);
@@ -1823,6 +2699,52 @@ mod tests {
         assert!((scroller.total_height() - 3000.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_virtual_scroller_mixed_heights() {
+        // Group headers of 48px, each followed by four 24px connection rows
+        let mut scroller = VirtualScroller::new(0, 24.0, 100.0)
+            .with_overscan(0)
+            .with_item_heights((0..50).map(|i| if i % 5 == 0 { 48.0 } else { 24.0 }));
+        assert_eq!(scroller.total_items, 50);
+        assert!((scroller.total_height() - 1440.0).abs() < f64::EPSILON);
+
+        // Each block is 144px, so 300px is 12px into the third header
+        scroller.set_scroll_offset(300.0);
+        assert!((scroller.item_offset(11) - 336.0).abs() < f64::EPSILON);
+        // Viewport 300..400 spans header 10 (288..336) and rows 11..=13 (..408)
+        assert_eq!(scroller.visible_range(), (10, 14));
+
+        let scroller = scroller.with_overscan(2);
+        assert_eq!(scroller.visible_range(), (8, 16));
+        assert!((scroller.item_height_at(10) - 48.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_virtual_scroller_expand_collapse() {
+        let mut scroller = VirtualScroller::new(3, 30.0, 100.0).with_overscan(0);
+        assert!(!scroller.has_variable_heights());
+
+        // Expanding the first group inserts its rows after the header
+        scroller.insert_items(1, &[20.0, 20.0, 20.0]);
+        assert!(scroller.has_variable_heights());
+        assert_eq!(scroller.total_items, 6);
+        assert!((scroller.item_offset(4) - 90.0).abs() < f64::EPSILON);
+        assert!((scroller.total_height() - 150.0).abs() < f64::EPSILON);
+
+        scroller.set_item_height(0, 40.0);
+        assert!((scroller.item_offset(4) - 100.0).abs() < f64::EPSILON);
+
+        // Collapsing removes them again
+        scroller.remove_items(1, 3);
+        assert_eq!(scroller.total_items, 3);
+        assert!((scroller.total_height() - 100.0).abs() < f64::EPSILON);
+        scroller.set_scroll_offset(45.0);
+        assert_eq!(scroller.visible_range(), (1, 3));
+
+        scroller.set_total_items(5);
+        assert!((scroller.total_height() - 160.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_string_interner_basic() {
         let interner = StringInterner::new();
@@ -1840,6 +2762,83 @@ mod tests {
         assert_eq!(interner.stats().hit_count.load(Ordering::Relaxed), 1);
     }
 
+    #[test]
+    fn test_string_interner_evicts_least_recently_used() {
+        let interner = StringInterner::with_capacity(4);
+
+        for name in ["a", "b", "c", "d"] {
+            let _ = interner.intern(name);
+        }
+        // Touch "a" so that "b" is the oldest
+        let _ = interner.intern("a");
+        let _ = interner.intern("e");
+
+        assert_eq!(interner.len(), 4);
+        assert_eq!(interner.stats().eviction_count.load(Ordering::Relaxed), 1);
+        assert_eq!(interner.stats().unique_count.load(Ordering::Relaxed), 4);
+
+        let hits = interner.stats().hit_count.load(Ordering::Relaxed);
+        let _ = interner.intern("a");
+        let _ = interner.intern("c");
+        assert_eq!(interner.stats().hit_count.load(Ordering::Relaxed), hits + 2);
+        // "b" was the least recently used entry
+        let _ = interner.intern("b");
+        assert_eq!(interner.stats().hit_count.load(Ordering::Relaxed), hits + 2);
+    }
+
+    #[test]
+    fn test_string_interner_evicts_untouched_before_refreshed() {
+        let interner = StringInterner::with_capacity(8);
+        for i in 0..8 {
+            let _ = interner.intern(&format!("host-{i}"));
+        }
+        // Refresh the even hosts; the odd ones become the oldest
+        for i in (0..8).step_by(2) {
+            let _ = interner.intern(&format!("host-{i}"));
+        }
+        let _ = interner.intern("new");
+
+        // A quarter of the capacity is freed at once
+        assert_eq!(interner.stats().eviction_count.load(Ordering::Relaxed), 2);
+        let hits = interner.stats().hit_count.load(Ordering::Relaxed);
+        for i in (0..8).step_by(2) {
+            let _ = interner.intern(&format!("host-{i}"));
+        }
+        assert_eq!(interner.stats().hit_count.load(Ordering::Relaxed), hits + 4);
+        let _ = interner.intern("host-1");
+        let _ = interner.intern("host-3");
+        assert_eq!(interner.stats().hit_count.load(Ordering::Relaxed), hits + 4);
+    }
+
+    #[test]
+    fn test_string_interner_keeps_live_entries() {
+        let interner = StringInterner::with_capacity(2);
+
+        let held = interner.intern("held");
+        let _ = interner.intern("transient");
+        let _ = interner.intern("new");
+
+        // "held" is older but still referenced, so "transient" goes instead
+        assert!(Arc::ptr_eq(&held, &interner.intern("held")));
+        assert_eq!(interner.stats().eviction_count.load(Ordering::Relaxed), 1);
+
+        // With every entry live the interner grows past capacity
+        let live: Vec<_> = ["x", "y", "z"].iter().map(|s| interner.intern(s)).collect();
+        assert!(interner.len() > interner.capacity());
+        assert!(live.iter().all(|s| Arc::ptr_eq(s, &interner.intern(s))));
+    }
+
+    #[test]
+    fn test_interner_eviction_churn() {
+        let interner = StringInterner::with_capacity(1);
+        assert!(interner.stats().eviction_churn().abs() < f64::EPSILON);
+
+        for i in 0..10 {
+            let _ = interner.intern(&format!("host-{i}"));
+        }
+        assert!(interner.stats().eviction_churn() > 0.8);
+    }
+
     #[test]
     fn test_string_interner_stats() {
         let interner = StringInterner::new();
@@ -1886,6 +2885,51 @@ mod tests {
         assert_eq!(snapshots[1].connection_count, 50);
     }
 
+    #[test]
+    fn test_memory_snapshot_diff() {
+        let optimizer = MemoryOptimizer::new();
+        assert!(optimizer.latest_diff().is_none());
+
+        optimizer.take_snapshot("initial", 10, 5, 4);
+        optimizer.take_snapshot("later", 30, 5, 1);
+
+        let delta = optimizer.latest_diff().unwrap();
+        assert_eq!(delta.connections, 20 * 2048);
+        assert_eq!(delta.groups, 0);
+        assert_eq!(delta.sessions, -3 * 512);
+        assert_eq!(delta.total(), 20 * 2048 - 3 * 512);
+    }
+
+    #[test]
+    fn test_growth_recommendations() {
+        let optimizer = MemoryOptimizer::new();
+        let base = MemoryBreakdown::estimate(10, 1, 1, 0, 0, 0);
+        let grown = MemoryBreakdown {
+            caches: base.caches + 4 * 1024 * 1024,
+            connections: base.connections + 1024,
+            ..base.clone()
+        };
+        optimizer.take_snapshot_with_breakdown("before", 10, 1, 1, base);
+        optimizer.take_snapshot_with_breakdown("after", 10, 1, 1, grown);
+
+        let snapshots = optimizer.snapshots();
+        let recommendations = MemoryOptimizer::growth_recommendations(
+            &snapshots[0],
+            &snapshots[1],
+            SNAPSHOT_GROWTH_THRESHOLD,
+        );
+        assert_eq!(recommendations.len(), 1);
+        assert_eq!(
+            recommendations[0].category,
+            OptimizationCategory::CacheManagement
+        );
+        assert!(recommendations[0].description.contains("caches"));
+        assert_eq!(
+            snapshots[0].diff(&snapshots[1]).grown_beyond(0),
+            vec![("caches", 4 * 1024 * 1024), ("connections", 1024)]
+        );
+    }
+
     #[test]
     fn test_memory_optimizer_estimate() {
         let optimizer = MemoryOptimizer::new();
@@ -2022,6 +3066,77 @@ mod tests {
         assert_eq!(pool.stats().dropped.load(Ordering::Relaxed), 1);
     }
 
+    #[test]
+    fn test_object_pool_try_get_backpressure() {
+        let pool: ObjectPool<Vec<u8>> = ObjectPool::new(2);
+
+        let mut first = pool.try_get().unwrap();
+        first.push(1);
+        let second = pool.try_get().unwrap();
+        assert_eq!(pool.outstanding(), 2);
+
+        // Exhausted: no allocation beyond max_size
+        assert!(pool.try_get().is_none());
+        assert_eq!(pool.stats().exhausted_count.load(Ordering::Relaxed), 1);
+        assert_eq!(pool.stats().created.load(Ordering::Relaxed), 2);
+
+        // Dropping a guard returns the object to the pool
+        drop(first);
+        assert_eq!(pool.size(), 1);
+        let reused = pool.try_get().unwrap();
+        assert_eq!(*reused, vec![1]);
+        assert_eq!(pool.stats().created.load(Ordering::Relaxed), 2);
+
+        // Detached objects free their slot without returning to the pool
+        let _owned = second.into_inner();
+        assert_eq!(pool.outstanding(), 1);
+        assert_eq!(pool.size(), 0);
+        assert!(pool.try_get().is_some());
+    }
+
+    #[test]
+    fn test_object_pool_acquire_counts_towards_limit() {
+        let pool: ObjectPool<Vec<u8>> = ObjectPool::new(1);
+
+        // acquire still allocates on miss regardless of the limit
+        let obj = pool.acquire();
+        let extra = pool.acquire();
+        assert_eq!(pool.stats().created.load(Ordering::Relaxed), 2);
+        assert!(pool.try_get().is_none());
+
+        pool.release(obj);
+        pool.release(extra);
+        assert_eq!(pool.outstanding(), 0);
+        assert!(pool.try_get().is_some());
+    }
+
+    #[test]
+    fn test_object_pool_guard_returns_on_every_drop() {
+        let pool: ObjectPool<Vec<u8>> = ObjectPool::new(2);
+
+        for _ in 0..100 {
+            let guard = pool.try_get().unwrap();
+            assert_eq!(pool.size(), 0);
+            drop(guard);
+            assert_eq!(pool.size(), 1);
+            assert_eq!(pool.outstanding(), 0);
+        }
+        assert_eq!(pool.stats().created.load(Ordering::Relaxed), 1);
+        assert_eq!(pool.stats().returned.load(Ordering::Relaxed), 100);
+
+        // A guard dropped while unwinding still goes back, even after the
+        // panic poisoned the pool lock
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = pool.try_get().unwrap();
+            let _lock = pool.pool.lock().unwrap();
+            panic!("poison the pool");
+        }));
+        assert!(result.is_err());
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.outstanding(), 0);
+        assert!(pool.try_get().is_some());
+    }
+
     #[test]
     fn test_object_pool_warm() {
         let pool: ObjectPool<Vec<u8>> = ObjectPool::new(10);
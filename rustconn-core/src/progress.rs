@@ -3,8 +3,9 @@
 //! This module provides traits and implementations for reporting progress
 //! during operations like imports, exports, and bulk operations.
 
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Trait for reporting progress during long operations.
 ///
@@ -199,10 +200,320 @@ where
     }
 }
 
+/// A stage registered with a [`CompositeProgressReporter`].
+#[derive(Debug, Clone)]
+struct ProgressStage {
+    name: String,
+    weight: u32,
+}
+
+/// Builder for [`CompositeProgressReporter`].
+pub struct CompositeProgressReporterBuilder<R: ProgressReporter> {
+    inner: R,
+    stages: Vec<ProgressStage>,
+}
+
+impl<R: ProgressReporter> CompositeProgressReporterBuilder<R> {
+    /// Registers the next stage with its weight relative to the other stages.
+    ///
+    /// A weight of zero is treated as one.
+    #[must_use]
+    pub fn stage(mut self, name: impl Into<String>, weight: u32) -> Self {
+        self.stages.push(ProgressStage {
+            name: name.into(),
+            weight: weight.max(1),
+        });
+        self
+    }
+
+    /// Builds the composite reporter.
+    #[must_use]
+    pub fn build(self) -> CompositeProgressReporter<R> {
+        CompositeProgressReporter {
+            inner: self.inner,
+            stages: self.stages,
+            percent: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// A progress reporter that combines several stages into one percentage.
+///
+/// Multi-stage operations, such as an import followed by testing each
+/// imported connection, report each stage from zero. This reporter maps every
+/// stage onto its weighted share of the whole operation and reports a single
+/// `(percent, 100)` to the inner reporter. The reported percentage never
+/// goes backward.
+///
+/// # Example
+///
+/// ```
+/// use rustconn_core::progress::{
+///     CompositeProgressReporter, NoOpProgressReporter, ProgressReporter,
+/// };
+///
+/// let progress = CompositeProgressReporter::builder(NoOpProgressReporter::new())
+///     .stage("import", 3)
+///     .stage("test", 1)
+///     .build();
+///
+/// progress.stage("import").unwrap().report(10, 10, "Imported");
+/// assert_eq!(progress.percent(), 75);
+/// ```
+pub struct CompositeProgressReporter<R: ProgressReporter> {
+    inner: R,
+    stages: Vec<ProgressStage>,
+    percent: AtomicUsize,
+}
+
+impl<R: ProgressReporter> CompositeProgressReporter<R> {
+    /// Starts building a composite reporter that reports to `inner`.
+    #[must_use]
+    pub const fn builder(inner: R) -> CompositeProgressReporterBuilder<R> {
+        CompositeProgressReporterBuilder {
+            inner,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Returns the reporter for the stage with the given name.
+    #[must_use]
+    pub fn stage(&self, name: &str) -> Option<StageProgressReporter<'_, R>> {
+        let index = self.stages.iter().position(|s| s.name == name)?;
+        Some(StageProgressReporter {
+            composite: self,
+            index,
+        })
+    }
+
+    /// Returns the number of registered stages.
+    #[must_use]
+    pub fn stage_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns the overall percentage reported so far.
+    #[must_use]
+    pub fn percent(&self) -> usize {
+        self.percent.load(Ordering::SeqCst)
+    }
+
+    /// Returns the inner reporter.
+    #[must_use]
+    pub const fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    fn report_stage(&self, index: usize, current: usize, total: usize, message: &str) {
+        let total_weight: u64 = self.stages.iter().map(|s| u64::from(s.weight)).sum();
+        let done_weight: u64 = self.stages[..index]
+            .iter()
+            .map(|s| u64::from(s.weight))
+            .sum();
+        let weight = u64::from(self.stages[index].weight);
+
+        // A stage with nothing to do is complete
+        let (current, total) = if total == 0 {
+            (1, 1)
+        } else {
+            (current.min(total) as u128, total as u128)
+        };
+        let numerator = (u128::from(done_weight) * total + u128::from(weight) * current) * 100;
+        let percent = usize::try_from(numerator / (u128::from(total_weight) * total))
+            .unwrap_or(100)
+            .min(100);
+
+        let previous = self.percent.fetch_max(percent, Ordering::SeqCst);
+        self.inner.report(previous.max(percent), 100, message);
+    }
+}
+
+/// Progress reporter for one stage of a [`CompositeProgressReporter`].
+///
+/// Cancellation is shared by all stages and follows the inner reporter.
+pub struct StageProgressReporter<'a, R: ProgressReporter> {
+    composite: &'a CompositeProgressReporter<R>,
+    index: usize,
+}
+
+impl<R: ProgressReporter> ProgressReporter for StageProgressReporter<'_, R> {
+    fn report(&self, current: usize, total: usize, message: &str) {
+        self.composite
+            .report_stage(self.index, current, total, message);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.composite.inner.is_cancelled()
+    }
+}
+
+/// Default weight given to the newest rate sample by [`EstimatingProgressReporter`].
+pub const DEFAULT_RATE_SMOOTHING: f64 = 0.3;
+
+/// Rate tracking state of an [`EstimatingProgressReporter`].
+#[derive(Debug, Default)]
+struct RateEstimate {
+    last_sample: Option<(Instant, usize)>,
+    items_per_second: Option<f64>,
+    current: usize,
+    total: usize,
+}
+
+/// A progress reporter that estimates throughput and time remaining.
+///
+/// Wraps another reporter and forwards every update unchanged, timing the
+/// updates as they pass through. The rate is smoothed with an exponential
+/// moving average so a single slow or fast item does not make the estimate
+/// jump. While the total is unknown (`total == 0`) no estimate is given.
+///
+/// # Example
+///
+/// ```
+/// use rustconn_core::progress::{
+///     EstimatingProgressReporter, NoOpProgressReporter, ProgressReporter,
+/// };
+///
+/// let progress = EstimatingProgressReporter::new(NoOpProgressReporter::new());
+/// progress.report(0, 100, "Importing");
+/// // No rate is known until items have completed
+/// assert!(progress.estimated_remaining().is_none());
+/// ```
+pub struct EstimatingProgressReporter<R: ProgressReporter> {
+    inner: R,
+    smoothing: f64,
+    estimate: Mutex<RateEstimate>,
+}
+
+impl<R: ProgressReporter> EstimatingProgressReporter<R> {
+    /// Creates an estimating reporter that forwards updates to `inner`.
+    #[must_use]
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            smoothing: DEFAULT_RATE_SMOOTHING,
+            estimate: Mutex::new(RateEstimate::default()),
+        }
+    }
+
+    /// Sets the weight of the newest rate sample, clamped to `0.01..=1.0`.
+    ///
+    /// Lower values give a steadier estimate that reacts more slowly to
+    /// changes in throughput; `1.0` disables smoothing.
+    #[must_use]
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = if smoothing.is_nan() {
+            DEFAULT_RATE_SMOOTHING
+        } else {
+            smoothing.clamp(0.01, 1.0)
+        };
+        self
+    }
+
+    /// Returns the inner reporter.
+    #[must_use]
+    pub const fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the smoothed throughput in items per second.
+    ///
+    /// Returns `None` until at least one item has completed after the first
+    /// update.
+    #[must_use]
+    pub fn items_per_second(&self) -> Option<f64> {
+        self.lock().items_per_second
+    }
+
+    /// Returns the estimated time until the operation completes.
+    ///
+    /// Returns `None` while the total is unknown or no rate has been
+    /// measured yet, and zero once every item is done.
+    #[must_use]
+    pub fn estimated_remaining(&self) -> Option<Duration> {
+        let (current, total, rate) = {
+            let estimate = self.lock();
+            (estimate.current, estimate.total, estimate.items_per_second)
+        };
+        if total == 0 {
+            return None;
+        }
+        if current >= total {
+            return Some(Duration::ZERO);
+        }
+        let rate = rate.filter(|r| *r > 0.0)?;
+        let remaining = (total - current) as f64 / rate;
+        Duration::try_from_secs_f64(remaining).ok()
+    }
+
+    /// Returns the remaining time formatted for display, e.g. `~42s remaining`.
+    #[must_use]
+    pub fn remaining_text(&self) -> Option<String> {
+        self.estimated_remaining().map(format_remaining)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RateEstimate> {
+        self.estimate
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn record(&self, current: usize, total: usize, now: Instant) {
+        let mut estimate = self.lock();
+        estimate.total = total;
+
+        match estimate.last_sample {
+            // Progress restarted: earlier samples no longer apply
+            Some((_, last)) if current < last => {
+                estimate.last_sample = Some((now, current));
+                estimate.items_per_second = None;
+            }
+            Some((at, last)) if current > last => {
+                let elapsed = now.saturating_duration_since(at).as_secs_f64();
+                if elapsed > 0.0 {
+                    let sample = (current - last) as f64 / elapsed;
+                    estimate.items_per_second = Some(
+                        estimate
+                            .items_per_second
+                            .map_or(sample, |rate| self.smoothing.mul_add(sample - rate, rate)),
+                    );
+                    estimate.last_sample = Some((now, current));
+                }
+            }
+            // Repeated updates for the same item keep the original timestamp
+            Some(_) => {}
+            None => estimate.last_sample = Some((now, current)),
+        }
+        estimate.current = current;
+    }
+}
+
+impl<R: ProgressReporter> ProgressReporter for EstimatingProgressReporter<R> {
+    fn report(&self, current: usize, total: usize, message: &str) {
+        self.record(current, total, Instant::now());
+        self.inner.report(current, total, message);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
+
+/// Formats a remaining duration as `~42s remaining`, `~3m 20s remaining`
+/// or `~1h 5m remaining`.
+#[must_use]
+pub fn format_remaining(remaining: Duration) -> String {
+    // Round up so the estimate does not read "~0s" while work remains
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    match secs {
+        0..=59 => format!("~{secs}s remaining"),
+        60..=3599 => format!("~{}m {}s remaining", secs / 60, secs % 60),
+        _ => format!("~{}h {}m remaining", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::AtomicUsize;
 
     #[test]
     fn test_callback_reporter_invokes_callback() {
@@ -244,6 +555,116 @@ mod tests {
         assert!(handle.is_cancelled());
     }
 
+    #[test]
+    fn test_composite_reporter_weights_stages() {
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let reported_clone = Arc::clone(&reported);
+        let inner = CallbackProgressReporter::new(move |current, total, _| {
+            reported_clone.lock().unwrap().push((current, total));
+        });
+        let progress = CompositeProgressReporter::builder(inner)
+            .stage("import", 3)
+            .stage("test", 1)
+            .build();
+        assert_eq!(progress.stage_count(), 2);
+        assert!(progress.stage("missing").is_none());
+
+        let import = progress.stage("import").unwrap();
+        let test = progress.stage("test").unwrap();
+        import.report(5, 10, "Importing");
+        import.report(10, 10, "Imported");
+        // The second stage starts from zero without moving the bar back
+        test.report(0, 4, "Testing");
+        test.report(2, 4, "Testing");
+        test.report(0, 0, "Nothing to test");
+
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![(37, 100), (75, 100), (75, 100), (87, 100), (100, 100)]
+        );
+
+        // Out-of-order updates never lower the overall percentage
+        import.report(0, 10, "Late update");
+        assert_eq!(progress.percent(), 100);
+    }
+
+    #[test]
+    fn test_composite_reporter_forwards_cancellation() {
+        let progress =
+            CompositeProgressReporter::builder(CallbackProgressReporter::new(|_, _, _| {}))
+                .stage("import", 1)
+                .stage("test", 0)
+                .build();
+        let import = progress.stage("import").unwrap();
+        let test = progress.stage("test").unwrap();
+        assert!(!import.is_cancelled());
+
+        progress.inner().cancel();
+        assert!(import.is_cancelled());
+        assert!(test.is_cancelled());
+    }
+
+    #[test]
+    fn test_estimating_reporter_smooths_rate() {
+        let progress = EstimatingProgressReporter::new(NoOpProgressReporter::new());
+        let start = Instant::now();
+        progress.record(0, 100, start);
+        assert!(progress.items_per_second().is_none());
+        assert!(progress.estimated_remaining().is_none());
+
+        progress.record(10, 100, start + Duration::from_secs(1));
+        assert_eq!(progress.items_per_second(), Some(10.0));
+        assert_eq!(progress.estimated_remaining(), Some(Duration::from_secs(9)));
+
+        // A slow item moves the estimate only part of the way
+        progress.record(20, 100, start + Duration::from_secs(3));
+        let rate = progress.items_per_second().unwrap();
+        assert!((rate - 8.5).abs() < 1e-9, "rate was {rate}");
+
+        // Repeated reports for the same item do not skew the rate
+        progress.record(20, 100, start + Duration::from_secs(4));
+        progress.record(30, 100, start + Duration::from_secs(4));
+        assert!(progress.items_per_second().unwrap() > 4.0);
+
+        progress.record(100, 100, start + Duration::from_secs(10));
+        assert_eq!(progress.estimated_remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_estimating_reporter_indeterminate_and_restart() {
+        let progress = EstimatingProgressReporter::new(CallbackProgressReporter::new(|_, _, _| {}))
+            .with_smoothing(1.0);
+        let start = Instant::now();
+        progress.record(0, 0, start);
+        progress.record(5, 0, start + Duration::from_secs(1));
+        assert_eq!(progress.items_per_second(), Some(5.0));
+        assert!(progress.estimated_remaining().is_none());
+        assert!(progress.remaining_text().is_none());
+
+        progress.record(0, 10, start + Duration::from_secs(2));
+        assert!(progress.items_per_second().is_none());
+
+        progress.inner().cancel();
+        assert!(progress.is_cancelled());
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_secs(42)), "~42s remaining");
+        assert_eq!(
+            format_remaining(Duration::from_millis(100)),
+            "~1s remaining"
+        );
+        assert_eq!(
+            format_remaining(Duration::from_secs(200)),
+            "~3m 20s remaining"
+        );
+        assert_eq!(
+            format_remaining(Duration::from_secs(3900)),
+            "~1h 5m remaining"
+        );
+    }
+
     #[test]
     fn test_noop_reporter() {
         let reporter = NoOpProgressReporter::new();
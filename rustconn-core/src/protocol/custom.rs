@@ -0,0 +1,164 @@
+//! User-defined protocol handlers
+//!
+//! A custom protocol is a command template such as `mosh {user}@{host} -p {port}`.
+//! The template is split on whitespace into an argument vector and each
+//! placeholder is substituted from the connection, so values are never
+//! interpreted by a shell.
+
+use crate::error::ProtocolError;
+use crate::models::Connection;
+
+use super::{Protocol, ProtocolResult};
+
+/// Placeholders recognized in custom protocol templates
+pub const CUSTOM_PROTOCOL_PLACEHOLDERS: &[&str] = &["host", "port", "user"];
+
+/// Protocol handler built from a user-supplied command template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomProtocol {
+    /// Protocol identifier chosen by the user
+    name: String,
+    /// Template split into arguments; the first is the program
+    template: Vec<String>,
+}
+
+impl CustomProtocol {
+    /// Creates a custom protocol handler
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProtocolError::InvalidConfig` if the name or template is empty,
+    /// a brace is unbalanced, or the template references an unknown placeholder.
+    pub fn new(name: impl Into<String>, command_template: &str) -> ProtocolResult<Self> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(ProtocolError::InvalidConfig(
+                "Custom protocol name cannot be empty".to_string(),
+            ));
+        }
+
+        let template: Vec<String> = command_template
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        if template.is_empty() {
+            return Err(ProtocolError::InvalidConfig(format!(
+                "Command template for '{name}' is empty"
+            )));
+        }
+        for arg in &template {
+            for placeholder in placeholders(arg)? {
+                if !CUSTOM_PROTOCOL_PLACEHOLDERS.contains(&placeholder) {
+                    return Err(ProtocolError::InvalidConfig(format!(
+                        "Unknown placeholder '{{{placeholder}}}' in command template for '{name}'"
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { name, template })
+    }
+
+    /// Returns the command template arguments
+    #[must_use]
+    pub fn template(&self) -> &[String] {
+        &self.template
+    }
+}
+
+/// Returns the placeholder names referenced by one template argument
+fn placeholders(arg: &str) -> ProtocolResult<Vec<&str>> {
+    let mut names = Vec::new();
+    let mut rest = arg;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(ProtocolError::InvalidConfig(format!(
+                "Unbalanced '}}' in template argument '{arg}'"
+            )));
+        }
+        let Some(len) = rest[start + 1..].find('}') else {
+            return Err(ProtocolError::InvalidConfig(format!(
+                "Unclosed '{{' in template argument '{arg}'"
+            )));
+        };
+        names.push(&rest[start + 1..start + 1 + len]);
+        rest = &rest[start + len + 2..];
+    }
+    Ok(names)
+}
+
+impl Protocol for CustomProtocol {
+    fn protocol_id(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.name
+    }
+
+    fn default_port(&self) -> u16 {
+        0
+    }
+
+    fn validate_connection(&self, connection: &Connection) -> ProtocolResult<()> {
+        if connection.host.is_empty() {
+            return Err(ProtocolError::InvalidConfig(
+                "Host cannot be empty".to_string(),
+            ));
+        }
+        let needs_user = self.template.iter().any(|arg| arg.contains("{user}"));
+        if needs_user && connection.username.is_none() {
+            return Err(ProtocolError::InvalidConfig(format!(
+                "Protocol '{}' requires a username",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::literal_string_with_formatting_args)] // Template placeholders, not format args
+    fn build_command(&self, connection: &Connection) -> Option<Vec<String>> {
+        let port = connection.port.to_string();
+        let user = connection.username.as_deref().unwrap_or_default();
+        Some(
+            self.template
+                .iter()
+                .map(|arg| {
+                    arg.replace("{host}", &connection.host)
+                        .replace("{port}", &port)
+                        .replace("{user}", user)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_command_substitutes_placeholders() {
+        let protocol =
+            CustomProtocol::new("mosh", "mosh --ssh=ssh -p{port} {user}@{host}").unwrap();
+        let connection = Connection::new_ssh("box".to_string(), "example.org".to_string(), 2222)
+            .with_username("alice");
+
+        assert_eq!(protocol.protocol_id(), "mosh");
+        assert!(protocol.validate_connection(&connection).is_ok());
+        assert_eq!(
+            protocol.build_command(&connection).unwrap(),
+            vec!["mosh", "--ssh=ssh", "-p2222", "alice@example.org"]
+        );
+    }
+
+    #[test]
+    fn test_template_validation() {
+        assert!(CustomProtocol::new("x", "   ").is_err());
+        assert!(CustomProtocol::new("", "cmd {host}").is_err());
+        assert!(CustomProtocol::new("x", "cmd {hostname}").is_err());
+        assert!(CustomProtocol::new("x", "cmd {host").is_err());
+        assert!(CustomProtocol::new("x", "cmd host}").is_err());
+        assert!(CustomProtocol::new("x", "cmd {host}:{port}").is_ok());
+    }
+}
@@ -2,9 +2,19 @@
 //!
 //! This module provides functionality to detect installed protocol clients
 //! (SSH, RDP, VNC) and retrieve their version information.
+//!
+//! Zero Trust CLI probes spawn external processes, so their results can be
+//! cached per provider with [`ClientDetectionCache`].
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::models::ZeroTrustProvider;
 
 /// Information about a detected protocol client
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,6 +29,8 @@ pub struct ClientInfo {
     pub installed: bool,
     /// Installation hint for missing clients
     pub install_hint: Option<String>,
+    /// Whether the client runs natively on Wayland (not through `XWayland`)
+    pub wayland_native: bool,
 }
 
 impl ClientInfo {
@@ -31,9 +43,17 @@ impl ClientInfo {
             version,
             installed: true,
             install_hint: None,
+            wayland_native: false,
         }
     }
 
+    /// Marks whether the client runs natively on Wayland
+    #[must_use]
+    pub const fn with_wayland_native(mut self, wayland_native: bool) -> Self {
+        self.wayland_native = wayland_native;
+        self
+    }
+
     /// Creates a new `ClientInfo` for a missing client
     #[must_use]
     pub fn not_installed(name: impl Into<String>, install_hint: impl Into<String>) -> Self {
@@ -43,6 +63,7 @@ impl ClientInfo {
             version: None,
             installed: false,
             install_hint: Some(install_hint.into()),
+            wayland_native: false,
         }
     }
 }
@@ -83,77 +104,209 @@ pub fn detect_ssh_client() -> ClientInfo {
     )
 }
 
-/// Detects the RDP client on the system
-///
-/// Checks for `xfreerdp3`, `xfreerdp`, or `rdesktop` binaries and extracts version information.
-#[must_use]
-pub fn detect_rdp_client() -> ClientInfo {
-    // Try xfreerdp3 first (FreeRDP 3.x)
-    if let Some(info) = try_detect_client("FreeRDP", "xfreerdp3", &["--version"]) {
-        return info;
-    }
+/// Display server support of a graphical client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DisplaySupport {
+    /// Runs through X11 (`XWayland` on a Wayland session)
+    X11,
+    /// Runs natively on both X11 and Wayland (GTK, Qt, SDL)
+    Native,
+    /// Requires a Wayland compositor
+    WaylandOnly,
+}
 
-    // Try xfreerdp (FreeRDP 2.x)
-    if let Some(info) = try_detect_client("FreeRDP", "xfreerdp", &["--version"]) {
-        return info;
+/// A client binary probed during detection
+#[derive(Debug, Clone, Copy)]
+struct ClientCandidate {
+    /// Display name of the client
+    name: &'static str,
+    /// Binary name looked up in `PATH`
+    binary: &'static str,
+    /// Arguments that print the version
+    version_args: &'static [&'static str],
+    /// Display server support
+    display: DisplaySupport,
+}
+
+impl ClientCandidate {
+    const fn new(
+        name: &'static str,
+        binary: &'static str,
+        version_args: &'static [&'static str],
+        display: DisplaySupport,
+    ) -> Self {
+        Self {
+            name,
+            binary,
+            version_args,
+            display,
+        }
     }
 
-    // Try rdesktop as fallback
-    if let Some(info) = try_detect_client("rdesktop", "rdesktop", &["--version"]) {
-        return info;
+    const fn wayland_native(&self) -> bool {
+        !matches!(self.display, DisplaySupport::X11)
     }
+}
 
-    ClientInfo::not_installed(
-        "RDP Client",
-        "Install FreeRDP: sudo apt install freerdp2-x11 (Debian/Ubuntu) or sudo dnf install freerdp (Fedora)",
-    )
+/// RDP clients in order of preference outside Wayland
+const RDP_CLIENTS: &[ClientCandidate] = &[
+    ClientCandidate::new("FreeRDP", "xfreerdp3", &["--version"], DisplaySupport::X11),
+    ClientCandidate::new("FreeRDP", "xfreerdp", &["--version"], DisplaySupport::X11),
+    ClientCandidate::new("rdesktop", "rdesktop", &["--version"], DisplaySupport::X11),
+    ClientCandidate::new(
+        "FreeRDP",
+        "wlfreerdp3",
+        &["--version"],
+        DisplaySupport::WaylandOnly,
+    ),
+    ClientCandidate::new(
+        "FreeRDP",
+        "wlfreerdp",
+        &["--version"],
+        DisplaySupport::WaylandOnly,
+    ),
+    ClientCandidate::new(
+        "FreeRDP",
+        "sdl-freerdp3",
+        &["--version"],
+        DisplaySupport::Native,
+    ),
+    ClientCandidate::new(
+        "FreeRDP",
+        "sdl-freerdp",
+        &["--version"],
+        DisplaySupport::Native,
+    ),
+];
+
+/// VNC viewers in order of preference outside Wayland
+const VNC_CLIENTS: &[ClientCandidate] = &[
+    // TigerVNC, TightVNC - most common and feature-rich
+    ClientCandidate::new("VNC Viewer", "vncviewer", &["-h"], DisplaySupport::X11),
+    ClientCandidate::new("TigerVNC", "tigervnc", &["-h"], DisplaySupport::X11),
+    ClientCandidate::new(
+        "GTK-VNC Viewer",
+        "gvncviewer",
+        &["--help"],
+        DisplaySupport::Native,
+    ),
+    ClientCandidate::new(
+        "RealVNC Viewer",
+        "xvnc4viewer",
+        &["-h"],
+        DisplaySupport::X11,
+    ),
+    // GNOME Vinagre (deprecated but still available)
+    ClientCandidate::new("Vinagre", "vinagre", &["--version"], DisplaySupport::Native),
+    ClientCandidate::new("Remmina", "remmina", &["--version"], DisplaySupport::Native),
+    ClientCandidate::new("KRDC", "krdc", &["--version"], DisplaySupport::Native),
+    ClientCandidate::new("wlvncc", "wlvncc", &["--help"], DisplaySupport::WaylandOnly),
+];
+
+/// Returns true when running inside a Wayland session (`WAYLAND_DISPLAY` is set)
+#[must_use]
+pub fn is_wayland_session() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some_and(|display| !display.is_empty())
 }
 
-/// Detects the VNC client on the system
+/// Orders candidates for the current display server
 ///
-/// Checks for various VNC viewer binaries and extracts version information.
-/// Supported viewers: vncviewer (TigerVNC/TightVNC), gvncviewer, xvnc4viewer, vinagre, remmina, krdc
-#[must_use]
-pub fn detect_vnc_client() -> ClientInfo {
-    // Try vncviewer (TigerVNC/TightVNC) - most common
-    if let Some(info) = try_detect_client("VNC Viewer", "vncviewer", &["-h"]) {
-        return info;
+/// On Wayland, native clients come first and X11 clients are the fallback.
+/// Elsewhere, Wayland-only clients are skipped.
+fn ordered_candidates(candidates: &[ClientCandidate], wayland: bool) -> Vec<ClientCandidate> {
+    if wayland {
+        let (native, x11): (Vec<_>, Vec<_>) = candidates.iter().partition(|c| c.wayland_native());
+        native.into_iter().chain(x11).collect()
+    } else {
+        candidates
+            .iter()
+            .filter(|c| c.display != DisplaySupport::WaylandOnly)
+            .copied()
+            .collect()
     }
+}
 
-    // Try tigervnc specifically
-    if let Some(info) = try_detect_client("TigerVNC", "tigervnc", &["-h"]) {
-        return info;
-    }
+/// Finds the first available candidate in `search_path`
+fn select_client(
+    candidates: &[ClientCandidate],
+    search_path: &OsStr,
+    wayland: bool,
+) -> Option<(ClientCandidate, PathBuf)> {
+    ordered_candidates(candidates, wayland)
+        .into_iter()
+        .find_map(|c| which_binary_in(c.binary, search_path).map(|path| (c, path)))
+}
 
-    // Try gvncviewer (GTK-VNC viewer)
-    if let Some(info) = try_detect_client("GTK-VNC Viewer", "gvncviewer", &["--help"]) {
-        return info;
-    }
+/// Detects the first available candidate and its version
+fn detect_candidate(
+    candidates: &[ClientCandidate],
+    search_path: &OsStr,
+    wayland: bool,
+) -> Option<ClientInfo> {
+    let (candidate, path) = select_client(candidates, search_path, wayland)?;
+    let version = get_version(&path, candidate.version_args);
+    Some(
+        ClientInfo::installed(candidate.name, path, version)
+            .with_wayland_native(candidate.wayland_native()),
+    )
+}
 
-    // Try xvnc4viewer (RealVNC)
-    if let Some(info) = try_detect_client("RealVNC Viewer", "xvnc4viewer", &["-h"]) {
-        return info;
-    }
+/// Returns the `PATH` environment variable
+fn search_path() -> OsString {
+    std::env::var_os("PATH").unwrap_or_default()
+}
 
-    // Try vinagre (GNOME Remote Desktop Viewer - deprecated but still available)
-    if let Some(info) = try_detect_client("Vinagre", "vinagre", &["--version"]) {
-        return info;
-    }
+/// Detects the RDP client on the system
+///
+/// Checks for `xfreerdp3`, `xfreerdp`, or `rdesktop` binaries and extracts version information.
+/// In a Wayland session the native `wlfreerdp3`, `wlfreerdp` and `sdl-freerdp`
+/// clients are preferred, falling back to the X11 clients.
+#[must_use]
+pub fn detect_rdp_client() -> ClientInfo {
+    detect_candidate(RDP_CLIENTS, &search_path(), is_wayland_session()).unwrap_or_else(|| {
+        ClientInfo::not_installed(
+            "RDP Client",
+            "Install FreeRDP: sudo apt install freerdp2-x11 (Debian/Ubuntu) or sudo dnf install freerdp (Fedora)",
+        )
+    })
+}
 
-    // Try remmina (supports VNC among other protocols)
-    if let Some(info) = try_detect_client("Remmina", "remmina", &["--version"]) {
-        return info;
-    }
+/// Selects the FreeRDP client used to launch external RDP sessions
+///
+/// Follows the same display-server preference as [`detect_rdp_client`] but
+/// skips `rdesktop`, which takes different arguments. The version is not
+/// probed. Returns `None` if no FreeRDP client is installed.
+#[must_use]
+pub fn detect_freerdp_client() -> Option<ClientInfo> {
+    select_freerdp_client(&search_path(), is_wayland_session())
+}
 
-    // Try krdc (KDE Remote Desktop Client)
-    if let Some(info) = try_detect_client("KRDC", "krdc", &["--version"]) {
-        return info;
-    }
+/// Finds the preferred FreeRDP client in `search_path`
+fn select_freerdp_client(search_path: &OsStr, wayland: bool) -> Option<ClientInfo> {
+    let freerdp: Vec<ClientCandidate> = RDP_CLIENTS
+        .iter()
+        .filter(|c| c.name == "FreeRDP")
+        .copied()
+        .collect();
+    select_client(&freerdp, search_path, wayland).map(|(candidate, path)| {
+        ClientInfo::installed(candidate.name, path, None)
+            .with_wayland_native(candidate.wayland_native())
+    })
+}
 
-    ClientInfo::not_installed(
-        "VNC Client",
-        "Install TigerVNC: sudo apt install tigervnc-viewer (Debian/Ubuntu) or sudo dnf install tigervnc (Fedora). Alternatives: gvncviewer, remmina, krdc",
-    )
+/// Detects the VNC client on the system
+///
+/// Checks for various VNC viewer binaries and extracts version information.
+/// Supported viewers: vncviewer (TigerVNC/TightVNC), gvncviewer, xvnc4viewer, vinagre, remmina, krdc, wlvncc.
+/// In a Wayland session native viewers are preferred over X11 ones.
+#[must_use]
+pub fn detect_vnc_client() -> ClientInfo {
+    detect_candidate(VNC_CLIENTS, &search_path(), is_wayland_session()).unwrap_or_else(|| {
+        ClientInfo::not_installed(
+            "VNC Client",
+            "Install TigerVNC: sudo apt install tigervnc-viewer (Debian/Ubuntu) or sudo dnf install tigervnc (Fedora). Alternatives: gvncviewer, remmina, krdc",
+        )
+    })
 }
 
 /// Returns the path to the first available VNC viewer binary
@@ -165,24 +318,7 @@ pub fn detect_vnc_client() -> ClientInfo {
 /// `Some(PathBuf)` with the path to the VNC viewer binary, or `None` if not found
 #[must_use]
 pub fn detect_vnc_viewer_path() -> Option<PathBuf> {
-    // VNC viewers in order of preference
-    let viewers = [
-        "vncviewer",   // TigerVNC, TightVNC - most common and feature-rich
-        "tigervnc",    // TigerVNC specific binary name
-        "gvncviewer",  // GTK-VNC viewer
-        "xvnc4viewer", // RealVNC
-        "vinagre",     // GNOME Vinagre (deprecated but still available)
-        "remmina",     // Remmina (supports VNC)
-        "krdc",        // KDE Remote Desktop Client
-    ];
-
-    for viewer in viewers {
-        if let Some(path) = which_binary(viewer) {
-            return Some(path);
-        }
-    }
-
-    None
+    select_client(VNC_CLIENTS, &search_path(), is_wayland_session()).map(|(_, path)| path)
 }
 
 /// Returns the name of the first available VNC viewer
@@ -194,24 +330,8 @@ pub fn detect_vnc_viewer_path() -> Option<PathBuf> {
 /// `Some(String)` with the VNC viewer binary name, or `None` if not found
 #[must_use]
 pub fn detect_vnc_viewer_name() -> Option<String> {
-    // VNC viewers in order of preference
-    let viewers = [
-        "vncviewer",   // TigerVNC, TightVNC - most common and feature-rich
-        "tigervnc",    // TigerVNC specific binary name
-        "gvncviewer",  // GTK-VNC viewer
-        "xvnc4viewer", // RealVNC
-        "vinagre",     // GNOME Vinagre (deprecated but still available)
-        "remmina",     // Remmina (supports VNC)
-        "krdc",        // KDE Remote Desktop Client
-    ];
-
-    for viewer in viewers {
-        if which_binary(viewer).is_some() {
-            return Some(viewer.to_string());
-        }
-    }
-
-    None
+    select_client(VNC_CLIENTS, &search_path(), is_wayland_session())
+        .map(|(candidate, _)| candidate.binary.to_string())
 }
 
 // ============================================================================
@@ -314,13 +434,146 @@ pub fn detect_boundary() -> ClientInfo {
     )
 }
 
+/// Detects the CLI used by a Zero Trust provider
+///
+/// This always probes the system; use [`ClientDetectionCache`] to avoid
+/// re-running the CLI on every call.
+#[must_use]
+pub fn detect_zero_trust_client(provider: ZeroTrustProvider) -> ClientInfo {
+    match provider {
+        ZeroTrustProvider::AwsSsm => detect_aws_cli(),
+        ZeroTrustProvider::GcpIap => detect_gcloud_cli(),
+        ZeroTrustProvider::AzureBastion | ZeroTrustProvider::AzureSsh => detect_azure_cli(),
+        ZeroTrustProvider::OciBastion => detect_oci_cli(),
+        ZeroTrustProvider::CloudflareAccess => detect_cloudflared(),
+        ZeroTrustProvider::Teleport => detect_teleport(),
+        ZeroTrustProvider::TailscaleSsh => detect_tailscale(),
+        ZeroTrustProvider::Boundary => detect_boundary(),
+        ZeroTrustProvider::GenericProxy => detect_ssh_client(),
+        // Generic commands run through the shell, which has no version flag
+        ZeroTrustProvider::Generic => which_binary("sh").map_or_else(
+            || ClientInfo::not_installed("POSIX shell", "Install a POSIX shell (sh)"),
+            |path| ClientInfo::installed("POSIX shell", path, None),
+        ),
+    }
+}
+
+// ============================================================================
+// Detection Cache
+// ============================================================================
+
+/// Default time a cached detection result stays valid
+pub const DEFAULT_DETECTION_TTL: Duration = Duration::from_secs(300);
+
+/// Process-wide cache used by [`detect_provider_client`]
+static DETECTION_CACHE: LazyLock<ClientDetectionCache> = LazyLock::new(ClientDetectionCache::new);
+
+/// Cache of Zero Trust CLI detection results, keyed by provider
+///
+/// A result is reused until its TTL expires, so opening the connection
+/// dialog repeatedly does not re-run every CLI. The cached [`ClientInfo`]
+/// keeps the binary path and version string for display in settings.
+#[derive(Debug)]
+pub struct ClientDetectionCache {
+    /// How long a result stays valid
+    ttl: Duration,
+    /// Cached results with the time they were probed
+    entries: Mutex<HashMap<ZeroTrustProvider, (Instant, ClientInfo)>>,
+}
+
+impl Default for ClientDetectionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientDetectionCache {
+    /// Creates an empty cache with [`DEFAULT_DETECTION_TTL`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_DETECTION_TTL)
+    }
+
+    /// Creates an empty cache with a custom TTL
+    #[must_use]
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cache TTL
+    #[must_use]
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the CLI for a provider, probing only if the cached result expired
+    #[must_use]
+    pub fn detect_provider(&self, provider: ZeroTrustProvider) -> ClientInfo {
+        self.detect_provider_with(provider, Instant::now(), detect_zero_trust_client)
+    }
+
+    /// Returns the cached result for a provider, or probes with `probe`
+    ///
+    /// The probe runs when no result is cached or the cached one is older
+    /// than the TTL at `now`. The cache is not locked while probing, so a
+    /// slow CLI does not block lookups for other providers; concurrent
+    /// misses for the same provider may each probe once.
+    pub fn detect_provider_with(
+        &self,
+        provider: ZeroTrustProvider,
+        now: Instant,
+        probe: impl FnOnce(ZeroTrustProvider) -> ClientInfo,
+    ) -> ClientInfo {
+        if let Some((detected_at, info)) = self.lock_entries().get(&provider) {
+            if now.saturating_duration_since(*detected_at) < self.ttl {
+                return info.clone();
+            }
+        }
+        let info = probe(provider);
+        self.lock_entries().insert(provider, (now, info.clone()));
+        info
+    }
+
+    /// Locks the cached entries, recovering from a poisoned lock
+    fn lock_entries(&self) -> MutexGuard<'_, HashMap<ZeroTrustProvider, (Instant, ClientInfo)>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the cached result for a provider without probing
+    #[must_use]
+    pub fn cached(&self, provider: ZeroTrustProvider) -> Option<ClientInfo> {
+        self.lock_entries()
+            .get(&provider)
+            .map(|(_, info)| info.clone())
+    }
+
+    /// Drops all cached results so the next lookup re-probes
+    pub fn refresh_detection(&self) {
+        self.lock_entries().clear();
+    }
+}
+
+/// Returns the CLI for a provider from the process-wide detection cache
+#[must_use]
+pub fn detect_provider_client(provider: ZeroTrustProvider) -> ClientInfo {
+    DETECTION_CACHE.detect_provider(provider)
+}
+
+/// Clears the process-wide detection cache, forcing a re-probe
+pub fn refresh_detection() {
+    DETECTION_CACHE.refresh_detection();
+}
+
 /// Attempts to detect a specific client binary
 fn try_detect_client(name: &str, binary: &str, version_args: &[&str]) -> Option<ClientInfo> {
     // First check if the binary exists in PATH
     let path = which_binary(binary)?;
 
     // Try to get version information
-    let version = get_version(binary, version_args);
+    let version = get_version(&path, version_args);
 
     Some(ClientInfo::installed(name, path, version))
 }
@@ -343,22 +596,21 @@ fn detect_client(
 
 /// Finds a binary in PATH
 fn which_binary(binary: &str) -> Option<PathBuf> {
-    // Use `which` command to find the binary
-    let output = Command::new("which").arg(binary).output().ok()?;
-
-    if output.status.success() {
-        let path_str = String::from_utf8_lossy(&output.stdout);
-        let path = path_str.trim();
-        if !path.is_empty() {
-            return Some(PathBuf::from(path));
-        }
-    }
+    which_binary_in(binary, &search_path())
+}
 
-    None
+/// Finds an executable binary in a `PATH`-style list of directories
+fn which_binary_in(binary: &str, search_path: &OsStr) -> Option<PathBuf> {
+    std::env::split_paths(search_path)
+        .map(|dir| dir.join(binary))
+        .find(|path| {
+            path.metadata()
+                .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        })
 }
 
 /// Gets version information from a binary
-fn get_version(binary: &str, args: &[&str]) -> Option<String> {
+fn get_version(binary: &Path, args: &[&str]) -> Option<String> {
     let output = Command::new(binary).args(args).output().ok()?;
 
     // Version info might be in stdout or stderr depending on the tool
@@ -479,6 +731,144 @@ mod tests {
         assert!(version.is_none());
     }
 
+    /// Creates executable stubs that print `FreeRDP version` in a temporary `PATH`
+    fn fake_path(binaries: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for binary in binaries {
+            let path = dir.path().join(binary);
+            std::fs::write(&path, "#!/bin/sh\necho \"This is FreeRDP version 3.5.0\"\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_rdp_selection_prefers_native_on_wayland() {
+        let dir = fake_path(&["xfreerdp", "wlfreerdp", "sdl-freerdp3"]);
+        let path = dir.path().as_os_str();
+
+        let info = detect_candidate(RDP_CLIENTS, path, true).unwrap();
+        assert_eq!(info.path, Some(dir.path().join("wlfreerdp")));
+        assert!(info.wayland_native);
+        assert_eq!(
+            info.version.as_deref(),
+            Some("This is FreeRDP version 3.5.0")
+        );
+
+        let info = detect_candidate(RDP_CLIENTS, path, false).unwrap();
+        assert_eq!(info.path, Some(dir.path().join("xfreerdp")));
+        assert!(!info.wayland_native);
+    }
+
+    #[test]
+    fn test_selection_falls_back_across_display_servers() {
+        // Only an X11 client: used on Wayland through XWayland
+        let dir = fake_path(&["xfreerdp3"]);
+        let (candidate, _) = select_client(RDP_CLIENTS, dir.path().as_os_str(), true).unwrap();
+        assert_eq!(candidate.binary, "xfreerdp3");
+
+        // Only a Wayland-only client: unusable without Wayland
+        let dir = fake_path(&["wlfreerdp"]);
+        assert!(select_client(RDP_CLIENTS, dir.path().as_os_str(), false).is_none());
+
+        // SDL runs on both
+        let dir = fake_path(&["sdl-freerdp"]);
+        assert!(select_client(RDP_CLIENTS, dir.path().as_os_str(), false).is_some());
+    }
+
+    #[test]
+    fn test_freerdp_selection_skips_rdesktop() {
+        let dir = fake_path(&["rdesktop", "xfreerdp", "wlfreerdp3"]);
+        let path = dir.path().as_os_str();
+
+        let info = select_freerdp_client(path, false).unwrap();
+        assert_eq!(info.path, Some(dir.path().join("xfreerdp")));
+        assert!(!info.wayland_native);
+        assert!(info.version.is_none());
+
+        let info = select_freerdp_client(path, true).unwrap();
+        assert_eq!(info.path, Some(dir.path().join("wlfreerdp3")));
+        assert!(info.wayland_native);
+
+        let dir = fake_path(&["rdesktop"]);
+        assert!(select_freerdp_client(dir.path().as_os_str(), false).is_none());
+    }
+
+    #[test]
+    fn test_vnc_selection_order() {
+        let dir = fake_path(&["vncviewer", "remmina", "wlvncc"]);
+        let path = dir.path().as_os_str();
+
+        let (candidate, _) = select_client(VNC_CLIENTS, path, true).unwrap();
+        assert_eq!(candidate.binary, "remmina");
+        let (candidate, _) = select_client(VNC_CLIENTS, path, false).unwrap();
+        assert_eq!(candidate.binary, "vncviewer");
+    }
+
+    #[test]
+    fn test_non_executable_files_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("xfreerdp"), "").unwrap();
+        assert!(which_binary_in("xfreerdp", dir.path().as_os_str()).is_none());
+    }
+
+    #[test]
+    fn test_detection_cache_reuses_until_ttl() {
+        let cache = ClientDetectionCache::with_ttl(Duration::from_secs(60));
+        let start = Instant::now();
+        let probes = std::cell::Cell::new(0);
+        let probe = |_| {
+            probes.set(probes.get() + 1);
+            ClientInfo::installed(
+                "AWS CLI",
+                PathBuf::from("/usr/bin/aws"),
+                Some("2.15".into()),
+            )
+        };
+
+        let info = cache.detect_provider_with(ZeroTrustProvider::AwsSsm, start, probe);
+        assert_eq!(info.path, Some(PathBuf::from("/usr/bin/aws")));
+        cache.detect_provider_with(
+            ZeroTrustProvider::AwsSsm,
+            start + Duration::from_secs(30),
+            probe,
+        );
+        assert_eq!(probes.get(), 1);
+
+        cache.detect_provider_with(
+            ZeroTrustProvider::AwsSsm,
+            start + Duration::from_secs(61),
+            probe,
+        );
+        assert_eq!(probes.get(), 2);
+
+        cache.refresh_detection();
+        assert!(cache.cached(ZeroTrustProvider::AwsSsm).is_none());
+        cache.detect_provider_with(
+            ZeroTrustProvider::AwsSsm,
+            start + Duration::from_secs(62),
+            probe,
+        );
+        assert_eq!(probes.get(), 3);
+    }
+
+    #[test]
+    fn test_detection_cache_unlocked_while_probing() {
+        let cache = ClientDetectionCache::new();
+        let now = Instant::now();
+        cache.detect_provider_with(ZeroTrustProvider::GcpIap, now, |_| {
+            ClientInfo::not_installed("gcloud CLI", "Install gcloud")
+        });
+
+        // Lookups from inside a running probe must not deadlock
+        let info = cache.detect_provider_with(ZeroTrustProvider::AwsSsm, now, |_| {
+            assert!(cache.cached(ZeroTrustProvider::GcpIap).is_some());
+            ClientInfo::not_installed("AWS CLI", "Install the AWS CLI")
+        });
+        assert!(!info.installed);
+        assert!(cache.cached(ZeroTrustProvider::AwsSsm).is_some());
+    }
+
     #[test]
     fn test_extract_version_string_truncates() {
         let long_line = "a".repeat(200);
@@ -13,6 +13,7 @@
 
 #![allow(clippy::needless_collect)]
 
+use super::ClientInfo;
 use crate::models::WindowGeometry;
 use std::path::PathBuf;
 
@@ -52,6 +53,8 @@ pub struct FreeRdpConfig {
     pub window_geometry: Option<WindowGeometry>,
     /// Whether to remember window position
     pub remember_window_position: bool,
+    /// Whether the client runs natively on Wayland (`wlfreerdp`, `sdl-freerdp`)
+    pub wayland_native: bool,
 }
 
 impl FreeRdpConfig {
@@ -71,6 +74,7 @@ impl FreeRdpConfig {
             extra_args: Vec::new(),
             window_geometry: None,
             remember_window_position: true,
+            wayland_native: false,
         }
     }
 
@@ -144,6 +148,29 @@ impl FreeRdpConfig {
         self.remember_window_position = remember;
         self
     }
+
+    /// Adapts the arguments to the detected client
+    #[must_use]
+    pub const fn with_client(mut self, client: &ClientInfo) -> Self {
+        self.wayland_native = client.wayland_native;
+        self
+    }
+
+    /// Sets whether the client runs natively on Wayland
+    #[must_use]
+    pub const fn with_wayland_native(mut self, wayland_native: bool) -> Self {
+        self.wayland_native = wayland_native;
+        self
+    }
+
+    /// Returns true if the arguments for this client include `/decorations`
+    ///
+    /// Wayland-native clients get server-side decorations from the
+    /// compositor and do not accept the flag.
+    #[must_use]
+    pub const fn uses_decorations_flag(&self) -> bool {
+        !self.wayland_native
+    }
 }
 
 /// Builds `FreeRDP` command-line arguments from configuration
@@ -202,10 +229,13 @@ pub fn build_freerdp_args(config: &FreeRdpConfig) -> Vec<String> {
     args.push("/dynamic-resolution".to_string());
 
     // Decorations flag for window controls (Requirement 6.1)
-    args.push("/decorations".to_string());
+    if config.uses_decorations_flag() {
+        args.push("/decorations".to_string());
+    }
 
     // Window geometry (Requirements 6.2, 6.3, 6.4)
-    if config.remember_window_position {
+    // Wayland clients cannot position their own windows
+    if config.remember_window_position && !config.wayland_native {
         if let Some(ref geometry) = config.window_geometry {
             args.push(format!("/x:{}", geometry.x));
             args.push(format!("/y:{}", geometry.y));
@@ -235,10 +265,11 @@ pub fn build_freerdp_args(config: &FreeRdpConfig) -> Vec<String> {
     }
 
     // Server address (must be last)
+    let host = crate::connection::host_for_uri(&config.host);
     if config.port == 3389 {
-        args.push(format!("/v:{}", config.host));
+        args.push(format!("/v:{host}"));
     } else {
-        args.push(format!("/v:{}:{}", config.host, config.port));
+        args.push(format!("/v:{host}:{}", config.port));
     }
 
     args
@@ -252,7 +283,8 @@ pub fn build_freerdp_args(config: &FreeRdpConfig) -> Vec<String> {
 ///
 /// # Returns
 ///
-/// `true` if the `/decorations` flag is present
+/// `true` if the `/decorations` flag is present. Arguments built for a
+/// Wayland-native client never contain it; see [`FreeRdpConfig::uses_decorations_flag`].
 #[must_use]
 pub fn has_decorations_flag(args: &[String]) -> bool {
     args.iter().any(|arg| arg == "/decorations")
@@ -371,6 +403,15 @@ mod tests {
         assert!(args.contains(&"/v:server.example.com:3390".to_string()));
     }
 
+    #[test]
+    fn test_build_freerdp_args_ipv6_host() {
+        let args = build_freerdp_args(&FreeRdpConfig::new("fe80::1"));
+        assert!(args.contains(&"/v:[fe80::1]".to_string()));
+
+        let args = build_freerdp_args(&FreeRdpConfig::new("[fe80::1]").with_port(3390));
+        assert!(args.contains(&"/v:[fe80::1]:3390".to_string()));
+    }
+
     #[test]
     fn test_build_freerdp_args_clipboard_disabled() {
         let config = FreeRdpConfig::new("server.example.com").with_clipboard(false);
@@ -421,4 +462,19 @@ mod tests {
         let drive_args: Vec<_> = args.iter().filter(|a| a.starts_with("/drive:")).collect();
         assert!(drive_args.is_empty());
     }
+
+    #[test]
+    fn test_build_freerdp_args_wayland_native_client() {
+        let client = ClientInfo::installed("FreeRDP", PathBuf::from("/usr/bin/wlfreerdp"), None)
+            .with_wayland_native(true);
+        let config = FreeRdpConfig::new("server.example.com")
+            .with_window_geometry(WindowGeometry::new(100, 200, 1280, 720))
+            .with_client(&client);
+        let args = build_freerdp_args(&config);
+
+        assert!(!config.uses_decorations_flag());
+        assert!(!has_decorations_flag(&args));
+        assert_eq!(extract_geometry_from_args(&args), None);
+        assert!(args.contains(&"/v:server.example.com".to_string()));
+    }
 }
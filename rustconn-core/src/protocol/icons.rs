@@ -30,6 +30,10 @@ pub enum CloudProvider {
     Tailscale,
     /// `HashiCorp` Boundary
     Boundary,
+    /// SSH through a custom `ProxyCommand`
+    ///
+    /// Always chosen by the user; [`detect_provider`] never returns it.
+    GenericProxy,
     /// Generic/unknown provider
     #[default]
     Generic,
@@ -54,6 +58,7 @@ impl CloudProvider {
             Self::Teleport => "emblem-system-symbolic", // Teleport - system/gear
             Self::Tailscale => "network-vpn-symbolic", // Tailscale - VPN
             Self::Boundary => "dialog-password-symbolic", // Boundary - password/lock
+            Self::GenericProxy => "network-wired-symbolic", // ProxyCommand - wired link
             Self::Generic => "system-run-symbolic",    // Generic - run command
         }
     }
@@ -70,6 +75,7 @@ impl CloudProvider {
             Self::Teleport => "Teleport",
             Self::Tailscale => "Tailscale",
             Self::Boundary => "Boundary",
+            Self::GenericProxy => "Proxy",
             Self::Generic => "Cloud",
         }
     }
@@ -86,6 +92,7 @@ impl CloudProvider {
             Self::Teleport,
             Self::Tailscale,
             Self::Boundary,
+            Self::GenericProxy,
             Self::Generic,
         ]
     }
@@ -163,6 +170,8 @@ pub const fn get_zero_trust_provider_icon(
 ///
 /// Analyzes the command to determine which cloud provider it belongs to.
 /// Returns `CloudProvider::Generic` if no specific provider is detected.
+/// A plain `ssh -o ProxyCommand=...` is not a cloud CLI, so
+/// `CloudProvider::GenericProxy` is left for the user to select.
 ///
 /// # Arguments
 /// * `command` - The CLI command string to analyze
@@ -373,6 +382,7 @@ impl ProviderIconCache {
             CloudProvider::Teleport => "teleport-logo.svg",
             CloudProvider::Tailscale => "tailscale-logo.svg",
             CloudProvider::Boundary => "boundary-logo.svg",
+            CloudProvider::GenericProxy => "proxy-symbolic.svg",
             CloudProvider::Generic => "cloud-symbolic.svg",
         };
         self.cache_dir.join(icon_name)
@@ -558,6 +568,10 @@ mod tests {
         assert_eq!(detect_provider("ssh user@host"), CloudProvider::Generic);
         assert_eq!(detect_provider("custom-command"), CloudProvider::Generic);
         assert_eq!(detect_provider(""), CloudProvider::Generic);
+        assert_eq!(
+            detect_provider("ssh -o ProxyCommand='nc %h %p' host"),
+            CloudProvider::Generic
+        );
     }
 
     #[test]
@@ -628,6 +642,7 @@ mod tests {
         assert!(all.contains(&CloudProvider::Gcloud));
         assert!(all.contains(&CloudProvider::Azure));
         assert!(all.contains(&CloudProvider::Generic));
-        assert_eq!(all.len(), 9);
+        assert!(all.contains(&CloudProvider::GenericProxy));
+        assert_eq!(all.len(), 10);
     }
 }
@@ -5,6 +5,7 @@
 //! Native session widgets will be implemented in later phases.
 
 mod cli;
+mod custom;
 mod detection;
 pub mod freerdp;
 pub mod icons;
@@ -15,11 +16,14 @@ mod ssh;
 mod vnc;
 
 pub use cli::{format_command_message, format_connection_message};
+pub use custom::{CustomProtocol, CUSTOM_PROTOCOL_PLACEHOLDERS};
 pub use detection::{
-    detect_aws_cli, detect_azure_cli, detect_boundary, detect_cloudflared, detect_gcloud_cli,
-    detect_oci_cli, detect_rdp_client, detect_ssh_client, detect_tailscale, detect_teleport,
-    detect_vnc_client, detect_vnc_viewer_name, detect_vnc_viewer_path, ClientDetectionResult,
-    ClientInfo,
+    detect_aws_cli, detect_azure_cli, detect_boundary, detect_cloudflared, detect_freerdp_client,
+    detect_gcloud_cli, detect_oci_cli, detect_provider_client, detect_rdp_client,
+    detect_ssh_client, detect_tailscale, detect_teleport, detect_vnc_client,
+    detect_vnc_viewer_name, detect_vnc_viewer_path, detect_zero_trust_client, is_wayland_session,
+    refresh_detection, ClientDetectionCache, ClientDetectionResult, ClientInfo,
+    DEFAULT_DETECTION_TTL,
 };
 pub use freerdp::{
     build_freerdp_args, extract_geometry_from_args, has_decorations_flag, FreeRdpConfig,
@@ -29,9 +33,9 @@ pub use icons::{
     CloudProvider, ProviderIconCache,
 };
 pub use rdp::RdpProtocol;
-pub use registry::ProtocolRegistry;
+pub use registry::{ProtocolRegistry, CUSTOM_PROTOCOL_PROPERTY};
 pub use spice::SpiceProtocol;
-pub use ssh::SshProtocol;
+pub use ssh::{ssh_session_established, SshProtocol, SSH_ERROR_EXIT_STATUS};
 pub use vnc::VncProtocol;
 
 use crate::error::ProtocolError;
@@ -48,10 +52,10 @@ pub type ProtocolResult<T> = Result<T, ProtocolError>;
 /// Note: Native session widget creation will be added in Phase 5-7.
 pub trait Protocol: Send + Sync {
     /// Returns the protocol identifier (e.g., "ssh", "rdp", "vnc")
-    fn protocol_id(&self) -> &'static str;
+    fn protocol_id(&self) -> &str;
 
     /// Returns human-readable protocol name
-    fn display_name(&self) -> &'static str;
+    fn display_name(&self) -> &str;
 
     /// Returns default port for this protocol
     fn default_port(&self) -> u16;
@@ -67,4 +71,11 @@ pub trait Protocol: Send + Sync {
     /// # Errors
     /// Returns `ProtocolError` if the connection configuration is invalid
     fn validate_connection(&self, connection: &Connection) -> ProtocolResult<()>;
+
+    /// Builds the external command (program followed by arguments) for a connection
+    ///
+    /// Built-in protocols launch through dedicated code paths and return `None`.
+    fn build_command(&self, _connection: &Connection) -> Option<Vec<String>> {
+        None
+    }
 }
@@ -3,9 +3,18 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::models::ProtocolType;
+use crate::models::{Connection, ProtocolType};
 
-use super::{Protocol, RdpProtocol, SpiceProtocol, SshProtocol, VncProtocol};
+use super::{
+    CustomProtocol, Protocol, ProtocolResult, RdpProtocol, SpiceProtocol, SshProtocol, VncProtocol,
+};
+use crate::error::ProtocolError;
+
+/// Custom property naming the user-defined protocol a connection launches with
+pub const CUSTOM_PROTOCOL_PROPERTY: &str = "protocol";
+
+/// Identifiers reserved for built-in protocols
+const BUILTIN_PROTOCOL_IDS: [&str; 5] = ["ssh", "rdp", "vnc", "spice", "zerotrust"];
 
 /// Registry for protocol handlers
 ///
@@ -13,28 +22,49 @@ use super::{Protocol, RdpProtocol, SpiceProtocol, SshProtocol, VncProtocol};
 /// by their identifier or protocol type. It is initialized with all supported
 /// protocols and provides thread-safe access to protocol implementations.
 pub struct ProtocolRegistry {
-    protocols: HashMap<&'static str, Arc<dyn Protocol>>,
+    protocols: HashMap<String, Arc<dyn Protocol>>,
 }
 
 impl ProtocolRegistry {
     /// Creates a new protocol registry with all supported protocols
     #[must_use]
     pub fn new() -> Self {
-        let mut protocols: HashMap<&'static str, Arc<dyn Protocol>> = HashMap::new();
+        let mut protocols: HashMap<String, Arc<dyn Protocol>> = HashMap::new();
 
         let ssh = Arc::new(SshProtocol::new());
         let rdp = Arc::new(RdpProtocol::new());
         let vnc = Arc::new(VncProtocol::new());
         let spice = Arc::new(SpiceProtocol::new());
 
-        protocols.insert(ssh.protocol_id(), ssh);
-        protocols.insert(rdp.protocol_id(), rdp);
-        protocols.insert(vnc.protocol_id(), vnc);
-        protocols.insert(spice.protocol_id(), spice);
+        protocols.insert(ssh.protocol_id().to_string(), ssh);
+        protocols.insert(rdp.protocol_id().to_string(), rdp);
+        protocols.insert(vnc.protocol_id().to_string(), vnc);
+        protocols.insert(spice.protocol_id().to_string(), spice);
 
         Self { protocols }
     }
 
+    /// Registers a user-defined protocol handler
+    ///
+    /// The command template is split on whitespace into arguments and may
+    /// reference the `{host}`, `{port}` and `{user}` placeholders. Registering
+    /// a name again replaces the previous custom handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProtocolError::InvalidConfig` if the name shadows a built-in
+    /// protocol or the template is invalid.
+    pub fn register_custom(&mut self, name: &str, command_template: &str) -> ProtocolResult<()> {
+        if BUILTIN_PROTOCOL_IDS.contains(&name) {
+            return Err(ProtocolError::InvalidConfig(format!(
+                "'{name}' is a built-in protocol"
+            )));
+        }
+        let protocol = CustomProtocol::new(name, command_template)?;
+        self.protocols.insert(name.to_string(), Arc::new(protocol));
+        Ok(())
+    }
+
     /// Gets a protocol handler by its identifier
     ///
     /// # Arguments
@@ -69,10 +99,29 @@ impl ProtocolRegistry {
         self.protocols.get(id).cloned()
     }
 
+    /// Gets the user-defined protocol handler selected by a connection
+    ///
+    /// A connection opts into a custom handler through its
+    /// [`CUSTOM_PROTOCOL_PROPERTY`] custom property. Returns `None` when the
+    /// property is absent or names a built-in or unregistered protocol.
+    #[must_use]
+    pub fn get_custom_for(&self, connection: &Connection) -> Option<Arc<dyn Protocol>> {
+        let name = connection
+            .custom_properties
+            .iter()
+            .find(|p| p.name == CUSTOM_PROTOCOL_PROPERTY)?
+            .value
+            .trim();
+        if BUILTIN_PROTOCOL_IDS.contains(&name) {
+            return None;
+        }
+        self.get(name)
+    }
+
     /// Returns all registered protocol IDs
     #[must_use]
-    pub fn protocol_ids(&self) -> Vec<&'static str> {
-        self.protocols.keys().copied().collect()
+    pub fn protocol_ids(&self) -> Vec<&str> {
+        self.protocols.keys().map(String::as_str).collect()
     }
 
     /// Returns the number of registered protocols
@@ -93,3 +142,35 @@ impl Default for ProtocolRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CustomProperty;
+
+    #[test]
+    fn test_register_custom() {
+        let mut registry = ProtocolRegistry::new();
+        registry
+            .register_custom("mosh", "mosh -p {port} {user}@{host}")
+            .unwrap();
+
+        assert_eq!(registry.len(), 5);
+        assert!(registry.register_custom("ssh", "ssh {host}").is_err());
+        assert!(registry.register_custom("bad", "cmd {address}").is_err());
+
+        let mut connection =
+            Connection::new_ssh("box".to_string(), "example.org".to_string(), 60001)
+                .with_username("alice");
+        assert!(registry.get_custom_for(&connection).is_none());
+
+        connection
+            .custom_properties
+            .push(CustomProperty::new_text(CUSTOM_PROTOCOL_PROPERTY, "mosh"));
+        let protocol = registry.get_custom_for(&connection).unwrap();
+        assert_eq!(
+            protocol.build_command(&connection).unwrap(),
+            vec!["mosh", "-p", "60001", "alice@example.org"]
+        );
+    }
+}
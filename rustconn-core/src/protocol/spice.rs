@@ -2,6 +2,7 @@
 
 use crate::error::ProtocolError;
 use crate::models::{Connection, ProtocolConfig, SpiceConfig};
+use crate::spice_client::normalize_fingerprint;
 
 use super::{Protocol, ProtocolResult};
 
@@ -78,6 +79,14 @@ impl Protocol for SpiceProtocol {
             }
         }
 
+        if let Some(fingerprint) = &spice_config.cert_fingerprint {
+            if normalize_fingerprint(fingerprint).is_none() {
+                return Err(ProtocolError::InvalidConfig(format!(
+                    "Invalid certificate fingerprint: {fingerprint}"
+                )));
+            }
+        }
+
         // Validate shared folders have non-empty paths and names
         for folder in &spice_config.shared_folders {
             if folder.local_path.as_os_str().is_empty() {
@@ -167,6 +176,24 @@ mod tests {
         assert!(protocol.validate_connection(&connection).is_err());
     }
 
+    #[test]
+    fn test_validate_cert_fingerprint() {
+        let protocol = SpiceProtocol::new();
+        let mut config = SpiceConfig {
+            tls_enabled: true,
+            cert_fingerprint: Some(format!("sha256:{}", "0f".repeat(32))),
+            ..Default::default()
+        };
+        assert!(protocol
+            .validate_connection(&create_spice_connection(config.clone()))
+            .is_ok());
+
+        config.cert_fingerprint = Some("0f:0f".to_string());
+        assert!(protocol
+            .validate_connection(&create_spice_connection(config))
+            .is_err());
+    }
+
     #[test]
     fn test_validate_with_usb_redirection() {
         let protocol = SpiceProtocol::new();
@@ -185,6 +212,7 @@ mod tests {
             shared_folders: vec![SharedFolder {
                 local_path: PathBuf::from("/home/user/share"),
                 share_name: "MyShare".to_string(),
+                read_only: false,
             }],
             ..Default::default()
         };
@@ -199,6 +227,7 @@ mod tests {
             shared_folders: vec![SharedFolder {
                 local_path: PathBuf::new(),
                 share_name: "MyShare".to_string(),
+                read_only: false,
             }],
             ..Default::default()
         };
@@ -213,6 +242,7 @@ mod tests {
             shared_folders: vec![SharedFolder {
                 local_path: PathBuf::from("/home/user/share"),
                 share_name: String::new(),
+                read_only: false,
             }],
             ..Default::default()
         };
@@ -5,6 +5,22 @@ use crate::models::{Connection, ProtocolConfig, SshAuthMethod, SshConfig};
 
 use super::{Protocol, ProtocolResult};
 
+/// Exit status `ssh` uses for its own errors, such as an unreachable host or
+/// failed authentication
+pub const SSH_ERROR_EXIT_STATUS: i32 = 255;
+
+/// Returns true if an `ssh` process that ended with this `waitpid` status
+/// had established its session
+///
+/// Only `ssh`'s own error status counts as a failed connection. Any other
+/// exit code is the remote shell's, and a process ended by a signal was
+/// typically closed by the user.
+#[must_use]
+pub const fn ssh_session_established(wait_status: i32) -> bool {
+    let signaled = wait_status & 0x7f != 0;
+    signaled || (wait_status >> 8) & 0xff != SSH_ERROR_EXIT_STATUS
+}
+
 /// SSH protocol handler
 ///
 /// Implements the Protocol trait for SSH connections.
@@ -102,6 +118,17 @@ mod tests {
         assert_eq!(protocol.default_port(), 22);
     }
 
+    #[test]
+    fn test_ssh_session_established() {
+        // Exit codes sit in the second byte of a waitpid status
+        assert!(ssh_session_established(0));
+        assert!(ssh_session_established(1 << 8));
+        assert!(ssh_session_established(130 << 8));
+        // SIGHUP from closing the tab
+        assert!(ssh_session_established(1));
+        assert!(!ssh_session_established(SSH_ERROR_EXIT_STATUS << 8));
+    }
+
     #[test]
     fn test_validate_valid_connection() {
         let protocol = SshProtocol::new();
@@ -0,0 +1,504 @@
+//! RDP audio input backend (MS-RDPEAI)
+//!
+//! This module implements the `AUDIO_INPUT` dynamic virtual channel, which
+//! redirects the local microphone to the RDP server.
+//!
+//! # Architecture
+//!
+//! Audio data flows from client to server:
+//! 1. Server creates the channel and sends its version and audio formats
+//! 2. Client responds with the PCM formats it can capture
+//! 3. Server opens the stream with one of those formats; the backend sends
+//!    `RdpClientEvent::AudioInputOpened` so the GUI starts recording
+//! 4. GUI sends captured PCM via `RdpClientCommand::AudioInputData`
+//! 5. Session wraps each frame in Data Incoming and Data PDUs
+//!
+//! When the server offers no usable format, or has not created the channel
+//! when capture is started, `RdpClientEvent::AudioInputDeclined` is sent.
+
+use super::audio::AudioFormatInfo;
+use super::RdpClientEvent;
+use ironrdp::core::{impl_as_any, Encode, EncodeResult, WriteCursor};
+use ironrdp::dvc::{encode_dvc_messages, DvcClientProcessor, DvcEncode, DvcMessage, DvcProcessor};
+use ironrdp::pdu::PduResult;
+use ironrdp::svc::{ChannelFlags, SvcMessage};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use tracing::{debug, trace, warn};
+
+/// Name of the audio input dynamic virtual channel
+pub const CHANNEL_NAME: &str = "AUDIO_INPUT";
+
+/// Protocol version announced by the client
+const SNDIN_VERSION: u32 = 1;
+
+const MSG_SNDIN_VERSION: u8 = 0x01;
+const MSG_SNDIN_FORMATS: u8 = 0x02;
+const MSG_SNDIN_OPEN: u8 = 0x03;
+const MSG_SNDIN_OPEN_REPLY: u8 = 0x04;
+const MSG_SNDIN_DATA_INCOMING: u8 = 0x05;
+const MSG_SNDIN_DATA: u8 = 0x06;
+const MSG_SNDIN_FORMATCHANGE: u8 = 0x07;
+
+/// `HRESULT` for a successful Open
+const S_OK: u32 = 0;
+/// `HRESULT` for an Open naming an unknown format
+const E_INVALIDARG: u32 = 0x8007_0057;
+
+/// Message sent by the server on the audio input channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ServerPdu {
+    Version(u32),
+    Formats(Vec<AudioFormatInfo>),
+    Open {
+        frames_per_packet: u32,
+        initial_format: u32,
+    },
+    FormatChange(u32),
+}
+
+/// Little-endian reader that fails instead of panicking on short input
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.0.get(..N)?.try_into().ok()?;
+        self.0 = &self.0[N..];
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[b]| b)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn skip(&mut self, n: usize) -> Option<()> {
+        self.0 = self.0.get(n..)?;
+        Some(())
+    }
+
+    /// Reads an `AUDIO_FORMAT` structure, skipping its extra data
+    fn format(&mut self) -> Option<AudioFormatInfo> {
+        let format = AudioFormatInfo {
+            format_tag: self.u16()?,
+            channels: self.u16()?,
+            samples_per_sec: self.u32()?,
+            avg_bytes_per_sec: self.u32()?,
+            block_align: self.u16()?,
+            bits_per_sample: self.u16()?,
+        };
+        let extra = self.u16()?;
+        self.skip(usize::from(extra))?;
+        Some(format)
+    }
+}
+
+fn decode_server_pdu(payload: &[u8]) -> Option<ServerPdu> {
+    let mut reader = Reader(payload);
+    match reader.u8()? {
+        MSG_SNDIN_VERSION => Some(ServerPdu::Version(reader.u32()?)),
+        MSG_SNDIN_FORMATS => {
+            let count = reader.u32()?;
+            let _packet_size = reader.u32()?;
+            let formats = (0..count)
+                .map(|_| reader.format())
+                .collect::<Option<Vec<_>>>()?;
+            Some(ServerPdu::Formats(formats))
+        }
+        MSG_SNDIN_OPEN => Some(ServerPdu::Open {
+            frames_per_packet: reader.u32()?,
+            initial_format: reader.u32()?,
+        }),
+        MSG_SNDIN_FORMATCHANGE => Some(ServerPdu::FormatChange(reader.u32()?)),
+        _ => None,
+    }
+}
+
+/// Encoded PDU sent to the server on the audio input channel
+#[derive(Debug)]
+struct AudioInputPdu(Vec<u8>);
+
+impl AudioInputPdu {
+    fn version() -> Self {
+        let mut buf = vec![MSG_SNDIN_VERSION];
+        buf.extend_from_slice(&SNDIN_VERSION.to_le_bytes());
+        Self(buf)
+    }
+
+    fn formats(formats: &[AudioFormatInfo]) -> Self {
+        let mut buf = vec![MSG_SNDIN_FORMATS];
+        buf.extend_from_slice(&(formats.len() as u32).to_le_bytes());
+        // Size of the whole PDU, patched in below
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        for format in formats {
+            buf.extend_from_slice(&format.format_tag.to_le_bytes());
+            buf.extend_from_slice(&format.channels.to_le_bytes());
+            buf.extend_from_slice(&format.samples_per_sec.to_le_bytes());
+            buf.extend_from_slice(&format.avg_bytes_per_sec.to_le_bytes());
+            buf.extend_from_slice(&format.block_align.to_le_bytes());
+            buf.extend_from_slice(&format.bits_per_sample.to_le_bytes());
+            buf.extend_from_slice(&0u16.to_le_bytes());
+        }
+        let size = (buf.len() as u32).to_le_bytes();
+        buf[5..9].copy_from_slice(&size);
+        Self(buf)
+    }
+
+    fn open_reply(result: u32) -> Self {
+        let mut buf = vec![MSG_SNDIN_OPEN_REPLY];
+        buf.extend_from_slice(&result.to_le_bytes());
+        Self(buf)
+    }
+
+    fn format_change(index: u32) -> Self {
+        let mut buf = vec![MSG_SNDIN_FORMATCHANGE];
+        buf.extend_from_slice(&index.to_le_bytes());
+        Self(buf)
+    }
+
+    fn data_incoming() -> Self {
+        Self(vec![MSG_SNDIN_DATA_INCOMING])
+    }
+
+    fn data(pcm: &[u8]) -> Self {
+        let mut buf = Vec::with_capacity(pcm.len() + 1);
+        buf.push(MSG_SNDIN_DATA);
+        buf.extend_from_slice(pcm);
+        Self(buf)
+    }
+}
+
+impl Encode for AudioInputPdu {
+    fn encode(&self, dst: &mut WriteCursor<'_>) -> EncodeResult<()> {
+        ironrdp::core::ensure_size!(in: dst, size: self.0.len());
+        dst.write_slice(&self.0);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "SNDIN_PDU"
+    }
+
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl DvcEncode for AudioInputPdu {}
+
+/// `RustConn` audio input client for `IronRDP`
+///
+/// This client negotiates the capture format with the server and reports
+/// it to the GUI, which records from the local default input device.
+/// Captured frames are only forwarded while capture is enabled.
+#[derive(Debug)]
+pub struct AudioInputClient {
+    /// Event sender to GUI
+    event_tx: Sender<RdpClientEvent>,
+    /// Formats offered to the server, indexed by Open and Format Change
+    formats: Vec<AudioFormatInfo>,
+    /// Format of the stream opened by the server
+    current_format: Option<AudioFormatInfo>,
+    /// Whether the server has opened the capture stream
+    open: AtomicBool,
+    /// Whether captured frames are sent to the server
+    capturing: AtomicBool,
+}
+
+impl_as_any!(AudioInputClient);
+
+impl AudioInputClient {
+    /// Creates a new audio input client
+    ///
+    /// # Arguments
+    /// * `event_tx` - Event sender to GUI
+    /// * `capturing` - Whether to forward frames as soon as the server opens
+    ///   the stream
+    #[must_use]
+    pub const fn new(event_tx: Sender<RdpClientEvent>, capturing: bool) -> Self {
+        Self {
+            event_tx,
+            formats: Vec::new(),
+            current_format: None,
+            open: AtomicBool::new(false),
+            capturing: AtomicBool::new(capturing),
+        }
+    }
+
+    /// Returns true if the client can capture in the given format
+    ///
+    /// Only 16-bit mono or stereo PCM is captured.
+    #[must_use]
+    pub const fn is_capturable(format: &AudioFormatInfo) -> bool {
+        format.is_pcm() && format.bits_per_sample == 16 && matches!(format.channels, 1 | 2)
+    }
+
+    /// Returns true if the server has opened the capture stream
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::SeqCst)
+    }
+
+    /// Marks the capture stream as closed, returning whether it was open
+    ///
+    /// `IronRDP` does not notify dynamic channel processors when the server
+    /// closes their channel, so the session calls this once it notices.
+    pub fn mark_closed(&self) -> bool {
+        self.open.swap(false, Ordering::SeqCst)
+    }
+
+    /// Returns the format of the open capture stream
+    #[must_use]
+    pub const fn format(&self) -> Option<&AudioFormatInfo> {
+        self.current_format.as_ref()
+    }
+
+    /// Returns true if captured frames are sent to the server
+    #[must_use]
+    pub fn is_capturing(&self) -> bool {
+        self.capturing.load(Ordering::SeqCst)
+    }
+
+    /// Starts or stops sending captured frames to the server
+    pub fn set_capturing(&self, capturing: bool) {
+        self.capturing.store(capturing, Ordering::SeqCst);
+    }
+
+    /// Encodes a captured PCM frame for the channel with the given ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame cannot be encoded.
+    pub fn encode_data(&self, channel_id: u32, pcm: &[u8]) -> EncodeResult<Vec<SvcMessage>> {
+        let messages: Vec<DvcMessage> = vec![
+            Box::new(AudioInputPdu::data_incoming()),
+            Box::new(AudioInputPdu::data(pcm)),
+        ];
+        encode_dvc_messages(channel_id, messages, ChannelFlags::empty())
+    }
+
+    /// Handles a server message and returns the responses
+    fn handle(&mut self, pdu: ServerPdu) -> Vec<AudioInputPdu> {
+        match pdu {
+            ServerPdu::Version(version) => {
+                debug!("Audio input: server version {version}");
+                vec![AudioInputPdu::version()]
+            }
+            ServerPdu::Formats(offered) => {
+                self.formats = offered.into_iter().filter(Self::is_capturable).collect();
+                debug!("Audio input: {} capturable format(s)", self.formats.len());
+                if self.formats.is_empty() {
+                    self.decline("The server offered no supported audio input format");
+                }
+                vec![AudioInputPdu::formats(&self.formats)]
+            }
+            ServerPdu::Open {
+                frames_per_packet,
+                initial_format,
+            } => {
+                let Some(format) = self.select_format(initial_format) else {
+                    self.decline("The server requested an unknown audio input format");
+                    return vec![AudioInputPdu::open_reply(E_INVALIDARG)];
+                };
+                self.open.store(true, Ordering::SeqCst);
+                debug!(
+                    "Audio input opened: {} Hz, {} ch, {} frames per packet",
+                    format.samples_per_sec, format.channels, frames_per_packet
+                );
+                let _ = self.event_tx.send(RdpClientEvent::AudioInputOpened {
+                    format,
+                    frames_per_packet,
+                });
+                vec![
+                    AudioInputPdu::format_change(initial_format),
+                    AudioInputPdu::open_reply(S_OK),
+                ]
+            }
+            ServerPdu::FormatChange(index) => {
+                let Some(format) = self.select_format(index) else {
+                    warn!("Audio input: server selected unknown format {index}");
+                    return Vec::new();
+                };
+                let _ = self.event_tx.send(RdpClientEvent::AudioInputOpened {
+                    format,
+                    frames_per_packet: 0,
+                });
+                vec![AudioInputPdu::format_change(index)]
+            }
+        }
+    }
+
+    /// Makes the format at `index` current, if it exists
+    fn select_format(&mut self, index: u32) -> Option<AudioFormatInfo> {
+        let format = self.formats.get(usize::try_from(index).ok()?)?.clone();
+        self.current_format = Some(format.clone());
+        Some(format)
+    }
+
+    fn decline(&self, reason: &str) {
+        warn!("Audio input declined: {reason}");
+        let _ = self
+            .event_tx
+            .send(RdpClientEvent::AudioInputDeclined(reason.to_string()));
+    }
+}
+
+impl DvcProcessor for AudioInputClient {
+    fn channel_name(&self) -> &str {
+        CHANNEL_NAME
+    }
+
+    fn start(&mut self, channel_id: u32) -> PduResult<Vec<DvcMessage>> {
+        // The server speaks first with its version
+        debug!("Audio input channel created: id={channel_id}");
+        Ok(Vec::new())
+    }
+
+    fn process(&mut self, _channel_id: u32, payload: &[u8]) -> PduResult<Vec<DvcMessage>> {
+        let Some(pdu) = decode_server_pdu(payload) else {
+            trace!("Audio input: ignoring {} byte message", payload.len());
+            return Ok(Vec::new());
+        };
+        Ok(self
+            .handle(pdu)
+            .into_iter()
+            .map(|pdu| Box::new(pdu) as DvcMessage)
+            .collect())
+    }
+
+    fn close(&mut self, _channel_id: u32) {
+        debug!("Audio input channel closed");
+        self.current_format = None;
+        if self.mark_closed() {
+            let _ = self.event_tx.send(RdpClientEvent::AudioInputClosed);
+        }
+    }
+}
+
+impl DvcClientProcessor for AudioInputClient {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn formats_pdu(formats: &[AudioFormatInfo]) -> Vec<u8> {
+        AudioInputPdu::formats(formats).0
+    }
+
+    fn open_pdu(initial_format: u32) -> Vec<u8> {
+        let mut buf = vec![MSG_SNDIN_OPEN];
+        buf.extend_from_slice(&441u32.to_le_bytes());
+        buf.extend_from_slice(&initial_format.to_le_bytes());
+        buf
+    }
+
+    fn responses(client: &mut AudioInputClient, payload: &[u8]) -> Vec<Vec<u8>> {
+        decode_server_pdu(payload)
+            .map(|pdu| client.handle(pdu).into_iter().map(|pdu| pdu.0).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_formats_round_trip() {
+        let formats = [
+            AudioFormatInfo::dvd_quality(),
+            AudioFormatInfo::new(AudioFormatInfo::FORMAT_ALAW, 1, 8000, 8),
+        ];
+        let pdu = formats_pdu(&formats);
+        assert_eq!(
+            u32::from_le_bytes(pdu[5..9].try_into().unwrap()) as usize,
+            pdu.len()
+        );
+        assert_eq!(
+            decode_server_pdu(&pdu),
+            Some(ServerPdu::Formats(formats.to_vec()))
+        );
+        assert_eq!(decode_server_pdu(&pdu[..pdu.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_negotiation_and_open() {
+        let (tx, rx) = mpsc::channel();
+        let mut client = AudioInputClient::new(tx, true);
+
+        let version = responses(&mut client, &[MSG_SNDIN_VERSION, 1, 0, 0, 0]);
+        assert_eq!(version, [vec![MSG_SNDIN_VERSION, 1, 0, 0, 0]]);
+
+        let offered = [
+            AudioFormatInfo::new(AudioFormatInfo::FORMAT_ALAW, 1, 8000, 8),
+            AudioFormatInfo::new(AudioFormatInfo::FORMAT_PCM, 1, 44_100, 16),
+        ];
+        let reply = responses(&mut client, &formats_pdu(&offered));
+        assert_eq!(reply, [formats_pdu(&offered[1..])]);
+        assert!(!client.is_open());
+
+        let reply = responses(&mut client, &open_pdu(0));
+        assert_eq!(
+            reply,
+            [
+                vec![MSG_SNDIN_FORMATCHANGE, 0, 0, 0, 0],
+                vec![MSG_SNDIN_OPEN_REPLY, 0, 0, 0, 0]
+            ]
+        );
+        assert_eq!(client.format(), Some(&offered[1]));
+        assert!(client.is_open());
+        match rx.try_recv().unwrap() {
+            RdpClientEvent::AudioInputOpened {
+                format,
+                frames_per_packet,
+            } => {
+                assert_eq!(format, offered[1]);
+                assert_eq!(frames_per_packet, 441);
+            }
+            other => panic!("Expected AudioInputOpened, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_declined_without_capturable_format() {
+        let (tx, rx) = mpsc::channel();
+        let mut client = AudioInputClient::new(tx, true);
+
+        let offered = [AudioFormatInfo::new(
+            AudioFormatInfo::FORMAT_MULAW,
+            1,
+            8000,
+            8,
+        )];
+        let reply = responses(&mut client, &formats_pdu(&offered));
+        assert_eq!(reply, [formats_pdu(&[])]);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(RdpClientEvent::AudioInputDeclined(_))
+        ));
+
+        let reply = responses(&mut client, &open_pdu(0));
+        assert_eq!(reply, [vec![MSG_SNDIN_OPEN_REPLY, 0x57, 0, 0x07, 0x80]]);
+        assert!(!client.is_open());
+    }
+
+    #[test]
+    fn test_capture_toggle_and_data() {
+        let (tx, _rx) = mpsc::channel();
+        let client = AudioInputClient::new(tx, false);
+        assert!(!client.is_capturing());
+        client.set_capturing(true);
+        assert!(client.is_capturing());
+
+        let messages = client.encode_data(3, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(messages.len(), 2);
+
+        assert!(!client.mark_closed());
+        client.open.store(true, Ordering::SeqCst);
+        assert!(client.mark_closed());
+        assert!(!client.is_open());
+    }
+}
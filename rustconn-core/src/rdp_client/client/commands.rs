@@ -1,10 +1,14 @@
-use super::super::{RdpClientCommand, RdpClientError};
+use super::super::audio_input::AudioInputClient;
+use super::super::clipboard::{cliprdr_message, RustConnClipboardBackend};
+use super::super::{RdpClientCommand, RdpClientError, RdpClientEvent};
+use ironrdp::cliprdr::pdu::ClipboardPdu;
 use ironrdp::cliprdr::CliprdrClient;
 use ironrdp::pdu::input::fast_path::{FastPathInputEvent, KeyboardFlags};
 use ironrdp::pdu::input::mouse::PointerFlags;
 use ironrdp::pdu::input::MousePdu;
 use ironrdp::session::image::DecodedImage;
 use ironrdp::session::{ActiveStage, ActiveStageOutput};
+use ironrdp::svc::SvcProcessorMessages;
 use ironrdp_tokio::FramedWrite;
 
 #[allow(clippy::too_many_lines)]
@@ -99,10 +103,18 @@ pub async fn process_command<W: FramedWrite>(
                 send_input_events(active_stage, image, writer, &[event_release]).await;
             }
         }
-        RdpClientCommand::Authenticate { .. } => {}
+        // Answered by the session loop, which owns the event channel
+        RdpClientCommand::Authenticate { .. }
+        | RdpClientCommand::CaptureFrame
+        | RdpClientCommand::StartAudioCapture
+        | RdpClientCommand::StopAudioCapture
+        | RdpClientCommand::AudioInputData(_) => {}
         RdpClientCommand::ClipboardData { format_id, data } => {
             handle_clipboard_data(active_stage, writer, format_id, data).await;
         }
+        RdpClientCommand::ClipboardImage { format_id, image } => {
+            handle_clipboard_image(active_stage, writer, format_id, &image).await;
+        }
         RdpClientCommand::ClipboardCopy(formats) => {
             handle_clipboard_copy(active_stage, writer, formats).await;
         }
@@ -124,12 +136,113 @@ pub async fn process_command<W: FramedWrite>(
                 request_size,
                 offset,
                 length,
-            );
+            )
+            .await;
+        }
+        RdpClientCommand::SaveClipboardFiles { target_dir } => {
+            if let Some(backend) = active_stage
+                .get_svc_processor_mut::<CliprdrClient>()
+                .and_then(|c| c.downcast_backend_mut::<RustConnClipboardBackend>())
+            {
+                backend.start_download(target_dir);
+            }
+            flush_clipboard_messages(active_stage, writer).await;
+        }
+        RdpClientCommand::ClipboardCopyFiles(paths) => {
+            handle_clipboard_copy_files(active_stage, writer, &paths).await;
         }
     }
     Ok(false)
 }
 
+/// Sends PDUs queued by the clipboard backend (file list and file contents)
+pub async fn flush_clipboard_messages<W: FramedWrite>(
+    active_stage: &mut ActiveStage,
+    writer: &mut W,
+) {
+    let messages = active_stage
+        .get_svc_processor_mut::<CliprdrClient>()
+        .and_then(|c| c.downcast_backend_mut::<RustConnClipboardBackend>())
+        .map(RustConnClipboardBackend::take_outgoing)
+        .unwrap_or_default();
+    if messages.is_empty() {
+        return;
+    }
+
+    match active_stage
+        .process_svc_processor_messages(SvcProcessorMessages::<CliprdrClient>::new(messages))
+    {
+        Ok(frame) => {
+            let _ = writer.write_all(&frame).await;
+        }
+        Err(e) => {
+            tracing::warn!("Failed to send clipboard messages: {}", e);
+        }
+    }
+}
+
+/// Handles microphone commands, returning an event for the GUI if needed
+pub async fn process_audio_input_command<W: FramedWrite>(
+    cmd: RdpClientCommand,
+    active_stage: &mut ActiveStage,
+    writer: &mut W,
+) -> Option<RdpClientEvent> {
+    let Some(dvc) = active_stage.get_dvc::<AudioInputClient>() else {
+        return matches!(cmd, RdpClientCommand::StartAudioCapture).then(|| {
+            RdpClientEvent::AudioInputDeclined(
+                "Microphone redirection is not enabled for this session".to_string(),
+            )
+        });
+    };
+    let channel_id = dvc.channel_id();
+    let client = dvc.channel_processor_downcast_ref::<AudioInputClient>()?;
+
+    match cmd {
+        RdpClientCommand::StartAudioCapture => {
+            client.set_capturing(true);
+            if !client.is_open() {
+                return Some(RdpClientEvent::AudioInputDeclined(
+                    "The server has not opened the audio input channel".to_string(),
+                ));
+            }
+        }
+        RdpClientCommand::StopAudioCapture => client.set_capturing(false),
+        RdpClientCommand::AudioInputData(pcm) => {
+            if !client.is_open() || !client.is_capturing() {
+                return None;
+            }
+            let channel_id = channel_id?;
+            let messages = match client.encode_data(channel_id, &pcm) {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::warn!("Failed to encode audio input data: {}", e);
+                    return None;
+                }
+            };
+
+            // IronRDP forgets the channel ID once the server closes the channel
+            if active_stage.get_dvc_by_channel_id(channel_id).is_none() {
+                let closed = active_stage
+                    .get_dvc::<AudioInputClient>()
+                    .and_then(|dvc| dvc.channel_processor_downcast_ref::<AudioInputClient>())
+                    .is_some_and(AudioInputClient::mark_closed);
+                return closed.then_some(RdpClientEvent::AudioInputClosed);
+            }
+
+            match active_stage.encode_dvc_messages(messages) {
+                Ok(frame) => {
+                    let _ = writer.write_all(&frame).await;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to send audio input data: {}", e);
+                }
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
 /// Creates a keyboard `FastPath` event
 fn create_keyboard_event(scancode: u16, pressed: bool, extended: bool) -> FastPathInputEvent {
     let mut flags = KeyboardFlags::empty();
@@ -272,12 +385,40 @@ async fn handle_clipboard_data<W: FramedWrite>(
     }
 }
 
+async fn handle_clipboard_image<W: FramedWrite>(
+    active_stage: &mut ActiveStage,
+    writer: &mut W,
+    format_id: u32,
+    image: &super::super::ClipboardImage,
+) {
+    let format = super::super::ClipboardFormatInfo::new(format_id, None);
+    if let Some(data) = image.encode_for(&format) {
+        handle_clipboard_data(active_stage, writer, format_id, data).await;
+        return;
+    }
+
+    tracing::warn!("Clipboard image cannot be encoded for format {}", format_id);
+    if let Some(cliprdr) = active_stage.get_svc_processor_mut::<CliprdrClient>() {
+        let response = ironrdp::cliprdr::pdu::OwnedFormatDataResponse::new_error();
+        if let Ok(messages) = cliprdr.submit_format_data(response) {
+            if let Ok(frame) = active_stage.process_svc_processor_messages(messages) {
+                let _ = writer.write_all(&frame).await;
+            }
+        }
+    }
+}
+
 async fn handle_clipboard_copy<W: FramedWrite>(
     active_stage: &mut ActiveStage,
     writer: &mut W,
     formats: Vec<super::super::ClipboardFormatInfo>,
 ) {
     if let Some(cliprdr) = active_stage.get_svc_processor_mut::<CliprdrClient>() {
+        // Drop image formats if the server turned out to be text-only
+        let formats = match cliprdr.downcast_backend::<RustConnClipboardBackend>() {
+            Some(backend) => backend.negotiate_formats(&formats),
+            None => formats,
+        };
         let clipboard_formats: Vec<ironrdp::cliprdr::pdu::ClipboardFormat> = formats
             .iter()
             .map(|f| {
@@ -329,10 +470,39 @@ async fn handle_clipboard_request<W: FramedWrite>(
     }
 }
 
+async fn handle_clipboard_copy_files<W: FramedWrite>(
+    active_stage: &mut ActiveStage,
+    writer: &mut W,
+    paths: &[std::path::PathBuf],
+) {
+    let Some(cliprdr) = active_stage.get_svc_processor_mut::<CliprdrClient>() else {
+        tracing::warn!("CLIPRDR channel not available for file copy");
+        return;
+    };
+    let Some(format) = cliprdr
+        .downcast_backend_mut::<RustConnClipboardBackend>()
+        .and_then(|backend| backend.offer_files(paths))
+    else {
+        return;
+    };
+
+    match cliprdr.initiate_copy(&[format]) {
+        Ok(messages) => {
+            if let Ok(frame) = active_stage.process_svc_processor_messages(messages) {
+                let _ = writer.write_all(&frame).await;
+                tracing::debug!("Clipboard file list offered: {} paths", paths.len());
+            }
+        }
+        Err(e) => {
+            tracing::warn!("initiate_copy for files failed: {}", e);
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
-fn handle_file_contents_request<W: FramedWrite>(
+async fn handle_file_contents_request<W: FramedWrite>(
     active_stage: &mut ActiveStage,
-    _writer: &mut W,
+    writer: &mut W,
     stream_id: u32,
     file_index: u32,
     request_size: bool,
@@ -348,32 +518,37 @@ fn handle_file_contents_request<W: FramedWrite>(
         length
     );
 
-    if let Some(cliprdr) = active_stage.get_svc_processor_mut::<CliprdrClient>() {
-        // Build the file contents request
-        let flags = if request_size {
-            ironrdp::cliprdr::pdu::FileContentsFlags::SIZE
-        } else {
-            ironrdp::cliprdr::pdu::FileContentsFlags::DATA
-        };
-
-        let request = ironrdp::cliprdr::pdu::FileContentsRequest {
-            stream_id,
-            index: file_index,
-            flags,
-            position: offset,
-            requested_size: length,
-            data_id: None,
-        };
-
-        // Try to send the request through the cliprdr client
-        // Note: The cliprdr API may not expose file contents request directly,
-        // so we log the attempt for now
-        tracing::debug!("File contents request prepared: {:?}", request);
+    if active_stage
+        .get_svc_processor_mut::<CliprdrClient>()
+        .is_none()
+    {
+        tracing::warn!("CLIPRDR channel not available for file contents request");
+        return;
+    }
 
-        // The actual sending depends on the cliprdr API
-        // For now, we acknowledge the request was processed
-        let _ = cliprdr;
+    let flags = if request_size {
+        ironrdp::cliprdr::pdu::FileContentsFlags::SIZE
     } else {
-        tracing::warn!("CLIPRDR channel not available for file contents request");
+        ironrdp::cliprdr::pdu::FileContentsFlags::DATA
+    };
+    let request = ironrdp::cliprdr::pdu::FileContentsRequest {
+        stream_id,
+        index: file_index,
+        flags,
+        position: offset,
+        requested_size: length,
+        data_id: None,
+    };
+
+    let messages = SvcProcessorMessages::<CliprdrClient>::new(vec![cliprdr_message(
+        ClipboardPdu::FileContentsRequest(request),
+    )]);
+    match active_stage.process_svc_processor_messages(messages) {
+        Ok(frame) => {
+            let _ = writer.write_all(&frame).await;
+        }
+        Err(e) => {
+            tracing::warn!("Failed to send file contents request: {}", e);
+        }
     }
 }
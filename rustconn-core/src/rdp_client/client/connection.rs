@@ -1,11 +1,19 @@
 use super::super::audio::RustConnAudioBackend;
+use super::super::audio_input::AudioInputClient;
 use super::super::clipboard::RustConnClipboardBackend;
 use super::super::rdpdr::RustConnRdpdrBackend;
-use super::super::{RdpClientConfig, RdpClientError, RdpClientEvent};
+use super::super::{
+    MonitorDefinition, RdpClientConfig, RdpClientError, RdpClientEvent, SharedFolder,
+};
 use ironrdp::cliprdr::CliprdrClient;
 use ironrdp::connector::{
     BitmapConfig, ClientConnector, Config, ConnectionResult, Credentials, DesktopSize, ServerName,
 };
+use ironrdp::displaycontrol::client::DisplayControlClient;
+use ironrdp::displaycontrol::pdu::{
+    DisplayControlMonitorLayout, DisplayControlPdu, MonitorLayoutEntry,
+};
+use ironrdp::dvc::{DrdynvcClient, DvcMessage};
 use ironrdp::pdu::gcc::KeyboardType;
 use ironrdp::pdu::rdp::capability_sets::{
     client_codecs_capabilities, BitmapCodecs, MajorPlatformType,
@@ -21,12 +29,16 @@ use tokio::net::TcpStream;
 pub type UpgradedFramed = TokioFramed<ironrdp_tls::TlsStream<TcpStream>>;
 
 /// Establishes the RDP connection and returns the framed stream and connection result.
+///
+/// `monitors` is the negotiated monitor layout; with more than one monitor
+/// the layout is sent through the Display Control channel.
 // The future is not Send because IronRDP's AsyncNetworkClient is not Send.
 // This is fine because we run on a single-threaded Tokio runtime.
 #[allow(clippy::future_not_send)]
 #[allow(clippy::too_many_lines)]
 pub async fn establish_connection(
     config: &RdpClientConfig,
+    monitors: &[MonitorDefinition],
     event_tx: std::sync::mpsc::Sender<RdpClientEvent>,
 ) -> Result<(UpgradedFramed, ConnectionResult), RdpClientError> {
     use tokio::time::{timeout, Duration};
@@ -59,7 +71,8 @@ pub async fn establish_connection(
 
     // Phase 2.5: Add clipboard channel if enabled
     if config.clipboard_enabled {
-        let clipboard_backend = RustConnClipboardBackend::new(event_tx.clone());
+        let clipboard_backend = RustConnClipboardBackend::new(event_tx.clone())
+            .with_max_transfer_size(config.clipboard_max_transfer_size);
         let cliprdr: CliprdrClient = ironrdp::cliprdr::Cliprdr::new(Box::new(clipboard_backend));
         connector.static_channels.insert(cliprdr);
         tracing::debug!("Clipboard channel enabled");
@@ -85,28 +98,35 @@ pub async fn establish_connection(
             |h| h.to_string_lossy().into_owned(),
         );
 
-        // Create initial drives list from shared folders config
-        let initial_drives: Vec<(u32, String)> = config
-            .shared_folders
-            .iter()
-            .enumerate()
-            .map(|(idx, folder)| {
-                // #[allow(clippy::cast_possible_truncation)]
-                let device_id = idx as u32 + 1;
-                tracing::debug!(
-                    "RDPDR: registering drive {} '{}' -> {:?}",
-                    device_id,
-                    folder.name,
-                    folder.path
-                );
-                (device_id, folder.name.clone())
-            })
-            .collect();
+        // Only advertise folders that exist locally; report the rest
+        let mut drives: Vec<(u32, SharedFolder)> = Vec::new();
+        for folder in &config.shared_folders {
+            if let Err(e) = folder.validate() {
+                tracing::warn!("RDPDR: skipping shared folder '{}': {e}", folder.name);
+                let _ = event_tx.send(RdpClientEvent::SharedFolderError {
+                    name: folder.name.clone(),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let device_id = drives.len() as u32 + 1;
+            tracing::debug!(
+                "RDPDR: registering drive {} '{}' -> {:?} (read-only: {})",
+                device_id,
+                folder.name,
+                folder.path,
+                folder.read_only
+            );
+            drives.push((device_id, folder.clone()));
+        }
 
-        // Create backend for the first shared folder
-        if let Some(folder) = config.shared_folders.first() {
-            let base_path = folder.path.to_string_lossy().into_owned();
-            let rdpdr_backend = RustConnRdpdrBackend::new(base_path);
+        if !drives.is_empty() {
+            let initial_drives: Vec<(u32, String)> = drives
+                .iter()
+                .map(|(device_id, folder)| (*device_id, folder.name.clone()))
+                .collect();
+            let rdpdr_backend = RustConnRdpdrBackend::with_shared_folders(drives, event_tx.clone());
             let rdpdr = Rdpdr::new(Box::new(rdpdr_backend), computer_name)
                 .with_drives(Some(initial_drives));
             connector.static_channels.insert(rdpdr);
@@ -119,6 +139,39 @@ pub async fn establish_connection(
         tracing::debug!("Audio channel enabled (without RDPDR)");
     }
 
+    // Phase 2.7: Dynamic virtual channels share a single DRDYNVC channel
+    let mut drdynvc = DrdynvcClient::new();
+    let mut has_dynamic_channels = false;
+
+    // Send the monitor layout once Display Control is ready
+    if monitors.len() > 1 {
+        match build_monitor_layout_pdu(monitors) {
+            Ok(pdu) => {
+                let display_control = DisplayControlClient::new(move |caps| {
+                    tracing::debug!(?caps, "Display Control ready, sending monitor layout");
+                    Ok(vec![Box::new(pdu.clone()) as DvcMessage])
+                });
+                drdynvc.attach_dynamic_channel(display_control);
+                has_dynamic_channels = true;
+                tracing::debug!("Multi-monitor layout enabled ({} monitors)", monitors.len());
+            }
+            Err(e) => {
+                tracing::warn!("Invalid monitor layout, using a single monitor: {e}");
+            }
+        }
+    }
+
+    // Phase 2.8: Add AUDIO_INPUT channel for microphone redirection
+    if config.microphone_enabled {
+        drdynvc.attach_dynamic_channel(AudioInputClient::new(event_tx.clone(), true));
+        has_dynamic_channels = true;
+        tracing::debug!("Microphone channel enabled");
+    }
+
+    if has_dynamic_channels {
+        connector.static_channels.insert(drdynvc);
+    }
+
     // Phase 3: Perform RDP connection sequence
     let mut framed = TokioFramed::new(stream);
 
@@ -181,6 +234,31 @@ pub async fn establish_connection(
     Ok((upgraded_framed, connection_result))
 }
 
+/// Builds the Display Control monitor layout PDU for the given monitors
+fn build_monitor_layout_pdu(
+    monitors: &[MonitorDefinition],
+) -> Result<DisplayControlPdu, ironrdp::core::EncodeError> {
+    let entries = monitors
+        .iter()
+        .map(|monitor| {
+            let entry = if monitor.is_primary {
+                MonitorLayoutEntry::new_primary(monitor.width(), monitor.height())?
+            } else {
+                MonitorLayoutEntry::new_secondary(monitor.width(), monitor.height())?
+                    .with_position(monitor.left, monitor.top)?
+            };
+            match (monitor.physical_width_mm, monitor.physical_height_mm) {
+                (Some(width_mm), Some(height_mm)) => {
+                    entry.with_physical_dimensions(width_mm, height_mm)
+                }
+                _ => Ok(entry),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DisplayControlMonitorLayout::new(&entries)?.into())
+}
+
 /// Builds `IronRDP` connector configuration from our config
 fn build_connector_config(config: &RdpClientConfig) -> Config {
     // Always use UsernamePassword credentials
@@ -17,10 +17,18 @@
 // #![allow(clippy::missing_panics_doc)]
 // #![allow(clippy::default_trait_access)]
 
-use super::{RdpClientCommand, RdpClientConfig, RdpClientError, RdpClientEvent};
+use super::input::negotiate_monitor_layout;
+use super::{
+    MonitorDefinition, RdpClientCommand, RdpClientConfig, RdpClientError, RdpClientEvent,
+    ReconnectState,
+};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Interval at which the shutdown signal is checked while waiting to reconnect
+const RECONNECT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 mod commands;
 mod connection;
@@ -188,6 +196,33 @@ impl RdpClient {
         self.send_command(RdpClientCommand::SetDesktopSize { width, height })
     }
 
+    /// Requests a screenshot of the remote desktop.
+    ///
+    /// The PNG image arrives as a `FrameCaptured` event.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or channel is closed.
+    pub fn capture_frame(&self) -> Result<(), RdpClientError> {
+        self.send_command(RdpClientCommand::CaptureFrame)
+    }
+
+    /// Starts or stops sending microphone audio to the server.
+    ///
+    /// Requires `microphone_enabled` in the client configuration; otherwise
+    /// starting is answered with an `AudioInputDeclined` event.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or channel is closed.
+    pub fn set_audio_capture(&self, enabled: bool) -> Result<(), RdpClientError> {
+        self.send_command(if enabled {
+            RdpClientCommand::StartAudioCapture
+        } else {
+            RdpClientCommand::StopAudioCapture
+        })
+    }
+
     /// Disconnects from the RDP server and cleans up resources
     pub fn disconnect(&mut self) {
         self.shutdown_signal.store(true, Ordering::SeqCst);
@@ -243,30 +278,139 @@ impl Drop for RdpClient {
 }
 
 /// Runs the RDP client protocol loop using `IronRDP`
+///
+/// Once a session has been established, transient failures are retried
+/// according to the configured `ReconnectPolicy`, emitting
+/// `RdpClientEvent::Reconnecting` before each attempt. A failed initial
+/// connection, permanent errors and user-initiated disconnects end the loop.
 #[allow(clippy::future_not_send)]
 async fn run_rdp_client(
     config: RdpClientConfig,
     event_tx: std::sync::mpsc::Sender<RdpClientEvent>,
     command_rx: std::sync::mpsc::Receiver<RdpClientCommand>,
     shutdown_signal: Arc<AtomicBool>,
+) -> Result<(), RdpClientError> {
+    let policy = &config.reconnect_policy;
+    let mut reconnect = ReconnectState::new();
+
+    loop {
+        let error = match connect_and_run(
+            &config,
+            &event_tx,
+            &command_rx,
+            &shutdown_signal,
+            &mut reconnect,
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+
+        if shutdown_signal.load(Ordering::SeqCst)
+            || reconnect.last_connected.is_none()
+            || !error.is_transient()
+            || !policy.should_retry(reconnect.attempt)
+        {
+            return Err(error);
+        }
+
+        reconnect.on_disconnected(Some(error.to_string()));
+        reconnect.on_attempt();
+        let delay = policy.delay_for_attempt(reconnect.attempt);
+        tracing::warn!(
+            attempt = reconnect.attempt,
+            delay_ms = delay.as_millis(),
+            %error,
+            "RDP connection lost, reconnecting"
+        );
+        let _ = event_tx.send(RdpClientEvent::Reconnecting {
+            attempt: reconnect.attempt,
+        });
+
+        if !wait_for_reconnect(delay, &command_rx, &shutdown_signal).await {
+            return Ok(());
+        }
+    }
+}
+
+/// Establishes a connection and runs the session until it ends
+///
+/// Reconnection attempts are bounded by the policy's attempt timeout.
+#[allow(clippy::future_not_send)]
+async fn connect_and_run(
+    config: &RdpClientConfig,
+    event_tx: &std::sync::mpsc::Sender<RdpClientEvent>,
+    command_rx: &std::sync::mpsc::Receiver<RdpClientCommand>,
+    shutdown_signal: &AtomicBool,
+    reconnect: &mut ReconnectState,
 ) -> Result<(), RdpClientError> {
     // Phase 1-3: Establish connection
-    let (framed, connection_result) =
-        connection::establish_connection(&config, event_tx.clone()).await?;
+    let monitors = negotiate_monitor_layout(&config.monitor_layout);
+    let connecting = connection::establish_connection(config, &monitors, event_tx.clone());
+    let (framed, connection_result) = if reconnect.reconnecting {
+        tokio::time::timeout(config.reconnect_policy.attempt_timeout, connecting)
+            .await
+            .map_err(|_| RdpClientError::Timeout)?
+    } else {
+        connecting.await
+    }
+    .inspect_err(|e| reconnect.on_attempt_failed(e.to_string()))?;
+    reconnect.on_connected();
 
     // Send connected event
+    let width = connection_result.desktop_size.width;
+    let height = connection_result.desktop_size.height;
+    let monitors = if monitors.len() > 1 {
+        monitors
+    } else {
+        vec![MonitorDefinition::primary(
+            u32::from(width),
+            u32::from(height),
+        )]
+    };
     let _ = event_tx.send(RdpClientEvent::Connected {
-        width: connection_result.desktop_size.width,
-        height: connection_result.desktop_size.height,
+        width,
+        height,
+        monitors,
     });
 
     // Phase 4: Active session loop
     session::run_active_session(
         framed,
         connection_result,
-        event_tx,
+        event_tx.clone(),
         command_rx,
         shutdown_signal,
     )
     .await
 }
+
+/// Waits out the reconnect delay, returning false if the user disconnected
+///
+/// Commands queued while disconnected are discarded so stale input is not
+/// replayed into the new session.
+#[allow(clippy::future_not_send)]
+async fn wait_for_reconnect(
+    delay: Duration,
+    command_rx: &std::sync::mpsc::Receiver<RdpClientCommand>,
+    shutdown_signal: &AtomicBool,
+) -> bool {
+    let deadline = tokio::time::Instant::now() + delay;
+    loop {
+        while let Ok(cmd) = command_rx.try_recv() {
+            if matches!(cmd, RdpClientCommand::Disconnect) {
+                return false;
+            }
+        }
+        if shutdown_signal.load(Ordering::SeqCst) {
+            return false;
+        }
+
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        tokio::time::sleep(RECONNECT_POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
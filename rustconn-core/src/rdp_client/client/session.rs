@@ -1,6 +1,7 @@
 use super::super::{RdpClientCommand, RdpClientError, RdpClientEvent, RdpRect};
-use super::commands::process_command;
+use super::commands::{flush_clipboard_messages, process_audio_input_command, process_command};
 use super::connection::UpgradedFramed;
+use crate::ffi::encode_bgra_png;
 use ironrdp::connector::connection_activation::ConnectionActivationState;
 use ironrdp::connector::ConnectionResult;
 use ironrdp::graphics::image_processing::PixelFormat as IronPixelFormat;
@@ -11,7 +12,6 @@ use ironrdp_tokio::{
     single_sequence_step_read, split_tokio_framed, Framed, FramedRead, FramedWrite,
 };
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
 
 /// Runs the active RDP session, processing framebuffer updates and input
 // The future is not Send because IronRDP's AsyncNetworkClient is not Send.
@@ -21,8 +21,8 @@ pub async fn run_active_session(
     framed: UpgradedFramed,
     connection_result: ConnectionResult,
     event_tx: std::sync::mpsc::Sender<RdpClientEvent>,
-    command_rx: std::sync::mpsc::Receiver<RdpClientCommand>,
-    shutdown_signal: Arc<AtomicBool>,
+    command_rx: &std::sync::mpsc::Receiver<RdpClientCommand>,
+    shutdown_signal: &AtomicBool,
 ) -> Result<(), RdpClientError> {
     let (mut reader, mut writer) = split_tokio_framed(framed);
 
@@ -50,11 +50,31 @@ pub async fn run_active_session(
 
         // Process commands from GUI (non-blocking)
         while let Ok(cmd) = command_rx.try_recv() {
+            if matches!(cmd, RdpClientCommand::CaptureFrame) {
+                let _ = event_tx.send(capture_frame(&image));
+                continue;
+            }
+            if matches!(
+                cmd,
+                RdpClientCommand::StartAudioCapture
+                    | RdpClientCommand::StopAudioCapture
+                    | RdpClientCommand::AudioInputData(_)
+            ) {
+                if let Some(event) =
+                    process_audio_input_command(cmd, &mut active_stage, &mut writer).await
+                {
+                    let _ = event_tx.send(event);
+                }
+                continue;
+            }
             if process_command(cmd, &mut active_stage, &mut image, &mut writer).await? {
                 return Ok(());
             }
         }
 
+        // Send clipboard PDUs queued while handling commands or server PDUs
+        flush_clipboard_messages(&mut active_stage, &mut writer).await;
+
         // Read and process RDP frames with timeout
         let read_result = tokio::time::timeout(
             std::time::Duration::from_millis(16), // ~60 FPS
@@ -250,6 +270,16 @@ where
     Ok(())
 }
 
+/// Encodes the decoded image as PNG, with the same colors the GUI renders
+fn capture_frame(image: &DecodedImage) -> RdpClientEvent {
+    let rect = RdpRect::full_screen(image.width(), image.height());
+    let data = extract_region_data(image, rect);
+    encode_bgra_png(u32::from(image.width()), u32::from(image.height()), &data).map_or_else(
+        |e| RdpClientEvent::CaptureFailed(e.to_string()),
+        RdpClientEvent::FrameCaptured,
+    )
+}
+
 /// Extracts pixel data for a specific region from the decoded image
 /// Converts from `IronRDP`'s BGRA format to Cairo's ARGB32 format by swapping R and B channels
 fn extract_region_data(image: &DecodedImage, rect: RdpRect) -> Vec<u8> {
@@ -13,17 +13,37 @@
 //!
 //! - `CF_UNICODETEXT` (13): Unicode text (UTF-16LE)
 //! - `CF_TEXT` (1): ANSI text
-//! - `CF_DIB` (8): Device-independent bitmap (future)
-//! - `CF_HDROP` (15): File list (future)
-
-use super::{ClipboardFileInfo, ClipboardFormatInfo, RdpClientEvent};
+//! - `CF_DIB` (8): Device-independent bitmap
+//! - `CF_DIBV5` (17): Device-independent bitmap with alpha (server → client)
+//! - `PNG` (registered): PNG image
+//! - `FileGroupDescriptorW` (registered): File list
+//!
+//! Image formats are advertised alongside text. If the server fails to
+//! deliver an image it offered, the peer is treated as text-only and image
+//! formats are left out of later format lists.
+//!
+//! # File Transfer
+//!
+//! File lists are exchanged as `FileGroupDescriptorW` and their contents are
+//! streamed with File Contents PDUs (see [`super::clipboard_files`]). PDUs
+//! produced by backend callbacks are queued and sent by the session loop via
+//! [`RustConnClipboardBackend::take_outgoing`].
+
+use super::clipboard_files::{ClipboardDownload, ClipboardTransferError, ClipboardUpload};
+use super::{
+    ClipboardFileInfo, ClipboardFormatInfo, ClipboardImage, ClipboardTransferDirection,
+    RdpClientEvent,
+};
 use ironrdp::cliprdr::backend::{ClipboardMessage, ClipboardMessageProxy, CliprdrBackend};
 use ironrdp::cliprdr::pdu::{
-    ClipboardFormat, ClipboardFormatId, ClipboardGeneralCapabilityFlags, FileContentsFlags,
-    FileContentsRequest, FileContentsResponse, FormatDataRequest, FormatDataResponse, LockDataId,
+    ClipboardFileAttributes, ClipboardFormat, ClipboardFormatId, ClipboardFormatName,
+    ClipboardGeneralCapabilityFlags, ClipboardPdu, FileContentsFlags, FileContentsRequest,
+    FileContentsResponse, FormatDataRequest, FormatDataResponse, LockDataId,
 };
 use ironrdp::core::impl_as_any;
+use ironrdp::svc::{ChannelFlags, SvcMessage};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::mpsc::Sender;
 use tracing::{debug, trace, warn};
 
@@ -49,7 +69,7 @@ impl ClipboardMessageProxy for RustConnClipboardProxy {
                 let format_infos: Vec<ClipboardFormatInfo> = formats
                     .iter()
                     .map(|f| {
-                        let name = f.name.as_ref().map(|n| format!("{n:?}"));
+                        let name = f.name.as_ref().map(|n| n.value().to_string());
                         ClipboardFormatInfo::new(f.id.value(), name)
                     })
                     .collect();
@@ -91,6 +111,24 @@ pub struct RustConnClipboardBackend {
     pending_copy_data: HashMap<u32, Vec<u8>>,
     /// Server's negotiated capabilities
     server_capabilities: ClipboardGeneralCapabilityFlags,
+    /// Whether image formats are exchanged with the server
+    peer_supports_images: bool,
+    /// Server's ID for the registered PNG format, once seen
+    peer_png_format: Option<ClipboardFormatId>,
+    /// Server's ID for the `FileGroupDescriptorW` format, once seen
+    peer_file_list_format: Option<ClipboardFormatId>,
+    /// Files listed on the server clipboard
+    remote_files: Vec<ClipboardFileInfo>,
+    /// Largest total size of a clipboard file transfer in bytes
+    max_transfer_size: u64,
+    /// Active guest-to-host file transfer
+    download: Option<ClipboardDownload>,
+    /// Local files offered on the server clipboard
+    upload: Option<ClipboardUpload>,
+    /// Stream ID for the next download
+    next_stream_id: u32,
+    /// PDUs waiting to be sent by the session loop
+    outgoing: Vec<SvcMessage>,
 }
 
 impl_as_any!(RustConnClipboardBackend);
@@ -105,9 +143,25 @@ impl RustConnClipboardBackend {
             pending_paste_format: None,
             pending_copy_data: HashMap::new(),
             server_capabilities: ClipboardGeneralCapabilityFlags::empty(),
+            peer_supports_images: true,
+            peer_png_format: None,
+            peer_file_list_format: None,
+            remote_files: Vec::new(),
+            max_transfer_size: u64::MAX,
+            download: None,
+            upload: None,
+            next_stream_id: 1,
+            outgoing: Vec::new(),
         }
     }
 
+    /// Sets the largest total size of a clipboard file transfer in bytes
+    #[must_use]
+    pub const fn with_max_transfer_size(mut self, bytes: u64) -> Self {
+        self.max_transfer_size = bytes;
+        self
+    }
+
     /// Returns true if the clipboard is ready
     #[must_use]
     pub const fn is_ready(&self) -> bool {
@@ -138,11 +192,152 @@ impl RustConnClipboardBackend {
         self.server_capabilities
     }
 
+    /// Returns true if image formats are exchanged with the server
+    #[must_use]
+    pub const fn peer_supports_images(&self) -> bool {
+        self.peer_supports_images
+    }
+
+    /// Filters a local format list down to what the server can handle
+    ///
+    /// Image formats are dropped once the server has been found not to
+    /// support them, leaving a text-only list.
+    #[must_use]
+    pub fn negotiate_formats(&self, formats: &[ClipboardFormatInfo]) -> Vec<ClipboardFormatInfo> {
+        formats
+            .iter()
+            .filter(|f| self.peer_supports_images || !f.is_image())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the format info for a server format ID
+    fn remote_format_info(&self, format_id: ClipboardFormatId) -> ClipboardFormatInfo {
+        if Some(format_id) == self.peer_png_format {
+            ClipboardFormatInfo::new(
+                format_id.value(),
+                Some(ClipboardFormatInfo::PNG_FORMAT_NAME.to_string()),
+            )
+        } else {
+            ClipboardFormatInfo::new(format_id.value(), None)
+        }
+    }
+
+    /// Returns the format info for a client format ID requested by the server
+    fn local_format_info(format_id: u32) -> ClipboardFormatInfo {
+        if format_id == ClipboardFormatInfo::PNG {
+            ClipboardFormatInfo::png()
+        } else {
+            ClipboardFormatInfo::new(format_id, None)
+        }
+    }
+
     /// Returns true if the server supports file clipboard
     #[must_use]
     pub const fn supports_file_clipboard(&self) -> bool {
         self.server_capabilities
-            .contains(ClipboardGeneralCapabilityFlags::USE_LONG_FORMAT_NAMES)
+            .contains(ClipboardGeneralCapabilityFlags::STREAM_FILECLIP_ENABLED)
+    }
+
+    /// Returns the files listed on the server clipboard
+    #[must_use]
+    pub fn remote_files(&self) -> &[ClipboardFileInfo] {
+        &self.remote_files
+    }
+
+    /// Returns and clears the PDUs waiting to be sent to the server
+    pub fn take_outgoing(&mut self) -> Vec<SvcMessage> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    /// Starts saving the files on the server clipboard into `target_dir`
+    ///
+    /// Failures are reported with a `ClipboardTransferFailed` event.
+    pub fn start_download(&mut self, target_dir: PathBuf) {
+        let stream_id = self.next_stream_id;
+        self.next_stream_id = self.next_stream_id.wrapping_add(1);
+        match ClipboardDownload::new(
+            self.remote_files.clone(),
+            target_dir,
+            stream_id,
+            self.max_transfer_size,
+        ) {
+            Ok(download) => {
+                debug!("Starting clipboard download on stream {}", stream_id);
+                self.download = Some(download);
+                self.advance_download();
+            }
+            Err(e) => self.fail_transfer(ClipboardTransferDirection::Download, &e),
+        }
+    }
+
+    /// Offers local files to the server clipboard
+    ///
+    /// Returns the format to advertise with `initiate_copy`, or `None` if the
+    /// files cannot be offered (reported with a `ClipboardTransferFailed` event).
+    pub fn offer_files(&mut self, paths: &[PathBuf]) -> Option<ClipboardFormat> {
+        match ClipboardUpload::new(paths, self.max_transfer_size) {
+            Ok(upload) => {
+                debug!("Offering {} clipboard files", upload.len());
+                self.upload = Some(upload);
+                Some(
+                    ClipboardFormat::new(ClipboardFormatId::new(
+                        ClipboardFormatInfo::FILE_GROUP_DESCRIPTOR,
+                    ))
+                    .with_name(ClipboardFormatName::FILE_LIST),
+                )
+            }
+            Err(e) => {
+                self.fail_transfer(ClipboardTransferDirection::Upload, &e);
+                None
+            }
+        }
+    }
+
+    /// Queues the next request of the active download or reports its end
+    fn advance_download(&mut self) {
+        let Some(download) = &mut self.download else {
+            return;
+        };
+        match download.next_request() {
+            Ok(Some(request)) => {
+                self.outgoing
+                    .push(cliprdr_message(ClipboardPdu::FileContentsRequest(request)));
+            }
+            Ok(None) => {
+                let files = download.saved_files();
+                debug!("Clipboard download complete: {} files", files);
+                self.download = None;
+                let _ = self
+                    .proxy
+                    .event_tx
+                    .send(RdpClientEvent::ClipboardTransferComplete {
+                        direction: ClipboardTransferDirection::Download,
+                        files,
+                    });
+            }
+            Err(e) => self.fail_transfer(ClipboardTransferDirection::Download, &e),
+        }
+    }
+
+    /// Aborts a transfer and reports the reason
+    fn fail_transfer(
+        &mut self,
+        direction: ClipboardTransferDirection,
+        error: &ClipboardTransferError,
+    ) {
+        warn!("Clipboard file transfer failed: {}", error);
+        match direction {
+            ClipboardTransferDirection::Download => self.download = None,
+            ClipboardTransferDirection::Upload => self.upload = None,
+        }
+        let _ = self
+            .proxy
+            .event_tx
+            .send(RdpClientEvent::ClipboardTransferFailed {
+                direction,
+                message: error.to_string(),
+            });
     }
 }
 
@@ -165,7 +360,9 @@ impl CliprdrBackend for RustConnClipboardBackend {
     }
 
     fn client_capabilities(&self) -> ClipboardGeneralCapabilityFlags {
-        ClipboardGeneralCapabilityFlags::empty()
+        ClipboardGeneralCapabilityFlags::STREAM_FILECLIP_ENABLED
+            | ClipboardGeneralCapabilityFlags::FILECLIP_NO_FILE_PATHS
+            | ClipboardGeneralCapabilityFlags::HUGE_FILE_SUPPORT_ENABLED
     }
 
     fn on_process_negotiated_capabilities(
@@ -207,10 +404,36 @@ impl CliprdrBackend for RustConnClipboardBackend {
         let format_infos: Vec<ClipboardFormatInfo> = available_formats
             .iter()
             .map(|f| {
-                let name = f.name.as_ref().map(|n| format!("{n:?}"));
+                let name = f.name.as_ref().map(|n| n.value().to_string());
                 ClipboardFormatInfo::new(f.id.value(), name)
             })
             .collect();
+        // Offering an image format shows the server handles images
+        self.peer_png_format = format_infos
+            .iter()
+            .find(|f| f.is_png())
+            .map(|f| ClipboardFormatId::new(f.id));
+        let image_format = self.peer_png_format.or_else(|| {
+            [ClipboardFormatId::CF_DIB, ClipboardFormatId::CF_DIBV5]
+                .into_iter()
+                .find(|id| available_formats.iter().any(|f| f.id == *id))
+        });
+        if image_format.is_some() {
+            self.peer_supports_images = true;
+        }
+
+        // The server owns the clipboard now; earlier file lists are stale
+        self.upload = None;
+        self.remote_files.clear();
+        if self.download.is_some() {
+            let error = ClipboardTransferError::Remote("files replaced on the clipboard".into());
+            self.fail_transfer(ClipboardTransferDirection::Download, &error);
+        }
+        self.peer_file_list_format = format_infos
+            .iter()
+            .find(|f| f.is_file_group_descriptor())
+            .map(|f| ClipboardFormatId::new(f.id));
+
         let _ = self
             .proxy
             .event_tx
@@ -226,7 +449,15 @@ impl CliprdrBackend for RustConnClipboardBackend {
                     .find(|f| f.id == ClipboardFormatId::CF_TEXT)
             });
 
-        if let Some(format) = text_format {
+        if let Some(format_id) = self.peer_file_list_format {
+            debug!(
+                "File list available (id={}), requesting paste",
+                format_id.value()
+            );
+            self.pending_paste_format = Some(format_id);
+            self.proxy
+                .send_clipboard_message(ClipboardMessage::SendInitiatePaste(format_id));
+        } else if let Some(format) = text_format {
             debug!(
                 "Text format available (id={}), requesting paste",
                 format.id.value()
@@ -234,8 +465,16 @@ impl CliprdrBackend for RustConnClipboardBackend {
             self.pending_paste_format = Some(format.id);
             self.proxy
                 .send_clipboard_message(ClipboardMessage::SendInitiatePaste(format.id));
+        } else if let Some(format_id) = image_format {
+            debug!(
+                "Image format available (id={}), requesting paste",
+                format_id.value()
+            );
+            self.pending_paste_format = Some(format_id);
+            self.proxy
+                .send_clipboard_message(ClipboardMessage::SendInitiatePaste(format_id));
         } else {
-            debug!("No text format available in clipboard");
+            debug!("No text or image format available in clipboard");
         }
     }
 
@@ -243,6 +482,17 @@ impl CliprdrBackend for RustConnClipboardBackend {
         let format_id = request.format.value();
         debug!("Server requested clipboard data for format {}", format_id);
 
+        if format_id == ClipboardFormatInfo::FILE_GROUP_DESCRIPTOR {
+            let response = self
+                .upload
+                .as_ref()
+                .and_then(|upload| FormatDataResponse::new_file_list(&upload.file_list()).ok())
+                .unwrap_or_else(FormatDataResponse::new_error);
+            self.outgoing
+                .push(cliprdr_message(ClipboardPdu::FormatDataResponse(response)));
+            return;
+        }
+
         // Check if we have pending data for this format
         if let Some(data) = self.pending_copy_data.get(&format_id) {
             debug!(
@@ -265,7 +515,7 @@ impl CliprdrBackend for RustConnClipboardBackend {
                 "No pending data for format {}, requesting from GUI",
                 format_id
             );
-            let format_info = ClipboardFormatInfo::new(format_id, None);
+            let format_info = Self::local_format_info(format_id);
             let _ = self
                 .proxy
                 .event_tx
@@ -282,16 +532,43 @@ impl CliprdrBackend for RustConnClipboardBackend {
             format_id
         );
 
-        // Check for file list format (CF_HDROP = 15 or FileGroupDescriptorW)
-        if format_id == Some(ClipboardFormatId::CF_HDROP) {
-            if let Some(files) = parse_file_group_descriptor(data) {
-                debug!("Parsed {} files from clipboard", files.len());
-                let _ = self
-                    .proxy
-                    .event_tx
-                    .send(RdpClientEvent::ClipboardFileList(files));
-                return;
+        if let Some(format) = format_id
+            .map(|id| self.remote_format_info(id))
+            .filter(ClipboardFormatInfo::is_image)
+        {
+            match ClipboardImage::decode(&format, data) {
+                Some(image) if !response.is_error() => {
+                    debug!("Clipboard image decoded from format {}", format.id);
+                    let _ = self
+                        .proxy
+                        .event_tx
+                        .send(RdpClientEvent::ClipboardImage(image));
+                }
+                _ => {
+                    warn!(
+                        "Server could not provide image format {}, falling back to text-only clipboard",
+                        format.id
+                    );
+                    self.peer_supports_images = false;
+                }
+            }
+            return;
+        }
+
+        if format_id.is_some() && format_id == self.peer_file_list_format {
+            match response.to_file_list() {
+                Ok(list) if !response.is_error() => {
+                    let files = files_from_descriptors(list.files);
+                    debug!("Parsed {} files from clipboard", files.len());
+                    self.remote_files.clone_from(&files);
+                    let _ = self
+                        .proxy
+                        .event_tx
+                        .send(RdpClientEvent::ClipboardFileList(files));
+                }
+                _ => warn!("Server could not provide the clipboard file list"),
             }
+            return;
         }
 
         match format_id {
@@ -351,24 +628,68 @@ impl CliprdrBackend for RustConnClipboardBackend {
             request.flags
         );
 
-        // Notify GUI about the file contents request
-        // The GUI should respond with the file data via ClipboardData command
-        let is_size_request = request.flags.contains(FileContentsFlags::SIZE);
-
-        let _ = self
-            .proxy
-            .event_tx
-            .send(RdpClientEvent::ServerMessage(format!(
-                "Server requested file contents: index={}, {}",
-                request.index,
-                if is_size_request { "size" } else { "data" }
-            )));
+        let Some(upload) = &mut self.upload else {
+            warn!("File contents requested, but no files are offered");
+            let response = FileContentsResponse::new_error(request.stream_id);
+            self.outgoing
+                .push(cliprdr_message(ClipboardPdu::FileContentsResponse(
+                    response,
+                )));
+            return;
+        };
+
+        let was_complete = upload.is_complete();
+        match upload.respond(&request) {
+            Ok(response) => {
+                self.outgoing
+                    .push(cliprdr_message(ClipboardPdu::FileContentsResponse(
+                        response,
+                    )));
+                if request.flags.contains(FileContentsFlags::DATA) {
+                    let _ = self.proxy.event_tx.send(upload.progress(request.index));
+                    if upload.is_complete() && !was_complete {
+                        let files = upload.len();
+                        let _ =
+                            self.proxy
+                                .event_tx
+                                .send(RdpClientEvent::ClipboardTransferComplete {
+                                    direction: ClipboardTransferDirection::Upload,
+                                    files,
+                                });
+                    }
+                }
+            }
+            Err(e) => {
+                let response = FileContentsResponse::new_error(request.stream_id);
+                self.outgoing
+                    .push(cliprdr_message(ClipboardPdu::FileContentsResponse(
+                        response,
+                    )));
+                self.fail_transfer(ClipboardTransferDirection::Upload, &e);
+            }
+        }
     }
 
     fn on_file_contents_response(&mut self, response: FileContentsResponse<'_>) {
         let stream_id = response.stream_id();
         let data = response.data();
 
+        if let Some(download) = self
+            .download
+            .as_mut()
+            .filter(|d| d.stream_id() == stream_id)
+        {
+            match download.on_response(&response) {
+                Ok(()) => {
+                    let progress = download.progress();
+                    let _ = self.proxy.event_tx.send(progress);
+                    self.advance_download();
+                }
+                Err(e) => self.fail_transfer(ClipboardTransferDirection::Download, &e),
+            }
+            return;
+        }
+
         debug!(
             "File contents response: stream_id={}, data_len={}",
             stream_id,
@@ -431,116 +752,40 @@ pub fn string_to_utf16(text: &str) -> Vec<u8> {
     result
 }
 
-/// Parses a `FileGroupDescriptorW` structure from clipboard data
+/// Wraps a clipboard PDU for the CLIPRDR channel
 ///
-/// The structure format (MS-RDPECLIP 2.2.5.2.3.1):
-/// - `cItems` (4 bytes): Number of `FILEDESCRIPTORW` entries
-/// - `FILEDESCRIPTORW[]`: Array of file descriptors
-///
-/// Each `FILEDESCRIPTORW` (MS-RDPECLIP 2.2.5.2.3.1.1):
-/// - `dwFlags` (4 bytes): Valid fields flags
-/// - `clsid` (16 bytes): Reserved
-/// - `sizelLow/High` (8 bytes): Reserved
-/// - `pointl` (8 bytes): Reserved
-/// - `dwFileAttributes` (4 bytes): File attributes
-/// - `ftCreationTime` (8 bytes): Creation time
-/// - `ftLastAccessTime` (8 bytes): Last access time
-/// - `ftLastWriteTime` (8 bytes): Last write time
-/// - `nFileSizeHigh` (4 bytes): High 32 bits of file size
-/// - `nFileSizeLow` (4 bytes): Low 32 bits of file size
-/// - `cFileName` (520 bytes): Null-terminated UTF-16LE filename (260 chars)
-fn parse_file_group_descriptor(data: &[u8]) -> Option<Vec<ClipboardFileInfo>> {
-    /// Size of each `FILEDESCRIPTORW` structure in bytes
-    const FILEDESCRIPTOR_SIZE: usize = 592;
-
-    // Minimum size: 4 bytes for count
-    if data.len() < 4 {
-        return None;
-    }
-
-    let count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
-    let expected_size = 4 + count * FILEDESCRIPTOR_SIZE;
-
-    if data.len() < expected_size {
-        tracing::warn!(
-            "FileGroupDescriptorW too small: expected {}, got {}",
-            expected_size,
-            data.len()
-        );
-        return None;
-    }
-
-    let mut files = Vec::with_capacity(count);
-    let mut offset = 4;
-
-    for index in 0..count {
-        if offset + FILEDESCRIPTOR_SIZE > data.len() {
-            break;
-        }
-
-        let descriptor = &data[offset..offset + FILEDESCRIPTOR_SIZE];
-
-        // dwFlags at offset 0
-        let _flags =
-            u32::from_le_bytes([descriptor[0], descriptor[1], descriptor[2], descriptor[3]]);
-
-        // dwFileAttributes at offset 36
-        let attributes = u32::from_le_bytes([
-            descriptor[36],
-            descriptor[37],
-            descriptor[38],
-            descriptor[39],
-        ]);
-
-        // ftLastWriteTime at offset 60 (FILETIME = 8 bytes)
-        let last_write_time = i64::from_le_bytes([
-            descriptor[60],
-            descriptor[61],
-            descriptor[62],
-            descriptor[63],
-            descriptor[64],
-            descriptor[65],
-            descriptor[66],
-            descriptor[67],
-        ]);
+/// Clipboard PDUs carry chunked data, so `CHANNEL_FLAG_SHOW_PROTOCOL` is
+/// required (MS-RDPBCGR 2.2.6.1.1).
+#[must_use]
+pub fn cliprdr_message(pdu: ClipboardPdu<'static>) -> SvcMessage {
+    SvcMessage::from(pdu).with_flags(ChannelFlags::SHOW_PROTOCOL)
+}
 
-        // nFileSizeHigh at offset 68, nFileSizeLow at offset 72
-        let size_high = u32::from_le_bytes([
-            descriptor[68],
-            descriptor[69],
-            descriptor[70],
-            descriptor[71],
-        ]);
-        let size_low = u32::from_le_bytes([
-            descriptor[72],
-            descriptor[73],
-            descriptor[74],
-            descriptor[75],
-        ]);
-        let size = (u64::from(size_high) << 32) | u64::from(size_low);
-
-        // cFileName at offset 76 (520 bytes = 260 UTF-16 chars)
-        let filename_bytes = &descriptor[76..76 + 520];
-        let filename = string_from_utf16(filename_bytes).unwrap_or_default();
-
-        if !filename.is_empty() {
-            files.push(ClipboardFileInfo::new(
-                filename,
-                size,
-                attributes,
-                last_write_time,
+/// Converts a decoded `FileGroupDescriptorW` list into file infos
+///
+/// The index of each file is its position in the list, which is how the
+/// server identifies it in File Contents Requests.
+fn files_from_descriptors(
+    descriptors: Vec<ironrdp::cliprdr::pdu::FileDescriptor>,
+) -> Vec<ClipboardFileInfo> {
+    descriptors
+        .into_iter()
+        .enumerate()
+        .filter(|(_, d)| !d.name.is_empty())
+        .map(|(index, d)| {
+            ClipboardFileInfo::new(
+                d.name,
+                d.file_size.unwrap_or_default(),
+                d.attributes
+                    .unwrap_or(ClipboardFileAttributes::empty())
+                    .bits(),
+                d.last_write_time
+                    .and_then(|t| i64::try_from(t).ok())
+                    .unwrap_or_default(),
                 index as u32,
-            ));
-        }
-
-        offset += FILEDESCRIPTOR_SIZE;
-    }
-
-    if files.is_empty() {
-        None
-    } else {
-        Some(files)
-    }
+            )
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -574,4 +819,86 @@ mod tests {
         assert!(format.is_text());
         assert_eq!(format.id, ClipboardFormatInfo::UNICODE_TEXT);
     }
+
+    #[test]
+    fn test_negotiate_formats_text_only_fallback() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut backend = RustConnClipboardBackend::new(tx);
+        let formats = vec![
+            ClipboardFormatInfo::unicode_text(),
+            ClipboardFormatInfo::dib(),
+            ClipboardFormatInfo::png(),
+        ];
+
+        assert!(backend.peer_supports_images());
+        assert_eq!(backend.negotiate_formats(&formats), formats);
+
+        backend.pending_paste_format = Some(ClipboardFormatId::CF_DIB);
+        backend.on_format_data_response(FormatDataResponse::new_error());
+        assert!(!backend.peer_supports_images());
+        assert_eq!(
+            backend.negotiate_formats(&formats),
+            vec![ClipboardFormatInfo::unicode_text()]
+        );
+    }
+
+    #[test]
+    fn test_remote_image_paste() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut backend = RustConnClipboardBackend::new(tx);
+
+        backend.on_remote_copy(&[
+            ClipboardFormat::new(ClipboardFormatId::CF_DIB),
+            ClipboardFormat::new(ClipboardFormatId::new(0xC123))
+                .with_name(ironrdp::cliprdr::pdu::ClipboardFormatName::new("PNG")),
+        ]);
+        assert_eq!(
+            backend.pending_paste_format,
+            Some(ClipboardFormatId::new(0xC123))
+        );
+
+        backend.on_format_data_response(FormatDataResponse::new_data(vec![0x89, b'P']));
+        let image = rx
+            .try_iter()
+            .find_map(|event| match event {
+                RdpClientEvent::ClipboardImage(image) => Some(image),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(image, ClipboardImage::Png(vec![0x89, b'P']));
+    }
+
+    #[test]
+    fn test_remote_file_list_download() {
+        use ironrdp::cliprdr::pdu::{FileDescriptor, PackedFileList};
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut backend = RustConnClipboardBackend::new(tx);
+
+        backend.on_remote_copy(&[ClipboardFormat::new(ClipboardFormatId::new(0xC0F1))
+            .with_name(ClipboardFormatName::FILE_LIST)]);
+        assert_eq!(
+            backend.pending_paste_format,
+            Some(ClipboardFormatId::new(0xC0F1))
+        );
+
+        let list = PackedFileList {
+            files: vec![FileDescriptor {
+                attributes: Some(ClipboardFileAttributes::ARCHIVE),
+                last_write_time: None,
+                file_size: Some(4),
+                name: "report.txt".to_string(),
+            }],
+        };
+        backend.on_format_data_response(FormatDataResponse::new_file_list(&list).unwrap());
+        assert_eq!(backend.remote_files().len(), 1);
+        assert!(rx
+            .try_iter()
+            .any(|event| matches!(event, RdpClientEvent::ClipboardFileList(files) if files[0].name == "report.txt")));
+
+        let dir = tempfile::tempdir().unwrap();
+        backend.start_download(dir.path().to_path_buf());
+        assert_eq!(backend.take_outgoing().len(), 1);
+        assert!(backend.take_outgoing().is_empty());
+    }
 }
@@ -0,0 +1,646 @@
+//! Clipboard file transfer over the CLIPRDR channel
+//!
+//! Files copied in the remote session arrive as a `FileGroupDescriptorW`
+//! file list (MS-RDPECLIP 2.2.5.2.3). [`ClipboardDownload`] fetches their
+//! contents in chunks with File Contents Request PDUs and writes them below
+//! a local directory. Local files are offered through [`ClipboardUpload`],
+//! which builds the file list and answers the server's File Contents
+//! Requests from disk.
+//!
+//! Both directions enforce a limit on the total number of bytes transferred.
+
+use super::{ClipboardFileInfo, ClipboardTransferDirection, RdpClientEvent};
+use ironrdp::cliprdr::pdu::{
+    ClipboardFileAttributes, FileContentsFlags, FileContentsRequest, FileContentsResponse,
+    FileDescriptor, PackedFileList,
+};
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Bytes requested from the server per File Contents Request
+pub const FILE_CHUNK_SIZE: u32 = 64 * 1024;
+
+/// Largest chunk served to the server per File Contents Request
+const MAX_SERVED_CHUNK: u32 = 4 * 1024 * 1024;
+
+/// Longest file name in a `CLIPRDR_FILEDESCRIPTOR`, excluding the terminator
+const MAX_NAME_UNITS: usize = 259;
+
+/// Windows FILETIME of the Unix epoch (100-nanosecond intervals since 1601)
+const FILETIME_UNIX_EPOCH: u64 = 116_444_736_000_000_000;
+
+/// Clipboard file transfer errors
+#[derive(Debug, Error)]
+pub enum ClipboardTransferError {
+    /// Nothing to transfer
+    #[error("No files to transfer")]
+    NoFiles,
+
+    /// Transfer exceeds the configured size limit
+    #[error("Transfer of {size} bytes exceeds the limit of {limit} bytes")]
+    TooLarge {
+        /// Total bytes of the transfer
+        size: u64,
+        /// Configured limit in bytes
+        limit: u64,
+    },
+
+    /// File name cannot be stored or advertised safely
+    #[error("Invalid file name: {0}")]
+    InvalidName(String),
+
+    /// Server requested a file that is not on the clipboard
+    #[error("Unknown clipboard file index {0}")]
+    UnknownFile(u32),
+
+    /// Server failed to deliver the contents of a file
+    #[error("Server failed to provide {0}")]
+    Remote(String),
+
+    /// Local I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Guest-to-host transfer of the files listed on the server clipboard
+#[derive(Debug)]
+pub struct ClipboardDownload {
+    files: Vec<ClipboardFileInfo>,
+    target_dir: PathBuf,
+    stream_id: u32,
+    max_size: u64,
+    /// Index into `files` of the file being transferred
+    current: usize,
+    /// Size of the current file, once reported by the server
+    current_size: Option<u64>,
+    /// Local copy of the current file
+    current_file: Option<BufWriter<File>>,
+    /// Bytes of the current file written so far
+    offset: u64,
+    transferred: u64,
+    total: u64,
+    saved: usize,
+}
+
+impl ClipboardDownload {
+    /// Prepares a download of `files` into `target_dir`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no files, a file name would escape
+    /// `target_dir`, or the advertised sizes exceed `max_size`.
+    pub fn new(
+        files: Vec<ClipboardFileInfo>,
+        target_dir: PathBuf,
+        stream_id: u32,
+        max_size: u64,
+    ) -> Result<Self, ClipboardTransferError> {
+        if files.is_empty() {
+            return Err(ClipboardTransferError::NoFiles);
+        }
+        for file in &files {
+            local_relative_path(&file.name)?;
+        }
+        let total = files
+            .iter()
+            .filter(|f| !f.is_directory())
+            .fold(0u64, |sum, f| sum.saturating_add(f.size));
+        check_limit(total, max_size)?;
+
+        Ok(Self {
+            files,
+            target_dir,
+            stream_id,
+            max_size,
+            current: 0,
+            current_size: None,
+            current_file: None,
+            offset: 0,
+            transferred: 0,
+            total,
+            saved: 0,
+        })
+    }
+
+    /// Returns the stream ID used for this download's requests
+    #[must_use]
+    pub const fn stream_id(&self) -> u32 {
+        self.stream_id
+    }
+
+    /// Returns the number of files saved so far
+    #[must_use]
+    pub const fn saved_files(&self) -> usize {
+        self.saved
+    }
+
+    /// Returns the request for the next chunk, or `None` once every file is saved
+    ///
+    /// Directories and empty files are created locally without a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a local file or directory cannot be created.
+    pub fn next_request(&mut self) -> Result<Option<FileContentsRequest>, ClipboardTransferError> {
+        loop {
+            let Some(info) = self.files.get(self.current) else {
+                return Ok(None);
+            };
+            let path = self.target_dir.join(local_relative_path(&info.name)?);
+
+            if info.is_directory() {
+                std::fs::create_dir_all(&path)?;
+                self.current += 1;
+                continue;
+            }
+
+            let Some(size) = self.current_size else {
+                return Ok(Some(self.request(FileContentsFlags::SIZE, 0, 8)));
+            };
+
+            if self.current_file.is_none() {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                self.current_file = Some(BufWriter::new(File::create(&path)?));
+            }
+
+            if self.offset >= size {
+                self.finish_file()?;
+                continue;
+            }
+
+            let length = u32::try_from(size - self.offset)
+                .map_or(FILE_CHUNK_SIZE, |remaining| remaining.min(FILE_CHUNK_SIZE));
+            return Ok(Some(self.request(
+                FileContentsFlags::DATA,
+                self.offset,
+                length,
+            )));
+        }
+    }
+
+    /// Handles the server's response to the last request
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server failed the request, the reported size
+    /// exceeds the limit, or the data cannot be written.
+    pub fn on_response(
+        &mut self,
+        response: &FileContentsResponse<'_>,
+    ) -> Result<(), ClipboardTransferError> {
+        let Some(info) = self.files.get(self.current) else {
+            return Ok(());
+        };
+
+        let Some(size) = self.current_size else {
+            let size = response
+                .data_as_size()
+                .map_err(|_| ClipboardTransferError::Remote(info.name.clone()))?;
+            // Trust the size reported for the stream over the file list
+            self.total = self.total.saturating_sub(info.size).saturating_add(size);
+            check_limit(self.total, self.max_size)?;
+            self.current_size = Some(size);
+            return Ok(());
+        };
+
+        let data = response.data();
+        if data.is_empty() {
+            return Err(ClipboardTransferError::Remote(info.name.clone()));
+        }
+        let remaining = usize::try_from(size - self.offset).unwrap_or(usize::MAX);
+        let data = &data[..data.len().min(remaining)];
+        if let Some(file) = &mut self.current_file {
+            file.write_all(data)?;
+        }
+        self.offset += data.len() as u64;
+        self.transferred += data.len() as u64;
+        Ok(())
+    }
+
+    /// Returns a progress event for the current state
+    #[must_use]
+    pub fn progress(&self) -> RdpClientEvent {
+        let file_name = self
+            .files
+            .get(self.current)
+            .or_else(|| self.files.last())
+            .map(|f| f.name.clone())
+            .unwrap_or_default();
+        RdpClientEvent::ClipboardTransferProgress {
+            direction: ClipboardTransferDirection::Download,
+            file_name,
+            transferred: self.transferred,
+            total: self.total,
+        }
+    }
+
+    fn request(
+        &self,
+        flags: FileContentsFlags,
+        position: u64,
+        requested_size: u32,
+    ) -> FileContentsRequest {
+        FileContentsRequest {
+            stream_id: self.stream_id,
+            index: self.files[self.current].index,
+            flags,
+            position,
+            requested_size,
+            data_id: None,
+        }
+    }
+
+    fn finish_file(&mut self) -> Result<(), ClipboardTransferError> {
+        if let Some(mut file) = self.current_file.take() {
+            file.flush()?;
+        }
+        self.current += 1;
+        self.current_size = None;
+        self.offset = 0;
+        self.saved += 1;
+        Ok(())
+    }
+}
+
+/// Local file or directory offered on the clipboard
+#[derive(Debug, Clone)]
+struct UploadEntry {
+    path: PathBuf,
+    /// Name relative to the copied items, `\`-separated
+    name: String,
+    size: u64,
+    is_dir: bool,
+    last_write_time: Option<u64>,
+}
+
+/// Host-to-guest transfer of local files placed on the server clipboard
+#[derive(Debug)]
+pub struct ClipboardUpload {
+    entries: Vec<UploadEntry>,
+    max_size: u64,
+    transferred: u64,
+    total: u64,
+}
+
+impl ClipboardUpload {
+    /// Collects `paths` (directories recursively) for the clipboard
+    ///
+    /// Symbolic links to directories are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is nothing to offer, a path cannot be read,
+    /// a name is too long for the file list, or the total size exceeds
+    /// `max_size`.
+    pub fn new(paths: &[PathBuf], max_size: u64) -> Result<Self, ClipboardTransferError> {
+        let mut entries = Vec::new();
+        for path in paths {
+            let name = path
+                .file_name()
+                .ok_or_else(|| ClipboardTransferError::InvalidName(path.display().to_string()))?
+                .to_string_lossy()
+                .into_owned();
+            collect_entries(path, name, &mut entries)?;
+        }
+        if entries.is_empty() {
+            return Err(ClipboardTransferError::NoFiles);
+        }
+
+        let total = entries
+            .iter()
+            .fold(0u64, |sum, e| sum.saturating_add(e.size));
+        check_limit(total, max_size)?;
+
+        Ok(Self {
+            entries,
+            max_size,
+            transferred: 0,
+            total,
+        })
+    }
+
+    /// Returns the number of files and directories offered
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is offered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true once every byte offered has been served
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.transferred >= self.total
+    }
+
+    /// Builds the `FileGroupDescriptorW` list advertised to the server
+    #[must_use]
+    pub fn file_list(&self) -> PackedFileList {
+        let files = self
+            .entries
+            .iter()
+            .map(|e| FileDescriptor {
+                attributes: Some(if e.is_dir {
+                    ClipboardFileAttributes::DIRECTORY
+                } else {
+                    ClipboardFileAttributes::ARCHIVE
+                }),
+                last_write_time: e.last_write_time,
+                file_size: Some(e.size),
+                name: e.name.clone(),
+            })
+            .collect();
+        PackedFileList { files }
+    }
+
+    /// Answers a File Contents Request from the server
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index is unknown, serving the data would exceed
+    /// the size limit, or the file cannot be read. The caller should answer
+    /// the request with an error response.
+    pub fn respond(
+        &mut self,
+        request: &FileContentsRequest,
+    ) -> Result<FileContentsResponse<'static>, ClipboardTransferError> {
+        let entry = self
+            .entries
+            .get(request.index as usize)
+            .ok_or(ClipboardTransferError::UnknownFile(request.index))?;
+
+        if request.flags.contains(FileContentsFlags::SIZE) {
+            return Ok(FileContentsResponse::new_size_response(
+                request.stream_id,
+                entry.size,
+            ));
+        }
+
+        let length = request.requested_size.min(MAX_SERVED_CHUNK);
+        let mut file = File::open(&entry.path)?;
+        file.seek(SeekFrom::Start(request.position))?;
+        let mut data = Vec::with_capacity(length as usize);
+        file.take(u64::from(length)).read_to_end(&mut data)?;
+
+        let transferred = self.transferred.saturating_add(data.len() as u64);
+        check_limit(transferred, self.max_size)?;
+        self.transferred = transferred;
+
+        Ok(FileContentsResponse::new_data_response(
+            request.stream_id,
+            data,
+        ))
+    }
+
+    /// Returns a progress event after serving the file at `index`
+    #[must_use]
+    pub fn progress(&self, index: u32) -> RdpClientEvent {
+        let file_name = self
+            .entries
+            .get(index as usize)
+            .map(|e| e.name.clone())
+            .unwrap_or_default();
+        RdpClientEvent::ClipboardTransferProgress {
+            direction: ClipboardTransferDirection::Upload,
+            file_name,
+            transferred: self.transferred.min(self.total),
+            total: self.total,
+        }
+    }
+}
+
+/// Converts a file list name (relative, `\`-separated) into a local relative path
+///
+/// Rejects parent components and drive letters so that files stay inside
+/// the target directory.
+fn local_relative_path(name: &str) -> Result<PathBuf, ClipboardTransferError> {
+    let mut path = PathBuf::new();
+    for part in name.split(['\\', '/']) {
+        match part {
+            "" | "." => {}
+            ".." => return Err(ClipboardTransferError::InvalidName(name.to_string())),
+            part if part.contains(':') => {
+                return Err(ClipboardTransferError::InvalidName(name.to_string()));
+            }
+            part => path.push(part),
+        }
+    }
+    if path.as_os_str().is_empty() {
+        return Err(ClipboardTransferError::InvalidName(name.to_string()));
+    }
+    Ok(path)
+}
+
+/// Adds `path` and, for directories, everything below it to `entries`
+fn collect_entries(
+    path: &Path,
+    name: String,
+    entries: &mut Vec<UploadEntry>,
+) -> Result<(), ClipboardTransferError> {
+    if name.encode_utf16().count() > MAX_NAME_UNITS {
+        return Err(ClipboardTransferError::InvalidName(name));
+    }
+
+    let mut meta = std::fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        meta = std::fs::metadata(path)?;
+        if meta.is_dir() {
+            tracing::debug!("Skipping symlinked directory {}", path.display());
+            return Ok(());
+        }
+    }
+    let last_write_time = meta.modified().ok().and_then(filetime_from_system_time);
+
+    if !meta.is_dir() {
+        entries.push(UploadEntry {
+            path: path.to_path_buf(),
+            name,
+            size: meta.len(),
+            is_dir: false,
+            last_write_time,
+        });
+        return Ok(());
+    }
+
+    entries.push(UploadEntry {
+        path: path.to_path_buf(),
+        name: name.clone(),
+        size: 0,
+        is_dir: true,
+        last_write_time,
+    });
+    let mut children = std::fs::read_dir(path)?.collect::<Result<Vec<_>, _>>()?;
+    children.sort_by_key(std::fs::DirEntry::file_name);
+    for child in children {
+        let child_name = format!("{name}\\{}", child.file_name().to_string_lossy());
+        collect_entries(&child.path(), child_name, entries)?;
+    }
+    Ok(())
+}
+
+const fn check_limit(size: u64, limit: u64) -> Result<(), ClipboardTransferError> {
+    if size > limit {
+        return Err(ClipboardTransferError::TooLarge { size, limit });
+    }
+    Ok(())
+}
+
+fn filetime_from_system_time(time: SystemTime) -> Option<u64> {
+    let since_epoch = time.duration_since(UNIX_EPOCH).ok()?;
+    let intervals = u64::try_from(since_epoch.as_nanos() / 100).ok()?;
+    intervals.checked_add(FILETIME_UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote_file(name: &str, size: u64, index: u32) -> ClipboardFileInfo {
+        ClipboardFileInfo::new(name.to_string(), size, 0, 0, index)
+    }
+
+    #[test]
+    fn test_local_relative_path() {
+        assert_eq!(
+            local_relative_path("dir\\file.txt").unwrap(),
+            PathBuf::from("dir/file.txt")
+        );
+        assert!(local_relative_path("..\\secret").is_err());
+        assert!(local_relative_path("C:\\Windows").is_err());
+        assert!(local_relative_path("\\").is_err());
+    }
+
+    #[test]
+    fn test_download_streams_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let content: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+        let mut download = ClipboardDownload::new(
+            vec![
+                ClipboardFileInfo::new(
+                    "docs".to_string(),
+                    0,
+                    ClipboardFileInfo::FILE_ATTRIBUTE_DIRECTORY,
+                    0,
+                    0,
+                ),
+                remote_file("docs\\data.bin", content.len() as u64, 1),
+            ],
+            dir.path().to_path_buf(),
+            7,
+            u64::MAX,
+        )
+        .unwrap();
+
+        let mut data_requests = 0;
+        while let Some(request) = download.next_request().unwrap() {
+            assert_eq!(request.stream_id, 7);
+            assert_eq!(request.index, 1);
+            let response = if request.flags.contains(FileContentsFlags::SIZE) {
+                FileContentsResponse::new_size_response(7, content.len() as u64)
+            } else {
+                data_requests += 1;
+                let start = request.position as usize;
+                let end = (start + request.requested_size as usize).min(content.len());
+                FileContentsResponse::new_data_response(7, content[start..end].to_vec())
+            };
+            download.on_response(&response).unwrap();
+        }
+
+        assert_eq!(data_requests, 2);
+        assert_eq!(download.saved_files(), 1);
+        assert_eq!(
+            std::fs::read(dir.path().join("docs/data.bin")).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_transfer_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = ClipboardDownload::new(
+            vec![remote_file("big.iso", 2048, 0)],
+            dir.path().to_path_buf(),
+            1,
+            1024,
+        );
+        assert!(matches!(
+            result,
+            Err(ClipboardTransferError::TooLarge { .. })
+        ));
+
+        // Sizes missing from the file list are checked once reported
+        let mut download = ClipboardDownload::new(
+            vec![remote_file("big.iso", 0, 0)],
+            dir.path().to_path_buf(),
+            1,
+            1024,
+        )
+        .unwrap();
+        download.next_request().unwrap();
+        let response = FileContentsResponse::new_size_response(1, 2048);
+        assert!(download.on_response(&response).is_err());
+
+        std::fs::write(dir.path().join("local.bin"), vec![0u8; 2048]).unwrap();
+        assert!(ClipboardUpload::new(&[dir.path().join("local.bin")], 1024).is_err());
+    }
+
+    #[test]
+    fn test_upload_serves_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        std::fs::create_dir(&folder).unwrap();
+        std::fs::write(folder.join("a.txt"), b"hello world").unwrap();
+
+        let mut upload = ClipboardUpload::new(&[folder], u64::MAX).unwrap();
+        let list = upload.file_list();
+        let names: Vec<_> = list.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["folder", "folder\\a.txt"]);
+        assert_eq!(list.files[1].file_size, Some(11));
+
+        let size = upload
+            .respond(&FileContentsRequest {
+                stream_id: 3,
+                index: 1,
+                flags: FileContentsFlags::SIZE,
+                position: 0,
+                requested_size: 8,
+                data_id: None,
+            })
+            .unwrap();
+        assert_eq!(size.data_as_size().unwrap(), 11);
+
+        let data = upload
+            .respond(&FileContentsRequest {
+                stream_id: 3,
+                index: 1,
+                flags: FileContentsFlags::DATA,
+                position: 6,
+                requested_size: 100,
+                data_id: None,
+            })
+            .unwrap();
+        assert_eq!(data.data(), b"world");
+        assert!(!upload.is_complete());
+
+        assert!(matches!(
+            upload.respond(&FileContentsRequest {
+                stream_id: 3,
+                index: 9,
+                flags: FileContentsFlags::DATA,
+                position: 0,
+                requested_size: 1,
+                data_id: None,
+            }),
+            Err(ClipboardTransferError::UnknownFile(9))
+        ));
+    }
+}
@@ -39,6 +39,29 @@ impl SharedFolder {
         self.read_only = read_only;
         self
     }
+
+    /// Validates the shared folder before it is advertised to the server
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is empty or the local path is not an
+    /// existing directory.
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(ConfigValidationError::InvalidSharedFolder(
+                "share name cannot be empty".to_string(),
+            ));
+        }
+
+        if !self.path.is_dir() {
+            return Err(ConfigValidationError::InvalidSharedFolder(format!(
+                "{} does not exist or is not a directory",
+                self.path.display()
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 /// Configuration for RDP client connection
@@ -72,6 +95,10 @@ pub struct RdpClientConfig {
     /// Enable clipboard sharing
     pub clipboard_enabled: bool,
 
+    /// Largest total size in bytes of files copied through the clipboard
+    #[serde(default = "default_clipboard_max_transfer_size")]
+    pub clipboard_max_transfer_size: u64,
+
     /// Enable audio redirection
     pub audio_enabled: bool,
 
@@ -120,7 +147,7 @@ pub struct RdpClientConfig {
     #[serde(default)]
     pub monitor_layout: MonitorLayout,
 
-    /// Reconnection policy
+    /// Reconnection policy applied when the connection drops unexpectedly
     #[serde(default)]
     pub reconnect_policy: ReconnectPolicy,
 
@@ -149,6 +176,10 @@ const fn default_true() -> bool {
     true
 }
 
+const fn default_clipboard_max_transfer_size() -> u64 {
+    1024 * 1024 * 1024
+}
+
 const fn default_scale_factor() -> u32 {
     100
 }
@@ -221,6 +252,7 @@ impl Default for RdpClientConfig {
             height: 720,
             color_depth: 32,
             clipboard_enabled: true,
+            clipboard_max_transfer_size: default_clipboard_max_transfer_size(),
             audio_enabled: false,
             timeout_secs: 30,
             ignore_certificate: true,
@@ -403,6 +435,20 @@ impl RdpClientConfig {
         self
     }
 
+    /// Sets the largest total size of a clipboard file transfer in bytes
+    #[must_use]
+    pub const fn with_clipboard_max_transfer_size(mut self, bytes: u64) -> Self {
+        self.clipboard_max_transfer_size = bytes;
+        self
+    }
+
+    /// Enables or disables microphone redirection (`AUDIO_INPUT` channel)
+    #[must_use]
+    pub const fn with_microphone(mut self, enabled: bool) -> Self {
+        self.microphone_enabled = enabled;
+        self
+    }
+
     /// Sets RemoteApp configuration
     #[must_use]
     pub fn with_remote_app(mut self, app: RemoteAppConfig) -> Self {
@@ -417,10 +463,14 @@ impl RdpClientConfig {
         self
     }
 
-    /// Returns the server address as "host:port"
+    /// Returns the server address as "host:port", bracketing IPv6 literals
     #[must_use]
     pub fn server_address(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+        format!(
+            "{}:{}",
+            crate::connection::host_for_uri(&self.host),
+            self.port
+        )
     }
 
     /// Returns whether this connection uses a gateway
@@ -493,6 +543,10 @@ pub enum ConfigValidationError {
     /// Gateway configuration error
     #[error("Gateway configuration error: {0}")]
     GatewayError(String),
+
+    /// Shared folder cannot be redirected
+    #[error("Invalid shared folder: {0}")]
+    InvalidSharedFolder(String),
 }
 
 #[cfg(test)]
@@ -523,6 +577,9 @@ mod tests {
     fn test_server_address() {
         let config = RdpClientConfig::new("localhost").with_port(3389);
         assert_eq!(config.server_address(), "localhost:3389");
+
+        let config = RdpClientConfig::new("fe80::1").with_port(3389);
+        assert_eq!(config.server_address(), "[fe80::1]:3389");
     }
 
     #[test]
@@ -533,4 +590,14 @@ mod tests {
         assert!(config.clipboard_enabled);
         assert!(config.nla_enabled);
     }
+
+    #[test]
+    fn test_shared_folder_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(SharedFolder::new("Home", dir.path()).validate().is_ok());
+        assert!(SharedFolder::new("", dir.path()).validate().is_err());
+        assert!(SharedFolder::new("Missing", dir.path().join("missing"))
+            .validate()
+            .is_err());
+    }
 }
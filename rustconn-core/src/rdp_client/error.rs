@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use super::DisconnectReason;
+
 /// Error type for RDP client operations
 #[derive(Debug, Error, Clone)]
 pub enum RdpClientError {
@@ -54,8 +56,75 @@ pub enum RdpClientError {
     Unsupported(String),
 }
 
+impl RdpClientError {
+    /// Classifies the error as a disconnect reason
+    ///
+    /// Connection failures are classified by message, since they cover both
+    /// network problems and rejected credentials.
+    #[must_use]
+    pub fn disconnect_reason(&self) -> DisconnectReason {
+        match self {
+            Self::ConnectionFailed(msg) => match DisconnectReason::from_error(msg) {
+                DisconnectReason::Unknown => DisconnectReason::NetworkError,
+                reason => reason,
+            },
+            Self::AuthenticationFailed(_) => DisconnectReason::AuthenticationFailed,
+            Self::ProtocolError(_) => DisconnectReason::ProtocolError,
+            Self::IoError(_) => DisconnectReason::NetworkError,
+            Self::Timeout => DisconnectReason::Timeout,
+            Self::ServerDisconnected(_) => DisconnectReason::ServerClosed,
+            Self::TlsError(_)
+            | Self::NotConnected
+            | Self::AlreadyConnected
+            | Self::InvalidConfig(_)
+            | Self::ChannelError(_)
+            | Self::Unsupported(_) => DisconnectReason::Unknown,
+        }
+    }
+
+    /// Returns true if the failure may go away by reconnecting
+    ///
+    /// Network drops, timeouts and server-side disconnects are transient;
+    /// protocol, authentication, TLS and configuration errors are permanent.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self.disconnect_reason(),
+            DisconnectReason::NetworkError
+                | DisconnectReason::ServerClosed
+                | DisconnectReason::Timeout
+        )
+    }
+}
+
 impl From<std::io::Error> for RdpClientError {
     fn from(err: std::io::Error) -> Self {
         Self::IoError(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_classification() {
+        assert!(RdpClientError::IoError("broken pipe".to_string()).is_transient());
+        assert!(RdpClientError::Timeout.is_transient());
+        assert!(RdpClientError::ConnectionFailed("Read error: reset".to_string()).is_transient());
+        assert!(!RdpClientError::ConnectionFailed(
+            "Connection finalize failed: CredSSP authentication rejected".to_string()
+        )
+        .is_transient());
+        assert!(!RdpClientError::AuthenticationFailed("bad password".to_string()).is_transient());
+        assert!(!RdpClientError::TlsError("certificate".to_string()).is_transient());
+        assert!(!RdpClientError::InvalidConfig("port".to_string()).is_transient());
+        assert!(
+            !RdpClientError::ProtocolError("Session error: bad PDU".to_string()).is_transient()
+        );
+        assert!(
+            !RdpClientError::ConnectionFailed("Protocol negotiation failed".to_string())
+                .is_transient()
+        );
+    }
+}
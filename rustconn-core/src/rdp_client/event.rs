@@ -10,6 +10,8 @@
 #![allow(clippy::option_if_let_else)]
 #![allow(clippy::redundant_clone)]
 
+use super::multimonitor::MonitorDefinition;
+
 /// Clipboard format information for RDP clipboard operations
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClipboardFormatInfo {
@@ -34,6 +36,15 @@ pub struct ClipboardFileInfo {
     pub index: u32,
 }
 
+/// Direction of a clipboard file transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardTransferDirection {
+    /// Files copied in the remote session, saved locally
+    Download,
+    /// Local files pasted into the remote session
+    Upload,
+}
+
 impl ClipboardFileInfo {
     /// Windows file attribute: Directory
     pub const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x10;
@@ -88,6 +99,18 @@ impl ClipboardFormatInfo {
     pub const HTML: u32 = 0xC0A0;
     /// File list format (`CF_HDROP`)
     pub const FILE_LIST: u32 = 15;
+    /// Registered file list format (`FileGroupDescriptorW`) advertised by this client
+    pub const FILE_GROUP_DESCRIPTOR: u32 = 0xC0A2;
+    /// Name under which file lists are registered on the clipboard
+    pub const FILE_GROUP_DESCRIPTOR_NAME: &'static str = "FileGroupDescriptorW";
+    /// Device-independent bitmap format (`CF_DIB`)
+    pub const DIB: u32 = 8;
+    /// Device-independent bitmap with a V5 header (`CF_DIBV5`)
+    pub const DIBV5: u32 = 17;
+    /// Registered PNG format advertised by this client
+    pub const PNG: u32 = 0xC0A1;
+    /// Name under which PNG images are registered on the clipboard
+    pub const PNG_FORMAT_NAME: &'static str = "PNG";
 
     /// Creates a new clipboard format info
     #[must_use]
@@ -104,11 +127,263 @@ impl ClipboardFormatInfo {
         }
     }
 
+    /// Creates a device-independent bitmap format
+    #[must_use]
+    pub const fn dib() -> Self {
+        Self {
+            id: Self::DIB,
+            name: None,
+        }
+    }
+
+    /// Creates the registered PNG format advertised by this client
+    #[must_use]
+    pub fn png() -> Self {
+        Self {
+            id: Self::PNG,
+            name: Some(Self::PNG_FORMAT_NAME.to_string()),
+        }
+    }
+
+    /// Creates the registered file list format advertised by this client
+    #[must_use]
+    pub fn file_group_descriptor() -> Self {
+        Self {
+            id: Self::FILE_GROUP_DESCRIPTOR,
+            name: Some(Self::FILE_GROUP_DESCRIPTOR_NAME.to_string()),
+        }
+    }
+
+    /// Returns true if this is a `FileGroupDescriptorW` file list
+    #[must_use]
+    pub fn is_file_group_descriptor(&self) -> bool {
+        self.name
+            .as_deref()
+            .map_or(self.id == Self::FILE_GROUP_DESCRIPTOR, |name| {
+                name.eq_ignore_ascii_case(Self::FILE_GROUP_DESCRIPTOR_NAME)
+            })
+    }
+
     /// Returns true if this is a text format
     #[must_use]
     pub const fn is_text(&self) -> bool {
         matches!(self.id, Self::TEXT | Self::UNICODE_TEXT)
     }
+
+    /// Returns true if this is a PNG format
+    ///
+    /// PNG is a registered format, so the peer's ID differs from ours and
+    /// the format is recognized by name.
+    #[must_use]
+    pub fn is_png(&self) -> bool {
+        self.name.as_deref().map_or(self.id == Self::PNG, |name| {
+            name.eq_ignore_ascii_case(Self::PNG_FORMAT_NAME)
+        })
+    }
+
+    /// Returns true if this is an image format
+    #[must_use]
+    pub fn is_image(&self) -> bool {
+        matches!(self.id, Self::DIB | Self::DIBV5) || self.is_png()
+    }
+}
+
+/// Image payload carried over the RDP clipboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardImage {
+    /// PNG-encoded image
+    Png(Vec<u8>),
+    /// Straight-alpha RGBA pixels, top-down rows without padding
+    Rgba {
+        /// Width in pixels
+        width: u32,
+        /// Height in pixels
+        height: u32,
+        /// Pixel data (`width * height * 4` bytes)
+        pixels: Vec<u8>,
+    },
+}
+
+impl ClipboardImage {
+    /// Size of a `BITMAPINFOHEADER` in bytes
+    const BITMAPINFOHEADER_SIZE: usize = 40;
+    /// `BI_RGB` compression (uncompressed)
+    const BI_RGB: u32 = 0;
+    /// `BI_BITFIELDS` compression (uncompressed with color masks)
+    const BI_BITFIELDS: u32 = 3;
+    /// `BI_ALPHABITFIELDS` compression (uncompressed with color and alpha masks)
+    const BI_ALPHABITFIELDS: u32 = 6;
+
+    /// Decodes clipboard data received in the given format
+    ///
+    /// Returns `None` for non-image formats and for bitmaps that cannot be
+    /// decoded.
+    #[must_use]
+    pub fn decode(format: &ClipboardFormatInfo, data: &[u8]) -> Option<Self> {
+        if format.is_png() {
+            Some(Self::Png(data.to_vec()))
+        } else if format.is_image() {
+            Self::from_dib(data)
+        } else {
+            None
+        }
+    }
+
+    /// Encodes the image for the given clipboard format
+    ///
+    /// PNG payloads can only be sent as PNG and pixel payloads only as
+    /// `CF_DIB`; other combinations return `None`.
+    #[must_use]
+    pub fn encode_for(&self, format: &ClipboardFormatInfo) -> Option<Vec<u8>> {
+        match self {
+            Self::Png(data) if format.is_png() => Some(data.clone()),
+            Self::Rgba { .. } if format.id == ClipboardFormatInfo::DIB => self.to_dib(),
+            _ => None,
+        }
+    }
+
+    /// Parses a packed device-independent bitmap (`CF_DIB` or `CF_DIBV5`)
+    ///
+    /// Supports uncompressed 24-bit and 32-bit bitmaps, bottom-up or
+    /// top-down, with `BITMAPINFOHEADER`, `BITMAPV4HEADER` or
+    /// `BITMAPV5HEADER`. A 32-bit bitmap whose alpha channel is all zero is
+    /// treated as opaque.
+    #[must_use]
+    pub fn from_dib(data: &[u8]) -> Option<Self> {
+        let u32_at = |offset: usize| -> Option<u32> {
+            let bytes = data.get(offset..offset + 4)?;
+            Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        };
+
+        let header_size = u32_at(0)? as usize;
+        if header_size < Self::BITMAPINFOHEADER_SIZE {
+            return None;
+        }
+        let width = u32_at(4)? as i32;
+        let height = u32_at(8)? as i32;
+        let bit_count = data.get(14..16).map(|b| u16::from_le_bytes([b[0], b[1]]))?;
+        let compression = u32_at(16)?;
+        if width <= 0 || height == 0 {
+            return None;
+        }
+
+        // Color masks follow a BITMAPINFOHEADER, or sit inside V4/V5 headers
+        let mut pixel_offset = header_size;
+        let masks = match (compression, bit_count) {
+            (Self::BI_RGB, 24 | 32) => [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000],
+            (Self::BI_BITFIELDS | Self::BI_ALPHABITFIELDS, 32) => {
+                let has_alpha_mask = compression == Self::BI_ALPHABITFIELDS
+                    || header_size > Self::BITMAPINFOHEADER_SIZE;
+                if header_size == Self::BITMAPINFOHEADER_SIZE {
+                    pixel_offset += if has_alpha_mask { 16 } else { 12 };
+                }
+                [
+                    u32_at(40)?,
+                    u32_at(44)?,
+                    u32_at(48)?,
+                    if has_alpha_mask { u32_at(52)? } else { 0 },
+                ]
+            }
+            _ => return None,
+        };
+
+        let width = width.unsigned_abs();
+        let rows = height.unsigned_abs();
+        let bytes_per_pixel = usize::from(bit_count / 8);
+        let stride = (width as usize * usize::from(bit_count)).div_ceil(32) * 4;
+        let pixel_data = data.get(pixel_offset..)?;
+        if pixel_data.len() < stride.checked_mul(rows as usize)? {
+            return None;
+        }
+
+        let channel = |pixel: u32, mask: u32| -> Option<u8> {
+            if mask == 0 {
+                return None;
+            }
+            let max = u64::from(mask >> mask.trailing_zeros());
+            let value = u64::from((pixel & mask) >> mask.trailing_zeros());
+            Some((value * 255 / max) as u8)
+        };
+
+        let mut pixels = Vec::with_capacity(width as usize * rows as usize * 4);
+        let mut has_alpha = false;
+        for row in 0..rows as usize {
+            // Positive heights are stored bottom-up
+            let source_row = if height > 0 {
+                rows as usize - 1 - row
+            } else {
+                row
+            };
+            let line = &pixel_data[source_row * stride..];
+            for px in line.chunks_exact(bytes_per_pixel).take(width as usize) {
+                let pixel = if bytes_per_pixel == 4 {
+                    u32::from_le_bytes([px[0], px[1], px[2], px[3]])
+                } else {
+                    u32::from_le_bytes([px[0], px[1], px[2], 0])
+                };
+                let alpha = if bytes_per_pixel == 4 {
+                    channel(pixel, masks[3])
+                } else {
+                    None
+                };
+                has_alpha |= alpha.is_some_and(|a| a != 0);
+                pixels.extend_from_slice(&[
+                    channel(pixel, masks[0]).unwrap_or(0),
+                    channel(pixel, masks[1]).unwrap_or(0),
+                    channel(pixel, masks[2]).unwrap_or(0),
+                    alpha.unwrap_or(0),
+                ]);
+            }
+        }
+
+        if !has_alpha {
+            pixels.iter_mut().skip(3).step_by(4).for_each(|a| *a = 255);
+        }
+
+        Some(Self::Rgba {
+            width,
+            height: rows,
+            pixels,
+        })
+    }
+
+    /// Encodes RGBA pixels as a 32-bit bottom-up `CF_DIB` bitmap
+    ///
+    /// Returns `None` for PNG payloads and for pixel buffers whose size does
+    /// not match the dimensions.
+    #[must_use]
+    pub fn to_dib(&self) -> Option<Vec<u8>> {
+        let Self::Rgba {
+            width,
+            height,
+            pixels,
+        } = self
+        else {
+            return None;
+        };
+        let row_len = (*width as usize).checked_mul(4)?;
+        let image_size = row_len.checked_mul(*height as usize)?;
+        if pixels.len() != image_size || image_size == 0 {
+            return None;
+        }
+
+        let mut dib = Vec::with_capacity(Self::BITMAPINFOHEADER_SIZE + image_size);
+        dib.extend_from_slice(&(Self::BITMAPINFOHEADER_SIZE as u32).to_le_bytes());
+        dib.extend_from_slice(&i32::try_from(*width).ok()?.to_le_bytes());
+        dib.extend_from_slice(&i32::try_from(*height).ok()?.to_le_bytes());
+        dib.extend_from_slice(&1u16.to_le_bytes()); // planes
+        dib.extend_from_slice(&32u16.to_le_bytes()); // bit count
+        dib.extend_from_slice(&Self::BI_RGB.to_le_bytes());
+        dib.extend_from_slice(&u32::try_from(image_size).ok()?.to_le_bytes());
+        dib.extend_from_slice(&[0; 16]); // resolution and palette fields
+
+        for row in pixels.chunks_exact(row_len).rev() {
+            for px in row.chunks_exact(4) {
+                dib.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+            }
+        }
+        Some(dib)
+    }
 }
 
 /// Rectangle coordinates for RDP operations
@@ -369,11 +644,21 @@ pub enum RdpClientEvent {
         width: u16,
         /// Server-negotiated height
         height: u16,
+        /// Monitor layout of the session, with exactly one primary monitor
+        ///
+        /// Single-monitor sessions report one monitor covering the desktop.
+        monitors: Vec<MonitorDefinition>,
     },
 
     /// Connection closed
     Disconnected,
 
+    /// Connection was lost and a reconnection attempt is about to start
+    Reconnecting {
+        /// Reconnection attempt number, starting at 1
+        attempt: u32,
+    },
+
     /// Resolution changed
     ResolutionChanged {
         /// New width
@@ -453,6 +738,9 @@ pub enum RdpClientEvent {
     /// Request to fetch clipboard data from server (internal, triggers `initiate_paste`)
     ClipboardPasteRequest(ClipboardFormatInfo),
 
+    /// Server clipboard image
+    ClipboardImage(ClipboardImage),
+
     /// File list available on server clipboard (`CF_HDROP`)
     ClipboardFileList(Vec<ClipboardFileInfo>),
 
@@ -474,6 +762,34 @@ pub enum RdpClientEvent {
         size: u64,
     },
 
+    /// Clipboard file transfer progressed
+    ClipboardTransferProgress {
+        /// Transfer direction
+        direction: ClipboardTransferDirection,
+        /// Name of the file being transferred
+        file_name: String,
+        /// Bytes transferred so far
+        transferred: u64,
+        /// Total bytes of the transfer
+        total: u64,
+    },
+
+    /// Clipboard file transfer finished
+    ClipboardTransferComplete {
+        /// Transfer direction
+        direction: ClipboardTransferDirection,
+        /// Number of files transferred
+        files: usize,
+    },
+
+    /// Clipboard file transfer failed or was rejected
+    ClipboardTransferFailed {
+        /// Transfer direction
+        direction: ClipboardTransferDirection,
+        /// Reason for the user
+        message: String,
+    },
+
     /// Authentication required (for NLA)
     AuthRequired,
 
@@ -483,8 +799,15 @@ pub enum RdpClientEvent {
     /// Server sent a warning/info message
     ServerMessage(String),
 
+    /// Response to `CaptureFrame`: the current frame as a PNG image
+    FrameCaptured(Vec<u8>),
+
+    /// Response to `CaptureFrame` when the frame could not be captured
+    CaptureFailed(String),
+
     // ========== Audio Events ==========
     /// Audio format changed (server selected a format)
+    #[cfg(feature = "rdp-embedded")]
     AudioFormatChanged(super::audio::AudioFormatInfo),
 
     /// Audio data received from server
@@ -507,6 +830,36 @@ pub enum RdpClientEvent {
 
     /// Audio channel closed
     AudioClose,
+
+    // ========== Audio Input Events ==========
+    /// Server opened the microphone stream; record in this format
+    #[cfg(feature = "rdp-embedded")]
+    AudioInputOpened {
+        /// Capture format selected by the server
+        format: super::audio::AudioFormatInfo,
+        /// Audio frames the server expects per Data PDU (0 if unchanged)
+        frames_per_packet: u32,
+    },
+
+    /// Microphone stream closed by the server
+    AudioInputClosed,
+
+    /// Server declined microphone redirection (reason for the user)
+    AudioInputDeclined(String),
+
+    /// Shared folder is available as a drive in the remote session
+    SharedFolderMounted {
+        /// Share name shown in the remote session
+        name: String,
+    },
+
+    /// Shared folder could not be redirected
+    SharedFolderError {
+        /// Share name of the folder
+        name: String,
+        /// Reason the folder was not mounted
+        message: String,
+    },
 }
 
 /// Commands sent from GUI to RDP client
@@ -582,6 +935,14 @@ pub enum RdpClientCommand {
         data: Vec<u8>,
     },
 
+    /// Send clipboard image to server (response to an image `ClipboardDataRequest`)
+    ClipboardImage {
+        /// Format ID requested by the server
+        format_id: u32,
+        /// Image to encode for the requested format
+        image: ClipboardImage,
+    },
+
     /// Notify server that client clipboard has new data
     ClipboardCopy(Vec<ClipboardFormatInfo>),
 
@@ -605,6 +966,18 @@ pub enum RdpClientCommand {
         length: u32,
     },
 
+    /// Save the files on the server clipboard into a local directory
+    ///
+    /// The files are streamed in chunks; progress is reported with
+    /// `ClipboardTransferProgress` events.
+    SaveClipboardFiles {
+        /// Directory receiving the files
+        target_dir: std::path::PathBuf,
+    },
+
+    /// Offer local files and directories to the server clipboard
+    ClipboardCopyFiles(Vec<std::path::PathBuf>),
+
     /// Request screen refresh
     RefreshScreen,
 
@@ -619,6 +992,23 @@ pub enum RdpClientCommand {
     /// Send Ctrl+Alt+Del key sequence
     SendCtrlAltDel,
 
+    /// Capture the latest framebuffer as a PNG image
+    ///
+    /// Answered with `FrameCaptured` or `CaptureFailed`.
+    CaptureFrame,
+
+    /// Start sending microphone audio to the server
+    ///
+    /// Answered with `AudioInputDeclined` if the server has not opened the
+    /// audio input channel.
+    StartAudioCapture,
+
+    /// Stop sending microphone audio to the server
+    StopAudioCapture,
+
+    /// Captured microphone PCM data in the format of `AudioInputOpened`
+    AudioInputData(Vec<u8>),
+
     /// Provide authentication credentials
     Authenticate {
         /// Username
@@ -679,10 +1069,17 @@ mod tests {
         let event = RdpClientEvent::Connected {
             width: 1920,
             height: 1080,
+            monitors: vec![MonitorDefinition::primary(1920, 1080)],
         };
-        if let RdpClientEvent::Connected { width, height } = event {
+        if let RdpClientEvent::Connected {
+            width,
+            height,
+            monitors,
+        } = event
+        {
             assert_eq!(width, 1920);
             assert_eq!(height, 1080);
+            assert_eq!(monitors.len(), 1);
         }
     }
 
@@ -825,4 +1222,57 @@ mod tests {
             panic!("Expected FrameUpdate event");
         }
     }
+
+    #[test]
+    fn test_clipboard_image_formats() {
+        assert!(ClipboardFormatInfo::dib().is_image());
+        assert!(ClipboardFormatInfo::png().is_png());
+        assert!(ClipboardFormatInfo::new(0xC0FF, Some("png".to_string())).is_image());
+        assert!(!ClipboardFormatInfo::new(0xC0FF, Some("HTML Format".to_string())).is_image());
+        assert!(!ClipboardFormatInfo::unicode_text().is_image());
+    }
+
+    #[test]
+    fn test_clipboard_image_dib_roundtrip() {
+        let image = ClipboardImage::Rgba {
+            width: 2,
+            height: 2,
+            pixels: vec![
+                255, 0, 0, 255, 0, 255, 0, 255, // top row: red, green
+                0, 0, 255, 255, 255, 255, 255, 128, // bottom row: blue, translucent white
+            ],
+        };
+        let dib = image.encode_for(&ClipboardFormatInfo::dib()).unwrap();
+        assert_eq!(dib.len(), 40 + 16);
+        // Bottom-up: first stored pixel is bottom-left (blue) in BGRA order
+        assert_eq!(&dib[40..44], &[255, 0, 0, 255]);
+
+        let decoded = ClipboardImage::decode(&ClipboardFormatInfo::dib(), &dib).unwrap();
+        assert_eq!(decoded, image);
+        assert!(image.encode_for(&ClipboardFormatInfo::png()).is_none());
+    }
+
+    #[test]
+    fn test_clipboard_image_from_24bit_dib() {
+        // 1x2 24-bit bottom-up bitmap, rows padded to 4 bytes, no alpha
+        let mut dib = vec![0u8; 40];
+        dib[0] = 40;
+        dib[4] = 1;
+        dib[8] = 2;
+        dib[12] = 1;
+        dib[14] = 24;
+        dib.extend_from_slice(&[0, 0, 255, 0]); // bottom: red
+        dib.extend_from_slice(&[255, 0, 0, 0]); // top: blue
+
+        let image = ClipboardImage::from_dib(&dib).unwrap();
+        assert_eq!(
+            image,
+            ClipboardImage::Rgba {
+                width: 1,
+                height: 2,
+                pixels: vec![0, 0, 255, 255, 255, 0, 0, 255],
+            }
+        );
+        assert!(ClipboardImage::from_dib(&dib[..45]).is_none());
+    }
 }
@@ -23,6 +23,7 @@
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::unreadable_literal)]
 
+use super::multimonitor::{MonitorDefinition, MonitorLayout};
 use serde::{Deserialize, Serialize};
 
 /// Represents the transformation parameters for coordinate conversion
@@ -406,6 +407,63 @@ pub fn should_resize(
     width_diff >= u32::from(threshold) || height_diff >= u32::from(threshold)
 }
 
+/// Clamps a monitor to the RDP resolution limits
+///
+/// The top-left corner is kept and the size is clamped to
+/// `MIN/MAX_RDP_WIDTH/HEIGHT`. The width is rounded down to an even value,
+/// as required by the monitor layout PDU (MS-RDPEDISP 2.2.2.2.1).
+#[must_use]
+pub fn clamp_monitor(monitor: &MonitorDefinition) -> MonitorDefinition {
+    let width = monitor
+        .width()
+        .clamp(u32::from(MIN_RDP_WIDTH), u32::from(MAX_RDP_WIDTH))
+        & !1;
+    let height = monitor
+        .height()
+        .clamp(u32::from(MIN_RDP_HEIGHT), u32::from(MAX_RDP_HEIGHT));
+
+    MonitorDefinition {
+        right: monitor.left + width as i32,
+        bottom: monitor.top + height as i32,
+        ..monitor.clone()
+    }
+}
+
+/// Builds the monitor list to negotiate with the RDP server
+///
+/// Takes the active monitors of the layout, clamps each one with
+/// [`clamp_monitor`] and makes sure exactly one monitor is primary (the
+/// first flagged one, or the first monitor if none is). Monitors are then
+/// translated so the primary monitor sits at the origin, as the server
+/// expects.
+///
+/// Returns an empty list if the layout has no active monitors. The client
+/// sends the result in a Display Control monitor layout PDU (MS-RDPEDISP
+/// 2.2.2.2) when it holds more than one monitor.
+#[must_use]
+pub fn negotiate_monitor_layout(layout: &MonitorLayout) -> Vec<MonitorDefinition> {
+    let mut monitors: Vec<MonitorDefinition> = layout
+        .active_monitors()
+        .into_iter()
+        .map(clamp_monitor)
+        .collect();
+
+    let primary = monitors.iter().position(|m| m.is_primary).unwrap_or(0);
+    let Some((origin_x, origin_y)) = monitors.get(primary).map(|m| (m.left, m.top)) else {
+        return monitors;
+    };
+
+    for (i, monitor) in monitors.iter_mut().enumerate() {
+        monitor.is_primary = i == primary;
+        monitor.left -= origin_x;
+        monitor.right -= origin_x;
+        monitor.top -= origin_y;
+        monitor.bottom -= origin_y;
+    }
+
+    monitors
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -504,6 +562,54 @@ mod tests {
         // Change above threshold
         assert!(should_resize(1920, 1080, 1980, 1080, 50));
     }
+
+    #[test]
+    fn test_clamp_monitor_limits_and_even_width() {
+        let tiny = clamp_monitor(&MonitorDefinition::new(1, 1920, 0, 1920 + 50, 50));
+        assert_eq!(tiny.left, 1920);
+        assert_eq!(tiny.width(), u32::from(MIN_RDP_WIDTH));
+        assert_eq!(tiny.height(), u32::from(MIN_RDP_HEIGHT));
+
+        let huge = clamp_monitor(&MonitorDefinition::primary(10_000, 9_999));
+        assert_eq!(huge.width(), u32::from(MAX_RDP_WIDTH));
+        assert_eq!(huge.height(), u32::from(MAX_RDP_HEIGHT));
+
+        let odd = clamp_monitor(&MonitorDefinition::primary(1367, 768));
+        assert_eq!(odd.width(), 1366);
+        assert_eq!(odd.height(), 768);
+    }
+
+    #[test]
+    fn test_negotiate_monitor_layout_single_primary_at_origin() {
+        let mut layout = MonitorLayout::new();
+        layout.use_all_monitors = true;
+        layout.add_monitor(MonitorDefinition::new(0, -1280, 0, 0, 1024));
+        layout.add_monitor(MonitorDefinition::new(1, 0, 0, 1920, 1080).with_primary(true));
+        layout.add_monitor(MonitorDefinition::new(2, 1920, 0, 3840, 1080).with_primary(true));
+
+        let monitors = negotiate_monitor_layout(&layout);
+        assert_eq!(monitors.len(), 3);
+        assert_eq!(monitors.iter().filter(|m| m.is_primary).count(), 1);
+        assert!(monitors[1].is_primary);
+        assert_eq!((monitors[1].left, monitors[1].top), (0, 0));
+        assert_eq!((monitors[0].left, monitors[0].right), (-1280, 0));
+    }
+
+    #[test]
+    fn test_negotiate_monitor_layout_defaults_primary() {
+        let mut layout = MonitorLayout::new();
+        layout.use_all_monitors = true;
+        layout.add_monitor(MonitorDefinition::new(0, 100, 50, 1380, 1074));
+        layout.add_monitor(MonitorDefinition::new(1, 1380, 50, 3300, 1130));
+
+        let monitors = negotiate_monitor_layout(&layout);
+        assert!(monitors[0].is_primary);
+        assert!(!monitors[1].is_primary);
+        assert_eq!((monitors[0].left, monitors[0].top), (0, 0));
+        assert_eq!((monitors[1].left, monitors[1].top), (1280, 0));
+
+        assert!(negotiate_monitor_layout(&MonitorLayout::new()).is_empty());
+    }
 }
 
 // ============================================================================
@@ -44,9 +44,13 @@
 #[cfg(feature = "rdp-embedded")]
 pub mod audio;
 #[cfg(feature = "rdp-embedded")]
+pub mod audio_input;
+#[cfg(feature = "rdp-embedded")]
 mod client;
 #[cfg(feature = "rdp-embedded")]
 pub mod clipboard;
+#[cfg(feature = "rdp-embedded")]
+pub mod clipboard_files;
 mod config;
 #[cfg(feature = "rdp-embedded")]
 pub mod dir_watcher;
@@ -70,7 +74,8 @@ pub use config::{
 pub use error::RdpClientError;
 pub use event::{
     convert_to_bgra, create_frame_update, create_frame_update_with_conversion, ClipboardFileInfo,
-    ClipboardFormatInfo, PixelFormat, RdpClientCommand, RdpClientEvent, RdpRect,
+    ClipboardFormatInfo, ClipboardImage, ClipboardTransferDirection, PixelFormat, RdpClientCommand,
+    RdpClientEvent, RdpRect,
 };
 pub use gateway::{GatewayAuthMethod, GatewayConfig, GatewayError, GatewayState};
 pub use graphics::{
@@ -1,8 +1,9 @@
 //! Multi-monitor support for RDP sessions
 //!
 //! This module provides structures and utilities for multi-monitor RDP sessions.
-//! While IronRDP doesn't fully support multi-monitor yet, this prepares the
-//! infrastructure for when it becomes available.
+//! The embedded client sends the layout through the Display Control channel
+//! (MS-RDPEDISP) once the server advertises it; see
+//! [`negotiate_monitor_layout`](super::input::negotiate_monitor_layout).
 //!
 //! # RDP Multi-Monitor Protocol
 //!
@@ -3,6 +3,10 @@
 //! This module implements the `RdpdrBackend` trait from `ironrdp-rdpdr` to provide
 //! shared folder functionality for RDP sessions.
 //!
+//! Each configured shared folder is announced as its own drive device. I/O
+//! requests are resolved against the folder of the device they target, and
+//! folders marked read-only reject every request that would modify them.
+//!
 //! # Directory Change Notifications
 //!
 //! The module supports real-time directory change notifications using the `notify` crate
@@ -11,6 +15,7 @@
 //! when files are created, modified, deleted, or renamed.
 
 use super::dir_watcher::{DirectoryChange, DirectoryWatcher, WatchRequest};
+use super::{RdpClientEvent, SharedFolder};
 use ironrdp::core::impl_as_any;
 use ironrdp::pdu::PduResult;
 use ironrdp::rdpdr::pdu::efs::{
@@ -37,13 +42,16 @@ use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::os::unix::fs::MetadataExt;
 use std::path::PathBuf;
+use std::sync::mpsc::Sender;
 use tracing::{debug, trace, warn};
 
 /// RDPDR backend for Linux/Unix shared folders
 #[derive(Debug)]
 pub struct RustConnRdpdrBackend {
-    /// Base path for the shared folder
-    base_path: String,
+    /// Redirected drives keyed by RDPDR device ID
+    drives: HashMap<u32, RedirectedDrive>,
+    /// Channel for mount results
+    event_tx: Option<Sender<RdpClientEvent>>,
     /// Next file ID to assign
     next_file_id: u32,
     /// Map of file IDs to open file handles
@@ -61,6 +69,17 @@ pub struct RustConnRdpdrBackend {
     dir_watcher: Option<DirectoryWatcher>,
 }
 
+/// Local folder announced to the server as a drive
+#[derive(Debug, Clone)]
+struct RedirectedDrive {
+    /// Share name shown in the remote session
+    name: String,
+    /// Local base path, always ending with `/`
+    base_path: String,
+    /// Reject requests that modify the folder
+    read_only: bool,
+}
+
 /// Pending directory change notification
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -88,15 +107,41 @@ struct FileLock {
 impl_as_any!(RustConnRdpdrBackend);
 
 impl RustConnRdpdrBackend {
-    /// Creates a new RDPDR backend with the given base path
+    /// Creates a new RDPDR backend serving a single writable drive (device 1)
     #[must_use]
     pub fn new(base_path: String) -> Self {
-        // Ensure path ends with /
-        let base_path = if base_path.ends_with('/') {
-            base_path
-        } else {
-            format!("{base_path}/")
-        };
+        Self::build(vec![(1, SharedFolder::new("RustConn", base_path))], None)
+    }
+
+    /// Creates a backend serving each shared folder under its device ID
+    ///
+    /// The result of every drive announcement is reported through `event_tx`
+    /// as `SharedFolderMounted` or `SharedFolderError`.
+    #[must_use]
+    pub fn with_shared_folders(
+        folders: Vec<(u32, SharedFolder)>,
+        event_tx: Sender<RdpClientEvent>,
+    ) -> Self {
+        Self::build(folders, Some(event_tx))
+    }
+
+    fn build(folders: Vec<(u32, SharedFolder)>, event_tx: Option<Sender<RdpClientEvent>>) -> Self {
+        let drives = folders
+            .into_iter()
+            .map(|(device_id, folder)| {
+                let mut base_path = folder.path.to_string_lossy().into_owned();
+                // Ensure path ends with /
+                if !base_path.ends_with('/') {
+                    base_path.push('/');
+                }
+                let drive = RedirectedDrive {
+                    name: folder.name,
+                    base_path,
+                    read_only: folder.read_only,
+                };
+                (device_id, drive)
+            })
+            .collect();
 
         // Try to create directory watcher
         let dir_watcher = match DirectoryWatcher::new() {
@@ -111,7 +156,8 @@ impl RustConnRdpdrBackend {
         };
 
         Self {
-            base_path,
+            drives,
+            event_tx,
             next_file_id: 1,
             file_handles: HashMap::new(),
             file_paths: HashMap::new(),
@@ -129,10 +175,22 @@ impl RustConnRdpdrBackend {
         id
     }
 
-    /// Converts a Windows-style path to Unix path
-    fn to_unix_path(&self, windows_path: &str) -> String {
+    /// Converts a Windows-style path on the given drive to a Unix path
+    ///
+    /// Returns `None` if the device ID is not a redirected drive.
+    fn to_unix_path(&self, device_id: u32, windows_path: &str) -> Option<String> {
+        let drive = self.drives.get(&device_id)?;
         let unix_path = windows_path.replace('\\', "/");
-        format!("{}{}", self.base_path, unix_path.trim_start_matches('/'))
+        Some(format!(
+            "{}{}",
+            drive.base_path,
+            unix_path.trim_start_matches('/')
+        ))
+    }
+
+    /// Returns whether the given drive rejects modifications
+    fn is_read_only(&self, device_id: u32) -> bool {
+        self.drives.get(&device_id).is_some_and(|d| d.read_only)
     }
 
     /// Notifies pending watchers about a directory change
@@ -234,6 +292,29 @@ impl RdpdrBackend for RustConnRdpdrBackend {
         pdu: ServerDeviceAnnounceResponse,
     ) -> PduResult<()> {
         tracing::debug!("RDPDR device announce response: {:?}", pdu);
+        let Some(drive) = self.drives.get(&pdu.device_id) else {
+            return Ok(());
+        };
+
+        let event = if pdu.result_code == NtStatus::SUCCESS {
+            debug!("Shared folder '{}' mounted", drive.name);
+            RdpClientEvent::SharedFolderMounted {
+                name: drive.name.clone(),
+            }
+        } else {
+            warn!(
+                "Server rejected shared folder '{}': {:?}",
+                drive.name, pdu.result_code
+            );
+            RdpClientEvent::SharedFolderError {
+                name: drive.name.clone(),
+                message: format!("Server rejected the drive ({:?})", pdu.result_code),
+            }
+        };
+
+        if let Some(tx) = &self.event_tx {
+            let _ = tx.send(event);
+        }
         Ok(())
     }
 
@@ -291,7 +372,11 @@ impl RustConnRdpdrBackend {
     #[allow(clippy::unnecessary_wraps)]
     fn handle_create(&mut self, req: DeviceCreateRequest) -> PduResult<Vec<SvcMessage>> {
         let file_id = self.alloc_file_id();
-        let path = self.to_unix_path(&req.path);
+        let device_id = req.device_io_request.device_id;
+        let Some(path) = self.to_unix_path(device_id, &req.path) else {
+            return Ok(vec![create_response(req, file_id, NtStatus::NO_SUCH_FILE)]);
+        };
+        let read_only = self.is_read_only(device_id);
         tracing::trace!(
             "RDPDR create: file_id={}, path='{}', disposition={:?}",
             file_id,
@@ -338,6 +423,9 @@ impl RustConnRdpdrBackend {
                         },
                     ))]);
                 }
+                Err(_) if read_only => {
+                    return Ok(vec![create_response(req, file_id, NtStatus::ACCESS_DENIED)]);
+                }
                 Err(_) => {
                     // Directory doesn't exist, try to create if requested
                     if (req.create_disposition == CreateDisposition::FILE_CREATE
@@ -363,10 +451,22 @@ impl RustConnRdpdrBackend {
             }
         }
 
+        // Read-only drives may only open existing files
+        if read_only {
+            let opens_existing = req.create_disposition == CreateDisposition::FILE_OPEN
+                || (req.create_disposition == CreateDisposition::FILE_OPEN_IF && metadata.is_ok());
+            if !opens_existing {
+                return Ok(vec![create_response(req, file_id, NtStatus::ACCESS_DENIED)]);
+            }
+        }
+
         // Handle file creation/opening
         let mut opts = OpenOptions::new();
         #[allow(clippy::match_same_arms)]
         match req.create_disposition {
+            _ if read_only => {
+                opts.read(true);
+            }
             CreateDisposition::FILE_OPEN => {
                 opts.read(true);
             }
@@ -487,6 +587,17 @@ impl RustConnRdpdrBackend {
     #[allow(clippy::unnecessary_wraps)]
     fn handle_write(&mut self, req: DeviceWriteRequest) -> PduResult<Vec<SvcMessage>> {
         let file_id = req.device_io_request.file_id;
+        if self.is_read_only(req.device_io_request.device_id) {
+            return Ok(vec![SvcMessage::from(RdpdrPdu::DeviceWriteResponse(
+                DeviceWriteResponse {
+                    device_io_reply: DeviceIoResponse::new(
+                        req.device_io_request,
+                        NtStatus::ACCESS_DENIED,
+                    ),
+                    length: 0,
+                },
+            ))]);
+        }
         if let Some(file) = self.file_handles.get_mut(&file_id) {
             if file.seek(SeekFrom::Start(req.offset)).is_ok() {
                 match file.write(&req.write_data) {
@@ -598,18 +709,21 @@ impl RustConnRdpdrBackend {
 
     #[allow(clippy::unnecessary_wraps)]
     #[allow(clippy::needless_pass_by_ref_mut)]
-    #[allow(clippy::unused_self)]
     fn handle_query_volume(
         &mut self,
         req: ServerDriveQueryVolumeInformationRequest,
     ) -> PduResult<Vec<SvcMessage>> {
         let buffer = match req.fs_info_class_lvl {
             FileSystemInformationClassLevel::FILE_FS_ATTRIBUTE_INFORMATION => {
+                let mut file_system_attributes = FileSystemAttributes::FILE_CASE_SENSITIVE_SEARCH
+                    | FileSystemAttributes::FILE_CASE_PRESERVED_NAMES
+                    | FileSystemAttributes::FILE_UNICODE_ON_DISK;
+                if self.is_read_only(req.device_io_request.device_id) {
+                    file_system_attributes |= FileSystemAttributes::FILE_READ_ONLY_VOLUME;
+                }
                 Some(FileSystemInformationClass::FileFsAttributeInformation(
                     FileFsAttributeInformation {
-                        file_system_attributes: FileSystemAttributes::FILE_CASE_SENSITIVE_SEARCH
-                            | FileSystemAttributes::FILE_CASE_PRESERVED_NAMES
-                            | FileSystemAttributes::FILE_UNICODE_ON_DISK,
+                        file_system_attributes,
                         max_component_name_len: 255,
                         file_system_name: "RustConn".to_owned(),
                     },
@@ -758,20 +872,22 @@ impl RustConnRdpdrBackend {
     #[allow(clippy::unnecessary_wraps)]
     #[allow(clippy::needless_pass_by_ref_mut)]
     #[allow(clippy::needless_pass_by_value)]
-    #[allow(clippy::unused_self)]
     fn handle_set_info(
         &mut self,
         req: ServerDriveSetInformationRequest,
     ) -> PduResult<Vec<SvcMessage>> {
         // Basic implementation - just acknowledge
+        let status = if self.is_read_only(req.device_io_request.device_id) {
+            NtStatus::ACCESS_DENIED
+        } else {
+            NtStatus::SUCCESS
+        };
         Ok(vec![SvcMessage::from(
             RdpdrPdu::ClientDriveSetInformationResponse(
-                ClientDriveSetInformationResponse::new(&req, NtStatus::SUCCESS).unwrap_or_else(
-                    |_| {
-                        ClientDriveSetInformationResponse::new(&req, NtStatus::UNSUCCESSFUL)
-                            .expect("infallible")
-                    },
-                ),
+                ClientDriveSetInformationResponse::new(&req, status).unwrap_or_else(|_| {
+                    ClientDriveSetInformationResponse::new(&req, NtStatus::UNSUCCESSFUL)
+                        .expect("infallible")
+                }),
             ),
         )])
     }
@@ -879,6 +995,15 @@ impl RustConnRdpdrBackend {
     }
 }
 
+/// Builds a failed or empty `DeviceCreateResponse` for a create request
+fn create_response(req: DeviceCreateRequest, file_id: u32, status: NtStatus) -> SvcMessage {
+    SvcMessage::from(RdpdrPdu::DeviceCreateResponse(DeviceCreateResponse {
+        device_io_reply: DeviceIoResponse::new(req.device_io_request, status),
+        file_id,
+        information: Information::empty(),
+    }))
+}
+
 /// Converts Unix timestamp (seconds) to Windows FILETIME (100-nanosecond intervals since 1601)
 const fn unix_to_filetime(unix_secs: i64) -> i64 {
     // Windows FILETIME epoch is January 1, 1601
@@ -942,3 +1067,124 @@ fn get_file_attributes(meta: &std::fs::Metadata, file_name: &str) -> FileAttribu
 
     attrs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironrdp::rdpdr::pdu::efs::{
+        DesiredAccess, DeviceIoRequest, MajorFunction, MinorFunction, SharedAccess,
+    };
+    use std::sync::mpsc;
+
+    fn create_request(device_id: u32, path: &str, directory: bool) -> DeviceCreateRequest {
+        DeviceCreateRequest {
+            device_io_request: DeviceIoRequest {
+                device_id,
+                file_id: 0,
+                completion_id: 1,
+                major_function: MajorFunction::Create,
+                minor_function: MinorFunction::IRP_MN_QUERY_DIRECTORY,
+            },
+            desired_access: DesiredAccess::FILE_READ_DATA_OR_FILE_LIST_DIRECTORY,
+            allocation_size: 0,
+            file_attributes: FileAttributes::empty(),
+            shared_access: SharedAccess::FILE_SHARE_READ,
+            create_disposition: CreateDisposition::FILE_CREATE,
+            create_options: if directory {
+                CreateOptions::FILE_DIRECTORY_FILE
+            } else {
+                CreateOptions::empty()
+            },
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_paths_resolve_per_drive() {
+        let (tx, _rx) = mpsc::channel();
+        let backend = RustConnRdpdrBackend::with_shared_folders(
+            vec![
+                (1, SharedFolder::new("Docs", "/home/user/docs")),
+                (2, SharedFolder::new("Music", "/home/user/music/")),
+            ],
+            tx,
+        );
+
+        assert_eq!(
+            backend.to_unix_path(1, "\\report.txt").as_deref(),
+            Some("/home/user/docs/report.txt")
+        );
+        assert_eq!(
+            backend.to_unix_path(2, "\\album\\track.ogg").as_deref(),
+            Some("/home/user/music/album/track.ogg")
+        );
+        assert_eq!(backend.to_unix_path(3, "\\file"), None);
+    }
+
+    #[test]
+    fn test_read_only_drive_rejects_creation() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = mpsc::channel();
+        let mut backend = RustConnRdpdrBackend::with_shared_folders(
+            vec![
+                (
+                    1,
+                    SharedFolder::new("Locked", dir.path()).with_read_only(true),
+                ),
+                (2, SharedFolder::new("Open", dir.path())),
+            ],
+            tx,
+        );
+        assert!(backend.is_read_only(1));
+        assert!(!backend.is_read_only(2));
+
+        backend
+            .handle_create(create_request(1, "\\locked.txt", false))
+            .unwrap();
+        backend
+            .handle_create(create_request(1, "\\locked_dir", true))
+            .unwrap();
+        assert!(!dir.path().join("locked.txt").exists());
+        assert!(!dir.path().join("locked_dir").exists());
+
+        backend
+            .handle_create(create_request(2, "\\open.txt", false))
+            .unwrap();
+        assert!(dir.path().join("open.txt").exists());
+    }
+
+    #[test]
+    fn test_announce_response_reports_mount_result() {
+        let (tx, rx) = mpsc::channel();
+        let mut backend = RustConnRdpdrBackend::with_shared_folders(
+            vec![
+                (1, SharedFolder::new("Docs", "/tmp")),
+                (2, SharedFolder::new("Music", "/tmp")),
+            ],
+            tx,
+        );
+
+        backend
+            .handle_server_device_announce_response(ServerDeviceAnnounceResponse {
+                device_id: 1,
+                result_code: NtStatus::SUCCESS,
+            })
+            .unwrap();
+        backend
+            .handle_server_device_announce_response(ServerDeviceAnnounceResponse {
+                device_id: 2,
+                result_code: NtStatus::ACCESS_DENIED,
+            })
+            .unwrap();
+
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(RdpClientEvent::SharedFolderMounted { name }) if name == "Docs"
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(RdpClientEvent::SharedFolderError { name, .. }) if name == "Music"
+        ));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -6,6 +6,8 @@
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+use uuid::Uuid;
+
 use super::ConnectionSearchResult;
 
 /// Default time-to-live for cached search results (30 seconds)
@@ -45,6 +47,9 @@ struct CachedEntry {
 ///     println!("Found {} cached results", results.len());
 /// }
 ///
+/// // Invalidate only entries an edited connection can affect
+/// cache.invalidate_connection(uuid::Uuid::new_v4(), |query| query == "server");
+///
 /// // Invalidate all entries when data changes
 /// cache.invalidate_all();
 /// ```
@@ -128,6 +133,30 @@ impl SearchCache {
         self.cache.clear();
     }
 
+    /// Invalidates cached entries a changed connection can affect
+    ///
+    /// Removes entries whose results reference the connection and entries
+    /// whose query `matches_query` accepts, i.e. queries the connection
+    /// matches after the change. Other cached queries are kept, so single
+    /// edits don't throw away the whole cache.
+    ///
+    /// Returns the number of entries removed.
+    pub fn invalidate_connection(
+        &mut self,
+        connection_id: Uuid,
+        matches_query: impl Fn(&str) -> bool,
+    ) -> usize {
+        let before = self.cache.len();
+        self.cache.retain(|query, entry| {
+            !entry
+                .results
+                .iter()
+                .any(|result| result.connection_id == connection_id)
+                && !matches_query(query)
+        });
+        before - self.cache.len()
+    }
+
     /// Evicts all entries that have exceeded their TTL
     ///
     /// Returns the number of entries evicted.
@@ -204,7 +233,6 @@ impl Default for SearchCache {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
 
     fn create_test_result(score: f32) -> ConnectionSearchResult {
         ConnectionSearchResult::new(Uuid::new_v4(), score)
@@ -243,6 +271,29 @@ mod tests {
         assert!(cache.get("query2").is_none());
     }
 
+    #[test]
+    fn test_cache_invalidate_connection() {
+        let mut cache = SearchCache::with_defaults();
+        let edited = create_test_result(0.9);
+        let edited_id = edited.connection_id;
+        cache.insert(
+            "affected".to_string(),
+            vec![edited, create_test_result(0.5)],
+        );
+        cache.insert("unrelated".to_string(), vec![create_test_result(0.8)]);
+        cache.insert("now-matching".to_string(), Vec::new());
+
+        assert_eq!(
+            cache.invalidate_connection(edited_id, |query| query == "now-matching"),
+            2
+        );
+
+        assert!(cache.get("affected").is_none());
+        assert!(cache.get("now-matching").is_none());
+        assert!(cache.get("unrelated").is_some());
+        assert_eq!(cache.invalidate_connection(edited_id, |_| false), 0);
+    }
+
     #[test]
     fn test_cache_ttl_expiration() {
         let mut cache = SearchCache::new(100, Duration::from_millis(10));
@@ -0,0 +1,435 @@
+//! Inverted index for narrowing search candidates
+//!
+//! The index maps each lowercase character of a connection's searchable
+//! text to the connections containing it. Every field the linear search
+//! scores above zero, down to a partial fuzzy match, contains the first
+//! character of the query, so a query is answered from the postings of that
+//! character and returns exactly the linear search's results in the same
+//! order. Group names are not indexed; they are checked at query time so
+//! renamed groups never leave the index stale.
+//!
+//! Each connection occupies a slot and postings are bitsets over the slots,
+//! so candidates come out in slot order rather than hash order.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::models::{Connection, ConnectionGroup};
+
+/// Set of slots stored as a bitset
+#[derive(Debug, Clone, Default)]
+struct SlotSet {
+    words: Vec<u64>,
+}
+
+impl SlotSet {
+    fn insert(&mut self, slot: usize) {
+        let word = slot / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (slot % 64);
+    }
+
+    fn remove(&mut self, slot: usize) {
+        if let Some(word) = self.words.get_mut(slot / 64) {
+            *word &= !(1 << (slot % 64));
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Returns the slots in ascending order
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            let mut bits = *word;
+            std::iter::from_fn(move || {
+                (bits != 0).then(|| {
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// Indexed state of one connection
+#[derive(Debug, Clone)]
+struct IndexedConnection {
+    /// Slot of the connection in the postings
+    slot: usize,
+    /// Group of the connection when it was indexed
+    group_id: Option<Uuid>,
+    /// Distinct lowercase characters of the searchable fields, empty for
+    /// stale connections
+    chars: Vec<char>,
+}
+
+/// Inverted index over the searchable fields of connections
+///
+/// Covers names, hosts, tags, usernames and custom properties (except
+/// protected values). [`crate::ConnectionManager`] keeps one current for its
+/// connections. Otherwise build it once with [`SearchIndex::build`] and keep it
+/// current with [`insert`](Self::insert), [`update`](Self::update) and
+/// [`remove`](Self::remove) as connections change. A connection that may be
+/// changed in place can be flagged with [`mark_stale`](Self::mark_stale); it is
+/// then scored by every search until it is re-indexed.
+///
+/// # Example
+///
+/// ```
+/// use rustconn_core::models::Connection;
+/// use rustconn_core::search::{SearchEngine, SearchIndex, SearchQuery};
+///
+/// let connections = vec![Connection::new_ssh(
+///     "web".to_string(),
+///     "web.example.com".to_string(),
+///     22,
+/// )];
+/// let index = SearchIndex::build(&connections);
+/// let query = SearchQuery::with_text("web");
+/// let lookup = |id| connections.iter().find(|c| c.id == id);
+/// let results = SearchEngine::new().search_indexed(&query, lookup, &[], &index);
+/// assert_eq!(results.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// Connection in each slot, `None` for freed slots
+    slots: Vec<Option<Uuid>>,
+    free_slots: Vec<usize>,
+    postings: HashMap<char, SlotSet>,
+    group_members: HashMap<Uuid, SlotSet>,
+    stale: SlotSet,
+    entries: HashMap<Uuid, IndexedConnection>,
+}
+
+impl SearchIndex {
+    /// Creates an empty index
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an index over the given connections
+    #[must_use]
+    pub fn build(connections: &[Connection]) -> Self {
+        let mut index = Self::new();
+        for connection in connections {
+            index.insert(connection);
+        }
+        index
+    }
+
+    /// Adds a connection, replacing any earlier entry with the same ID
+    pub fn insert(&mut self, connection: &Connection) {
+        self.remove(connection.id);
+
+        let mut chars: Vec<char> = searchable_fields(connection)
+            .flat_map(str::chars)
+            .flat_map(char::to_lowercase)
+            .collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let slot = self.allocate_slot(connection.id);
+        for c in &chars {
+            self.postings.entry(*c).or_default().insert(slot);
+        }
+        if let Some(group_id) = connection.group_id {
+            self.group_members.entry(group_id).or_default().insert(slot);
+        }
+        self.entries.insert(
+            connection.id,
+            IndexedConnection {
+                slot,
+                group_id: connection.group_id,
+                chars,
+            },
+        );
+    }
+
+    /// Re-indexes a connection after it changed
+    pub fn update(&mut self, connection: &Connection) {
+        self.insert(connection);
+    }
+
+    /// Flags a connection that may change without being re-indexed
+    ///
+    /// The connection is scored by every search until it is inserted or
+    /// updated again.
+    pub fn mark_stale(&mut self, id: Uuid) {
+        self.remove(id);
+        let slot = self.allocate_slot(id);
+        self.stale.insert(slot);
+        self.entries.insert(
+            id,
+            IndexedConnection {
+                slot,
+                group_id: None,
+                chars: Vec::new(),
+            },
+        );
+    }
+
+    /// Removes a connection from the index
+    ///
+    /// Returns `true` if the connection was indexed.
+    pub fn remove(&mut self, id: Uuid) -> bool {
+        let Some(entry) = self.entries.remove(&id) else {
+            return false;
+        };
+        for c in entry.chars {
+            if let Some(slots) = self.postings.get_mut(&c) {
+                slots.remove(entry.slot);
+                if slots.is_empty() {
+                    self.postings.remove(&c);
+                }
+            }
+        }
+        if let Some(group_id) = entry.group_id {
+            if let Some(slots) = self.group_members.get_mut(&group_id) {
+                slots.remove(entry.slot);
+                if slots.is_empty() {
+                    self.group_members.remove(&group_id);
+                }
+            }
+        }
+        self.stale.remove(entry.slot);
+        self.slots[entry.slot] = None;
+        self.free_slots.push(entry.slot);
+        true
+    }
+
+    /// Returns whether a connection is indexed
+    #[must_use]
+    pub fn contains(&self, id: Uuid) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    /// Returns the number of indexed connections
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no connections are indexed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the IDs of the connections that can match `text`
+    ///
+    /// A connection is a candidate when the first character of the text
+    /// occurs in its searchable fields or its group name. Blank text matches
+    /// every connection that passes the filters, so all connections are
+    /// returned.
+    pub(crate) fn candidates(&self, text: &str, groups: &[ConnectionGroup]) -> Vec<Uuid> {
+        if text.trim().is_empty() {
+            return self.slots.iter().flatten().copied().collect();
+        }
+
+        // Every match, down to the weakest partial fuzzy match, contains the
+        // first character of the text
+        let Some(key) = text.chars().next().and_then(|c| c.to_lowercase().next()) else {
+            return Vec::new();
+        };
+
+        let mut matched = self.postings.get(&key).cloned().unwrap_or_default();
+        let named_groups = groups.iter().filter(|g| {
+            g.name
+                .chars()
+                .flat_map(char::to_lowercase)
+                .any(|c| c == key)
+        });
+        for group in named_groups {
+            if let Some(members) = self.group_members.get(&group.id) {
+                matched.union_with(members);
+            }
+        }
+        matched.union_with(&self.stale);
+        matched.iter().filter_map(|slot| self.slots[slot]).collect()
+    }
+
+    /// Assigns a free slot to a connection
+    fn allocate_slot(&mut self, id: Uuid) -> usize {
+        if let Some(slot) = self.free_slots.pop() {
+            self.slots[slot] = Some(id);
+            slot
+        } else {
+            self.slots.push(Some(id));
+            self.slots.len() - 1
+        }
+    }
+}
+
+/// Returns the fields of a connection that search scores, except the group
+fn searchable_fields(connection: &Connection) -> impl Iterator<Item = &str> {
+    [connection.name.as_str(), connection.host.as_str()]
+        .into_iter()
+        .chain(connection.tags.iter().map(String::as_str))
+        .chain(connection.username.as_deref())
+        .chain(connection.custom_properties.iter().flat_map(|p| {
+            let value = (!p.is_protected()).then_some(p.value.as_str());
+            std::iter::once(p.name.as_str()).chain(value)
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CustomProperty;
+    use crate::search::{SearchEngine, SearchQuery};
+
+    fn connections() -> (Vec<Connection>, Vec<ConnectionGroup>) {
+        let prod = ConnectionGroup::new("Zeta".to_string());
+        let mut connections = crate::search::benchmark::generate_test_connections(300);
+        connections[0].group_id = Some(prod.id);
+        connections[1].tags.push("Ünicode".to_string());
+        connections[2]
+            .custom_properties
+            .push(CustomProperty::new_text("rack", "Q7"));
+        connections[3].username = Some("jdoe".to_string());
+        (connections, vec![prod])
+    }
+
+    fn assert_same_ranking(
+        engine: &SearchEngine,
+        text: &str,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        index: &SearchIndex,
+    ) {
+        let query = SearchEngine::parse_query(text).unwrap();
+        let linear: Vec<(Uuid, f32)> = engine
+            .search(&query, connections, groups)
+            .into_iter()
+            .map(|r| (r.connection_id, r.score))
+            .collect();
+        let indexed: Vec<(Uuid, f32)> = engine
+            .search_indexed(
+                &query,
+                |id| connections.iter().find(|c| c.id == id),
+                groups,
+                index,
+            )
+            .into_iter()
+            .map(|r| (r.connection_id, r.score))
+            .collect();
+        assert_eq!(indexed, linear, "ranking differs for query {text:?}");
+    }
+
+    #[test]
+    fn test_indexed_search_matches_linear_ranking() {
+        let (connections, groups) = connections();
+        let index = SearchIndex::build(&connections);
+        assert_eq!(index.len(), connections.len());
+
+        let engine = SearchEngine::new();
+        for text in [
+            "server",
+            "Server-1",
+            "host-15",
+            "zeta",
+            "zeta-1",
+            "ü",
+            "q7",
+            "jdoe",
+            "xyz",
+            "   ",
+            " s",
+            "protocol:ssh",
+            "protocol:rdp 10",
+            "1",
+        ] {
+            assert_same_ranking(&engine, text, &connections, &groups, &index);
+        }
+        let case_sensitive = SearchEngine::new().with_case_sensitive(true);
+        for text in ["Server", "server", "HOST"] {
+            assert_same_ranking(&case_sensitive, text, &connections, &groups, &index);
+        }
+    }
+
+    #[test]
+    fn test_slot_set() {
+        let mut a = SlotSet::default();
+        for slot in [3, 64, 130] {
+            a.insert(slot);
+        }
+        let mut b = SlotSet::default();
+        b.insert(64);
+        b.insert(200);
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        assert_eq!(union.iter().collect::<Vec<_>>(), [3, 64, 130, 200]);
+
+        for slot in [3, 64, 130] {
+            a.remove(slot);
+        }
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn test_indexed_search_keeps_partial_matches() {
+        let (connections, groups) = connections();
+        let index = SearchIndex::build(&connections);
+        let engine = SearchEngine::new();
+
+        // Every connection shares the leading "s" but none contains "w", so
+        // all results are partial fuzzy matches
+        let query = SearchQuery::with_text("sw");
+        assert!(!engine.search(&query, &connections, &groups).is_empty());
+        assert_same_ranking(&engine, "sw", &connections, &groups, &index);
+    }
+
+    #[test]
+    fn test_index_insert_update_remove() {
+        let (mut connections, groups) = connections();
+        let mut index = SearchIndex::build(&connections);
+        let engine = SearchEngine::new();
+        let query = SearchQuery::with_text("walrus");
+        let search = |connections: &[Connection], index: &SearchIndex| {
+            engine.search_indexed(
+                &query,
+                |id| connections.iter().find(|c| c.id == id),
+                &groups,
+                index,
+            )
+        };
+        assert!(search(&connections, &index).is_empty());
+
+        // A connection marked stale is scored even before it is re-indexed
+        index.mark_stale(connections[5].id);
+        connections[5].name = "walrus".to_string();
+        assert_eq!(search(&connections, &index).len(), 1);
+        index.update(&connections[5]);
+        assert_same_ranking(&engine, "walrus", &connections, &groups, &index);
+
+        let removed = connections.remove(5);
+        assert!(index.remove(removed.id));
+        assert!(!index.remove(removed.id));
+        assert!(!index.contains(removed.id));
+        assert_same_ranking(&engine, "walrus", &connections, &groups, &index);
+
+        let added = Connection::new_ssh("walrus-2".to_string(), "w2".to_string(), 22);
+        connections.push(added.clone());
+        assert!(search(&connections, &index).is_empty());
+        index.insert(&added);
+        assert!(index.contains(added.id));
+        assert_same_ranking(&engine, "walrus", &connections, &groups, &index);
+    }
+}
@@ -14,6 +14,8 @@
 //!   and avoids unnecessary allocations
 //! - **Parallel Search**: For large datasets (100+ connections), consider using
 //!   `search_parallel` for multi-threaded search
+//! - **Search Index**: Use `SearchIndex` with `SearchEngine::search_indexed` to
+//!   score only the connections containing the first character of the query
 
 // Allow cast warnings - search scoring uses f64 for precision
 #![allow(clippy::cast_possible_truncation)]
@@ -24,6 +26,9 @@
 #![allow(clippy::missing_panics_doc)]
 
 pub mod cache;
+mod index;
+
+pub use index::SearchIndex;
 
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -74,6 +79,33 @@ pub enum SearchFilter {
     GroupName(String),
     /// Search within custom properties
     InCustomProperty(String),
+    /// Filter by archive state (e.g., status:archived)
+    Status(ConnectionStatus),
+}
+
+/// Archive state selected by the `status:` operator
+///
+/// Queries without a status filter only match active connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectionStatus {
+    /// Connections that are not archived (status:active)
+    Active,
+    /// Archived connections only (status:archived)
+    Archived,
+    /// Both active and archived connections (status:any)
+    Any,
+}
+
+impl ConnectionStatus {
+    /// Returns whether a connection has this status
+    #[must_use]
+    pub const fn matches(self, connection: &Connection) -> bool {
+        match self {
+            Self::Active => !connection.archived,
+            Self::Archived => connection.archived,
+            Self::Any => true,
+        }
+    }
 }
 
 /// A parsed search query with text and filters
@@ -179,6 +211,59 @@ impl ConnectionSearchResult {
     }
 }
 
+/// Usage-based ordering for connection lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UsageSort {
+    /// Most recently connected first
+    RecentlyUsed,
+    /// Highest connect count first, then most recently connected
+    MostUsed,
+}
+
+impl UsageSort {
+    /// Compares two connections by usage
+    ///
+    /// Connections that were never used sort last. Ties are broken by
+    /// case-insensitive name.
+    #[must_use]
+    pub fn compare(self, a: &Connection, b: &Connection) -> std::cmp::Ordering {
+        self.usage_ordering(a, b)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    }
+
+    /// Sorts connections by usage
+    pub fn sort(self, connections: &mut [Connection]) {
+        connections.sort_by(|a, b| self.compare(a, b));
+    }
+
+    /// Reorders search results by usage, keeping relevance order for ties
+    ///
+    /// Results whose connection is not in `connections` are placed last.
+    pub fn sort_results(self, results: &mut [ConnectionSearchResult], connections: &[Connection]) {
+        let by_id: std::collections::HashMap<Uuid, &Connection> =
+            connections.iter().map(|c| (c.id, c)).collect();
+        results.sort_by(
+            |a, b| match (by_id.get(&a.connection_id), by_id.get(&b.connection_id)) {
+                (Some(a), Some(b)) => self.usage_ordering(a, b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
+        );
+    }
+
+    /// Descending usage order; `None` timestamps compare lowest
+    fn usage_ordering(self, a: &Connection, b: &Connection) -> std::cmp::Ordering {
+        match self {
+            Self::RecentlyUsed => b.last_connected.cmp(&a.last_connected),
+            Self::MostUsed => b
+                .connect_count
+                .cmp(&a.connect_count)
+                .then_with(|| b.last_connected.cmp(&a.last_connected)),
+        }
+    }
+}
+
 /// Search engine for connections
 pub struct SearchEngine {
     /// Whether to use case-sensitive matching
@@ -213,6 +298,8 @@ impl SearchEngine {
     /// - `protocol:ssh` - filter by protocol
     /// - `tag:production` - filter by tag
     /// - `group:servers` - filter by group name
+    /// - `status:archived` - filter by archive state (`active`, `archived`
+    ///   or `any`); archived connections are hidden unless this is given
     ///
     /// # Errors
     ///
@@ -264,6 +351,10 @@ impl SearchEngine {
                             .filters
                             .push(SearchFilter::InCustomProperty(value.to_string()));
                     }
+                    "status" => {
+                        let status = Self::parse_status(value)?;
+                        query.filters.push(SearchFilter::Status(status));
+                    }
                     _ => {
                         // Unknown operator, treat as regular text
                         text_parts.push(part);
@@ -295,6 +386,19 @@ impl SearchEngine {
         }
     }
 
+    /// Parses a status string into a `ConnectionStatus`
+    fn parse_status(value: &str) -> SearchResult<ConnectionStatus> {
+        match value.to_lowercase().as_str() {
+            "active" => Ok(ConnectionStatus::Active),
+            "archived" => Ok(ConnectionStatus::Archived),
+            "any" | "all" => Ok(ConnectionStatus::Any),
+            _ => Err(SearchError::InvalidOperator {
+                operator: "status".to_string(),
+                reason: format!("unknown status '{value}', expected active, archived, or any"),
+            }),
+        }
+    }
+
     /// Calculates a fuzzy match score between a query and a target string
     ///
     /// Returns a score between 0.0 (no match) and 1.0 (exact match)
@@ -343,7 +447,10 @@ impl SearchEngine {
         // Prefix match optimization (very common in search)
         let query_len = query.len();
         let target_len = target.len();
-        if target_len >= query_len && target[..query_len].eq_ignore_ascii_case(query) {
+        if target
+            .get(..query_len)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(query))
+        {
             let ratio = query_len as f32 / target_len as f32;
             return ratio.mul_add(0.4, 0.6).min(0.99);
         }
@@ -372,8 +479,12 @@ impl SearchEngine {
             return None;
         }
 
-        (0..=(haystack_len - needle_len))
-            .find(|&i| haystack[i..i + needle_len].eq_ignore_ascii_case(needle))
+        // `get` skips offsets that are not on a char boundary
+        (0..=(haystack_len - needle_len)).find(|&i| {
+            haystack
+                .get(i..i + needle_len)
+                .is_some_and(|window| window.eq_ignore_ascii_case(needle))
+        })
     }
 
     /// Searches connections and returns ranked results
@@ -407,20 +518,69 @@ impl SearchEngine {
             return Vec::new();
         }
 
-        let mut results: Vec<ConnectionSearchResult> = connections
-            .iter()
-            .filter_map(|conn| self.score_connection(query, conn, groups))
+        let results = self.rank(query, connections.iter(), groups);
+        debug!(result_count = results.len(), "Search completed");
+        results
+    }
+
+    /// Searches the connections in `index`, scoring only the candidates
+    /// that can match the query text
+    ///
+    /// `lookup` resolves the indexed IDs to connections; IDs it cannot
+    /// resolve are skipped. Returns the same results as [`Self::search`]
+    /// over the indexed connections, in the same order.
+    #[must_use]
+    pub fn search_indexed<'a>(
+        &self,
+        query: &SearchQuery,
+        lookup: impl Fn(Uuid) -> Option<&'a Connection>,
+        groups: &[ConnectionGroup],
+        index: &SearchIndex,
+    ) -> Vec<ConnectionSearchResult> {
+        let _span = info_span!(
+            span_names::SEARCH_EXECUTE,
+            query = %query.text,
+            filter_count = query.filters.len(),
+            connection_count = index.len(),
+            indexed = true
+        )
+        .entered();
+
+        if query.is_empty() {
+            debug!("Empty query, returning no results");
+            return Vec::new();
+        }
+
+        let candidates = index.candidates(&query.text, groups);
+        let results = self.rank(query, candidates.into_iter().filter_map(lookup), groups);
+        debug!(result_count = results.len(), "Indexed search completed");
+        results
+    }
+
+    /// Scores connections and sorts the matches by score, then by name and
+    /// ID so the order does not depend on the input order
+    fn rank<'a>(
+        &self,
+        query: &SearchQuery,
+        connections: impl Iterator<Item = &'a Connection>,
+        groups: &[ConnectionGroup],
+    ) -> Vec<ConnectionSearchResult> {
+        let mut results: Vec<(&Connection, ConnectionSearchResult)> = connections
+            .filter_map(|conn| {
+                self.score_connection(query, conn, groups)
+                    .map(|result| (conn, result))
+            })
             .collect();
 
         // Sort by score descending
-        results.sort_by(|a, b| {
+        results.sort_by(|(a_conn, a), (b_conn, b)| {
             b.score
                 .partial_cmp(&a.score)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a_conn.name.cmp(&b_conn.name))
+                .then_with(|| a_conn.id.cmp(&b_conn.id))
         });
-
-        debug!(result_count = results.len(), "Search completed");
-        results
+        results.into_iter().map(|(_, result)| result).collect()
     }
 
     /// Scores a single connection against the query
@@ -544,6 +704,9 @@ impl SearchEngine {
     }
 
     /// Checks if a connection passes all filters in the query
+    ///
+    /// Archived connections only pass when the query has a status filter
+    /// that selects them.
     #[allow(clippy::unused_self)]
     fn passes_filters(
         &self,
@@ -551,6 +714,14 @@ impl SearchEngine {
         connection: &Connection,
         groups: &[ConnectionGroup],
     ) -> bool {
+        let has_status = query
+            .filters
+            .iter()
+            .any(|f| matches!(f, SearchFilter::Status(_)));
+        if connection.archived && !has_status {
+            return false;
+        }
+
         for filter in &query.filters {
             match filter {
                 SearchFilter::Protocol(protocol) => {
@@ -569,16 +740,14 @@ impl SearchEngine {
                     }
                 }
                 SearchFilter::Group(group_id) => {
-                    if connection.group_id != Some(*group_id) {
+                    if !connection.is_in_group(*group_id) {
                         return false;
                     }
                 }
                 SearchFilter::GroupName(name) => {
                     let name_lower = name.to_lowercase();
-                    let matches = connection.group_id.is_some_and(|gid| {
-                        groups
-                            .iter()
-                            .any(|g| g.id == gid && g.name.to_lowercase() == name_lower)
+                    let matches = groups.iter().any(|g| {
+                        g.name.to_lowercase() == name_lower && connection.is_in_group(g.id)
                     });
                     if !matches {
                         return false;
@@ -594,6 +763,11 @@ impl SearchEngine {
                         return false;
                     }
                 }
+                SearchFilter::Status(status) => {
+                    if !status.matches(connection) {
+                        return false;
+                    }
+                }
             }
         }
         true
@@ -643,7 +817,10 @@ impl SearchEngine {
             }
 
             // Check for prefix match (very common in search)
-            if target_len >= query_len && target[..query_len].eq_ignore_ascii_case(query) {
+            if target
+                .get(..query_len)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(query))
+            {
                 let ratio = query_len as f32 / target_len as f32;
                 return ratio.mul_add(0.4, 0.6).min(0.99);
             }
@@ -807,6 +984,23 @@ impl DebouncedSearchEngine {
         }
     }
 
+    /// Creates a debounced search engine that searches at least every `max_wait`
+    ///
+    /// During sustained typing a search still runs once per `max_wait`
+    /// instead of waiting for the input to settle. With `leading` set, the
+    /// first keystroke after a pause searches immediately.
+    /// See [`Debouncer::with_max_wait`].
+    #[must_use]
+    pub fn with_max_wait(delay: Duration, max_wait: Duration, leading: bool) -> Self {
+        Self {
+            engine: SearchEngine::new(),
+            debouncer: Debouncer::with_max_wait(delay, max_wait).with_leading(leading),
+            last_query: Arc::new(Mutex::new(None)),
+            search_pending: AtomicBool::new(false),
+            search_cache: Arc::new(Mutex::new(cache::SearchCache::with_defaults())),
+        }
+    }
+
     /// Creates a debounced search engine with default search delay (100ms)
     #[must_use]
     pub fn for_search() -> Self {
@@ -832,6 +1026,31 @@ impl DebouncedSearchEngine {
         query: &SearchQuery,
         connections: &[Connection],
         groups: &[ConnectionGroup],
+    ) -> Option<Vec<ConnectionSearchResult>> {
+        self.debounced(query, || self.search(query, connections, groups))
+    }
+
+    /// Performs a debounced search, scoring only the candidates in `index`
+    ///
+    /// Behaves like [`search_debounced`](Self::search_debounced) with the
+    /// results of [`SearchEngine::search_indexed`].
+    #[must_use]
+    pub fn search_debounced_indexed<'a>(
+        &self,
+        query: &SearchQuery,
+        lookup: impl Fn(Uuid) -> Option<&'a Connection>,
+        groups: &[ConnectionGroup],
+        index: &SearchIndex,
+    ) -> Option<Vec<ConnectionSearchResult>> {
+        self.debounced(query, || self.search_indexed(query, lookup, groups, index))
+    }
+
+    /// Runs `search` if the debouncer lets the call through, going through
+    /// the result cache
+    fn debounced(
+        &self,
+        query: &SearchQuery,
+        search: impl FnOnce() -> Vec<ConnectionSearchResult>,
     ) -> Option<Vec<ConnectionSearchResult>> {
         // Store the query for potential deferred execution
         *self.last_query.lock().unwrap() = Some(query.text.clone());
@@ -848,7 +1067,7 @@ impl DebouncedSearchEngine {
             }
 
             // Execute search
-            let results = self.engine.search(query, connections, groups);
+            let results = search();
 
             // Cache the results
             {
@@ -877,6 +1096,20 @@ impl DebouncedSearchEngine {
         self.engine.search(query, connections, groups)
     }
 
+    /// Performs an indexed search without debouncing
+    ///
+    /// Use this for a deferred search once the debounce delay has passed.
+    #[must_use]
+    pub fn search_indexed<'a>(
+        &self,
+        query: &SearchQuery,
+        lookup: impl Fn(Uuid) -> Option<&'a Connection>,
+        groups: &[ConnectionGroup],
+        index: &SearchIndex,
+    ) -> Vec<ConnectionSearchResult> {
+        self.engine.search_indexed(query, lookup, groups, index)
+    }
+
     /// Returns cached results if available and still valid
     ///
     /// Results are considered valid if they match the current query
@@ -898,6 +1131,34 @@ impl DebouncedSearchEngine {
         cache.invalidate_all();
     }
 
+    /// Invalidates cached search results an added or edited connection
+    /// affects
+    ///
+    /// Use this instead of [`invalidate_cache`](Self::invalidate_cache) when
+    /// a single connection changes, so unrelated cached queries stay warm.
+    /// Queries whose results referenced the connection, and queries the
+    /// updated connection now matches, are re-run on their next search.
+    ///
+    /// The cache is keyed by query text, so the connection is matched
+    /// against the text alone with archived connections included. That can
+    /// drop more entries than needed but never keeps a stale one.
+    pub fn invalidate_connection(&self, connection: &Connection, groups: &[ConnectionGroup]) {
+        let mut cache = self.search_cache.lock().unwrap();
+        cache.invalidate_connection(connection.id, |text| {
+            let query = SearchQuery::with_text(text)
+                .with_filter(SearchFilter::Status(ConnectionStatus::Any));
+            self.engine
+                .score_connection(&query, connection, groups)
+                .is_some()
+        });
+    }
+
+    /// Invalidates cached search results that reference a deleted connection
+    pub fn invalidate_removed_connection(&self, connection_id: Uuid) {
+        let mut cache = self.search_cache.lock().unwrap();
+        cache.invalidate_connection(connection_id, |_| false);
+    }
+
     /// Checks if there's a pending search operation
     #[must_use]
     pub fn has_pending_search(&self) -> bool {
@@ -1076,6 +1337,29 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_archived_hidden_without_status_filter() {
+        let active = create_test_connection("web-1", "10.0.0.1", ProtocolType::Ssh);
+        let mut archived = create_test_connection("web-2", "10.0.0.2", ProtocolType::Ssh);
+        archived.archived = true;
+        let connections = vec![active.clone(), archived.clone()];
+        let engine = SearchEngine::new();
+
+        let ids = |text: &str| -> Vec<Uuid> {
+            let query = SearchEngine::parse_query(text).unwrap();
+            engine
+                .search(&query, &connections, &[])
+                .into_iter()
+                .map(|r| r.connection_id)
+                .collect()
+        };
+        assert_eq!(ids("web"), vec![active.id]);
+        assert_eq!(ids("web status:archived"), vec![archived.id]);
+        assert_eq!(ids("status:active"), vec![active.id]);
+        assert_eq!(ids("web status:any").len(), 2);
+        assert!(SearchEngine::parse_query("status:deleted").is_err());
+    }
+
     #[test]
     fn test_fuzzy_score_exact_match() {
         let engine = SearchEngine::new();
@@ -1112,6 +1396,15 @@ mod tests {
         assert!((score - 1.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_fuzzy_score_multibyte_target() {
+        let engine = SearchEngine::new();
+        assert!(engine.fuzzy_score("ab", "aé") > 0.0);
+        assert!(engine.fuzzy_score("cafe", "échec café") > 0.0);
+        assert!(engine.fuzzy_score("CH", "échec") >= 0.5);
+        assert!(engine.fuzzy_score_optimized("ab", "aé") > 0.0);
+    }
+
     #[test]
     fn test_search_by_name() {
         let engine = SearchEngine::new();
@@ -1201,6 +1494,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_usage_sort() {
+        let now = chrono::Utc::now();
+        let mut rare = create_test_connection("rare", "10.0.0.1", ProtocolType::Ssh);
+        rare.connect_count = 1;
+        rare.last_connected = Some(now);
+        let mut frequent = create_test_connection("frequent", "10.0.0.2", ProtocolType::Ssh);
+        frequent.connect_count = 9;
+        frequent.last_connected = Some(now - chrono::Duration::days(3));
+        let never = create_test_connection("never", "10.0.0.3", ProtocolType::Ssh);
+
+        let mut connections = vec![never.clone(), frequent.clone(), rare.clone()];
+        UsageSort::RecentlyUsed.sort(&mut connections);
+        let names: Vec<_> = connections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["rare", "frequent", "never"]);
+
+        UsageSort::MostUsed.sort(&mut connections);
+        let names: Vec<_> = connections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["frequent", "rare", "never"]);
+
+        let mut results = vec![
+            ConnectionSearchResult::new(never.id, 1.0),
+            ConnectionSearchResult::new(Uuid::new_v4(), 0.9),
+            ConnectionSearchResult::new(rare.id, 0.5),
+        ];
+        UsageSort::MostUsed.sort_results(&mut results, &connections);
+        assert_eq!(results[0].connection_id, rare.id);
+        assert_eq!(results[1].connection_id, never.id);
+    }
+
     #[test]
     fn test_search_custom_properties() {
         let engine = SearchEngine::new();
@@ -1375,6 +1698,30 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_debounced_search_with_max_wait() {
+        let engine =
+            DebouncedSearchEngine::with_max_wait(Duration::from_secs(1), Duration::ZERO, true);
+        let connections = vec![create_test_connection(
+            "server",
+            "192.168.1.1",
+            ProtocolType::Ssh,
+        )];
+        let query = SearchQuery::with_text("server");
+
+        // Leading edge, then every call within the burst hits the max-wait bound
+        assert!(engine.search_debounced(&query, &connections, &[]).is_some());
+        assert!(engine.search_debounced(&query, &connections, &[]).is_some());
+
+        let engine = DebouncedSearchEngine::with_max_wait(
+            Duration::from_secs(1),
+            Duration::from_secs(5),
+            false,
+        );
+        assert!(engine.search_debounced(&query, &connections, &[]).is_none());
+        assert!(engine.has_pending_search());
+    }
+
     #[test]
     fn test_debounced_search_cached_results() {
         let engine = DebouncedSearchEngine::for_search();
@@ -1396,6 +1743,94 @@ mod tests {
         assert!(!cached.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_debounced_search_invalidate_connection() {
+        let engine = DebouncedSearchEngine::new(Duration::ZERO);
+        let mut connections = vec![
+            create_test_connection("web-server", "10.0.0.1", ProtocolType::Ssh),
+            create_test_connection("database", "10.0.0.2", ProtocolType::Ssh),
+        ];
+        let groups = vec![];
+        let web_query = SearchQuery::with_text("web");
+        let db_query = SearchQuery::with_text("database");
+
+        let _ = engine.search_debounced(&web_query, &connections, &groups);
+        let _ = engine.search_debounced(&db_query, &connections, &groups);
+        assert_eq!(engine.cache_size(), 2);
+
+        // Rename the web server so it no longer matches "web"
+        connections[0].name = "proxy".to_string();
+        engine.invalidate_connection(&connections[0], &groups);
+
+        // Unrelated query stays cached, affected one is dropped
+        assert!(engine.get_cached_results("database").is_some());
+        assert!(engine.get_cached_results("web").is_none());
+
+        // Affected query is refreshed on the next search
+        let refreshed = engine
+            .search_debounced(&web_query, &connections, &groups)
+            .unwrap();
+        assert!(refreshed
+            .iter()
+            .all(|result| result.connection_id != connections[0].id));
+    }
+
+    #[test]
+    fn test_debounced_search_invalidate_connection_new_match() {
+        let engine = DebouncedSearchEngine::new(Duration::ZERO);
+        let mut connections = vec![
+            create_test_connection("web-server", "10.0.0.1", ProtocolType::Ssh),
+            create_test_connection("database", "10.0.0.2", ProtocolType::Ssh),
+        ];
+        let groups = vec![];
+        let db_query = SearchQuery::with_text("database");
+        let cache_query = SearchQuery::with_text("cache");
+
+        let _ = engine.search_debounced(&db_query, &connections, &groups);
+        let _ = engine.search_debounced(&cache_query, &connections, &groups);
+        assert!(engine.get_cached_results("cache").unwrap().is_empty());
+
+        // Rename the web server so it now matches "cache"
+        connections[0].name = "cache".to_string();
+        engine.invalidate_connection(&connections[0], &groups);
+
+        assert!(engine.get_cached_results("database").is_some());
+        assert!(engine.get_cached_results("cache").is_none());
+        let refreshed = engine
+            .search_debounced(&cache_query, &connections, &groups)
+            .unwrap();
+        assert_eq!(refreshed[0].connection_id, connections[0].id);
+    }
+
+    #[test]
+    fn test_debounced_search_indexed_matches_search() {
+        let engine = DebouncedSearchEngine::new(Duration::from_millis(100));
+        let mut connections = vec![
+            create_test_connection("web", "web.example.com", ProtocolType::Ssh),
+            create_test_connection("db", "db.example.com", ProtocolType::Ssh),
+        ];
+        let mut index = SearchIndex::build(&connections);
+
+        let query = SearchQuery::with_text("db");
+        let lookup = |id| connections.iter().find(|c| c.id == id);
+        let results = engine
+            .search_debounced_indexed(&query, lookup, &[], &index)
+            .unwrap();
+        let expected = engine.search(&query, &connections, &[]);
+        assert_eq!(results.len(), expected.len());
+        assert_eq!(results[0].connection_id, expected[0].connection_id);
+
+        let removed = connections.pop().unwrap();
+        index.remove(removed.id);
+        engine.invalidate_removed_connection(removed.id);
+        let lookup = |id| connections.iter().find(|c| c.id == id);
+        let results = engine.search_indexed(&query, lookup, &[], &index);
+        assert_eq!(
+            results.len(),
+            engine.search(&query, &connections, &[]).len()
+        );
+    }
+
     #[test]
     fn test_debounced_search_reset() {
         let engine = DebouncedSearchEngine::for_search();
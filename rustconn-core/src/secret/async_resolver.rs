@@ -2,41 +2,56 @@
 //!
 //! This module provides async credential resolution that doesn't block the UI thread.
 //! It includes support for cancellation tokens and callback-based resolution.
+//!
+//! Backend queries that run as child processes (such as `keepassxc-cli`) are
+//! tracked by the `CancellationToken` and killed when it is cancelled, so
+//! closing a dialog mid-resolution does not leak processes.
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::process::{Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::sync::oneshot;
+use secrecy::{ExposeSecret, SecretString};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+use tokio::sync::{oneshot, Notify};
 use tracing::{debug, instrument, warn};
+use uuid::Uuid;
 
 use crate::config::SecretSettings;
-use crate::models::{Connection, Credentials};
+use crate::models::{Connection, ConnectionGroup, Credentials, PasswordSource};
 
+use super::hierarchy::KeePassHierarchy;
 use super::manager::SecretManager;
 use super::resolver::CredentialResolver;
+use super::status::KeePassStatus;
 
 /// Token for cancelling pending credential resolution requests
 ///
 /// This token can be cloned and shared across threads. When `cancel()` is called,
-/// all pending operations using this token will be cancelled.
+/// all pending operations using this token will be cancelled and any child
+/// processes started through [`run_cancellable_process`] will be killed.
 #[derive(Clone, Default)]
 pub struct CancellationToken {
     cancelled: Arc<AtomicBool>,
+    /// Wakes tasks waiting in `cancelled()`
+    notify: Arc<Notify>,
+    /// Number of child processes currently running under this token
+    active_processes: Arc<AtomicUsize>,
 }
 
 impl CancellationToken {
     /// Creates a new cancellation token
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            cancelled: Arc::new(AtomicBool::new(false)),
-        }
+        Self::default()
     }
 
     /// Cancels all operations using this token
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
     }
 
     /// Checks if the token has been cancelled
@@ -45,6 +60,30 @@ impl CancellationToken {
         self.cancelled.load(Ordering::SeqCst)
     }
 
+    /// Waits until the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking the flag so a concurrent cancel is not missed
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Returns the number of child processes currently tracked by this token
+    #[must_use]
+    pub fn active_processes(&self) -> usize {
+        self.active_processes.load(Ordering::SeqCst)
+    }
+
+    /// Registers a running child process until the returned guard is dropped
+    fn track_process(&self) -> ProcessGuard {
+        self.active_processes.fetch_add(1, Ordering::SeqCst);
+        ProcessGuard(Arc::clone(&self.active_processes))
+    }
+
     /// Resets the cancellation state
     pub fn reset(&self) {
         self.cancelled.store(false, Ordering::SeqCst);
@@ -55,16 +94,117 @@ impl std::fmt::Debug for CancellationToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CancellationToken")
             .field("cancelled", &self.is_cancelled())
+            .field("active_processes", &self.active_processes())
             .finish()
     }
 }
 
+/// Decrements the token's process count when the process is done
+struct ProcessGuard(Arc<AtomicUsize>);
+
+impl Drop for ProcessGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Outcome of a child process run under a `CancellationToken`
+#[derive(Debug)]
+pub enum ProcessOutcome {
+    /// The process exited on its own
+    Completed(Output),
+    /// The token was cancelled and the process was killed
+    Cancelled,
+    /// The process could not be spawned or awaited
+    Failed(String),
+}
+
+/// Reads a child pipe to the end, treating a missing pipe as empty
+async fn read_pipe<R: AsyncRead + Unpin>(pipe: Option<R>) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    if let Some(mut pipe) = pipe {
+        pipe.read_to_end(&mut buf).await?;
+    }
+    Ok(buf)
+}
+
+/// Runs a child process that is killed when the token is cancelled
+///
+/// The process is tracked by `cancel_token` while it runs. On cancellation
+/// it receives a kill signal and is reaped before this function returns.
+/// If the returned future is dropped instead (e.g. on timeout), the process
+/// is killed as well.
+///
+/// # Arguments
+/// * `command` - The command to run; stdio is configured by this function
+/// * `stdin_data` - Optional data written to the process's stdin
+/// * `cancel_token` - Token that aborts the process when cancelled
+pub async fn run_cancellable_process(
+    command: &mut Command,
+    stdin_data: Option<&[u8]>,
+    cancel_token: &CancellationToken,
+) -> ProcessOutcome {
+    if cancel_token.is_cancelled() {
+        return ProcessOutcome::Cancelled;
+    }
+
+    let stdin = if stdin_data.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    let mut child = match command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return ProcessOutcome::Failed(format!("Failed to spawn process: {e}")),
+    };
+    let _guard = cancel_token.track_process();
+
+    if let (Some(data), Some(mut stdin)) = (stdin_data, child.stdin.take()) {
+        // A process that exits before reading stdin is reported via its exit status
+        if let Err(e) = stdin.write_all(data).await {
+            debug!(error = %e, "Failed to write to child stdin");
+        }
+    }
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let result = tokio::select! {
+        result = async {
+            let (stdout, stderr) = tokio::try_join!(read_pipe(stdout), read_pipe(stderr))?;
+            let status = child.wait().await?;
+            Ok::<_, std::io::Error>(Output { status, stdout, stderr })
+        } => Some(result),
+        () = cancel_token.cancelled() => None,
+    };
+
+    match result {
+        Some(Ok(output)) => ProcessOutcome::Completed(output),
+        Some(Err(e)) => ProcessOutcome::Failed(format!("Failed to wait for process: {e}")),
+        None => {
+            debug!(pid = ?child.id(), "Cancellation requested, killing child process");
+            if let Err(e) = child.kill().await {
+                warn!(error = %e, "Failed to kill cancelled child process");
+            }
+            ProcessOutcome::Cancelled
+        }
+    }
+}
+
 /// Result of an async credential resolution operation
 #[derive(Debug)]
 pub enum AsyncCredentialResult {
     /// Credentials were successfully resolved
     Success(Option<Credentials>),
     /// The operation was cancelled
+    ///
+    /// Returned whenever the token was cancelled, even if the backend query
+    /// failed as a consequence (e.g. its process was killed).
     Cancelled,
     /// An error occurred during resolution
     Error(String),
@@ -122,6 +262,9 @@ impl AsyncCredentialResult {
 /// async methods that can be used with callbacks or awaited directly.
 pub struct AsyncCredentialResolver {
     resolver: CredentialResolver,
+    settings: SecretSettings,
+    /// Groups used to build hierarchical `KeePass` entry paths
+    groups: Vec<ConnectionGroup>,
 }
 
 impl AsyncCredentialResolver {
@@ -131,12 +274,24 @@ impl AsyncCredentialResolver {
     /// * `secret_manager` - The secret manager with configured backends
     /// * `settings` - Secret settings for configuration
     #[must_use]
-    pub const fn new(secret_manager: Arc<SecretManager>, settings: SecretSettings) -> Self {
+    pub fn new(secret_manager: Arc<SecretManager>, settings: SecretSettings) -> Self {
         Self {
-            resolver: CredentialResolver::new(secret_manager, settings),
+            resolver: CredentialResolver::new(secret_manager, settings.clone()),
+            settings,
+            groups: Vec::new(),
         }
     }
 
+    /// Sets the connection groups used to find KDBX entries
+    ///
+    /// Entries are looked up at the connection's hierarchical path (see
+    /// [`KeePassHierarchy::build_entry_path`]) before the legacy flat names.
+    #[must_use]
+    pub fn with_groups(mut self, groups: Vec<ConnectionGroup>) -> Self {
+        self.groups = groups;
+        self
+    }
+
     /// Resolves credentials asynchronously
     ///
     /// This method resolves credentials without blocking the calling thread.
@@ -186,7 +341,12 @@ impl AsyncCredentialResolver {
             return AsyncCredentialResult::Cancelled;
         }
 
-        // Use tokio::select! to race between resolution and cancellation check
+        // Query the KDBX database in a child process the token can kill
+        if let Some(result) = self.resolve_from_kdbx(connection, cancel_token).await {
+            return result;
+        }
+
+        // Use tokio::select! to race between resolution and cancellation
         tokio::select! {
             result = self.resolver.resolve(connection) => {
                 // Check cancellation after resolution completes
@@ -199,18 +359,102 @@ impl AsyncCredentialResolver {
                     Err(e) => AsyncCredentialResult::Error(e.to_string()),
                 }
             }
-            () = async {
-                // Poll cancellation token periodically
-                loop {
-                    if cancel_token.is_cancelled() {
-                        return;
+            () = cancel_token.cancelled() => {
+                AsyncCredentialResult::Cancelled
+            }
+        }
+    }
+
+    /// Looks up the connection's password in the KDBX database via `keepassxc-cli`
+    ///
+    /// Each lookup runs as a child process tracked by `cancel_token`, so
+    /// cancelling kills `keepassxc-cli` instead of leaving it running.
+    ///
+    /// # Returns
+    /// `Some(result)` if the lookup produced a final result (credentials found
+    /// or cancelled), `None` if resolution should continue with the backends
+    async fn resolve_from_kdbx(
+        &self,
+        connection: &Connection,
+        cancel_token: &CancellationToken,
+    ) -> Option<AsyncCredentialResult> {
+        if connection.password_source != PasswordSource::KeePass || !self.settings.kdbx_enabled {
+            return None;
+        }
+        let kdbx_path = self.settings.kdbx_path.as_deref()?;
+        let cli_path = KeePassStatus::find_keepassxc_cli()?;
+
+        let db_password = self.settings.kdbx_password.as_ref().map(|p| {
+            let mut input = p.expose_secret().to_string();
+            input.push('\n');
+            SecretString::from(input)
+        });
+        let key_file = self.settings.kdbx_key_file.as_deref();
+
+        for entry_path in self.kdbx_entry_paths(connection) {
+            let args = KeePassStatus::kdbx_show_args(
+                kdbx_path,
+                db_password.is_some(),
+                key_file,
+                &entry_path,
+            );
+            let mut command = Command::new(&cli_path);
+            command.args(&args);
+
+            let stdin = db_password.as_ref().map(|p| p.expose_secret().as_bytes());
+            match run_cancellable_process(&mut command, stdin, cancel_token).await {
+                ProcessOutcome::Completed(output) if output.status.success() => {
+                    let password = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                    if !password.is_empty() {
+                        debug!(entry_path = %entry_path, "Found password in KDBX database");
+                        let creds = Credentials {
+                            username: connection.username.clone(),
+                            password: Some(SecretString::from(password)),
+                            key_passphrase: None,
+                            domain: connection.domain.clone(),
+                        };
+                        return Some(AsyncCredentialResult::Success(Some(creds)));
                     }
-                    tokio::time::sleep(Duration::from_millis(10)).await;
                 }
-            } => {
-                AsyncCredentialResult::Cancelled
+                ProcessOutcome::Completed(_) => {}
+                ProcessOutcome::Cancelled => return Some(AsyncCredentialResult::Cancelled),
+                ProcessOutcome::Failed(e) => {
+                    warn!(error = %e, "KDBX lookup failed");
+                    return None;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the KDBX entry paths to try for a connection
+    ///
+    /// The hierarchical path comes first, followed by the flat
+    /// `{name} ({protocol})` paths older versions saved passwords under.
+    fn kdbx_entry_paths(&self, connection: &Connection) -> Vec<String> {
+        let mut paths = vec![KeePassHierarchy::build_entry_path(connection, &self.groups)];
+        for path in KeePassStatus::kdbx_entry_paths(&Self::kdbx_entry_name(connection), None) {
+            if !paths.contains(&path) {
+                paths.push(path);
             }
         }
+        paths
+    }
+
+    /// Returns the KDBX entry name for a connection: `{name} ({protocol})`
+    fn kdbx_entry_name(connection: &Connection) -> String {
+        let base_name = if connection.name.trim().is_empty() {
+            &connection.host
+        } else {
+            &connection.name
+        };
+        // Passwords are saved with '/' replaced to avoid creating subgroups
+        format!(
+            "{} ({})",
+            base_name.replace('/', "-"),
+            connection.protocol_config.protocol_type().as_str()
+        )
     }
 
     /// Resolves credentials with a timeout
@@ -255,32 +499,16 @@ impl AsyncCredentialResolver {
         cancel_token: &CancellationToken,
         timeout: Duration,
     ) -> AsyncCredentialResult {
-        if cancel_token.is_cancelled() {
-            return AsyncCredentialResult::Cancelled;
-        }
-
-        tokio::select! {
-            result = tokio::time::timeout(timeout, self.resolver.resolve(connection)) => {
-                if cancel_token.is_cancelled() {
-                    return AsyncCredentialResult::Cancelled;
-                }
-
-                match result {
-                    Ok(Ok(creds)) => AsyncCredentialResult::Success(creds),
-                    Ok(Err(e)) => AsyncCredentialResult::Error(e.to_string()),
-                    Err(_) => AsyncCredentialResult::Timeout,
-                }
-            }
-            () = async {
-                loop {
-                    if cancel_token.is_cancelled() {
-                        return;
-                    }
-                    tokio::time::sleep(Duration::from_millis(10)).await;
-                }
-            } => {
-                AsyncCredentialResult::Cancelled
-            }
+        // Dropping the resolution on timeout also kills any child process
+        match tokio::time::timeout(
+            timeout,
+            self.resolve_with_cancellation(connection, cancel_token),
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(_) if cancel_token.is_cancelled() => AsyncCredentialResult::Cancelled,
+            Err(_) => AsyncCredentialResult::Timeout,
         }
     }
 
@@ -299,6 +527,8 @@ pub struct PendingCredentialResolution {
     receiver: oneshot::Receiver<AsyncCredentialResult>,
     /// Cancellation token for this operation
     cancel_token: CancellationToken,
+    /// Connection being resolved, used to resume the connect after an unlock
+    connection_id: Option<Uuid>,
 }
 
 impl PendingCredentialResolution {
@@ -311,9 +541,26 @@ impl PendingCredentialResolution {
         Self {
             receiver,
             cancel_token,
+            connection_id: None,
         }
     }
 
+    /// Sets the connection this resolution belongs to
+    #[must_use]
+    pub const fn with_connection_id(mut self, connection_id: Uuid) -> Self {
+        self.connection_id = Some(connection_id);
+        self
+    }
+
+    /// Returns the connection this resolution belongs to
+    ///
+    /// When the resolution fails because a vault was locked, the UI can
+    /// resolve this connection again once it has been unlocked.
+    #[must_use]
+    pub const fn connection_id(&self) -> Option<Uuid> {
+        self.connection_id
+    }
+
     /// Cancels the pending operation
     pub fn cancel(&self) {
         self.cancel_token.cancel();
@@ -363,6 +610,7 @@ pub fn spawn_credential_resolution(
     let (sender, receiver) = oneshot::channel();
     let cancel_token = CancellationToken::new();
     let token_clone = cancel_token.clone();
+    let connection_id = connection.id;
 
     tokio::spawn(async move {
         let result = if let Some(timeout_duration) = timeout {
@@ -379,7 +627,7 @@ pub fn spawn_credential_resolution(
         let _ = sender.send(result);
     });
 
-    PendingCredentialResolution::new(receiver, cancel_token)
+    PendingCredentialResolution::new(receiver, cancel_token).with_connection_id(connection_id)
 }
 
 /// Resolves credentials and calls a callback with the result
@@ -485,6 +733,98 @@ mod tests {
         assert_eq!(result.error_message(), Some("test error"));
     }
 
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_cancellable_process_completes() {
+        let token = CancellationToken::new();
+        let outcome = block_on(run_cancellable_process(
+            Command::new("cat").arg("-"),
+            Some(b"hello"),
+            &token,
+        ));
+
+        match outcome {
+            ProcessOutcome::Completed(output) => {
+                assert!(output.status.success());
+                assert_eq!(output.stdout, b"hello");
+            }
+            other => panic!("Expected completion, got {other:?}"),
+        }
+        assert_eq!(token.active_processes(), 0);
+    }
+
+    #[test]
+    fn test_cancel_kills_child_process() {
+        let token = CancellationToken::new();
+        let started = std::time::Instant::now();
+
+        let outcome = block_on(async {
+            let canceller = token.clone();
+            tokio::spawn(async move {
+                // Wait until the process is actually running before cancelling
+                while canceller.active_processes() == 0 {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+                canceller.cancel();
+            });
+            run_cancellable_process(Command::new("sleep").arg("30"), None, &token).await
+        });
+
+        assert!(matches!(outcome, ProcessOutcome::Cancelled));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(token.active_processes(), 0);
+    }
+
+    #[test]
+    fn test_cancelled_token_does_not_spawn() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let outcome = block_on(run_cancellable_process(
+            &mut Command::new("true"),
+            None,
+            &token,
+        ));
+        assert!(matches!(outcome, ProcessOutcome::Cancelled));
+    }
+
+    #[test]
+    fn test_spawn_failure_is_failed() {
+        let token = CancellationToken::new();
+        let outcome = block_on(run_cancellable_process(
+            &mut Command::new("/nonexistent/rustconn-test-binary"),
+            None,
+            &token,
+        ));
+        assert!(matches!(outcome, ProcessOutcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_kdbx_entry_paths_start_with_hierarchy() {
+        let group = ConnectionGroup::new("Production".to_string());
+        let mut connection = Connection::new_ssh("web".to_string(), "web.example".to_string(), 22);
+        connection.group_id = Some(group.id);
+
+        let resolver = AsyncCredentialResolver::new(
+            Arc::new(SecretManager::empty()),
+            SecretSettings::default(),
+        )
+        .with_groups(vec![group]);
+        assert_eq!(
+            resolver.kdbx_entry_paths(&connection),
+            [
+                "RustConn/Production/web",
+                "RustConn/web (ssh)",
+                "RustConn/web",
+                "web (ssh)",
+            ]
+        );
+    }
+
     #[test]
     fn test_async_credential_result_timeout() {
         let result = AsyncCredentialResult::Timeout;
@@ -4,10 +4,38 @@
 //! implementations must implement.
 
 use async_trait::async_trait;
+use secrecy::SecretString;
 
-use crate::error::SecretResult;
+use crate::error::{SecretError, SecretResult};
 use crate::models::Credentials;
 
+/// Lock state change reported by a secret backend
+///
+/// Password managers such as `KeePassXC` or Bitwarden can lock their vault
+/// while `RustConn` is running. Forwarding these events to
+/// `SecretManager::handle_backend_event` keeps cached credentials from
+/// outliving the vault session they were read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretBackendEvent {
+    /// The vault was locked
+    Locked,
+    /// The vault was locked and unlocked again, possibly by another user
+    Relocked,
+    /// The vault was unlocked
+    Unlocked,
+}
+
+/// Reference to an entry created with [`SecretBackend::create_entry`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretEntryRef {
+    /// Entry path, e.g. `RustConn/Production/web-01`
+    pub path: String,
+    /// URL the entry is looked up by
+    pub url: String,
+    /// Backend-specific entry identifier, if the backend reports one
+    pub uuid: Option<String>,
+}
+
 /// Abstraction over secret storage backends
 ///
 /// This trait defines the interface for storing, retrieving, and deleting
@@ -45,12 +73,51 @@ pub trait SecretBackend: Send + Sync {
     /// Returns `SecretError` if the deletion operation fails
     async fn delete(&self, connection_id: &str) -> SecretResult<()>;
 
+    /// Create a new entry at a hierarchical path
+    ///
+    /// Unlike [`Self::store`], this never overwrites: an existing entry for
+    /// the same URL and username is reported as [`SecretError::EntryExists`].
+    /// Backends without hierarchical entries keep the default implementation,
+    /// which returns [`SecretError::EntryCreationUnsupported`].
+    ///
+    /// # Arguments
+    /// * `path` - Entry path, e.g. from `KeePassHierarchy::build_entry_path`
+    /// * `username` - Login stored in the entry
+    /// * `password` - Password stored in the entry
+    /// * `url` - URL the entry is looked up by
+    ///
+    /// # Errors
+    /// Returns `SecretError` if the entry exists or cannot be created
+    async fn create_entry(
+        &self,
+        path: &str,
+        username: Option<&str>,
+        password: &SecretString,
+        url: &str,
+    ) -> SecretResult<SecretEntryRef> {
+        let _ = (path, username, password, url);
+        Err(SecretError::EntryCreationUnsupported(
+            self.display_name().to_string(),
+        ))
+    }
+
     /// Check if the backend is available and operational
     ///
     /// # Returns
     /// `true` if the backend is available, `false` otherwise
     async fn is_available(&self) -> bool;
 
+    /// Check if the backend's vault is locked
+    ///
+    /// Backends without a lock keep the default implementation, which
+    /// returns `false`.
+    ///
+    /// # Returns
+    /// `true` if the backend must be unlocked before entries can be read
+    async fn is_locked(&self) -> bool {
+        false
+    }
+
     /// Returns the backend identifier
     ///
     /// # Returns
@@ -384,6 +384,10 @@ impl SecretBackend for BitwardenBackend {
             .unwrap_or(false)
     }
 
+    async fn is_locked(&self) -> bool {
+        !self.is_unlocked().await
+    }
+
     fn backend_id(&self) -> &'static str {
         "bitwarden"
     }
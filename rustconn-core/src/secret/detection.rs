@@ -469,6 +469,8 @@ pub fn get_password_manager_launch_command(
                 vec!["https://vault.bitwarden.com".to_string()],
             ))
         }
+        // pass is a command-line tool without a GUI application
+        crate::config::SecretBackendType::Pass => None,
     }
 }
 
@@ -5,16 +5,26 @@
 //! using the native messaging protocol.
 
 use async_trait::async_trait;
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::UnixStream;
 
 use crate::error::{SecretError, SecretResult};
 use crate::models::Credentials;
 
-use super::backend::SecretBackend;
+use super::async_resolver::CancellationToken;
+use super::backend::{SecretBackend, SecretBackendEvent, SecretEntryRef};
+use super::hierarchy::KeePassHierarchy;
+
+/// `errorCode` sent by `KeePassXC` while its database is locked
+const ERROR_DATABASE_NOT_OPENED: u64 = 1;
+
+/// `errorCode` sent by `KeePassXC` when no entry matches a URL
+const ERROR_NO_LOGINS_FOUND: u64 = 15;
 
 /// `KeePassXC` browser integration protocol client
 ///
@@ -46,6 +56,12 @@ struct KeePassXcRequest {
     group: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     uuid: Option<String>,
+    /// Group path for `create-new-group`
+    #[serde(rename = "groupName", skip_serializing_if = "Option::is_none")]
+    group_name: Option<String>,
+    /// Group to create a login in, for `set-login`
+    #[serde(rename = "groupUuid", skip_serializing_if = "Option::is_none")]
+    group_uuid: Option<String>,
 }
 
 /// Response message from `KeePassXC` protocol
@@ -57,6 +73,29 @@ struct KeePassXcResponse {
     error: Option<String>,
     #[serde(default)]
     entries: Option<Vec<KeePassXcEntry>>,
+    /// Numeric error code, sent as a number or a string
+    #[serde(default, rename = "errorCode")]
+    error_code: Option<serde_json::Value>,
+    /// Name of a group returned by `create-new-group`
+    #[serde(default)]
+    name: Option<String>,
+    /// UUID of a group returned by `create-new-group`
+    #[serde(default)]
+    uuid: Option<String>,
+    /// Database hash returned by `get-databasehash`
+    #[serde(default)]
+    hash: Option<String>,
+}
+
+impl KeePassXcResponse {
+    /// Returns the numeric `errorCode`, if any
+    fn error_code(&self) -> Option<u64> {
+        match self.error_code.as_ref()? {
+            serde_json::Value::Number(code) => code.as_u64(),
+            serde_json::Value::String(code) => code.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 /// Entry returned from `KeePassXC`
@@ -72,13 +111,8 @@ struct KeePassXcEntry {
     #[serde(default)]
     #[allow(dead_code)] // Required for JSON deserialization completeness
     name: Option<String>,
-    /// Entry UUID from `KeePassXC`
-    ///
-    /// Part of the `KeePassXC` response structure. Currently unused but
-    /// preserved for potential future use in entry identification or updates.
-    /// Required for correct JSON deserialization of `KeePassXC` responses.
+    /// Entry UUID from `KeePassXC`, returned as the reference of created entries
     #[serde(default)]
-    #[allow(dead_code)] // Required for JSON deserialization completeness
     uuid: Option<String>,
 }
 
@@ -135,6 +169,23 @@ impl KeePassXcBackend {
 
     /// Sends a request and receives a response
     async fn send_request(&self, request: &KeePassXcRequest) -> SecretResult<KeePassXcResponse> {
+        let response = self.exchange(request).await?;
+
+        // Check for errors
+        if response.error_code() == Some(ERROR_DATABASE_NOT_OPENED) {
+            return Err(SecretError::BackendUnavailable(
+                "KeePassXC database is locked".to_string(),
+            ));
+        }
+        if let Some(error) = &response.error {
+            return Err(SecretError::KeePassXC(error.clone()));
+        }
+
+        Ok(response)
+    }
+
+    /// Sends a request and receives a response without checking its `error`
+    async fn exchange(&self, request: &KeePassXcRequest) -> SecretResult<KeePassXcResponse> {
         let mut stream = self.connect().await?;
 
         // Serialize request
@@ -169,19 +220,137 @@ impl KeePassXcBackend {
             .map_err(|e| SecretError::KeePassXC(format!("Failed to read response: {e}")))?;
 
         // Parse response
-        let response: KeePassXcResponse = serde_json::from_slice(&response_buf)
-            .map_err(|e| SecretError::KeePassXC(format!("Failed to parse response: {e}")))?;
+        serde_json::from_slice(&response_buf)
+            .map_err(|e| SecretError::KeePassXC(format!("Failed to parse response: {e}")))
+    }
 
-        // Check for errors
-        if let Some(error) = &response.error {
-            return Err(SecretError::KeePassXC(error.clone()));
+    /// Returns the logins stored for a URL, treating "no logins found" as empty
+    async fn find_logins(&self, url: &str) -> SecretResult<Vec<KeePassXcEntry>> {
+        let request = KeePassXcRequest {
+            action: "get-logins".to_string(),
+            id: Some(self.client_id.clone()),
+            url: Some(url.to_string()),
+            login: None,
+            password: None,
+            group: None,
+            uuid: None,
+            group_name: None,
+            group_uuid: None,
+        };
+
+        let response = self.exchange(&request).await?;
+        if response.error_code() == Some(ERROR_NO_LOGINS_FOUND) {
+            return Ok(Vec::new());
         }
+        if let Some(error) = response.error {
+            return Err(SecretError::KeePassXC(error));
+        }
+        Ok(response.entries.unwrap_or_default())
+    }
 
-        Ok(response)
+    /// Creates a group path if missing and returns its name and UUID
+    async fn create_group(&self, path: &str) -> SecretResult<(String, Option<String>)> {
+        let request = KeePassXcRequest {
+            action: "create-new-group".to_string(),
+            id: Some(self.client_id.clone()),
+            url: None,
+            login: None,
+            password: None,
+            group: None,
+            uuid: None,
+            group_name: Some(path.to_string()),
+            group_uuid: None,
+        };
+
+        let response = self.send_request(&request).await?;
+        let name = response
+            .name
+            .unwrap_or_else(|| KeePassHierarchy::extract_entry_name(path).to_string());
+        Ok((name, response.uuid))
+    }
+
+    /// Returns the hash of the open database, or `None` while it is locked
+    ///
+    /// An unreachable `KeePassXC` counts as locked, since no credentials can
+    /// be read from it either.
+    pub async fn database_hash(&self) -> Option<String> {
+        let request = KeePassXcRequest {
+            action: "get-databasehash".to_string(),
+            id: Some(self.client_id.clone()),
+            url: None,
+            login: None,
+            password: None,
+            group: None,
+            uuid: None,
+            group_name: None,
+            group_uuid: None,
+        };
+
+        match self.exchange(&request).await {
+            Ok(response) if response.error.is_none() => response.hash,
+            _ => None,
+        }
+    }
+
+    /// Checks whether the `KeePassXC` database is currently unlocked
+    pub async fn is_unlocked(&self) -> bool {
+        self.database_hash().await.is_some()
+    }
+
+    /// Polls the lock state and reports changes until the token is cancelled
+    ///
+    /// The first poll only records the current state. After that `on_event`
+    /// receives [`SecretBackendEvent::Locked`] and
+    /// [`SecretBackendEvent::Unlocked`] on transitions, and
+    /// [`SecretBackendEvent::Relocked`] when a different database was opened
+    /// between two polls. Must be called from within a tokio runtime.
+    ///
+    /// # Arguments
+    /// * `interval` - Time between polls
+    /// * `on_event` - Called with each lock state change
+    ///
+    /// # Returns
+    /// A `CancellationToken` that stops the watcher
+    pub fn watch_lock_state<F>(
+        self: Arc<Self>,
+        interval: Duration,
+        on_event: F,
+    ) -> CancellationToken
+    where
+        F: Fn(SecretBackendEvent) + Send + 'static,
+    {
+        let cancel_token = CancellationToken::new();
+        let token = cancel_token.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut previous: Option<Option<String>> = None;
+
+            loop {
+                tokio::select! {
+                    () = token.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+
+                let current = self.database_hash().await;
+                if let Some(event) = previous
+                    .as_ref()
+                    .and_then(|previous| lock_transition(previous.as_deref(), current.as_deref()))
+                {
+                    tracing::debug!(?event, "KeePassXC lock state changed");
+                    on_event(event);
+                }
+                previous = Some(current);
+            }
+        });
+
+        cancel_token
     }
 
     /// Generates a URL for a connection ID (used as lookup key)
-    fn connection_url(connection_id: &str) -> String {
+    #[must_use]
+    pub fn connection_url(connection_id: &str) -> String {
         format!("rustconn://{connection_id}")
     }
 
@@ -199,6 +368,8 @@ impl KeePassXcBackend {
             password: None,
             group: None,
             uuid: None,
+            group_name: None,
+            group_uuid: None,
         };
 
         let response = self.send_request(&request).await?;
@@ -213,6 +384,8 @@ impl KeePassXcBackend {
                 password: None,
                 group: None,
                 uuid: None,
+                group_name: None,
+                group_uuid: None,
             };
 
             let assoc_response = self.send_request(&assoc_request).await?;
@@ -227,6 +400,18 @@ impl KeePassXcBackend {
     }
 }
 
+/// Maps two consecutive database hashes to a lock state change
+fn lock_transition(previous: Option<&str>, current: Option<&str>) -> Option<SecretBackendEvent> {
+    match (previous, current) {
+        (Some(_), None) => Some(SecretBackendEvent::Locked),
+        (None, Some(_)) => Some(SecretBackendEvent::Unlocked),
+        (Some(previous), Some(current)) if previous != current => {
+            Some(SecretBackendEvent::Relocked)
+        }
+        _ => None,
+    }
+}
+
 #[async_trait]
 impl SecretBackend for KeePassXcBackend {
     async fn store(&self, connection_id: &str, credentials: &Credentials) -> SecretResult<()> {
@@ -247,6 +432,8 @@ impl SecretBackend for KeePassXcBackend {
             password: Some(password),
             group: Some("RustConn".to_string()),
             uuid: None,
+            group_name: None,
+            group_uuid: None,
         };
 
         let response = self.send_request(&request).await?;
@@ -273,6 +460,8 @@ impl SecretBackend for KeePassXcBackend {
             password: None,
             group: None,
             uuid: None,
+            group_name: None,
+            group_uuid: None,
         };
 
         let response = self.send_request(&request).await?;
@@ -292,6 +481,73 @@ impl SecretBackend for KeePassXcBackend {
         Ok(None)
     }
 
+    /// Creates a login under the group part of `path`, creating missing groups
+    ///
+    /// The browser protocol cannot list a group's entries, so an existing
+    /// login for `url` with the same username counts as an entry at `path`.
+    /// `KeePassXC` titles the entry itself; the last path component is not
+    /// sent.
+    async fn create_entry(
+        &self,
+        path: &str,
+        username: Option<&str>,
+        password: &SecretString,
+        url: &str,
+    ) -> SecretResult<SecretEntryRef> {
+        self.ensure_associated().await?;
+
+        let login = username.unwrap_or_default();
+        if self
+            .find_logins(url)
+            .await?
+            .iter()
+            .any(|entry| entry.login.as_deref().unwrap_or_default() == login)
+        {
+            return Err(SecretError::EntryExists(path.to_string()));
+        }
+
+        let (group, group_uuid) = match KeePassHierarchy::extract_parent_path(path) {
+            Some(parent) => {
+                let (name, uuid) = self.create_group(&parent).await?;
+                (Some(name), uuid)
+            }
+            None => (None, None),
+        };
+
+        let request = KeePassXcRequest {
+            action: "set-login".to_string(),
+            id: Some(self.client_id.clone()),
+            url: Some(url.to_string()),
+            login: Some(login.to_string()),
+            password: Some(password.expose_secret().to_string()),
+            group,
+            uuid: None,
+            group_name: None,
+            group_uuid,
+        };
+
+        let response = self.send_request(&request).await?;
+        if response.success.as_deref() != Some("true") {
+            return Err(SecretError::StoreFailed(
+                "KeePassXC did not confirm entry creation".to_string(),
+            ));
+        }
+
+        // set-login does not return the new entry, so look it up
+        let uuid = self
+            .find_logins(url)
+            .await?
+            .into_iter()
+            .find(|entry| entry.login.as_deref().unwrap_or_default() == login)
+            .and_then(|entry| entry.uuid);
+
+        Ok(SecretEntryRef {
+            path: path.to_string(),
+            url: url.to_string(),
+            uuid,
+        })
+    }
+
     async fn delete(&self, connection_id: &str) -> SecretResult<()> {
         // KeePassXC browser protocol doesn't support deletion directly
         // We would need to use a different approach or mark as deleted
@@ -312,6 +568,10 @@ impl SecretBackend for KeePassXcBackend {
         self.connect().await.is_ok()
     }
 
+    async fn is_locked(&self) -> bool {
+        !self.is_unlocked().await
+    }
+
     fn backend_id(&self) -> &'static str {
         "keepassxc"
     }
@@ -320,3 +580,211 @@ impl SecretBackend for KeePassXcBackend {
         "KeePassXC"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Logins stored by the fake server as (url, login, group, uuid)
+    type Logins = Arc<Mutex<Vec<(String, String, String, String)>>>;
+
+    /// Answers each request on a socket with `handler`
+    fn serve(
+        socket_path: &std::path::Path,
+        mut handler: impl FnMut(&Value) -> Value + Send + 'static,
+    ) {
+        let listener = UnixListener::bind(socket_path).unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut len = [0u8; 4];
+                stream.read_exact(&mut len).unwrap();
+                let mut body = vec![0u8; u32::from_ne_bytes(len) as usize];
+                stream.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let response = handler(&request).to_string();
+                #[allow(clippy::cast_possible_truncation)]
+                let len = response.len() as u32;
+                stream.write_all(&len.to_ne_bytes()).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+    }
+
+    /// Serves the browser protocol on a socket, keeping logins in memory
+    fn fake_keepassxc(socket_path: &std::path::Path) -> Logins {
+        let logins: Logins = Arc::default();
+        let state = Arc::clone(&logins);
+        serve(socket_path, move |request| {
+            respond(request, &mut state.lock().unwrap())
+        });
+        logins
+    }
+
+    /// Serves `get-databasehash` with the given hash, `None` meaning locked
+    fn fake_database(socket_path: &std::path::Path) -> Arc<Mutex<Option<String>>> {
+        let hash = Arc::new(Mutex::new(Some("hash-1".to_string())));
+        let state = Arc::clone(&hash);
+        serve(socket_path, move |request| {
+            let hash = state.lock().unwrap().clone();
+            match (request["action"].as_str(), hash) {
+                (Some("get-databasehash"), Some(hash)) => json!({ "hash": hash }),
+                _ => json!({ "error": "Database not opened", "errorCode": 1 }),
+            }
+        });
+        hash
+    }
+
+    fn respond(request: &Value, logins: &mut Vec<(String, String, String, String)>) -> Value {
+        let text = |key: &str| request[key].as_str().unwrap_or_default().to_string();
+        match request["action"].as_str() {
+            Some("test-associate") => json!({ "success": "true" }),
+            Some("get-logins") => {
+                let entries: Vec<Value> = logins
+                    .iter()
+                    .filter(|(url, ..)| *url == text("url"))
+                    .map(|(_, login, _, uuid)| json!({ "login": login, "uuid": uuid }))
+                    .collect();
+                if entries.is_empty() {
+                    json!({ "error": "No logins found", "errorCode": "15" })
+                } else {
+                    json!({ "entries": entries })
+                }
+            }
+            Some("create-new-group") => {
+                json!({ "name": text("groupName"), "uuid": "group-uuid" })
+            }
+            Some("set-login") => {
+                assert_eq!(text("groupUuid"), "group-uuid");
+                let uuid = format!("entry-{}", logins.len());
+                logins.push((text("url"), text("login"), text("group"), uuid));
+                json!({ "success": "true" })
+            }
+            other => json!({ "error": format!("unexpected action {other:?}") }),
+        }
+    }
+
+    #[test]
+    fn test_create_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("kpxc_server");
+        let logins = fake_keepassxc(&socket_path);
+        let backend = KeePassXcBackend::with_socket_path("test", socket_path);
+        let password = SecretString::from("s3cret");
+        let url = KeePassXcBackend::connection_url("web");
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let entry = runtime
+            .block_on(backend.create_entry(
+                "RustConn/Production/web",
+                Some("admin"),
+                &password,
+                &url,
+            ))
+            .unwrap();
+
+        assert_eq!(entry.path, "RustConn/Production/web");
+        assert_eq!(entry.url, "rustconn://web");
+        assert_eq!(entry.uuid.as_deref(), Some("entry-0"));
+        assert_eq!(
+            logins.lock().unwrap()[0],
+            (
+                url.clone(),
+                "admin".to_string(),
+                "RustConn/Production".to_string(),
+                "entry-0".to_string()
+            )
+        );
+
+        let duplicate = runtime.block_on(backend.create_entry(
+            "RustConn/Production/web",
+            Some("admin"),
+            &password,
+            &url,
+        ));
+        assert!(matches!(duplicate, Err(SecretError::EntryExists(path)) if path.ends_with("/web")));
+        assert_eq!(logins.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_is_unlocked() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("kpxc_server");
+        let hash = fake_database(&socket_path);
+        let backend = KeePassXcBackend::with_socket_path("test", socket_path);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        assert!(runtime.block_on(backend.is_unlocked()));
+
+        *hash.lock().unwrap() = None;
+        assert!(!runtime.block_on(backend.is_unlocked()));
+        let retrieve = runtime.block_on(backend.retrieve("web"));
+        assert!(matches!(retrieve, Err(SecretError::BackendUnavailable(_))));
+
+        let missing = KeePassXcBackend::with_socket_path("test", dir.path().join("missing"));
+        assert!(!runtime.block_on(missing.is_unlocked()));
+    }
+
+    #[test]
+    fn test_lock_transition() {
+        assert_eq!(lock_transition(Some("a"), Some("a")), None);
+        assert_eq!(lock_transition(None, None), None);
+        assert_eq!(
+            lock_transition(Some("a"), None),
+            Some(SecretBackendEvent::Locked)
+        );
+        assert_eq!(
+            lock_transition(None, Some("a")),
+            Some(SecretBackendEvent::Unlocked)
+        );
+        assert_eq!(
+            lock_transition(Some("a"), Some("b")),
+            Some(SecretBackendEvent::Relocked)
+        );
+    }
+
+    #[test]
+    fn test_watch_lock_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("kpxc_server");
+        let hash = fake_database(&socket_path);
+        let backend = Arc::new(KeePassXcBackend::with_socket_path("test", socket_path));
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let seen = Arc::clone(&events);
+            let token = backend.watch_lock_state(Duration::from_millis(10), move |event| {
+                seen.lock().unwrap().push(event);
+            });
+            let wait_for = |count: usize| {
+                let events = Arc::clone(&events);
+                async move {
+                    for _ in 0..200 {
+                        if events.lock().unwrap().len() >= count {
+                            return;
+                        }
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                    panic!("expected {count} lock events");
+                }
+            };
+
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            *hash.lock().unwrap() = None;
+            wait_for(1).await;
+            *hash.lock().unwrap() = Some("hash-2".to_string());
+            wait_for(2).await;
+            token.cancel();
+        });
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [SecretBackendEvent::Locked, SecretBackendEvent::Unlocked]
+        );
+    }
+}
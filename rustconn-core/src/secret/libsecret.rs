@@ -3,11 +3,15 @@
 //! This module implements credential storage using the Secret Service API
 //! via the libsecret library. It provides fallback storage when `KeePassXC`
 //! is unavailable.
+//!
+//! By default secrets go to the default keyring collection. A specific
+//! collection can be targeted with [`LibSecretBackend::with_collection`].
 
 use async_trait::async_trait;
 use secrecy::SecretString;
 use std::collections::HashMap;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::process::Command;
 
 use crate::error::{SecretError, SecretResult};
@@ -23,6 +27,10 @@ use super::backend::SecretBackend;
 pub struct LibSecretBackend {
     /// Application identifier for stored secrets
     application_id: String,
+    /// Keyring collection alias (`None` uses the default collection)
+    collection: Option<String>,
+    /// Set once the collection alias has been resolved successfully
+    collection_verified: AtomicBool,
 }
 
 impl LibSecretBackend {
@@ -37,6 +45,8 @@ impl LibSecretBackend {
     pub fn new(application_id: impl Into<String>) -> Self {
         Self {
             application_id: application_id.into(),
+            collection: None,
+            collection_verified: AtomicBool::new(false),
         }
     }
 
@@ -46,14 +56,105 @@ impl LibSecretBackend {
         Self::new("rustconn")
     }
 
+    /// Targets a specific keyring collection instead of the default one
+    ///
+    /// Secrets are stored in, looked up from, and deleted from the collection
+    /// with the given alias (e.g. `"kiosk"`). If the collection does not exist,
+    /// operations fail with [`SecretError::LibSecret`] instead of falling back
+    /// to the default collection. The alias is resolved on first use only.
+    ///
+    /// # Arguments
+    /// * `name` - Alias of the keyring collection
+    #[must_use]
+    pub fn with_collection(mut self, name: impl Into<String>) -> Self {
+        self.collection = Some(name.into());
+        self.collection_verified = AtomicBool::new(false);
+        self
+    }
+
+    /// Returns the targeted collection alias, or `None` for the default collection
+    #[must_use]
+    pub fn collection(&self) -> Option<&str> {
+        self.collection.as_deref()
+    }
+
     /// Builds the attribute map for a connection
+    ///
+    /// When a collection is selected it is recorded as an attribute so that
+    /// lookups and deletions only match items stored in that collection.
     fn build_attributes(&self, connection_id: &str) -> HashMap<String, String> {
         let mut attrs = HashMap::new();
         attrs.insert("application".to_string(), self.application_id.clone());
         attrs.insert("connection_id".to_string(), connection_id.to_string());
+        if let Some(collection) = &self.collection {
+            attrs.insert("collection".to_string(), collection.clone());
+        }
         attrs
     }
 
+    /// Verifies that the selected collection exists
+    ///
+    /// Does nothing when the default collection is used or the alias was
+    /// already resolved. Otherwise the collection alias is resolved through
+    /// the Secret Service D-Bus API; failures are not cached, so a collection
+    /// created later is picked up.
+    async fn ensure_collection(&self) -> SecretResult<()> {
+        let Some(collection) = &self.collection else {
+            return Ok(());
+        };
+        if self.collection_verified.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        let output = Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.secrets",
+                "--object-path",
+                "/org/freedesktop/secrets",
+                "--method",
+                "org.freedesktop.Secret.Service.ReadAlias",
+                collection,
+            ])
+            .output()
+            .await
+            .map_err(|e| SecretError::LibSecret(format!("Failed to run gdbus: {e}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SecretError::LibSecret(format!(
+                "Failed to resolve keyring collection '{collection}': {}",
+                stderr.trim()
+            )));
+        }
+
+        if Self::alias_resolved(&String::from_utf8_lossy(&output.stdout)) {
+            self.collection_verified.store(true, Ordering::Release);
+            Ok(())
+        } else {
+            Err(SecretError::LibSecret(format!(
+                "Keyring collection '{collection}' does not exist"
+            )))
+        }
+    }
+
+    /// Checks whether a `ReadAlias` reply points to an existing collection
+    ///
+    /// The Secret Service returns the root path `/` for unknown aliases.
+    fn alias_resolved(reply: &str) -> bool {
+        let path = reply
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .trim_end_matches(',')
+            .trim_start_matches("objectpath")
+            .trim()
+            .trim_matches('\'');
+        !path.is_empty() && path != "/"
+    }
+
     /// Converts attributes to secret-tool command arguments
     fn attrs_to_args(attrs: &HashMap<String, String>) -> Vec<String> {
         attrs
@@ -78,6 +179,9 @@ impl LibSecretBackend {
             "--label".to_string(),
             label.to_string(),
         ];
+        if let Some(collection) = &self.collection {
+            args.push(format!("--collection={collection}"));
+        }
         args.extend(Self::attrs_to_args(&attrs));
 
         let mut child = Command::new("secret-tool")
@@ -167,6 +271,7 @@ impl LibSecretBackend {
 #[async_trait]
 impl SecretBackend for LibSecretBackend {
     async fn store(&self, connection_id: &str, credentials: &Credentials) -> SecretResult<()> {
+        self.ensure_collection().await?;
         let label = format!("RustConn: {connection_id}");
 
         // Store username if present
@@ -197,6 +302,7 @@ impl SecretBackend for LibSecretBackend {
     }
 
     async fn retrieve(&self, connection_id: &str) -> SecretResult<Option<Credentials>> {
+        self.ensure_collection().await?;
         let username = self.retrieve_value(connection_id, "username").await?;
         let password = self.retrieve_value(connection_id, "password").await?;
         let key_passphrase = self.retrieve_value(connection_id, "key_passphrase").await?;
@@ -217,6 +323,7 @@ impl SecretBackend for LibSecretBackend {
     }
 
     async fn delete(&self, connection_id: &str) -> SecretResult<()> {
+        self.ensure_collection().await?;
         // Delete all stored values for this connection
         // Ignore errors for individual keys (they might not exist)
         let _ = self.delete_value(connection_id, "username").await;
@@ -245,3 +352,50 @@ impl SecretBackend for LibSecretBackend {
         "GNOME Keyring / KDE Wallet"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_backend_uses_default_collection() {
+        let backend = LibSecretBackend::default_app();
+        assert!(backend.collection().is_none());
+
+        let attrs = backend.build_attributes("conn-1");
+        assert!(!attrs.contains_key("collection"));
+    }
+
+    #[test]
+    fn test_with_collection_threads_alias_into_attributes() {
+        let backend = LibSecretBackend::new("kiosk-app").with_collection("kiosk");
+        assert_eq!(backend.collection(), Some("kiosk"));
+
+        let attrs = backend.build_attributes("conn-1");
+        assert_eq!(attrs.get("collection").map(String::as_str), Some("kiosk"));
+        assert_eq!(
+            attrs.get("application").map(String::as_str),
+            Some("kiosk-app")
+        );
+    }
+
+    #[test]
+    fn test_verified_collection_is_not_resolved_again() {
+        let backend = LibSecretBackend::default_app().with_collection("kiosk");
+        backend.collection_verified.store(true, Ordering::Release);
+        // No gdbus call is made, so this succeeds even without a Secret Service
+        let result = tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(backend.ensure_collection());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_alias_resolved() {
+        assert!(LibSecretBackend::alias_resolved(
+            "(objectpath '/org/freedesktop/secrets/collection/kiosk',)\n"
+        ));
+        assert!(!LibSecretBackend::alias_resolved("(objectpath '/',)\n"));
+        assert!(!LibSecretBackend::alias_resolved(""));
+    }
+}
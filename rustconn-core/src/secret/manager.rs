@@ -3,28 +3,88 @@
 //! This module provides the `SecretManager` which manages multiple secret backends
 //! and automatically falls back to alternative backends when the primary is unavailable.
 
+use secrecy::SecretString;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::config::{SecretBackendType, SecretSettings};
 use crate::error::{SecretError, SecretResult};
-use crate::models::Credentials;
+use crate::models::{Connection, ConnectionGroup, Credentials, PasswordSource};
 
-use super::backend::SecretBackend;
+use super::backend::{SecretBackend, SecretBackendEvent, SecretEntryRef};
+use super::bitwarden::BitwardenBackend;
+use super::hierarchy::KeePassHierarchy;
+use super::keepassxc::KeePassXcBackend;
+use super::libsecret::LibSecretBackend;
+use super::pass::PassBackend;
+use super::verification::VerifiedCredentials;
+
+/// Default time-to-live for cached credentials (5 minutes)
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Application ID under which `KeePassXC` and libsecret entries are stored
+const APPLICATION_ID: &str = "rustconn";
+
+/// Cache entry holding resolved credentials and the time they were cached
+///
+/// Secrets are kept in `SecretString`, which zeroizes its buffer on drop,
+/// so expired or invalidated entries do not leave plaintext in memory.
+struct CachedCredentials {
+    /// Resolved credentials
+    credentials: VerifiedCredentials,
+    /// SSH key passphrase, which `VerifiedCredentials` does not carry
+    key_passphrase: Option<SecretString>,
+    /// When the entry was cached
+    cached_at: Instant,
+}
+
+impl CachedCredentials {
+    /// Creates a cache entry from credentials returned by a backend
+    fn new(credentials: &Credentials) -> Self {
+        Self {
+            credentials: VerifiedCredentials::from_verified(
+                credentials.username.clone(),
+                credentials.password.clone(),
+                credentials.domain.clone(),
+            ),
+            key_passphrase: credentials.key_passphrase.clone(),
+            cached_at: Instant::now(),
+        }
+    }
+
+    /// Checks whether the entry is older than the given TTL
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.cached_at.elapsed() >= ttl
+    }
+
+    /// Converts the entry back into backend credentials
+    fn to_credentials(&self) -> Credentials {
+        Credentials {
+            username: self.credentials.username.clone(),
+            password: self.credentials.password.clone(),
+            key_passphrase: self.key_passphrase.clone(),
+            domain: self.credentials.domain.clone(),
+        }
+    }
+}
 
 /// Composite secret manager with fallback support
 ///
 /// The `SecretManager` maintains a list of secret backends in priority order.
 /// When storing or retrieving credentials, it tries each backend in order
-/// until one succeeds. It also provides session-level caching to avoid
-/// repeated queries to the backend.
+/// until one succeeds. It also caches retrieved credentials for a limited
+/// time (see [`DEFAULT_CACHE_TTL`]) to avoid repeated queries to the backend.
 pub struct SecretManager {
     /// Backends in priority order (first = highest priority)
     backends: Vec<Arc<dyn SecretBackend>>,
-    /// Session cache for retrieved credentials
-    cache: Arc<RwLock<HashMap<String, Credentials>>>,
+    /// Cache for retrieved credentials, keyed by connection ID
+    cache: Arc<RwLock<HashMap<String, CachedCredentials>>>,
     /// Whether caching is enabled
     cache_enabled: bool,
+    /// How long cached credentials remain valid
+    cache_ttl: Duration,
 }
 
 impl Clone for SecretManager {
@@ -33,6 +93,7 @@ impl Clone for SecretManager {
             backends: self.backends.clone(),
             cache: Arc::clone(&self.cache),
             cache_enabled: self.cache_enabled,
+            cache_ttl: self.cache_ttl,
         }
     }
 }
@@ -51,20 +112,65 @@ impl SecretManager {
             backends,
             cache: Arc::new(RwLock::new(HashMap::new())),
             cache_enabled: true,
+            cache_ttl: DEFAULT_CACHE_TTL,
         }
     }
 
+    /// Sets how long cached credentials remain valid
+    #[must_use]
+    pub const fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
     /// Creates an empty `SecretManager` with no backends
     #[must_use]
     pub fn empty() -> Self {
         Self::new(Vec::new())
     }
 
+    /// Creates a `SecretManager` with the backends selected in the settings
+    ///
+    /// The preferred backend comes first, followed by libsecret when
+    /// `enable_fallback` is set. KDBX files are read directly rather than
+    /// through a backend, so [`SecretBackendType::KdbxFile`] only gets the
+    /// fallback.
+    #[must_use]
+    pub fn from_settings(settings: &SecretSettings) -> Self {
+        let mut backends: Vec<Arc<dyn SecretBackend>> = Vec::new();
+        match settings.preferred_backend {
+            SecretBackendType::KeePassXc => {
+                backends.push(Arc::new(KeePassXcBackend::new(APPLICATION_ID)));
+            }
+            SecretBackendType::KdbxFile => {}
+            SecretBackendType::LibSecret => {
+                backends.push(Arc::new(LibSecretBackend::new(APPLICATION_ID)));
+            }
+            SecretBackendType::Bitwarden => backends.push(Arc::new(BitwardenBackend::new())),
+            SecretBackendType::Pass => backends.push(Arc::new(PassBackend::new())),
+        }
+        if settings.enable_fallback && settings.preferred_backend != SecretBackendType::LibSecret {
+            backends.push(Arc::new(LibSecretBackend::new(APPLICATION_ID)));
+        }
+        Self::new(backends)
+    }
+
     /// Enables or disables credential caching
     pub const fn set_cache_enabled(&mut self, enabled: bool) {
         self.cache_enabled = enabled;
     }
 
+    /// Sets how long cached credentials remain valid
+    pub const fn set_cache_ttl(&mut self, ttl: Duration) {
+        self.cache_ttl = ttl;
+    }
+
+    /// Returns how long cached credentials remain valid
+    #[must_use]
+    pub const fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+
     /// Adds a backend to the manager
     ///
     /// The backend is added at the end of the priority list.
@@ -116,7 +222,10 @@ impl SecretManager {
         // Update cache
         if self.cache_enabled {
             let mut cache = self.cache.write().await;
-            cache.insert(connection_id.to_string(), credentials.clone());
+            cache.insert(
+                connection_id.to_string(),
+                CachedCredentials::new(credentials),
+            );
         }
 
         Ok(())
@@ -125,7 +234,7 @@ impl SecretManager {
     /// Retrieve credentials for a connection
     ///
     /// First checks the cache (if enabled), then queries backends in order.
-    /// Caches the result for the session duration.
+    /// Caches the result until the cache TTL elapses.
     ///
     /// # Arguments
     /// * `connection_id` - Unique identifier for the connection
@@ -139,8 +248,10 @@ impl SecretManager {
         // Check cache first
         if self.cache_enabled {
             let cache = self.cache.read().await;
-            if let Some(creds) = cache.get(connection_id) {
-                return Ok(Some(creds.clone()));
+            if let Some(entry) = cache.get(connection_id) {
+                if !entry.is_expired(self.cache_ttl) {
+                    return Ok(Some(entry.to_credentials()));
+                }
             }
         }
 
@@ -154,7 +265,7 @@ impl SecretManager {
                 // Cache the result
                 if self.cache_enabled {
                     let mut cache = self.cache.write().await;
-                    cache.insert(connection_id.to_string(), creds.clone());
+                    cache.insert(connection_id.to_string(), CachedCredentials::new(&creds));
                 }
                 return Ok(Some(creds));
             }
@@ -163,6 +274,46 @@ impl SecretManager {
         Ok(None)
     }
 
+    /// Looks up credentials in the backends, bypassing the cache
+    ///
+    /// Unlike [`Self::retrieve`], backend errors are not swallowed: if no
+    /// backend has the credentials, the last backend error is returned, so
+    /// that a locked vault can be told apart from a missing entry. Locked
+    /// backends are skipped and reported as `SecretError::BackendLocked`.
+    ///
+    /// # Errors
+    /// Returns `SecretError::BackendUnavailable` if no backend is available,
+    /// or the last backend error if no backend found the credentials
+    pub async fn lookup(&self, key: &str) -> SecretResult<Option<Credentials>> {
+        let mut available = false;
+        let mut last_error = None;
+        for backend in &self.backends {
+            if !backend.is_available().await {
+                continue;
+            }
+            available = true;
+            if backend.is_locked().await {
+                last_error = Some(SecretError::BackendLocked(
+                    backend.display_name().to_string(),
+                ));
+                continue;
+            }
+            match backend.retrieve(key).await {
+                Ok(Some(creds)) => return Ok(Some(creds)),
+                Ok(None) => {}
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(e),
+            None if available => Ok(None),
+            None => Err(SecretError::BackendUnavailable(
+                "No secret backend available".to_string(),
+            )),
+        }
+    }
+
     /// Delete credentials for a connection
     ///
     /// Deletes credentials from all backends that have them.
@@ -175,10 +326,7 @@ impl SecretManager {
     /// Returns `SecretError` if deletion fails on all backends
     pub async fn delete(&self, connection_id: &str) -> SecretResult<()> {
         // Remove from cache
-        if self.cache_enabled {
-            let mut cache = self.cache.write().await;
-            cache.remove(connection_id);
-        }
+        self.invalidate(connection_id).await;
 
         // Try to delete from all available backends
         let mut deleted = false;
@@ -207,15 +355,89 @@ impl SecretManager {
         }
     }
 
+    /// Creates a `KeePassXC` entry holding the password of a new connection
+    ///
+    /// Returns `Ok(None)` unless the connection's password source is
+    /// [`PasswordSource::KeePass`]. The entry path mirrors the connection's
+    /// group hierarchy (see [`KeePassHierarchy::build_entry_path`]), and its
+    /// URL is the one `KeePassXcBackend` looks credentials up by, so later
+    /// [`Self::retrieve`] calls find it.
+    ///
+    /// # Errors
+    /// Returns `SecretError::EntryExists` if the connection already has an
+    /// entry, `SecretError::BackendUnavailable` if no `KeePassXC` backend is
+    /// available, or another `SecretError` if creation fails
+    pub async fn create_connection_entry(
+        &self,
+        connection: &Connection,
+        groups: &[ConnectionGroup],
+        password: &SecretString,
+    ) -> SecretResult<Option<SecretEntryRef>> {
+        if connection.password_source != PasswordSource::KeePass {
+            return Ok(None);
+        }
+
+        let mut backend = None;
+        for candidate in &self.backends {
+            if candidate.backend_id() == "keepassxc" && candidate.is_available().await {
+                backend = Some(candidate);
+                break;
+            }
+        }
+        let backend = backend.ok_or_else(|| {
+            SecretError::BackendUnavailable("KeePassXC is not available".to_string())
+        })?;
+
+        let connection_id = connection.id.to_string();
+        let entry = backend
+            .create_entry(
+                &KeePassHierarchy::build_entry_path(connection, groups),
+                connection.username.as_deref(),
+                password,
+                &KeePassXcBackend::connection_url(&connection_id),
+            )
+            .await?;
+
+        self.invalidate(&connection_id).await;
+        Ok(Some(entry))
+    }
+
     /// Clear the credential cache
     ///
     /// This should be called when the session ends or when
     /// credentials may have changed externally.
     pub async fn clear_cache(&self) {
+        self.invalidate_all().await;
+    }
+
+    /// Removes cached credentials for a single connection
+    ///
+    /// The next retrieval for this connection queries the backends again.
+    pub async fn invalidate(&self, connection_id: &str) {
+        let mut cache = self.cache.write().await;
+        cache.remove(connection_id);
+    }
+
+    /// Removes all cached credentials
+    pub async fn invalidate_all(&self) {
         let mut cache = self.cache.write().await;
         cache.clear();
     }
 
+    /// Handles a lock state change reported by a backend
+    ///
+    /// Locking (or re-locking) a vault invalidates every cached credential,
+    /// since they were resolved under the previous vault session.
+    pub async fn handle_backend_event(&self, event: SecretBackendEvent) {
+        match event {
+            SecretBackendEvent::Locked | SecretBackendEvent::Relocked => {
+                tracing::debug!(?event, "Secret backend locked, clearing credential cache");
+                self.invalidate_all().await;
+            }
+            SecretBackendEvent::Unlocked => {}
+        }
+    }
+
     /// Check if any backend is available
     pub async fn is_available(&self) -> bool {
         for backend in &self.backends {
@@ -232,3 +454,291 @@ impl Default for SecretManager {
         Self::empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// In-memory backend that counts retrieve calls
+    struct CountingBackend {
+        retrievals: AtomicUsize,
+    }
+
+    impl CountingBackend {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                retrievals: AtomicUsize::new(0),
+            })
+        }
+
+        fn retrievals(&self) -> usize {
+            self.retrievals.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl SecretBackend for CountingBackend {
+        async fn store(
+            &self,
+            _connection_id: &str,
+            _credentials: &Credentials,
+        ) -> SecretResult<()> {
+            Ok(())
+        }
+
+        async fn retrieve(&self, _connection_id: &str) -> SecretResult<Option<Credentials>> {
+            self.retrievals.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(Credentials {
+                username: Some("admin".to_string()),
+                password: Some(SecretString::from("s3cret")),
+                key_passphrase: Some(SecretString::from("hunter2")),
+                domain: None,
+            }))
+        }
+
+        async fn delete(&self, _connection_id: &str) -> SecretResult<()> {
+            Ok(())
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        fn backend_id(&self) -> &'static str {
+            "counting"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Counting"
+        }
+    }
+
+    /// Backend posing as `KeePassXC` that records created entries
+    #[derive(Default)]
+    struct EntryBackend {
+        entries: std::sync::Mutex<Vec<SecretEntryRef>>,
+    }
+
+    #[async_trait]
+    impl SecretBackend for EntryBackend {
+        async fn store(
+            &self,
+            _connection_id: &str,
+            _credentials: &Credentials,
+        ) -> SecretResult<()> {
+            Ok(())
+        }
+
+        async fn retrieve(&self, _connection_id: &str) -> SecretResult<Option<Credentials>> {
+            Ok(None)
+        }
+
+        async fn delete(&self, _connection_id: &str) -> SecretResult<()> {
+            Ok(())
+        }
+
+        async fn create_entry(
+            &self,
+            path: &str,
+            _username: Option<&str>,
+            _password: &SecretString,
+            url: &str,
+        ) -> SecretResult<SecretEntryRef> {
+            let mut entries = self.entries.lock().unwrap();
+            if entries.iter().any(|entry| entry.url == url) {
+                return Err(SecretError::EntryExists(path.to_string()));
+            }
+            let entry = SecretEntryRef {
+                path: path.to_string(),
+                url: url.to_string(),
+                uuid: None,
+            };
+            entries.push(entry.clone());
+            drop(entries);
+            Ok(entry)
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        fn backend_id(&self) -> &'static str {
+            "keepassxc"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "KeePassXC"
+        }
+    }
+
+    fn manager_with(backend: &Arc<CountingBackend>) -> SecretManager {
+        SecretManager::new(vec![Arc::clone(backend) as Arc<dyn SecretBackend>])
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_create_connection_entry() {
+        let backend = Arc::new(EntryBackend::default());
+        let manager = SecretManager::new(vec![
+            CountingBackend::new() as Arc<dyn SecretBackend>,
+            Arc::clone(&backend) as Arc<dyn SecretBackend>,
+        ]);
+        let group = ConnectionGroup::new("Production".to_string());
+        let mut connection =
+            Connection::new_ssh("web".to_string(), "web.example.com".to_string(), 22);
+        connection.group_id = Some(group.id);
+        let groups = [group];
+        let password = SecretString::from("s3cret");
+
+        block_on(async {
+            let entry = manager
+                .create_connection_entry(&connection, &groups, &password)
+                .await
+                .unwrap();
+            assert!(entry.is_none());
+
+            connection.password_source = PasswordSource::KeePass;
+            let entry = manager
+                .create_connection_entry(&connection, &groups, &password)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(entry.path, "RustConn/Production/web");
+            assert_eq!(entry.url, format!("rustconn://{}", connection.id));
+
+            let duplicate = manager
+                .create_connection_entry(&connection, &groups, &password)
+                .await;
+            assert!(matches!(duplicate, Err(SecretError::EntryExists(_))));
+        });
+        assert_eq!(backend.entries.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_create_connection_entry_without_keepassxc() {
+        let manager = manager_with(&CountingBackend::new());
+        let mut connection =
+            Connection::new_ssh("web".to_string(), "web.example.com".to_string(), 22);
+        connection.password_source = PasswordSource::KeePass;
+
+        let result = block_on(manager.create_connection_entry(
+            &connection,
+            &[],
+            &SecretString::from("s3cret"),
+        ));
+        assert!(matches!(result, Err(SecretError::BackendUnavailable(_))));
+    }
+
+    #[test]
+    fn test_default_cache_ttl() {
+        assert_eq!(SecretManager::empty().cache_ttl(), DEFAULT_CACHE_TTL);
+        let manager = SecretManager::empty().with_cache_ttl(Duration::from_secs(30));
+        assert_eq!(manager.cache_ttl(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_second_retrieve_within_ttl_uses_cache() {
+        let backend = CountingBackend::new();
+        let manager = manager_with(&backend);
+
+        block_on(async {
+            let first = manager.retrieve("conn").await.unwrap().unwrap();
+            let second = manager.retrieve("conn").await.unwrap().unwrap();
+
+            assert_eq!(first.expose_password(), Some("s3cret"));
+            assert_eq!(second.expose_password(), Some("s3cret"));
+            assert_eq!(second.expose_key_passphrase(), Some("hunter2"));
+        });
+        assert_eq!(backend.retrievals(), 1);
+    }
+
+    #[test]
+    fn test_expired_entry_queries_backend() {
+        let backend = CountingBackend::new();
+        let manager = manager_with(&backend).with_cache_ttl(Duration::ZERO);
+
+        block_on(async {
+            manager.retrieve("conn").await.unwrap();
+            manager.retrieve("conn").await.unwrap();
+        });
+        assert_eq!(backend.retrievals(), 2);
+    }
+
+    #[test]
+    fn test_invalidate_single_connection() {
+        let backend = CountingBackend::new();
+        let manager = manager_with(&backend);
+
+        block_on(async {
+            manager.retrieve("a").await.unwrap();
+            manager.retrieve("b").await.unwrap();
+            manager.invalidate("a").await;
+            manager.retrieve("a").await.unwrap();
+            manager.retrieve("b").await.unwrap();
+        });
+        assert_eq!(backend.retrievals(), 3);
+    }
+
+    #[test]
+    fn test_lock_events_clear_cache() {
+        let backend = CountingBackend::new();
+        let manager = manager_with(&backend);
+
+        block_on(async {
+            manager.retrieve("conn").await.unwrap();
+            manager
+                .handle_backend_event(SecretBackendEvent::Unlocked)
+                .await;
+            manager.retrieve("conn").await.unwrap();
+            assert_eq!(backend.retrievals(), 1);
+
+            manager
+                .handle_backend_event(SecretBackendEvent::Locked)
+                .await;
+            manager.retrieve("conn").await.unwrap();
+            assert_eq!(backend.retrievals(), 2);
+
+            manager
+                .handle_backend_event(SecretBackendEvent::Relocked)
+                .await;
+            manager.retrieve("conn").await.unwrap();
+            assert_eq!(backend.retrievals(), 3);
+        });
+    }
+
+    #[test]
+    fn test_from_settings_selects_backends() {
+        let ids = |preferred_backend, enable_fallback| {
+            let settings = SecretSettings {
+                preferred_backend,
+                enable_fallback,
+                ..SecretSettings::default()
+            };
+            SecretManager::from_settings(&settings)
+                .backends
+                .iter()
+                .map(|b| b.backend_id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(SecretBackendType::Pass, false), vec!["pass"]);
+        assert_eq!(
+            ids(SecretBackendType::Pass, true),
+            vec!["pass", "libsecret"]
+        );
+        assert_eq!(
+            ids(SecretBackendType::KeePassXc, true),
+            vec!["keepassxc", "libsecret"]
+        );
+        assert_eq!(ids(SecretBackendType::Bitwarden, false), vec!["bitwarden"]);
+        assert_eq!(ids(SecretBackendType::LibSecret, true), vec!["libsecret"]);
+        assert_eq!(ids(SecretBackendType::KdbxFile, true), vec!["libsecret"]);
+        assert!(ids(SecretBackendType::KdbxFile, false).is_empty());
+    }
+}
@@ -5,6 +5,7 @@
 //! - libsecret for GNOME Keyring/KDE Wallet integration (fallback)
 //! - Direct KDBX file access (compatible with GNOME Secrets, `OneKeePass`, KeePass)
 //! - Bitwarden CLI integration
+//! - pass(1), the standard Unix password manager (headless setups)
 //!
 //! The `SecretManager` provides a unified interface with automatic fallback
 //! when the primary backend is unavailable.
@@ -18,15 +19,17 @@ mod kdbx;
 mod keepassxc;
 mod libsecret;
 mod manager;
+mod pass;
 mod resolver;
 mod status;
 mod verification;
 
 pub use async_resolver::{
-    resolve_with_callback, spawn_credential_resolution, AsyncCredentialResolver,
-    AsyncCredentialResult, CancellationToken, PendingCredentialResolution,
+    resolve_with_callback, run_cancellable_process, spawn_credential_resolution,
+    AsyncCredentialResolver, AsyncCredentialResult, CancellationToken, PendingCredentialResolution,
+    ProcessOutcome,
 };
-pub use backend::SecretBackend;
+pub use backend::{SecretBackend, SecretBackendEvent, SecretEntryRef};
 pub use bitwarden::{
     get_bitwarden_version, lock_vault, unlock_vault, BitwardenBackend, BitwardenVersion,
 };
@@ -39,9 +42,11 @@ pub use hierarchy::{GroupCreationResult, KeePassHierarchy, KEEPASS_ROOT_GROUP, P
 pub use kdbx::KdbxExporter;
 pub use keepassxc::KeePassXcBackend;
 pub use libsecret::LibSecretBackend;
-pub use manager::SecretManager;
+pub use manager::{SecretManager, DEFAULT_CACHE_TTL};
+pub use pass::{PassBackend, DEFAULT_PASS_PATH_TEMPLATE};
 pub use resolver::CredentialResolver;
 pub use status::{parse_keepassxc_version, KeePassStatus};
 pub use verification::{
-    CredentialStatus, CredentialVerificationManager, DialogPreFillData, VerifiedCredentials,
+    CredentialLookup, CredentialStatus, CredentialVerificationManager, DialogPreFillData,
+    VerifiedCredentials, VERIFY_ALL_CONCURRENCY,
 };
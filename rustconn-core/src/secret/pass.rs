@@ -0,0 +1,306 @@
+//! pass(1) backend for headless password storage
+//!
+//! This module implements credential storage using the standard Unix
+//! password manager `pass`. It is intended for headless machines (CI boxes,
+//! servers) where neither `KeePassXC` nor a desktop keyring is available.
+//!
+//! Each connection is stored as a multi-line entry. Following the `pass`
+//! convention, the first line holds the password and the remaining lines
+//! hold `key: value` metadata:
+//!
+//! ```text
+//! s3cret
+//! username: admin
+//! domain: CORP
+//! key_passphrase: hunter2
+//! ```
+
+use async_trait::async_trait;
+use secrecy::SecretString;
+use std::fmt::Write as _;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::error::{SecretError, SecretResult};
+use crate::models::Credentials;
+
+use super::backend::SecretBackend;
+
+/// Default path template for entries in the password store
+pub const DEFAULT_PASS_PATH_TEMPLATE: &str = "rustconn/{id}";
+
+/// Placeholder replaced by the connection identifier in path templates
+const ID_PLACEHOLDER: &str = "{id}";
+
+/// Marker printed by `pass` when an entry does not exist
+const NOT_IN_STORE_MARKER: &str = "is not in the password store";
+
+/// pass(1) backend
+///
+/// This backend shells out to `pass show` to resolve credentials and
+/// `pass insert` to store them. Connections are mapped to entries through
+/// a configurable path template (default `rustconn/{id}`).
+pub struct PassBackend {
+    /// Path template with an `{id}` placeholder for the connection identifier
+    path_template: String,
+}
+
+impl PassBackend {
+    /// Creates a new pass backend using the default path template
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            path_template: DEFAULT_PASS_PATH_TEMPLATE.to_string(),
+        }
+    }
+
+    /// Sets the path template used to map connections to entries
+    ///
+    /// Every `{id}` occurrence is replaced by the connection identifier.
+    /// Templates without a placeholder get the identifier appended as the
+    /// last path component so that connections never share an entry.
+    #[must_use]
+    pub fn with_path_template(mut self, template: impl Into<String>) -> Self {
+        self.path_template = template.into();
+        self
+    }
+
+    /// Returns the configured path template
+    #[must_use]
+    pub fn path_template(&self) -> &str {
+        &self.path_template
+    }
+
+    /// Resolves the password store path for a connection
+    #[must_use]
+    pub fn entry_path(&self, connection_id: &str) -> String {
+        if self.path_template.contains(ID_PLACEHOLDER) {
+            self.path_template.replace(ID_PLACEHOLDER, connection_id)
+        } else {
+            format!(
+                "{}/{connection_id}",
+                self.path_template.trim_end_matches('/')
+            )
+        }
+    }
+
+    /// Serializes credentials into the multi-line entry format
+    fn format_entry(credentials: &Credentials) -> String {
+        let mut entry = String::new();
+        entry.push_str(credentials.expose_password().unwrap_or_default());
+        entry.push('\n');
+
+        if let Some(username) = &credentials.username {
+            let _ = writeln!(entry, "username: {username}");
+        }
+        if let Some(domain) = &credentials.domain {
+            let _ = writeln!(entry, "domain: {domain}");
+        }
+        if let Some(passphrase) = credentials.expose_key_passphrase() {
+            let _ = writeln!(entry, "key_passphrase: {passphrase}");
+        }
+
+        entry
+    }
+
+    /// Parses a multi-line entry produced by `pass show`
+    fn parse_entry(content: &str) -> Option<Credentials> {
+        let mut lines = content.lines();
+        let password = lines
+            .next()
+            .filter(|line| !line.is_empty())
+            .map(ToString::to_string);
+
+        let mut username = None;
+        let mut domain = None;
+        let mut key_passphrase = None;
+
+        for line in lines {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match key.trim().to_lowercase().as_str() {
+                "username" | "user" | "login" => username = Some(value.to_string()),
+                "domain" => domain = Some(value.to_string()),
+                "key_passphrase" => key_passphrase = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        if password.is_none() && username.is_none() && domain.is_none() && key_passphrase.is_none()
+        {
+            return None;
+        }
+
+        Some(Credentials {
+            username,
+            password: password.map(SecretString::from),
+            key_passphrase: key_passphrase.map(SecretString::from),
+            domain,
+        })
+    }
+
+    /// Runs a pass command and returns its output
+    async fn run_pass(args: &[&str]) -> SecretResult<std::process::Output> {
+        Command::new("pass")
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .map_err(|e| SecretError::Pass(format!("Failed to run pass: {e}")))
+    }
+}
+
+impl Default for PassBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SecretBackend for PassBackend {
+    async fn store(&self, connection_id: &str, credentials: &Credentials) -> SecretResult<()> {
+        let path = self.entry_path(connection_id);
+        let entry = Self::format_entry(credentials);
+
+        let mut child = Command::new("pass")
+            .args(["insert", "--multiline", "--force", &path])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| SecretError::Pass(format!("Failed to spawn pass: {e}")))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(entry.as_bytes())
+                .await
+                .map_err(|e| SecretError::Pass(format!("Failed to write entry: {e}")))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| SecretError::Pass(format!("Failed to wait for pass: {e}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SecretError::StoreFailed(format!(
+                "pass insert {path} failed: {}",
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn retrieve(&self, connection_id: &str) -> SecretResult<Option<Credentials>> {
+        let path = self.entry_path(connection_id);
+        let output = Self::run_pass(&["show", &path]).await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // A missing entry is not an error, just return None
+            if stderr.contains(NOT_IN_STORE_MARKER) {
+                return Ok(None);
+            }
+            return Err(SecretError::RetrieveFailed(format!(
+                "pass show {path} failed: {}",
+                stderr.trim()
+            )));
+        }
+
+        Ok(Self::parse_entry(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn delete(&self, connection_id: &str) -> SecretResult<()> {
+        let path = self.entry_path(connection_id);
+        let output = Self::run_pass(&["rm", "--force", &path]).await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Already deleted
+            if stderr.contains(NOT_IN_STORE_MARKER) {
+                return Ok(());
+            }
+            return Err(SecretError::DeleteFailed(format!(
+                "pass rm {path} failed: {}",
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn is_available(&self) -> bool {
+        // `pass ls` fails when pass is missing or the store is not initialized
+        Self::run_pass(&["ls"])
+            .await
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn backend_id(&self) -> &'static str {
+        "pass"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "pass (Password Store)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_path_default_template() {
+        let backend = PassBackend::new();
+        assert_eq!(backend.entry_path("abc-123"), "rustconn/abc-123");
+    }
+
+    #[test]
+    fn test_entry_path_custom_template() {
+        let backend = PassBackend::new().with_path_template("ci/{id}/login");
+        assert_eq!(backend.entry_path("web"), "ci/web/login");
+
+        let backend = PassBackend::new().with_path_template("servers/");
+        assert_eq!(backend.entry_path("web"), "servers/web");
+    }
+
+    #[test]
+    fn test_entry_round_trip() {
+        let credentials = Credentials {
+            username: Some("admin".to_string()),
+            password: Some(SecretString::from("s3cret")),
+            key_passphrase: Some(SecretString::from("hunter2")),
+            domain: Some("CORP".to_string()),
+        };
+
+        let parsed = PassBackend::parse_entry(&PassBackend::format_entry(&credentials)).unwrap();
+        assert_eq!(parsed.username.as_deref(), Some("admin"));
+        assert_eq!(parsed.expose_password(), Some("s3cret"));
+        assert_eq!(parsed.expose_key_passphrase(), Some("hunter2"));
+        assert_eq!(parsed.domain.as_deref(), Some("CORP"));
+    }
+
+    #[test]
+    fn test_parse_entry_password_only() {
+        let parsed = PassBackend::parse_entry("only-password\n").unwrap();
+        assert_eq!(parsed.expose_password(), Some("only-password"));
+        assert!(parsed.username.is_none());
+    }
+
+    #[test]
+    fn test_parse_entry_empty() {
+        assert!(PassBackend::parse_entry("").is_none());
+        assert!(PassBackend::parse_entry("\n").is_none());
+    }
+}
@@ -8,12 +8,14 @@ use std::sync::Arc;
 use tracing::{debug, warn};
 
 use crate::config::{SecretBackendType, SecretSettings};
-use crate::error::SecretResult;
+use crate::error::{SecretError, SecretResult};
 use crate::models::{Connection, ConnectionGroup, Credentials, PasswordSource};
 
 use super::hierarchy::KeePassHierarchy;
 use super::manager::SecretManager;
-use super::verification::{CredentialStatus, CredentialVerificationManager, VerifiedCredentials};
+use super::verification::{
+    CredentialLookup, CredentialStatus, CredentialVerificationManager, VerifiedCredentials,
+};
 
 /// Resolves credentials for a connection based on configuration
 ///
@@ -116,6 +118,58 @@ impl CredentialResolver {
         result
     }
 
+    /// Checks that the stored credentials of a connection can be resolved
+    ///
+    /// The same keys as [`Self::resolve_with_hierarchy`] are looked up for
+    /// the connection's `password_source`: the hierarchical entry path, then
+    /// the legacy flat key, for `KeePass` and Bitwarden; the connection ID
+    /// for the keyring. Fallback backends and the credential cache are not
+    /// used. A vault is reported as locked only if its backend says so.
+    ///
+    /// # Arguments
+    /// * `connection` - The connection to check
+    /// * `groups` - All available connection groups for hierarchy resolution
+    ///
+    /// # Returns
+    /// `None` for password sources that store nothing (`Prompt`, `Inherit`,
+    /// `None`), otherwise the outcome of the lookup
+    pub async fn check(
+        &self,
+        connection: &Connection,
+        groups: &[ConnectionGroup],
+    ) -> Option<CredentialLookup> {
+        let keys = match connection.password_source {
+            PasswordSource::KeePass if !self.settings.kdbx_enabled => {
+                return Some(CredentialLookup::Failed(
+                    "KeePass integration is disabled".to_string(),
+                ));
+            }
+            PasswordSource::KeePass | PasswordSource::Bitwarden => vec![
+                Self::generate_hierarchical_lookup_key(connection, groups),
+                Self::generate_lookup_key(connection),
+            ],
+            PasswordSource::Keyring => vec![connection.id.to_string()],
+            PasswordSource::Prompt | PasswordSource::Inherit | PasswordSource::None => {
+                return None;
+            }
+        };
+
+        for key in keys {
+            match self.secret_manager.lookup(&key).await {
+                Ok(Some(_)) => return Some(CredentialLookup::Ok),
+                Ok(None) => {}
+                Err(SecretError::BackendLocked(_)) => {
+                    return Some(CredentialLookup::BackendLocked);
+                }
+                Err(SecretError::BackendUnavailable(_)) => {
+                    return Some(CredentialLookup::BackendUnavailable);
+                }
+                Err(e) => return Some(CredentialLookup::Failed(e.to_string())),
+            }
+        }
+        Some(CredentialLookup::EntryMissing)
+    }
+
     /// Resolves credentials from `KeePass`
     async fn resolve_from_keepass(
         &self,
@@ -211,6 +265,7 @@ impl CredentialResolver {
                 }
             }
             SecretBackendType::LibSecret => SecretBackendType::LibSecret,
+            SecretBackendType::Pass => SecretBackendType::Pass,
         }
     }
 
@@ -416,7 +471,7 @@ impl CredentialResolver {
                 let lookup_key = Self::generate_lookup_key(connection);
                 self.secret_manager.store(&lookup_key, credentials).await
             }
-            SecretBackendType::LibSecret => {
+            SecretBackendType::LibSecret | SecretBackendType::Pass => {
                 let connection_id = connection.id.to_string();
                 self.secret_manager.store(&connection_id, credentials).await
             }
@@ -450,7 +505,7 @@ impl CredentialResolver {
                 let lookup_key = Self::generate_hierarchical_lookup_key(connection, groups);
                 self.secret_manager.store(&lookup_key, credentials).await
             }
-            SecretBackendType::LibSecret => {
+            SecretBackendType::LibSecret | SecretBackendType::Pass => {
                 let connection_id = connection.id.to_string();
                 self.secret_manager.store(&connection_id, credentials).await
             }
@@ -897,12 +952,15 @@ mod tests {
             protocol: ProtocolType::Ssh,
             username: None,
             group_id: None,
+            additional_group_ids: Vec::new(),
             tags: Vec::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             protocol_config: ProtocolConfig::Ssh(SshConfig::default()),
             sort_order: 0,
+            launch_order: 0,
             last_connected: None,
+            connect_count: 0,
             password_source: PasswordSource::None,
             domain: None,
             custom_properties: Vec::new(),
@@ -917,6 +975,9 @@ mod tests {
             remember_window_position: false,
             window_geometry: None,
             skip_port_check: false,
+            color: None,
+            icon: None,
+            archived: false,
         }
     }
 
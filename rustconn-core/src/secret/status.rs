@@ -110,7 +110,7 @@ impl KeePassStatus {
     /// Finds the `keepassxc-cli` binary
     ///
     /// Searches in PATH and common installation locations.
-    fn find_keepassxc_cli() -> Option<std::path::PathBuf> {
+    pub(crate) fn find_keepassxc_cli() -> Option<std::path::PathBuf> {
         // First, try to find in PATH using `which`
         if let Ok(output) = Command::new("which").arg("keepassxc-cli").output() {
             if output.status.success() {
@@ -613,28 +613,7 @@ impl KeePassStatus {
         let cli_path = Self::find_keepassxc_cli()
             .ok_or_else(|| "keepassxc-cli not found. Please install KeePassXC.".to_string())?;
 
-        // Build list of paths to try, prioritizing exact match then legacy formats
-        let mut entry_paths = Vec::new();
-
-        // First try exact entry name (may already include protocol suffix)
-        entry_paths.push(format!("RustConn/{entry_name}"));
-
-        // If entry_name contains protocol suffix like "name (ssh)", also try without it (legacy)
-        // This handles migration from old format where entries were stored without protocol
-        if let Some(base_name) = entry_name
-            .strip_suffix(')')
-            .and_then(|s| s.rfind(" (").map(|pos| &entry_name[..pos]))
-        {
-            entry_paths.push(format!("RustConn/{base_name}"));
-        }
-
-        // If protocol provided separately, try with it (for backward compatibility)
-        if let Some(proto) = protocol {
-            entry_paths.push(format!("RustConn/{entry_name} ({proto})"));
-        }
-
-        // Finally try direct entry name without RustConn prefix
-        entry_paths.push(entry_name.to_string());
+        let entry_paths = Self::kdbx_entry_paths(entry_name, protocol);
 
         tracing::debug!(
             "get_password: entry_name='{}', protocol={:?}, has_password={}, has_key_file={}",
@@ -645,25 +624,7 @@ impl KeePassStatus {
         );
 
         for entry_path in &entry_paths {
-            let mut args = vec![
-                "show".to_string(),
-                "-s".to_string(),
-                "-a".to_string(),
-                "Password".to_string(),
-            ];
-
-            // If using key file without password, add --no-password flag
-            if db_password.is_none() && key_file.is_some() {
-                args.push("--no-password".to_string());
-            }
-
-            if let Some(kf) = key_file {
-                args.push("--key-file".to_string());
-                args.push(kf.display().to_string());
-            }
-
-            args.push(kdbx_path.display().to_string());
-            args.push(entry_path.clone());
+            let args = Self::kdbx_show_args(kdbx_path, db_password.is_some(), key_file, entry_path);
 
             tracing::debug!("get_password: trying path '{entry_path}'");
 
@@ -710,6 +671,66 @@ impl KeePassStatus {
         Ok(None)
     }
 
+    /// Builds the list of entry paths to try for a password lookup
+    ///
+    /// Searches in order: `RustConn/{name}`, `RustConn/{base_name}` (without
+    /// protocol suffix), `RustConn/{name} ({protocol})`, `{name}`.
+    pub(crate) fn kdbx_entry_paths(entry_name: &str, protocol: Option<&str>) -> Vec<String> {
+        // Build list of paths to try, prioritizing exact match then legacy formats
+        let mut entry_paths = Vec::new();
+
+        // First try exact entry name (may already include protocol suffix)
+        entry_paths.push(format!("RustConn/{entry_name}"));
+
+        // If entry_name contains protocol suffix like "name (ssh)", also try without it (legacy)
+        // This handles migration from old format where entries were stored without protocol
+        if let Some(base_name) = entry_name
+            .strip_suffix(')')
+            .and_then(|s| s.rfind(" (").map(|pos| &entry_name[..pos]))
+        {
+            entry_paths.push(format!("RustConn/{base_name}"));
+        }
+
+        // If protocol provided separately, try with it (for backward compatibility)
+        if let Some(proto) = protocol {
+            entry_paths.push(format!("RustConn/{entry_name} ({proto})"));
+        }
+
+        // Finally try direct entry name without RustConn prefix
+        entry_paths.push(entry_name.to_string());
+
+        entry_paths
+    }
+
+    /// Builds `keepassxc-cli show` arguments for reading an entry's password
+    pub(crate) fn kdbx_show_args(
+        kdbx_path: &Path,
+        has_password: bool,
+        key_file: Option<&Path>,
+        entry_path: &str,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "show".to_string(),
+            "-s".to_string(),
+            "-a".to_string(),
+            "Password".to_string(),
+        ];
+
+        // If using key file without password, add --no-password flag
+        if !has_password && key_file.is_some() {
+            args.push("--no-password".to_string());
+        }
+
+        if let Some(kf) = key_file {
+            args.push("--key-file".to_string());
+            args.push(kf.display().to_string());
+        }
+
+        args.push(kdbx_path.display().to_string());
+        args.push(entry_path.to_string());
+        args
+    }
+
     /// Verifies a KDBX database password using `keepassxc-cli`
     ///
     /// # Arguments
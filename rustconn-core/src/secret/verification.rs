@@ -11,11 +11,21 @@
 //! - Requirement 2.5: Mark credentials as verified after successful auth
 
 use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::models::{Connection, ConnectionGroup};
+use crate::progress::ProgressReporter;
+
+use super::resolver::CredentialResolver;
+
+/// Number of credential lookups [`CredentialVerificationManager::verify_all`]
+/// runs at once
+pub const VERIFY_ALL_CONCURRENCY: usize = 8;
+
 /// Status of credential verification for a connection
 ///
 /// Tracks whether credentials have been successfully used and when
@@ -32,6 +42,31 @@ pub struct CredentialStatus {
     pub failure_count: u32,
     /// Error message from last failure (if any)
     pub last_error: Option<String>,
+    /// Outcome of the last backend lookup by [`CredentialVerificationManager::verify_all`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lookup: Option<CredentialLookup>,
+    /// Timestamp of the last backend lookup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of looking up stored credentials in their backend
+///
+/// Unlike verification, a lookup does not authenticate; it only confirms
+/// that the credentials referenced by a connection can be resolved.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "result", content = "error", rename_all = "snake_case")]
+pub enum CredentialLookup {
+    /// The credentials resolved
+    Ok,
+    /// The backend is reachable but holds no entry for the connection
+    EntryMissing,
+    /// The backend reports its vault as locked
+    BackendLocked,
+    /// No backend is available to look the credentials up in
+    BackendUnavailable,
+    /// The lookup failed for another reason
+    Failed(String),
 }
 
 impl CredentialStatus {
@@ -50,6 +85,8 @@ impl CredentialStatus {
             failed_at: None,
             failure_count: 0,
             last_error: None,
+            lookup: None,
+            checked_at: None,
         }
     }
 
@@ -99,6 +136,30 @@ impl CredentialStatus {
     pub const fn has_failures(&self) -> bool {
         self.failure_count > 0
     }
+
+    /// Records the outcome of a backend lookup
+    pub fn record_lookup(&mut self, lookup: CredentialLookup) {
+        self.lookup = Some(lookup);
+        self.checked_at = Some(Utc::now());
+    }
+
+    /// Checks if the last lookup resolved the credentials
+    #[must_use]
+    pub const fn lookup_ok(&self) -> bool {
+        matches!(self.lookup, Some(CredentialLookup::Ok))
+    }
+
+    /// Checks if the last lookup found the backend locked or unavailable
+    #[must_use]
+    pub const fn is_backend_locked(&self) -> bool {
+        matches!(self.lookup, Some(CredentialLookup::BackendLocked))
+    }
+
+    /// Checks if the last lookup found no entry for the connection
+    #[must_use]
+    pub const fn is_entry_missing(&self) -> bool {
+        matches!(self.lookup, Some(CredentialLookup::EntryMissing))
+    }
 }
 
 /// Verified credentials with status information
@@ -220,6 +281,10 @@ pub struct DialogPreFillData {
     pub connection_name: Option<String>,
     /// Whether to show the "Save to `KeePass`" migration button
     pub show_migrate_button: bool,
+    /// Connection to resume connecting to once the dialog is done
+    pub connection_id: Option<Uuid>,
+    /// Display name of a locked backend the user should unlock first
+    pub locked_backend: Option<String>,
 }
 
 impl DialogPreFillData {
@@ -246,6 +311,8 @@ impl DialogPreFillData {
             domain,
             connection_name: Some(connection_name),
             show_migrate_button: false,
+            connection_id: None,
+            locked_backend: None,
         }
     }
 
@@ -267,6 +334,8 @@ impl DialogPreFillData {
             domain: verified_creds.domain.clone(),
             connection_name: Some(connection_name),
             show_migrate_button: false,
+            connection_id: None,
+            locked_backend: None,
         }
     }
 
@@ -277,6 +346,20 @@ impl DialogPreFillData {
         self
     }
 
+    /// Asks the user to unlock `backend` before resuming `connection_id`
+    #[must_use]
+    pub fn with_unlock_prompt(mut self, connection_id: Uuid, backend: impl Into<String>) -> Self {
+        self.connection_id = Some(connection_id);
+        self.locked_backend = Some(backend.into());
+        self
+    }
+
+    /// Returns true if the dialog should prompt to unlock a backend
+    #[must_use]
+    pub const fn needs_unlock(&self) -> bool {
+        self.locked_backend.is_some()
+    }
+
     /// Returns true if username should be pre-filled
     #[must_use]
     pub fn has_username(&self) -> bool {
@@ -387,6 +470,55 @@ impl CredentialVerificationManager {
             .collect()
     }
 
+    /// Looks up the stored credentials of all connections concurrently
+    ///
+    /// Each connection is checked against the backend selected by its
+    /// password source (see [`CredentialResolver::check`]), with `groups`
+    /// used to build hierarchical entry paths; connections without stored
+    /// credentials are skipped. Progress is reported as
+    /// lookups complete, and no new lookups start once `progress` is
+    /// cancelled. The outcome is recorded in each connection's status,
+    /// which is also returned, in the order of `connections`.
+    pub async fn verify_all(
+        &mut self,
+        resolver: &CredentialResolver,
+        connections: &[Connection],
+        groups: &[ConnectionGroup],
+        progress: &dyn ProgressReporter,
+    ) -> Vec<(Uuid, CredentialStatus)> {
+        let total = connections.len();
+        let mut done = 0;
+        progress.report(0, total, "Verifying credentials");
+
+        let mut lookups: Vec<(usize, CredentialLookup)> =
+            stream::iter(connections.iter().enumerate())
+                .map(|(index, connection)| async move {
+                    if progress.is_cancelled() {
+                        return (index, None);
+                    }
+                    (index, resolver.check(connection, groups).await)
+                })
+                .buffer_unordered(VERIFY_ALL_CONCURRENCY)
+                .filter_map(|(index, lookup)| {
+                    done += 1;
+                    progress.report(done, total, &connections[index].name);
+                    std::future::ready(lookup.map(|lookup| (index, lookup)))
+                })
+                .collect()
+                .await;
+        lookups.sort_by_key(|(index, _)| *index);
+
+        lookups
+            .into_iter()
+            .map(|(index, lookup)| {
+                let id = connections[index].id;
+                let status = self.statuses.entry(id).or_default();
+                status.record_lookup(lookup);
+                (id, status.clone())
+            })
+            .collect()
+    }
+
     /// Returns all connection IDs with failed credentials
     #[must_use]
     pub fn failed_connections(&self) -> Vec<Uuid> {
@@ -565,6 +697,18 @@ mod tests {
         assert_eq!(status.last_error, deserialized.last_error);
     }
 
+    #[test]
+    fn test_dialog_prefill_unlock_prompt() {
+        let data = DialogPreFillData::from_connection(None, None, "web".to_string());
+        assert!(!data.needs_unlock());
+
+        let id = Uuid::new_v4();
+        let data = data.with_unlock_prompt(id, "KeePassXC");
+        assert!(data.needs_unlock());
+        assert_eq!(data.connection_id, Some(id));
+        assert_eq!(data.locked_backend.as_deref(), Some("KeePassXC"));
+    }
+
     #[test]
     fn test_manager_serialization() {
         let mut manager = CredentialVerificationManager::new();
@@ -581,4 +725,148 @@ mod tests {
         assert!(!deserialized.is_verified(id2));
         assert_eq!(deserialized.get_status(id2).failure_count, 1);
     }
+
+    /// Backend that knows a set of keys and can report its vault as locked
+    struct LookupBackend {
+        known: Vec<String>,
+        locked: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::secret::SecretBackend for LookupBackend {
+        async fn store(
+            &self,
+            _connection_id: &str,
+            _credentials: &crate::models::Credentials,
+        ) -> crate::error::SecretResult<()> {
+            Ok(())
+        }
+
+        async fn retrieve(
+            &self,
+            key: &str,
+        ) -> crate::error::SecretResult<Option<crate::models::Credentials>> {
+            Ok(self
+                .known
+                .iter()
+                .any(|known| known == key)
+                .then(|| crate::models::Credentials::with_password("admin", "pw")))
+        }
+
+        async fn delete(&self, _connection_id: &str) -> crate::error::SecretResult<()> {
+            Ok(())
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn is_locked(&self) -> bool {
+            self.locked
+        }
+
+        fn backend_id(&self) -> &'static str {
+            "lookup"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Lookup"
+        }
+    }
+
+    fn connection(name: &str, source: crate::models::PasswordSource) -> Connection {
+        let mut connection = Connection::new_ssh(name.to_string(), "example.com".to_string(), 22);
+        connection.password_source = source;
+        connection
+    }
+
+    #[test]
+    fn test_verify_all() {
+        use crate::models::PasswordSource;
+        use crate::progress::CallbackProgressReporter;
+        use std::sync::{Arc, Mutex};
+
+        let group = ConnectionGroup::new("Production".to_string());
+        let mut vault = connection("vault", PasswordSource::Bitwarden);
+        vault.group_id = Some(group.id);
+        let groups = [group];
+        let connections = [
+            connection("stored", PasswordSource::Keyring),
+            connection("missing", PasswordSource::Keyring),
+            vault,
+            connection("prompt", PasswordSource::Prompt),
+            connection("kdbx", PasswordSource::KeePass),
+        ];
+        let resolver = |locked| {
+            let backend = LookupBackend {
+                known: vec![
+                    connections[0].id.to_string(),
+                    CredentialResolver::generate_hierarchical_lookup_key(&connections[2], &groups),
+                ],
+                locked,
+            };
+            CredentialResolver::new(
+                Arc::new(crate::secret::SecretManager::new(vec![Arc::new(backend)])),
+                crate::config::SecretSettings::default(),
+            )
+        };
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let progress = CallbackProgressReporter::new(move |current, total, _: &str| {
+            sink.lock().unwrap().push((current, total));
+        });
+
+        let mut manager = CredentialVerificationManager::new();
+        let results = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(manager.verify_all(&resolver(false), &connections, &groups, &progress));
+
+        let lookups: Vec<(Uuid, Option<CredentialLookup>)> = results
+            .iter()
+            .map(|(id, status)| (*id, status.lookup.clone()))
+            .collect();
+        assert_eq!(
+            lookups,
+            vec![
+                (connections[0].id, Some(CredentialLookup::Ok)),
+                (connections[1].id, Some(CredentialLookup::EntryMissing)),
+                (connections[2].id, Some(CredentialLookup::Ok)),
+                (
+                    connections[4].id,
+                    Some(CredentialLookup::Failed(
+                        "KeePass integration is disabled".to_string()
+                    ))
+                ),
+            ]
+        );
+        assert!(manager.get_status(connections[0].id).lookup_ok());
+        assert!(manager.get_status(connections[1].id).is_entry_missing());
+        assert!(manager.get_status(connections[2].id).lookup_ok());
+        assert!(!manager.is_verified(connections[0].id));
+        assert_eq!(reports.lock().unwrap().last(), Some(&(5, 5)));
+
+        // Only a backend reporting its vault as locked counts as locked
+        let results = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(manager.verify_all(&resolver(true), &connections[..2], &groups, &progress));
+        assert!(results.iter().all(|(_, status)| status.is_backend_locked()));
+
+        let empty = CredentialResolver::new(
+            Arc::new(crate::secret::SecretManager::empty()),
+            crate::config::SecretSettings::default(),
+        );
+        let results = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(manager.verify_all(&empty, &connections[..1], &groups, &progress));
+        assert_eq!(
+            results[0].1.lookup,
+            Some(CredentialLookup::BackendUnavailable)
+        );
+
+        progress.cancel();
+        let results = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(manager.verify_all(&resolver(false), &connections, &groups, &progress));
+        assert!(results.is_empty());
+    }
 }
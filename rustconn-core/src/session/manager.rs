@@ -4,13 +4,19 @@
 //! of active connection sessions, including starting, terminating,
 //! and tracking sessions.
 
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info_span, warn};
 use uuid::Uuid;
 
 use crate::error::SessionError;
-use crate::models::Connection;
+use crate::models::{Connection, PasswordSource};
+use crate::notification::{NoOpNotifier, Notification, NotificationUrgency, Notifier};
 use crate::protocol::ProtocolRegistry;
+use crate::tracing::span_names;
 
 use super::logger::{LogConfig, LogContext, SessionLogger};
 use super::session::{Session, SessionState, SessionType};
@@ -25,6 +31,7 @@ pub type SessionResult<T> = Result<T, SessionError>;
 /// - Tracking active sessions
 /// - Terminating sessions
 /// - Managing session logging
+/// - Disconnecting idle sessions
 pub struct SessionManager {
     /// Active sessions indexed by session ID
     sessions: HashMap<Uuid, Session>,
@@ -36,6 +43,8 @@ pub struct SessionManager {
     default_log_config: Option<LogConfig>,
     /// Whether logging is enabled globally
     logging_enabled: bool,
+    /// Receives user-facing notifications about session events
+    notifier: Arc<dyn Notifier>,
 }
 
 impl SessionManager {
@@ -48,9 +57,15 @@ impl SessionManager {
             session_loggers: HashMap::new(),
             default_log_config: None,
             logging_enabled: false,
+            notifier: Arc::new(NoOpNotifier::new()),
         }
     }
 
+    /// Sets the notifier used to alert the user about session events
+    pub fn set_notifier(&mut self, notifier: Arc<dyn Notifier>) {
+        self.notifier = notifier;
+    }
+
     /// Creates a new `SessionManager` with logging enabled
     ///
     /// # Arguments
@@ -85,6 +100,7 @@ impl SessionManager {
             session_loggers: HashMap::new(),
             default_log_config: Some(config),
             logging_enabled: true,
+            notifier: Arc::new(NoOpNotifier::new()),
         })
     }
 
@@ -106,18 +122,47 @@ impl SessionManager {
     /// # Errors
     /// Returns an error if the session cannot be started
     pub fn start_session(&mut self, connection: &Connection) -> SessionResult<Uuid> {
+        self.start_session_with_idle_timeout(connection, None)
+    }
+
+    /// Starts a new session that is disconnected after `idle_timeout` of inactivity
+    ///
+    /// Activity is reported with [`touch`](Self::touch) and idle sessions are
+    /// disconnected by [`reap_idle`](Self::reap_idle).
+    ///
+    /// # Errors
+    /// Returns an error if the session cannot be started
+    pub fn start_session_with_idle_timeout(
+        &mut self,
+        connection: &Connection,
+        idle_timeout: Option<Duration>,
+    ) -> SessionResult<Uuid> {
+        // Events inside this span can be pulled per connection for diagnostics
+        let _span = info_span!(
+            span_names::CONNECTION_ESTABLISH,
+            connection.id = %connection.id,
+            protocol = %connection.protocol,
+            host = %connection.host,
+            port = connection.port
+        )
+        .entered();
+
         // Get the protocol handler
         let protocol = self
             .protocol_registry
             .get(connection.protocol.as_str())
             .ok_or_else(|| {
                 SessionError::StartFailed(format!("Unknown protocol: {}", connection.protocol))
-            })?;
+            })
+            .inspect_err(|e| warn!(error = %e, "Connection failed"))?;
 
         // Validate the connection
-        protocol.validate_connection(connection).map_err(|e| {
-            SessionError::StartFailed(format!("Invalid connection configuration: {e}"))
-        })?;
+        protocol
+            .validate_connection(connection)
+            .map_err(|e| {
+                SessionError::StartFailed(format!("Invalid connection configuration: {e}"))
+            })
+            .inspect_err(|e| warn!(error = %e, "Connection failed"))?;
 
         // Determine session type based on protocol
         let session_type = match connection.protocol.as_str() {
@@ -131,14 +176,17 @@ impl SessionManager {
             connection.name.clone(),
             protocol.protocol_id().to_string(),
             session_type,
-        );
+        )
+        .with_idle_timeout(idle_timeout);
 
         let session_id = session.id;
 
         // Set up logging if enabled
         if self.logging_enabled {
             if let Some(ref config) = self.default_log_config {
-                let context = LogContext::new(&connection.name, connection.protocol.as_str());
+                let context = LogContext::new(&connection.name, connection.protocol.as_str())
+                    .with_host(&connection.host)
+                    .with_sensitive(connection.password_source != PasswordSource::None);
                 match SessionLogger::new(config.clone(), &context, None) {
                     Ok(logger) => {
                         let log_path = logger.log_path().to_path_buf();
@@ -172,6 +220,30 @@ impl SessionManager {
         Ok(session_id)
     }
 
+    /// Tracks a session whose process is run by the caller, such as a
+    /// terminal tab
+    ///
+    /// The session keeps the caller's `session_id`, so the IDs returned by
+    /// [`reap_idle`](Self::reap_idle) name the caller's sessions. No log is
+    /// opened; the caller logs the session itself.
+    pub fn track_session(
+        &mut self,
+        session_id: Uuid,
+        connection: &Connection,
+        idle_timeout: Option<Duration>,
+    ) {
+        let mut session = Session::new(
+            connection.id,
+            connection.name.clone(),
+            connection.protocol.as_str().to_string(),
+            SessionType::Embedded,
+        )
+        .with_idle_timeout(idle_timeout);
+        session.id = session_id;
+        session.state = SessionState::Active;
+        self.sessions.insert(session_id, session);
+    }
+
     /// Sets the process handle for a session
     ///
     /// This is called by the GUI layer after spawning the process.
@@ -300,7 +372,10 @@ impl SessionManager {
     /// Removes sessions that have been terminated from tracking.
     pub fn cleanup_terminated_sessions(&mut self) {
         self.sessions.retain(|_, session| {
-            session.state != SessionState::Terminated && session.state != SessionState::Error
+            !matches!(
+                session.state,
+                SessionState::Terminated | SessionState::Disconnected | SessionState::Error
+            )
         });
     }
 
@@ -334,11 +409,74 @@ impl SessionManager {
         self.session_loggers.get_mut(&session_id)
     }
 
+    /// Records activity on a session, resetting its idle time
+    ///
+    /// The terminal layer calls this on every input and output.
+    pub fn touch(&mut self, session_id: Uuid) {
+        if let Some(session) = self.sessions.get_mut(&session_id) {
+            session.touch();
+        }
+    }
+
+    /// Disconnects sessions idle past their own idle timeout
+    ///
+    /// Reaped sessions move to [`SessionState::Disconnected`] and emit one
+    /// notification each. Sessions without an idle timeout are never reaped.
+    ///
+    /// # Returns
+    /// The IDs of the reaped sessions
+    pub fn reap_idle(&mut self, now: DateTime<Utc>) -> Vec<Uuid> {
+        let idle: Vec<(Uuid, chrono::Duration)> = self
+            .sessions
+            .values()
+            .filter(|s| matches!(s.state, SessionState::Active | SessionState::Starting))
+            .filter_map(|s| {
+                let timeout = chrono::Duration::from_std(s.idle_timeout?).ok()?;
+                (s.idle_duration(now) >= timeout).then_some((s.id, timeout))
+            })
+            .collect();
+
+        let mut reaped = Vec::with_capacity(idle.len());
+        for (session_id, timeout) in idle {
+            if let Err(e) = self.terminate_session(session_id) {
+                tracing::warn!(%session_id, error = %e, "Failed to disconnect idle session");
+                continue;
+            }
+            if let Some(session) = self.sessions.get_mut(&session_id) {
+                session.state = SessionState::Disconnected;
+                session.ended_at = Some(now);
+            }
+            self.notify_idle_disconnect(session_id, timeout.num_minutes());
+            reaped.push(session_id);
+        }
+        reaped
+    }
+
+    /// Notifies the user that a session was disconnected for inactivity
+    fn notify_idle_disconnect(&self, session_id: Uuid, minutes: impl std::fmt::Display) {
+        if let Some(session) = self.sessions.get(&session_id) {
+            self.notifier.notify(
+                &Notification::new(
+                    "Session disconnected",
+                    format!(
+                        "'{}' was idle for {minutes} minute(s) and has been disconnected",
+                        session.connection_name
+                    ),
+                )
+                .with_urgency(NotificationUrgency::Low)
+                .with_connection(session.connection_id),
+            );
+        }
+    }
+
     /// Writes data to a session's log
     ///
+    /// Also records activity on the session.
+    ///
     /// # Errors
     /// Returns an error if writing fails
     pub fn write_to_session_log(&mut self, session_id: Uuid, data: &[u8]) -> SessionResult<()> {
+        self.touch(session_id);
         if let Some(logger) = self.session_loggers.get_mut(&session_id) {
             logger
                 .write(data)
@@ -395,4 +533,122 @@ mod tests {
         let result = manager.terminate_session(Uuid::new_v4());
         assert!(result.is_err());
     }
+
+    /// Notifier that records every notification it receives
+    #[derive(Default)]
+    struct RecordingNotifier {
+        notifications: std::sync::Mutex<Vec<Notification>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, notification: &Notification) {
+            self.notifications
+                .lock()
+                .unwrap()
+                .push(notification.clone());
+        }
+    }
+
+    fn ssh_connection(name: &str) -> Connection {
+        Connection::new(
+            name.to_string(),
+            "example.com".to_string(),
+            22,
+            crate::models::ProtocolConfig::Ssh(crate::models::SshConfig::default()),
+        )
+    }
+
+    #[test]
+    fn test_idle_disconnect_emits_one_notification() {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let mut manager = SessionManager::new();
+        manager.set_notifier(Arc::clone(&notifier) as Arc<dyn Notifier>);
+
+        let timeout = Some(Duration::from_secs(30 * 60));
+        let idle = ssh_connection("idle-host");
+        let idle_session = manager
+            .start_session_with_idle_timeout(&idle, timeout)
+            .unwrap();
+        let busy_session = manager
+            .start_session_with_idle_timeout(&ssh_connection("busy-host"), timeout)
+            .unwrap();
+
+        let now = Utc::now();
+        manager.get_session_mut(idle_session).unwrap().last_activity =
+            now - chrono::Duration::minutes(45);
+        manager.touch(busy_session);
+
+        assert_eq!(manager.reap_idle(now), vec![idle_session]);
+        assert_eq!(
+            manager.get_session(idle_session).unwrap().state,
+            SessionState::Disconnected
+        );
+
+        let notifications = notifier.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].connection_id, Some(idle.id));
+        assert!(notifications[0].body.contains("idle-host"));
+        assert!(notifications[0].body.contains("30 minute(s)"));
+
+        // Already disconnected sessions are not disconnected again
+        drop(notifications);
+        assert!(manager.reap_idle(now).is_empty());
+        assert_eq!(notifier.notifications.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_tracked_session_is_reaped_under_its_own_id() {
+        let mut manager = SessionManager::new();
+        let tab_id = Uuid::new_v4();
+        manager.track_session(
+            tab_id,
+            &ssh_connection("jump-host"),
+            Some(Duration::from_secs(60)),
+        );
+        assert_eq!(manager.active_session_count(), 1);
+        assert!(manager.session_logger(tab_id).is_none());
+
+        let now = Utc::now();
+        assert!(manager.reap_idle(now).is_empty());
+        assert_eq!(
+            manager.reap_idle(now + chrono::Duration::minutes(2)),
+            vec![tab_id]
+        );
+    }
+
+    #[test]
+    fn test_reap_idle_uses_per_session_timeout() {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let mut manager = SessionManager::new();
+        manager.set_notifier(Arc::clone(&notifier) as Arc<dyn Notifier>);
+
+        let timeout = Some(Duration::from_secs(15 * 60));
+        let jump = manager
+            .start_session_with_idle_timeout(&ssh_connection("jump-host"), timeout)
+            .unwrap();
+        let busy = manager
+            .start_session_with_idle_timeout(&ssh_connection("busy-host"), timeout)
+            .unwrap();
+        let unlimited = manager.start_session(&ssh_connection("dev-box")).unwrap();
+
+        let now = Utc::now() + chrono::Duration::minutes(20);
+        manager.get_session_mut(busy).unwrap().last_activity = now;
+
+        assert_eq!(manager.reap_idle(now), vec![jump]);
+        assert_eq!(
+            manager.get_session(jump).unwrap().state,
+            SessionState::Disconnected
+        );
+        assert_eq!(manager.get_session(jump).unwrap().ended_at, Some(now));
+        assert_ne!(
+            manager.get_session(unlimited).unwrap().state,
+            SessionState::Disconnected
+        );
+        assert_eq!(notifier.notifications.lock().unwrap().len(), 1);
+
+        // Reaped sessions are not reaped again and are cleaned up
+        assert!(manager.reap_idle(now).is_empty());
+        manager.cleanup_terminated_sessions();
+        assert!(manager.get_session(jump).is_none());
+    }
 }
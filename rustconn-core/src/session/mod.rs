@@ -5,9 +5,14 @@
 
 mod logger;
 mod manager;
+mod redaction;
 #[allow(clippy::module_inception)]
 mod session;
 
-pub use logger::{LogConfig, LogContext, LogError, LogResult, SessionLogger};
+pub use logger::{
+    LogConfig, LogContext, LogDirection, LogError, LogFormat, LogResult, SecretWindow,
+    SessionLogger,
+};
 pub use manager::SessionManager;
+pub use redaction::{SecretRedactor, REDACTED};
 pub use session::{Session, SessionState, SessionType};
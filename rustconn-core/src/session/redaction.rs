@@ -0,0 +1,136 @@
+//! Secret redaction for session transcripts
+//!
+//! Masks passwords and other secrets before they reach a log file. Input
+//! typed after a password prompt is masked up to the end of the line, and
+//! any text matching a built-in `key=value` pattern or a configured regex is
+//! replaced with [`REDACTED`].
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use super::logger::{LogDirection, LogError, LogResult};
+
+/// Replacement text for redacted secrets
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Matches a password prompt at the end of output (`Password: `, `Enter passphrase for key:`)
+static PASSWORD_PROMPT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(password|passphrase|passcode|pin)[^\n:]*:\s*$")
+        .expect("password prompt regex is valid")
+});
+
+/// Matches inline secrets such as `password=hunter2` or `token: abc123`
+static INLINE_SECRET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(password|passwd|pwd|secret|token|api[_-]?key)(\s*[=:]\s*)\S+")
+        .expect("inline secret regex is valid")
+});
+
+/// Compiles a redaction pattern, mapping failures to [`LogError`]
+///
+/// # Errors
+///
+/// Returns `LogError::InvalidRedactionPattern` if the regex is invalid.
+pub fn compile_pattern(pattern: &str) -> LogResult<Regex> {
+    Regex::new(pattern).map_err(|e| LogError::InvalidRedactionPattern(format!("{pattern}: {e}")))
+}
+
+/// Stateful redactor applied to every logged chunk
+#[derive(Debug, Clone)]
+pub struct SecretRedactor {
+    /// User-configured patterns
+    patterns: Vec<Regex>,
+    /// Output ended with a password prompt; the next input line is a secret
+    awaiting_secret: bool,
+}
+
+impl SecretRedactor {
+    /// Creates a redactor from configured patterns
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any pattern is not a valid regex.
+    pub fn new(patterns: &[String]) -> LogResult<Self> {
+        Ok(Self {
+            patterns: patterns
+                .iter()
+                .map(|p| compile_pattern(p))
+                .collect::<LogResult<_>>()?,
+            awaiting_secret: false,
+        })
+    }
+
+    /// Returns the text with secrets masked
+    pub fn redact(&mut self, direction: LogDirection, text: &str) -> String {
+        match direction {
+            LogDirection::Output => {
+                if !text.trim().is_empty() {
+                    self.awaiting_secret = PASSWORD_PROMPT.is_match(text);
+                }
+                self.redact_patterns(text)
+            }
+            LogDirection::Input if self.awaiting_secret => {
+                // Mask everything up to the end of the line; keep the line ending
+                match text.find(['\r', '\n']) {
+                    Some(end) => {
+                        self.awaiting_secret = false;
+                        format!("{REDACTED}{}", self.redact_patterns(&text[end..]))
+                    }
+                    None => REDACTED.to_string(),
+                }
+            }
+            LogDirection::Input => self.redact_patterns(text),
+        }
+    }
+
+    /// Applies the inline and configured patterns
+    fn redact_patterns(&self, text: &str) -> String {
+        let mut result = INLINE_SECRET
+            .replace_all(text, format!("${{1}}${{2}}{REDACTED}"))
+            .into_owned();
+        for pattern in &self.patterns {
+            result = pattern.replace_all(&result, REDACTED).into_owned();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_after_password_prompt_is_masked() {
+        let mut redactor = SecretRedactor::new(&[]).unwrap();
+
+        redactor.redact(LogDirection::Output, "user@host's password: ");
+        assert_eq!(redactor.redact(LogDirection::Input, "hunt"), REDACTED);
+        assert_eq!(
+            redactor.redact(LogDirection::Input, "er2\r"),
+            format!("{REDACTED}\r")
+        );
+        // The prompt has been answered
+        assert_eq!(redactor.redact(LogDirection::Input, "ls\r"), "ls\r");
+    }
+
+    #[test]
+    fn test_inline_and_configured_patterns() {
+        let mut redactor = SecretRedactor::new(&[r"\d{4}-\d{4}-\d{4}-\d{4}".to_string()]).unwrap();
+
+        assert_eq!(
+            redactor.redact(LogDirection::Input, "mysql --password=s3cret db"),
+            format!("mysql --password={REDACTED} db")
+        );
+        assert_eq!(
+            redactor.redact(LogDirection::Output, "card 1234-5678-9012-3456 ok"),
+            format!("card {REDACTED} ok")
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(matches!(
+            SecretRedactor::new(&["(".to_string()]),
+            Err(LogError::InvalidRedactionPattern(_))
+        ));
+    }
+}
@@ -7,6 +7,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Child;
+use std::time::Duration;
 use tracing::{debug, info_span};
 use uuid::Uuid;
 
@@ -24,6 +25,8 @@ pub enum SessionState {
     Disconnecting,
     /// Session has been terminated
     Terminated,
+    /// Session was disconnected after exceeding its idle timeout
+    Disconnected,
     /// Session encountered an error
     Error,
 }
@@ -55,6 +58,10 @@ pub struct Session {
     pub started_at: DateTime<Utc>,
     /// Timestamp when the session ended (if terminated)
     pub ended_at: Option<DateTime<Utc>>,
+    /// Timestamp of the last observed activity (input or output)
+    pub last_activity: DateTime<Utc>,
+    /// Inactivity after which the session is disconnected (`None` = never)
+    pub idle_timeout: Option<Duration>,
     /// Path to the log file for this session
     pub log_file: Option<PathBuf>,
     /// The child process handle (if running)
@@ -83,6 +90,7 @@ impl Session {
             "Creating new session"
         );
 
+        let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
             connection_id,
@@ -90,19 +98,47 @@ impl Session {
             protocol,
             state: SessionState::Starting,
             session_type,
-            started_at: Utc::now(),
+            started_at: now,
             ended_at: None,
+            last_activity: now,
+            idle_timeout: None,
             log_file: None,
             process: None,
         }
     }
 
+    /// Sets the idle timeout for this session
+    #[must_use]
+    pub const fn with_idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
     /// Sets the process handle for this session
     pub fn set_process(&mut self, process: Child) {
         self.process = Some(process);
         self.state = SessionState::Active;
     }
 
+    /// Records activity on this session, resetting its idle time
+    pub fn touch(&mut self) {
+        self.last_activity = Utc::now();
+    }
+
+    /// Returns how long the session has been idle at the given time
+    #[must_use]
+    pub fn idle_duration(&self, now: DateTime<Utc>) -> chrono::Duration {
+        now.signed_duration_since(self.last_activity)
+    }
+
+    /// Returns true if the session has been idle past its timeout at the given time
+    #[must_use]
+    pub fn is_idle(&self, now: DateTime<Utc>) -> bool {
+        self.idle_timeout
+            .and_then(|timeout| chrono::Duration::from_std(timeout).ok())
+            .is_some_and(|timeout| self.idle_duration(now) >= timeout)
+    }
+
     /// Sets the log file path for this session
     pub fn set_log_file(&mut self, path: PathBuf) {
         self.log_file = Some(path);
@@ -206,6 +242,8 @@ impl std::fmt::Debug for Session {
             .field("session_type", &self.session_type)
             .field("started_at", &self.started_at)
             .field("ended_at", &self.ended_at)
+            .field("last_activity", &self.last_activity)
+            .field("idle_timeout", &self.idle_timeout)
             .field("log_file", &self.log_file)
             .field("pid", &self.process.as_ref().map(std::process::Child::id))
             .finish()
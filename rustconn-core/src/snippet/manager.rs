@@ -3,7 +3,7 @@
 //! This module provides the `SnippetManager` which handles creating, reading,
 //! updating, and deleting command snippets with persistence through `ConfigManager`.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::LazyLock;
 
 use regex::Regex;
@@ -12,12 +12,25 @@ use uuid::Uuid;
 use crate::config::ConfigManager;
 use crate::error::{ConfigError, ConfigResult};
 use crate::models::{Snippet, SnippetVariable};
+use crate::search::SearchEngine;
 
 /// Cached regex for variable extraction: matches `${var_name}` patterns
 static VARIABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"\$\{([a-zA-Z_][a-zA-Z0-9_]*)\}").expect("VARIABLE_REGEX is a valid regex pattern")
 });
 
+/// Minimum fuzzy score for a snippet field to match a search
+///
+/// A field containing every query character in order always scores above
+/// this; one matching only a few of them stays below it.
+const SEARCH_MIN_SCORE: f32 = 0.4;
+
+/// Sorts snippets by name
+fn sorted_by_name(mut snippets: Vec<&Snippet>) -> Vec<&Snippet> {
+    snippets.sort_by(|a, b| a.name.cmp(&b.name));
+    snippets
+}
+
 /// Manager for snippet CRUD operations
 ///
 /// Provides in-memory storage with persistence through `ConfigManager`.
@@ -186,6 +199,31 @@ impl SnippetManager {
             .collect()
     }
 
+    /// Groups all snippets by category
+    ///
+    /// Categories are sorted by name, and snippets by name within each
+    /// category. Uncategorized snippets come last, under `None`.
+    #[must_use]
+    pub fn by_category(&self) -> Vec<(Option<&str>, Vec<&Snippet>)> {
+        let mut categories: BTreeMap<&str, Vec<&Snippet>> = BTreeMap::new();
+        let mut uncategorized = Vec::new();
+        for snippet in self.snippets.values() {
+            match snippet.category.as_deref() {
+                Some(category) => categories.entry(category).or_default().push(snippet),
+                None => uncategorized.push(snippet),
+            }
+        }
+
+        let mut grouped: Vec<(Option<&str>, Vec<&Snippet>)> = categories
+            .into_iter()
+            .map(|(category, snippets)| (Some(category), sorted_by_name(snippets)))
+            .collect();
+        if !uncategorized.is_empty() {
+            grouped.push((None, sorted_by_name(uncategorized)));
+        }
+        grouped
+    }
+
     /// Gets all snippets without a category
     #[must_use]
     pub fn get_uncategorized(&self) -> Vec<&Snippet> {
@@ -260,8 +298,10 @@ impl SnippetManager {
 
     /// Searches snippets by query string
     ///
-    /// Matches against name, command content, description, and category.
-    /// Case-insensitive matching.
+    /// Uses the fuzzy matching of [`SearchEngine`], case-insensitive like
+    /// connection search, against name, command content, tags, category and
+    /// description, in decreasing weight. A field matches when its score
+    /// reaches `SEARCH_MIN_SCORE`. An empty query returns all snippets.
     ///
     /// # Arguments
     ///
@@ -269,50 +309,51 @@ impl SnippetManager {
     ///
     /// # Returns
     ///
-    /// A vector of references to matching snippets
+    /// A vector of references to matching snippets, best match first
     #[must_use]
     pub fn search(&self, query: &str) -> Vec<&Snippet> {
-        let query_lower = query.to_lowercase();
+        let query = query.trim();
+        if query.is_empty() {
+            return sorted_by_name(self.snippets.values().collect());
+        }
 
-        self.snippets
+        let engine = SearchEngine::new();
+        let mut scored: Vec<(f32, &Snippet)> = self
+            .snippets
             .values()
-            .filter(|snippet| {
-                // Match name
-                if snippet.name.to_lowercase().contains(&query_lower) {
-                    return true;
-                }
-
-                // Match command content
-                if snippet.command.to_lowercase().contains(&query_lower) {
-                    return true;
-                }
-
-                // Match description
-                if let Some(ref desc) = snippet.description {
-                    if desc.to_lowercase().contains(&query_lower) {
-                        return true;
-                    }
-                }
-
-                // Match category
-                if let Some(ref cat) = snippet.category {
-                    if cat.to_lowercase().contains(&query_lower) {
-                        return true;
-                    }
-                }
+            .filter_map(|snippet| {
+                let score = Self::score_snippet(&engine, query, snippet);
+                (score > 0.0).then_some((score, snippet))
+            })
+            .collect();
 
-                // Match tags
-                if snippet
-                    .tags
-                    .iter()
-                    .any(|tag| tag.to_lowercase().contains(&query_lower))
-                {
-                    return true;
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .partial_cmp(a_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        scored.into_iter().map(|(_, snippet)| snippet).collect()
+    }
+
+    /// Returns the best weighted field score of a snippet, or 0.0
+    fn score_snippet(engine: &SearchEngine, query: &str, snippet: &Snippet) -> f32 {
+        let fields = std::iter::once((snippet.name.as_str(), 1.0))
+            .chain(std::iter::once((snippet.command.as_str(), 0.9)))
+            .chain(snippet.tags.iter().map(|tag| (tag.as_str(), 0.8)))
+            .chain(snippet.category.as_deref().map(|category| (category, 0.7)))
+            .chain(snippet.description.as_deref().map(|desc| (desc, 0.6)));
+
+        fields
+            .map(|(field, weight)| {
+                let score = engine.fuzzy_score(query, field);
+                if score >= SEARCH_MIN_SCORE {
+                    score * weight
+                } else {
+                    0.0
                 }
-
-                false
             })
-            .collect()
+            .fold(0.0, f32::max)
     }
 
     // ========== Variable Extraction and Substitution ==========
@@ -614,6 +655,72 @@ mod tests {
         assert_eq!(results[0].name, "Disk usage");
     }
 
+    #[test]
+    fn test_search_fuzzy_ranked() {
+        let (mut manager, _temp) = create_test_manager();
+
+        let mut restart = Snippet::new(
+            "Restart nginx".to_string(),
+            "sudo systemctl restart nginx".to_string(),
+        );
+        restart.tags = vec!["web".to_string()];
+        manager.create_snippet_from(restart).unwrap();
+        manager
+            .create_snippet(
+                "Follow logs".to_string(),
+                "journalctl -u nginx -f".to_string(),
+            )
+            .unwrap();
+        manager
+            .create_snippet("Disk usage".to_string(), "df -h".to_string())
+            .unwrap();
+
+        // Subsequence match on the name, case-insensitive
+        let results = manager.search("RSTNGX");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Restart nginx");
+
+        // Name matches rank above command matches
+        let results = manager.search("nginx");
+        let names: Vec<&str> = results.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Restart nginx", "Follow logs"]);
+
+        assert!(manager.search("zzz").is_empty());
+        assert_eq!(manager.search("  ").len(), 3);
+    }
+
+    #[test]
+    fn test_by_category() {
+        let (mut manager, _temp) = create_test_manager();
+
+        for (name, category) in [
+            ("Top", Some("Monitoring")),
+            ("Df", Some("Disk")),
+            ("Free", Some("Monitoring")),
+            ("Echo", None),
+        ] {
+            let mut snippet = Snippet::new(name.to_string(), "true".to_string());
+            snippet.category = category.map(str::to_string);
+            manager.create_snippet_from(snippet).unwrap();
+        }
+
+        let grouped: Vec<(Option<&str>, Vec<&str>)> = manager
+            .by_category()
+            .into_iter()
+            .map(|(category, snippets)| {
+                (category, snippets.iter().map(|s| s.name.as_str()).collect())
+            })
+            .collect();
+        assert_eq!(
+            grouped,
+            vec![
+                (Some("Disk"), vec!["Df"]),
+                (Some("Monitoring"), vec!["Free", "Top"]),
+                (None, vec!["Echo"]),
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_variables_simple() {
         let command = "ssh ${user}@${host}";
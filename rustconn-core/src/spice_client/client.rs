@@ -32,8 +32,9 @@
 
 use super::event::SpiceChannel;
 use super::{
-    launch_spice_viewer, SpiceClientCommand, SpiceClientConfig, SpiceClientError, SpiceClientEvent,
-    SpiceViewerLaunchResult,
+    certificate_fingerprint, launch_spice_viewer, tls_relay, verify_certificate, SpiceCertDecision,
+    SpiceCertificateMismatch, SpiceClientCommand, SpiceClientConfig, SpiceClientError,
+    SpiceClientEvent, SpiceSharedFolder, SpiceUsbDeviceId, SpiceViewerLaunchResult,
 };
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -92,6 +93,8 @@ pub struct SpiceClient {
     using_fallback: bool,
     /// Process ID of external viewer (if using fallback)
     fallback_pid: Option<u32>,
+    /// Whether the GUI answers certificate mismatch prompts
+    certificate_prompt: bool,
 }
 
 impl SpiceClient {
@@ -107,9 +110,21 @@ impl SpiceClient {
             shutdown_signal: Arc::new(AtomicBool::new(false)),
             using_fallback: false,
             fallback_pid: None,
+            certificate_prompt: false,
         }
     }
 
+    /// Sets whether the GUI answers certificate mismatch prompts
+    ///
+    /// When enabled, a server certificate that does not match the pinned
+    /// fingerprint is reported with `SpiceClientEvent::CertificateMismatch`
+    /// and the connection waits for a `CertificateDecision` command.
+    /// Otherwise the connection fails with
+    /// `SpiceClientError::CertificateMismatch`.
+    pub const fn set_certificate_prompt(&mut self, enabled: bool) {
+        self.certificate_prompt = enabled;
+    }
+
     /// Connects to the SPICE server using native protocol embedding
     ///
     /// This method attempts to connect using the native SPICE protocol when
@@ -172,6 +187,7 @@ impl SpiceClient {
         let config = self.config.clone();
         let connected = self.connected.clone();
         let shutdown_signal = self.shutdown_signal.clone();
+        let certificate_prompt = self.certificate_prompt;
 
         self.connected.store(true, Ordering::SeqCst);
 
@@ -192,8 +208,14 @@ impl SpiceClient {
             };
 
             rt.block_on(async move {
-                let result =
-                    run_spice_client(config, event_tx.clone(), command_rx, shutdown_signal).await;
+                let result = run_spice_client(
+                    config,
+                    event_tx.clone(),
+                    command_rx,
+                    shutdown_signal,
+                    certificate_prompt,
+                )
+                .await;
                 connected.store(false, Ordering::SeqCst);
 
                 if let Err(e) = result {
@@ -379,6 +401,39 @@ impl SpiceClient {
         self.connected.load(Ordering::SeqCst)
     }
 
+    /// Shares a local folder with the running session
+    ///
+    /// A `SharedFolderAdded` event confirms the change once the server has
+    /// accepted the share. The native client has no webdav channel yet, so
+    /// for now it answers with an `Error` event reporting the feature as
+    /// unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the local path is not an existing directory, or if
+    /// not connected or channel is closed.
+    pub fn add_shared_folder(&self, folder: SpiceSharedFolder) -> Result<(), SpiceClientError> {
+        folder.validate()?;
+        self.send_command(SpiceClientCommand::AddSharedFolder(folder))
+    }
+
+    /// Stops sharing a folder with the running session
+    ///
+    /// A `SharedFolderRemoved` event confirms the change; an unknown share is
+    /// reported with an `Error` event.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or channel is closed.
+    pub fn remove_shared_folder(
+        &self,
+        share_name: impl Into<String>,
+    ) -> Result<(), SpiceClientError> {
+        self.send_command(SpiceClientCommand::RemoveSharedFolder {
+            share_name: share_name.into(),
+        })
+    }
+
     /// Returns the configuration
     #[must_use]
     pub const fn config(&self) -> &SpiceClientConfig {
@@ -412,27 +467,126 @@ impl Drop for SpiceClient {
     }
 }
 
+/// Connects the TLS upstream, checks its certificate and starts the relay
+///
+/// A certificate that does not match the pinned fingerprint is offered to
+/// the user when `certificate_prompt` is set, which consumes and hands back
+/// the command receiver.
+async fn start_tls_relay(
+    config: &SpiceClientConfig,
+    event_tx: &std::sync::mpsc::Sender<SpiceClientEvent>,
+    mut command_rx: std::sync::mpsc::Receiver<SpiceClientCommand>,
+    shutdown_signal: &Arc<AtomicBool>,
+    certificate_prompt: bool,
+) -> Result<
+    (
+        tls_relay::TlsRelay,
+        std::sync::mpsc::Receiver<SpiceClientCommand>,
+    ),
+    SpiceClientError,
+> {
+    use tokio::time::{timeout, Duration};
+
+    let connect_timeout = Duration::from_secs(config.timeout_secs);
+    let connector = tls_relay::build_connector(config)?;
+    let (stream, der) = timeout(
+        connect_timeout,
+        tls_relay::connect_upstream(&connector, &config.host, config.port),
+    )
+    .await
+    .map_err(|_| SpiceClientError::Timeout)??;
+
+    let accepted = match verify_certificate(config, &der, None) {
+        Ok(_) => None,
+        Err(SpiceClientError::CertificateMismatch {
+            host,
+            expected,
+            actual,
+        }) if certificate_prompt => {
+            let mismatch = SpiceCertificateMismatch {
+                host,
+                port: config.port,
+                fingerprint: actual,
+                pinned: expected,
+            };
+            let _ = event_tx.send(SpiceClientEvent::CertificateMismatch(mismatch));
+            let (decision, rx) =
+                wait_for_certificate_decision(command_rx, shutdown_signal.clone()).await?;
+            command_rx = rx;
+            let mut answer = |_: &SpiceCertificateMismatch| decision;
+            verify_certificate(config, &der, Some(&mut answer))?
+        }
+        Err(e) => return Err(e),
+    };
+    if let Some(ref fingerprint) = accepted {
+        tracing::info!(
+            "Pinned SPICE server certificate {fingerprint} for {}",
+            config.host
+        );
+        let _ = event_tx.send(SpiceClientEvent::CertificatePinned {
+            fingerprint: fingerprint.clone(),
+        });
+    }
+
+    // Later channels must present the certificate accepted here
+    let session_fingerprint = config
+        .cert_fingerprint
+        .is_some()
+        .then(|| certificate_fingerprint(&der));
+    let relay = tls_relay::TlsRelay::start(
+        stream,
+        connector,
+        config.host.clone(),
+        config.port,
+        session_fingerprint,
+    )
+    .await?;
+    Ok((relay, command_rx))
+}
+
 /// Runs the SPICE client protocol loop using the `spice-client` crate
 ///
 /// This function handles the SPICE connection lifecycle:
-/// 1. Creates `SpiceClient` from spice-client crate
-/// 2. Connects to the server
-/// 3. Starts the event loop for display updates
-/// 4. Forwards input commands to the server
-/// 5. Cleans up resources on disconnect
+/// 1. For TLS, checks the server certificate against the pinned fingerprint
+///    and starts the loopback TLS relay the native client connects through
+/// 2. Creates `SpiceClient` from spice-client crate
+/// 3. Connects to the server
+/// 4. Starts the event loop for display updates
+/// 5. Forwards input commands to the server
+/// 6. Cleans up resources on disconnect
 async fn run_spice_client(
     config: SpiceClientConfig,
     event_tx: std::sync::mpsc::Sender<SpiceClientEvent>,
     command_rx: std::sync::mpsc::Receiver<SpiceClientCommand>,
     shutdown_signal: Arc<AtomicBool>,
+    certificate_prompt: bool,
 ) -> Result<(), SpiceClientError> {
     use spice_client::SpiceClient as NativeSpiceClient;
     use tokio::time::{timeout, Duration};
 
     let connect_timeout = Duration::from_secs(config.timeout_secs);
 
+    // The native client has no TLS support; route it through a loopback
+    // relay that verifies the certificate of every channel it opens
+    let (relay, command_rx) = if config.tls_enabled {
+        let (relay, command_rx) = start_tls_relay(
+            &config,
+            &event_tx,
+            command_rx,
+            &shutdown_signal,
+            certificate_prompt,
+        )
+        .await?;
+        (Some(relay), command_rx)
+    } else {
+        (None, command_rx)
+    };
+
     // Create native SPICE client
-    let mut native_client = NativeSpiceClient::new(config.host.clone(), config.port);
+    let mut native_client = match relay {
+        Some(ref relay) => NativeSpiceClient::new("127.0.0.1".to_string(), relay.port()),
+        None => NativeSpiceClient::new(config.host.clone(), config.port),
+    };
 
     // Set password if provided
     if let Some(ref password) = config.password {
@@ -445,6 +599,10 @@ async fn run_spice_client(
     match connect_result {
         Ok(Ok(())) => {
             tracing::info!("Connected to SPICE server {}:{}", config.host, config.port);
+            // All channels are open, so nothing else may use the relay
+            if let Some(ref relay) = relay {
+                relay.stop_listening();
+            }
         }
         Ok(Err(e)) => {
             return Err(SpiceClientError::ConnectionFailed(format!(
@@ -476,6 +634,13 @@ async fn run_spice_client(
 
     // Main command processing loop
     let command_rx = std::sync::Mutex::new(command_rx);
+    let mut session = SessionState {
+        usb_devices: config.usb_devices.clone(),
+        clipboard_enabled: config.clipboard_enabled,
+    };
+    if config.usb_redirection {
+        send_usb_device_list(&event_tx, &session.usb_devices);
+    }
 
     loop {
         // Check shutdown signal
@@ -504,7 +669,7 @@ async fn run_spice_client(
                 break;
             }
             Ok(cmd) => {
-                handle_command(&cmd, &event_tx);
+                handle_command(&cmd, &event_tx, &mut session);
             }
             Err(std::sync::mpsc::TryRecvError::Empty) => {
                 // No command available
@@ -522,16 +687,54 @@ async fn run_spice_client(
 
     // Abort the event loop task if still running
     event_loop_handle.abort();
+    drop(relay);
 
     Ok(())
 }
 
+/// Waits for the GUI to answer a certificate mismatch prompt
+///
+/// Runs on the blocking pool so the runtime keeps serving other tasks, and
+/// hands the receiver back afterwards. Commands other than the decision are
+/// dropped, since nothing is connected yet. Disconnecting rejects the
+/// certificate.
+async fn wait_for_certificate_decision(
+    command_rx: std::sync::mpsc::Receiver<SpiceClientCommand>,
+    shutdown_signal: Arc<AtomicBool>,
+) -> Result<
+    (
+        SpiceCertDecision,
+        std::sync::mpsc::Receiver<SpiceClientCommand>,
+    ),
+    SpiceClientError,
+> {
+    tokio::task::spawn_blocking(move || {
+        while !shutdown_signal.load(Ordering::SeqCst) {
+            match command_rx.recv_timeout(std::time::Duration::from_millis(100)) {
+                Ok(SpiceClientCommand::CertificateDecision(decision)) => {
+                    return (decision, command_rx)
+                }
+                Ok(SpiceClientCommand::Disconnect)
+                | Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                Ok(_) | Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            }
+        }
+        (SpiceCertDecision::Reject, command_rx)
+    })
+    .await
+    .map_err(|e| SpiceClientError::ChannelError(e.to_string()))
+}
+
 /// Handles a command from the GUI
 ///
 /// Note: The spice-client crate 0.2.0 has limited input support.
 /// Keyboard and mouse input forwarding is logged but actual implementation
 /// depends on the crate's Inputs channel support.
-fn handle_command(cmd: &SpiceClientCommand, event_tx: &std::sync::mpsc::Sender<SpiceClientEvent>) {
+fn handle_command(
+    cmd: &SpiceClientCommand,
+    event_tx: &std::sync::mpsc::Sender<SpiceClientEvent>,
+    session: &mut SessionState,
+) {
     match cmd {
         SpiceClientCommand::KeyEvent { scancode, pressed } => {
             tracing::trace!("SPICE key event: scancode={scancode:#x}, pressed={pressed}");
@@ -558,14 +761,14 @@ fn handle_command(cmd: &SpiceClientCommand, event_tx: &std::sync::mpsc::Sender<S
         }
         SpiceClientCommand::ClipboardText(text) => {
             tracing::trace!("SPICE clipboard text: {} chars", text.len());
-            // Clipboard not yet implemented in spice-client crate
-            let _ = event_tx.send(SpiceClientEvent::ServerMessage(
-                "Clipboard sharing not available in native mode".to_string(),
-            ));
+            send_clipboard(event_tx, session);
         }
         SpiceClientCommand::RefreshScreen => {
             tracing::trace!("SPICE screen refresh requested");
         }
+        SpiceClientCommand::CertificateDecision(decision) => {
+            tracing::debug!("SPICE certificate decision without a pending prompt: {decision:?}");
+        }
         SpiceClientCommand::Authenticate { .. } => {
             tracing::debug!("SPICE authentication provided");
         }
@@ -576,14 +779,39 @@ fn handle_command(cmd: &SpiceClientCommand, event_tx: &std::sync::mpsc::Sender<S
                 "USB redirection not available in native mode".to_string(),
             ));
         }
-        SpiceClientCommand::RedirectUsbDevice { device_id } => {
-            tracing::debug!("SPICE redirect USB device: {device_id}");
+        SpiceClientCommand::RedirectUsbDevice {
+            vendor_id,
+            product_id,
+            redirect,
+        } => {
+            let device = SpiceUsbDeviceId::new(*vendor_id, *product_id);
+            tracing::debug!("SPICE redirect USB device {device}: {redirect}");
+            session.usb_devices.retain(|d| *d != device);
+            if *redirect {
+                session.usb_devices.push(device);
+            }
+            // USB not yet implemented in spice-client crate
+            let _ = event_tx.send(SpiceClientEvent::ServerMessage(
+                "USB redirection not available in native mode".to_string(),
+            ));
+            send_usb_device_list(event_tx, &session.usb_devices);
         }
-        SpiceClientCommand::UnredirectUsbDevice { device_id } => {
-            tracing::debug!("SPICE unredirect USB device: {device_id}");
+        SpiceClientCommand::ListUsbDevices => {
+            send_usb_device_list(event_tx, &session.usb_devices);
         }
         SpiceClientCommand::SetClipboardEnabled { enabled } => {
             tracing::debug!("SPICE clipboard enabled: {enabled}");
+            session.clipboard_enabled = *enabled;
+        }
+        SpiceClientCommand::AddSharedFolder(folder) => {
+            add_shared_folder(folder, event_tx);
+        }
+        SpiceClientCommand::RemoveSharedFolder { share_name } => {
+            // Nothing can have been shared, see `add_shared_folder`
+            let _ = event_tx.send(SpiceClientEvent::Error(
+                SpiceClientError::SharedFolderError(format!("Share '{share_name}' not found"))
+                    .to_string(),
+            ));
         }
         SpiceClientCommand::Disconnect => {
             tracing::debug!("SPICE disconnect requested");
@@ -591,6 +819,54 @@ fn handle_command(cmd: &SpiceClientCommand, event_tx: &std::sync::mpsc::Sender<S
     }
 }
 
+/// Session state changed by commands while connected
+#[derive(Debug, Default)]
+struct SessionState {
+    /// USB devices selected for redirection
+    usb_devices: Vec<SpiceUsbDeviceId>,
+    /// Whether local clipboard contents may be sent to the guest
+    clipboard_enabled: bool,
+}
+
+/// Handles local clipboard contents if sharing is enabled
+///
+/// spice-client 0.2.0 has no agent channel, so enabled sharing only reports
+/// that the clipboard is unavailable in native mode.
+fn send_clipboard(event_tx: &std::sync::mpsc::Sender<SpiceClientEvent>, session: &SessionState) {
+    if !session.clipboard_enabled {
+        tracing::trace!("SPICE clipboard sharing disabled, dropping local clipboard");
+        return;
+    }
+    // Agent clipboard not yet implemented in spice-client crate
+    let _ = event_tx.send(SpiceClientEvent::ServerMessage(
+        "Clipboard sharing not available in native mode".to_string(),
+    ));
+}
+
+/// Sends the local USB devices, marking the ones selected for redirection
+fn send_usb_device_list(
+    event_tx: &std::sync::mpsc::Sender<SpiceClientEvent>,
+    usb_devices: &[SpiceUsbDeviceId],
+) {
+    let devices = super::enumerate_usb_devices(usb_devices);
+    let _ = event_tx.send(SpiceClientEvent::UsbDeviceList(devices));
+}
+
+/// Handles a request to share a folder while connected
+///
+/// spice-client 0.2.0 has no webdav channel, so a valid folder is reported
+/// as unsupported instead of being confirmed with `SharedFolderAdded`.
+fn add_shared_folder(
+    folder: &SpiceSharedFolder,
+    event_tx: &std::sync::mpsc::Sender<SpiceClientEvent>,
+) {
+    let error = folder.validate().err().unwrap_or_else(|| {
+        // Webdav not yet implemented in spice-client crate
+        SpiceClientError::Unsupported("folder sharing in native mode".to_string())
+    });
+    let _ = event_tx.send(SpiceClientEvent::Error(error.to_string()));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -620,6 +896,105 @@ mod tests {
         assert!(matches!(result, Err(SpiceClientError::NotConnected)));
     }
 
+    #[test]
+    fn test_add_shared_folder_rejects_missing_path() {
+        let config = SpiceClientConfig::new("localhost");
+        let client = SpiceClient::new(config);
+        let result = client.add_shared_folder(SpiceSharedFolder::new("/nonexistent/dir", "Share"));
+        assert!(matches!(
+            result,
+            Err(SpiceClientError::SharedFolderError(_))
+        ));
+    }
+
+    #[test]
+    fn test_redirect_usb_device_toggles_selection() {
+        let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let mut session = SessionState::default();
+        let redirect = |redirect| SpiceClientCommand::RedirectUsbDevice {
+            vendor_id: 0x1050,
+            product_id: 0x0407,
+            redirect,
+        };
+
+        handle_command(&redirect(true), &event_tx, &mut session);
+        handle_command(&redirect(true), &event_tx, &mut session);
+        assert_eq!(
+            session.usb_devices,
+            vec![SpiceUsbDeviceId::new(0x1050, 0x0407)]
+        );
+
+        handle_command(&redirect(false), &event_tx, &mut session);
+        assert!(session.usb_devices.is_empty());
+        assert!(event_rx
+            .try_iter()
+            .any(|e| matches!(e, SpiceClientEvent::UsbDeviceList(_))));
+    }
+
+    #[test]
+    fn test_clipboard_respects_toggle() {
+        let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let mut session = SessionState::default();
+        let text = SpiceClientCommand::ClipboardText("hello".to_string());
+
+        handle_command(&text, &event_tx, &mut session);
+        assert!(event_rx.try_recv().is_err());
+
+        handle_command(
+            &SpiceClientCommand::SetClipboardEnabled { enabled: true },
+            &event_tx,
+            &mut session,
+        );
+        handle_command(&text, &event_tx, &mut session);
+        assert!(event_rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_shared_folder_hot_plug_is_unsupported() {
+        let dir = tempfile::tempdir().unwrap();
+        let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let mut session = SessionState::default();
+
+        handle_command(
+            &SpiceClientCommand::AddSharedFolder(SpiceSharedFolder::new(dir.path(), "Transfer")),
+            &event_tx,
+            &mut session,
+        );
+        handle_command(
+            &SpiceClientCommand::AddSharedFolder(SpiceSharedFolder::new(
+                dir.path().join("missing"),
+                "Missing",
+            )),
+            &event_tx,
+            &mut session,
+        );
+        handle_command(
+            &SpiceClientCommand::RemoveSharedFolder {
+                share_name: "Transfer".to_string(),
+            },
+            &event_tx,
+            &mut session,
+        );
+
+        let events: Vec<_> = event_rx.try_iter().collect();
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            SpiceClientEvent::SharedFolderAdded { .. }
+                | SpiceClientEvent::SharedFolderRemoved { .. }
+        )));
+        let errors: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                SpiceClientEvent::Error(msg) => Some(msg.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("Unsupported feature"));
+        assert!(errors[1].starts_with("Shared folder error"));
+        assert!(errors[2].contains("not found"));
+    }
+
     #[test]
     fn test_spice_client_double_connect() {
         let config = SpiceClientConfig::new("localhost");
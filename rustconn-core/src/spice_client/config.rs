@@ -3,6 +3,7 @@
 // Allow struct with multiple bools - SPICE has many boolean options
 #![allow(clippy::struct_excessive_bools)]
 
+use super::{SpiceClientError, SpiceUsbDeviceId};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -34,12 +35,28 @@ pub struct SpiceClientConfig {
     /// Skip TLS certificate verification (insecure)
     pub skip_cert_verify: bool,
 
+    /// SHA-256 fingerprint the server certificate must match
+    ///
+    /// Enforced by the embedded client only; external viewers verify
+    /// against `ca_cert_path`.
+    #[serde(default)]
+    pub cert_fingerprint: Option<String>,
+
     /// Enable clipboard sharing
     pub clipboard_enabled: bool,
 
+    /// Let remote-viewer resize the guest to match its window
+    ///
+    /// Passed as `--auto-resize`; the native client cannot resize the guest.
+    #[serde(default = "default_true")]
+    pub resize_guest: bool,
+
     /// Enable USB redirection
     pub usb_redirection: bool,
 
+    /// Devices to redirect; all non-HID devices when empty
+    pub usb_devices: Vec<SpiceUsbDeviceId>,
+
     /// Shared folders for webdav
     pub shared_folders: Vec<SpiceSharedFolder>,
 
@@ -117,6 +134,31 @@ impl SpiceSharedFolder {
         self.read_only = read_only;
         self
     }
+
+    /// Validates the folder before it is shared
+    ///
+    /// # Errors
+    ///
+    /// Returns `SpiceClientError::SharedFolderError` if the share name is
+    /// empty or the local path is not an existing directory.
+    pub fn validate(&self) -> Result<(), SpiceClientError> {
+        if self.share_name.is_empty() {
+            return Err(SpiceClientError::SharedFolderError(
+                "Shared folder name cannot be empty".to_string(),
+            ));
+        }
+        if !self.local_path.is_dir() {
+            return Err(SpiceClientError::SharedFolderError(format!(
+                "'{}' is not an existing directory",
+                self.local_path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+const fn default_true() -> bool {
+    true
 }
 
 impl Default for SpiceClientConfig {
@@ -130,8 +172,11 @@ impl Default for SpiceClientConfig {
             tls_enabled: false,
             ca_cert_path: None,
             skip_cert_verify: false,
+            cert_fingerprint: None,
             clipboard_enabled: true,
+            resize_guest: true,
             usb_redirection: false,
+            usb_devices: Vec::new(),
             shared_folders: Vec::new(),
             image_compression: SpiceImageCompression::default(),
             audio_playback: true,
@@ -195,6 +240,13 @@ impl SpiceClientConfig {
         self
     }
 
+    /// Pins the server certificate to a SHA-256 fingerprint
+    #[must_use]
+    pub fn with_cert_fingerprint(mut self, fingerprint: impl Into<String>) -> Self {
+        self.cert_fingerprint = Some(fingerprint.into());
+        self
+    }
+
     /// Enables or disables clipboard sharing
     #[must_use]
     pub const fn with_clipboard(mut self, enabled: bool) -> Self {
@@ -202,6 +254,13 @@ impl SpiceClientConfig {
         self
     }
 
+    /// Enables or disables resizing the guest to match the remote-viewer window
+    #[must_use]
+    pub const fn with_resize_guest(mut self, enabled: bool) -> Self {
+        self.resize_guest = enabled;
+        self
+    }
+
     /// Enables or disables USB redirection
     #[must_use]
     pub const fn with_usb_redirection(mut self, enabled: bool) -> Self {
@@ -209,6 +268,15 @@ impl SpiceClientConfig {
         self
     }
 
+    /// Limits USB redirection to a device, in addition to any already added
+    #[must_use]
+    pub fn with_usb_device(mut self, device: SpiceUsbDeviceId) -> Self {
+        if !self.usb_devices.contains(&device) {
+            self.usb_devices.push(device);
+        }
+        self
+    }
+
     /// Adds a shared folder
     #[must_use]
     pub fn with_shared_folder(mut self, folder: SpiceSharedFolder) -> Self {
@@ -251,10 +319,14 @@ impl SpiceClientConfig {
         self
     }
 
-    /// Returns the server address as "host:port"
+    /// Returns the server address as "host:port", bracketing IPv6 literals
     #[must_use]
     pub fn server_address(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+        format!(
+            "{}:{}",
+            crate::connection::host_for_uri(&self.host),
+            self.port
+        )
     }
 
     /// Validates the configuration
@@ -269,9 +341,19 @@ impl SpiceClientConfig {
         if self.port == 0 {
             return Err("Port cannot be 0".to_string());
         }
-        if self.tls_enabled && !self.skip_cert_verify && self.ca_cert_path.is_none() {
+        if let Some(ref fingerprint) = self.cert_fingerprint {
+            if super::normalize_fingerprint(fingerprint).is_none() {
+                return Err(format!("Invalid certificate fingerprint '{fingerprint}'"));
+            }
+        }
+        if self.tls_enabled
+            && !self.skip_cert_verify
+            && self.ca_cert_path.is_none()
+            && self.cert_fingerprint.is_none()
+        {
             return Err(
-                "TLS enabled but no CA certificate provided and skip_cert_verify is false"
+                "TLS enabled but no CA certificate or fingerprint provided and \
+                 skip_cert_verify is false"
                     .to_string(),
             );
         }
@@ -317,6 +399,9 @@ mod tests {
     fn test_server_address() {
         let config = SpiceClientConfig::new("localhost").with_port(5900);
         assert_eq!(config.server_address(), "localhost:5900");
+
+        let config = SpiceClientConfig::new("fe80::1").with_port(5900);
+        assert_eq!(config.server_address(), "[fe80::1]:5900");
     }
 
     #[test]
@@ -324,6 +409,7 @@ mod tests {
         let config = SpiceClientConfig::default();
         assert_eq!(config.port, 5900);
         assert!(config.clipboard_enabled);
+        assert!(config.resize_guest);
         assert!(!config.usb_redirection);
         assert!(config.audio_playback);
         assert!(!config.audio_record);
@@ -379,6 +465,18 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_validate_tls_with_fingerprint() {
+        let fingerprint = "ab".repeat(32);
+        let config = SpiceClientConfig::new("localhost")
+            .with_tls(true)
+            .with_cert_fingerprint(&fingerprint);
+        assert!(config.validate().is_ok());
+
+        let config = config.with_cert_fingerprint("ab:cd");
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_validate_empty_shared_folder_name() {
         let folder = SpiceSharedFolder::new("/tmp", "");
@@ -401,4 +499,23 @@ mod tests {
             SpiceImageCompression::Auto
         );
     }
+
+    #[test]
+    fn test_shared_folder_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(SpiceSharedFolder::new(dir.path(), "Share")
+            .validate()
+            .is_ok());
+        assert!(SpiceSharedFolder::new(dir.path(), "").validate().is_err());
+
+        let file = dir.path().join("file.txt");
+        std::fs::write(&file, "data").unwrap();
+        assert!(matches!(
+            SpiceSharedFolder::new(&file, "Share").validate(),
+            Err(SpiceClientError::SharedFolderError(_))
+        ));
+        assert!(SpiceSharedFolder::new(dir.path().join("missing"), "Share")
+            .validate()
+            .is_err());
+    }
 }
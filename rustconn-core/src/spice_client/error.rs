@@ -25,6 +25,29 @@ pub enum SpiceClientError {
     #[error("TLS error: {0}")]
     TlsError(String),
 
+    /// Server certificate does not match the pinned fingerprint
+    #[error(
+        "Server certificate for {host} does not match the pinned fingerprint \
+         (expected {expected}, got {actual})"
+    )]
+    CertificateMismatch {
+        /// Server hostname
+        host: String,
+        /// Pinned fingerprint
+        expected: String,
+        /// Fingerprint of the presented certificate
+        actual: String,
+    },
+
+    /// The user rejected the server certificate
+    #[error("Server certificate {fingerprint} for {host} was rejected")]
+    CertificateRejected {
+        /// Server hostname
+        host: String,
+        /// Fingerprint of the rejected certificate
+        fingerprint: String,
+    },
+
     /// Client is not connected
     #[error("Not connected")]
     NotConnected,
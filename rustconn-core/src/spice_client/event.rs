@@ -3,6 +3,8 @@
 //! This module provides event and command types for the SPICE client,
 //! following the same pattern as VNC and RDP clients.
 
+use super::{SpiceCertDecision, SpiceCertificateMismatch, SpiceSharedFolder, SpiceUsbDevice};
+
 /// Rectangle coordinates for SPICE operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SpiceRect {
@@ -128,6 +130,18 @@ pub enum SpiceClientEvent {
     /// Authentication required
     AuthRequired,
 
+    /// Server certificate does not match the pinned fingerprint
+    ///
+    /// The connection waits for a `CertificateDecision` command.
+    CertificateMismatch(SpiceCertificateMismatch),
+
+    /// The user chose to always trust a certificate; store the fingerprint
+    /// as the connection's new pin
+    CertificatePinned {
+        /// Fingerprint of the trusted certificate
+        fingerprint: String,
+    },
+
     /// Error occurred
     Error(String),
 
@@ -159,6 +173,21 @@ pub enum SpiceClientEvent {
         /// Device ID
         device_id: u32,
     },
+
+    /// Local USB devices and whether each one is redirected
+    UsbDeviceList(Vec<SpiceUsbDevice>),
+
+    /// Shared folder accepted by the server
+    SharedFolderAdded {
+        /// Name visible to the remote system
+        share_name: String,
+    },
+
+    /// Shared folder removed from the session
+    SharedFolderRemoved {
+        /// Name visible to the remote system
+        share_name: String,
+    },
 }
 
 /// SPICE channel types
@@ -252,6 +281,9 @@ pub enum SpiceClientCommand {
     /// Send Ctrl+Alt+Del key sequence
     SendCtrlAltDel,
 
+    /// Answer a `CertificateMismatch` event
+    CertificateDecision(SpiceCertDecision),
+
     /// Provide authentication credentials
     Authenticate {
         /// Password
@@ -264,23 +296,33 @@ pub enum SpiceClientCommand {
         enabled: bool,
     },
 
-    /// Redirect a specific USB device
+    /// Start or stop redirecting a USB device model
     RedirectUsbDevice {
-        /// Device ID
-        device_id: u32,
+        /// USB vendor ID
+        vendor_id: u16,
+        /// USB product ID
+        product_id: u16,
+        /// Redirect (true) or stop redirecting (false)
+        redirect: bool,
     },
 
-    /// Stop redirecting a USB device
-    UnredirectUsbDevice {
-        /// Device ID
-        device_id: u32,
-    },
+    /// Request the list of local USB devices
+    ListUsbDevices,
 
     /// Enable/disable clipboard sharing
     SetClipboardEnabled {
         /// Enable or disable
         enabled: bool,
     },
+
+    /// Share a local folder while connected
+    AddSharedFolder(SpiceSharedFolder),
+
+    /// Stop sharing a folder
+    RemoveSharedFolder {
+        /// Name of the share to remove
+        share_name: String,
+    },
 }
 
 #[cfg(test)]
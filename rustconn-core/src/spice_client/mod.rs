@@ -37,6 +37,10 @@ mod client;
 mod config;
 mod error;
 mod event;
+mod tls;
+#[cfg(feature = "spice-embedded")]
+mod tls_relay;
+mod usb;
 
 #[cfg(feature = "spice-embedded")]
 pub use client::{SpiceClient, SpiceClientState, SpiceCommandSender, SpiceEventReceiver};
@@ -46,6 +50,11 @@ pub use config::{
 };
 pub use error::SpiceClientError;
 pub use event::{SpiceClientCommand, SpiceClientEvent, SpiceRect};
+pub use tls::{
+    certificate_fingerprint, normalize_fingerprint, verify_certificate, SpiceCertDecision,
+    SpiceCertificateMismatch,
+};
+pub use usb::{enumerate_usb_devices, usbredir_filter, SpiceUsbDevice, SpiceUsbDeviceId};
 
 /// Check if embedded SPICE support is available
 ///
@@ -96,10 +105,11 @@ pub fn build_spice_viewer_args(config: &SpiceClientConfig) -> Vec<String> {
     let mut args = Vec::new();
 
     // Connection URI: spice://host:port
+    let host = crate::connection::host_for_uri(&config.host);
     let uri = if config.tls_enabled {
-        format!("spice+tls://{}:{}", config.host, config.port)
+        format!("spice+tls://{host}:{}", config.port)
     } else {
-        format!("spice://{}:{}", config.host, config.port)
+        format!("spice://{host}:{}", config.port)
     };
     args.push(uri);
 
@@ -110,10 +120,10 @@ pub fn build_spice_viewer_args(config: &SpiceClientConfig) -> Vec<String> {
     args.push("--title".to_string());
     args.push(format!("SPICE: {}", config.host));
 
-    // USB redirection
+    // USB redirection, limited to the selected devices if any
     if config.usb_redirection {
         args.push("--spice-usbredir-auto-redirect-filter".to_string());
-        args.push("0x03,-1,-1,-1,0|-1,-1,-1,-1,1".to_string());
+        args.push(usbredir_filter(&config.usb_devices));
     }
 
     // Shared folders (webdav)
@@ -137,6 +147,15 @@ pub fn build_spice_viewer_args(config: &SpiceClientConfig) -> Vec<String> {
         }
     }
 
+    // Let the guest agent follow the window size, or keep the guest
+    // resolution fixed. remote-viewer has no switch for the agent clipboard,
+    // so `clipboard_enabled` only applies to the embedded client.
+    args.push(if config.resize_guest {
+        "--auto-resize=always".to_string()
+    } else {
+        "--auto-resize=never".to_string()
+    });
+
     // Disable audio if not wanted
     if !config.audio_playback {
         args.push("--spice-disable-audio".to_string());
@@ -220,6 +239,14 @@ mod tests {
         assert!(args.contains(&"spice+tls://secure.example.com:5901".to_string()));
     }
 
+    #[test]
+    fn test_build_spice_viewer_args_ipv6_host() {
+        let config = SpiceClientConfig::new("fe80::1").with_port(5900);
+        let args = build_spice_viewer_args(&config);
+
+        assert!(args.contains(&"spice://[fe80::1]:5900".to_string()));
+    }
+
     #[test]
     fn test_build_spice_viewer_args_with_usb() {
         let config = SpiceClientConfig::new("localhost").with_usb_redirection(true);
@@ -228,6 +255,20 @@ mod tests {
         assert!(args.contains(&"--spice-usbredir-auto-redirect-filter".to_string()));
     }
 
+    #[test]
+    fn test_build_spice_viewer_args_with_selected_usb_device() {
+        let config = SpiceClientConfig::new("localhost")
+            .with_usb_redirection(true)
+            .with_usb_device(SpiceUsbDeviceId::new(0x1050, 0x0407));
+        let args = build_spice_viewer_args(&config);
+
+        let filter = args
+            .iter()
+            .position(|a| a == "--spice-usbredir-auto-redirect-filter")
+            .map(|i| args[i + 1].as_str());
+        assert_eq!(filter, Some("-1,0x1050,0x0407,-1,1|-1,-1,-1,-1,0"));
+    }
+
     #[test]
     fn test_build_spice_viewer_args_with_shared_folder() {
         let folder = SpiceSharedFolder::new("/home/user/share", "MyShare");
@@ -246,6 +287,16 @@ mod tests {
         assert!(args.contains(&"--spice-disable-audio".to_string()));
     }
 
+    #[test]
+    fn test_build_spice_viewer_args_resize_guest() {
+        let args = build_spice_viewer_args(&SpiceClientConfig::new("localhost"));
+        assert!(args.contains(&"--auto-resize=always".to_string()));
+
+        let config = SpiceClientConfig::new("localhost").with_resize_guest(false);
+        let args = build_spice_viewer_args(&config);
+        assert!(args.contains(&"--auto-resize=never".to_string()));
+    }
+
     #[test]
     fn test_build_spice_viewer_args_with_ca_cert() {
         let config = SpiceClientConfig::new("localhost")
@@ -0,0 +1,213 @@
+//! TLS certificate pinning for SPICE connections
+//!
+//! Self-signed hosts such as oVirt or Proxmox nodes are trusted by pinning
+//! the SHA-256 fingerprint of their certificate. When the server presents a
+//! different certificate, the embedded client reports it with
+//! `SpiceClientEvent::CertificateMismatch` and waits for a
+//! `SpiceClientCommand::CertificateDecision`. Without a prompt handler the
+//! connection fails instead. Every further channel of the session must then
+//! present the same certificate, or it is refused.
+
+use super::{SpiceClientConfig, SpiceClientError};
+use ring::digest;
+
+/// Answer to a certificate mismatch prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpiceCertDecision {
+    /// Refuse the certificate and abort the connection
+    Reject,
+    /// Accept the certificate for this connection only
+    TrustOnce,
+    /// Accept the certificate and pin it for future connections
+    TrustAlways,
+}
+
+/// A server certificate that does not match the pinned fingerprint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpiceCertificateMismatch {
+    /// Server hostname
+    pub host: String,
+    /// Server TLS port
+    pub port: u16,
+    /// Fingerprint of the certificate the server presented
+    pub fingerprint: String,
+    /// Fingerprint the connection is pinned to
+    pub pinned: String,
+}
+
+/// Returns the SHA-256 fingerprint of a DER certificate as colon-separated
+/// uppercase hex, the format `openssl x509 -fingerprint -sha256` prints
+#[must_use]
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+    digest::digest(&digest::SHA256, der)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Normalizes a SHA-256 fingerprint to colon-separated uppercase hex
+///
+/// Accepts an optional `sha256:` or `SHA256 Fingerprint=` prefix and hex
+/// digits in either case, separated by colons, spaces or nothing. Returns
+/// `None` if the value is not 32 bytes of hex.
+#[must_use]
+pub fn normalize_fingerprint(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value
+        .split_once('=')
+        .or_else(|| value.split_once("sha256:"))
+        .or_else(|| value.split_once("SHA256:"))
+        .map_or(value, |(_, hex)| hex);
+    let hex: String = value
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_uppercase();
+    Some(
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).into_owned())
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
+/// Checks a server certificate against the pinned fingerprint
+///
+/// Connections without a pin accept any certificate here; CA verification
+/// is left to the TLS layer. On a mismatch `prompt` decides, and a
+/// certificate the user trusts always is returned so the caller can store
+/// it as the new pin.
+///
+/// # Errors
+///
+/// Returns `SpiceClientError::CertificateMismatch` on a mismatch without a
+/// prompt, `SpiceClientError::CertificateRejected` if the prompt rejects the
+/// certificate, and `SpiceClientError::InvalidConfig` if the pin is not a
+/// valid fingerprint.
+pub fn verify_certificate(
+    config: &SpiceClientConfig,
+    der: &[u8],
+    prompt: Option<&mut dyn FnMut(&SpiceCertificateMismatch) -> SpiceCertDecision>,
+) -> Result<Option<String>, SpiceClientError> {
+    let Some(pin) = config.cert_fingerprint.as_deref() else {
+        return Ok(None);
+    };
+    let pinned = normalize_fingerprint(pin).ok_or_else(|| {
+        SpiceClientError::InvalidConfig(format!("Invalid certificate fingerprint '{pin}'"))
+    })?;
+    let fingerprint = certificate_fingerprint(der);
+    if fingerprint == pinned {
+        return Ok(None);
+    }
+
+    let mismatch = SpiceCertificateMismatch {
+        host: config.host.clone(),
+        port: config.port,
+        fingerprint,
+        pinned,
+    };
+    let Some(prompt) = prompt else {
+        return Err(SpiceClientError::CertificateMismatch {
+            host: mismatch.host,
+            expected: mismatch.pinned,
+            actual: mismatch.fingerprint,
+        });
+    };
+    match prompt(&mismatch) {
+        SpiceCertDecision::Reject => Err(SpiceClientError::CertificateRejected {
+            host: mismatch.host,
+            fingerprint: mismatch.fingerprint,
+        }),
+        SpiceCertDecision::TrustOnce => Ok(None),
+        SpiceCertDecision::TrustAlways => Ok(Some(mismatch.fingerprint)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERT: &[u8] = b"not really DER, but hashed all the same";
+
+    fn pinned_config(pin: &str) -> SpiceClientConfig {
+        SpiceClientConfig::new("pve.example.com")
+            .with_tls(true)
+            .with_cert_fingerprint(pin)
+    }
+
+    #[test]
+    fn test_normalize_fingerprint() {
+        let fingerprint = certificate_fingerprint(CERT);
+        assert_eq!(fingerprint.len(), 95);
+        let bare = fingerprint.replace(':', "").to_lowercase();
+        assert_eq!(normalize_fingerprint(&bare), Some(fingerprint.clone()));
+        assert_eq!(
+            normalize_fingerprint(&format!("sha256:{bare}")),
+            Some(fingerprint.clone())
+        );
+        assert_eq!(
+            normalize_fingerprint(&format!("SHA256 Fingerprint={fingerprint}")),
+            Some(fingerprint)
+        );
+        assert_eq!(normalize_fingerprint("AB:CD"), None);
+        assert_eq!(normalize_fingerprint(&"zz".repeat(32)), None);
+    }
+
+    #[test]
+    fn test_verify_certificate_pin() {
+        let fingerprint = certificate_fingerprint(CERT);
+        let config = pinned_config(&fingerprint.to_lowercase());
+        assert_eq!(verify_certificate(&config, CERT, None).unwrap(), None);
+
+        let unpinned = SpiceClientConfig::new("pve.example.com").with_tls(true);
+        assert_eq!(verify_certificate(&unpinned, CERT, None).unwrap(), None);
+
+        let invalid = pinned_config("not-a-fingerprint");
+        assert!(matches!(
+            verify_certificate(&invalid, CERT, None),
+            Err(SpiceClientError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_certificate_mismatch() {
+        let fingerprint = certificate_fingerprint(CERT);
+        let config = pinned_config(&certificate_fingerprint(b"old certificate"));
+
+        let err = verify_certificate(&config, CERT, None).unwrap_err();
+        assert!(matches!(
+            err,
+            SpiceClientError::CertificateMismatch { ref actual, .. } if *actual == fingerprint
+        ));
+        assert!(err.to_string().contains("pve.example.com"), "{err}");
+
+        let answer = |decision| {
+            move |mismatch: &SpiceCertificateMismatch| {
+                assert_eq!(mismatch.port, 5900);
+                decision
+            }
+        };
+        let mut trust_once = answer(SpiceCertDecision::TrustOnce);
+        assert_eq!(
+            verify_certificate(&config, CERT, Some(&mut trust_once)).unwrap(),
+            None
+        );
+        let mut trust_always = answer(SpiceCertDecision::TrustAlways);
+        assert_eq!(
+            verify_certificate(&config, CERT, Some(&mut trust_always)).unwrap(),
+            Some(fingerprint)
+        );
+
+        let mut reject = answer(SpiceCertDecision::Reject);
+        assert!(matches!(
+            verify_certificate(&config, CERT, Some(&mut reject)),
+            Err(SpiceClientError::CertificateRejected { .. })
+        ));
+    }
+}
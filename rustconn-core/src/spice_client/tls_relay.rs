@@ -0,0 +1,215 @@
+//! Loopback TLS relay for the native SPICE client
+//!
+//! The `spice-client` crate only speaks plain TCP. For TLS connections the
+//! embedded client connects to a relay on the loopback interface instead,
+//! and every channel it opens is forwarded over its own TLS connection to
+//! the server. The certificate of each of those connections is checked, so
+//! the pinned fingerprint is verified on the streams the session uses.
+//!
+//! The relay only forwards connections opened by the current user and stops
+//! listening once the client has opened its channels.
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use super::{certificate_fingerprint, SpiceClientConfig, SpiceClientError};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_native_tls::native_tls;
+use tokio_native_tls::{TlsConnector, TlsStream};
+
+/// A TLS connection to the server together with its certificate in DER form
+pub(super) type UpstreamConnection = (TlsStream<TcpStream>, Vec<u8>);
+
+fn tls_error(err: native_tls::Error) -> SpiceClientError {
+    SpiceClientError::TlsError(err.to_string())
+}
+
+/// Builds the connector used for every channel of a session
+///
+/// A pinned fingerprint replaces CA verification, so pinned and
+/// `skip_cert_verify` connections accept any certificate at the TLS layer.
+/// Otherwise the system roots and `ca_cert_path` are trusted.
+///
+/// # Errors
+///
+/// Returns `SpiceClientError::TlsError` if the CA file cannot be read or
+/// parsed.
+pub(super) fn build_connector(
+    config: &SpiceClientConfig,
+) -> Result<TlsConnector, SpiceClientError> {
+    let mut builder = native_tls::TlsConnector::builder();
+    if config.cert_fingerprint.is_some() || config.skip_cert_verify {
+        builder
+            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true);
+    } else if let Some(ref ca_path) = config.ca_cert_path {
+        let pem = std::fs::read(ca_path).map_err(|e| {
+            SpiceClientError::TlsError(format!("Cannot read CA file {}: {e}", ca_path.display()))
+        })?;
+        builder.add_root_certificate(native_tls::Certificate::from_pem(&pem).map_err(tls_error)?);
+    }
+    Ok(TlsConnector::from(builder.build().map_err(tls_error)?))
+}
+
+/// Opens one TLS connection to the server
+///
+/// # Errors
+///
+/// Returns an error if the TCP connection or TLS handshake fails, or the
+/// server sends no certificate.
+pub(super) async fn connect_upstream(
+    connector: &TlsConnector,
+    host: &str,
+    port: u16,
+) -> Result<UpstreamConnection, SpiceClientError> {
+    let tcp = TcpStream::connect((host, port)).await?;
+    let stream = connector.connect(host, tcp).await.map_err(tls_error)?;
+    let der = stream
+        .get_ref()
+        .peer_certificate()
+        .map_err(tls_error)?
+        .ok_or_else(|| SpiceClientError::TlsError("Server sent no certificate".to_string()))?
+        .to_der()
+        .map_err(tls_error)?;
+    Ok((stream, der))
+}
+
+/// Returns whether the loopback connection from `peer` to the relay on
+/// `relay_port` was opened by the current user
+///
+/// `SO_PEERCRED` only covers Unix sockets, so the owner of the peer's socket
+/// is looked up in `/proc/net/tcp`, which lists the UID of every IPv4 TCP
+/// socket.
+fn peer_is_current_user(peer: SocketAddr, relay_port: u16) -> bool {
+    use std::os::unix::fs::MetadataExt as _;
+
+    let SocketAddr::V4(peer) = peer else {
+        return false;
+    };
+    let Ok(uid) = std::fs::metadata("/proc/self").map(|m| m.uid()) else {
+        return false;
+    };
+    let Ok(table) = std::fs::read_to_string("/proc/net/tcp") else {
+        return false;
+    };
+    // The kernel prints addresses in host byte order and ports in hex
+    let local = format!(
+        "{:08X}:{:04X}",
+        u32::from_ne_bytes(peer.ip().octets()),
+        peer.port()
+    );
+    let remote = format!(
+        "{:08X}:{:04X}",
+        u32::from_ne_bytes(Ipv4Addr::LOCALHOST.octets()),
+        relay_port
+    );
+    table.lines().skip(1).any(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        fields.get(1) == Some(&local.as_str())
+            && fields.get(2) == Some(&remote.as_str())
+            && fields.get(7).and_then(|f| f.parse::<u32>().ok()) == Some(uid)
+    })
+}
+
+/// Relay listening on the loopback interface; stops when dropped
+pub(super) struct TlsRelay {
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+impl TlsRelay {
+    /// Starts the relay
+    ///
+    /// `first` is the already verified connection and serves the first
+    /// channel. Later channels open new connections, which are refused
+    /// unless their certificate has the `accepted` fingerprint. Loopback
+    /// connections from other users are refused.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the loopback listener cannot be bound.
+    pub(super) async fn start(
+        first: TlsStream<TcpStream>,
+        connector: TlsConnector,
+        host: String,
+        port: u16,
+        accepted: Option<String>,
+    ) -> Result<Self, SpiceClientError> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let local_port = listener.local_addr()?.port();
+
+        let task = tokio::spawn(async move {
+            let mut first = Some(first);
+            while let Ok((mut local, peer)) = listener.accept().await {
+                if !peer_is_current_user(peer, local_port) {
+                    tracing::warn!("Refusing SPICE relay connection from {peer} of another user");
+                    continue;
+                }
+                let mut upstream = if let Some(stream) = first.take() {
+                    stream
+                } else {
+                    match connect_upstream(&connector, &host, port).await {
+                        Ok((stream, der)) => {
+                            let fingerprint = certificate_fingerprint(&der);
+                            if accepted.as_ref().is_some_and(|a| *a != fingerprint) {
+                                tracing::warn!(
+                                    "SPICE channel to {host}:{port} presented certificate \
+                                     {fingerprint}, refusing it"
+                                );
+                                continue;
+                            }
+                            stream
+                        }
+                        Err(e) => {
+                            tracing::warn!("SPICE channel to {host}:{port} failed: {e}");
+                            continue;
+                        }
+                    }
+                };
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy_bidirectional(&mut local, &mut upstream).await;
+                });
+            }
+        });
+
+        Ok(Self {
+            port: local_port,
+            task,
+        })
+    }
+
+    /// Returns the loopback port the native client connects to
+    pub(super) const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Closes the listener once the client has opened its channels
+    ///
+    /// Channels already relayed keep running; new connections to the port
+    /// are refused.
+    pub(super) fn stop_listening(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for TlsRelay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_is_current_user() {
+        let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let _client = std::net::TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        let (_server, peer) = listener.accept().unwrap();
+
+        assert!(peer_is_current_user(peer, port));
+        assert!(!peer_is_current_user(peer, port.wrapping_add(1)));
+    }
+}
@@ -0,0 +1,184 @@
+//! USB device selection for SPICE redirection
+//!
+//! Lists local USB devices so the GUI can offer per-device redirection
+//! toggles, and builds the usbredir filter used by external viewers.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Location of USB devices in sysfs
+const SYSFS_USB_DEVICES: &str = "/sys/bus/usb/devices";
+
+/// USB device class code for hubs
+const USB_CLASS_HUB: &str = "09";
+
+/// Vendor and product ID identifying a USB device model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpiceUsbDeviceId {
+    /// USB vendor ID
+    pub vendor_id: u16,
+    /// USB product ID
+    pub product_id: u16,
+}
+
+impl SpiceUsbDeviceId {
+    /// Creates a new device ID
+    #[must_use]
+    pub const fn new(vendor_id: u16, product_id: u16) -> Self {
+        Self {
+            vendor_id,
+            product_id,
+        }
+    }
+}
+
+impl std::fmt::Display for SpiceUsbDeviceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vendor_id, self.product_id)
+    }
+}
+
+/// USB device available on the local machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpiceUsbDevice {
+    /// Vendor and product ID
+    pub id: SpiceUsbDeviceId,
+    /// Human-readable description (manufacturer and product)
+    pub description: String,
+    /// Whether the device is redirected to the remote session
+    pub redirected: bool,
+}
+
+/// Builds a usbredir auto-redirect filter
+///
+/// With no devices, every device except HID (keyboards, mice) is
+/// redirected. Otherwise only the listed devices are redirected.
+/// Rules use the `class,vendor,product,version,allow` format.
+#[must_use]
+pub fn usbredir_filter(devices: &[SpiceUsbDeviceId]) -> String {
+    if devices.is_empty() {
+        return "0x03,-1,-1,-1,0|-1,-1,-1,-1,1".to_string();
+    }
+
+    devices
+        .iter()
+        .map(|d| format!("-1,0x{:04x},0x{:04x},-1,1", d.vendor_id, d.product_id))
+        .chain(std::iter::once("-1,-1,-1,-1,0".to_string()))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Lists USB devices attached to the local machine
+///
+/// Hubs are skipped. Returns an empty list where sysfs is unavailable.
+/// Devices listed in `redirected` are marked as redirected.
+#[must_use]
+pub fn enumerate_usb_devices(redirected: &[SpiceUsbDeviceId]) -> Vec<SpiceUsbDevice> {
+    enumerate_usb_devices_in(Path::new(SYSFS_USB_DEVICES), redirected)
+}
+
+/// Lists USB devices below a sysfs-style directory
+fn enumerate_usb_devices_in(root: &Path, redirected: &[SpiceUsbDeviceId]) -> Vec<SpiceUsbDevice> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<SpiceUsbDevice> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().contains(':'))
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let read = |name: &str| {
+                std::fs::read_to_string(dir.join(name))
+                    .ok()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+
+            if read("bDeviceClass").as_deref() == Some(USB_CLASS_HUB) {
+                return None;
+            }
+            let vendor_id = u16::from_str_radix(&read("idVendor")?, 16).ok()?;
+            let product_id = u16::from_str_radix(&read("idProduct")?, 16).ok()?;
+            let id = SpiceUsbDeviceId::new(vendor_id, product_id);
+
+            let description = match (read("manufacturer"), read("product")) {
+                (Some(manufacturer), Some(product)) => format!("{manufacturer} {product}"),
+                (None, Some(name)) | (Some(name), None) => name,
+                (None, None) => format!("USB device {id}"),
+            };
+
+            Some(SpiceUsbDevice {
+                id,
+                description,
+                redirected: redirected.contains(&id),
+            })
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a.description.cmp(&b.description));
+    devices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_device(root: &Path, name: &str, files: &[(&str, &str)]) {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            std::fs::write(dir.join(file), format!("{content}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_usbredir_filter() {
+        assert_eq!(usbredir_filter(&[]), "0x03,-1,-1,-1,0|-1,-1,-1,-1,1");
+        assert_eq!(
+            usbredir_filter(&[SpiceUsbDeviceId::new(0x1050, 0x0407)]),
+            "-1,0x1050,0x0407,-1,1|-1,-1,-1,-1,0"
+        );
+    }
+
+    #[test]
+    fn test_enumerate_usb_devices() {
+        let root = tempfile::tempdir().unwrap();
+        write_device(
+            root.path(),
+            "usb1",
+            &[
+                ("idVendor", "1d6b"),
+                ("idProduct", "0002"),
+                ("bDeviceClass", "09"),
+            ],
+        );
+        write_device(
+            root.path(),
+            "1-2",
+            &[
+                ("idVendor", "1050"),
+                ("idProduct", "0407"),
+                ("bDeviceClass", "00"),
+                ("manufacturer", "Yubico"),
+                ("product", "YubiKey OTP+FIDO+CCID"),
+            ],
+        );
+        write_device(
+            root.path(),
+            "1-3",
+            &[("idVendor", "046d"), ("idProduct", "c52b")],
+        );
+        write_device(root.path(), "1-2:1.0", &[("bInterfaceClass", "03")]);
+
+        let key = SpiceUsbDeviceId::new(0x1050, 0x0407);
+        let devices = enumerate_usb_devices_in(root.path(), &[key]);
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].description, "USB device 046d:c52b");
+        assert!(!devices[0].redirected);
+        assert_eq!(devices[1].id, key);
+        assert_eq!(devices[1].description, "Yubico YubiKey OTP+FIDO+CCID");
+        assert!(devices[1].redirected);
+    }
+}
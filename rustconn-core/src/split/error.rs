@@ -27,6 +27,10 @@ pub enum SplitError {
     /// The specified session was not found.
     #[error("session not found: {0}")]
     SessionNotFound(SessionId),
+
+    /// A saved layout could not be serialized or parsed.
+    #[error("invalid layout: {0}")]
+    InvalidLayout(String),
 }
 
 /// Result of placing a session in a panel.
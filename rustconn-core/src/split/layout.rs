@@ -0,0 +1,221 @@
+//! Saved split layouts
+//!
+//! This module provides a serializable description of a panel tree that
+//! refers to connections instead of runtime sessions, so a layout can be
+//! saved under a name and reopened in a later run.
+//!
+//! # Example
+//!
+//! ```
+//! use rustconn_core::split::{LayoutRegistry, NamedLayout, SplitDirection, SplitLayoutModel};
+//!
+//! let mut layout = SplitLayoutModel::new();
+//! layout.split(SplitDirection::Vertical).unwrap();
+//!
+//! let mut registry = LayoutRegistry::new();
+//! registry.save(NamedLayout::new("monitoring grid", layout.to_layout(|_| None)));
+//! assert!(registry.get("monitoring grid").is_some());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::model::SplitLayoutModel;
+use super::types::{PanelId, SplitDirection};
+
+/// Serializable node of a saved panel tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutNode {
+    /// A pane, optionally showing a connection
+    Pane {
+        /// Connection displayed in this pane (None = empty pane)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        connection_id: Option<Uuid>,
+    },
+    /// Two nodes sharing space in one direction
+    Split {
+        /// Split direction
+        direction: SplitDirection,
+        /// Proportion of space given to the first child (0.0 to 1.0)
+        position: f64,
+        /// First child (top or left)
+        first: Box<Self>,
+        /// Second child (bottom or right)
+        second: Box<Self>,
+    },
+}
+
+impl LayoutNode {
+    /// Returns the number of panes in this subtree
+    #[must_use]
+    pub fn pane_count(&self) -> usize {
+        match self {
+            Self::Pane { .. } => 1,
+            Self::Split { first, second, .. } => first.pane_count() + second.pane_count(),
+        }
+    }
+
+    /// Returns the connections referenced by panes, in pane order
+    #[must_use]
+    pub fn connection_ids(&self) -> Vec<Uuid> {
+        match self {
+            Self::Pane { connection_id } => connection_id.iter().copied().collect(),
+            Self::Split { first, second, .. } => {
+                let mut ids = first.connection_ids();
+                ids.extend(second.connection_ids());
+                ids
+            }
+        }
+    }
+}
+
+/// A panel tree saved under a user-chosen name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedLayout {
+    /// Display name (e.g. "monitoring grid")
+    pub name: String,
+    /// Saved panel tree
+    pub root: LayoutNode,
+}
+
+impl NamedLayout {
+    /// Creates a named layout
+    #[must_use]
+    pub fn new(name: impl Into<String>, root: LayoutNode) -> Self {
+        Self {
+            name: name.into(),
+            root,
+        }
+    }
+}
+
+/// Registry of saved layouts, keyed by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayoutRegistry {
+    layouts: Vec<NamedLayout>,
+}
+
+impl LayoutRegistry {
+    /// Creates an empty registry
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            layouts: Vec::new(),
+        }
+    }
+
+    /// Creates a registry from previously saved layouts
+    #[must_use]
+    pub const fn from_layouts(layouts: Vec<NamedLayout>) -> Self {
+        Self { layouts }
+    }
+
+    /// Saves a layout, replacing any layout with the same name
+    pub fn save(&mut self, layout: NamedLayout) {
+        match self.layouts.iter_mut().find(|l| l.name == layout.name) {
+            Some(existing) => *existing = layout,
+            None => self.layouts.push(layout),
+        }
+    }
+
+    /// Returns the layout with the given name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&NamedLayout> {
+        self.layouts.iter().find(|l| l.name == name)
+    }
+
+    /// Removes and returns the layout with the given name
+    pub fn remove(&mut self, name: &str) -> Option<NamedLayout> {
+        let index = self.layouts.iter().position(|l| l.name == name)?;
+        Some(self.layouts.remove(index))
+    }
+
+    /// Returns the names of all saved layouts
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.layouts.iter().map(|l| l.name.as_str()).collect()
+    }
+
+    /// Returns all saved layouts
+    #[must_use]
+    pub fn layouts(&self) -> &[NamedLayout] {
+        &self.layouts
+    }
+}
+
+/// What to show in a pane of a restored layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneRestore {
+    /// Connect to this connection and show it in the pane
+    Connect(Uuid),
+    /// The saved connection no longer exists; show a placeholder
+    Missing(Uuid),
+    /// The pane was saved empty
+    Empty,
+}
+
+/// A layout rebuilt from a saved description
+#[derive(Debug, Clone)]
+pub struct RestoredLayout {
+    /// Panel tree with empty panels, ready for sessions to be placed
+    pub model: SplitLayoutModel,
+    /// What each panel should show, in pane order
+    pub panes: Vec<(PanelId, PaneRestore)>,
+}
+
+impl RestoredLayout {
+    /// Returns the panels to connect, paired with their connection
+    #[must_use]
+    pub fn connections(&self) -> Vec<(PanelId, Uuid)> {
+        self.panes
+            .iter()
+            .filter_map(|(panel_id, restore)| match restore {
+                PaneRestore::Connect(connection_id) => Some((*panel_id, *connection_id)),
+                PaneRestore::Missing(_) | PaneRestore::Empty => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_save_replaces_by_name() {
+        let pane = LayoutNode::Pane {
+            connection_id: None,
+        };
+        let mut registry = LayoutRegistry::new();
+        registry.save(NamedLayout::new("grid", pane.clone()));
+        registry.save(NamedLayout::new("grid", pane.clone()));
+        registry.save(NamedLayout::new("pair", pane));
+
+        assert_eq!(registry.names(), vec!["grid", "pair"]);
+        assert!(registry.remove("grid").is_some());
+        assert!(registry.get("grid").is_none());
+    }
+
+    #[test]
+    fn test_layout_node_json() {
+        let id = Uuid::new_v4();
+        let node = LayoutNode::Split {
+            direction: SplitDirection::Vertical,
+            position: 0.3,
+            first: Box::new(LayoutNode::Pane {
+                connection_id: Some(id),
+            }),
+            second: Box::new(LayoutNode::Pane {
+                connection_id: None,
+            }),
+        };
+
+        let json = serde_json::to_string(&node).unwrap();
+        assert!(json.contains(r#""type":"split""#));
+        assert!(json.contains(r#""direction":"Vertical""#));
+        assert_eq!(serde_json::from_str::<LayoutNode>(&json).unwrap(), node);
+        assert_eq!(node.pane_count(), 2);
+        assert_eq!(node.connection_ids(), vec![id]);
+    }
+}
@@ -16,6 +16,7 @@
 //! - `types` - Core type definitions (`PanelId`, `TabId`, `SessionId`, `ColorId`, `SplitDirection`)
 //! - `tree` - Panel tree structure (`PanelNode`, `LeafPanel`, `SplitNode`)
 //! - `model` - Split layout model (`SplitLayoutModel`)
+//! - `layout` - Saved layouts (`LayoutNode`, `NamedLayout`, `LayoutRegistry`)
 //! - `color` - Color pool management (`ColorPool`)
 //! - `error` - Error types (`SplitError`, `DropResult`)
 //!
@@ -45,12 +46,14 @@
 
 mod color;
 mod error;
+mod layout;
 mod model;
 mod tree;
 mod types;
 
 pub use color::{ColorPool, SPLIT_COLORS};
 pub use error::{DropResult, SplitError};
+pub use layout::{LayoutNode, LayoutRegistry, NamedLayout, PaneRestore, RestoredLayout};
 pub use model::SplitLayoutModel;
 pub use tree::{
     LeafPanel, PanelNode, RemoveResult, SplitNode, DEFAULT_SPLIT_POSITION, MAX_SPLIT_POSITION,
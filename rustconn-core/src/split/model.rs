@@ -28,8 +28,13 @@
 //! assert!(matches!(result, DropResult::Placed));
 //! ```
 
+use uuid::Uuid;
+
 use super::error::{DropResult, SplitError};
-use super::tree::{LeafPanel, PanelNode, RemoveResult, SplitNode};
+use super::layout::{LayoutNode, PaneRestore, RestoredLayout};
+use super::tree::{
+    LeafPanel, PanelNode, RemoveResult, SplitNode, MAX_SPLIT_POSITION, MIN_SPLIT_POSITION,
+};
 use super::types::{ColorId, PanelId, SessionId, SplitDirection};
 
 /// Manages the split layout for a single tab.
@@ -355,10 +360,199 @@ impl SplitLayoutModel {
         self.root.as_ref()
     }
 
+    /// Resizes all splits so that panels share space evenly.
+    ///
+    /// See [`PanelNode::equalize`] for how nested splits are balanced.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` without changes when there are fewer than two panels.
+    pub fn equalize(&mut self) -> bool {
+        match &mut self.root {
+            None => false,
+            Some(node) => {
+                node.equalize();
+                true
+            }
+        }
+    }
+
+    /// Exchanges the sessions shown in two panels.
+    ///
+    /// The tree structure and panel IDs are unchanged.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` without changes when there are fewer than two panels
+    /// or both IDs refer to the same panel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SplitError::PanelNotFound` if either panel doesn't exist.
+    pub fn swap_panes(&mut self, a: PanelId, b: PanelId) -> Result<bool, SplitError> {
+        let Some(node) = &mut self.root else {
+            return Ok(false);
+        };
+        let session_a = node
+            .find_panel(a)
+            .ok_or(SplitError::PanelNotFound(a))?
+            .session;
+        let session_b = node
+            .find_panel(b)
+            .ok_or(SplitError::PanelNotFound(b))?
+            .session;
+        if a == b {
+            return Ok(false);
+        }
+
+        self.find_panel_mut(a)?.session = session_b;
+        self.find_panel_mut(b)?.session = session_a;
+        Ok(true)
+    }
+
+    // ========================================================================
+    // Saved Layouts
+    // ========================================================================
+
+    /// Describes the panel tree in terms of connections.
+    ///
+    /// `connection_of` maps the session in each panel to the connection it
+    /// belongs to; panels whose session has no connection are saved empty.
+    #[must_use]
+    pub fn to_layout<F>(&self, connection_of: F) -> LayoutNode
+    where
+        F: Fn(SessionId) -> Option<Uuid>,
+    {
+        match &self.root {
+            None => Self::leaf_layout(&self.single_panel, &connection_of),
+            Some(node) => Self::node_layout(node, &connection_of),
+        }
+    }
+
+    /// Serializes the panel tree to JSON.
+    ///
+    /// See [`to_layout`](Self::to_layout) for how sessions are recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SplitError::InvalidLayout` if serialization fails.
+    pub fn serialize<F>(&self, connection_of: F) -> Result<String, SplitError>
+    where
+        F: Fn(SessionId) -> Option<Uuid>,
+    {
+        serde_json::to_string_pretty(&self.to_layout(connection_of))
+            .map_err(|e| SplitError::InvalidLayout(e.to_string()))
+    }
+
+    /// Rebuilds a layout from a saved description.
+    ///
+    /// The new model has empty panels with fresh IDs. The returned plan
+    /// lists, for every panel, the connection to open in it. Panes that
+    /// refer to a connection for which `connection_exists` returns false are
+    /// marked [`PaneRestore::Missing`] so a placeholder can be shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SplitError::InvalidPosition` if a split position is out of range.
+    pub fn from_layout<F>(
+        layout: &LayoutNode,
+        connection_exists: F,
+    ) -> Result<RestoredLayout, SplitError>
+    where
+        F: Fn(Uuid) -> bool,
+    {
+        let mut plan = Vec::new();
+        let mut model = Self::new();
+        match Self::build_node(layout, &connection_exists, &mut plan)? {
+            PanelNode::Leaf(panel) => model.single_panel = panel,
+            node @ PanelNode::Split(_) => model.root = Some(node),
+        }
+        model.focused_panel = Some(model.first_panel().id);
+        Ok(RestoredLayout { model, panes: plan })
+    }
+
+    /// Rebuilds a layout from JSON produced by [`serialize`](Self::serialize).
+    ///
+    /// # Errors
+    ///
+    /// Returns `SplitError::InvalidLayout` if the JSON is not a valid layout.
+    pub fn deserialize<F>(json: &str, connection_exists: F) -> Result<RestoredLayout, SplitError>
+    where
+        F: Fn(Uuid) -> bool,
+    {
+        let layout: LayoutNode =
+            serde_json::from_str(json).map_err(|e| SplitError::InvalidLayout(e.to_string()))?;
+        Self::from_layout(&layout, connection_exists)
+    }
+
     // ========================================================================
     // Private Helper Methods
     // ========================================================================
 
+    /// Describes a leaf panel for a saved layout.
+    fn leaf_layout<F>(panel: &LeafPanel, connection_of: &F) -> LayoutNode
+    where
+        F: Fn(SessionId) -> Option<Uuid>,
+    {
+        LayoutNode::Pane {
+            connection_id: panel.session.and_then(connection_of),
+        }
+    }
+
+    /// Describes a subtree for a saved layout.
+    fn node_layout<F>(node: &PanelNode, connection_of: &F) -> LayoutNode
+    where
+        F: Fn(SessionId) -> Option<Uuid>,
+    {
+        match node {
+            PanelNode::Leaf(panel) => Self::leaf_layout(panel, connection_of),
+            PanelNode::Split(split) => LayoutNode::Split {
+                direction: split.direction,
+                position: split.position,
+                first: Box::new(Self::node_layout(&split.first, connection_of)),
+                second: Box::new(Self::node_layout(&split.second, connection_of)),
+            },
+        }
+    }
+
+    /// Builds a subtree from a saved layout, recording each pane's plan.
+    fn build_node<F>(
+        layout: &LayoutNode,
+        connection_exists: &F,
+        plan: &mut Vec<(PanelId, PaneRestore)>,
+    ) -> Result<PanelNode, SplitError>
+    where
+        F: Fn(Uuid) -> bool,
+    {
+        match layout {
+            LayoutNode::Pane { connection_id } => {
+                let panel = LeafPanel::new();
+                let restore = match connection_id {
+                    Some(id) if connection_exists(*id) => PaneRestore::Connect(*id),
+                    Some(id) => PaneRestore::Missing(*id),
+                    None => PaneRestore::Empty,
+                };
+                plan.push((panel.id, restore));
+                Ok(PanelNode::Leaf(panel))
+            }
+            LayoutNode::Split {
+                direction,
+                position,
+                first,
+                second,
+            } => {
+                if !(MIN_SPLIT_POSITION..=MAX_SPLIT_POSITION).contains(position) {
+                    return Err(SplitError::InvalidPosition(*position));
+                }
+                let first = Self::build_node(first, connection_exists, plan)?;
+                let second = Self::build_node(second, connection_exists, plan)?;
+                Ok(PanelNode::Split(SplitNode::with_position(
+                    *direction, first, second, *position,
+                )))
+            }
+        }
+    }
+
     /// Finds a panel by ID and returns a mutable reference.
     fn find_panel_mut(&mut self, panel_id: PanelId) -> Result<&mut LeafPanel, SplitError> {
         match &mut self.root {
@@ -386,6 +580,179 @@ impl Default for SplitLayoutModel {
 mod tests {
     use super::*;
 
+    // ========================================================================
+    // Equalize and Swap Tests
+    // ========================================================================
+
+    /// Builds `A | (B / (C | D))`: a vertical split whose right side is a
+    /// horizontal split with a nested vertical split at the bottom.
+    fn nested_layout() -> (SplitLayoutModel, Vec<PanelId>) {
+        let mut layout = SplitLayoutModel::new();
+        let a = layout.first_panel().id;
+        let b = layout.split(SplitDirection::Vertical).unwrap();
+        layout.set_focus(b).unwrap();
+        let c = layout.split(SplitDirection::Horizontal).unwrap();
+        layout.set_focus(c).unwrap();
+        let d = layout.split(SplitDirection::Vertical).unwrap();
+        (layout, vec![a, b, c, d])
+    }
+
+    /// Collects `(direction, position)` for every split in pre-order.
+    fn positions(node: &PanelNode, out: &mut Vec<(SplitDirection, f64)>) {
+        if let PanelNode::Split(split) = node {
+            out.push((split.direction, split.position));
+            positions(&split.first, out);
+            positions(&split.second, out);
+        }
+    }
+
+    #[test]
+    fn equalize_balances_nested_splits() {
+        let (mut layout, _) = nested_layout();
+        if let Some(PanelNode::Split(root)) = layout.root.as_mut() {
+            root.position = 0.9;
+        }
+
+        assert!(layout.equalize());
+
+        let mut splits = Vec::new();
+        positions(layout.root().unwrap(), &mut splits);
+        let expected = [
+            // A takes one of three columns: A, C, D
+            (SplitDirection::Vertical, 1.0 / 3.0),
+            (SplitDirection::Horizontal, 0.5),
+            (SplitDirection::Vertical, 0.5),
+        ];
+        assert_eq!(splits.len(), expected.len());
+        for ((direction, position), (exp_direction, exp_position)) in splits.iter().zip(expected) {
+            assert_eq!(*direction, exp_direction);
+            assert!((position - exp_position).abs() < f64::EPSILON);
+        }
+
+        // Column widths of A, C and D sum to the full width and are equal
+        let root = splits[0].1;
+        let widths = [
+            root,
+            (1.0 - root) * splits[2].1,
+            (1.0 - root) * (1.0 - splits[2].1),
+        ];
+        assert!((widths.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(widths.iter().all(|w| (w - 1.0 / 3.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn equalize_single_panel_is_noop() {
+        let mut layout = SplitLayoutModel::new();
+        assert!(!layout.equalize());
+    }
+
+    #[test]
+    fn swap_panes_exchanges_sessions() {
+        let (mut layout, panels) = nested_layout();
+        let first = SessionId::new();
+        let last = SessionId::new();
+        layout.place_in_panel(panels[0], first).unwrap();
+        layout.place_in_panel(panels[3], last).unwrap();
+        let depth = layout.depth();
+
+        assert!(layout.swap_panes(panels[0], panels[3]).unwrap());
+        assert_eq!(layout.get_panel_session(panels[0]), Some(last));
+        assert_eq!(layout.get_panel_session(panels[3]), Some(first));
+        assert_eq!(layout.panel_ids(), panels);
+        assert_eq!(layout.depth(), depth);
+
+        // Swapping with an empty panel moves the session
+        assert!(layout.swap_panes(panels[3], panels[1]).unwrap());
+        assert_eq!(layout.get_panel_session(panels[1]), Some(first));
+        assert_eq!(layout.get_panel_session(panels[3]), None);
+    }
+
+    #[test]
+    fn swap_panes_noop_and_errors() {
+        let mut single = SplitLayoutModel::new();
+        let only = single.first_panel().id;
+        assert!(!single.swap_panes(only, only).unwrap());
+
+        let (mut layout, panels) = nested_layout();
+        assert!(!layout.swap_panes(panels[1], panels[1]).unwrap());
+        assert!(matches!(
+            layout.swap_panes(panels[0], PanelId::new()),
+            Err(SplitError::PanelNotFound(_))
+        ));
+    }
+
+    // ========================================================================
+    // Saved Layout Tests
+    // ========================================================================
+
+    #[test]
+    fn serialize_and_restore_layout() {
+        let web = Uuid::new_v4();
+        let deleted = Uuid::new_v4();
+        let web_session = SessionId::new();
+        let old_session = SessionId::new();
+
+        let mut layout = SplitLayoutModel::with_session(web_session);
+        let second = layout.split(SplitDirection::Vertical).unwrap();
+        layout.place_in_panel(second, old_session).unwrap();
+        layout.set_focus(second).unwrap();
+        layout.split(SplitDirection::Horizontal).unwrap();
+
+        let json = layout
+            .serialize(|session| {
+                if session == web_session {
+                    Some(web)
+                } else if session == old_session {
+                    Some(deleted)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+
+        let restored = SplitLayoutModel::deserialize(&json, |id| id == web).unwrap();
+
+        assert_eq!(restored.model.panel_count(), 3);
+        assert_eq!(restored.model.depth(), layout.depth());
+        let plans: Vec<PaneRestore> = restored.panes.iter().map(|(_, p)| *p).collect();
+        assert_eq!(
+            plans,
+            vec![
+                PaneRestore::Connect(web),
+                PaneRestore::Missing(deleted),
+                PaneRestore::Empty
+            ]
+        );
+        assert_eq!(restored.connections(), vec![(restored.panes[0].0, web)]);
+        assert_eq!(
+            restored.model.panel_ids(),
+            restored.panes.iter().map(|(id, _)| *id).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn restore_rejects_invalid_layouts() {
+        assert!(matches!(
+            SplitLayoutModel::deserialize("{", |_| true),
+            Err(SplitError::InvalidLayout(_))
+        ));
+
+        let bad = LayoutNode::Split {
+            direction: SplitDirection::Vertical,
+            position: 1.5,
+            first: Box::new(LayoutNode::Pane {
+                connection_id: None,
+            }),
+            second: Box::new(LayoutNode::Pane {
+                connection_id: None,
+            }),
+        };
+        assert!(matches!(
+            SplitLayoutModel::from_layout(&bad, |_| true),
+            Err(SplitError::InvalidPosition(_))
+        ));
+    }
+
     // ========================================================================
     // Construction Tests
     // ========================================================================
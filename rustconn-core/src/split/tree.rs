@@ -338,10 +338,45 @@ impl PanelNode {
         }
     }
 
+    /// Returns the number of panels laid side by side in the given direction.
+    ///
+    /// Splits in the same direction add up their children's spans; splits
+    /// in the other direction take the larger span of their children.
+    #[must_use]
+    pub fn span(&self, direction: SplitDirection) -> usize {
+        match self {
+            Self::Leaf(_) => 1,
+            Self::Split(split) => {
+                let first = split.first.span(direction);
+                let second = split.second.span(direction);
+                if split.direction == direction {
+                    first + second
+                } else {
+                    first.max(second)
+                }
+            }
+        }
+    }
+
     // ========================================================================
     // Tree Mutation Methods
     // ========================================================================
 
+    /// Sets split positions so panels in each direction get equal space.
+    ///
+    /// Each split gives its first child a share proportional to that child's
+    /// [`span`](Self::span), so `A | (B | C)` becomes three equal columns.
+    #[allow(clippy::cast_precision_loss)] // Panel counts are far below 2^52
+    pub fn equalize(&mut self) {
+        if let Self::Split(split) = self {
+            let first = split.first.span(split.direction);
+            let second = split.second.span(split.direction);
+            split.position = first as f64 / (first + second) as f64;
+            split.first.equalize();
+            split.second.equalize();
+        }
+    }
+
     /// Splits a panel in the given direction.
     ///
     /// The panel with `panel_id` is replaced by a split node containing:
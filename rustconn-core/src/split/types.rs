@@ -3,6 +3,7 @@
 //! This module contains the fundamental identifier types and enums used
 //! throughout the split view system.
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
@@ -131,7 +132,7 @@ impl fmt::Display for ColorId {
 ///
 /// When a panel is split, it is divided into two child panels
 /// arranged either horizontally (top/bottom) or vertically (left/right).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SplitDirection {
     /// Split horizontally, creating top and bottom panels.
     Horizontal,
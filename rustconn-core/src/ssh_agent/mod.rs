@@ -4,9 +4,14 @@
 //! including starting the agent, managing keys, and parsing agent output.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+use crate::secret::{run_cancellable_process, CancellationToken, ProcessOutcome};
+
 /// SSH Agent status and key information
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AgentStatus {
@@ -29,6 +34,40 @@ pub struct AgentKey {
     pub key_type: String,
     /// Key comment (usually the key file path or email)
     pub comment: String,
+    /// Time left before the agent drops the key, when known
+    ///
+    /// `ssh-add -l` does not report lifetimes, so this is only set for keys
+    /// added with a lifetime by this process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_lifetime: Option<Duration>,
+}
+
+/// Constraints applied when adding a key to the agent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddKeyOptions {
+    /// Remove the key from the agent after this long (`ssh-add -t`)
+    pub lifetime: Option<Duration>,
+    /// Require confirmation each time the key is used (`ssh-add -c`)
+    pub confirm: bool,
+}
+
+impl AddKeyOptions {
+    /// Returns the `ssh-add` flags for these options
+    ///
+    /// Lifetimes are rounded up to whole seconds, with a minimum of one.
+    #[must_use]
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(lifetime) = self.lifetime {
+            let secs = lifetime.as_secs() + u64::from(lifetime.subsec_nanos() > 0);
+            args.push("-t".to_string());
+            args.push(secs.max(1).to_string());
+        }
+        if self.confirm {
+            args.push("-c".to_string());
+        }
+        args
+    }
 }
 
 /// Errors related to SSH agent operations
@@ -54,10 +93,18 @@ pub enum AgentError {
     #[error("Failed to add key: {0}")]
     AddKeyFailed(String),
 
+    /// The passphrase supplied for an encrypted key was rejected
+    #[error("Failed to add key: incorrect passphrase for {0}")]
+    BadPassphrase(String),
+
     /// Failed to remove key from agent
     #[error("Failed to remove key: {0}")]
     RemoveKeyFailed(String),
 
+    /// The operation was cancelled before it completed
+    #[error("Agent operation cancelled")]
+    Cancelled,
+
     /// I/O error during agent operation
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -214,6 +261,10 @@ echo Agent pid 12346;";
 ///
 /// Each line contains: bits fingerprint comment (`key_type`)
 ///
+/// The trailing parentheses are only taken as the key type when they hold a
+/// known type (see [`is_known_key_type`]); otherwise the whole remainder is
+/// the comment, so comments such as `work laptop (primary)` stay intact.
+///
 /// # Returns
 ///
 /// A vector of `AgentKey` structs, one for each loaded key.
@@ -259,32 +310,54 @@ pub fn parse_key_list(output: &str) -> AgentResult<Vec<AgentKey>> {
         // The rest contains comment and (key_type)
         let rest = parts[2];
 
-        // Extract key type from parentheses at the end
-        // Using nested if-let for clarity over map_or_else
-        #[allow(clippy::option_if_let_else)]
-        let (comment, key_type) = if let Some(paren_start) = rest.rfind('(') {
-            if let Some(paren_end) = rest.rfind(')') {
-                let key_type = rest[paren_start + 1..paren_end].to_string();
-                let comment = rest[..paren_start].trim().to_string();
-                (comment, key_type)
-            } else {
-                (rest.to_string(), String::new())
-            }
-        } else {
-            (rest.to_string(), String::new())
-        };
+        // Extract key type from a trailing "(TYPE)" token
+        let (comment, key_type) = rest
+            .strip_suffix(')')
+            .and_then(|head| head.rsplit_once('('))
+            .filter(|(comment, key_type)| {
+                (comment.is_empty() || comment.ends_with(' ')) && is_known_key_type(key_type)
+            })
+            .map_or_else(
+                || (rest.trim().to_string(), String::new()),
+                |(comment, key_type)| (comment.trim().to_string(), key_type.to_string()),
+            );
 
         keys.push(AgentKey {
             fingerprint,
             bits,
             key_type,
             comment,
+            remaining_lifetime: None,
         });
     }
 
     Ok(keys)
 }
 
+/// Key types printed by `ssh-add -l`, without the `-CERT` suffix
+const KNOWN_KEY_TYPES: [&str; 8] = [
+    "RSA",
+    "DSA",
+    "DSS",
+    "ECDSA",
+    "ED25519",
+    "ECDSA-SK",
+    "ED25519-SK",
+    "XMSS",
+];
+
+/// Returns true if `key_type` names an SSH key type
+///
+/// Matching is case-insensitive and ignores an `ssh-` prefix and a `-CERT`
+/// suffix, so `ED25519-SK`, `ssh-ed25519-sk` and `RSA-CERT` are all known.
+#[must_use]
+pub fn is_known_key_type(key_type: &str) -> bool {
+    let name = key_type.to_ascii_uppercase();
+    let name = name.strip_prefix("SSH-").unwrap_or(&name);
+    let name = name.strip_suffix("-CERT").unwrap_or(name);
+    KNOWN_KEY_TYPES.contains(&name)
+}
+
 #[cfg(test)]
 mod key_list_tests {
     use super::*;
@@ -344,6 +417,49 @@ mod key_list_tests {
         assert_eq!(keys[0].key_type, "ED25519");
     }
 
+    #[test]
+    fn test_parse_fido_key_types() {
+        let output = "256 SHA256:abc123 yubikey (ED25519-SK)\n\
+                      256 SHA256:def456 token (ECDSA-SK)\n\
+                      256 SHA256:ghi789 solo (ssh-ed25519-sk)";
+        let keys = parse_key_list(output).unwrap();
+
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].key_type, "ED25519-SK");
+        assert_eq!(keys[0].comment, "yubikey");
+        assert_eq!(keys[1].key_type, "ECDSA-SK");
+        assert_eq!(keys[2].key_type, "ssh-ed25519-sk");
+        assert_eq!(keys[2].comment, "solo");
+    }
+
+    #[test]
+    fn test_parse_comment_with_parentheses() {
+        let output = "256 SHA256:abc123 work laptop (primary) (ED25519)";
+        let keys = parse_key_list(output).unwrap();
+        assert_eq!(keys[0].comment, "work laptop (primary)");
+        assert_eq!(keys[0].key_type, "ED25519");
+
+        let keys = parse_key_list("256 SHA256:abc123 (ED25519)").unwrap();
+        assert_eq!(keys[0].comment, "");
+        assert_eq!(keys[0].key_type, "ED25519");
+
+        // Without a known trailing type the whole remainder is the comment
+        let output = "256 SHA256:abc123 work laptop (primary)";
+        let keys = parse_key_list(output).unwrap();
+        assert_eq!(keys[0].comment, "work laptop (primary)");
+        assert_eq!(keys[0].key_type, "");
+    }
+
+    #[test]
+    fn test_is_known_key_type() {
+        for key_type in ["RSA", "ed25519", "ECDSA-SK", "ssh-ed25519-sk", "RSA-CERT"] {
+            assert!(is_known_key_type(key_type), "{key_type}");
+        }
+        for key_type in ["primary", "", "SK", "ED25519 "] {
+            assert!(!is_known_key_type(key_type), "{key_type}");
+        }
+    }
+
     #[test]
     fn test_parse_invalid_bits() {
         let output = "notanumber SHA256:abc123 comment (RSA)";
@@ -397,6 +513,238 @@ mod manager_tests {
         assert!(status.socket_path.is_none());
         assert!(status.keys.is_empty());
     }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_get_status_async_matches_sync() {
+        let manager = SshAgentManager::default();
+        assert_eq!(
+            block_on(manager.get_status_async()).unwrap(),
+            manager.get_status().unwrap()
+        );
+
+        let manager = SshAgentManager::new(Some("/nonexistent/agent.sock".to_string()));
+        let status = block_on(manager.get_status_async()).unwrap();
+        assert!(!status.running);
+        assert_eq!(
+            status.socket_path.as_deref(),
+            Some("/nonexistent/agent.sock")
+        );
+    }
+
+    #[test]
+    fn test_get_status_with_cancelled_token() {
+        let manager = SshAgentManager::new(Some("/tmp/test.sock".to_string()));
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = block_on(manager.get_status_with_cancellation(&token));
+        assert!(matches!(result, Err(AgentError::Cancelled)));
+    }
+}
+
+// ============================================================================
+// Agent Key Selection
+// ============================================================================
+
+/// Builds SSH arguments that restrict authentication to a single agent key.
+///
+/// The key is identified by its public key file: with `IdentitiesOnly=yes`,
+/// SSH only offers the agent key matching the file passed via `-i`, which
+/// disambiguates agents holding several keys for the same host.
+///
+/// # Arguments
+///
+/// * `socket_path` - Agent socket to use (`IdentityAgent`)
+/// * `public_key_path` - File containing the selected key's public key
+#[must_use]
+pub fn build_agent_identity_args(socket_path: &str, public_key_path: &Path) -> Vec<String> {
+    vec![
+        "-o".to_string(),
+        format!("IdentityAgent={socket_path}"),
+        "-o".to_string(),
+        "IdentitiesOnly=yes".to_string(),
+        "-i".to_string(),
+        public_key_path.display().to_string(),
+    ]
+}
+
+/// Returns the directory agent public key files are written to
+///
+/// `$XDG_RUNTIME_DIR` is private to the user; the system temporary
+/// directory is only used when it is not set.
+#[must_use]
+pub fn agent_key_dir() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Writes an agent key's public key to its per-fingerprint file in `dir`
+///
+/// The file name is derived from the fingerprint, so repeated connects reuse
+/// one file instead of leaving a new one behind each time. An existing
+/// private regular file with the same key is reused as is; otherwise the key
+/// is written to a fresh 0600 file that is renamed over the path, so an
+/// existing file or symlink at that path is never written through.
+///
+/// # Arguments
+///
+/// * `fingerprint` - Fingerprint of the key (e.g., "SHA256:abc123...")
+/// * `public_key` - Public key in OpenSSH format
+/// * `dir` - Directory to write the file into
+///
+/// # Errors
+///
+/// Returns `AgentError::Io` if the file cannot be created or written.
+pub fn write_agent_public_key(
+    fingerprint: &str,
+    public_key: &str,
+    dir: &Path,
+) -> AgentResult<PathBuf> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let file_stem: String = fingerprint
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let path = dir.join(format!("rustconn-agent-{file_stem}.pub"));
+    let contents = format!("{}\n", public_key.trim());
+    if is_reusable_key_file(&path, &contents) {
+        return Ok(path);
+    }
+
+    let staging = dir.join(format!(
+        ".rustconn-agent-{file_stem}-{}.tmp",
+        uuid::Uuid::new_v4().simple()
+    ));
+    let written = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&staging)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .and_then(|()| std::fs::rename(&staging, &path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&staging);
+        return Err(AgentError::Io(e));
+    }
+    Ok(path)
+}
+
+/// Checks whether `path` is a 0600 regular file holding `contents`
+fn is_reusable_key_file(path: &Path, contents: &str) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return false;
+    };
+    metadata.file_type().is_file()
+        && metadata.permissions().mode() & 0o777 == 0o600
+        && std::fs::read_to_string(path).is_ok_and(|existing| existing == contents)
+}
+
+#[cfg(test)]
+mod agent_key_selection_tests {
+    use super::*;
+    use crate::models::SshConfig;
+
+    #[test]
+    fn test_selected_fingerprint_drives_identity_restriction() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let work_key = ("SHA256:work/key+1", "ssh-ed25519 AAAAwork work@laptop");
+        let home_key = ("SHA256:home/key+2", "ssh-ed25519 AAAAhome home@laptop");
+
+        let config = SshConfig {
+            agent_key_fingerprint: Some(work_key.0.to_string()),
+            ..SshConfig::default()
+        };
+
+        // The per-launch override wins over the stored preference
+        for (override_fp, expected) in [(None, work_key), (Some(home_key.0), home_key)] {
+            let fingerprint = config.effective_agent_fingerprint(override_fp).unwrap();
+            assert_eq!(fingerprint, expected.0);
+
+            let key_file = write_agent_public_key(fingerprint, expected.1, dir.path()).unwrap();
+            let args = build_agent_identity_args("/tmp/agent.sock", &key_file);
+
+            assert_eq!(
+                args,
+                vec![
+                    "-o".to_string(),
+                    "IdentityAgent=/tmp/agent.sock".to_string(),
+                    "-o".to_string(),
+                    "IdentitiesOnly=yes".to_string(),
+                    "-i".to_string(),
+                    key_file.display().to_string(),
+                ]
+            );
+            let written = std::fs::read_to_string(&key_file).unwrap();
+            assert_eq!(written.trim(), expected.1);
+        }
+    }
+
+    #[test]
+    fn test_public_key_file_name_is_sanitized() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_agent_public_key("SHA256:a/b+c=", "ssh-rsa AAAA", dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("rustconn-agent-SHA256_a_b_c_.pub"));
+    }
+
+    #[test]
+    fn test_public_key_file_is_private_and_reused() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = write_agent_public_key("SHA256:abc", "ssh-rsa AAAA", dir.path()).unwrap();
+        let second = write_agent_public_key("SHA256:abc", "ssh-rsa AAAA", dir.path()).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let mode = std::fs::metadata(&first).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_public_key_file_replaces_stale_or_symlinked_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("target");
+        std::fs::write(&target, "untouched").unwrap();
+        let path = dir.path().join("rustconn-agent-SHA256_abc.pub");
+        std::os::unix::fs::symlink(&target, &path).unwrap();
+
+        let written = write_agent_public_key("SHA256:abc", "ssh-rsa AAAA", dir.path()).unwrap();
+        assert_eq!(written, path);
+        assert!(std::fs::symlink_metadata(&path)
+            .unwrap()
+            .file_type()
+            .is_file());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "ssh-rsa AAAA\n");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "untouched");
+    }
+
+    #[test]
+    fn test_no_fingerprint_without_preference() {
+        let config = SshConfig::default();
+        assert_eq!(config.effective_agent_fingerprint(None), None);
+        assert_eq!(config.effective_agent_fingerprint(Some("")), None);
+    }
+
+    #[test]
+    fn test_identity_args_require_agent() {
+        let manager = SshAgentManager::default();
+        assert!(matches!(
+            manager.identity_args_for_key("SHA256:abc"),
+            Err(AgentError::NotRunning)
+        ));
+    }
 }
 
 // ============================================================================
@@ -432,21 +780,17 @@ impl SshAgentManager {
 
     /// Gets the current agent status including loaded keys.
     ///
+    /// Runs `ssh-add -l` on the calling thread; GUI code should prefer
+    /// [`Self::get_status_async`].
+    ///
     /// # Errors
     ///
     /// Returns `AgentError::NotRunning` if no socket path is configured.
     pub fn get_status(&self) -> AgentResult<AgentStatus> {
         use std::process::Command;
 
-        let socket_path = match &self.socket_path {
-            Some(path) => path.clone(),
-            None => {
-                return Ok(AgentStatus {
-                    running: false,
-                    socket_path: None,
-                    keys: Vec::new(),
-                });
-            }
+        let Some(socket_path) = self.socket_path.clone() else {
+            return Ok(Self::stopped_status(None));
         };
 
         // Check if the socket exists and agent is responsive
@@ -455,101 +799,176 @@ impl SshAgentManager {
             .env("SSH_AUTH_SOCK", &socket_path)
             .output();
 
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-
-                // Exit code 1 with "no identities" is still a running agent
-                if output.status.success()
-                    || stdout.contains("no identities")
-                    || stderr.contains("no identities")
-                {
-                    let keys = parse_key_list(&stdout).unwrap_or_default();
-                    Ok(AgentStatus {
-                        running: true,
-                        socket_path: Some(socket_path),
-                        keys,
-                    })
-                } else {
-                    // Agent not responding
-                    Ok(AgentStatus {
-                        running: false,
-                        socket_path: Some(socket_path),
-                        keys: Vec::new(),
-                    })
-                }
-            }
-            Err(_) => Ok(AgentStatus {
-                running: false,
+        Ok(match output {
+            Ok(output) => Self::status_from_output(socket_path, &output),
+            Err(_) => Self::stopped_status(Some(socket_path)),
+        })
+    }
+
+    /// Gets the current agent status without blocking the calling thread.
+    ///
+    /// `ssh-add -l` runs as a tokio child process, so this must be awaited
+    /// inside a tokio runtime. Dropping the future kills the process.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::get_status`].
+    pub async fn get_status_async(&self) -> AgentResult<AgentStatus> {
+        self.get_status_with_cancellation(&CancellationToken::new())
+            .await
+    }
+
+    /// Gets the current agent status, aborting when `cancel_token` is cancelled.
+    ///
+    /// On cancellation the running `ssh-add` process is killed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AgentError::Cancelled` if the token is cancelled before the
+    /// status is known.
+    pub async fn get_status_with_cancellation(
+        &self,
+        cancel_token: &CancellationToken,
+    ) -> AgentResult<AgentStatus> {
+        let Some(socket_path) = self.socket_path.clone() else {
+            return Ok(Self::stopped_status(None));
+        };
+
+        let mut command = tokio::process::Command::new("ssh-add");
+        command.arg("-l").env("SSH_AUTH_SOCK", &socket_path);
+
+        match run_cancellable_process(&mut command, None, cancel_token).await {
+            ProcessOutcome::Completed(output) => Ok(Self::status_from_output(socket_path, &output)),
+            ProcessOutcome::Cancelled => Err(AgentError::Cancelled),
+            ProcessOutcome::Failed(_) => Ok(Self::stopped_status(Some(socket_path))),
+        }
+    }
+
+    /// Interprets the output of `ssh-add -l` for the agent at `socket_path`
+    fn status_from_output(socket_path: String, output: &std::process::Output) -> AgentStatus {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        // Exit code 1 with "no identities" is still a running agent
+        if output.status.success()
+            || stdout.contains("no identities")
+            || stderr.contains("no identities")
+        {
+            let mut keys = parse_key_list(&stdout).unwrap_or_default();
+            apply_key_expiries(&mut keys);
+            AgentStatus {
+                running: true,
                 socket_path: Some(socket_path),
-                keys: Vec::new(),
-            }),
+                keys,
+            }
+        } else {
+            // Agent not responding
+            Self::stopped_status(Some(socket_path))
+        }
+    }
+
+    /// Status for an agent that is absent or not responding
+    const fn stopped_status(socket_path: Option<String>) -> AgentStatus {
+        AgentStatus {
+            running: false,
+            socket_path,
+            keys: Vec::new(),
         }
     }
 
     /// Adds a key to the SSH agent.
     ///
+    /// When a passphrase is given, `ssh-add` is run with a temporary
+    /// `SSH_ASKPASS` helper that reads it from a pipe, so the passphrase never
+    /// touches the disk, the environment, or the command line. The copy held
+    /// for the pipe is zeroized once written.
+    ///
     /// # Arguments
     ///
     /// * `key_path` - Path to the private key file
     /// * `passphrase` - Optional passphrase for encrypted keys
+    /// * `options` - Lifetime and confirmation constraints for the key
     ///
     /// # Errors
     ///
     /// Returns `AgentError::NotRunning` if no socket is configured.
-    /// Returns `AgentError::AddKeyFailed` if the key cannot be added.
-    pub fn add_key(&self, key_path: &std::path::Path, passphrase: Option<&str>) -> AgentResult<()> {
-        use std::process::{Command, Stdio};
+    /// Returns `AgentError::BadPassphrase` if the passphrase is wrong.
+    /// Returns `AgentError::AddKeyFailed` if the key cannot be added otherwise.
+    pub fn add_key(
+        &self,
+        key_path: &std::path::Path,
+        passphrase: Option<&str>,
+        options: &AddKeyOptions,
+    ) -> AgentResult<()> {
+        use std::process::Command;
 
         let socket_path = self.socket_path.as_ref().ok_or(AgentError::NotRunning)?;
+        let option_args = options.args();
 
-        if let Some(pass) = passphrase {
-            // Use SSH_ASKPASS with a helper script for passphrase
-            // This is a simplified approach - in production, you'd use expect or a proper PTY
-            let mut cmd = Command::new("ssh-add");
-            cmd.arg(key_path)
-                .env("SSH_AUTH_SOCK", socket_path)
-                .env("SSH_ASKPASS_REQUIRE", "force")
-                .env("DISPLAY", ":0")
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-
-            // For now, we'll use a simple echo approach via environment
-            // In a real implementation, you'd want to use a proper PTY or expect-like library
-            let output = cmd
-                .output()
-                .map_err(|e| AgentError::AddKeyFailed(e.to_string()))?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                // If it needs a passphrase, we need a different approach
-                if stderr.contains("passphrase") || stderr.contains("bad passphrase") {
-                    return Err(AgentError::AddKeyFailed(format!(
-                        "Passphrase required or incorrect: {stderr}"
-                    )));
-                }
-                return Err(AgentError::AddKeyFailed(stderr.to_string()));
-            }
-            let _ = pass; // Acknowledge passphrase was provided
+        let output = if let Some(pass) = passphrase {
+            Self::add_key_with_askpass(socket_path, key_path, pass, &option_args)?
         } else {
             // No passphrase - simple ssh-add
-            let output = Command::new("ssh-add")
+            Command::new("ssh-add")
+                .args(&option_args)
                 .arg(key_path)
                 .env("SSH_AUTH_SOCK", socket_path)
                 .output()
-                .map_err(|e| AgentError::AddKeyFailed(e.to_string()))?;
+                .map_err(|e| AgentError::AddKeyFailed(e.to_string()))?
+        };
 
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(AgentError::AddKeyFailed(stderr.to_string()));
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains(BAD_PASSPHRASE_PROMPT) {
+                return Err(AgentError::BadPassphrase(key_path.display().to_string()));
             }
+            return Err(AgentError::AddKeyFailed(stderr.trim().to_string()));
         }
 
+        record_key_expiry(key_path, options.lifetime);
         Ok(())
     }
 
+    /// Runs `ssh-add` with an askpass helper that is fed `passphrase` over stdin
+    fn add_key_with_askpass(
+        socket_path: &str,
+        key_path: &Path,
+        passphrase: &str,
+        option_args: &[String],
+    ) -> AgentResult<std::process::Output> {
+        use secrecy::{ExposeSecret, SecretString};
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let helper = AskpassHelper::create().map_err(|e| {
+            AgentError::AddKeyFailed(format!("Failed to create askpass helper: {e}"))
+        })?;
+        let input = SecretString::from(format!("{passphrase}\n"));
+
+        let mut child = Command::new("ssh-add")
+            .args(option_args)
+            .arg(key_path)
+            .env("SSH_AUTH_SOCK", socket_path)
+            .env("SSH_ASKPASS", helper.script_path())
+            .env("SSH_ASKPASS_REQUIRE", "force")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AgentError::AddKeyFailed(e.to_string()))?;
+
+        // The helper inherits this pipe; closing it makes any retry prompt fail
+        if let Some(mut stdin) = child.stdin.take() {
+            // An early exit is reported through the exit status below
+            let _ = stdin.write_all(input.expose_secret().as_bytes());
+        }
+        drop(input);
+
+        child
+            .wait_with_output()
+            .map_err(|e| AgentError::AddKeyFailed(e.to_string()))
+    }
+
     /// Removes a key from the SSH agent.
     ///
     /// # Arguments
@@ -751,6 +1170,24 @@ impl SshAgentManager {
         Err(AgentError::KeyNotFound(fingerprint.to_string()))
     }
 
+    /// Builds SSH arguments that restrict authentication to one agent key.
+    ///
+    /// Looks up the public key for `fingerprint` in the agent, writes it to
+    /// its per-fingerprint file and returns `-o IdentityAgent=... -o IdentitiesOnly=yes
+    /// -i <pubkey>` arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AgentError::NotRunning` if no socket is configured.
+    /// Returns `AgentError::KeyNotFound` if no agent key matches the fingerprint.
+    /// Returns `AgentError::Io` if the public key file cannot be written.
+    pub fn identity_args_for_key(&self, fingerprint: &str) -> AgentResult<Vec<String>> {
+        let socket_path = self.socket_path.as_ref().ok_or(AgentError::NotRunning)?;
+        let public_key = self.get_public_key_by_fingerprint(fingerprint)?;
+        let key_file = write_agent_public_key(fingerprint, &public_key, &agent_key_dir())?;
+        Ok(build_agent_identity_args(socket_path, &key_file))
+    }
+
     /// Checks if a file contains a private key by reading its header
     fn is_private_key_file(path: &PathBuf) -> bool {
         use std::io::{BufRead, BufReader};
@@ -772,3 +1209,237 @@ impl SshAgentManager {
             || first_line.contains("DSA PRIVATE KEY")
     }
 }
+
+// ============================================================================
+// Askpass Helper
+// ============================================================================
+
+/// `SSH_ASKPASS` helper that answers a prompt with one line read from stdin
+///
+/// The prompt is echoed to stderr so a retry prompt after a wrong passphrase
+/// shows up in `ssh-add`'s output. A second prompt hits EOF and fails, which
+/// makes `ssh-add` give up instead of asking again.
+const ASKPASS_SCRIPT: &str = "#!/bin/sh
+printf '%s\\n' \"$1\" >&2
+IFS= read -r passphrase || exit 1
+printf '%s\\n' \"$passphrase\"
+";
+
+/// Prompt `ssh-add` shows when the previous passphrase was wrong
+const BAD_PASSPHRASE_PROMPT: &str = "Bad passphrase";
+
+/// Private directory holding the askpass helper script, removed on drop
+///
+/// The script holds no secret; the directory is only readable by the owner
+/// so the helper cannot be swapped out while `ssh-add` runs.
+struct AskpassHelper {
+    dir: PathBuf,
+}
+
+impl AskpassHelper {
+    /// Creates the directory and writes the helper script into it
+    fn create() -> std::io::Result<Self> {
+        use std::io::Write;
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+        let dir = std::env::temp_dir().join(format!("rustconn-askpass-{}", uuid::Uuid::new_v4()));
+        std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+        let helper = Self { dir };
+
+        let mut script = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o700)
+            .open(helper.script_path())?;
+        script.write_all(ASKPASS_SCRIPT.as_bytes())?;
+
+        Ok(helper)
+    }
+
+    /// Path of the executable helper script
+    fn script_path(&self) -> PathBuf {
+        self.dir.join("askpass")
+    }
+}
+
+impl Drop for AskpassHelper {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[cfg(test)]
+mod askpass_tests {
+    use super::*;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    fn run_helper(helper: &AskpassHelper, prompt: &str, stdin: &[u8]) -> std::process::Output {
+        let mut child = Command::new(helper.script_path())
+            .arg(prompt)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(stdin).unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    #[test]
+    fn test_helper_answers_with_first_line_and_echoes_prompt() {
+        let helper = AskpassHelper::create().unwrap();
+        let output = run_helper(
+            &helper,
+            "Enter passphrase for key:",
+            b" p@ss word \nignored\n",
+        );
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b" p@ss word \n");
+        assert_eq!(output.stderr, b"Enter passphrase for key:\n");
+    }
+
+    #[test]
+    fn test_helper_fails_once_input_is_exhausted() {
+        let helper = AskpassHelper::create().unwrap();
+        let output = run_helper(&helper, "Bad passphrase, try again for key:", b"");
+
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+    }
+
+    #[test]
+    fn test_helper_directory_is_private_and_removed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let helper = AskpassHelper::create().unwrap();
+        let dir = helper.dir.clone();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        drop(helper);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_add_key_requires_agent() {
+        let manager = SshAgentManager::default();
+        let result = manager.add_key(
+            Path::new("/tmp/id_test"),
+            Some("secret"),
+            &AddKeyOptions::default(),
+        );
+        assert!(matches!(result, Err(AgentError::NotRunning)));
+    }
+}
+
+// ============================================================================
+// Key Lifetime Tracking
+// ============================================================================
+
+/// Expiry of keys added with a lifetime, by fingerprint
+static KEY_EXPIRIES: LazyLock<Mutex<HashMap<String, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Remembers when a just-added key expires, or forgets it if it has no lifetime
+fn record_key_expiry(key_path: &Path, lifetime: Option<Duration>) {
+    let Some(fingerprint) = key_file_fingerprint(key_path) else {
+        return;
+    };
+    let mut expiries = KEY_EXPIRIES.lock().unwrap_or_else(PoisonError::into_inner);
+    match lifetime {
+        Some(lifetime) => {
+            expiries.insert(fingerprint, Instant::now() + lifetime);
+        }
+        None => {
+            expiries.remove(&fingerprint);
+        }
+    }
+}
+
+/// Fills in `remaining_lifetime` for keys with a recorded expiry
+fn apply_key_expiries(keys: &mut [AgentKey]) {
+    let now = Instant::now();
+    let mut expiries = KEY_EXPIRIES.lock().unwrap_or_else(PoisonError::into_inner);
+    expiries.retain(|_, expiry| *expiry > now);
+    for key in keys {
+        key.remaining_lifetime = expiries
+            .get(&key.fingerprint)
+            .map(|expiry| expiry.saturating_duration_since(now));
+    }
+}
+
+/// Returns the fingerprint of a key file as reported by `ssh-keygen -l`
+fn key_file_fingerprint(key_path: &Path) -> Option<String> {
+    let output = std::process::Command::new("ssh-keygen")
+        .arg("-lf")
+        .arg(key_path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_key_list(&String::from_utf8_lossy(&output.stdout))
+        .ok()?
+        .into_iter()
+        .next()
+        .map(|key| key.fingerprint)
+}
+
+#[cfg(test)]
+mod lifetime_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_key_options_args() {
+        assert!(AddKeyOptions::default().args().is_empty());
+
+        let options = AddKeyOptions {
+            lifetime: Some(Duration::from_secs(3600)),
+            confirm: true,
+        };
+        assert_eq!(options.args(), vec!["-t", "3600", "-c"]);
+    }
+
+    #[test]
+    fn test_add_key_options_lifetime_rounds_up() {
+        let options = AddKeyOptions {
+            lifetime: Some(Duration::from_millis(1500)),
+            confirm: false,
+        };
+        assert_eq!(options.args(), vec!["-t", "2"]);
+
+        let options = AddKeyOptions {
+            lifetime: Some(Duration::ZERO),
+            confirm: false,
+        };
+        assert_eq!(options.args(), vec!["-t", "1"]);
+    }
+
+    #[test]
+    fn test_apply_key_expiries() {
+        let fingerprint = "SHA256:lifetime-test-key";
+        KEY_EXPIRIES.lock().unwrap().insert(
+            fingerprint.to_string(),
+            Instant::now() + Duration::from_secs(600),
+        );
+
+        let mut keys = parse_key_list(&format!(
+            "256 {fingerprint} temp (ED25519)\n256 SHA256:other other (ED25519)"
+        ))
+        .unwrap();
+        apply_key_expiries(&mut keys);
+
+        let remaining = keys[0].remaining_lifetime.unwrap();
+        assert!(remaining > Duration::from_secs(590) && remaining <= Duration::from_secs(600));
+        assert_eq!(keys[1].remaining_lifetime, None);
+    }
+
+    #[test]
+    fn test_agent_key_lifetime_not_serialized_when_unknown() {
+        let keys = parse_key_list("256 SHA256:abc user@host (ED25519)").unwrap();
+        let json = serde_json::to_string(&keys[0]).unwrap();
+        assert!(!json.contains("remaining_lifetime"));
+    }
+}
@@ -8,17 +8,22 @@
 // Allow truncation for millisecond conversion - latencies won't exceed u64::MAX
 #![allow(clippy::cast_possible_truncation)]
 
+pub mod roundtrip;
+
+pub use roundtrip::{assert_roundtrip, roundtrip_fields, RoundtripField};
+
 use std::collections::HashMap;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tokio::time::timeout;
 use uuid::Uuid;
 
 use crate::models::{Connection, ProtocolType};
+use crate::performance::BatchProcessor;
 
 /// Default timeout for connection tests (10 seconds)
 pub const DEFAULT_TEST_TIMEOUT_SECS: u64 = 10;
@@ -26,6 +31,9 @@ pub const DEFAULT_TEST_TIMEOUT_SECS: u64 = 10;
 /// Default number of concurrent tests for batch operations
 pub const DEFAULT_CONCURRENCY: usize = 10;
 
+/// Operation name under which batch tests are recorded in the metrics
+const BATCH_OPERATION: &str = "connection_test_batch";
+
 /// Errors that can occur during connection testing
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum TestError {
@@ -143,6 +151,14 @@ pub struct TestSummary {
     pub failed: usize,
     /// Individual test results
     pub results: Vec<TestResult>,
+    /// Standard deviation of the latencies of successful tests in
+    /// milliseconds (None if no test succeeded)
+    #[serde(default)]
+    pub jitter_ms: Option<f64>,
+    /// Latencies of successful tests in ascending order, kept so percentiles
+    /// survive a serialization round trip
+    #[serde(default)]
+    latencies_ms: Vec<u64>,
 }
 
 impl TestSummary {
@@ -154,6 +170,8 @@ impl TestSummary {
             passed: 0,
             failed: 0,
             results: Vec::new(),
+            jitter_ms: None,
+            latencies_ms: Vec::new(),
         }
     }
 
@@ -164,12 +182,23 @@ impl TestSummary {
         let passed = results.iter().filter(|r| r.success).count();
         let failed = total - passed;
 
-        Self {
+        let mut latencies_ms: Vec<u64> = results
+            .iter()
+            .filter(|r| r.success)
+            .filter_map(|r| r.latency_ms)
+            .collect();
+        latencies_ms.sort_unstable();
+
+        let mut summary = Self {
             total,
             passed,
             failed,
             results,
-        }
+            jitter_ms: None,
+            latencies_ms,
+        };
+        summary.update_jitter();
+        summary
     }
 
     /// Adds a test result to the summary
@@ -180,9 +209,47 @@ impl TestSummary {
             self.failed += 1;
         }
         self.total += 1;
+        if let Some(latency) = result.latency_ms.filter(|_| result.success) {
+            let index = self.latencies_ms.partition_point(|&l| l <= latency);
+            self.latencies_ms.insert(index, latency);
+            self.update_jitter();
+        }
         self.results.push(result);
     }
 
+    /// Returns the latency at percentile `p` (0 to 100) in milliseconds
+    ///
+    /// Uses the nearest-rank method over successful tests only; failed tests
+    /// have no latency and would skew the result. Returns None if no test
+    /// succeeded.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn latency_percentile(&self, p: f64) -> Option<u64> {
+        if self.latencies_ms.is_empty() {
+            return None;
+        }
+        let count = self.latencies_ms.len();
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * count as f64).ceil() as usize;
+        Some(self.latencies_ms[rank.clamp(1, count) - 1])
+    }
+
+    /// Recomputes the jitter from the sorted latencies
+    fn update_jitter(&mut self) {
+        self.jitter_ms = if self.latencies_ms.is_empty() {
+            None
+        } else {
+            let count = self.latencies_ms.len() as f64;
+            let mean = self.latencies_ms.iter().sum::<u64>() as f64 / count;
+            let variance = self
+                .latencies_ms
+                .iter()
+                .map(|&latency| (latency as f64 - mean).powi(2))
+                .sum::<f64>()
+                / count;
+            Some(variance.sqrt())
+        };
+    }
+
     /// Returns true if all tests passed
     #[must_use]
     pub const fn all_passed(&self) -> bool {
@@ -292,7 +359,11 @@ impl ConnectionTester {
     ///
     /// This method tests connectivity to the specified connection by:
     /// 1. Testing TCP port accessibility
-    /// 2. For SSH connections, verifying the SSH banner exchange
+    /// 2. Probing the protocol handshake: the SSH version banner, an RDP
+    ///    X.224 connection request or the VNC (RFB) protocol version
+    ///
+    /// The server version detected by the probe is recorded in the
+    /// `server_version` detail. Other protocols are tested with TCP only.
     ///
     /// # Arguments
     ///
@@ -305,35 +376,38 @@ impl ConnectionTester {
         let start = std::time::Instant::now();
 
         // First test port connectivity
-        match self.test_port(&connection.host, connection.port).await {
-            Ok(latency) => {
-                // For SSH, also verify the protocol handshake
-                if connection.protocol == ProtocolType::Ssh {
-                    match self.test_ssh(connection).await {
-                        Ok(()) => {
-                            let latency_ms = latency.as_millis() as u64;
-                            TestResult::success(connection.id, connection.name.clone(), latency_ms)
-                                .with_detail("protocol", "SSH")
-                                .with_detail("handshake", "verified")
-                        }
-                        Err(e) => {
-                            TestResult::from_error(connection.id, connection.name.clone(), &e)
-                                .with_detail("protocol", "SSH")
-                                .with_detail("port_open", "true")
-                        }
-                    }
-                } else {
-                    // For RDP/VNC, port connectivity is sufficient
-                    let latency_ms = latency.as_millis() as u64;
-                    TestResult::success(connection.id, connection.name.clone(), latency_ms)
-                        .with_detail("protocol", connection.protocol.to_string())
-                }
-            }
+        let latency = match self.test_port(&connection.host, connection.port).await {
+            Ok(latency) => latency,
             Err(e) => {
                 let elapsed = start.elapsed().as_millis() as u64;
-                TestResult::from_error(connection.id, connection.name.clone(), &e)
-                    .with_detail("elapsed_ms", elapsed.to_string())
+                return TestResult::from_error(connection.id, connection.name.clone(), &e)
+                    .with_detail("elapsed_ms", elapsed.to_string());
+            }
+        };
+
+        let probe = match connection.protocol {
+            ProtocolType::Ssh => self.probe_ssh(connection).await,
+            ProtocolType::Rdp => self.probe_rdp(connection).await,
+            ProtocolType::Vnc => self.probe_vnc(connection).await,
+            _ => {
+                // Port connectivity is all we can check
+                let latency_ms = latency.as_millis() as u64;
+                return TestResult::success(connection.id, connection.name.clone(), latency_ms)
+                    .with_detail("protocol", connection.protocol.to_string());
             }
+        };
+
+        match probe {
+            Ok(version) => {
+                let latency_ms = latency.as_millis() as u64;
+                TestResult::success(connection.id, connection.name.clone(), latency_ms)
+                    .with_detail("protocol", connection.protocol.to_string())
+                    .with_detail("handshake", "verified")
+                    .with_detail("server_version", version)
+            }
+            Err(e) => TestResult::from_error(connection.id, connection.name.clone(), &e)
+                .with_detail("protocol", connection.protocol.to_string())
+                .with_detail("port_open", "true"),
         }
     }
 
@@ -353,7 +427,7 @@ impl ConnectionTester {
     /// Returns an error if the connection fails due to timeout, connection refused,
     /// host unreachable, DNS resolution failure, or other I/O errors.
     pub async fn test_port(&self, host: &str, port: u16) -> TestResult2<Duration> {
-        let addr = format!("{host}:{port}");
+        let addr = format!("{}:{port}", crate::connection::host_for_uri(host));
         let start = std::time::Instant::now();
 
         let timeout_secs = self.timeout.as_secs();
@@ -400,37 +474,32 @@ impl ConnectionTester {
     /// Returns an error if the connection fails, times out, or the SSH banner
     /// is invalid or missing.
     pub async fn test_ssh(&self, connection: &Connection) -> TestResult2<()> {
-        let addr = format!("{}:{}", connection.host, connection.port);
-
-        let stream = match timeout(self.timeout, TcpStream::connect(&addr)).await {
-            Ok(Ok(s)) => s,
-            Ok(Err(e)) => return Err(TestError::IoError(e.to_string())),
-            Err(_) => return Err(TestError::Timeout(self.timeout.as_secs())),
-        };
+        self.probe_ssh(connection).await.map(|_| ())
+    }
 
+    /// Reads the SSH version banner and returns it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails, times out, or the SSH banner
+    /// is invalid or missing.
+    pub async fn probe_ssh(&self, connection: &Connection) -> TestResult2<String> {
+        let stream = self.connect(connection).await?;
         let (reader, mut writer) = stream.into_split();
         let mut reader = BufReader::new(reader);
         let mut banner = String::new();
 
         // Read the SSH banner (server sends it first)
-        match timeout(self.timeout, reader.read_line(&mut banner)).await {
+        let version = match timeout(self.timeout, reader.read_line(&mut banner)).await {
             Ok(Ok(0)) => {
                 return Err(TestError::ProtocolError(
                     "Connection closed before receiving banner".to_string(),
                 ));
             }
-            Ok(Ok(_)) => {
-                // Verify it's a valid SSH banner (starts with "SSH-")
-                if !banner.starts_with("SSH-") {
-                    return Err(TestError::ProtocolError(format!(
-                        "Invalid SSH banner: {}",
-                        banner.trim()
-                    )));
-                }
-            }
+            Ok(Ok(_)) => parse_ssh_banner(&banner)?,
             Ok(Err(e)) => return Err(TestError::IoError(e.to_string())),
             Err(_) => return Err(TestError::Timeout(self.timeout.as_secs())),
-        }
+        };
 
         // Send our client banner to complete the handshake
         let client_banner = "SSH-2.0-RustConn_Test\r\n";
@@ -438,31 +507,202 @@ impl ConnectionTester {
             return Err(TestError::IoError(format!("Failed to send banner: {e}")));
         }
 
-        Ok(())
+        Ok(version)
+    }
+
+    /// Sends an RDP X.224 connection request and returns the negotiated
+    /// security protocol
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails, times out, or the server
+    /// does not confirm the connection or rejects the negotiation.
+    pub async fn probe_rdp(&self, connection: &Connection) -> TestResult2<String> {
+        let mut stream = self.connect(connection).await?;
+        self.timed(stream.write_all(&X224_CONNECTION_REQUEST))
+            .await?;
+
+        // TPKT header: version 3, reserved, big-endian length including itself
+        let mut header = [0u8; 4];
+        self.timed(stream.read_exact(&mut header)).await?;
+        let length = usize::from(u16::from_be_bytes([header[2], header[3]]));
+        if header[0] != 3 || !(4 + 7..=MAX_TPKT_LENGTH).contains(&length) {
+            return Err(TestError::ProtocolError(format!(
+                "Invalid TPKT header: {header:02X?}"
+            )));
+        }
+
+        let mut tpdu = vec![0u8; length - 4];
+        self.timed(stream.read_exact(&mut tpdu)).await?;
+        parse_x224_confirm(&tpdu)
+    }
+
+    /// Reads the VNC (RFB) protocol version and returns it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails, times out, or the server
+    /// does not send an RFB protocol version.
+    pub async fn probe_vnc(&self, connection: &Connection) -> TestResult2<String> {
+        let mut stream = self.connect(connection).await?;
+        let mut version = [0u8; 12];
+        self.timed(stream.read_exact(&mut version)).await?;
+        parse_rfb_version(&version)
+    }
+
+    /// Opens a TCP connection to the connection's host and port
+    async fn connect(&self, connection: &Connection) -> TestResult2<TcpStream> {
+        let addr = format!(
+            "{}:{}",
+            crate::connection::host_for_uri(&connection.host),
+            connection.port
+        );
+
+        match timeout(self.timeout, TcpStream::connect(&addr)).await {
+            Ok(Ok(s)) => Ok(s),
+            Ok(Err(e)) => Err(TestError::IoError(e.to_string())),
+            Err(_) => Err(TestError::Timeout(self.timeout.as_secs())),
+        }
+    }
+
+    /// Runs an I/O operation with the test timeout
+    async fn timed<T>(
+        &self,
+        operation: impl std::future::Future<Output = std::io::Result<T>>,
+    ) -> TestResult2<T> {
+        match timeout(self.timeout, operation).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => Err(
+                TestError::ProtocolError("Connection closed during handshake".to_string()),
+            ),
+            Ok(Err(e)) => Err(TestError::IoError(e.to_string())),
+            Err(_) => Err(TestError::Timeout(self.timeout.as_secs())),
+        }
     }
 
     /// Tests multiple connections concurrently
     ///
+    /// At most [`Self::get_concurrency`] tests run at once. The run is
+    /// recorded as the `connection_test_batch` operation, including its
+    /// peak concurrency.
+    ///
     /// # Arguments
     ///
     /// * `connections` - The connections to test
     ///
     /// # Returns
     ///
-    /// A `TestSummary` with results for all connections
+    /// A `TestSummary` with results for all connections, in input order
     pub async fn test_batch(&self, connections: &[Connection]) -> TestSummary {
-        use futures::stream::{self, StreamExt};
+        let metrics = crate::performance::metrics();
+        let _timing = metrics.time_operation(BATCH_OPERATION);
 
-        let results: Vec<TestResult> = stream::iter(connections)
-            .map(|conn| self.test_connection(conn))
-            .buffer_unordered(self.concurrency)
-            .collect()
+        let processor = BatchProcessor::default().with_concurrency(self.concurrency);
+        let results = processor
+            .process_concurrent(connections, |conn| self.test_connection(conn))
             .await;
+        metrics.record_concurrency(BATCH_OPERATION, processor.peak_concurrency());
 
         TestSummary::from_results(results)
     }
 }
 
+/// X.224 connection request carrying an RDP negotiation request for TLS and
+/// `CredSSP`, wrapped in a TPKT header
+const X224_CONNECTION_REQUEST: [u8; 19] = [
+    0x03, 0x00, 0x00, 0x13, // TPKT: version 3, length 19
+    0x0E, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, // X.224 CR, no cookie
+    0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, // RDP_NEG_REQ: TLS | CredSSP
+];
+
+/// Upper bound for a connection confirm; real ones are 19 bytes
+const MAX_TPKT_LENGTH: usize = 1024;
+
+/// Validates an SSH identification line and returns it without the line ending
+fn parse_ssh_banner(line: &str) -> TestResult2<String> {
+    let banner = line.trim();
+    if banner.starts_with("SSH-") {
+        Ok(banner.to_string())
+    } else {
+        Err(TestError::ProtocolError(format!(
+            "Invalid SSH banner: {banner}"
+        )))
+    }
+}
+
+/// Parses an X.224 connection confirm (without the TPKT header) and returns
+/// the security protocol the server selected
+fn parse_x224_confirm(tpdu: &[u8]) -> TestResult2<String> {
+    if tpdu.len() < 7 {
+        return Err(TestError::ProtocolError(
+            "Truncated X.224 connection confirm".to_string(),
+        ));
+    }
+    let code = tpdu[1] & 0xF0;
+    if code != 0xD0 {
+        return Err(TestError::ProtocolError(format!(
+            "Expected X.224 connection confirm, got TPDU code 0x{code:02X}"
+        )));
+    }
+
+    // Servers without protocol negotiation only support standard RDP security
+    let Some(negotiation) = tpdu.get(7..15) else {
+        return Ok("RDP (standard security)".to_string());
+    };
+    let value = u32::from_le_bytes([
+        negotiation[4],
+        negotiation[5],
+        negotiation[6],
+        negotiation[7],
+    ]);
+    match negotiation[0] {
+        // RDP_NEG_RSP
+        0x02 => Ok(match value {
+            0 => "RDP (standard security)".to_string(),
+            1 => "RDP (TLS)".to_string(),
+            2 => "RDP (CredSSP)".to_string(),
+            8 => "RDP (CredSSP with Early User Authorization)".to_string(),
+            other => format!("RDP (security protocol 0x{other:X})"),
+        }),
+        // RDP_NEG_FAILURE
+        0x03 => {
+            let reason = match value {
+                1 => "TLS required by server",
+                2 => "TLS not allowed by server",
+                3 => "no TLS certificate on server",
+                4 => "inconsistent negotiation flags",
+                5 => "CredSSP required by server",
+                6 => "TLS with user authentication required by server",
+                _ => "unknown failure code",
+            };
+            Err(TestError::ProtocolError(format!(
+                "RDP negotiation rejected: {reason} ({value})"
+            )))
+        }
+        other => Err(TestError::ProtocolError(format!(
+            "Unexpected RDP negotiation message type 0x{other:02X}"
+        ))),
+    }
+}
+
+/// Validates an RFB `ProtocolVersion` message and returns it without the
+/// line ending, e.g. `RFB 003.008`
+fn parse_rfb_version(message: &[u8; 12]) -> TestResult2<String> {
+    let valid = message.starts_with(b"RFB ")
+        && message[4..7].iter().all(u8::is_ascii_digit)
+        && message[7] == b'.'
+        && message[8..11].iter().all(u8::is_ascii_digit)
+        && message[11] == b'\n';
+    if valid {
+        Ok(String::from_utf8_lossy(&message[..11]).into_owned())
+    } else {
+        Err(TestError::ProtocolError(format!(
+            "Invalid RFB protocol version: {}",
+            String::from_utf8_lossy(message).trim()
+        )))
+    }
+}
+
 impl Default for ConnectionTester {
     fn default() -> Self {
         Self::new()
@@ -628,6 +868,59 @@ mod tests {
         assert_eq!(successful[0].connection_name, "S1");
     }
 
+    #[test]
+    fn test_test_summary_latency_stats() {
+        let latencies = [40, 10, 30, 20, 100];
+        let mut results: Vec<_> = latencies
+            .iter()
+            .map(|&ms| TestResult::success(Uuid::new_v4(), "Server".to_string(), ms))
+            .collect();
+        results.push(TestResult::failure(
+            Uuid::new_v4(),
+            "Down".to_string(),
+            "Connection refused",
+        ));
+        let summary = TestSummary::from_results(results);
+
+        assert_eq!(summary.latency_percentile(50.0), Some(30));
+        assert_eq!(summary.latency_percentile(95.0), Some(100));
+        assert_eq!(summary.latency_percentile(0.0), Some(10));
+        assert_eq!(summary.latency_percentile(100.0), Some(100));
+
+        // Mean 40, squared deviations 0 + 900 + 100 + 400 + 3600 over 5
+        let jitter = summary.jitter_ms.unwrap();
+        assert!((jitter - 1000.0_f64.sqrt()).abs() < 1e-9);
+
+        let mut summary = TestSummary::new();
+        assert_eq!(summary.latency_percentile(50.0), None);
+        assert_eq!(summary.jitter_ms, None);
+        summary.add_result(TestResult::failure(Uuid::new_v4(), "Down".to_string(), "x"));
+        assert_eq!(summary.latency_percentile(50.0), None);
+        summary.add_result(TestResult::success(Uuid::new_v4(), "Up".to_string(), 25));
+        assert_eq!(summary.latency_percentile(95.0), Some(25));
+        assert_eq!(summary.jitter_ms, Some(0.0));
+    }
+
+    #[test]
+    fn test_test_summary_latency_stats_survive_round_trip() {
+        let mut summary = TestSummary::new();
+        for ms in [40, 10, 30, 20, 100] {
+            summary.add_result(TestResult::success(
+                Uuid::new_v4(),
+                "Server".to_string(),
+                ms,
+            ));
+        }
+        assert_eq!(summary.latency_percentile(0.0), Some(10));
+        assert_eq!(summary.latency_percentile(50.0), Some(30));
+
+        let json = serde_json::to_string(&summary).unwrap();
+        let restored: TestSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.latency_percentile(50.0), Some(30));
+        assert_eq!(restored.latency_percentile(95.0), Some(100));
+        assert_eq!(restored.jitter_ms, summary.jitter_ms);
+    }
+
     #[test]
     fn test_connection_tester_new() {
         let tester = ConnectionTester::new();
@@ -679,4 +972,94 @@ mod tests {
             "Protocol handshake failed: Invalid banner"
         );
     }
+
+    #[test]
+    fn test_parse_ssh_banner() {
+        assert_eq!(
+            parse_ssh_banner("SSH-2.0-OpenSSH_9.6\r\n"),
+            Ok("SSH-2.0-OpenSSH_9.6".to_string())
+        );
+        assert!(matches!(
+            parse_ssh_banner("HTTP/1.1 400 Bad Request\r\n"),
+            Err(TestError::ProtocolError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_x224_confirm() {
+        let confirm = |neg: &[u8]| {
+            let mut tpdu = vec![0x0E, 0xD0, 0x00, 0x00, 0x12, 0x34, 0x00];
+            tpdu.extend_from_slice(neg);
+            parse_x224_confirm(&tpdu)
+        };
+
+        assert_eq!(
+            confirm(&[0x02, 0x00, 0x08, 0x00, 0x02, 0x00, 0x00, 0x00]),
+            Ok("RDP (CredSSP)".to_string())
+        );
+        assert_eq!(confirm(&[]), Ok("RDP (standard security)".to_string()));
+
+        let rejected = confirm(&[0x03, 0x00, 0x08, 0x00, 0x05, 0x00, 0x00, 0x00]);
+        assert!(
+            matches!(&rejected, Err(TestError::ProtocolError(reason)) if reason.contains("CredSSP required"))
+        );
+
+        // Disconnect request instead of a confirm
+        assert!(parse_x224_confirm(&[0x06, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00]).is_err());
+        assert!(parse_x224_confirm(&[0x02, 0xD0]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rfb_version() {
+        assert_eq!(
+            parse_rfb_version(b"RFB 003.008\n"),
+            Ok("RFB 003.008".to_string())
+        );
+        assert!(parse_rfb_version(b"SSH-2.0-x\r\n\0").is_err());
+        assert!(parse_rfb_version(b"RFB 3.8\n\0\0\0\0").is_err());
+    }
+
+    /// Serves `reply` to the first client on a local port and returns the port
+    fn serve_once(runtime: &tokio::runtime::Runtime, reply: &'static [u8]) -> u16 {
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        runtime.spawn(async move {
+            // The first accept is the port check, the second the probe
+            for _ in 0..2 {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let _ = socket.write_all(reply).await;
+                let mut buf = [0u8; 64];
+                let _ = socket.read(&mut buf).await;
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_connection_probes_handshake() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let tester = ConnectionTester::with_timeout(Duration::from_secs(5));
+
+        let port = serve_once(&runtime, b"RFB 003.008\n");
+        let vnc = Connection::new_vnc("VNC".to_string(), "127.0.0.1".to_string(), port);
+        let result = runtime.block_on(tester.test_connection(&vnc));
+        assert!(result.is_success(), "{:?}", result.error);
+        assert_eq!(
+            result.details.get("server_version").map(String::as_str),
+            Some("RFB 003.008")
+        );
+
+        // Accepts the connection but answers with something else
+        let port = serve_once(&runtime, b"HTTP/1.1 400 Bad Request\r\n\r\n");
+        let rdp = Connection::new_rdp("RDP".to_string(), "127.0.0.1".to_string(), port);
+        let result = runtime.block_on(tester.test_connection(&rdp));
+        assert!(result.is_failure());
+        assert_eq!(
+            result.details.get("port_open").map(String::as_str),
+            Some("true")
+        );
+        assert!(result.error.unwrap().contains("TPKT"));
+    }
 }
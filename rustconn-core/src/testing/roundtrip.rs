@@ -0,0 +1,329 @@
+//! Export/import round-trip assertions for format authors.
+//!
+//! [`assert_roundtrip`] exports connections with the exporter for a format,
+//! imports the written output with the matching importer and checks that
+//! every connection survived. Only the fields the format can carry are
+//! compared; [`roundtrip_fields`] documents them per format.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::export::{
+    AnsibleExporter, AsbruExporter, ExportFormat, ExportOptions, ExportTarget, MobaXtermExporter,
+    NativeExport, RemminaExporter, RoyalTsExporter, SshConfigExporter, TermiusExporter,
+};
+use crate::import::{
+    AnsibleInventoryImporter, AsbruImporter, ImportSource, MobaXtermImporter, RemminaImporter,
+    RoyalTsImporter, SshConfigImporter,
+};
+use crate::models::{Connection, ProtocolConfig, SshKeySource};
+
+/// A connection field compared by [`assert_roundtrip`].
+///
+/// Connections are always matched by name, so the name is not listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundtripField {
+    /// Protocol type
+    Protocol,
+    /// Host name or address
+    Host,
+    /// Port number
+    Port,
+    /// User name
+    Username,
+    /// Windows domain
+    Domain,
+    /// Free-form description
+    Description,
+    /// Tags
+    Tags,
+    /// SSH identity file
+    KeyPath,
+    /// SSH jump host chain
+    ProxyJump,
+}
+
+impl RoundtripField {
+    /// Returns the field name used in assertion messages
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Protocol => "protocol",
+            Self::Host => "host",
+            Self::Port => "port",
+            Self::Username => "username",
+            Self::Domain => "domain",
+            Self::Description => "description",
+            Self::Tags => "tags",
+            Self::KeyPath => "key_path",
+            Self::ProxyJump => "proxy_jump",
+        }
+    }
+
+    /// Returns the field value of a connection in comparable form
+    fn value(self, conn: &Connection) -> String {
+        match self {
+            Self::Protocol => conn.protocol.to_string(),
+            Self::Host => conn.host.clone(),
+            Self::Port => conn.port.to_string(),
+            Self::Username => format!("{:?}", conn.username),
+            Self::Domain => format!("{:?}", conn.domain),
+            Self::Description => format!("{:?}", conn.description),
+            Self::Tags => format!("{:?}", conn.tags),
+            Self::KeyPath => format!("{:?}", ssh_key_path(conn)),
+            Self::ProxyJump => format!(
+                "{:?}",
+                match conn.protocol_config {
+                    ProtocolConfig::Ssh(ref ssh) => ssh.proxy_jump.as_deref(),
+                    _ => None,
+                }
+            ),
+        }
+    }
+}
+
+/// Returns the fields a format preserves through export and re-import.
+///
+/// | Format      | Fields                                                    |
+/// |-------------|-----------------------------------------------------------|
+/// | Native      | all                                                       |
+/// | SSH Config  | protocol, host, port, username, key path, proxy jump      |
+/// | Ansible     | protocol, host, port, username, key path                  |
+/// | Asbru-CM    | protocol, host, port, username, description               |
+/// | Remmina     | protocol, host, port, username, domain                    |
+/// | Royal TS    | protocol, host, port, username, domain                    |
+/// | MobaXterm   | protocol, host, port, username                            |
+/// | Termius     | none, Termius documents cannot be imported                |
+#[must_use]
+pub const fn roundtrip_fields(format: ExportFormat) -> &'static [RoundtripField] {
+    use RoundtripField::{
+        Description, Domain, Host, KeyPath, Port, Protocol, ProxyJump, Tags, Username,
+    };
+    match format {
+        ExportFormat::Native => &[
+            Protocol,
+            Host,
+            Port,
+            Username,
+            Domain,
+            Description,
+            Tags,
+            KeyPath,
+            ProxyJump,
+        ],
+        ExportFormat::SshConfig => &[Protocol, Host, Port, Username, KeyPath, ProxyJump],
+        ExportFormat::Ansible => &[Protocol, Host, Port, Username, KeyPath],
+        ExportFormat::Asbru => &[Protocol, Host, Port, Username, Description],
+        ExportFormat::Remmina => &[Protocol, Host, Port, Username, Domain],
+        ExportFormat::RoyalTs => &[Protocol, Host, Port, Username, Domain],
+        ExportFormat::MobaXterm => &[Protocol, Host, Port, Username],
+        ExportFormat::Termius => &[],
+    }
+}
+
+/// Exports `connections` in `format`, re-imports them and asserts that
+/// every connection the format supports comes back with the fields listed
+/// by [`roundtrip_fields`] unchanged.
+///
+/// Connections are matched by name, so names must be unique. Connections
+/// whose protocol the exporter does not support must not come back.
+/// The export is written to a temporary directory that is removed afterwards.
+///
+/// # Panics
+///
+/// Panics if the export or import fails, if a connection is missing or
+/// differs after the round trip, or if the format has no importer.
+pub fn assert_roundtrip(connections: &[Connection], format: ExportFormat) {
+    let fields = roundtrip_fields(format);
+    assert!(
+        !fields.is_empty(),
+        "{} exports cannot be imported back",
+        format.display_name()
+    );
+
+    let dir = TempDir::new();
+    let imported = export_and_import(connections, format, dir.path());
+
+    for original in connections {
+        let found = imported.iter().find(|c| c.name == original.name);
+        if !supports_protocol(format, original) {
+            assert!(
+                found.is_none(),
+                "{}: unsupported connection '{}' was exported",
+                format.display_name(),
+                original.name
+            );
+            continue;
+        }
+        let Some(reimported) = found else {
+            panic!(
+                "{}: connection '{}' is missing after round-trip",
+                format.display_name(),
+                original.name
+            );
+        };
+        for field in fields {
+            assert_eq!(
+                field.value(reimported),
+                field.value(original),
+                "{}: {} of '{}' changed after round-trip",
+                format.display_name(),
+                field.name(),
+                original.name
+            );
+        }
+    }
+}
+
+/// Returns whether the exporter for `format` writes the connection
+fn supports_protocol(format: ExportFormat, conn: &Connection) -> bool {
+    exporter(format).is_none_or(|e| e.supports_protocol(&conn.protocol))
+}
+
+/// Returns the trait exporter for a format, `None` for the native format
+fn exporter(format: ExportFormat) -> Option<Box<dyn ExportTarget>> {
+    Some(match format {
+        ExportFormat::Ansible => Box::new(AnsibleExporter::new()),
+        ExportFormat::SshConfig => Box::new(SshConfigExporter::new()),
+        ExportFormat::Remmina => Box::new(RemminaExporter::new()),
+        ExportFormat::Asbru => Box::new(AsbruExporter::new()),
+        ExportFormat::RoyalTs => Box::new(RoyalTsExporter::new()),
+        ExportFormat::MobaXterm => Box::new(MobaXtermExporter::new()),
+        ExportFormat::Termius => Box::new(TermiusExporter::new()),
+        ExportFormat::Native => return None,
+    })
+}
+
+/// Returns the importer reading what the exporter for a format writes
+fn importer(format: ExportFormat) -> Option<Box<dyn ImportSource>> {
+    Some(match format {
+        ExportFormat::Ansible => Box::new(AnsibleInventoryImporter::new()),
+        ExportFormat::SshConfig => Box::new(SshConfigImporter::new()),
+        ExportFormat::Remmina => Box::new(RemminaImporter::new()),
+        ExportFormat::Asbru => Box::new(AsbruImporter::new()),
+        ExportFormat::RoyalTs => Box::new(RoyalTsImporter::new()),
+        ExportFormat::MobaXterm => Box::new(MobaXtermImporter::new()),
+        ExportFormat::Native | ExportFormat::Termius => return None,
+    })
+}
+
+/// Writes the export below `dir` and returns the re-imported connections
+fn export_and_import(
+    connections: &[Connection],
+    format: ExportFormat,
+    dir: &Path,
+) -> Vec<Connection> {
+    let output = if format.exports_to_directory() {
+        dir.join("export")
+    } else {
+        dir.join(format!("export.{}", format.file_extension()))
+    };
+
+    let Some(exporter) = exporter(format) else {
+        let export = NativeExport::with_data(connections.to_vec(), vec![], vec![], vec![], vec![]);
+        export.to_file(&output).expect("native export failed");
+        return NativeExport::from_file(&output)
+            .expect("native import failed")
+            .connections;
+    };
+
+    let options = ExportOptions::new(format, output.clone());
+    exporter
+        .export(connections, &[], &options)
+        .unwrap_or_else(|e| panic!("{} export failed: {e}", format.display_name()));
+
+    let source = importer(format).expect("format has no importer");
+    let paths = if output.is_dir() {
+        let mut paths: Vec<PathBuf> = fs::read_dir(&output)
+            .expect("export directory is unreadable")
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        paths.sort();
+        paths
+    } else {
+        vec![output]
+    };
+
+    let mut imported = Vec::new();
+    for path in paths {
+        let result = source
+            .import_from_path(&path)
+            .unwrap_or_else(|e| panic!("{} import failed: {e}", format.display_name()));
+        imported.extend(result.connections);
+    }
+    imported
+}
+
+/// Returns the SSH identity file, whichever field holds it
+fn ssh_key_path(conn: &Connection) -> Option<&Path> {
+    let ProtocolConfig::Ssh(ref ssh) = conn.protocol_config else {
+        return None;
+    };
+    match ssh.key_source {
+        SshKeySource::File { ref path } => Some(path.as_path()),
+        _ => ssh.key_path.as_deref(),
+    }
+}
+
+/// Temporary directory removed when dropped, also when an assertion fails
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("rustconn-roundtrip-{}", Uuid::new_v4()));
+        fs::create_dir_all(&path).expect("failed to create temporary directory");
+        Self(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_connections() -> Vec<Connection> {
+        let mut web = Connection::new_ssh("web".to_string(), "web.example.com".to_string(), 2222)
+            .with_username("deploy");
+        web.description = Some("Frontend".to_string());
+        web.tags = vec!["prod".to_string()];
+        if let ProtocolConfig::Ssh(ref mut ssh) = web.protocol_config {
+            ssh.key_path = Some(PathBuf::from("/home/user/.ssh/id_ed25519"));
+            ssh.proxy_jump = Some("bastion.example.com".to_string());
+        }
+
+        let mut desktop = Connection::new_rdp("desktop".to_string(), "10.0.0.5".to_string(), 3389)
+            .with_username("admin");
+        desktop.domain = Some("CORP".to_string());
+        desktop.description = Some("Office PC".to_string());
+
+        let vnc = Connection::new_vnc("console".to_string(), "vnc.example.com".to_string(), 5901);
+        vec![web, desktop, vnc]
+    }
+
+    #[test]
+    fn test_roundtrip_all_importable_formats() {
+        let connections = sample_connections();
+        for format in ExportFormat::all() {
+            if !roundtrip_fields(*format).is_empty() {
+                assert_roundtrip(&connections, *format);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be imported back")]
+    fn test_roundtrip_rejects_export_only_format() {
+        assert_roundtrip(&sample_connections(), ExportFormat::Termius);
+    }
+}
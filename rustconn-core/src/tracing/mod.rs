@@ -4,15 +4,22 @@
 //! enabling structured logging with spans for key operations like connection establishment,
 //! search execution, import/export, and credential resolution.
 
+mod ring_buffer;
+
+pub use ring_buffer::{
+    EventRingBuffer, RecordedEvent, RingBufferLayer, DEFAULT_RING_BUFFER_CAPACITY,
+};
+
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
 
 use thiserror::Error;
 use tracing::Level;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{reload, EnvFilter, Registry};
 
 /// Global flag indicating whether tracing has been initialized
 static TRACING_INITIALIZED: AtomicBool = AtomicBool::new(false);
@@ -20,6 +27,12 @@ static TRACING_INITIALIZED: AtomicBool = AtomicBool::new(false);
 /// Global tracing configuration
 static TRACING_CONFIG: OnceLock<TracingConfig> = OnceLock::new();
 
+/// Handle for replacing the active filter after initialization
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Event buffer installed by [`TracingOutput::InMemoryRingBuffer`]
+static RING_BUFFER: OnceLock<Arc<EventRingBuffer>> = OnceLock::new();
+
 /// Errors that can occur during tracing initialization
 #[derive(Debug, Error)]
 pub enum TracingError {
@@ -38,6 +51,14 @@ pub enum TracingError {
     /// Failed to create log file
     #[error("Failed to create log file: {0}")]
     FileCreationFailed(String),
+
+    /// Tracing has not been initialized
+    #[error("Tracing has not been initialized")]
+    NotInitialized,
+
+    /// Failed to replace the active filter
+    #[error("Failed to change log level: {0}")]
+    ReloadFailed(String),
 }
 
 /// Result type for tracing operations
@@ -120,6 +141,13 @@ pub enum TracingOutput {
         /// Endpoint URL for the collector
         endpoint: String,
     },
+    /// Keep recent events in memory for diagnostics, and write them to stderr
+    ///
+    /// Events are queried with [`get_recent_events`].
+    InMemoryRingBuffer {
+        /// Maximum number of events kept; older events are dropped
+        capacity: usize,
+    },
 }
 
 /// Configuration for tracing initialization
@@ -253,9 +281,10 @@ pub fn init_tracing(config: &TracingConfig) -> TracingResult<()> {
         EnvFilter::try_new(custom_filter)
             .map_err(|e| TracingError::InitializationFailed(e.to_string()))?
     } else {
-        EnvFilter::try_new(format!("rustconn={}", config.level))
-            .unwrap_or_else(|_| EnvFilter::new("info"))
+        level_filter(config.level)
     };
+    // Wrapped so the level can be changed at runtime
+    let (filter, filter_handle) = reload::Layer::new(filter);
 
     // Initialize based on output type
     match &config.output {
@@ -321,8 +350,26 @@ pub fn init_tracing(config: &TracingConfig) -> TracingResult<()> {
                 "OpenTelemetry output is not yet implemented, falling back to stderr"
             );
         }
+        TracingOutput::InMemoryRingBuffer { capacity } => {
+            let buffer = RING_BUFFER.get_or_init(|| Arc::new(EventRingBuffer::new(*capacity)));
+
+            tracing_subscriber::registry()
+                .with(filter)
+                .with(RingBufferLayer::new(Arc::clone(buffer)))
+                .with(
+                    tracing_subscriber::fmt::layer()
+                        .with_target(true)
+                        .with_level(true)
+                        .with_thread_ids(config.profiling_enabled)
+                        .with_writer(std::io::stderr),
+                )
+                .try_init()
+                .map_err(|e| TracingError::InitializationFailed(e.to_string()))?;
+        }
     }
 
+    let _ = FILTER_HANDLE.set(filter_handle);
+
     tracing::info!(
         level = %config.level,
         profiling = config.profiling_enabled,
@@ -332,6 +379,34 @@ pub fn init_tracing(config: &TracingConfig) -> TracingResult<()> {
     Ok(())
 }
 
+/// Changes the log level of the running subscriber
+///
+/// Takes effect immediately for subsequent events on every thread. A custom
+/// filter set through [`TracingConfig::with_filter`] is replaced by the
+/// level filter.
+///
+/// # Errors
+///
+/// Returns `TracingError::NotInitialized` if [`init_tracing`] has not been
+/// called, or `TracingError::ReloadFailed` if the filter cannot be replaced.
+pub fn set_tracing_level(level: TracingLevel) -> TracingResult<()> {
+    if !is_tracing_initialized() {
+        return Err(TracingError::NotInitialized);
+    }
+    let handle = FILTER_HANDLE.get().ok_or(TracingError::NotInitialized)?;
+    handle
+        .reload(level_filter(level))
+        .map_err(|e| TracingError::ReloadFailed(e.to_string()))?;
+
+    tracing::info!(level = %level, "Log level changed");
+    Ok(())
+}
+
+/// Builds the filter used for a plain log level
+fn level_filter(level: TracingLevel) -> EnvFilter {
+    EnvFilter::try_new(format!("rustconn={level}")).unwrap_or_else(|_| EnvFilter::new("info"))
+}
+
 /// Checks if tracing has been initialized
 #[must_use]
 pub fn is_tracing_initialized() -> bool {
@@ -344,6 +419,25 @@ pub fn get_tracing_config() -> Option<&'static TracingConfig> {
     TRACING_CONFIG.get()
 }
 
+/// Returns the in-memory event buffer, if tracing was initialized with
+/// [`TracingOutput::InMemoryRingBuffer`]
+#[must_use]
+pub fn ring_buffer() -> Option<&'static Arc<EventRingBuffer>> {
+    RING_BUFFER.get()
+}
+
+/// Returns the buffered events recorded for a connection, oldest first
+///
+/// Events belong to a connection when they, or a span they were emitted in,
+/// carry a `connection.id` field. Returns an empty list if the in-memory
+/// buffer is not in use.
+#[must_use]
+pub fn get_recent_events(connection_id: Uuid) -> Vec<RecordedEvent> {
+    ring_buffer().map_or_else(Vec::new, |buffer| {
+        buffer.events_for_connection(connection_id)
+    })
+}
+
 /// Resets the tracing initialization state (for testing only)
 ///
 /// # Safety
@@ -517,6 +611,15 @@ mod tests {
         assert!(!config.profiling_enabled);
     }
 
+    #[test]
+    fn test_set_tracing_level_requires_init() {
+        assert!(!is_tracing_initialized());
+        assert!(matches!(
+            set_tracing_level(TracingLevel::Debug),
+            Err(TracingError::NotInitialized)
+        ));
+    }
+
     #[test]
     fn test_tracing_output_default() {
         let output = TracingOutput::default();
@@ -0,0 +1,303 @@
+//! In-memory capture of recent tracing events
+//!
+//! [`RingBufferLayer`] records every event into a bounded [`EventRingBuffer`],
+//! tagging it with the connection it belongs to. The connection is taken
+//! from a `connection.id` (or `connection_id`) field on the event itself or
+//! on any enclosing span, so everything logged inside a connection attempt
+//! can be pulled back out for diagnostics.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use uuid::Uuid;
+
+use super::TracingLevel;
+
+/// Default number of events kept by the ring buffer
+pub const DEFAULT_RING_BUFFER_CAPACITY: usize = 1000;
+
+/// Field names that identify the connection an event belongs to
+const CONNECTION_ID_FIELDS: &[&str] = &["connection.id", "connection_id"];
+
+/// A captured tracing event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// When the event was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Event level
+    pub level: TracingLevel,
+    /// Module path or custom target of the event
+    pub target: String,
+    /// Event message
+    pub message: String,
+    /// Remaining structured fields, in recording order
+    pub fields: Vec<(String, String)>,
+    /// Connection the event belongs to, if any
+    pub connection_id: Option<Uuid>,
+}
+
+impl fmt::Display for RecordedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:>5} {}: {}",
+            self.timestamp.to_rfc3339(),
+            self.level.to_string().to_uppercase(),
+            self.target,
+            self.message
+        )?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Bounded buffer of recent events; the oldest event is dropped when full
+#[derive(Debug)]
+pub struct EventRingBuffer {
+    capacity: usize,
+    events: Mutex<VecDeque<RecordedEvent>>,
+}
+
+impl EventRingBuffer {
+    /// Creates a buffer holding at most `capacity` events (minimum 1)
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Returns the maximum number of events kept
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Appends an event, dropping the oldest one if the buffer is full
+    pub fn push(&self, event: RecordedEvent) {
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Returns all buffered events, oldest first
+    #[must_use]
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        let events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        events.iter().cloned().collect()
+    }
+
+    /// Returns the buffered events of one connection, oldest first
+    #[must_use]
+    pub fn events_for_connection(&self, connection_id: Uuid) -> Vec<RecordedEvent> {
+        let events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        events
+            .iter()
+            .filter(|e| e.connection_id == Some(connection_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of buffered events
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns true if no events are buffered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all buffered events
+    pub fn clear(&self) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+/// Tracing layer that records events into an [`EventRingBuffer`]
+#[derive(Debug, Clone)]
+pub struct RingBufferLayer {
+    buffer: Arc<EventRingBuffer>,
+}
+
+impl RingBufferLayer {
+    /// Creates a layer writing into the given buffer
+    #[must_use]
+    pub const fn new(buffer: Arc<EventRingBuffer>) -> Self {
+        Self { buffer }
+    }
+
+    /// Returns the buffer this layer writes into
+    #[must_use]
+    pub const fn buffer(&self) -> &Arc<EventRingBuffer> {
+        &self.buffer
+    }
+}
+
+/// Connection ID stored in the extensions of a span that carries one
+struct SpanConnectionId(Uuid);
+
+impl<S> Layer<S> for RingBufferLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldCollector::default();
+        attrs.record(&mut visitor);
+        if let (Some(connection_id), Some(span)) = (visitor.connection_id(), ctx.span(id)) {
+            span.extensions_mut()
+                .insert(SpanConnectionId(connection_id));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldCollector::default();
+        values.record(&mut visitor);
+        if let (Some(connection_id), Some(span)) = (visitor.connection_id(), ctx.span(id)) {
+            span.extensions_mut()
+                .replace(SpanConnectionId(connection_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldCollector::default();
+        event.record(&mut visitor);
+
+        // The innermost span with a connection ID wins
+        let connection_id = visitor.connection_id().or_else(|| {
+            ctx.event_scope(event)?
+                .find_map(|span| span.extensions().get::<SpanConnectionId>().map(|id| id.0))
+        });
+
+        let metadata = event.metadata();
+        self.buffer.push(RecordedEvent {
+            timestamp: Utc::now(),
+            level: level_of(*metadata.level()),
+            target: metadata.target().to_string(),
+            message: visitor.message.unwrap_or_default(),
+            fields: visitor.fields,
+            connection_id,
+        });
+    }
+}
+
+const fn level_of(level: Level) -> TracingLevel {
+    match level {
+        Level::ERROR => TracingLevel::Error,
+        Level::WARN => TracingLevel::Warn,
+        Level::INFO => TracingLevel::Info,
+        Level::DEBUG => TracingLevel::Debug,
+        Level::TRACE => TracingLevel::Trace,
+    }
+}
+
+/// Collects the message and fields of an event or span
+#[derive(Default)]
+struct FieldCollector {
+    message: Option<String>,
+    fields: Vec<(String, String)>,
+}
+
+impl FieldCollector {
+    fn connection_id(&self) -> Option<Uuid> {
+        self.fields
+            .iter()
+            .find(|(name, _)| CONNECTION_ID_FIELDS.contains(&name.as_str()))
+            .and_then(|(_, value)| value.parse().ok())
+    }
+}
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.fields
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        } else {
+            self.fields
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn capture(capacity: usize, f: impl FnOnce()) -> Arc<EventRingBuffer> {
+        let buffer = Arc::new(EventRingBuffer::new(capacity));
+        let subscriber =
+            tracing_subscriber::registry().with(RingBufferLayer::new(Arc::clone(&buffer)));
+        tracing::subscriber::with_default(subscriber, f);
+        buffer
+    }
+
+    #[test]
+    fn test_events_are_tagged_with_span_connection() {
+        let id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let buffer = capture(10, || {
+            let _span = tracing::info_span!("connection.establish", connection.id = %id).entered();
+            tracing::info!("resolving host");
+            {
+                let _inner = tracing::debug_span!("port_check").entered();
+                tracing::warn!(port = 22, "port closed");
+            }
+            tracing::info!(connection_id = %other, "unrelated");
+        });
+
+        let events = buffer.events_for_connection(id);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].message, "resolving host");
+        assert_eq!(events[1].level, TracingLevel::Warn);
+        assert_eq!(
+            events[1].fields,
+            vec![("port".to_string(), "22".to_string())]
+        );
+        assert!(events[1].to_string().ends_with("port closed port=22"));
+        assert_eq!(buffer.events_for_connection(other).len(), 1);
+    }
+
+    #[test]
+    fn test_buffer_drops_oldest_events() {
+        let buffer = capture(3, || {
+            for i in 0..5 {
+                tracing::info!("event {i}");
+            }
+        });
+
+        let messages: Vec<_> = buffer.events().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, ["event 2", "event 3", "event 4"]);
+        assert_eq!(EventRingBuffer::new(0).capacity(), 1);
+    }
+}
@@ -4,12 +4,18 @@
 //! resolution across scopes, and substitution in strings.
 
 use std::collections::{HashMap, HashSet};
-use std::sync::LazyLock;
+use std::fmt;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
 
 use regex::Regex;
+use secrecy::{ExposeSecret, SecretString};
+use tracing::warn;
 use uuid::Uuid;
 
-use super::{Variable, VariableError, VariableResult, VariableScope, MAX_NESTING_DEPTH};
+use super::{
+    Variable, VariableError, VariableResult, VariableScope, VariableType, MAX_NESTING_DEPTH,
+};
+use crate::secret::SecretManager;
 
 /// Cached regex for variable extraction: matches `${var_name}` patterns
 static VARIABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
@@ -23,7 +29,14 @@ static VARIABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 /// - Substituting all variables in a string
 /// - Parsing variable references from strings
 /// - Detecting circular references
-#[derive(Debug, Default)]
+///
+/// Variables of type [`VariableType::Secret`] are looked up through the
+/// configured [`SecretManager`] by [`VariableManager::resolve_with_secrets`],
+/// [`VariableManager::substitute_with_secrets`] and
+/// [`VariableManager::load_secrets`]. The passwords are kept in memory, so
+/// the synchronous [`VariableManager::resolve`] and
+/// [`VariableManager::substitute`] resolve them once they have been loaded.
+#[derive(Default)]
 pub struct VariableManager {
     /// Global variables available to all connections
     global_vars: HashMap<String, Variable>,
@@ -33,6 +46,30 @@ pub struct VariableManager {
     connection_vars: HashMap<Uuid, HashMap<String, Variable>>,
     /// Mapping from connection ID to document ID for scope chain resolution
     connection_to_document: HashMap<Uuid, Uuid>,
+    /// Secret manager used to resolve secret variables
+    secret_manager: Option<Arc<SecretManager>>,
+    /// Passwords of secret variables, keyed by secret reference
+    secret_cache: RwLock<HashMap<String, SecretString>>,
+}
+
+impl fmt::Debug for VariableManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VariableManager")
+            .field("global_vars", &self.global_vars)
+            .field("document_vars", &self.document_vars)
+            .field("connection_vars", &self.connection_vars)
+            .field("connection_to_document", &self.connection_to_document)
+            .field("has_secret_manager", &self.secret_manager.is_some())
+            .field(
+                "cached_secrets",
+                &self
+                    .secret_cache
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .len(),
+            )
+            .finish()
+    }
 }
 
 impl VariableManager {
@@ -42,6 +79,50 @@ impl VariableManager {
         Self::default()
     }
 
+    /// Sets the secret manager used to resolve secret variables
+    ///
+    /// Passwords loaded from a previous secret manager are discarded.
+    pub fn set_secret_manager(&mut self, secret_manager: Arc<SecretManager>) {
+        self.secret_manager = Some(secret_manager);
+        self.clear_secret_cache();
+    }
+
+    /// Forgets the loaded secret passwords, e.g. when the backend locks
+    pub fn clear_secret_cache(&self) {
+        self.secret_cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Fetches the passwords of every secret variable in every scope
+    ///
+    /// Afterwards the synchronous [`Self::resolve`] and [`Self::substitute`]
+    /// resolve secret variables too. Call this after connecting to the
+    /// secret backend and again whenever it is unlocked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VariableError::SecretUnresolved` for the first secret
+    /// reference with no password in the secret manager; the others are
+    /// still loaded.
+    pub async fn load_secrets(&self) -> VariableResult<()> {
+        let secrets: Vec<&Variable> = self
+            .global_vars
+            .values()
+            .chain(self.document_vars.values().flat_map(HashMap::values))
+            .chain(self.connection_vars.values().flat_map(HashMap::values))
+            .filter(|var| var.value_type == VariableType::Secret)
+            .collect();
+        let mut first_error = None;
+        for var in secrets {
+            if let Err(e) = self.fetch_secret(var).await {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
     // ========== Variable Management ==========
 
     /// Sets a global variable
@@ -65,6 +146,31 @@ impl VariableManager {
             .insert(variable.name.clone(), variable);
     }
 
+    /// Validates a variable against its type and stores it in `scope`
+    ///
+    /// Unlike the scope-specific setters this rejects values that do not
+    /// match the variable's [`VariableType`](super::VariableType), and stores
+    /// accepted values in canonical form.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VariableError::EmptyName` for a blank name and
+    /// `VariableError::InvalidValue` if the value does not match the type.
+    pub fn set(&mut self, scope: VariableScope, mut variable: Variable) -> VariableResult<()> {
+        if variable.name.trim().is_empty() {
+            return Err(VariableError::EmptyName);
+        }
+        variable.validate()?;
+        match scope {
+            VariableScope::Global => self.set_global(variable),
+            VariableScope::Document(document_id) => self.set_document(document_id, variable),
+            VariableScope::Connection(connection_id) => {
+                self.set_connection(connection_id, variable);
+            }
+        }
+        Ok(())
+    }
+
     /// Associates a connection with a document for scope chain resolution
     pub fn set_connection_document(&mut self, connection_id: Uuid, document_id: Uuid) {
         self.connection_to_document
@@ -156,11 +262,32 @@ impl VariableManager {
     ///
     /// # Errors
     ///
-    /// Returns `VariableError::Undefined` if the variable is not found in any scope.
+    /// Returns `VariableError::Undefined` if the variable is not found in any scope,
+    /// and `VariableError::SecretUnresolved` if it depends on a secret variable
+    /// whose password has not been loaded.
     pub fn resolve(&self, name: &str, scope: VariableScope) -> VariableResult<String> {
         self.resolve_with_depth(name, scope, 0, &mut HashSet::new())
     }
 
+    /// Resolves a variable reference, fetching secret variables it depends on
+    ///
+    /// Only the secret variables reachable from `name` are looked up, using
+    /// their value as the key into the secret manager and the stored password
+    /// as the resolved value.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::resolve`]; `VariableError::SecretUnresolved`
+    /// is returned if a secret reference has no password in the secret manager.
+    pub async fn resolve_with_secrets(
+        &self,
+        name: &str,
+        scope: VariableScope,
+    ) -> VariableResult<String> {
+        self.fetch_secrets(&format!("${{{name}}}"), scope).await?;
+        self.resolve_with_depth(name, scope, 0, &mut HashSet::new())
+    }
+
     /// Internal resolution with depth tracking and cycle detection
     fn resolve_with_depth(
         &self,
@@ -181,6 +308,16 @@ impl VariableManager {
         let variable = self.lookup_in_scope_chain(name, scope);
 
         match variable {
+            Some(var) if var.value_type == VariableType::Secret => self
+                .secret_cache
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(&var.value)
+                .map(|secret| secret.expose_secret().to_string())
+                .ok_or_else(|| VariableError::SecretUnresolved {
+                    name: name.to_string(),
+                    reference: var.value.clone(),
+                }),
             Some(var) => {
                 // Check if the value contains nested variable references
                 let refs = Self::parse_references(&var.value)?;
@@ -192,7 +329,8 @@ impl VariableManager {
                     let result =
                         self.substitute_with_depth(&var.value, scope, depth + 1, visited)?;
                     visited.remove(name);
-                    Ok(result)
+                    // Typed values built from references are checked once resolved
+                    var.value_type.format(name, &result)
                 }
             }
             None => Err(VariableError::Undefined(name.to_string())),
@@ -256,10 +394,107 @@ impl VariableManager {
     /// - A referenced variable is undefined (logs warning, uses empty string)
     /// - Circular reference is detected
     /// - Maximum nesting depth is exceeded
+    /// - A secret variable whose password has not been loaded is referenced
+    ///   (see [`Self::load_secrets`] and [`Self::substitute_with_secrets`])
     pub fn substitute(&self, input: &str, scope: VariableScope) -> VariableResult<String> {
         self.substitute_with_depth(input, scope, 0, &mut HashSet::new())
     }
 
+    /// Substitutes all variable references, fetching referenced secret variables
+    ///
+    /// Fetched passwords are kept in memory for later synchronous
+    /// substitution; they are never written back to the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::substitute`]; `VariableError::SecretUnresolved`
+    /// is returned if a secret reference has no password in the secret manager.
+    pub async fn substitute_with_secrets(
+        &self,
+        input: &str,
+        scope: VariableScope,
+    ) -> VariableResult<String> {
+        self.fetch_secrets(input, scope).await?;
+        self.substitute_with_depth(input, scope, 0, &mut HashSet::new())
+    }
+
+    /// Loads the secret variables reachable from `input` from the secret manager
+    async fn fetch_secrets(&self, input: &str, scope: VariableScope) -> VariableResult<()> {
+        let mut pending = Vec::new();
+        self.collect_secret_variables(input, scope, 0, &mut HashSet::new(), &mut pending);
+        for var in pending {
+            self.fetch_secret(var).await?;
+        }
+        Ok(())
+    }
+
+    /// Loads the password of one secret variable unless it is already cached
+    async fn fetch_secret(&self, var: &Variable) -> VariableResult<()> {
+        let cached = self
+            .secret_cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&var.value);
+        if cached {
+            return Ok(());
+        }
+        let password = match &self.secret_manager {
+            Some(manager) => match manager.retrieve(&var.value).await {
+                Ok(credentials) => credentials.and_then(|c| c.password),
+                Err(e) => {
+                    warn!(
+                        variable = %var.name,
+                        reference = %var.value,
+                        "Failed to look up secret variable: {e}"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+        let password = password.ok_or_else(|| VariableError::SecretUnresolved {
+            name: var.name.clone(),
+            reference: var.value.clone(),
+        })?;
+        self.secret_cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(var.value.clone(), password);
+        Ok(())
+    }
+
+    /// Collects the secret variables referenced, directly or through other
+    /// variables, by `input`
+    fn collect_secret_variables<'a>(
+        &'a self,
+        input: &str,
+        scope: VariableScope,
+        depth: usize,
+        seen: &mut HashSet<String>,
+        found: &mut Vec<&'a Variable>,
+    ) {
+        // Cycles and depth overflows are reported by the substitution itself
+        if depth > MAX_NESTING_DEPTH {
+            return;
+        }
+        let Ok(refs) = Self::parse_references(input) else {
+            return;
+        };
+        for name in refs {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let Some(var) = self.lookup_in_scope_chain(&name, scope) else {
+                continue;
+            };
+            if var.value_type == VariableType::Secret {
+                found.push(var);
+            } else {
+                self.collect_secret_variables(&var.value, scope, depth + 1, seen, found);
+            }
+        }
+    }
+
     /// Internal substitution with depth tracking
     fn substitute_with_depth(
         &self,
@@ -651,4 +886,177 @@ mod tests {
         );
         assert!(matches!(result, Err(VariableError::MaxDepthExceeded(_))));
     }
+
+    #[test]
+    #[allow(clippy::literal_string_with_formatting_args)] // Template placeholders, not format args
+    fn test_set_validates_typed_variables() {
+        use crate::variables::VariableType;
+
+        let mut manager = VariableManager::new();
+        let conn_id = Uuid::new_v4();
+
+        let port = Variable::new("port", "abc").with_type(VariableType::Int);
+        assert!(matches!(
+            manager.set(VariableScope::Connection(conn_id), port),
+            Err(VariableError::InvalidValue { .. })
+        ));
+        assert!(manager.get_connection(conn_id, "port").is_none());
+
+        let port = Variable::new("port", "2222").with_type(VariableType::Int);
+        manager
+            .set(VariableScope::Connection(conn_id), port)
+            .unwrap();
+        assert_eq!(
+            manager
+                .substitute("ssh -p ${port}", VariableScope::Connection(conn_id))
+                .unwrap(),
+            "ssh -p 2222"
+        );
+
+        assert_eq!(
+            manager.set(VariableScope::Global, Variable::new(" ", "x")),
+            Err(VariableError::EmptyName)
+        );
+    }
+
+    #[test]
+    fn test_typed_reference_checked_on_resolution() {
+        use crate::variables::VariableType;
+
+        let mut manager = VariableManager::new();
+        manager.set_global(Variable::new("base", "not-a-number"));
+        manager
+            .set(
+                VariableScope::Global,
+                Variable::new("port", "${base}").with_type(VariableType::Int),
+            )
+            .unwrap();
+
+        assert!(matches!(
+            manager.resolve("port", VariableScope::Global),
+            Err(VariableError::InvalidValue { .. })
+        ));
+    }
+
+    /// Secret backend holding passwords keyed by lookup key
+    struct StaticBackend(HashMap<&'static str, &'static str>);
+
+    #[async_trait::async_trait]
+    impl crate::secret::SecretBackend for StaticBackend {
+        async fn store(
+            &self,
+            _connection_id: &str,
+            _credentials: &crate::models::Credentials,
+        ) -> crate::error::SecretResult<()> {
+            Ok(())
+        }
+
+        async fn retrieve(
+            &self,
+            connection_id: &str,
+        ) -> crate::error::SecretResult<Option<crate::models::Credentials>> {
+            Ok(self
+                .0
+                .get(connection_id)
+                .map(|password| crate::models::Credentials {
+                    username: None,
+                    password: Some(SecretString::from(*password)),
+                    key_passphrase: None,
+                    domain: None,
+                }))
+        }
+
+        async fn delete(&self, _connection_id: &str) -> crate::error::SecretResult<()> {
+            Ok(())
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        fn backend_id(&self) -> &'static str {
+            "static"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Static"
+        }
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to create runtime")
+            .block_on(future)
+    }
+
+    fn secret_test_manager() -> VariableManager {
+        let backend = StaticBackend(HashMap::from([("rustconn/api", "t0ken")]));
+        let mut manager = VariableManager::new();
+        manager.set_secret_manager(Arc::new(SecretManager::new(vec![Arc::new(backend)])));
+        manager.set_global(Variable::new("token", "rustconn/api").with_type(VariableType::Secret));
+        manager.set_global(Variable::new("header", "Bearer ${token}"));
+        manager
+    }
+
+    #[test]
+    #[allow(clippy::literal_string_with_formatting_args)] // Template placeholders, not format args
+    fn test_secret_variable_resolved_from_backend() {
+        let manager = secret_test_manager();
+
+        assert_eq!(
+            block_on(manager.substitute_with_secrets("curl -H '${header}'", VariableScope::Global))
+                .unwrap(),
+            "curl -H 'Bearer t0ken'"
+        );
+        assert_eq!(
+            block_on(manager.resolve_with_secrets("token", VariableScope::Global)).unwrap(),
+            "t0ken"
+        );
+        // The stored value stays a reference
+        assert_eq!(manager.get_global("token").unwrap().value, "rustconn/api");
+        let json = serde_json::to_string(manager.get_global("token").unwrap()).unwrap();
+        assert!(!json.contains("t0ken"));
+    }
+
+    #[test]
+    #[allow(clippy::literal_string_with_formatting_args)] // Template placeholders, not format args
+    fn test_unresolvable_secret_variable_names_reference() {
+        let mut manager = secret_test_manager();
+        manager
+            .set_global(Variable::new("missing", "rustconn/none").with_type(VariableType::Secret));
+
+        let err = block_on(manager.substitute_with_secrets("${missing}", VariableScope::Global))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            VariableError::SecretUnresolved {
+                name: "missing".to_string(),
+                reference: "rustconn/none".to_string(),
+            }
+        );
+
+        // Synchronous substitution never reaches the backend
+        let err = manager
+            .substitute("${header}", VariableScope::Global)
+            .unwrap_err();
+        assert!(matches!(&err, VariableError::SecretUnresolved { name, .. } if name == "token"));
+        assert!(!err.to_string().contains("t0ken"));
+
+        // Loading reports the missing reference but keeps the others
+        assert!(matches!(
+            block_on(manager.load_secrets()),
+            Err(VariableError::SecretUnresolved { name, .. }) if name == "missing"
+        ));
+        assert_eq!(
+            manager
+                .substitute("${header}", VariableScope::Global)
+                .unwrap(),
+            "Bearer t0ken"
+        );
+
+        manager.clear_secret_cache();
+        assert!(manager
+            .substitute("${header}", VariableScope::Global)
+            .is_err());
+    }
 }
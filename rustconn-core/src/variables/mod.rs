@@ -5,11 +5,16 @@
 //! - Variable substitution in strings using `${variable_name}` syntax
 //! - Nested variable resolution with cycle detection
 //! - Secure storage for secret variables
+//! - Typed variables (integer, boolean, enumeration) validated on assignment
+//! - Secret variables resolved from the secret backend at interpolation time
 
 mod manager;
 
 pub use manager::VariableManager;
 
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
@@ -31,6 +36,9 @@ pub struct Variable {
     pub is_secret: bool,
     /// Optional description for documentation
     pub description: Option<String>,
+    /// Type the value must conform to
+    #[serde(default, skip_serializing_if = "VariableType::is_string")]
+    pub value_type: VariableType,
 }
 
 impl Variable {
@@ -42,6 +50,7 @@ impl Variable {
             value: value.into(),
             is_secret: false,
             description: None,
+            value_type: VariableType::String,
         }
     }
 
@@ -53,6 +62,7 @@ impl Variable {
             value: value.into(),
             is_secret: true,
             description: None,
+            value_type: VariableType::String,
         }
     }
 
@@ -70,6 +80,30 @@ impl Variable {
         self
     }
 
+    /// Sets the type the value must conform to
+    #[must_use]
+    pub fn with_type(mut self, value_type: VariableType) -> Self {
+        self.value_type = value_type;
+        self
+    }
+
+    /// Checks the value against the variable's type
+    ///
+    /// Values containing `${...}` references are checked once resolved, so
+    /// they are accepted here. On success the value is rewritten in its
+    /// canonical form (e.g. `yes` becomes `true` for a boolean).
+    ///
+    /// # Errors
+    ///
+    /// Returns `VariableError::InvalidValue` if the value does not match the type.
+    pub fn validate(&mut self) -> VariableResult<()> {
+        if self.value.contains("${") {
+            return Ok(());
+        }
+        self.value = self.value_type.format(&self.name, &self.value)?;
+        Ok(())
+    }
+
     /// Returns the value for display, masking secret values
     #[must_use]
     pub fn display_value(&self) -> &str {
@@ -87,6 +121,119 @@ impl Variable {
     }
 }
 
+/// The kind of value a variable holds
+///
+/// Values are always stored and interpolated as strings; the type only
+/// restricts which strings are accepted. A [`VariableType::Secret`] value is
+/// the lookup key of an entry in the secret backend, and the entry's password
+/// is what gets interpolated.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "allowed")]
+pub enum VariableType {
+    /// Any string (the default)
+    #[default]
+    String,
+    /// A signed integer
+    Int,
+    /// `true` or `false` (also accepts `yes`/`no`, `on`/`off`, `1`/`0`)
+    Bool,
+    /// One of a fixed set of values
+    Enum(Vec<String>),
+    /// A reference to a secret backend entry, resolved when interpolated
+    Secret,
+}
+
+impl VariableType {
+    /// Returns true for the unrestricted string type
+    #[must_use]
+    pub const fn is_string(&self) -> bool {
+        matches!(self, Self::String)
+    }
+
+    /// Validates `value` and returns it in canonical string form
+    ///
+    /// # Errors
+    ///
+    /// Returns `VariableError::InvalidValue` naming `name` if the value does
+    /// not match this type.
+    pub fn format(&self, name: &str, value: &str) -> VariableResult<String> {
+        let invalid = |reason: String| VariableError::InvalidValue {
+            name: name.to_string(),
+            reason,
+        };
+        match self {
+            Self::String => Ok(value.to_string()),
+            Self::Int => value
+                .trim()
+                .parse::<i64>()
+                .map(|n| n.to_string())
+                .map_err(|_| invalid(format!("'{value}' is not an integer"))),
+            Self::Bool => match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => Ok("true".to_string()),
+                "false" | "no" | "off" | "0" => Ok("false".to_string()),
+                _ => Err(invalid(format!("'{value}' is not a boolean"))),
+            },
+            Self::Enum(allowed) => allowed
+                .iter()
+                .find(|candidate| candidate.as_str() == value.trim())
+                .cloned()
+                .ok_or_else(|| invalid(format!("'{value}' is not one of: {}", allowed.join(", ")))),
+            Self::Secret => {
+                let reference = value.trim();
+                if reference.is_empty() {
+                    Err(invalid("secret reference is empty".to_string()))
+                } else {
+                    Ok(reference.to_string())
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for VariableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String => write!(f, "string"),
+            Self::Int => write!(f, "int"),
+            Self::Bool => write!(f, "bool"),
+            Self::Enum(allowed) => write!(f, "enum:{}", allowed.join(",")),
+            Self::Secret => write!(f, "secret"),
+        }
+    }
+}
+
+impl FromStr for VariableType {
+    type Err = VariableError;
+
+    /// Parses `string`, `int`, `bool`, `secret` or `enum:a,b,c`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "string" => Ok(Self::String),
+            "int" => Ok(Self::Int),
+            "bool" => Ok(Self::Bool),
+            "secret" => Ok(Self::Secret),
+            other => {
+                let allowed: Vec<String> = other
+                    .strip_prefix("enum:")
+                    .ok_or_else(|| {
+                        VariableError::InvalidSyntax(format!("Unknown variable type: {other}"))
+                    })?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(String::from)
+                    .collect();
+                if allowed.is_empty() {
+                    return Err(VariableError::InvalidSyntax(
+                        "Enum type needs at least one allowed value".to_string(),
+                    ));
+                }
+                Ok(Self::Enum(allowed))
+            }
+        }
+    }
+}
+
 /// Variable scope for resolution
 ///
 /// Variables are resolved in order from most specific to least specific:
@@ -137,6 +284,24 @@ pub enum VariableError {
     /// Empty variable name
     #[error("Empty variable name")]
     EmptyName,
+
+    /// Value does not match the variable's type
+    #[error("Invalid value for variable '{name}': {reason}")]
+    InvalidValue {
+        /// The variable name
+        name: String,
+        /// Why the value was rejected
+        reason: String,
+    },
+
+    /// Secret variable whose reference could not be resolved
+    #[error("Secret variable '{name}' could not be resolved from reference '{reference}'")]
+    SecretUnresolved {
+        /// The variable name
+        name: String,
+        /// The secret backend lookup key
+        reference: String,
+    },
 }
 
 /// Result type for variable operations
@@ -201,6 +366,60 @@ mod tests {
         assert_eq!(normal_var.display_value(), "example.com");
     }
 
+    #[test]
+    fn test_variable_type_validation() {
+        let mut port = Variable::new("port", " 2222 ").with_type(VariableType::Int);
+        assert!(port.validate().is_ok());
+        assert_eq!(port.value, "2222");
+
+        let mut port = Variable::new("port", "abc").with_type(VariableType::Int);
+        assert!(matches!(
+            port.validate(),
+            Err(VariableError::InvalidValue { name, .. }) if name == "port"
+        ));
+
+        let mut flag = Variable::new("x11", "Yes").with_type(VariableType::Bool);
+        assert!(flag.validate().is_ok());
+        assert_eq!(flag.value, "true");
+
+        let env = VariableType::Enum(vec!["dev".to_string(), "prod".to_string()]);
+        assert!(Variable::new("env", "prod")
+            .with_type(env.clone())
+            .validate()
+            .is_ok());
+        assert!(Variable::new("env", "qa")
+            .with_type(env)
+            .validate()
+            .is_err());
+
+        // References are checked after resolution
+        let mut nested = Variable::new("port", "${base_port}").with_type(VariableType::Int);
+        assert!(nested.validate().is_ok());
+    }
+
+    #[test]
+    fn test_variable_type_from_str_round_trip() {
+        for text in ["string", "int", "bool", "secret", "enum:dev,prod"] {
+            let parsed: VariableType = text.parse().unwrap();
+            assert_eq!(parsed.to_string(), text);
+        }
+        assert!("enum:".parse::<VariableType>().is_err());
+        assert!("float".parse::<VariableType>().is_err());
+    }
+
+    #[test]
+    fn test_untyped_variable_serialization_unchanged() {
+        let json = serde_json::to_string(&Variable::new("host", "example.com")).unwrap();
+        assert!(!json.contains("value_type"));
+
+        let var = Variable::new("env", "dev").with_type(VariableType::Enum(vec![
+            "dev".to_string(),
+            "prod".to_string(),
+        ]));
+        let parsed: Variable = serde_json::from_str(&serde_json::to_string(&var).unwrap()).unwrap();
+        assert_eq!(parsed, var);
+    }
+
     #[test]
     fn test_is_secret_method() {
         let secret_var = Variable::new_secret("password", "secret");
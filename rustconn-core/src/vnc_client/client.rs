@@ -3,6 +3,11 @@
 //! This module provides the async VNC client that connects to VNC servers
 //! and produces framebuffer events for the GUI to render.
 
+use super::clipboard::ExtendedClipboard;
+use super::framebuffer::Framebuffer;
+use super::stream::{
+    client_cut_text_message, set_encodings_message, InjectableStream, MAX_VNC_LEVEL,
+};
 use super::{VncClientCommand, VncClientConfig, VncClientError, VncClientEvent, VncRect};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -168,6 +173,21 @@ impl VncClient {
         self.send_command(VncClientCommand::SetDesktopSize { width, height })
     }
 
+    /// Renegotiates Tight compression and JPEG quality levels (0-9)
+    ///
+    /// The session keeps running; a `QualityChanged` event confirms the
+    /// levels that were requested from the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or channel is closed.
+    pub fn set_quality(&self, compression: u8, quality: u8) -> Result<(), VncClientError> {
+        self.send_command(VncClientCommand::SetQuality {
+            compression,
+            quality,
+        })
+    }
+
     /// Sends Ctrl+Alt+Del key sequence
     ///
     /// This is commonly used to unlock Windows login screens or access
@@ -180,6 +200,18 @@ impl VncClient {
         self.send_command(VncClientCommand::SendCtrlAltDel)
     }
 
+    /// Requests a screenshot of the remote desktop
+    ///
+    /// The PNG image arrives as a `FrameCaptured` event, or a
+    /// `CaptureFailed` event if no frame has been received yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or channel is closed.
+    pub fn capture_frame(&self) -> Result<(), VncClientError> {
+        self.send_command(VncClientCommand::CaptureFrame)
+    }
+
     /// Disconnects from the VNC server
     pub fn disconnect(&mut self) {
         if let Some(tx) = &self.command_tx {
@@ -231,9 +263,14 @@ async fn run_vnc_client(
         .await
         .map_err(|e| VncClientError::ConnectionFailed(e.to_string()))?;
 
+    // Wrap the socket so encodings can be renegotiated mid-session
+    let (mut stream, injector) = InjectableStream::new(tcp);
+    // vnc-rs cannot parse extended cut text, so the stream takes it out
+    let mut clipboard_rx = stream.filter_extended_clipboard();
+
     // Build the VNC connector
     let password = config.password.clone();
-    let mut connector = VncConnector::new(tcp)
+    let mut connector = VncConnector::new(stream)
         .set_auth_method(async move { Ok(password.unwrap_or_default()) })
         .allow_shared(config.shared)
         .set_pixel_format(PixelFormat::bgra());
@@ -261,7 +298,20 @@ async fn run_vnc_client(
     // Notify connected
     let _ = event_tx.send(VncClientEvent::Connected);
 
+    // vnc-rs cannot express the level and extended clipboard
+    // pseudo-encodings, so request them with a second SetEncodings message
+    if config.compression.is_some() || config.quality.is_some() || config.clipboard_enabled {
+        injector.inject(&set_encodings_message(
+            &config.encodings,
+            config.compression,
+            config.quality,
+            config.clipboard_enabled,
+        ));
+    }
+    let mut clipboard = ExtendedClipboard::default();
+
     // Main event loop
+    let mut framebuffer = Framebuffer::default();
     let mut last_refresh = std::time::Instant::now();
     let refresh_interval = std::time::Duration::from_millis(16); // ~60 FPS
 
@@ -299,9 +349,20 @@ async fn run_vnc_client(
                                 });
                                 let _ = vnc.input(event).await;
                             }
-                            VncClientCommand::ClipboardText(text) => {
-                                let event = X11Event::CopyText(text);
-                                let _ = vnc.input(event).await;
+                            #[allow(deprecated)]
+                            VncClientCommand::SetClipboard(text)
+                            | VncClientCommand::ClipboardText(text) => {
+                                if config.clipboard_enabled {
+                                    // Without the extended clipboard, send Latin-1 cut text;
+                                    // vnc-rs would send it as UTF-8
+                                    let message = clipboard
+                                        .set_local_text(text.clone())
+                                        .unwrap_or_else(|| client_cut_text_message(&text));
+                                    injector.inject(&message);
+                                    let _ = vnc.input(X11Event::Refresh).await;
+                                } else {
+                                    tracing::debug!("[VNC] Clipboard disabled, not sending cut text");
+                                }
                             }
                             VncClientCommand::RefreshScreen => {
                                 let _ = vnc.input(X11Event::Refresh).await;
@@ -336,15 +397,73 @@ async fn run_vnc_client(
                                 }
                                 tracing::debug!("[VNC] Typed {} characters", text.len());
                             }
+                            VncClientCommand::SetQuality { compression, quality } => {
+                                let compression = compression.min(MAX_VNC_LEVEL);
+                                let quality = quality.min(MAX_VNC_LEVEL);
+                                injector.inject(&set_encodings_message(
+                                    &config.encodings,
+                                    Some(compression),
+                                    Some(quality),
+                                    config.clipboard_enabled,
+                                ));
+                                // The next client message carries the new encodings out
+                                let _ = vnc.input(X11Event::Refresh).await;
+                                tracing::debug!(
+                                    "[VNC] Quality renegotiated: compression={compression}, quality={quality}"
+                                );
+                                let _ = event_tx.send(VncClientEvent::QualityChanged {
+                                    compression,
+                                    quality,
+                                });
+                            }
+                            VncClientCommand::CaptureFrame => {
+                                let event = if framebuffer.is_empty() {
+                                    VncClientEvent::CaptureFailed(
+                                        "No frame received from the server yet".to_string(),
+                                    )
+                                } else {
+                                    framebuffer.to_png().map_or_else(
+                                        |e| VncClientEvent::CaptureFailed(e.to_string()),
+                                        VncClientEvent::FrameCaptured,
+                                    )
+                                };
+                                let _ = event_tx.send(event);
+                            }
                             VncClientCommand::Authenticate(_) | VncClientCommand::Disconnect => {}
                         }
                     }
                 }
             }
+            Some(message) = clipboard_rx.recv() => {
+                if !config.clipboard_enabled {
+                    continue;
+                }
+                let reply = clipboard.handle_server_message(message);
+                if let Some(message) = reply.message {
+                    injector.inject(&message);
+                    let _ = vnc.input(X11Event::Refresh).await;
+                }
+                if let Some(text) = reply.server_text {
+                    if event_tx.send(VncClientEvent::ServerCutText(text)).is_err() {
+                        break;
+                    }
+                }
+            }
             event = vnc.poll_event() => {
                 match event {
+                    Ok(Some(VncEvent::Text(_))) if !config.clipboard_enabled => {
+                        tracing::debug!("[VNC] Clipboard disabled, ignoring server cut text");
+                    }
                     Ok(Some(event)) => {
                         let client_event = convert_vnc_event(event);
+                        match &client_event {
+                            VncClientEvent::ResolutionChanged { width, height } => {
+                                framebuffer.resize(*width, *height);
+                            }
+                            VncClientEvent::FrameUpdate { rect, data } => framebuffer.blit(*rect, data),
+                            VncClientEvent::CopyRect { dst, src } => framebuffer.copy(*dst, *src),
+                            _ => {}
+                        }
                         if event_tx.send(client_event).is_err() {
                             break;
                         }
@@ -389,7 +508,7 @@ fn convert_vnc_event(event: VncEvent) -> VncClientEvent {
             data,
         },
         VncEvent::Bell => VncClientEvent::Bell,
-        VncEvent::Text(text) => VncClientEvent::ClipboardText(text),
+        VncEvent::Text(text) => VncClientEvent::ServerCutText(text),
         VncEvent::JpegImage(rect, data) => {
             // JPEG images need decoding - for now treat as raw
             // In a full implementation, we'd decode JPEG here
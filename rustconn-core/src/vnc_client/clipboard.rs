@@ -0,0 +1,778 @@
+//! RFB extended clipboard (UTF-8 cut text)
+//!
+//! Plain cut text is Latin-1 only. The extended clipboard pseudo-encoding
+//! lets both sides exchange zlib-compressed UTF-8 text instead: the server
+//! answers the pseudo-encoding with a `Caps` message, and from then on cut
+//! text messages carry a negative length followed by a flags word.
+//!
+//! vnc-rs reads the `ServerCutText` length as unsigned and would try to
+//! allocate gigabytes for an extended message, so [`ServerMessageFilter`]
+//! follows the server message stream and takes extended cut text out before
+//! vnc-rs sees it.
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Extended clipboard pseudo-encoding (`0xC0A1E5CE`)
+pub const EXTENDED_CLIPBOARD_ENCODING: i32 = -1_063_131_698;
+
+/// Largest clipboard text accepted from or offered to the server
+pub const MAX_CLIPBOARD_TEXT: u32 = 10 * 1024 * 1024;
+
+/// Text format flag
+const FORMAT_TEXT: u32 = 1;
+/// Capabilities action
+const ACTION_CAPS: u32 = 1 << 24;
+/// Request action
+const ACTION_REQUEST: u32 = 1 << 25;
+/// Peek action
+const ACTION_PEEK: u32 = 1 << 26;
+/// Notify action
+const ACTION_NOTIFY: u32 = 1 << 27;
+/// Provide action
+const ACTION_PROVIDE: u32 = 1 << 28;
+/// Actions this client supports
+const CLIENT_ACTIONS: u32 =
+    ACTION_CAPS | ACTION_REQUEST | ACTION_PEEK | ACTION_NOTIFY | ACTION_PROVIDE;
+
+/// RFB `ClientCutText` message type
+const CLIENT_CUT_TEXT: u8 = 6;
+/// RFB `ServerCutText` message type
+const SERVER_CUT_TEXT: u8 = 3;
+
+/// Extended clipboard message exchanged through cut text messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtendedClipboardMessage {
+    /// Supported actions and whether text is supported
+    Caps {
+        /// Action flags the sender accepts
+        actions: u32,
+        /// Whether the text format is supported
+        text: bool,
+    },
+    /// Asks the peer to provide its clipboard
+    Request {
+        /// Whether text is requested
+        text: bool,
+    },
+    /// Asks the peer which formats its clipboard holds
+    Peek,
+    /// Announces which formats the clipboard holds
+    Notify {
+        /// Whether the clipboard holds text
+        text: bool,
+    },
+    /// Clipboard contents
+    Provide {
+        /// Clipboard text, if provided
+        text: Option<String>,
+    },
+}
+
+impl ExtendedClipboardMessage {
+    /// Parses the payload of an extended cut text message
+    ///
+    /// Returns `None` for payloads without a known action.
+    #[must_use]
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let flags = u32::from_be_bytes(payload.get(..4)?.try_into().ok()?);
+        let text = flags & FORMAT_TEXT != 0;
+
+        if flags & ACTION_CAPS != 0 {
+            Some(Self::Caps {
+                actions: flags & 0xFF00_0000,
+                text,
+            })
+        } else if flags & ACTION_REQUEST != 0 {
+            Some(Self::Request { text })
+        } else if flags & ACTION_PEEK != 0 {
+            Some(Self::Peek)
+        } else if flags & ACTION_NOTIFY != 0 {
+            Some(Self::Notify { text })
+        } else if flags & ACTION_PROVIDE != 0 {
+            let text = if text {
+                decode_provided_text(&payload[4..])
+            } else {
+                None
+            };
+            Some(Self::Provide { text })
+        } else {
+            None
+        }
+    }
+
+    /// Builds a `ClientCutText` message carrying this message
+    #[must_use]
+    pub fn to_client_message(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        match self {
+            Self::Caps { actions, text } => {
+                let format = if *text { FORMAT_TEXT } else { 0 };
+                payload.extend_from_slice(&(ACTION_CAPS | actions | format).to_be_bytes());
+                if *text {
+                    payload.extend_from_slice(&MAX_CLIPBOARD_TEXT.to_be_bytes());
+                }
+            }
+            Self::Request { text } => {
+                payload.extend_from_slice(&(ACTION_REQUEST | text_flag(*text)).to_be_bytes());
+            }
+            Self::Peek => payload.extend_from_slice(&ACTION_PEEK.to_be_bytes()),
+            Self::Notify { text } => {
+                payload.extend_from_slice(&(ACTION_NOTIFY | text_flag(*text)).to_be_bytes());
+            }
+            Self::Provide { text } => {
+                let flags = ACTION_PROVIDE | text_flag(text.is_some());
+                payload.extend_from_slice(&flags.to_be_bytes());
+                if let Some(text) = text {
+                    payload.extend_from_slice(&encode_provided_text(text));
+                }
+            }
+        }
+
+        // A negative length marks the message as extended
+        let length = i32::try_from(payload.len()).map_or(i32::MIN, |len| -len);
+        let mut message = vec![CLIENT_CUT_TEXT, 0, 0, 0];
+        message.extend_from_slice(&length.to_be_bytes());
+        message.extend_from_slice(&payload);
+        message
+    }
+}
+
+/// Returns the text format flag if `text` is set
+const fn text_flag(text: bool) -> u32 {
+    if text {
+        FORMAT_TEXT
+    } else {
+        0
+    }
+}
+
+/// Compresses clipboard text as NUL-terminated UTF-8 with CRLF line endings
+fn encode_provided_text(text: &str) -> Vec<u8> {
+    let mut text = text.replace("\r\n", "\n").replace('\n', "\r\n");
+    if text.len() >= MAX_CLIPBOARD_TEXT as usize {
+        let end = (0..MAX_CLIPBOARD_TEXT as usize)
+            .rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0);
+        text.truncate(end);
+    }
+    let mut data = text.into_bytes();
+    data.push(0);
+
+    let size = u32::try_from(data.len()).unwrap_or(MAX_CLIPBOARD_TEXT);
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    // Writing into a Vec cannot fail
+    let _ = encoder.write_all(&size.to_be_bytes());
+    let _ = encoder.write_all(&data);
+    encoder.finish().unwrap_or_default()
+}
+
+/// Decompresses provided clipboard text, returning it with LF line endings
+fn decode_provided_text(compressed: &[u8]) -> Option<String> {
+    let mut decoder = ZlibDecoder::new(compressed).take(u64::from(MAX_CLIPBOARD_TEXT) + 4);
+    let mut size = [0_u8; 4];
+    decoder.read_exact(&mut size).ok()?;
+    let size = u32::from_be_bytes(size).min(MAX_CLIPBOARD_TEXT);
+
+    let mut data = Vec::new();
+    decoder.take(u64::from(size)).read_to_end(&mut data).ok()?;
+    if let Some(end) = data.iter().position(|&b| b == 0) {
+        data.truncate(end);
+    }
+    Some(String::from_utf8_lossy(&data).replace("\r\n", "\n"))
+}
+
+/// Outcome of handling a server clipboard message
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ClipboardReply {
+    /// Client message to send back to the server
+    pub message: Option<Vec<u8>>,
+    /// Server clipboard text to apply locally
+    pub server_text: Option<String>,
+}
+
+/// Client side of the extended clipboard exchange
+#[derive(Debug, Default)]
+pub struct ExtendedClipboard {
+    /// Actions the server accepts, once it has sent its capabilities
+    server_actions: Option<u32>,
+    /// Local clipboard text last offered to the server
+    local_text: Option<String>,
+}
+
+impl ExtendedClipboard {
+    /// Handles a message from the server
+    pub fn handle_server_message(&mut self, message: ExtendedClipboardMessage) -> ClipboardReply {
+        match message {
+            ExtendedClipboardMessage::Caps { actions, text } => {
+                if !text {
+                    return ClipboardReply::default();
+                }
+                self.server_actions = Some(actions);
+                ClipboardReply {
+                    message: Some(
+                        ExtendedClipboardMessage::Caps {
+                            actions: CLIENT_ACTIONS,
+                            text: true,
+                        }
+                        .to_client_message(),
+                    ),
+                    server_text: None,
+                }
+            }
+            ExtendedClipboardMessage::Notify { text: true } => ClipboardReply {
+                message: Some(ExtendedClipboardMessage::Request { text: true }.to_client_message()),
+                server_text: None,
+            },
+            ExtendedClipboardMessage::Request { text: true } => ClipboardReply {
+                message: Some(
+                    ExtendedClipboardMessage::Provide {
+                        text: self.local_text.clone(),
+                    }
+                    .to_client_message(),
+                ),
+                server_text: None,
+            },
+            ExtendedClipboardMessage::Peek => ClipboardReply {
+                message: Some(
+                    ExtendedClipboardMessage::Notify {
+                        text: self.local_text.is_some(),
+                    }
+                    .to_client_message(),
+                ),
+                server_text: None,
+            },
+            ExtendedClipboardMessage::Provide { text } => ClipboardReply {
+                message: None,
+                server_text: text,
+            },
+            ExtendedClipboardMessage::Notify { text: false }
+            | ExtendedClipboardMessage::Request { text: false } => ClipboardReply::default(),
+        }
+    }
+
+    /// Offers local clipboard text to the server
+    ///
+    /// Returns `None` if the extended clipboard was not negotiated, in which
+    /// case the caller falls back to Latin-1 cut text.
+    pub fn set_local_text(&mut self, text: String) -> Option<Vec<u8>> {
+        let actions = self.server_actions?;
+        let message = if actions & ACTION_NOTIFY != 0 {
+            self.local_text = Some(text);
+            ExtendedClipboardMessage::Notify { text: true }
+        } else {
+            self.local_text = Some(text.clone());
+            ExtendedClipboardMessage::Provide { text: Some(text) }
+        };
+        Some(message.to_client_message())
+    }
+}
+
+/// What follows the bytes being skipped
+#[derive(Debug, Clone, Copy)]
+enum AfterSkip {
+    /// The current message is complete
+    Message,
+    /// The current rectangle is complete
+    Rect,
+    /// Tight data of the given uncompressed size follows
+    TightData(usize),
+}
+
+/// Header currently being collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Message type byte
+    MessageType,
+    /// `FramebufferUpdate` padding and rectangle count
+    UpdateHeader,
+    /// Rectangle position, size and encoding
+    RectHeader,
+    /// `SetColorMapEntries` padding, first color and count
+    ColorMapHeader,
+    /// `ServerCutText` padding and length
+    CutTextHeader,
+    /// ZRLE data length
+    ZrleLength,
+    /// Tight compression control byte
+    TightControl,
+    /// Tight filter id
+    TightFilter,
+    /// Tight palette size
+    TightPaletteSize,
+    /// Tight compact length (1-3 bytes)
+    TightLength,
+}
+
+/// Follows the server message stream and extracts extended cut text
+///
+/// Only the encodings this client advertises (Raw, `CopyRect`, Tight with a
+/// 32-bit pixel format, ZRLE) are understood. On anything else the filter
+/// stops filtering and passes all further bytes through unchanged.
+#[derive(Debug)]
+pub struct ServerMessageFilter {
+    active: bool,
+    synced: bool,
+    stage: Stage,
+    held: Vec<u8>,
+    skip: usize,
+    after_skip: AfterSkip,
+    rects_left: u16,
+    rect_size: (usize, usize),
+    cut_remaining: usize,
+    cut_data: Option<Vec<u8>>,
+}
+
+impl Default for ServerMessageFilter {
+    fn default() -> Self {
+        Self {
+            active: false,
+            synced: true,
+            stage: Stage::MessageType,
+            held: Vec::new(),
+            skip: 0,
+            after_skip: AfterSkip::Message,
+            rects_left: 0,
+            rect_size: (0, 0),
+            cut_remaining: 0,
+            cut_data: None,
+        }
+    }
+}
+
+impl ServerMessageFilter {
+    /// Starts filtering at the first server message after the handshake
+    pub fn start(&mut self) {
+        self.active = true;
+    }
+
+    /// Returns true once filtering has started
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Feeds server bytes, appending the ones vnc-rs should see to `out`
+    pub fn feed(
+        &mut self,
+        mut input: &[u8],
+        out: &mut Vec<u8>,
+        messages: &mut Vec<ExtendedClipboardMessage>,
+    ) {
+        while !input.is_empty() {
+            if !self.active || !self.synced {
+                out.extend_from_slice(input);
+                return;
+            }
+
+            if self.skip > 0 {
+                let n = self.skip.min(input.len());
+                out.extend_from_slice(&input[..n]);
+                input = &input[n..];
+                self.skip -= n;
+                if self.skip == 0 {
+                    self.finish_skip();
+                }
+                continue;
+            }
+
+            if self.cut_remaining > 0 {
+                let n = self.cut_remaining.min(input.len());
+                if let Some(data) = self.cut_data.as_mut() {
+                    data.extend_from_slice(&input[..n]);
+                }
+                input = &input[n..];
+                self.cut_remaining -= n;
+                if self.cut_remaining == 0 {
+                    self.finish_cut_text(messages);
+                }
+                continue;
+            }
+
+            self.held.push(input[0]);
+            input = &input[1..];
+            if self.header_complete() {
+                self.advance(out, messages);
+            }
+        }
+    }
+
+    /// Returns true once the current stage has all its header bytes
+    fn header_complete(&self) -> bool {
+        let len = self.held.len();
+        match self.stage {
+            Stage::MessageType
+            | Stage::TightControl
+            | Stage::TightFilter
+            | Stage::TightPaletteSize => len == 1,
+            Stage::UpdateHeader => len == 3,
+            Stage::RectHeader => len == 12,
+            Stage::ColorMapHeader => len == 5,
+            Stage::CutTextHeader => len == 8,
+            Stage::ZrleLength => len == 4,
+            Stage::TightLength => len == 3 || self.held[len - 1] & 0x80 == 0,
+        }
+    }
+
+    /// Interprets a complete header
+    fn advance(&mut self, out: &mut Vec<u8>, messages: &mut Vec<ExtendedClipboardMessage>) {
+        let held = std::mem::take(&mut self.held);
+        let be_u16 = |at: usize| usize::from(u16::from_be_bytes([held[at], held[at + 1]]));
+        let be_u32 = |at: usize| [held[at], held[at + 1], held[at + 2], held[at + 3]];
+
+        if self.stage == Stage::MessageType && held[0] == SERVER_CUT_TEXT {
+            // Hold the type byte until the length shows whether it is extended
+            self.held = held;
+            self.stage = Stage::CutTextHeader;
+            return;
+        }
+        if self.stage == Stage::CutTextHeader {
+            let length = i32::from_be_bytes(be_u32(4));
+            if length >= 0 {
+                out.extend_from_slice(&held);
+                self.start_skip(length.unsigned_abs() as usize, AfterSkip::Message);
+            } else {
+                self.start_cut_text(length.unsigned_abs() as usize, messages);
+            }
+            return;
+        }
+
+        out.extend_from_slice(&held);
+        match self.stage {
+            Stage::MessageType => match held[0] {
+                0 => self.stage = Stage::UpdateHeader,
+                1 => self.stage = Stage::ColorMapHeader,
+                2 => {}
+                _ => self.synced = false,
+            },
+            Stage::UpdateHeader => {
+                self.rects_left = u16::from_be_bytes([held[1], held[2]]);
+                self.stage = if self.rects_left == 0 {
+                    Stage::MessageType
+                } else {
+                    Stage::RectHeader
+                };
+            }
+            Stage::RectHeader => {
+                let (width, height) = (be_u16(4), be_u16(6));
+                self.rect_size = (width, height);
+                match i32::from_be_bytes(be_u32(8)) {
+                    // Raw with 32 bits per pixel
+                    0 => self.start_skip(width * height * 4, AfterSkip::Rect),
+                    // CopyRect source position
+                    1 => self.start_skip(4, AfterSkip::Rect),
+                    7 => self.stage = Stage::TightControl,
+                    16 => self.stage = Stage::ZrleLength,
+                    // DesktopSize pseudo-encoding carries no data
+                    -223 => self.finish_rect(),
+                    // LastRect pseudo-encoding ends the update
+                    -224 => {
+                        self.rects_left = 0;
+                        self.stage = Stage::MessageType;
+                    }
+                    _ => self.synced = false,
+                }
+            }
+            Stage::ColorMapHeader => self.start_skip(be_u16(3) * 6, AfterSkip::Message),
+            Stage::ZrleLength => {
+                let length = u32::from_be_bytes(be_u32(0)) as usize;
+                self.start_skip(length, AfterSkip::Rect);
+            }
+            Stage::TightControl
+            | Stage::TightFilter
+            | Stage::TightPaletteSize
+            | Stage::TightLength => self.advance_tight(&held),
+            Stage::CutTextHeader => {}
+        }
+    }
+
+    /// Interprets a complete Tight header
+    fn advance_tight(&mut self, held: &[u8]) {
+        let (width, height) = self.rect_size;
+        match self.stage {
+            Stage::TightControl => {
+                match held[0] >> 4 {
+                    // Fill: one TPIXEL
+                    8 => self.start_skip(3, AfterSkip::Rect),
+                    // JPEG: compact length and data
+                    9 => self.stage = Stage::TightLength,
+                    ctrl if ctrl & 0x8 == 0 => {
+                        if ctrl & 0x4 == 0 {
+                            self.start_tight_data(width * height * 3);
+                        } else {
+                            self.stage = Stage::TightFilter;
+                        }
+                    }
+                    _ => self.synced = false,
+                }
+            }
+            Stage::TightFilter => {
+                match held[0] {
+                    // Copy and gradient filters send TPIXELs
+                    0 | 2 => self.start_tight_data(width * height * 3),
+                    1 => self.stage = Stage::TightPaletteSize,
+                    _ => self.synced = false,
+                }
+            }
+            Stage::TightPaletteSize => {
+                let colors = usize::from(held[0]) + 1;
+                let bits = if colors <= 2 { 1 } else { 8 };
+                let size = (width * bits).div_ceil(8) * height;
+                self.start_skip(colors * 3, AfterSkip::TightData(size));
+            }
+            Stage::TightLength => {
+                let mut length = usize::from(held[0] & 0x7f);
+                if let Some(&byte) = held.get(1) {
+                    length |= usize::from(byte & 0x7f) << 7;
+                }
+                if let Some(&byte) = held.get(2) {
+                    length |= usize::from(byte) << 14;
+                }
+                self.start_skip(length, AfterSkip::Rect);
+            }
+            _ => {}
+        }
+    }
+
+    /// Collects an extended cut text payload of `length` bytes
+    ///
+    /// Payloads larger than any clipboard text are consumed and dropped.
+    fn start_cut_text(&mut self, length: usize, messages: &mut Vec<ExtendedClipboardMessage>) {
+        self.cut_data = (length <= MAX_CLIPBOARD_TEXT as usize + 4).then(Vec::new);
+        self.cut_remaining = length;
+        if length == 0 {
+            self.finish_cut_text(messages);
+        }
+    }
+
+    /// Skips `len` bytes of message data
+    fn start_skip(&mut self, len: usize, after: AfterSkip) {
+        self.skip = len;
+        self.after_skip = after;
+        if len == 0 {
+            self.finish_skip();
+        }
+    }
+
+    /// Continues after skipped data
+    fn finish_skip(&mut self) {
+        match self.after_skip {
+            AfterSkip::Message => self.stage = Stage::MessageType,
+            AfterSkip::Rect => self.finish_rect(),
+            AfterSkip::TightData(size) => self.start_tight_data(size),
+        }
+    }
+
+    /// Tight data shorter than 12 bytes is sent uncompressed without a length
+    fn start_tight_data(&mut self, size: usize) {
+        if size == 0 {
+            self.finish_rect();
+        } else if size < 12 {
+            self.start_skip(size, AfterSkip::Rect);
+        } else {
+            self.stage = Stage::TightLength;
+        }
+    }
+
+    /// Moves on to the next rectangle or message
+    fn finish_rect(&mut self) {
+        self.rects_left = self.rects_left.saturating_sub(1);
+        self.stage = if self.rects_left == 0 {
+            Stage::MessageType
+        } else {
+            Stage::RectHeader
+        };
+    }
+
+    /// Parses a complete extended cut text message
+    fn finish_cut_text(&mut self, messages: &mut Vec<ExtendedClipboardMessage>) {
+        if let Some(message) = self
+            .cut_data
+            .take()
+            .and_then(|data| ExtendedClipboardMessage::parse(&data))
+        {
+            messages.push(message);
+        }
+        self.stage = Stage::MessageType;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Turns a client message into the equivalent server message
+    fn as_server_message(mut message: Vec<u8>) -> Vec<u8> {
+        message[0] = SERVER_CUT_TEXT;
+        message
+    }
+
+    fn filter(chunks: &[&[u8]]) -> (Vec<u8>, Vec<ExtendedClipboardMessage>) {
+        let mut filter = ServerMessageFilter::default();
+        filter.start();
+        let mut out = Vec::new();
+        let mut messages = Vec::new();
+        for chunk in chunks {
+            filter.feed(chunk, &mut out, &mut messages);
+        }
+        (out, messages)
+    }
+
+    #[test]
+    fn test_provide_round_trip() {
+        let message = ExtendedClipboardMessage::Provide {
+            text: Some("héllo\n世界".to_string()),
+        }
+        .to_client_message();
+        assert_eq!(message[0], CLIENT_CUT_TEXT);
+        let length = i32::from_be_bytes(message[4..8].try_into().unwrap());
+        assert_eq!(length.unsigned_abs() as usize, message.len() - 8);
+
+        assert_eq!(
+            ExtendedClipboardMessage::parse(&message[8..]),
+            Some(ExtendedClipboardMessage::Provide {
+                text: Some("héllo\n世界".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_filter_extracts_extended_cut_text() {
+        let caps = as_server_message(
+            ExtendedClipboardMessage::Caps {
+                actions: CLIENT_ACTIONS,
+                text: true,
+            }
+            .to_client_message(),
+        );
+        let legacy = [3, 0, 0, 0, 0, 0, 0, 2, b'h', b'i'];
+        // FramebufferUpdate: one raw 1x1 rect, one ZRLE rect, one tight fill
+        let update = [
+            0, 0, 0, 3, //
+            0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 2, 3, 4, //
+            0, 0, 0, 0, 0, 8, 0, 8, 0, 0, 0, 16, 0, 0, 0, 2, 9, 9, //
+            0, 0, 0, 0, 0, 8, 0, 8, 0, 0, 0, 7, 0x80, 5, 6, 7,
+        ];
+        let bell = [2];
+
+        let mut stream = Vec::new();
+        stream.extend_from_slice(&update);
+        stream.extend_from_slice(&caps);
+        stream.extend_from_slice(&legacy);
+        stream.extend_from_slice(&bell);
+
+        let mut expected = update.to_vec();
+        expected.extend_from_slice(&legacy);
+        expected.extend_from_slice(&bell);
+
+        // Whole stream and byte by byte give the same result
+        let (out, messages) = filter(&[&stream]);
+        assert_eq!(out, expected);
+        assert_eq!(
+            messages,
+            vec![ExtendedClipboardMessage::Caps {
+                actions: CLIENT_ACTIONS,
+                text: true,
+            }]
+        );
+        let bytes: Vec<&[u8]> = stream.chunks(1).collect();
+        assert_eq!(filter(&bytes), (expected, messages));
+    }
+
+    #[test]
+    fn test_filter_follows_tight_palette_and_length() {
+        // 16x2 basic rect with a 2-color palette: 4 bytes of data, sent raw
+        // 16x16 copy-filter rect: 768 bytes, compressed to 3 (length 0x03)
+        let update = [
+            0, 0, 0, 2, //
+            0, 0, 0, 0, 0, 16, 0, 2, 0, 0, 0, 7, 0x40, 1, 1, 0, 0, 0, 1, 1, 1, 1, 2, 3, 4, //
+            0, 0, 0, 0, 0, 16, 0, 16, 0, 0, 0, 7, 0x00, 3, 7, 7, 7,
+        ];
+        let provide = as_server_message(
+            ExtendedClipboardMessage::Provide {
+                text: Some("x".to_string()),
+            }
+            .to_client_message(),
+        );
+        let mut stream = update.to_vec();
+        stream.extend_from_slice(&provide);
+
+        let (out, messages) = filter(&[&stream[..7], &stream[7..]]);
+        assert_eq!(out, update);
+        assert_eq!(
+            messages,
+            vec![ExtendedClipboardMessage::Provide {
+                text: Some("x".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_filter_passes_through_before_start_and_after_unknown_data() {
+        let mut filter = ServerMessageFilter::default();
+        let mut out = Vec::new();
+        let mut messages = Vec::new();
+        filter.feed(
+            &[3, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFC],
+            &mut out,
+            &mut messages,
+        );
+        assert_eq!(out.len(), 8);
+
+        filter.start();
+        out.clear();
+        filter.feed(&[42, 3, 0, 0, 0, 0xFF], &mut out, &mut messages);
+        assert_eq!(out, [42, 3, 0, 0, 0, 0xFF]);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn test_extended_clipboard_exchange() {
+        let mut clipboard = ExtendedClipboard::default();
+        assert!(clipboard.set_local_text("early".to_string()).is_none());
+
+        let reply = clipboard.handle_server_message(ExtendedClipboardMessage::Caps {
+            actions: ACTION_CAPS | ACTION_REQUEST | ACTION_NOTIFY | ACTION_PROVIDE,
+            text: true,
+        });
+        let caps = reply.message.expect("client caps");
+        assert_eq!(
+            ExtendedClipboardMessage::parse(&caps[8..]),
+            Some(ExtendedClipboardMessage::Caps {
+                actions: CLIENT_ACTIONS,
+                text: true,
+            })
+        );
+
+        // Local text is announced, then provided on request
+        let notify = clipboard.set_local_text("ünïcode".to_string()).unwrap();
+        assert_eq!(
+            ExtendedClipboardMessage::parse(&notify[8..]),
+            Some(ExtendedClipboardMessage::Notify { text: true })
+        );
+        let reply =
+            clipboard.handle_server_message(ExtendedClipboardMessage::Request { text: true });
+        assert_eq!(
+            ExtendedClipboardMessage::parse(&reply.message.unwrap()[8..]),
+            Some(ExtendedClipboardMessage::Provide {
+                text: Some("ünïcode".to_string()),
+            })
+        );
+
+        // Server text is requested when announced and applied when provided
+        let reply =
+            clipboard.handle_server_message(ExtendedClipboardMessage::Notify { text: true });
+        assert_eq!(
+            ExtendedClipboardMessage::parse(&reply.message.unwrap()[8..]),
+            Some(ExtendedClipboardMessage::Request { text: true })
+        );
+        let reply = clipboard.handle_server_message(ExtendedClipboardMessage::Provide {
+            text: Some("remote".to_string()),
+        });
+        assert_eq!(reply.server_text.as_deref(), Some("remote"));
+        assert!(reply.message.is_none());
+    }
+}
@@ -1,5 +1,6 @@
 //! VNC client configuration
 
+use super::stream::MAX_VNC_LEVEL;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for VNC client connection
@@ -27,8 +28,17 @@ pub struct VncClientConfig {
     /// View-only mode (no input forwarding)
     pub view_only: bool,
 
+    /// Synchronize clipboard text in both directions
+    pub clipboard_enabled: bool,
+
     /// Connection timeout in seconds
     pub timeout_secs: u64,
+
+    /// Tight compression level (0-9), server default if unset
+    pub compression: Option<u8>,
+
+    /// Tight JPEG quality level (0-9), server default if unset
+    pub quality: Option<u8>,
 }
 
 impl Default for VncClientConfig {
@@ -46,7 +56,10 @@ impl Default for VncClientConfig {
             ],
             shared: true,
             view_only: false,
+            clipboard_enabled: true,
             timeout_secs: 30,
+            compression: None,
+            quality: None,
         }
     }
 }
@@ -82,6 +95,13 @@ impl VncClientConfig {
         self
     }
 
+    /// Sets whether clipboard text is synchronized with the server
+    #[must_use]
+    pub const fn with_clipboard_enabled(mut self, enabled: bool) -> Self {
+        self.clipboard_enabled = enabled;
+        self
+    }
+
     /// Sets shared session mode
     #[must_use]
     pub const fn with_shared(mut self, shared: bool) -> Self {
@@ -89,10 +109,28 @@ impl VncClientConfig {
         self
     }
 
-    /// Returns the server address as "host:port"
+    /// Sets the Tight compression level (clamped to 0-9)
+    #[must_use]
+    pub fn with_compression(mut self, level: u8) -> Self {
+        self.compression = Some(level.min(MAX_VNC_LEVEL));
+        self
+    }
+
+    /// Sets the Tight JPEG quality level (clamped to 0-9)
+    #[must_use]
+    pub fn with_quality(mut self, level: u8) -> Self {
+        self.quality = Some(level.min(MAX_VNC_LEVEL));
+        self
+    }
+
+    /// Returns the server address as "host:port", bracketing IPv6 literals
     #[must_use]
     pub fn server_address(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+        format!(
+            "{}:{}",
+            crate::connection::host_for_uri(&self.host),
+            self.port
+        )
     }
 }
 
@@ -121,6 +159,19 @@ pub enum VncEncoding {
     Zrle,
 }
 
+impl VncEncoding {
+    /// Returns the RFB encoding type number
+    #[must_use]
+    pub const fn code(self) -> i32 {
+        match self {
+            Self::Raw => 0,
+            Self::CopyRect => 1,
+            Self::Tight => 7,
+            Self::Zrle => 16,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,19 +182,35 @@ mod tests {
             .with_port(5901)
             .with_password("secret")
             .with_view_only(true)
-            .with_shared(false);
+            .with_shared(false)
+            .with_clipboard_enabled(false);
 
         assert_eq!(config.host, "192.168.1.100");
         assert_eq!(config.port, 5901);
         assert_eq!(config.password, Some("secret".to_string()));
         assert!(config.view_only);
         assert!(!config.shared);
+        assert!(!config.clipboard_enabled);
+        assert!(VncClientConfig::default().clipboard_enabled);
     }
 
     #[test]
     fn test_server_address() {
         let config = VncClientConfig::new("localhost").with_port(5900);
         assert_eq!(config.server_address(), "localhost:5900");
+
+        let config = VncClientConfig::new("fe80::1").with_port(5900);
+        assert_eq!(config.server_address(), "[fe80::1]:5900");
+    }
+
+    #[test]
+    fn test_quality_levels_clamped() {
+        let config = VncClientConfig::new("localhost")
+            .with_compression(3)
+            .with_quality(12);
+        assert_eq!(config.compression, Some(3));
+        assert_eq!(config.quality, Some(9));
+        assert_eq!(VncClientConfig::default().quality, None);
     }
 
     #[test]
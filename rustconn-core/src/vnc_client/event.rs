@@ -70,12 +70,33 @@ pub enum VncClientEvent {
     /// Server sent bell notification
     Bell,
 
+    /// Server clipboard text received through RFB `ServerCutText`, as UTF-8
+    /// when the extended clipboard was negotiated
+    ///
+    /// Not emitted when the clipboard is disabled in the configuration.
+    ServerCutText(String),
+
     /// Server clipboard text
+    #[deprecated(since = "0.6.6", note = "Never emitted; use `ServerCutText`")]
     ClipboardText(String),
 
     /// Authentication required
     AuthRequired,
 
+    /// Tight compression and JPEG quality levels were renegotiated
+    QualityChanged {
+        /// Compression level now requested (0-9)
+        compression: u8,
+        /// JPEG quality level now requested (0-9)
+        quality: u8,
+    },
+
+    /// Response to `CaptureFrame`: the current frame as a PNG image
+    FrameCaptured(Vec<u8>),
+
+    /// Response to `CaptureFrame` when no frame could be captured
+    CaptureFailed(String),
+
     /// Error occurred
     Error(String),
 }
@@ -104,7 +125,15 @@ pub enum VncClientCommand {
         buttons: u8,
     },
 
+    /// Set the server clipboard through RFB `ClientCutText`
+    ///
+    /// Uses UTF-8 when the server supports the extended clipboard and
+    /// Latin-1 otherwise. Ignored when the clipboard is disabled in the
+    /// configuration.
+    SetClipboard(String),
+
     /// Send clipboard text to server
+    #[deprecated(since = "0.6.6", note = "Use `SetClipboard`")]
     ClipboardText(String),
 
     /// Request full framebuffer refresh
@@ -126,6 +155,22 @@ pub enum VncClientCommand {
 
     /// Type text by emulating key presses (for paste functionality)
     TypeText(String),
+
+    /// Renegotiate Tight compression and JPEG quality without reconnecting
+    ///
+    /// Levels range from 0 to 9 and are clamped; lower quality uses less
+    /// bandwidth, higher compression uses less bandwidth but more CPU.
+    SetQuality {
+        /// Compression level (0-9)
+        compression: u8,
+        /// JPEG quality level (0-9)
+        quality: u8,
+    },
+
+    /// Capture the latest framebuffer as a PNG image
+    ///
+    /// Answered with `FrameCaptured` or `CaptureFailed`.
+    CaptureFrame,
 }
 
 #[cfg(test)]
@@ -0,0 +1,126 @@
+//! Client-side copy of the remote framebuffer
+//!
+//! The GUI renders updates as they arrive and does not keep the pixels
+//! around, so the protocol loop mirrors them here to answer screenshot
+//! requests.
+
+use super::VncRect;
+use crate::ffi::{encode_bgra_png, FfiResult};
+
+/// Bytes per BGRA pixel
+const BYTES_PER_PIXEL: usize = 4;
+
+/// BGRA framebuffer kept in sync with server updates
+#[derive(Debug, Default)]
+pub(super) struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    /// Resizes the framebuffer, clearing it to black
+    pub(super) fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.pixels = vec![0; width as usize * height as usize * BYTES_PER_PIXEL];
+    }
+
+    /// Returns true until the server has reported a resolution
+    pub(super) const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Writes raw BGRA pixels into a rectangle
+    ///
+    /// Updates whose data does not match the rectangle (e.g. undecoded JPEG)
+    /// and parts outside the framebuffer are ignored.
+    pub(super) fn blit(&mut self, rect: VncRect, data: &[u8]) {
+        let rect_stride = usize::from(rect.width) * BYTES_PER_PIXEL;
+        if data.len() != rect_stride * usize::from(rect.height) {
+            return;
+        }
+        let Some((x, y, width, height)) = self.clip(rect) else {
+            return;
+        };
+        let stride = self.width as usize * BYTES_PER_PIXEL;
+        for row in 0..height {
+            let src = row * rect_stride;
+            let dst = (y + row) * stride + x * BYTES_PER_PIXEL;
+            self.pixels[dst..dst + width * BYTES_PER_PIXEL]
+                .copy_from_slice(&data[src..src + width * BYTES_PER_PIXEL]);
+        }
+    }
+
+    /// Copies a rectangle within the framebuffer
+    pub(super) fn copy(&mut self, dst: VncRect, src: VncRect) {
+        let Some((x, y, width, height)) = self.clip(src) else {
+            return;
+        };
+        if width != usize::from(src.width) || height != usize::from(src.height) {
+            return;
+        }
+        let mut region = Vec::with_capacity(width * height * BYTES_PER_PIXEL);
+        let stride = self.width as usize * BYTES_PER_PIXEL;
+        for row in 0..height {
+            let start = (y + row) * stride + x * BYTES_PER_PIXEL;
+            region.extend_from_slice(&self.pixels[start..start + width * BYTES_PER_PIXEL]);
+        }
+        self.blit(
+            VncRect {
+                x: dst.x,
+                y: dst.y,
+                ..src
+            },
+            &region,
+        );
+    }
+
+    /// Encodes the framebuffer as PNG
+    pub(super) fn to_png(&self) -> FfiResult<Vec<u8>> {
+        encode_bgra_png(self.width, self.height, &self.pixels)
+    }
+
+    /// Clips a rectangle to the framebuffer as `(x, y, width, height)`
+    fn clip(&self, rect: VncRect) -> Option<(usize, usize, usize, usize)> {
+        let (fb_width, fb_height) = (self.width as usize, self.height as usize);
+        let (x, y) = (usize::from(rect.x), usize::from(rect.y));
+        if x >= fb_width || y >= fb_height {
+            return None;
+        }
+        let width = usize::from(rect.width).min(fb_width - x);
+        let height = usize::from(rect.height).min(fb_height - y);
+        (width > 0 && height > 0).then_some((x, y, width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(fb: &Framebuffer, x: usize, y: usize) -> &[u8] {
+        let start = (y * fb.width as usize + x) * BYTES_PER_PIXEL;
+        &fb.pixels[start..start + BYTES_PER_PIXEL]
+    }
+
+    #[test]
+    fn test_blit_and_copy() {
+        let mut fb = Framebuffer::default();
+        assert!(fb.is_empty());
+        fb.resize(4, 3);
+
+        fb.blit(VncRect::new(1, 1, 1, 1), &[1, 2, 3, 4]);
+        assert_eq!(pixel(&fb, 1, 1), [1, 2, 3, 4]);
+
+        fb.copy(VncRect::new(3, 2, 1, 1), VncRect::new(1, 1, 1, 1));
+        assert_eq!(pixel(&fb, 3, 2), [1, 2, 3, 4]);
+
+        // Mismatched data is ignored, overhanging rectangles are clipped
+        fb.blit(VncRect::new(0, 0, 2, 1), &[9; 4]);
+        assert_eq!(pixel(&fb, 0, 0), [0, 0, 0, 0]);
+        fb.blit(VncRect::new(3, 0, 2, 1), &[7; 8]);
+        assert_eq!(pixel(&fb, 3, 0), [7, 7, 7, 7]);
+
+        assert!(fb.to_png().unwrap().starts_with(b"\x89PNG"));
+    }
+}
@@ -20,11 +20,17 @@
 #[cfg(feature = "vnc-embedded")]
 mod client;
 #[cfg(feature = "vnc-embedded")]
+mod clipboard;
+#[cfg(feature = "vnc-embedded")]
 mod config;
 #[cfg(feature = "vnc-embedded")]
 mod error;
 #[cfg(feature = "vnc-embedded")]
 mod event;
+#[cfg(feature = "vnc-embedded")]
+mod framebuffer;
+#[cfg(feature = "vnc-embedded")]
+mod stream;
 
 #[cfg(feature = "vnc-embedded")]
 pub use client::{VncClient, VncCommandSender, VncEventReceiver};
@@ -34,6 +40,8 @@ pub use config::VncClientConfig;
 pub use error::VncClientError;
 #[cfg(feature = "vnc-embedded")]
 pub use event::{VncClientCommand, VncClientEvent, VncRect};
+#[cfg(feature = "vnc-embedded")]
+pub use stream::MAX_VNC_LEVEL;
 
 /// Check if embedded VNC support is available
 #[must_use]
@@ -0,0 +1,370 @@
+//! Stream wrapper for injecting RFB client messages
+//!
+//! vnc-rs owns the connection once the handshake finishes and offers no way
+//! to resend `SetEncodings`. `InjectableStream` sits between vnc-rs and the
+//! socket and writes queued messages just before the next message vnc-rs
+//! sends. vnc-rs writes every client message with a single `write_all`, so
+//! a write that the socket only partly accepts is continued by the next
+//! write; injected messages wait until it is complete and never land inside
+//! a partially written message.
+//!
+//! On the read side the wrapper can run a [`ServerMessageFilter`] that takes
+//! extended clipboard messages out of the server stream.
+
+use super::clipboard::{
+    ExtendedClipboardMessage, ServerMessageFilter, EXTENDED_CLIPBOARD_ENCODING,
+};
+use super::config::VncEncoding;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::mpsc;
+
+/// Highest JPEG quality and compression level accepted by Tight encoding
+pub const MAX_VNC_LEVEL: u8 = 9;
+
+/// Base of the compression level pseudo-encodings (-256..=-247)
+const COMPRESS_LEVEL_BASE: i32 = -256;
+
+/// Base of the JPEG quality level pseudo-encodings (-32..=-23)
+const QUALITY_LEVEL_BASE: i32 = -32;
+
+/// RFB `SetEncodings` message type
+const SET_ENCODINGS: u8 = 2;
+
+/// RFB `ClientCutText` message type
+const CLIENT_CUT_TEXT: u8 = 6;
+
+/// RFB `SetPixelFormat` message type and length, the first message vnc-rs
+/// sends after the handshake
+const SET_PIXEL_FORMAT: (u8, usize) = (0, 20);
+
+/// Builds a `SetEncodings` message
+///
+/// The compression and quality levels are appended as pseudo-encodings after
+/// the regular encodings and are clamped to [`MAX_VNC_LEVEL`], followed by
+/// the extended clipboard pseudo-encoding if requested.
+#[must_use]
+pub fn set_encodings_message(
+    encodings: &[VncEncoding],
+    compression: Option<u8>,
+    quality: Option<u8>,
+    extended_clipboard: bool,
+) -> Vec<u8> {
+    let codes: Vec<i32> = encodings
+        .iter()
+        .map(|encoding| encoding.code())
+        .chain(compression.map(|level| COMPRESS_LEVEL_BASE + i32::from(level.min(MAX_VNC_LEVEL))))
+        .chain(quality.map(|level| QUALITY_LEVEL_BASE + i32::from(level.min(MAX_VNC_LEVEL))))
+        .chain(extended_clipboard.then_some(EXTENDED_CLIPBOARD_ENCODING))
+        .collect();
+
+    let count = u16::try_from(codes.len()).unwrap_or(u16::MAX);
+    let mut message = vec![SET_ENCODINGS, 0];
+    message.extend_from_slice(&count.to_be_bytes());
+    for code in codes.iter().take(usize::from(count)) {
+        message.extend_from_slice(&code.to_be_bytes());
+    }
+    message
+}
+
+/// Builds a `ClientCutText` message
+///
+/// Plain cut text is Latin-1 with LF line endings (RFC 6143 7.5.6).
+/// Characters outside Latin-1 are replaced with `?`. Used for servers that
+/// do not support the extended clipboard.
+#[must_use]
+pub fn client_cut_text_message(text: &str) -> Vec<u8> {
+    let latin1: Vec<u8> = text
+        .replace("\r\n", "\n")
+        .chars()
+        .map(|ch| u8::try_from(u32::from(ch)).unwrap_or(b'?'))
+        .collect();
+
+    let length = u32::try_from(latin1.len()).unwrap_or(u32::MAX);
+    let mut message = vec![CLIENT_CUT_TEXT, 0, 0, 0];
+    message.extend_from_slice(&length.to_be_bytes());
+    message.extend_from_slice(&latin1[..length as usize]);
+    message
+}
+
+/// Handle for queueing messages on an [`InjectableStream`]
+#[derive(Debug, Clone, Default)]
+pub struct MessageInjector {
+    queue: Arc<Mutex<Vec<u8>>>,
+}
+
+impl MessageInjector {
+    /// Queues a complete client message for sending
+    pub fn inject(&self, message: &[u8]) {
+        self.queue.lock().unwrap().extend_from_slice(message);
+    }
+
+    /// Takes all queued bytes
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.queue.lock().unwrap())
+    }
+}
+
+/// Stream that interleaves injected messages with the ones vnc-rs writes
+#[derive(Debug)]
+pub struct InjectableStream<S> {
+    inner: S,
+    /// Injected bytes not yet accepted by the socket
+    pending: Vec<u8>,
+    /// Whether the last write left part of its message unwritten
+    mid_message: bool,
+    injector: MessageInjector,
+    filter: Option<ServerMessageFilter>,
+    filtered: Vec<u8>,
+    clipboard_tx: Option<mpsc::UnboundedSender<ExtendedClipboardMessage>>,
+}
+
+impl<S: AsyncWrite + Unpin> InjectableStream<S> {
+    /// Wraps a stream, returning it with a handle for injecting messages
+    pub fn new(inner: S) -> (Self, MessageInjector) {
+        let injector = MessageInjector::default();
+        let stream = Self {
+            inner,
+            pending: Vec::new(),
+            mid_message: false,
+            injector: injector.clone(),
+            filter: None,
+            filtered: Vec::new(),
+            clipboard_tx: None,
+        };
+        (stream, injector)
+    }
+
+    /// Takes extended clipboard messages out of the server stream
+    ///
+    /// Filtering starts with the first message vnc-rs sends after the
+    /// handshake. The returned receiver yields the extracted messages.
+    pub fn filter_extended_clipboard(
+        &mut self,
+    ) -> mpsc::UnboundedReceiver<ExtendedClipboardMessage> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.filter = Some(ServerMessageFilter::default());
+        self.clipboard_tx = Some(tx);
+        rx
+    }
+
+    /// Writes out buffered bytes until none remain
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.pending.is_empty() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.pending))?;
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.pending.drain(..written);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for InjectableStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let Some(filter) = this.filter.as_mut().filter(|f| f.is_active()) else {
+            return Pin::new(&mut this.inner).poll_read(cx, buf);
+        };
+
+        while this.filtered.is_empty() {
+            let start = buf.filled().len();
+            ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+            if buf.filled().len() == start {
+                // End of stream
+                return Poll::Ready(Ok(()));
+            }
+
+            let received = buf.filled()[start..].to_vec();
+            buf.set_filled(start);
+            let mut messages = Vec::new();
+            filter.feed(&received, &mut this.filtered, &mut messages);
+            if let Some(tx) = &this.clipboard_tx {
+                for message in messages {
+                    let _ = tx.send(message);
+                }
+            }
+        }
+
+        let n = this.filtered.len().min(buf.remaining());
+        buf.put_slice(&this.filtered[..n]);
+        this.filtered.drain(..n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for InjectableStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        // Injected messages go out before any new bytes
+        ready!(this.poll_drain(cx))?;
+        if !this.mid_message {
+            let injected = this.injector.take();
+            if !injected.is_empty() {
+                this.pending = injected;
+                ready!(this.poll_drain(cx))?;
+            }
+        }
+
+        let written = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+
+        // The server stream after the handshake starts with the reply to
+        // the first client message
+        if !this.mid_message && written > 0 {
+            if let Some(filter) = this.filter.as_mut() {
+                if !filter.is_active()
+                    && buf.len() == SET_PIXEL_FORMAT.1
+                    && buf[0] == SET_PIXEL_FORMAT.0
+                {
+                    filter.start();
+                }
+            }
+        }
+        this.mid_message = written < buf.len();
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_set_encodings_message() {
+        let message = set_encodings_message(
+            &[VncEncoding::Tight, VncEncoding::Raw],
+            Some(2),
+            Some(42),
+            false,
+        );
+
+        assert_eq!(&message[..4], &[2, 0, 0, 4]);
+        assert_eq!(&message[4..8], &7_i32.to_be_bytes());
+        assert_eq!(&message[8..12], &0_i32.to_be_bytes());
+        assert_eq!(&message[12..16], &(-254_i32).to_be_bytes());
+        // Quality is clamped to level 9
+        assert_eq!(&message[16..20], &(-23_i32).to_be_bytes());
+    }
+
+    #[test]
+    fn test_set_encodings_message_without_levels() {
+        let message = set_encodings_message(&[VncEncoding::Zrle], None, None, false);
+        assert_eq!(message, vec![2, 0, 0, 1, 0, 0, 0, 16]);
+
+        let message = set_encodings_message(&[VncEncoding::Zrle], None, None, true);
+        assert_eq!(&message[..4], &[2, 0, 0, 2]);
+        assert_eq!(&message[8..], &EXTENDED_CLIPBOARD_ENCODING.to_be_bytes());
+    }
+
+    #[test]
+    fn test_client_cut_text_message_latin1() {
+        let message = client_cut_text_message("caf\u{e9}\r\n\u{4e16}");
+        assert_eq!(&message[..8], &[6, 0, 0, 0, 0, 0, 0, 6]);
+        assert_eq!(&message[8..], &[b'c', b'a', b'f', 0xE9, b'\n', b'?']);
+    }
+
+    #[test]
+    fn test_injected_message_precedes_next_write() {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        runtime.block_on(async {
+            let (client, mut server) = tokio::io::duplex(64);
+            let (mut stream, injector) = InjectableStream::new(client);
+
+            stream.write_all(&[3, 1]).await.unwrap();
+            injector.inject(&[9, 9, 9]);
+            stream.write_all(&[4, 2]).await.unwrap();
+            stream.flush().await.unwrap();
+
+            let mut received = [0_u8; 7];
+            server.read_exact(&mut received).await.unwrap();
+            assert_eq!(received, [3, 1, 9, 9, 9, 4, 2]);
+        });
+    }
+
+    #[test]
+    fn test_partial_writes_apply_backpressure() {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        runtime.block_on(async {
+            let (client, mut server) = tokio::io::duplex(4);
+            let (mut stream, injector) = InjectableStream::new(client);
+
+            // Only the bytes the socket takes are reported as written
+            let written = stream.write(&[1; 10]).await.unwrap();
+            assert_eq!(written, 4);
+
+            // The rest of the message is still pending, so nothing is
+            // injected until it has been written
+            injector.inject(&[9, 9]);
+            let writer = async {
+                stream.write_all(&[1; 6]).await.unwrap();
+                stream.write_all(&[2]).await.unwrap();
+                stream.flush().await.unwrap();
+            };
+            let mut received = [0_u8; 13];
+            let reader = server.read_exact(&mut received);
+            let ((), read) = tokio::join!(writer, reader);
+            read.unwrap();
+
+            let mut expected = vec![1_u8; 10];
+            expected.extend_from_slice(&[9, 9, 2]);
+            assert_eq!(received.to_vec(), expected);
+        });
+    }
+
+    #[test]
+    fn test_extended_clipboard_filtered_after_handshake() {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        runtime.block_on(async {
+            let (client, mut server) = tokio::io::duplex(256);
+            let (mut stream, _injector) = InjectableStream::new(client);
+            let mut clipboard_rx = stream.filter_extended_clipboard();
+
+            // Handshake bytes pass through unfiltered
+            server.write_all(&[3, 0, 0, 0]).await.unwrap();
+            let mut handshake = [0_u8; 4];
+            stream.read_exact(&mut handshake).await.unwrap();
+            assert_eq!(handshake, [3, 0, 0, 0]);
+
+            stream.write_all(&[0; 20]).await.unwrap();
+            let mut caps = ExtendedClipboardMessage::Caps {
+                actions: 0,
+                text: true,
+            }
+            .to_client_message();
+            caps[0] = 3;
+            server.write_all(&caps).await.unwrap();
+            server.write_all(&[2]).await.unwrap();
+
+            let mut bell = [0_u8; 1];
+            stream.read_exact(&mut bell).await.unwrap();
+            assert_eq!(bell, [2]);
+            assert!(matches!(
+                clipboard_rx.try_recv(),
+                Ok(ExtendedClipboardMessage::Caps { text: true, .. })
+            ));
+        });
+    }
+}
@@ -2,10 +2,16 @@
 //!
 //! This module provides functionality to wake sleeping machines before connecting
 //! by sending magic packets to their MAC addresses.
+//!
+//! Packets can be sent to a single broadcast address ([`send_wol`]) or out of
+//! every local IPv4 interface ([`send_wol_all_interfaces`]) on multi-homed
+//! hosts. Hosts on a remote subnet can be reached through a directed
+//! broadcast address if the router forwards it.
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::net::UdpSocket;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::process::Command;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -31,6 +37,14 @@ pub enum WolError {
     /// Failed to set socket options
     #[error("Failed to set socket options: {0}")]
     SocketOptionError(String),
+
+    /// Invalid SecureOn password
+    #[error("Invalid SecureOn password: {0}")]
+    InvalidSecureOnPassword(String),
+
+    /// Failed to enumerate local network interfaces
+    #[error("Failed to enumerate network interfaces: {0}")]
+    InterfaceEnumeration(String),
 }
 
 /// Result type alias for WOL operations
@@ -160,6 +174,85 @@ impl TryFrom<String> for MacAddress {
     }
 }
 
+/// Size of a SecureOn password in bytes
+pub const SECURE_ON_PASSWORD_SIZE: usize = 6;
+
+/// A SecureOn password appended to magic packets
+///
+/// Some network cards only wake up when the magic packet ends with this
+/// 6-byte password. It is written in the same notation as a MAC address
+/// (e.g. `01:02:03:04:05:06`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SecureOnPassword([u8; SECURE_ON_PASSWORD_SIZE]);
+
+impl SecureOnPassword {
+    /// Creates a new `SecureOnPassword` from raw bytes
+    #[must_use]
+    pub const fn new(bytes: [u8; SECURE_ON_PASSWORD_SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// Creates a password from a byte slice
+    ///
+    /// # Errors
+    ///
+    /// Returns `WolError::InvalidSecureOnPassword` if the slice is not
+    /// exactly 6 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> WolResult<Self> {
+        <[u8; SECURE_ON_PASSWORD_SIZE]>::try_from(bytes)
+            .map(Self)
+            .map_err(|_| {
+                WolError::InvalidSecureOnPassword(format!(
+                    "expected {SECURE_ON_PASSWORD_SIZE} bytes, found {}",
+                    bytes.len()
+                ))
+            })
+    }
+
+    /// Returns the raw bytes of the password
+    #[must_use]
+    pub const fn bytes(&self) -> &[u8; SECURE_ON_PASSWORD_SIZE] {
+        &self.0
+    }
+}
+
+impl FromStr for SecureOnPassword {
+    type Err = WolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Same notation as a MAC address
+        MacAddress::from_str(s)
+            .map(|mac| Self(*mac.bytes()))
+            .map_err(|e| match e {
+                WolError::InvalidMacFormat(msg) | WolError::InvalidMacByte(msg) => {
+                    WolError::InvalidSecureOnPassword(msg)
+                }
+                other => other,
+            })
+    }
+}
+
+impl fmt::Display for SecureOnPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", MacAddress::new(self.0).format_colon())
+    }
+}
+
+impl From<SecureOnPassword> for String {
+    fn from(password: SecureOnPassword) -> Self {
+        password.to_string()
+    }
+}
+
+impl TryFrom<String> for SecureOnPassword {
+    type Error = WolError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 /// Default WOL port (discard protocol)
 pub const DEFAULT_WOL_PORT: u16 = 9;
 
@@ -183,6 +276,16 @@ pub struct WolConfig {
     /// Seconds to wait after sending the packet before attempting connection
     #[serde(default = "default_wait_seconds")]
     pub wait_seconds: u32,
+    /// SecureOn password appended to the magic packet, if the NIC requires one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure_on_password: Option<SecureOnPassword>,
+    /// Directed broadcast address of a remote subnet (e.g. `10.20.30.255`)
+    ///
+    /// When set, it replaces `broadcast_address` for [`send_wol`] and is
+    /// added to the targets of [`send_wol_all_interfaces`]. The router must
+    /// be configured to forward directed broadcasts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directed_broadcast: Option<Ipv4Addr>,
 }
 
 fn default_broadcast_address() -> String {
@@ -206,6 +309,8 @@ impl WolConfig {
             broadcast_address: DEFAULT_BROADCAST_ADDRESS.to_string(),
             port: DEFAULT_WOL_PORT,
             wait_seconds: DEFAULT_WOL_WAIT_SECONDS,
+            secure_on_password: None,
+            directed_broadcast: None,
         }
     }
 
@@ -229,9 +334,33 @@ impl WolConfig {
         self.wait_seconds = seconds;
         self
     }
+
+    /// Sets the SecureOn password
+    #[must_use]
+    pub const fn with_secure_on_password(mut self, password: SecureOnPassword) -> Self {
+        self.secure_on_password = Some(password);
+        self
+    }
+
+    /// Sets a directed broadcast address for waking hosts on a remote subnet
+    #[must_use]
+    pub const fn with_directed_broadcast(mut self, address: Ipv4Addr) -> Self {
+        self.directed_broadcast = Some(address);
+        self
+    }
+
+    /// Returns the address [`send_wol`] sends to
+    #[must_use]
+    pub fn target_address(&self) -> String {
+        self.directed_broadcast
+            .map_or_else(|| self.broadcast_address.clone(), |addr| addr.to_string())
+    }
 }
 
 /// Magic packet size: 6 bytes of 0xFF + 16 repetitions of 6-byte MAC address
+///
+/// Packets carrying a SecureOn password are [`SECURE_ON_PASSWORD_SIZE`]
+/// bytes longer.
 pub const MAGIC_PACKET_SIZE: usize = 6 + (16 * 6);
 
 /// Generates a Wake On LAN magic packet for the given MAC address
@@ -239,20 +368,23 @@ pub const MAGIC_PACKET_SIZE: usize = 6 + (16 * 6);
 /// The magic packet consists of:
 /// - 6 bytes of 0xFF (synchronization stream)
 /// - 16 repetitions of the target MAC address (96 bytes)
+/// - the SecureOn password, if given (6 bytes)
 ///
-/// Total size: 102 bytes
+/// Total size: 102 bytes, or 108 bytes with a SecureOn password
 #[must_use]
-pub fn generate_magic_packet(mac: &MacAddress) -> [u8; MAGIC_PACKET_SIZE] {
-    let mut packet = [0u8; MAGIC_PACKET_SIZE];
+pub fn generate_magic_packet(mac: &MacAddress, secure_on: Option<&SecureOnPassword>) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(MAGIC_PACKET_SIZE + SECURE_ON_PASSWORD_SIZE);
 
     // First 6 bytes are 0xFF
-    packet[..6].fill(0xFF);
+    packet.extend_from_slice(&[0xFF; 6]);
 
     // Next 96 bytes are 16 repetitions of the MAC address
-    let mac_bytes = mac.bytes();
-    for i in 0..16 {
-        let offset = 6 + (i * 6);
-        packet[offset..offset + 6].copy_from_slice(mac_bytes);
+    for _ in 0..16 {
+        packet.extend_from_slice(mac.bytes());
+    }
+
+    if let Some(password) = secure_on {
+        packet.extend_from_slice(password.bytes());
     }
 
     packet
@@ -262,16 +394,26 @@ pub fn generate_magic_packet(mac: &MacAddress) -> [u8; MAGIC_PACKET_SIZE] {
 ///
 /// # Arguments
 /// * `mac` - The MAC address of the target machine
+/// * `secure_on` - Optional SecureOn password appended to the packet
 /// * `broadcast` - The broadcast address to send to (e.g., "255.255.255.255")
 /// * `port` - The UDP port to send to (typically 9 or 7)
 ///
 /// # Errors
 /// Returns an error if the socket cannot be created or the packet cannot be sent.
-pub fn send_magic_packet(mac: &MacAddress, broadcast: &str, port: u16) -> WolResult<()> {
-    let packet = generate_magic_packet(mac);
+pub fn send_magic_packet(
+    mac: &MacAddress,
+    secure_on: Option<&SecureOnPassword>,
+    broadcast: &str,
+    port: u16,
+) -> WolResult<()> {
+    let packet = generate_magic_packet(mac, secure_on);
+    send_packet(&packet, Ipv4Addr::UNSPECIFIED, broadcast, port)
+}
 
+/// Sends a packet from the given local address to a broadcast target
+fn send_packet(packet: &[u8], local: Ipv4Addr, broadcast: &str, port: u16) -> WolResult<()> {
     // Create UDP socket
-    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| WolError::SocketError(e.to_string()))?;
+    let socket = UdpSocket::bind((local, 0)).map_err(|e| WolError::SocketError(e.to_string()))?;
 
     // Enable broadcast
     socket
@@ -281,8 +423,8 @@ pub fn send_magic_packet(mac: &MacAddress, broadcast: &str, port: u16) -> WolRes
     // Send the magic packet
     let target = format!("{broadcast}:{port}");
     socket
-        .send_to(&packet, &target)
-        .map_err(|e| WolError::SendError(e.to_string()))?;
+        .send_to(packet, &target)
+        .map_err(|e| WolError::SendError(format!("{target}: {e}")))?;
 
     Ok(())
 }
@@ -292,7 +434,121 @@ pub fn send_magic_packet(mac: &MacAddress, broadcast: &str, port: u16) -> WolRes
 /// # Errors
 /// Returns an error if the packet cannot be sent.
 pub fn send_wol(config: &WolConfig) -> WolResult<()> {
-    send_magic_packet(&config.mac_address, &config.broadcast_address, config.port)
+    send_magic_packet(
+        &config.mac_address,
+        config.secure_on_password.as_ref(),
+        &config.target_address(),
+        config.port,
+    )
+}
+
+/// An IPv4 address of a local network interface and its broadcast address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceBroadcast {
+    /// Interface name (e.g. `eth0`)
+    pub interface: String,
+    /// Local address assigned to the interface
+    pub address: Ipv4Addr,
+    /// Broadcast address of the interface's subnet
+    pub broadcast: Ipv4Addr,
+}
+
+/// Enumerates the IPv4 broadcast addresses of local interfaces
+///
+/// Uses `ip -4 -o addr show`; interfaces without a broadcast address
+/// (loopback, point-to-point links) are skipped.
+///
+/// # Errors
+/// Returns `WolError::InterfaceEnumeration` if `ip` cannot be run or fails.
+pub fn local_broadcast_addresses() -> WolResult<Vec<InterfaceBroadcast>> {
+    let output = Command::new("ip")
+        .args(["-4", "-o", "addr", "show"])
+        .output()
+        .map_err(|e| WolError::InterfaceEnumeration(format!("Failed to run ip: {e}")))?;
+
+    if !output.status.success() {
+        return Err(WolError::InterfaceEnumeration(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(parse_ip_addr_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parses `ip -4 -o addr show` output into interface broadcast addresses
+///
+/// Each line looks like
+/// `2: eth0    inet 192.168.1.10/24 brd 192.168.1.255 scope global eth0`.
+#[must_use]
+pub fn parse_ip_addr_output(output: &str) -> Vec<InterfaceBroadcast> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace().skip(1);
+            let interface = tokens.next()?.trim_end_matches(':').to_string();
+            let mut address = None;
+            let mut broadcast = None;
+            while let Some(token) = tokens.next() {
+                match token {
+                    "inet" => {
+                        address = tokens
+                            .next()
+                            .and_then(|cidr| cidr.split('/').next())
+                            .and_then(|ip| ip.parse().ok());
+                    }
+                    "brd" => broadcast = tokens.next().and_then(|ip| ip.parse().ok()),
+                    _ => {}
+                }
+            }
+            Some(InterfaceBroadcast {
+                interface,
+                address: address?,
+                broadcast: broadcast?,
+            })
+        })
+        .collect()
+}
+
+/// Sends the magic packet out of every local IPv4 interface
+///
+/// The packet is sent to each interface's broadcast address from that
+/// interface's own address, and additionally to the configured directed
+/// broadcast address, if any.
+///
+/// # Errors
+///
+/// Fatal errors are returned as the outer error and mean nothing was sent:
+/// `WolError::InterfaceEnumeration` if interfaces cannot be listed, or if no
+/// interface has a broadcast address and no directed broadcast is set.
+///
+/// Socket and send failures are per-interface: they appear as `Err` entries
+/// in the returned list while the remaining interfaces are still tried.
+/// Successful entries contain the broadcast address the packet was sent to.
+pub fn send_wol_all_interfaces(config: &WolConfig) -> WolResult<Vec<WolResult<Ipv4Addr>>> {
+    let interfaces = local_broadcast_addresses()?;
+    if interfaces.is_empty() && config.directed_broadcast.is_none() {
+        return Err(WolError::InterfaceEnumeration(
+            "no interface with an IPv4 broadcast address".to_string(),
+        ));
+    }
+
+    let packet = generate_magic_packet(&config.mac_address, config.secure_on_password.as_ref());
+    let targets = interfaces
+        .iter()
+        .map(|iface| (iface.address, iface.broadcast))
+        .chain(
+            config
+                .directed_broadcast
+                .map(|addr| (Ipv4Addr::UNSPECIFIED, addr)),
+        );
+
+    Ok(targets
+        .map(|(local, broadcast)| {
+            send_packet(&packet, local, &broadcast.to_string(), config.port).map(|()| broadcast)
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -346,7 +602,7 @@ mod tests {
     #[test]
     fn test_magic_packet_format() {
         let mac = MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
-        let packet = generate_magic_packet(&mac);
+        let packet = generate_magic_packet(&mac, None);
 
         // Check size
         assert_eq!(packet.len(), MAGIC_PACKET_SIZE);
@@ -362,6 +618,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_magic_packet_with_secure_on_password() {
+        let mac = MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+        let password = SecureOnPassword::new([1, 2, 3, 4, 5, 6]);
+        let packet = generate_magic_packet(&mac, Some(&password));
+
+        assert_eq!(packet.len(), MAGIC_PACKET_SIZE + SECURE_ON_PASSWORD_SIZE);
+        assert_eq!(
+            &packet[..MAGIC_PACKET_SIZE],
+            &generate_magic_packet(&mac, None)[..]
+        );
+        assert_eq!(&packet[MAGIC_PACKET_SIZE..], &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_secure_on_password_parsing() {
+        let password: SecureOnPassword = "01:02:03:04:05:06".parse().unwrap();
+        assert_eq!(password.bytes(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(password.to_string(), "01:02:03:04:05:06");
+
+        assert!(matches!(
+            "01:02:03".parse::<SecureOnPassword>(),
+            Err(WolError::InvalidSecureOnPassword(_))
+        ));
+        assert!(matches!(
+            SecureOnPassword::from_bytes(&[1, 2, 3, 4, 5, 6, 7]),
+            Err(WolError::InvalidSecureOnPassword(_))
+        ));
+        assert!(SecureOnPassword::from_bytes(&[0; 6]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_ip_addr_output() {
+        let output = "\
+1: lo    inet 127.0.0.1/8 scope host lo\\       valid_lft forever preferred_lft forever
+2: eth0    inet 192.168.1.10/24 brd 192.168.1.255 scope global eth0\\       valid_lft forever
+3: wg0    inet 10.8.0.2/32 scope global wg0\\       valid_lft forever preferred_lft forever
+4: eth1    inet 10.20.0.5/16 brd 10.20.255.255 scope global eth1\\       valid_lft forever
+";
+        let interfaces = parse_ip_addr_output(output);
+        assert_eq!(
+            interfaces,
+            vec![
+                InterfaceBroadcast {
+                    interface: "eth0".to_string(),
+                    address: Ipv4Addr::new(192, 168, 1, 10),
+                    broadcast: Ipv4Addr::new(192, 168, 1, 255),
+                },
+                InterfaceBroadcast {
+                    interface: "eth1".to_string(),
+                    address: Ipv4Addr::new(10, 20, 0, 5),
+                    broadcast: Ipv4Addr::new(10, 20, 255, 255),
+                },
+            ]
+        );
+        assert!(parse_ip_addr_output("").is_empty());
+    }
+
+    #[test]
+    fn test_directed_broadcast_overrides_target() {
+        let mac = MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+        let config = WolConfig::new(mac);
+        assert_eq!(config.target_address(), DEFAULT_BROADCAST_ADDRESS);
+
+        let config = config.with_directed_broadcast(Ipv4Addr::new(10, 20, 30, 255));
+        assert_eq!(config.target_address(), "10.20.30.255");
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""directed_broadcast":"10.20.30.255""#));
+        assert_eq!(serde_json::from_str::<WolConfig>(&json).unwrap(), config);
+    }
+
     #[test]
     fn test_wol_config_defaults() {
         let mac = MacAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
@@ -391,9 +719,15 @@ mod tests {
         let config = WolConfig::new(mac);
 
         let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("secure_on_password"));
         let parsed: WolConfig = serde_json::from_str(&json).unwrap();
 
         assert_eq!(config, parsed);
+
+        let config = config.with_secure_on_password(SecureOnPassword::new([1, 2, 3, 4, 5, 6]));
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""secure_on_password":"01:02:03:04:05:06""#));
+        assert_eq!(serde_json::from_str::<WolConfig>(&json).unwrap(), config);
     }
 
     #[test]
@@ -6,8 +6,8 @@
 use std::path::PathBuf;
 
 use rustconn_core::models::{
-    Connection, ConnectionGroup, ProtocolConfig, RdpConfig, Resolution, SshAuthMethod, SshConfig,
-    SshKeySource, VncConfig,
+    Connection, ConnectionGroup, ProtocolConfig, RdpConfig, Resolution, SshAlgorithms,
+    SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 
 /// Creates a sample SSH connection with key file authentication.
@@ -32,6 +32,9 @@ pub fn sample_ssh_connection_with_key() -> Connection {
         custom_options: std::collections::HashMap::new(),
         startup_command: None,
         jump_host_id: None,
+        forwards: Vec::new(),
+        environment: Vec::new(),
+        algorithms: SshAlgorithms::default(),
     };
 
     let mut conn = Connection::new(
@@ -65,6 +68,9 @@ pub fn sample_ssh_connection_custom_port() -> Connection {
         custom_options: std::collections::HashMap::new(),
         startup_command: None,
         jump_host_id: None,
+        forwards: Vec::new(),
+        environment: Vec::new(),
+        algorithms: SshAlgorithms::default(),
     };
 
     let mut conn = Connection::new(
@@ -96,6 +102,9 @@ pub fn sample_ssh_connection_with_proxy() -> Connection {
         custom_options: std::collections::HashMap::new(),
         startup_command: None,
         jump_host_id: None,
+        forwards: Vec::new(),
+        environment: Vec::new(),
+        algorithms: SshAlgorithms::default(),
     };
 
     let mut conn = Connection::new(
@@ -120,6 +129,7 @@ pub fn sample_rdp_connection_with_domain() -> Connection {
         resolution: Some(Resolution::new(1920, 1080)),
         color_depth: Some(32),
         audio_redirect: true,
+        microphone_redirect: false,
         gateway: None,
         shared_folders: Vec::new(),
         custom_args: Vec::new(),
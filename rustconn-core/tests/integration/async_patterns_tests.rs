@@ -26,12 +26,15 @@ fn create_test_connection(name: &str, host: &str) -> Connection {
         protocol: ProtocolType::Ssh,
         username: Some("testuser".to_string()),
         group_id: None,
+        additional_group_ids: Vec::new(),
         tags: Vec::new(),
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         protocol_config: ProtocolConfig::Ssh(SshConfig::default()),
         sort_order: 0,
+        launch_order: 0,
         last_connected: None,
+        connect_count: 0,
         password_source: PasswordSource::None,
         domain: None,
         custom_properties: Vec::new(),
@@ -46,6 +49,9 @@ fn create_test_connection(name: &str, host: &str) -> Connection {
         remember_window_position: false,
         window_geometry: None,
         skip_port_check: false,
+        color: None,
+        icon: None,
+        archived: false,
     }
 }
 
@@ -0,0 +1,131 @@
+//! Integration tests for configuration schema migrations
+//!
+//! These tests load configuration files written by older releases and verify
+//! they are upgraded to the current schema without losing data.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustconn_core::config::CURRENT_SCHEMA_VERSION;
+use rustconn_core::models::{PasswordSource, ProtocolConfig, SshAuthMethod};
+use rustconn_core::{ConfigError, ConfigManager};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Copies the v1 configuration fixtures into a temporary config directory
+fn v1_config_dir() -> TempDir {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("config_v1");
+    let temp = TempDir::new().expect("temp dir");
+    for name in ["connections.toml", "groups.toml"] {
+        fs::copy(fixtures.join(name), temp.path().join(name)).expect("copy fixture");
+    }
+    temp
+}
+
+fn schema_version(path: &Path) -> Option<i64> {
+    let table: toml::Table = toml::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    table.get("schema_version")?.as_integer()
+}
+
+#[test]
+fn test_v1_connections_migrate_without_data_loss() {
+    let temp = v1_config_dir();
+    let manager = ConfigManager::with_config_dir(temp.path().to_path_buf());
+
+    let connections = manager.load_connections().expect("load v1 connections");
+    assert_eq!(connections.len(), 2);
+
+    let web = &connections[0];
+    assert_eq!(
+        web.id,
+        Uuid::parse_str("d5033eaa-38d7-489e-a4d0-61ba612fc983").unwrap()
+    );
+    assert_eq!(web.name, "web");
+    assert_eq!(web.host, "web.example.com");
+    assert_eq!(web.port, 2222);
+    assert_eq!(web.username.as_deref(), Some("deploy"));
+    assert_eq!(web.tags, vec!["prod", "web"]);
+    assert_eq!(web.sort_order, 1);
+    assert_eq!(web.launch_order, 0);
+    assert_eq!(web.password_source, PasswordSource::Keyring);
+    assert_eq!(web.created_at.to_rfc3339(), "2024-03-01T10:00:00+00:00");
+    let ProtocolConfig::Ssh(ssh) = &web.protocol_config else {
+        panic!("expected SSH config");
+    };
+    assert_eq!(ssh.auth_method, SshAuthMethod::PublicKey);
+    assert!(ssh.identities_only);
+    assert!(ssh.compression);
+
+    let desk = &connections[1];
+    assert_eq!(desk.domain.as_deref(), Some("CORP"));
+    assert!(matches!(&desk.protocol_config, ProtocolConfig::Rdp(rdp) if rdp.audio_redirect));
+
+    // The upgraded form is persisted and the original kept as a backup
+    let path = temp.path().join("connections.toml");
+    assert_eq!(
+        schema_version(&path),
+        Some(i64::from(CURRENT_SCHEMA_VERSION))
+    );
+    assert_eq!(manager.connection_backups().len(), 1);
+    assert_eq!(schema_version(&manager.connection_backups()[0]), None);
+    assert_eq!(manager.load_connections().unwrap(), connections);
+}
+
+#[test]
+fn test_v1_groups_migrate_without_data_loss() {
+    let temp = v1_config_dir();
+    let manager = ConfigManager::with_config_dir(temp.path().to_path_buf());
+
+    let groups = manager.load_groups().expect("load v1 groups");
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "Production");
+    assert!(!groups[0].expanded);
+
+    let connections = manager.load_connections().unwrap();
+    assert_eq!(connections[0].group_id, Some(groups[0].id));
+    assert_eq!(
+        schema_version(&temp.path().join("groups.toml")),
+        Some(i64::from(CURRENT_SCHEMA_VERSION))
+    );
+    assert_eq!(manager.load_groups().unwrap(), groups);
+}
+
+#[test]
+fn test_newer_schema_is_neither_loaded_nor_overwritten() {
+    let temp = v1_config_dir();
+    let manager = ConfigManager::with_config_dir(temp.path().to_path_buf());
+    // A valid backup must not be used in place of the newer file
+    manager.load_connections().expect("load v1 connections");
+
+    let newer = format!("schema_version = {}\n", CURRENT_SCHEMA_VERSION + 1);
+    for name in ["connections.toml", "groups.toml"] {
+        fs::write(temp.path().join(name), &newer).unwrap();
+    }
+
+    let error = manager.load_connections().unwrap_err();
+    assert!(matches!(
+        error,
+        ConfigError::NewerSchema { version, supported, .. }
+            if version == CURRENT_SCHEMA_VERSION + 1 && supported == CURRENT_SCHEMA_VERSION
+    ));
+    assert!(error.to_string().contains("update RustConn"));
+    assert!(matches!(
+        manager.load_groups(),
+        Err(ConfigError::NewerSchema { .. })
+    ));
+
+    assert!(matches!(
+        manager.save_connections(&[]),
+        Err(ConfigError::NewerSchema { .. })
+    ));
+    assert!(matches!(
+        manager.save_groups(&[]),
+        Err(ConfigError::NewerSchema { .. })
+    ));
+    for name in ["connections.toml", "groups.toml"] {
+        assert_eq!(fs::read_to_string(temp.path().join(name)).unwrap(), newer);
+    }
+}
@@ -14,6 +14,7 @@ use rustconn_core::import::{
     AnsibleInventoryImporter, AsbruImporter, RemminaImporter, SshConfigImporter,
 };
 use rustconn_core::models::{Connection, ConnectionGroup, ProtocolConfig, ProtocolType};
+use rustconn_core::testing::roundtrip::{assert_roundtrip, roundtrip_fields};
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -640,3 +641,27 @@ fn test_export_special_characters_in_names() {
         "Remmina should preserve name"
     );
 }
+
+// ============================================================================
+// Round-Trip Harness Tests
+// ============================================================================
+
+#[test]
+fn test_all_formats_roundtrip_ssh_connections() {
+    let connections = create_test_ssh_connections();
+    for format in ExportFormat::all() {
+        if !roundtrip_fields(*format).is_empty() {
+            assert_roundtrip(&connections, *format);
+        }
+    }
+}
+
+#[test]
+fn test_all_formats_roundtrip_mixed_protocols() {
+    let connections = create_mixed_protocol_connections();
+    for format in ExportFormat::all() {
+        if !roundtrip_fields(*format).is_empty() {
+            assert_roundtrip(&connections, *format);
+        }
+    }
+}
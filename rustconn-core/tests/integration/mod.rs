@@ -4,5 +4,6 @@
 //! and re-importing them preserves all connection data.
 
 pub mod async_patterns_tests;
+pub mod config_migration_tests;
 pub mod export_import_roundtrip;
 pub mod importer_tests;
@@ -10,9 +10,11 @@ use tempfile::TempDir;
 
 /// Generates a valid hostname (no wildcards, valid characters)
 fn arb_hostname() -> impl Strategy<Value = String> {
-    prop::string::string_regex("[a-z][a-z0-9-]{0,20}(\\.[a-z][a-z0-9-]{0,10})*")
-        .unwrap()
-        .prop_filter("hostname must not be empty", |s| !s.is_empty())
+    prop::string::string_regex(
+        "[a-z]([a-z0-9-]{0,19}[a-z0-9])?(\\.[a-z]([a-z0-9-]{0,9}[a-z0-9])?){0,4}",
+    )
+    .unwrap()
+    .prop_filter("hostname must not be empty", |s| !s.is_empty())
 }
 
 /// Generates a valid connection name (alphanumeric with underscores/hyphens)
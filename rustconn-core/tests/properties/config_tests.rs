@@ -11,8 +11,8 @@ use rustconn_core::{
     config::AppSettings, config::ColorScheme, config::ConfigManager, config::LoggingSettings,
     config::SecretBackendType, config::SecretSettings, config::SessionRestoreSettings,
     config::TerminalSettings, config::UiSettings, Connection, ConnectionGroup, HistorySettings,
-    ProtocolConfig, RdpConfig, RdpGateway, Resolution, Snippet, SnippetVariable, SshAuthMethod,
-    SshConfig, SshKeySource, VncConfig,
+    ProtocolConfig, RdpConfig, RdpGateway, Resolution, Snippet, SnippetVariable, SshAlgorithms,
+    SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -106,6 +106,9 @@ fn arb_ssh_config() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -162,6 +165,7 @@ fn arb_rdp_config() -> impl Strategy<Value = RdpConfig> {
                 resolution,
                 color_depth,
                 audio_redirect,
+                microphone_redirect: false,
                 gateway,
                 shared_folders: Vec::new(),
                 custom_args,
@@ -7,7 +7,7 @@ use chrono;
 use proptest::prelude::*;
 use rustconn_core::{
     ConfigManager, Connection, ConnectionManager, ProtocolConfig, RdpConfig, RdpGateway,
-    Resolution, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
+    Resolution, SshAlgorithms, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -23,7 +23,7 @@ fn arb_name() -> impl Strategy<Value = String> {
 
 // Strategy for generating valid hostnames (non-empty)
 fn arb_host() -> impl Strategy<Value = String> {
-    "[a-z0-9]([a-z0-9-]{0,15}[a-z0-9])?(\\.[a-z0-9]([a-z0-9-]{0,15}[a-z0-9])?)*".prop_map(|s| s)
+    "[a-z]([a-z0-9-]{0,15}[a-z0-9])?(\\.[a-z0-9]([a-z0-9-]{0,15}[a-z0-9])?){0,4}".prop_map(|s| s)
 }
 
 // Strategy for generating valid ports (non-zero)
@@ -102,6 +102,9 @@ fn arb_ssh_config() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -158,6 +161,7 @@ fn arb_rdp_config() -> impl Strategy<Value = RdpConfig> {
                 resolution,
                 color_depth,
                 audio_redirect,
+                microphone_redirect: false,
                 gateway,
                 shared_folders: Vec::new(),
                 custom_args,
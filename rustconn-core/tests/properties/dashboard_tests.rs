@@ -32,6 +32,7 @@ fn arb_session_state() -> impl Strategy<Value = SessionState> {
         Just(SessionState::Active),
         Just(SessionState::Disconnecting),
         Just(SessionState::Terminated),
+        Just(SessionState::Disconnected),
         Just(SessionState::Error),
     ]
 }
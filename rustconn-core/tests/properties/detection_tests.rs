@@ -658,13 +658,13 @@ proptest! {
     /// Test that all provider types persist correctly
     #[test]
     fn prop_provider_persistence_all_providers(
-        provider_idx in 0usize..9usize,
+        provider_idx in 0usize..10usize,
         target in "[a-z][a-z0-9-]{0,20}",
     ) {
         use rustconn_core::models::{
             AwsSsmConfig, GcpIapConfig, AzureBastionConfig, AzureSshConfig,
             OciBastionConfig, CloudflareAccessConfig, TeleportConfig,
-            TailscaleSshConfig, BoundaryConfig, GenericZeroTrustConfig,
+            TailscaleSshConfig, BoundaryConfig, GenericProxyConfig, GenericZeroTrustConfig,
             ZeroTrustConfig, ZeroTrustProvider, ZeroTrustProviderConfig,
         };
 
@@ -678,6 +678,7 @@ proptest! {
             ("teleport-symbolic", ZeroTrustProvider::Teleport),
             ("tailscale-symbolic", ZeroTrustProvider::TailscaleSsh),
             ("boundary-symbolic", ZeroTrustProvider::Boundary),
+            ("proxy-symbolic", ZeroTrustProvider::GenericProxy),
         ];
 
         let (icon_name, provider) = &providers[provider_idx];
@@ -730,6 +731,11 @@ proptest! {
             ZeroTrustProvider::Generic => ZeroTrustProviderConfig::Generic(GenericZeroTrustConfig {
                 command_template: format!("ssh {target}"),
             }),
+            ZeroTrustProvider::GenericProxy => ZeroTrustProviderConfig::GenericProxy(GenericProxyConfig {
+                host: target.clone(),
+                proxy_command: "nc {host} {port}".to_string(),
+                ..GenericProxyConfig::default()
+            }),
         };
 
         let config = ZeroTrustConfig {
@@ -12,7 +12,8 @@ use rustconn_core::dialog_utils::{
     validate_name, validate_port,
 };
 use rustconn_core::{
-    ConfigManager, Connection, ProtocolConfig, SshAuthMethod, SshConfig, SshKeySource,
+    ConfigManager, Connection, ProtocolConfig, SshAlgorithms, SshAuthMethod, SshConfig,
+    SshKeySource,
 };
 use std::collections::HashMap;
 
@@ -50,7 +51,7 @@ fn arb_valid_name() -> impl Strategy<Value = String> {
 
 /// Strategy for generating valid hostnames
 fn arb_valid_host() -> impl Strategy<Value = String> {
-    "[a-z0-9]([a-z0-9-]{0,15}[a-z0-9])?(\\.[a-z0-9]([a-z0-9-]{0,15}[a-z0-9])?)*".prop_map(|s| s)
+    "[a-z]([a-z0-9-]{0,15}[a-z0-9])?(\\.[a-z0-9]([a-z0-9-]{0,15}[a-z0-9])?){0,4}".prop_map(|s| s)
 }
 
 /// Strategy for generating valid ports
@@ -287,6 +288,9 @@ fn arb_ssh_config() -> impl Strategy<Value = SshConfig> {
         custom_options: HashMap::new(),
         startup_command: None,
         jump_host_id: None,
+        forwards: Vec::new(),
+        environment: Vec::new(),
+        algorithms: SshAlgorithms::default(),
     })
 }
 
@@ -178,7 +178,7 @@ proptest! {
     fn prop_vnc_display_scaling_toggle_is_consistent(enabled in any::<bool>()) {
         let display = VncDisplay::new();
 
-        display.set_scaling(enabled);
+        display.set_scaling(enabled.into());
         prop_assert_eq!(
             display.scaling_enabled(),
             enabled,
@@ -283,7 +283,7 @@ fn test_vnc_display_lifecycle() {
         .set_credential(VncCredentialType::Username, "user")
         .unwrap();
 
-    display.set_scaling(true);
+    display.set_scaling(true.into());
     assert!(display.scaling_enabled());
 
     display.close();
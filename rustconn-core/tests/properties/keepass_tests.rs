@@ -378,12 +378,15 @@ fn create_test_connection(name: &str, host: &str) -> Connection {
         protocol: ProtocolType::Ssh,
         username: None,
         group_id: None,
+        additional_group_ids: Vec::new(),
         tags: Vec::new(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
         protocol_config: ProtocolConfig::Ssh(SshConfig::default()),
         sort_order: 0,
+        launch_order: 0,
         last_connected: None,
+        connect_count: 0,
         password_source: PasswordSource::None,
         domain: None,
         custom_properties: Vec::new(),
@@ -398,6 +401,9 @@ fn create_test_connection(name: &str, host: &str) -> Connection {
         remember_window_position: false,
         window_geometry: None,
         skip_port_check: false,
+        color: None,
+        icon: None,
+        archived: false,
     }
 }
 
@@ -770,12 +776,15 @@ mod hierarchy_tests {
             protocol: ProtocolType::Ssh,
             username: None,
             group_id,
+            additional_group_ids: Vec::new(),
             tags: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             protocol_config: ProtocolConfig::Ssh(SshConfig::default()),
             sort_order: 0,
+            launch_order: 0,
             last_connected: None,
+            connect_count: 0,
             password_source: PasswordSource::None,
             domain: None,
             custom_properties: Vec::new(),
@@ -790,6 +799,9 @@ mod hierarchy_tests {
             remember_window_position: false,
             window_geometry: None,
             skip_port_check: false,
+            color: None,
+            icon: None,
+            archived: false,
         }
     }
 
@@ -75,10 +75,6 @@ fn arb_key_element() -> impl Strategy<Value = KeyElement> {
 }
 
 /// Strategy for generating a key sequence
-///
-/// Reserved for future property tests that need complete key sequence generation.
-/// Currently, tests use more targeted element-based strategies.
-#[allow(dead_code)]
 fn arb_key_sequence() -> impl Strategy<Value = KeySequence> {
     prop::collection::vec(arb_key_element(), 0..10).prop_map(|elements| {
         // Filter out empty text elements
@@ -538,6 +534,14 @@ proptest! {
         prop_assert_eq!(p1, p2, "Double round-trip should be stable");
         prop_assert_eq!(s1, s2, "Serialized strings should be identical");
     }
+
+    /// Macro scripts round-trip every element exactly, without merging text
+    #[test]
+    fn key_sequence_script_round_trip(seq in arb_key_sequence()) {
+        let script = seq.to_script();
+        let reparsed = KeySequence::from_script(&script);
+        prop_assert_eq!(reparsed, Ok(seq), "Script should round-trip: {}", script);
+    }
 }
 
 // Import Variable and VariableManager for substitution tests
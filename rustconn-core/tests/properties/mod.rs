@@ -70,6 +70,7 @@ mod progress_tests;
 mod protocol_tests;
 mod quick_connect_tests;
 mod rdp_client_tests;
+mod roundtrip_fuzz_tests;
 mod search_tests;
 mod security_tests;
 mod selection_tests;
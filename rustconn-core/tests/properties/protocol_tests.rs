@@ -8,7 +8,7 @@ use std::collections::HashMap;
 
 use rustconn_core::models::{
     Connection, ProtocolConfig, RdpConfig, RdpGateway, Resolution, SharedFolder, SpiceConfig,
-    SpiceImageCompression, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
+    SpiceImageCompression, SshAlgorithms, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 use rustconn_core::protocol::{Protocol, RdpProtocol, SshProtocol, VncProtocol};
 use std::path::PathBuf;
@@ -54,6 +54,9 @@ fn arb_ssh_config() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -109,6 +112,7 @@ fn arb_shared_folder() -> impl Strategy<Value = SharedFolder> {
         .prop_map(|(path, name)| SharedFolder {
             local_path: std::path::PathBuf::from(path),
             share_name: name,
+            read_only: false,
         })
 }
 
@@ -131,6 +135,7 @@ fn arb_rdp_config() -> impl Strategy<Value = RdpConfig> {
                     resolution,
                     color_depth,
                     audio_redirect,
+                    microphone_redirect: false,
                     gateway,
                     shared_folders,
                     custom_args,
@@ -545,6 +550,7 @@ fn arb_spice_config() -> impl Strategy<Value = SpiceConfig> {
                 tls_enabled,
                 ca_cert_path,
                 skip_cert_verify,
+                cert_fingerprint: None,
                 usb_redirection,
                 shared_folders,
                 clipboard_enabled,
@@ -610,6 +616,7 @@ proptest! {
         config.shared_folders.push(SharedFolder {
             local_path: PathBuf::new(),
             share_name: "test".to_string(),
+            read_only: false,
         });
         let result = validate_spice_config(&config);
         prop_assert!(result.is_err(), "Empty shared folder path should be rejected");
@@ -626,6 +633,7 @@ proptest! {
         config.shared_folders.push(SharedFolder {
             local_path: PathBuf::from("/tmp/test"),
             share_name: String::new(),
+            read_only: false,
         });
         let result = validate_spice_config(&config);
         prop_assert!(result.is_err(), "Empty shared folder name should be rejected");
@@ -789,6 +797,9 @@ fn arb_ssh_config_with_identities_only() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command: None,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -823,6 +834,9 @@ fn arb_ssh_config_with_agent_fingerprint() -> impl Strategy<Value = SshConfig> {
                 custom_options: HashMap::new(),
                 startup_command: None,
                 jump_host_id: None,
+                forwards: Vec::new(),
+                environment: Vec::new(),
+                algorithms: SshAlgorithms::default(),
             }
         })
 }
@@ -978,6 +992,9 @@ fn arb_ssh_config_with_file_key_source() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command: None,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -1011,6 +1028,9 @@ fn arb_ssh_config_with_agent_key_source() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command: None,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -113,6 +113,7 @@ fn arb_rdp_client_config() -> impl Strategy<Value = RdpClientConfig> {
                     height,
                     color_depth,
                     clipboard_enabled,
+                    clipboard_max_transfer_size: 1024 * 1024 * 1024,
                     audio_enabled,
                     timeout_secs,
                     ignore_certificate,
@@ -0,0 +1,313 @@
+//! Fuzz-style round-trip tests for export/import
+//!
+//! Generates arbitrary valid connection/group sets and checks that:
+//! - native export followed by import is lossless
+//! - format-specific exporters never panic
+//! - format-specific importers skip (rather than crash on) exported output
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use proptest::prelude::*;
+use rustconn_core::export::{
+    AnsibleExporter, AsbruExporter, MobaXtermExporter, NativeExport, RemminaExporter,
+    RoyalTsExporter, SshConfigExporter,
+};
+use rustconn_core::import::{
+    AnsibleInventoryImporter, AsbruImporter, MobaXtermImporter, RemminaImporter, RoyalTsImporter,
+    SshConfigImporter,
+};
+use rustconn_core::models::{
+    Connection, ConnectionGroup, CustomProperty, PasswordSource, ProtocolConfig, RdpConfig,
+    Resolution, SpiceConfig, SshAuthMethod, SshConfig, VncConfig, WindowMode,
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+// ========== Generators ==========
+
+/// Arbitrary printable text, including characters that are special in
+/// INI, YAML, XML and SSH config syntax
+fn arb_text() -> impl Strategy<Value = String> {
+    prop_oneof!["\\PC{0,24}", "[a-zA-Z0-9 _.:;#=\\[\\]{}<>&\"'|%-]{0,24}",]
+}
+
+/// Non-empty connection or group name
+fn arb_name() -> impl Strategy<Value = String> {
+    arb_text().prop_filter("name must not be blank", |s| !s.trim().is_empty())
+}
+
+/// Valid hostname or IP address
+fn arb_host() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z][a-z0-9-]{0,15}(\\.[a-z][a-z0-9-]{0,10}){0,2}",
+        (0u8..=255, 0u8..=255, 0u8..=255, 1u8..=254)
+            .prop_map(|(a, b, c, d)| format!("{a}.{b}.{c}.{d}")),
+    ]
+}
+
+fn arb_optional_text() -> impl Strategy<Value = Option<String>> {
+    prop::option::of(arb_text())
+}
+
+fn arb_ssh_config() -> impl Strategy<Value = SshConfig> {
+    (
+        prop_oneof![
+            Just(SshAuthMethod::Password),
+            Just(SshAuthMethod::PublicKey),
+            Just(SshAuthMethod::KeyboardInteractive),
+            Just(SshAuthMethod::Agent),
+        ],
+        prop::option::of("/[a-z/._-]{1,30}".prop_map(PathBuf::from)),
+        prop::option::of(arb_host()),
+        any::<(bool, bool, bool, bool)>(),
+        prop::collection::hash_map("[A-Za-z]{1,12}", "[a-z0-9]{1,8}", 0..3),
+        arb_optional_text(),
+    )
+        .prop_map(
+            |(auth_method, key_path, proxy_jump, flags, custom_options, startup_command)| {
+                SshConfig {
+                    auth_method,
+                    key_path,
+                    proxy_jump,
+                    use_control_master: flags.0,
+                    agent_forwarding: flags.1,
+                    x11_forwarding: flags.2,
+                    compression: flags.3,
+                    custom_options,
+                    startup_command,
+                    ..SshConfig::default()
+                }
+            },
+        )
+}
+
+fn arb_rdp_config() -> impl Strategy<Value = RdpConfig> {
+    (
+        prop::option::of((640u32..4096, 480u32..2160)),
+        prop::option::of(prop::sample::select(vec![8u8, 15, 16, 24, 32])),
+        any::<bool>(),
+        prop::collection::vec("[a-z/+-]{1,12}", 0..3),
+    )
+        .prop_map(
+            |(resolution, color_depth, audio_redirect, custom_args)| RdpConfig {
+                resolution: resolution.map(|(width, height)| Resolution { width, height }),
+                color_depth,
+                audio_redirect,
+                custom_args,
+                ..RdpConfig::default()
+            },
+        )
+}
+
+fn arb_vnc_config() -> impl Strategy<Value = VncConfig> {
+    (
+        prop::option::of(prop::sample::select(vec!["tight", "zrle", "hextile"])),
+        prop::option::of(0u8..=9),
+        prop::option::of(0u8..=9),
+        any::<bool>(),
+    )
+        .prop_map(|(encoding, compression, quality, view_only)| VncConfig {
+            encoding: encoding.map(String::from),
+            compression,
+            quality,
+            view_only,
+            ..VncConfig::default()
+        })
+}
+
+fn arb_spice_config() -> impl Strategy<Value = SpiceConfig> {
+    any::<(bool, bool, bool)>().prop_map(|(tls_enabled, usb_redirection, clipboard_enabled)| {
+        SpiceConfig {
+            tls_enabled,
+            usb_redirection,
+            clipboard_enabled,
+            ..SpiceConfig::default()
+        }
+    })
+}
+
+fn arb_protocol_config() -> impl Strategy<Value = ProtocolConfig> {
+    prop_oneof![
+        arb_ssh_config().prop_map(ProtocolConfig::Ssh),
+        arb_rdp_config().prop_map(ProtocolConfig::Rdp),
+        arb_vnc_config().prop_map(ProtocolConfig::Vnc),
+        arb_spice_config().prop_map(ProtocolConfig::Spice),
+    ]
+}
+
+fn arb_password_source() -> impl Strategy<Value = PasswordSource> {
+    prop_oneof![
+        Just(PasswordSource::None),
+        Just(PasswordSource::KeePass),
+        Just(PasswordSource::Keyring),
+        Just(PasswordSource::Bitwarden),
+        Just(PasswordSource::Prompt),
+        Just(PasswordSource::Inherit),
+    ]
+}
+
+fn arb_custom_property() -> impl Strategy<Value = CustomProperty> {
+    (arb_name(), arb_text(), 0u8..3).prop_map(|(name, value, kind)| match kind {
+        0 => CustomProperty::new_text(name, value),
+        1 => CustomProperty::new_url(name, value),
+        _ => CustomProperty::new_protected(name, value),
+    })
+}
+
+/// Generates a connection, optionally placed in one of the given groups
+fn arb_connection(group_ids: Vec<Uuid>) -> impl Strategy<Value = Connection> {
+    let group_choice = if group_ids.is_empty() {
+        Just(None).boxed()
+    } else {
+        prop::option::of(prop::sample::select(group_ids)).boxed()
+    };
+
+    (
+        (arb_name(), arb_host(), 1u16..=65535, arb_protocol_config()),
+        (
+            arb_optional_text(),
+            prop::option::of("[a-zA-Z][a-zA-Z0-9_.-]{0,15}"),
+            arb_optional_text(),
+            prop::collection::vec("[a-z0-9-]{1,10}", 0..4),
+        ),
+        (
+            group_choice,
+            arb_password_source(),
+            prop::collection::vec(arb_custom_property(), 0..3),
+            any::<i32>(),
+            prop_oneof![
+                Just(WindowMode::Embedded),
+                Just(WindowMode::External),
+                Just(WindowMode::Fullscreen),
+            ],
+        ),
+    )
+        .prop_map(
+            |(
+                (name, host, port, config),
+                (description, username, domain, tags),
+                (group_id, password_source, custom_properties, sort_order, window_mode),
+            )| {
+                let mut conn = Connection::new(name, host, port, config);
+                conn.description = description;
+                conn.username = username;
+                conn.domain = domain;
+                conn.tags = tags;
+                conn.group_id = group_id;
+                conn.password_source = password_source;
+                conn.custom_properties = custom_properties;
+                conn.sort_order = sort_order;
+                conn.window_mode = window_mode;
+                conn
+            },
+        )
+}
+
+/// Generates a group hierarchy where each group's parent (if any) precedes it
+fn arb_groups() -> impl Strategy<Value = Vec<ConnectionGroup>> {
+    prop::collection::vec(
+        (arb_name(), any::<prop::sample::Index>(), any::<bool>()),
+        0..5,
+    )
+    .prop_map(|specs| {
+        let mut groups: Vec<ConnectionGroup> = Vec::with_capacity(specs.len());
+        for (name, parent_index, nested) in specs {
+            let group = if nested && !groups.is_empty() {
+                let parent_id = groups[parent_index.index(groups.len())].id;
+                ConnectionGroup::with_parent(name, parent_id)
+            } else {
+                ConnectionGroup::new(name)
+            };
+            groups.push(group);
+        }
+        groups
+    })
+}
+
+/// Generates a consistent set of groups and connections referencing them
+fn arb_connection_set() -> impl Strategy<Value = (Vec<Connection>, Vec<ConnectionGroup>)> {
+    arb_groups().prop_flat_map(|groups| {
+        let group_ids: Vec<Uuid> = groups.iter().map(|g| g.id).collect();
+        (
+            prop::collection::vec(arb_connection(group_ids), 0..8),
+            Just(groups),
+        )
+    })
+}
+
+// ========== Properties ==========
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    /// Native export followed by import preserves every connection and group exactly
+    #[test]
+    fn prop_native_roundtrip_is_lossless((connections, groups) in arb_connection_set()) {
+        let export = NativeExport::with_data(
+            connections.clone(),
+            groups.clone(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+
+        let json = export.to_json().expect("native export should serialize");
+        let imported = NativeExport::from_json(&json).expect("native export should re-import");
+
+        prop_assert_eq!(imported.connections, connections);
+        prop_assert_eq!(imported.groups, groups);
+    }
+
+    /// Text-based exporters never panic and their importers never crash on the output
+    #[test]
+    fn prop_text_exporters_roundtrip_without_panic(
+        (connections, groups) in arb_connection_set()
+    ) {
+        let ssh_config = SshConfigExporter::export(&connections);
+        let result = SshConfigImporter::new().parse_config(&ssh_config, "fuzz");
+        prop_assert!(result.connections.len() <= connections.len());
+
+        let ini = AnsibleExporter::export_ini(&connections, &groups);
+        let _ = AnsibleInventoryImporter::new().parse_ini_inventory(&ini, "fuzz");
+
+        let yaml = AnsibleExporter::export_yaml(&connections, &groups);
+        let _ = AnsibleInventoryImporter::new().parse_yaml_inventory(&yaml, "fuzz");
+
+        let asbru = AsbruExporter::export(&connections, &groups);
+        let result = AsbruImporter::new().parse_config(&asbru, "fuzz");
+        prop_assert!(result.connections.len() <= connections.len());
+
+        let royalts = RoyalTsExporter::export_to_xml(&connections, &groups);
+        let result = RoyalTsImporter::new().parse_xml(&royalts, "fuzz");
+        prop_assert!(result.connections.len() <= connections.len());
+    }
+
+    /// Per-connection Remmina files either export cleanly or are rejected with an error
+    #[test]
+    fn prop_remmina_roundtrip_without_panic((connections, _groups) in arb_connection_set()) {
+        let importer = RemminaImporter::new();
+        let mut group_map = HashMap::new();
+
+        for connection in &connections {
+            if let Ok(content) = RemminaExporter::export_connection(connection) {
+                let result = importer.parse_remmina_file(&content, "fuzz.remmina", &mut group_map);
+                prop_assert!(result.connections.len() <= 1);
+            }
+        }
+    }
+
+    /// MobaXterm export never panics and its importer never crashes on the output
+    #[test]
+    fn prop_mobaxterm_roundtrip_without_panic((connections, groups) in arb_connection_set()) {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let path = temp_dir.path().join("fuzz.mxtsessions");
+
+        if MobaXtermExporter::export_to_file(&connections, &groups, &path).is_ok() {
+            let bytes = std::fs::read(&path).expect("exported file should be readable");
+            let content = String::from_utf8_lossy(&bytes);
+            let result = MobaXtermImporter::new().parse_content(&content, "fuzz");
+            prop_assert!(result.connections.len() <= connections.len());
+        }
+    }
+}
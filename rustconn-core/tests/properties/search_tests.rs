@@ -6,7 +6,7 @@
 use proptest::prelude::*;
 use rustconn_core::{
     Connection, ConnectionGroup, CustomProperty, ProtocolType, SearchEngine, SearchFilter,
-    SearchQuery,
+    SearchIndex, SearchQuery,
 };
 use uuid::Uuid;
 
@@ -507,6 +507,45 @@ proptest! {
             );
         }
     }
+
+    #[test]
+    fn search_indexed_matches_linear_search(
+        search_text in "[a-dA-D0-9 -]{1,6}",
+        group_names in prop::collection::vec(arb_group_name(), 0..3),
+        members in prop::collection::vec((arb_connection(), 0..4usize), 0..20),
+        case_sensitive in any::<bool>()
+    ) {
+        let groups: Vec<ConnectionGroup> =
+            group_names.into_iter().map(ConnectionGroup::new).collect();
+        let connections: Vec<Connection> = members
+            .into_iter()
+            .map(|(mut conn, group)| {
+                conn.group_id = groups.get(group).map(|g| g.id);
+                conn
+            })
+            .collect();
+        let engine = SearchEngine::new().with_case_sensitive(case_sensitive);
+        let index = SearchIndex::build(&connections);
+
+        let query = SearchQuery::with_text(&search_text);
+        let linear: Vec<(Uuid, f32)> = engine
+            .search(&query, &connections, &groups)
+            .into_iter()
+            .map(|r| (r.connection_id, r.score))
+            .collect();
+        let indexed: Vec<(Uuid, f32)> = engine
+            .search_indexed(
+                &query,
+                |id| connections.iter().find(|c| c.id == id),
+                &groups,
+                &index,
+            )
+            .into_iter()
+            .map(|r| (r.connection_id, r.score))
+            .collect();
+
+        prop_assert_eq!(indexed, linear, "Indexed search should match linear search");
+    }
 }
 
 proptest! {
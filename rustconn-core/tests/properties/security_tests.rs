@@ -4,7 +4,9 @@
 //! **Validates: Requirements 5.5**
 
 use proptest::prelude::*;
-use rustconn_core::models::{Connection, ProtocolConfig, SshAuthMethod, SshConfig, SshKeySource};
+use rustconn_core::models::{
+    Connection, ProtocolConfig, SshAlgorithms, SshAuthMethod, SshConfig, SshKeySource,
+};
 use rustconn_core::Credentials;
 use std::path::PathBuf;
 
@@ -40,6 +42,9 @@ fn arb_ssh_config_with_key() -> impl Strategy<Value = SshConfig> {
         custom_options: std::collections::HashMap::new(),
         startup_command: None,
         jump_host_id: None,
+        forwards: Vec::new(),
+        environment: Vec::new(),
+        algorithms: SshAlgorithms::default(),
     })
 }
 
@@ -7,7 +7,7 @@ use proptest::prelude::*;
 use rustconn_core::models::SharedFolder;
 use rustconn_core::{
     Connection, ProtocolConfig, RdpConfig, RdpGateway, Resolution, SpiceConfig,
-    SpiceImageCompression, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
+    SpiceImageCompression, SshAlgorithms, SshAuthMethod, SshConfig, SshKeySource, VncConfig,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -98,6 +98,9 @@ fn arb_ssh_config() -> impl Strategy<Value = SshConfig> {
                     custom_options,
                     startup_command,
                     jump_host_id: None,
+                    forwards: Vec::new(),
+                    environment: Vec::new(),
+                    algorithms: SshAlgorithms::default(),
                 }
             },
         )
@@ -154,6 +157,7 @@ fn arb_rdp_config() -> impl Strategy<Value = RdpConfig> {
                 resolution,
                 color_depth,
                 audio_redirect,
+                microphone_redirect: false,
                 gateway,
                 shared_folders: Vec::new(),
                 custom_args,
@@ -224,6 +228,7 @@ fn arb_shared_folders() -> impl Strategy<Value = Vec<SharedFolder>> {
             .prop_map(|(path, name)| SharedFolder {
                 local_path: PathBuf::from(path),
                 share_name: name,
+                read_only: false,
             }),
         0..3,
     )
@@ -253,6 +258,7 @@ fn arb_spice_config() -> impl Strategy<Value = SpiceConfig> {
                 tls_enabled,
                 ca_cert_path,
                 skip_cert_verify,
+                cert_fingerprint: None,
                 usb_redirection,
                 shared_folders,
                 clipboard_enabled,
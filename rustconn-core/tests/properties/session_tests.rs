@@ -493,7 +493,7 @@ proptest! {
 
         // Set different scaling values for each session
         for (i, session) in sessions.iter().enumerate() {
-            session.set_scaling(scaling_flags[i]);
+            session.set_scaling(scaling_flags[i].into());
         }
 
         // Verify each session has its own scaling value
@@ -507,7 +507,7 @@ proptest! {
 
         // Toggle scaling on first session
         let new_scaling = !scaling_flags[0];
-        sessions[0].set_scaling(new_scaling);
+        sessions[0].set_scaling(new_scaling.into());
 
         // Verify first session changed
         prop_assert_eq!(
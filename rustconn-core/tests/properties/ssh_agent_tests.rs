@@ -345,7 +345,9 @@ proptest! {
 // Agent Key Fingerprint Storage Property Tests
 // ============================================================================
 
-use rustconn_core::{Connection, ProtocolConfig, SshAuthMethod, SshConfig, SshKeySource};
+use rustconn_core::{
+    Connection, ProtocolConfig, SshAlgorithms, SshAuthMethod, SshConfig, SshKeySource,
+};
 
 /// Generates a valid hostname
 fn arb_hostname() -> impl Strategy<Value = String> {
@@ -393,7 +395,7 @@ proptest! {
             x11_forwarding: false,
             compression: false,
             custom_options: std::collections::HashMap::new(),
-            startup_command: None, jump_host_id: None,
+            startup_command: None, jump_host_id: None, forwards: Vec::new(), environment: Vec::new(), algorithms: SshAlgorithms::default(),
         };
 
         // Create connection
@@ -461,7 +463,7 @@ proptest! {
             x11_forwarding: false,
             compression: false,
             custom_options: std::collections::HashMap::new(),
-            startup_command: None, jump_host_id: None,
+            startup_command: None, jump_host_id: None, forwards: Vec::new(), environment: Vec::new(), algorithms: SshAlgorithms::default(),
         };
 
         let connection = Connection::new(
@@ -27,6 +27,8 @@ fn arb_task_timing() -> impl Strategy<Value = TaskTiming> {
     prop_oneof![
         Just(TaskTiming::PreConnect),
         Just(TaskTiming::PostDisconnect),
+        (1u64..86_400_000)
+            .prop_map(|ms| TaskTiming::Interval(std::time::Duration::from_millis(ms))),
     ]
 }
 
@@ -301,6 +301,39 @@ proptest! {
             actual_offset
         );
     }
+
+    #[test]
+    fn variable_heights_visible_range_covers_viewport(
+        heights in prop::collection::vec(arb_item_height(), 1..500),
+        viewport_height in arb_viewport_height(),
+        overscan in arb_overscan(),
+        scroll_fraction in 0.0f64..1.0
+    ) {
+        let total_height: f64 = heights.iter().sum();
+        let mut scroller = VirtualScroller::new(0, 30.0, viewport_height)
+            .with_overscan(overscan)
+            .with_item_heights(heights.iter().copied());
+        let offset = total_height * scroll_fraction;
+        scroller.set_scroll_offset(offset);
+
+        let (start, end) = scroller.visible_range();
+        prop_assert!(start <= end && end <= heights.len());
+
+        // Every item overlapping the viewport is in range
+        let mut top = 0.0;
+        for (index, height) in heights.iter().enumerate() {
+            let bottom = top + height;
+            if bottom > offset && top < offset + viewport_height {
+                prop_assert!(
+                    index >= start && index < end,
+                    "Item {} at {}..{} overlaps viewport {}..{} but range is {}..{}",
+                    index, top, bottom, offset, offset + viewport_height, start, end
+                );
+            }
+            top = bottom;
+        }
+        prop_assert!((scroller.total_height() - total_height).abs() < 1e-6);
+    }
 }
 
 // ========== Selection Preservation Helper ==========
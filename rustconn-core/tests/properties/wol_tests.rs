@@ -173,7 +173,7 @@ proptest! {
 
     #[test]
     fn magic_packet_has_correct_size(mac in arb_mac_address()) {
-        let packet = generate_magic_packet(&mac);
+        let packet = generate_magic_packet(&mac, None);
 
         prop_assert_eq!(
             packet.len(), MAGIC_PACKET_SIZE,
@@ -187,7 +187,7 @@ proptest! {
 
     #[test]
     fn magic_packet_starts_with_sync_stream(mac in arb_mac_address()) {
-        let packet = generate_magic_packet(&mac);
+        let packet = generate_magic_packet(&mac, None);
 
         // First 6 bytes should all be 0xFF
         for (i, &byte) in packet[..6].iter().enumerate() {
@@ -201,7 +201,7 @@ proptest! {
 
     #[test]
     fn magic_packet_contains_16_mac_repetitions(mac in arb_mac_address()) {
-        let packet = generate_magic_packet(&mac);
+        let packet = generate_magic_packet(&mac, None);
         let mac_bytes = mac.bytes();
 
         // Check all 16 repetitions of the MAC address
@@ -220,8 +220,8 @@ proptest! {
     #[test]
     fn magic_packet_deterministic(mac in arb_mac_address()) {
         // Generate packet twice
-        let packet1 = generate_magic_packet(&mac);
-        let packet2 = generate_magic_packet(&mac);
+        let packet1 = generate_magic_packet(&mac, None);
+        let packet2 = generate_magic_packet(&mac, None);
 
         // Should be identical
         prop_assert_eq!(
@@ -238,12 +238,12 @@ proptest! {
         // Skip if MACs happen to be the same
         prop_assume!(mac1 != mac2);
 
-        let packet1 = generate_magic_packet(&mac1);
-        let packet2 = generate_magic_packet(&mac2);
+        let packet1 = generate_magic_packet(&mac1, None);
+        let packet2 = generate_magic_packet(&mac2, None);
 
         // Packets should be different (at least in the MAC portion)
         prop_assert_ne!(
-            packet1, packet2,
+            &packet1, &packet2,
             "Different MACs should produce different packets"
         );
 
@@ -266,7 +266,7 @@ mod edge_case_tests {
         let mac = MacAddress::new([0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
         assert_eq!(mac.format_colon(), "00:00:00:00:00:00");
 
-        let packet = generate_magic_packet(&mac);
+        let packet = generate_magic_packet(&mac, None);
         // First 6 bytes are 0xFF, rest are 0x00
         assert!(packet[..6].iter().all(|&b| b == 0xFF));
         assert!(packet[6..].iter().all(|&b| b == 0x00));
@@ -277,7 +277,7 @@ mod edge_case_tests {
         let mac = MacAddress::new([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
         assert_eq!(mac.format_colon(), "FF:FF:FF:FF:FF:FF");
 
-        let packet = generate_magic_packet(&mac);
+        let packet = generate_magic_packet(&mac, None);
         // Entire packet should be 0xFF
         assert!(packet.iter().all(|&b| b == 0xFF));
     }
@@ -80,6 +80,7 @@ impl TabInfo {
                 "tailscale" | "tailscale_ssh" => "network-vpn-symbolic",
                 "boundary" => "dialog-password-symbolic",
                 "generic" => "system-run-symbolic",
+                "generic_proxy" => "network-wired-symbolic",
                 _ => "folder-remote-symbolic",
             }
             .to_string();
@@ -15,6 +15,7 @@ use crate::state::{create_shared_state, SharedAppState};
 use crate::tray::{TrayManager, TrayMessage};
 use crate::window::MainWindow;
 use rustconn_core::config::ColorScheme;
+use rustconn_core::SecretBackendEvent;
 
 /// Applies a color scheme to GTK/libadwaita settings
 pub fn apply_color_scheme(scheme: ColorScheme) {
@@ -99,12 +100,45 @@ fn build_ui(app: &adw::Application, tray_manager: SharedTrayManager) {
     // Set up application actions
     setup_app_actions(app, &window, &state, tray_manager.clone());
 
+    // Clear cached credentials when the password manager locks
+    setup_lock_watcher(&window, &state);
+
     // Set up tray message polling
     setup_tray_polling(app, &window, state, tray_manager);
 
     window.present();
 }
 
+/// Starts the secret backend lock watcher and applies its events
+fn setup_lock_watcher(window: &MainWindow, state: &SharedAppState) {
+    let Some(receiver) = state.borrow_mut().start_lock_watcher() else {
+        return;
+    };
+
+    let state = state.clone();
+    let toasts = window.toast_overlay().clone();
+    let window_weak = window.gtk_window().downgrade();
+    glib::timeout_add_local(std::time::Duration::from_millis(500), move || loop {
+        let event = match receiver.try_recv() {
+            Ok(event) => event,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+        };
+        let resume = state.borrow_mut().handle_secret_backend_event(event);
+        if event == SecretBackendEvent::Locked {
+            toasts.show_warning("KeePassXC database locked. Unlock it to use stored passwords.");
+        }
+        // Resume the connect that was waiting for the unlock
+        if let (Some(connection_id), Some(win)) = (resume, window_weak.upgrade()) {
+            let _ = gtk4::prelude::WidgetExt::activate_action(
+                &win,
+                "win.connect-by-id",
+                Some(&connection_id.to_string().to_variant()),
+            );
+        }
+    });
+}
+
 /// Updates the tray icon state from the application state
 ///
 /// Only updates if state has actually changed to avoid unnecessary work.
@@ -1,7 +1,7 @@
-//! Audio playback module for RDP embedded mode
+//! Audio playback and capture module for RDP embedded mode
 //!
-//! This module provides audio playback functionality using `cpal` for
-//! RDP sessions with audio redirection enabled.
+//! This module provides audio playback and microphone capture using `cpal`
+//! for RDP sessions with audio redirection enabled.
 //!
 //! # Architecture
 //!
@@ -11,6 +11,10 @@
 //! 3. `RdpClientEvent::AudioVolume` - adjusts playback volume
 //! 4. `RdpClientEvent::AudioClose` - stops playback
 //!
+//! Microphone audio flows the other way: `RdpClientEvent::AudioInputOpened`
+//! starts an [`RdpAudioRecorder`] on the default input device, which sends
+//! PCM data back as `RdpClientCommand::AudioInputData`.
+//!
 //! # Safety Notes
 //!
 //! Mutex locks in audio callbacks are safe - they protect a simple buffer and
@@ -25,7 +29,7 @@
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Stream, StreamConfig};
-use rustconn_core::AudioFormatInfo;
+use rustconn_core::{AudioFormatInfo, RdpClientCommand, RdpCommandSender};
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
@@ -38,6 +42,10 @@ pub enum AudioError {
     #[error("No audio output device available")]
     NoDevice,
 
+    /// No audio input device available
+    #[error("No audio input device available")]
+    NoInputDevice,
+
     /// Unsupported audio format
     #[error("Unsupported audio format: {0}")]
     UnsupportedFormat(String),
@@ -49,6 +57,10 @@ pub enum AudioError {
     /// Playback error
     #[error("Audio playback error: {0}")]
     Playback(String),
+
+    /// Recording error
+    #[error("Audio recording error: {0}")]
+    Recording(String),
 }
 
 /// Audio buffer for queuing PCM data
@@ -351,6 +363,130 @@ impl Drop for RdpAudioPlayer {
     }
 }
 
+/// Microphone recorder for RDP audio input redirection
+///
+/// Records from the default input device in the format opened by the
+/// server and forwards the PCM data to the RDP client in packets of the
+/// size the server asked for.
+pub struct RdpAudioRecorder {
+    /// Current capture stream (if recording)
+    stream: Option<Stream>,
+    /// Audio frames per packet requested when the stream was opened
+    frames_per_packet: u32,
+}
+
+impl RdpAudioRecorder {
+    /// Creates a new, idle recorder
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            stream: None,
+            frames_per_packet: 0,
+        }
+    }
+
+    /// Starts recording in `format`, sending PCM data through `sender`
+    ///
+    /// Data is sent in packets of `frames_per_packet` audio frames. A value
+    /// of 0 keeps the packet size of the previous stream, as servers send
+    /// it only when the stream is opened and not on format changes; if no
+    /// size is known, captured buffers are sent as they arrive.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no input device is available, the format is not
+    /// 16-bit PCM, or the device cannot record in it.
+    pub fn start(
+        &mut self,
+        format: &AudioFormatInfo,
+        frames_per_packet: u32,
+        sender: RdpCommandSender,
+    ) -> Result<(), AudioError> {
+        self.stop();
+        if frames_per_packet > 0 {
+            self.frames_per_packet = frames_per_packet;
+        }
+
+        if !format.is_pcm() || format.bits_per_sample != 16 {
+            return Err(AudioError::UnsupportedFormat(format!(
+                "Only 16-bit PCM capture supported, got tag {} with {} bits",
+                format.format_tag, format.bits_per_sample
+            )));
+        }
+
+        tracing::debug!(
+            "[Audio] Recording: {} Hz, {} ch, {} frames per packet",
+            format.samples_per_sec,
+            format.channels,
+            self.frames_per_packet
+        );
+
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or(AudioError::NoInputDevice)?;
+
+        let config = StreamConfig {
+            channels: format.channels,
+            sample_rate: format.samples_per_sec,
+            buffer_size: cpal::BufferSize::Default,
+        };
+
+        // 16-bit samples, so two bytes per channel in each frame
+        let packet_len = self.frames_per_packet as usize * usize::from(format.channels) * 2;
+        let mut pending: Vec<u8> = Vec::with_capacity(packet_len);
+        let stream = device
+            .build_input_stream(
+                &config,
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    pending.extend(data.iter().flat_map(|s| s.to_le_bytes()));
+                    if packet_len == 0 {
+                        let _ = sender.send(RdpClientCommand::AudioInputData(std::mem::take(
+                            &mut pending,
+                        )));
+                        return;
+                    }
+                    while pending.len() >= packet_len {
+                        let rest = pending.split_off(packet_len);
+                        let packet = std::mem::replace(&mut pending, rest);
+                        let _ = sender.send(RdpClientCommand::AudioInputData(packet));
+                    }
+                },
+                |err| {
+                    tracing::error!("[Audio] Input stream error: {}", err);
+                },
+                None,
+            )
+            .map_err(|e| AudioError::StreamCreation(e.to_string()))?;
+
+        stream
+            .play()
+            .map_err(|e| AudioError::Recording(e.to_string()))?;
+
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    /// Stops recording
+    pub fn stop(&mut self) {
+        if self.stream.take().is_some() {
+            tracing::debug!("[Audio] Recording stopped");
+        }
+    }
+
+    /// Returns true if the microphone is being recorded
+    #[must_use]
+    pub const fn is_recording(&self) -> bool {
+        self.stream.is_some()
+    }
+}
+
+impl Default for RdpAudioRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,6 +524,12 @@ mod tests {
         assert!(buffer.len() <= buffer.max_size);
     }
 
+    #[test]
+    fn test_audio_recorder_creation() {
+        let recorder = RdpAudioRecorder::new();
+        assert!(!recorder.is_recording());
+    }
+
     #[test]
     fn test_audio_player_creation() {
         let player = RdpAudioPlayer::new();
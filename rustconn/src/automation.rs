@@ -2,12 +2,18 @@
 //!
 //! This module provides "Expect"-like functionality for terminal sessions,
 //! allowing automatic responses to specific text patterns in the output.
+//! Immediate rules respond whenever their pattern shows up; wait-for-prompt
+//! rules run as an ordered sequence of steps on the shared runtime.
 
 use gtk4::glib;
 use gtk4::glib::ControlFlow;
-use regex::Regex;
+use rustconn_core::automation::{
+    CompiledRule, ExpectEngine, ExpectResult, ExpectRule, PromptWaiter,
+};
+use rustconn_core::session::SecretWindow;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::mpsc;
 use std::time::Duration;
 use vte4::prelude::*;
 use vte4::{Format, Terminal};
@@ -15,17 +21,41 @@ use vte4::{Format, Terminal};
 /// A trigger rule that matches output and sends input
 #[derive(Debug, Clone)]
 pub struct Trigger {
-    /// Regex pattern to match in terminal output
-    pub pattern: Regex,
-    /// Text to send when pattern matches
-    pub response: String,
+    /// Expect rule whose pattern is matched in terminal output and whose
+    /// response, with capture groups substituted, is sent
+    pub rule: CompiledRule,
     /// Whether this trigger should only fire once
     pub one_shot: bool,
 }
 
+/// Progress reported by the wait-for-prompt sequence
+enum PromptEvent {
+    /// A step's prompt appeared; send its rule's response
+    Respond {
+        /// The step that matched
+        rule: ExpectRule,
+        /// Response with capture groups and escapes substituted
+        response: String,
+    },
+    /// The sequence completed or stopped
+    Finished(ExpectResult<usize>),
+}
+
+/// Channels to a running wait-for-prompt sequence
+struct PromptSequence {
+    /// Terminal output not yet seen by the sequence
+    output: tokio::sync::mpsc::UnboundedSender<String>,
+    /// Responses and completion sent back by the sequence
+    events: mpsc::Receiver<PromptEvent>,
+}
+
 /// Shared state for automation triggers
 struct AutomationState {
     triggers: Vec<Trigger>,
+    /// Wait-for-prompt steps still running, if any
+    prompt_sequence: Option<PromptSequence>,
+    /// Pauses the session log while a secret response is sent
+    secret_window: SecretWindow,
     /// Track which patterns have been matched (for one-shot)
     matched_patterns: Vec<String>,
     /// Last content to detect changes
@@ -34,6 +64,13 @@ struct AutomationState {
     poll_count: u32,
 }
 
+impl AutomationState {
+    /// Returns true once no trigger or prompt step is left
+    fn is_complete(&self) -> bool {
+        self.triggers.is_empty() && self.prompt_sequence.is_none()
+    }
+}
+
 /// Manages automation for a terminal session
 ///
 /// The `state` field holds the shared automation state that is accessed by the
@@ -52,14 +89,24 @@ impl AutomationSession {
         self.state.borrow().triggers.len()
     }
 
-    /// Returns whether all triggers have been processed
+    /// Returns whether all triggers and prompt steps have been processed
     #[must_use]
     pub fn is_complete(&self) -> bool {
-        self.state.borrow().triggers.is_empty()
+        self.state.borrow().is_complete()
     }
 
-    /// Creates a new automation session with the given triggers
-    pub fn new(terminal: Terminal, triggers: Vec<Trigger>) -> Self {
+    /// Creates a new automation session
+    ///
+    /// `triggers` respond whenever their pattern appears; `prompt_steps` are
+    /// wait-for-prompt rules run in order, each with its own timeout.
+    /// `secret_window` is opened while a rule answering a password prompt
+    /// sends its response.
+    pub fn new(
+        terminal: Terminal,
+        triggers: Vec<Trigger>,
+        prompt_steps: Vec<ExpectRule>,
+        secret_window: SecretWindow,
+    ) -> Self {
         tracing::info!(
             "AutomationSession: Created with {} triggers",
             triggers.len()
@@ -67,13 +114,21 @@ impl AutomationSession {
         for trigger in &triggers {
             tracing::info!(
                 "AutomationSession: Trigger pattern='{}', response='{}'",
-                trigger.pattern,
-                trigger.response.escape_debug()
+                trigger.rule.regex,
+                trigger.rule.rule.response.escape_debug()
             );
         }
 
+        let prompt_sequence = if prompt_steps.is_empty() {
+            None
+        } else {
+            Self::start_prompt_sequence(prompt_steps)
+        };
+
         let state = Rc::new(RefCell::new(AutomationState {
             triggers,
+            prompt_sequence,
+            secret_window,
             matched_patterns: Vec::new(),
             last_content: String::new(),
             poll_count: 0,
@@ -90,19 +145,92 @@ impl AutomationSession {
 
             Self::check_terminal_content(&terminal, &state_clone);
 
-            // Continue polling while we have triggers
-            let has_triggers = !state_clone.borrow().triggers.is_empty();
-            if has_triggers {
-                ControlFlow::Continue
-            } else {
+            // Continue polling while we have triggers or prompt steps
+            if state_clone.borrow().is_complete() {
                 tracing::debug!("AutomationSession: No more triggers, stopping polling");
                 ControlFlow::Break
+            } else {
+                ControlFlow::Continue
             }
         });
 
         Self { state }
     }
 
+    /// Runs the wait-for-prompt steps on the shared runtime
+    ///
+    /// Output reaches the steps through the returned sequence's `output`
+    /// channel; dropping it ends the sequence.
+    fn start_prompt_sequence(prompt_steps: Vec<ExpectRule>) -> Option<PromptSequence> {
+        let engine = match ExpectEngine::from_rules(prompt_steps) {
+            Ok(engine) => engine,
+            Err(e) => {
+                tracing::warn!("AutomationSession: Cannot run prompt steps: {e}");
+                return None;
+            }
+        };
+        let runtime = match crate::utils::shared_runtime() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                tracing::warn!("AutomationSession: Cannot run prompt steps: {e}");
+                return None;
+            }
+        };
+
+        let (output, mut waiter) = PromptWaiter::channel();
+        let (sender, events) = mpsc::channel();
+        runtime.spawn(async move {
+            let result = engine
+                .run_prompt_sequence(&mut waiter, |rule, matched| {
+                    // Escapes apply to the rule's text, not to captured output
+                    if let Ok(Some(response)) =
+                        rule.response_with(matched, |text| Ok(Self::process_escapes(text)))
+                    {
+                        let _ = sender.send(PromptEvent::Respond {
+                            rule: rule.rule.clone(),
+                            response,
+                        });
+                    }
+                })
+                .await;
+            let _ = sender.send(PromptEvent::Finished(result));
+        });
+
+        Some(PromptSequence { output, events })
+    }
+
+    /// Sends the responses of completed prompt steps to the terminal
+    fn deliver_prompt_events(terminal: &Terminal, state: &Rc<RefCell<AutomationState>>) {
+        let (events, secret_window) = {
+            let state_ref = state.borrow();
+            let events: Vec<PromptEvent> = state_ref
+                .prompt_sequence
+                .as_ref()
+                .map(|sequence| sequence.events.try_iter().collect())
+                .unwrap_or_default();
+            (events, state_ref.secret_window.clone())
+        };
+
+        for event in events {
+            match event {
+                PromptEvent::Respond { rule, response } => {
+                    rule.send_response(&secret_window, || {
+                        terminal.feed_child(response.as_bytes());
+                    });
+                }
+                PromptEvent::Finished(result) => {
+                    match result {
+                        Ok(steps) => {
+                            tracing::debug!("AutomationSession: Completed {steps} prompt step(s)");
+                        }
+                        Err(e) => tracing::warn!("AutomationSession: Prompt steps stopped: {e}"),
+                    }
+                    state.borrow_mut().prompt_sequence = None;
+                }
+            }
+        }
+    }
+
     /// Process escape sequences in response string
     fn process_escapes(s: &str) -> String {
         let mut result = String::with_capacity(s.len());
@@ -138,10 +266,12 @@ impl AutomationSession {
     }
 
     fn check_terminal_content(terminal: &Terminal, state: &Rc<RefCell<AutomationState>>) {
+        Self::deliver_prompt_events(terminal, state);
+
         let mut state_ref = state.borrow_mut();
 
-        // Skip if no triggers left
-        if state_ref.triggers.is_empty() {
+        // Skip if no triggers or prompt steps left
+        if state_ref.is_complete() {
             return;
         }
 
@@ -184,6 +314,14 @@ impl AutomationSession {
             return;
         }
 
+        // Prompt steps consume output in order, so they only get what is new
+        if let Some(sequence) = &state_ref.prompt_sequence {
+            let unseen = PromptWaiter::unseen_output(&state_ref.last_content, &content);
+            if !unseen.is_empty() {
+                let _ = sequence.output.send(unseen.to_string());
+            }
+        }
+
         state_ref.last_content = content.clone();
 
         let mut to_remove = Vec::new();
@@ -198,7 +336,7 @@ impl AutomationSession {
             }
 
             for (idx, trigger) in state_ref.triggers.iter().enumerate() {
-                let pattern_str = trigger.pattern.to_string();
+                let pattern_str = trigger.rule.regex.to_string();
 
                 // Skip if already matched
                 if state_ref.matched_patterns.contains(&pattern_str) {
@@ -211,21 +349,33 @@ impl AutomationSession {
                 }
 
                 // Try matching against both full line and trimmed
-                let matches = trigger.pattern.is_match(line) || trigger.pattern.is_match(trimmed);
-
-                if matches {
+                let matched = if trigger.rule.matches(line) {
+                    Some(line)
+                } else if trigger.rule.matches(trimmed) {
+                    Some(trimmed)
+                } else {
+                    None
+                };
+
+                if let Some(matched) = matched {
                     tracing::info!(
                         "AutomationSession: MATCHED pattern '{}' on line '{}'",
-                        trigger.pattern,
+                        trigger.rule.regex,
                         trimmed
                     );
 
-                    let response = Self::process_escapes(&trigger.response);
+                    // Escapes apply to the rule's text, not to captured output
+                    let Ok(Some(response)) = trigger
+                        .rule
+                        .response_with(matched, |text| Ok(Self::process_escapes(text)))
+                    else {
+                        continue;
+                    };
                     tracing::info!(
                         "AutomationSession: Sending response: '{}'",
                         response.escape_debug()
                     );
-                    responses_to_send.push(response);
+                    responses_to_send.push((trigger.rule.rule.clone(), response));
 
                     if trigger.one_shot {
                         patterns_to_mark.push(pattern_str);
@@ -248,11 +398,14 @@ impl AutomationSession {
         }
 
         // Drop borrow before sending
+        let secret_window = state_ref.secret_window.clone();
         drop(state_ref);
 
-        // Send responses
-        for response in responses_to_send {
-            terminal.feed_child(response.as_bytes());
+        // Send responses, keeping answers to password prompts out of the log
+        for (rule, response) in responses_to_send {
+            rule.send_response(&secret_window, || {
+                terminal.feed_child(response.as_bytes());
+            });
         }
     }
 }
@@ -127,7 +127,7 @@ impl SessionStats {
             SessionState::Starting => "Starting",
             SessionState::Active => "Connected",
             SessionState::Disconnecting => "Disconnecting",
-            SessionState::Terminated => "Disconnected",
+            SessionState::Terminated | SessionState::Disconnected => "Disconnected",
             SessionState::Error => "Error",
         }
     }
@@ -139,7 +139,7 @@ impl SessionStats {
             SessionState::Starting => "session-starting",
             SessionState::Active => "session-active",
             SessionState::Disconnecting => "session-disconnecting",
-            SessionState::Terminated => "session-terminated",
+            SessionState::Terminated | SessionState::Disconnected => "session-terminated",
             SessionState::Error => "session-error",
         }
     }
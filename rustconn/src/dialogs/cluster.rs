@@ -367,6 +367,7 @@ pub struct ClusterListDialog {
     clusters_list: ListBox,
     cluster_rows: Rc<RefCell<Vec<ClusterListRow>>>,
     on_connect: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>>,
+    on_run_command: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>>,
     on_edit: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>>,
     on_delete: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>>,
     on_new: Rc<RefCell<Option<Box<dyn Fn()>>>>,
@@ -387,6 +388,8 @@ struct ClusterListRow {
     count_label: Label,
     /// Connect button
     connect_button: Button,
+    /// Run command button
+    run_command_button: Button,
     /// Edit button
     edit_button: Button,
     /// Delete button
@@ -465,6 +468,7 @@ impl ClusterListDialog {
         content.append(&scrolled);
 
         let on_connect: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>> = Rc::new(RefCell::new(None));
+        let on_run_command: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>> = Rc::new(RefCell::new(None));
         let on_edit: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>> = Rc::new(RefCell::new(None));
         let on_delete: Rc<RefCell<Option<Box<dyn Fn(Uuid)>>>> = Rc::new(RefCell::new(None));
         let on_new: Rc<RefCell<Option<Box<dyn Fn()>>>> = Rc::new(RefCell::new(None));
@@ -485,6 +489,7 @@ impl ClusterListDialog {
             clusters_list,
             cluster_rows,
             on_connect,
+            on_run_command,
             on_edit,
             on_delete,
             on_new,
@@ -540,6 +545,12 @@ impl ClusterListDialog {
             .css_classes(["flat"])
             .build();
 
+        let run_command_button = Button::builder()
+            .icon_name("utilities-terminal-symbolic")
+            .tooltip_text("Run a command on connected members")
+            .css_classes(["flat"])
+            .build();
+
         let edit_button = Button::builder()
             .icon_name("document-edit-symbolic")
             .tooltip_text("Edit cluster")
@@ -553,6 +564,7 @@ impl ClusterListDialog {
             .build();
 
         hbox.append(&connect_button);
+        hbox.append(&run_command_button);
         hbox.append(&edit_button);
         hbox.append(&delete_button);
 
@@ -564,6 +576,7 @@ impl ClusterListDialog {
             name_label,
             count_label,
             connect_button,
+            run_command_button,
             edit_button,
             delete_button,
         }
@@ -607,6 +620,13 @@ impl ClusterListDialog {
                 }
             });
 
+            let on_run_command_clone = self.on_run_command.clone();
+            cluster_row.run_command_button.connect_clicked(move |_| {
+                if let Some(ref cb) = *on_run_command_clone.borrow() {
+                    cb(cluster_id);
+                }
+            });
+
             let on_edit_clone = self.on_edit.clone();
             cluster_row.edit_button.connect_clicked(move |_| {
                 if let Some(ref cb) = *on_edit_clone.borrow() {
@@ -631,6 +651,11 @@ impl ClusterListDialog {
         *self.on_connect.borrow_mut() = Some(Box::new(cb));
     }
 
+    /// Sets the callback for running a command on a cluster's members
+    pub fn set_on_run_command<F: Fn(Uuid) + 'static>(&self, cb: F) {
+        *self.on_run_command.borrow_mut() = Some(Box::new(cb));
+    }
+
     /// Sets the callback for editing a cluster
     pub fn set_on_edit<F: Fn(Uuid) + 'static>(&self, cb: F) {
         *self.on_edit.borrow_mut() = Some(Box::new(cb));
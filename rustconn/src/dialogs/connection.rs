@@ -20,20 +20,23 @@ use gtk4::{
     TextView, WrapMode,
 };
 use libadwaita as adw;
-use rustconn_core::automation::{ConnectionTask, ExpectRule, TaskCondition};
+use rustconn_core::automation::{CompiledRule, ConnectionTask, ExpectRule, TaskCondition};
 use rustconn_core::models::{
     AwsSsmConfig, AzureBastionConfig, AzureSshConfig, BoundaryConfig, CloudflareAccessConfig,
-    Connection, CustomProperty, GcpIapConfig, GenericZeroTrustConfig, OciBastionConfig,
-    PasswordSource, PropertyType, ProtocolConfig, RdpClientMode, RdpConfig, RdpPerformanceMode,
-    Resolution, SharedFolder, SpiceConfig, SpiceImageCompression, SshAuthMethod, SshConfig,
-    SshKeySource, TailscaleSshConfig, TeleportConfig, VncClientMode, VncConfig, VncPerformanceMode,
-    WindowMode, ZeroTrustConfig, ZeroTrustProvider, ZeroTrustProviderConfig,
+    Connection, CustomProperty, GcpIapConfig, GenericProxyConfig, GenericZeroTrustConfig,
+    OciBastionConfig, PasswordSource, PropertyType, ProtocolConfig, RdpClientMode, RdpConfig,
+    RdpPerformanceMode, Resolution, SharedFolder, SpiceConfig, SpiceImageCompression,
+    SshAlgorithmList, SshAlgorithms, SshAuthMethod, SshConfig, SshForward, SshKeySource,
+    TailscaleSshConfig, TeleportConfig, VncClientMode, VncConfig, VncPerformanceMode, WindowMode,
+    ZeroTrustConfig, ZeroTrustProvider, ZeroTrustProviderConfig,
 };
 use rustconn_core::secret::SecretBackend;
 use rustconn_core::session::LogConfig;
+use rustconn_core::spice_client::normalize_fingerprint;
 use rustconn_core::variables::Variable;
 use rustconn_core::wol::{
-    MacAddress, WolConfig, DEFAULT_BROADCAST_ADDRESS, DEFAULT_WOL_PORT, DEFAULT_WOL_WAIT_SECONDS,
+    MacAddress, SecureOnPassword, WolConfig, DEFAULT_BROADCAST_ADDRESS, DEFAULT_WOL_PORT,
+    DEFAULT_WOL_WAIT_SECONDS,
 };
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -41,6 +44,18 @@ use std::path::PathBuf;
 use std::rc::Rc;
 use uuid::Uuid;
 
+/// SecureOn password and directed broadcast of the edited WoL config
+///
+/// The dialog has no controls for them, so they are kept as loaded and
+/// written back on save.
+type WolPreserved = Rc<RefCell<(Option<SecureOnPassword>, Option<std::net::Ipv4Addr>)>>;
+
+/// Log configuration of the edited connection
+///
+/// Logging modes, format and redaction settings have no controls in the
+/// dialog, so they are kept as loaded and written back on save.
+type LogPreserved = Rc<RefCell<Option<LogConfig>>>;
+
 /// Connection dialog for creating/editing connections
 #[allow(dead_code)] // Many fields kept for GTK widget lifecycle and signal handlers
 pub struct ConnectionDialog {
@@ -93,6 +108,7 @@ pub struct ConnectionDialog {
     rdp_height_spin: SpinButton,
     rdp_color_dropdown: DropDown,
     rdp_audio_check: CheckButton,
+    rdp_microphone_check: CheckButton,
     rdp_gateway_entry: Entry,
     rdp_shared_folders: Rc<RefCell<Vec<SharedFolder>>>,
     rdp_shared_folders_list: gtk4::ListBox,
@@ -112,6 +128,7 @@ pub struct ConnectionDialog {
     spice_ca_cert_entry: Entry,
     spice_ca_cert_button: Button,
     spice_skip_verify_check: CheckButton,
+    spice_cert_fingerprint_entry: Entry,
     spice_usb_check: CheckButton,
     spice_clipboard_check: CheckButton,
     spice_compression_dropdown: DropDown,
@@ -151,6 +168,9 @@ pub struct ConnectionDialog {
     zt_boundary_addr_entry: Entry,
     // Generic fields
     zt_generic_command_entry: Entry,
+    // Generic ProxyCommand fields
+    zt_proxy_host_entry: Entry,
+    zt_proxy_command_entry: Entry,
     // Custom args for all providers
     zt_custom_args_entry: Entry,
     // Variables fields
@@ -165,6 +185,7 @@ pub struct ConnectionDialog {
     logging_timestamp_dropdown: DropDown,
     logging_max_size_spin: SpinButton,
     logging_retention_spin: SpinButton,
+    log_preserved: LogPreserved,
     // Expect rules fields
     expect_rules_list: ListBox,
     expect_rules: Rc<RefCell<Vec<ExpectRule>>>,
@@ -198,6 +219,7 @@ pub struct ConnectionDialog {
     wol_broadcast_entry: Entry,
     wol_port_spin: SpinButton,
     wol_wait_spin: SpinButton,
+    wol_preserved: WolPreserved,
     // State
     editing_id: Rc<RefCell<Option<Uuid>>>,
     // Callback
@@ -224,6 +246,8 @@ struct LocalVariableRow {
     delete_button: Button,
     /// Whether this is an inherited global variable (read-only name)
     is_inherited: bool,
+    /// Type carried over from the edited variable
+    value_type: rustconn_core::variables::VariableType,
 }
 
 /// Represents an expect rule row in the connection dialog
@@ -266,6 +290,19 @@ struct CustomPropertyRow {
     delete_button: Button,
 }
 
+/// SSH options parsed from the "Key=Value" options entry
+#[derive(Default)]
+struct ParsedSshOptions {
+    /// Options passed with `-o`
+    options: HashMap<String, String>,
+    /// `LocalForward`/`RemoteForward`/`DynamicForward` tunnels
+    forwards: Vec<SshForward>,
+    /// `SetEnv=NAME=value` entries
+    environment: Vec<(String, String)>,
+    /// `Ciphers`/`MACs`/`KexAlgorithms` lists
+    algorithms: SshAlgorithms,
+}
+
 impl ConnectionDialog {
     /// Creates a new connection dialog
     #[must_use]
@@ -345,6 +382,7 @@ impl ConnectionDialog {
             rdp_height_spin,
             rdp_color_dropdown,
             rdp_audio_check,
+            rdp_microphone_check,
             rdp_gateway_entry,
             rdp_shared_folders,
             rdp_shared_folders_list,
@@ -374,6 +412,7 @@ impl ConnectionDialog {
             spice_ca_cert_entry,
             spice_ca_cert_button,
             spice_skip_verify_check,
+            spice_cert_fingerprint_entry,
             spice_usb_check,
             spice_clipboard_check,
             spice_compression_dropdown,
@@ -408,6 +447,8 @@ impl ConnectionDialog {
             zt_boundary_target_entry,
             zt_boundary_addr_entry,
             zt_generic_command_entry,
+            zt_proxy_host_entry,
+            zt_proxy_command_entry,
             zt_custom_args_entry,
         ) = Self::create_zerotrust_options();
         protocol_stack.add_named(&zt_box, Some("zerotrust"));
@@ -529,6 +570,8 @@ impl ConnectionDialog {
             Rc::new(RefCell::new(vec![(None, "(Root)".to_string())]));
         let connections_data: Rc<RefCell<Vec<(Option<Uuid>, String)>>> =
             Rc::new(RefCell::new(vec![(None, "(None)".to_string())]));
+        let wol_preserved: WolPreserved = Rc::new(RefCell::new((None, None)));
+        let log_preserved: LogPreserved = Rc::new(RefCell::new(None));
 
         // Connect save button handler
         Self::connect_save_button(
@@ -568,6 +611,7 @@ impl ConnectionDialog {
             &rdp_height_spin,
             &rdp_color_dropdown,
             &rdp_audio_check,
+            &rdp_microphone_check,
             &rdp_gateway_entry,
             &rdp_shared_folders,
             &rdp_custom_args_entry,
@@ -583,6 +627,7 @@ impl ConnectionDialog {
             &spice_tls_check,
             &spice_ca_cert_entry,
             &spice_skip_verify_check,
+            &spice_cert_fingerprint_entry,
             &spice_usb_check,
             &spice_clipboard_check,
             &spice_compression_dropdown,
@@ -609,6 +654,8 @@ impl ConnectionDialog {
             &zt_boundary_target_entry,
             &zt_boundary_addr_entry,
             &zt_generic_command_entry,
+            &zt_proxy_host_entry,
+            &zt_proxy_command_entry,
             &zt_custom_args_entry,
             &variables_rows,
             &logging_enabled_check,
@@ -634,6 +681,8 @@ impl ConnectionDialog {
             &wol_broadcast_entry,
             &wol_port_spin,
             &wol_wait_spin,
+            &wol_preserved,
+            &log_preserved,
             &connections_data,
         );
 
@@ -678,6 +727,7 @@ impl ConnectionDialog {
             rdp_height_spin,
             rdp_color_dropdown,
             rdp_audio_check,
+            rdp_microphone_check,
             rdp_gateway_entry,
             rdp_shared_folders,
             rdp_shared_folders_list,
@@ -704,6 +754,7 @@ impl ConnectionDialog {
             spice_ca_cert_entry,
             spice_ca_cert_button,
             spice_skip_verify_check,
+            spice_cert_fingerprint_entry,
             spice_usb_check,
             spice_clipboard_check,
             spice_compression_dropdown,
@@ -732,6 +783,8 @@ impl ConnectionDialog {
             zt_boundary_target_entry,
             zt_boundary_addr_entry,
             zt_generic_command_entry,
+            zt_proxy_host_entry,
+            zt_proxy_command_entry,
             zt_custom_args_entry,
             expect_rules_list,
             expect_rules,
@@ -757,6 +810,8 @@ impl ConnectionDialog {
             wol_broadcast_entry,
             wol_port_spin,
             wol_wait_spin,
+            wol_preserved,
+            log_preserved,
             editing_id,
             on_save,
             connections_data,
@@ -1178,6 +1233,7 @@ impl ConnectionDialog {
         rdp_height_spin: &SpinButton,
         rdp_color_dropdown: &DropDown,
         rdp_audio_check: &CheckButton,
+        rdp_microphone_check: &CheckButton,
         rdp_gateway_entry: &Entry,
         rdp_shared_folders: &Rc<RefCell<Vec<SharedFolder>>>,
         rdp_custom_args_entry: &Entry,
@@ -1193,6 +1249,7 @@ impl ConnectionDialog {
         spice_tls_check: &CheckButton,
         spice_ca_cert_entry: &Entry,
         spice_skip_verify_check: &CheckButton,
+        spice_cert_fingerprint_entry: &Entry,
         spice_usb_check: &CheckButton,
         spice_clipboard_check: &CheckButton,
         spice_compression_dropdown: &DropDown,
@@ -1219,6 +1276,8 @@ impl ConnectionDialog {
         zt_boundary_target_entry: &Entry,
         zt_boundary_addr_entry: &Entry,
         zt_generic_command_entry: &Entry,
+        zt_proxy_host_entry: &Entry,
+        zt_proxy_command_entry: &Entry,
         zt_custom_args_entry: &Entry,
         variables_rows: &Rc<RefCell<Vec<LocalVariableRow>>>,
         logging_enabled_check: &CheckButton,
@@ -1244,6 +1303,8 @@ impl ConnectionDialog {
         wol_broadcast_entry: &Entry,
         wol_port_spin: &SpinButton,
         wol_wait_spin: &SpinButton,
+        wol_preserved: &WolPreserved,
+        log_preserved: &LogPreserved,
         connections_data: &Rc<RefCell<Vec<(Option<Uuid>, String)>>>,
     ) {
         let window = window.clone();
@@ -1279,6 +1340,7 @@ impl ConnectionDialog {
         let rdp_height_spin = rdp_height_spin.clone();
         let rdp_color_dropdown = rdp_color_dropdown.clone();
         let rdp_audio_check = rdp_audio_check.clone();
+        let rdp_microphone_check = rdp_microphone_check.clone();
         let rdp_gateway_entry = rdp_gateway_entry.clone();
         let rdp_shared_folders = rdp_shared_folders.clone();
         let rdp_custom_args_entry = rdp_custom_args_entry.clone();
@@ -1295,6 +1357,7 @@ impl ConnectionDialog {
         let spice_tls_check = spice_tls_check.clone();
         let spice_ca_cert_entry = spice_ca_cert_entry.clone();
         let spice_skip_verify_check = spice_skip_verify_check.clone();
+        let spice_cert_fingerprint_entry = spice_cert_fingerprint_entry.clone();
         let spice_usb_check = spice_usb_check.clone();
         let spice_clipboard_check = spice_clipboard_check.clone();
         let spice_compression_dropdown = spice_compression_dropdown.clone();
@@ -1321,6 +1384,8 @@ impl ConnectionDialog {
         let zt_boundary_target_entry = zt_boundary_target_entry.clone();
         let zt_boundary_addr_entry = zt_boundary_addr_entry.clone();
         let zt_generic_command_entry = zt_generic_command_entry.clone();
+        let zt_proxy_host_entry = zt_proxy_host_entry.clone();
+        let zt_proxy_command_entry = zt_proxy_command_entry.clone();
         let zt_custom_args_entry = zt_custom_args_entry.clone();
         let variables_rows = variables_rows.clone();
         let logging_enabled_check = logging_enabled_check.clone();
@@ -1346,6 +1411,8 @@ impl ConnectionDialog {
         let wol_broadcast_entry = wol_broadcast_entry.clone();
         let wol_port_spin = wol_port_spin.clone();
         let wol_wait_spin = wol_wait_spin.clone();
+        let wol_preserved = wol_preserved.clone();
+        let log_preserved = log_preserved.clone();
         let editing_id = editing_id.clone();
         let connections_data = connections_data.clone();
 
@@ -1386,6 +1453,7 @@ impl ConnectionDialog {
                 rdp_height_spin: &rdp_height_spin,
                 rdp_color_dropdown: &rdp_color_dropdown,
                 rdp_audio_check: &rdp_audio_check,
+                rdp_microphone_check: &rdp_microphone_check,
                 rdp_gateway_entry: &rdp_gateway_entry,
                 rdp_shared_folders: &rdp_shared_folders,
                 rdp_custom_args_entry: &rdp_custom_args_entry,
@@ -1400,6 +1468,7 @@ impl ConnectionDialog {
                 spice_tls_check: &spice_tls_check,
                 spice_ca_cert_entry: &spice_ca_cert_entry,
                 spice_skip_verify_check: &spice_skip_verify_check,
+                spice_cert_fingerprint_entry: &spice_cert_fingerprint_entry,
                 spice_usb_check: &spice_usb_check,
                 spice_clipboard_check: &spice_clipboard_check,
                 spice_compression_dropdown: &spice_compression_dropdown,
@@ -1426,6 +1495,8 @@ impl ConnectionDialog {
                 zt_boundary_target_entry: &zt_boundary_target_entry,
                 zt_boundary_addr_entry: &zt_boundary_addr_entry,
                 zt_generic_command_entry: &zt_generic_command_entry,
+                zt_proxy_host_entry: &zt_proxy_host_entry,
+                zt_proxy_command_entry: &zt_proxy_command_entry,
                 zt_custom_args_entry: &zt_custom_args_entry,
                 local_variables: &local_variables,
                 logging_enabled_check: &logging_enabled_check,
@@ -1451,6 +1522,8 @@ impl ConnectionDialog {
                 wol_broadcast_entry: &wol_broadcast_entry,
                 wol_port_spin: &wol_port_spin,
                 wol_wait_spin: &wol_wait_spin,
+                wol_preserved: &wol_preserved,
+                log_preserved: &log_preserved,
                 rdp_performance_mode_dropdown: &rdp_performance_mode_dropdown,
                 vnc_performance_mode_dropdown: &vnc_performance_mode_dropdown,
                 editing_id: &editing_id,
@@ -2081,7 +2154,7 @@ impl ConnectionDialog {
 
         let options_row = adw::ActionRow::builder()
             .title("Custom Options")
-            .subtitle("Additional SSH command-line options")
+            .subtitle("Additional SSH options; SetEnv=NAME=value sets a session variable")
             .build();
         options_row.add_suffix(&options_entry);
         session_group.add(&options_row);
@@ -2123,6 +2196,7 @@ impl ConnectionDialog {
         SpinButton,
         DropDown,
         CheckButton,
+        CheckButton,
         Entry,
         Rc<RefCell<Vec<SharedFolder>>>,
         gtk4::ListBox,
@@ -2260,6 +2334,16 @@ impl ConnectionDialog {
         audio_row.add_suffix(&audio_check);
         features_group.add(&audio_row);
 
+        // Microphone redirect
+        let microphone_check = CheckButton::new();
+        let microphone_row = adw::ActionRow::builder()
+            .title("Microphone Redirection")
+            .subtitle("Send the local microphone to the remote session")
+            .activatable_widget(&microphone_check)
+            .build();
+        microphone_row.add_suffix(&microphone_check);
+        features_group.add(&microphone_row);
+
         // Gateway
         let gateway_entry = Entry::builder()
             .hexpand(true)
@@ -2357,6 +2441,7 @@ impl ConnectionDialog {
             height_spin,
             color_dropdown,
             audio_check,
+            microphone_check,
             gateway_entry,
             shared_folders,
             folders_list,
@@ -2396,6 +2481,7 @@ impl ConnectionDialog {
                             let folder = SharedFolder {
                                 local_path: path.clone(),
                                 share_name: share_name.clone(),
+                                read_only: false,
                             };
 
                             shared_folders.borrow_mut().push(folder);
@@ -2661,6 +2747,7 @@ impl ConnectionDialog {
         Entry,
         Button,
         CheckButton,
+        Entry,
         CheckButton,
         CheckButton,
         DropDown,
@@ -2728,6 +2815,19 @@ impl ConnectionDialog {
         skip_verify_row.add_suffix(&skip_verify_check);
         security_group.add(&skip_verify_row);
 
+        // Pinned certificate fingerprint
+        let cert_fingerprint_entry = Entry::builder()
+            .hexpand(true)
+            .valign(gtk4::Align::Center)
+            .placeholder_text("SHA-256 fingerprint")
+            .build();
+        let cert_fingerprint_row = adw::ActionRow::builder()
+            .title("Pinned Certificate")
+            .subtitle("Only trust the server certificate with this fingerprint")
+            .build();
+        cert_fingerprint_row.add_suffix(&cert_fingerprint_entry);
+        security_group.add(&cert_fingerprint_row);
+
         content.append(&security_group);
 
         // === Features Group ===
@@ -2830,6 +2930,7 @@ impl ConnectionDialog {
             ca_cert_entry,
             ca_cert_button,
             skip_verify_check,
+            cert_fingerprint_entry,
             usb_check,
             clipboard_check,
             compression_dropdown,
@@ -2866,6 +2967,8 @@ impl ConnectionDialog {
         Entry,
         Entry,
         Entry,
+        Entry,
+        Entry,
     ) {
         let scrolled = ScrolledWindow::builder()
             .hscrollbar_policy(gtk4::PolicyType::Never)
@@ -2898,6 +3001,7 @@ impl ConnectionDialog {
             "Tailscale SSH",
             "HashiCorp Boundary",
             "Generic Command",
+            "SSH via ProxyCommand",
         ]);
         let provider_dropdown = DropDown::new(Some(provider_list), gtk4::Expression::NONE);
         provider_dropdown.set_selected(0);
@@ -2958,6 +3062,10 @@ impl ConnectionDialog {
         let (generic_box, generic_command) = Self::create_generic_zt_fields_adw();
         provider_stack.add_named(&generic_box, Some("generic"));
 
+        // Generic ProxyCommand options
+        let (proxy_box, proxy_host, proxy_command) = Self::create_generic_proxy_fields_adw();
+        provider_stack.add_named(&proxy_box, Some("generic_proxy"));
+
         // Set initial view
         provider_stack.set_visible_child_name("aws_ssm");
 
@@ -2977,6 +3085,7 @@ impl ConnectionDialog {
                 "tailscale",
                 "boundary",
                 "generic",
+                "generic_proxy",
             ];
             let selected = dropdown.selected() as usize;
             if selected < providers.len() {
@@ -3033,6 +3142,8 @@ impl ConnectionDialog {
             boundary_target,
             boundary_addr,
             generic_command,
+            proxy_host,
+            proxy_command,
             custom_args_entry,
         )
     }
@@ -3420,6 +3531,43 @@ impl ConnectionDialog {
         (vbox, command_entry)
     }
 
+    /// Creates generic SSH-over-ProxyCommand provider fields using libadwaita
+    fn create_generic_proxy_fields_adw() -> (GtkBox, Entry, Entry) {
+        let group = adw::PreferencesGroup::builder()
+            .title("SSH via ProxyCommand")
+            .description("Connect through your own SSH proxy or tunnel")
+            .build();
+
+        let host_entry = Entry::builder()
+            .hexpand(true)
+            .placeholder_text("host or host:port")
+            .valign(gtk4::Align::Center)
+            .build();
+        let host_row = adw::ActionRow::builder()
+            .title("Target Host")
+            .subtitle("Host as seen by the proxy (port defaults to 22)")
+            .build();
+        host_row.add_suffix(&host_entry);
+        group.add(&host_row);
+
+        let command_entry = Entry::builder()
+            .hexpand(true)
+            .placeholder_text("iap-tunnel --target {host}:{port}")
+            .valign(gtk4::Align::Center)
+            .build();
+        let command_row = adw::ActionRow::builder()
+            .title("Proxy Command")
+            .subtitle("Use {host} and {port} for the target")
+            .build();
+        command_row.add_suffix(&command_entry);
+        group.add(&command_row);
+
+        let vbox = GtkBox::new(Orientation::Vertical, 0);
+        vbox.append(&group);
+
+        (vbox, host_entry, command_entry)
+    }
+
     /// Creates the Data tab combining Variables and Custom Properties
     ///
     /// Uses libadwaita components following GNOME HIG.
@@ -4733,7 +4881,10 @@ impl ConnectionDialog {
         let response_entry = Entry::builder()
             .hexpand(true)
             .placeholder_text("Text to send when pattern matches")
-            .tooltip_text("Response to send (supports ${variable} syntax)")
+            .tooltip_text(
+                "Response to send (supports ${variable} syntax, $1, $2, ... for \
+                 capture groups and $$ for a literal $)",
+            )
             .build();
 
         grid.attach(&response_label, 0, 1, 1, 1);
@@ -5025,12 +5176,13 @@ impl ConnectionDialog {
             sorted_rules.sort_by(|a, b| b.priority.cmp(&a.priority));
 
             for rule in sorted_rules {
-                match regex::Regex::new(&rule.pattern) {
-                    Ok(re) => {
-                        if re.is_match(&test_text) {
+                match CompiledRule::new(rule.clone()) {
+                    Ok(compiled) => {
+                        // Capture groups substituted, variables left as written
+                        if let Some(response) = compiled.response_for(&test_text) {
                             result_clone.set_text(&format!(
                                 "✓ Matched pattern: \"{}\"\n  Response: \"{}\"",
-                                rule.pattern, rule.response
+                                rule.pattern, response
                             ));
                             result_clone.remove_css_class("dim-label");
                             result_clone.remove_css_class("error");
@@ -5040,8 +5192,7 @@ impl ConnectionDialog {
                         }
                     }
                     Err(e) => {
-                        result_clone
-                            .set_text(&format!("✗ Invalid pattern \"{}\": {}", rule.pattern, e));
+                        result_clone.set_text(&format!("✗ Invalid rule: {e}"));
                         result_clone.remove_css_class("dim-label");
                         result_clone.remove_css_class("success");
                         result_clone.add_css_class("error");
@@ -5213,6 +5364,9 @@ impl ConnectionDialog {
             description_entry,
             delete_button,
             is_inherited,
+            value_type: variable
+                .map(|var| var.value_type.clone())
+                .unwrap_or_default(),
         }
     }
 
@@ -5270,7 +5424,7 @@ impl ConnectionDialog {
                 Some(desc.trim().to_string())
             };
 
-            let mut var = Variable::new(name.clone(), value);
+            let mut var = Variable::new(name.clone(), value).with_type(row.value_type.clone());
             var.is_secret = is_secret;
             var.description = description;
             vars.insert(name, var);
@@ -5538,6 +5692,9 @@ impl ConnectionDialog {
 
     /// Sets the WOL configuration fields
     fn set_wol_config(&self, config: Option<&WolConfig>) {
+        *self.wol_preserved.borrow_mut() = config.map_or((None, None), |wol| {
+            (wol.secure_on_password, wol.directed_broadcast)
+        });
         if let Some(wol) = config {
             self.wol_enabled_check.set_active(true);
             self.wol_mac_entry.set_text(&wol.mac_address.to_string());
@@ -5723,6 +5880,7 @@ impl ConnectionDialog {
 
     /// Sets the log configuration for this connection
     fn set_log_config(&self, log_config: Option<&LogConfig>) {
+        *self.log_preserved.borrow_mut() = log_config.cloned();
         if let Some(config) = log_config {
             self.logging_enabled_check.set_active(config.enabled);
             self.logging_path_entry.set_text(&config.path_template);
@@ -5893,12 +6051,32 @@ impl ConnectionDialog {
             self.ssh_startup_entry.set_text(cmd);
         }
 
-        // Format custom options as "Key=Value, Key2=Value2"
-        if !ssh.custom_options.is_empty() {
+        // Format custom options, port forwards and environment variables
+        // as "Key=Value, Key2=Value2"
+        if !ssh.custom_options.is_empty()
+            || !ssh.forwards.is_empty()
+            || !ssh.environment.is_empty()
+            || !ssh.algorithms.is_empty()
+        {
             let opts: Vec<String> = ssh
                 .custom_options
                 .iter()
                 .map(|(k, v)| format!("{k}={v}"))
+                .chain(
+                    ssh.forwards
+                        .iter()
+                        .map(|f| format!("{}={}", f.config_keyword(), f.config_value())),
+                )
+                .chain(
+                    ssh.environment
+                        .iter()
+                        .map(|(name, value)| format!("SetEnv={name}={value}")),
+                )
+                .chain(
+                    ssh.algorithms
+                        .iter()
+                        .map(|(keyword, list)| format!("{keyword}={}", list.to_value())),
+                )
                 .collect();
             self.ssh_options_entry.set_text(&opts.join(", "));
         }
@@ -5942,6 +6120,8 @@ impl ConnectionDialog {
             self.rdp_color_dropdown.set_selected(idx);
         }
         self.rdp_audio_check.set_active(rdp.audio_redirect);
+        self.rdp_microphone_check
+            .set_active(rdp.microphone_redirect);
         if let Some(ref gw) = rdp.gateway {
             self.rdp_gateway_entry.set_text(&gw.hostname);
         }
@@ -6020,6 +6200,9 @@ impl ConnectionDialog {
         }
         self.spice_skip_verify_check
             .set_active(spice.skip_cert_verify);
+        if let Some(ref fingerprint) = spice.cert_fingerprint {
+            self.spice_cert_fingerprint_entry.set_text(fingerprint);
+        }
         self.spice_usb_check.set_active(spice.usb_redirection);
         self.spice_clipboard_check
             .set_active(spice.clipboard_enabled);
@@ -6063,6 +6246,7 @@ impl ConnectionDialog {
             ZeroTrustProvider::TailscaleSsh => 7,
             ZeroTrustProvider::Boundary => 8,
             ZeroTrustProvider::Generic => 9,
+            ZeroTrustProvider::GenericProxy => 10,
         };
         self.zt_provider_dropdown.set_selected(provider_idx);
 
@@ -6078,6 +6262,7 @@ impl ConnectionDialog {
             ZeroTrustProvider::TailscaleSsh => "tailscale",
             ZeroTrustProvider::Boundary => "boundary",
             ZeroTrustProvider::Generic => "generic",
+            ZeroTrustProvider::GenericProxy => "generic_proxy",
         };
         self.zt_provider_stack.set_visible_child_name(stack_name);
 
@@ -6135,6 +6320,10 @@ impl ConnectionDialog {
                 self.zt_generic_command_entry
                     .set_text(&cfg.command_template);
             }
+            ZeroTrustProviderConfig::GenericProxy(cfg) => {
+                self.zt_proxy_host_entry.set_text(&cfg.target());
+                self.zt_proxy_command_entry.set_text(&cfg.proxy_command);
+            }
         }
 
         // Set custom args
@@ -6607,6 +6796,7 @@ impl ConnectionDialog {
             SecretBackendType::KeePassXc | SecretBackendType::KdbxFile => 1, // KeePass
             SecretBackendType::LibSecret => 2,                               // Keyring
             SecretBackendType::Bitwarden => 3,                               // Bitwarden
+            SecretBackendType::Pass => 0,                                    // Prompt
         };
 
         self.password_source_dropdown.set_selected(default_idx);
@@ -6649,6 +6839,7 @@ struct ConnectionDialogData<'a> {
     rdp_height_spin: &'a SpinButton,
     rdp_color_dropdown: &'a DropDown,
     rdp_audio_check: &'a CheckButton,
+    rdp_microphone_check: &'a CheckButton,
     rdp_gateway_entry: &'a Entry,
     rdp_shared_folders: &'a Rc<RefCell<Vec<SharedFolder>>>,
     rdp_custom_args_entry: &'a Entry,
@@ -6664,6 +6855,7 @@ struct ConnectionDialogData<'a> {
     spice_tls_check: &'a CheckButton,
     spice_ca_cert_entry: &'a Entry,
     spice_skip_verify_check: &'a CheckButton,
+    spice_cert_fingerprint_entry: &'a Entry,
     spice_usb_check: &'a CheckButton,
     spice_clipboard_check: &'a CheckButton,
     spice_compression_dropdown: &'a DropDown,
@@ -6691,6 +6883,8 @@ struct ConnectionDialogData<'a> {
     zt_boundary_target_entry: &'a Entry,
     zt_boundary_addr_entry: &'a Entry,
     zt_generic_command_entry: &'a Entry,
+    zt_proxy_host_entry: &'a Entry,
+    zt_proxy_command_entry: &'a Entry,
     zt_custom_args_entry: &'a Entry,
     local_variables: &'a HashMap<String, Variable>,
     logging_enabled_check: &'a CheckButton,
@@ -6720,6 +6914,8 @@ struct ConnectionDialogData<'a> {
     wol_broadcast_entry: &'a Entry,
     wol_port_spin: &'a SpinButton,
     wol_wait_spin: &'a SpinButton,
+    wol_preserved: &'a WolPreserved,
+    log_preserved: &'a LogPreserved,
     editing_id: &'a Rc<RefCell<Option<Uuid>>>,
     // Jump Host fields
     ssh_jump_host_dropdown: &'a DropDown,
@@ -6924,12 +7120,15 @@ impl ConnectionDialogData<'_> {
         let port = self.wol_port_spin.value() as u16;
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         let wait_seconds = self.wol_wait_spin.value() as u32;
+        let preserved = *self.wol_preserved.borrow();
 
         Some(WolConfig {
             mac_address,
             broadcast_address,
             port,
             wait_seconds,
+            secure_on_password: preserved.0,
+            directed_broadcast: preserved.1,
         })
     }
 
@@ -7041,9 +7240,7 @@ impl ConnectionDialogData<'_> {
             timestamp_format,
             max_size_mb,
             retention_days,
-            log_activity: true,
-            log_input: false,
-            log_output: false,
+            ..self.log_preserved.borrow().clone().unwrap_or_default()
         })
     }
 
@@ -7072,6 +7269,7 @@ impl ConnectionDialogData<'_> {
             6 => ZeroTrustProvider::Teleport,
             7 => ZeroTrustProvider::TailscaleSsh,
             8 => ZeroTrustProvider::Boundary,
+            10 => ZeroTrustProvider::GenericProxy,
             _ => ZeroTrustProvider::Generic,
         };
 
@@ -7171,6 +7369,12 @@ impl ConnectionDialogData<'_> {
                     command_template: self.zt_generic_command_entry.text().trim().to_string(),
                 })
             }
+            ZeroTrustProvider::GenericProxy => {
+                ZeroTrustProviderConfig::GenericProxy(GenericProxyConfig::from_target(
+                    &self.zt_proxy_host_entry.text(),
+                    self.zt_proxy_command_entry.text().trim(),
+                ))
+            }
         };
 
         let custom_args = Self::parse_args(&self.zt_custom_args_entry.text());
@@ -7183,10 +7387,15 @@ impl ConnectionDialogData<'_> {
             detected_provider: None,
         };
 
-        // Detect and persist the provider based on the generated command
-        let (program, args) = config.build_command(None);
-        let full_command = format!("{} {}", program, args.join(" "));
-        let detected = rustconn_core::detect_provider(&full_command);
+        // Detect and persist the provider based on the generated command;
+        // a ProxyCommand connection is always user-selected
+        let detected = if provider == ZeroTrustProvider::GenericProxy {
+            rustconn_core::CloudProvider::GenericProxy
+        } else {
+            let (program, args) = config.build_command(None);
+            let full_command = format!("{} {}", program, args.join(" "));
+            rustconn_core::detect_provider(&full_command)
+        };
         config.detected_provider = Some(detected.icon_name().to_string());
 
         config
@@ -7263,7 +7472,12 @@ impl ConnectionDialogData<'_> {
             Some(proxy_jump.trim().to_string())
         };
 
-        let custom_options = Self::parse_custom_options(&self.ssh_options_entry.text());
+        let ParsedSshOptions {
+            options: custom_options,
+            forwards,
+            environment,
+            algorithms,
+        } = Self::parse_ssh_options(&self.ssh_options_entry.text());
 
         SshConfig {
             auth_method,
@@ -7279,6 +7493,9 @@ impl ConnectionDialogData<'_> {
             compression: self.ssh_compression.is_active(),
             custom_options,
             startup_command,
+            forwards,
+            environment,
+            algorithms,
         }
     }
 
@@ -7325,6 +7542,7 @@ impl ConnectionDialogData<'_> {
             resolution,
             color_depth,
             audio_redirect: self.rdp_audio_check.is_active(),
+            microphone_redirect: self.rdp_microphone_check.is_active(),
             gateway,
             shared_folders,
             custom_args,
@@ -7375,6 +7593,14 @@ impl ConnectionDialogData<'_> {
             }
         };
 
+        let cert_fingerprint = {
+            let text = self.spice_cert_fingerprint_entry.text();
+            let text = text.trim();
+            // Store fingerprints in one format; validation reports invalid ones
+            (!text.is_empty())
+                .then(|| normalize_fingerprint(text).unwrap_or_else(|| text.to_string()))
+        };
+
         // Map dropdown index to compression mode: 0->Auto, 1->Off, 2->Glz, 3->Lz, 4->Quic
         let image_compression = match self.spice_compression_dropdown.selected() {
             1 => Some(SpiceImageCompression::Off),
@@ -7388,6 +7614,7 @@ impl ConnectionDialogData<'_> {
             tls_enabled: self.spice_tls_check.is_active(),
             ca_cert_path,
             skip_cert_verify: self.spice_skip_verify_check.is_active(),
+            cert_fingerprint,
             usb_redirection: self.spice_usb_check.is_active(),
             shared_folders: self.spice_shared_folders.borrow().clone(),
             clipboard_enabled: self.spice_clipboard_check.is_active(),
@@ -7395,22 +7622,51 @@ impl ConnectionDialogData<'_> {
         }
     }
 
-    fn parse_custom_options(text: &str) -> HashMap<String, String> {
-        let mut options = HashMap::new();
+    /// Parses "Key=Value" options, separating `LocalForward`/`RemoteForward`/
+    /// `DynamicForward` entries into port forwards and `SetEnv=NAME=value`
+    /// entries into the session environment
+    fn parse_ssh_options(text: &str) -> ParsedSshOptions {
+        let mut parsed = ParsedSshOptions::default();
         if text.trim().is_empty() {
-            return options;
+            return parsed;
         }
+        // Algorithm lists are comma-separated themselves, so parts without
+        // `=` continue the preceding `Ciphers`/`MACs`/`KexAlgorithms` list
+        let mut algorithm_key: Option<String> = None;
         for part in text.split(',') {
             let part = part.trim();
             if let Some((key, value)) = part.split_once('=') {
                 let key = key.trim().to_string();
                 let value = value.trim().to_string();
-                if !key.is_empty() {
-                    options.insert(key, value);
+                algorithm_key = None;
+                if let Some(forward) = SshForward::from_config(&key, &value) {
+                    parsed.forwards.push(forward);
+                } else if let Some(list) = parsed.algorithms.get_mut(&key) {
+                    *list = SshAlgorithmList::parse(&value);
+                    algorithm_key = Some(key);
+                } else if key.eq_ignore_ascii_case("SetEnv") {
+                    if let Some((name, value)) = value.split_once('=') {
+                        parsed
+                            .environment
+                            .push((name.trim().to_string(), value.to_string()));
+                    }
+                } else if !key.is_empty() {
+                    parsed.options.insert(key, value);
+                }
+            } else if let Some(list) = algorithm_key
+                .as_deref()
+                .and_then(|key| parsed.algorithms.get_mut(key))
+            {
+                if !part.is_empty() {
+                    list.algorithms.push(part.to_string());
                 }
             }
         }
-        options
+        // Unknown names are kept; OpenSSH versions support different algorithms
+        for warning in parsed.algorithms.warnings() {
+            tracing::warn!("{warning}");
+        }
+        parsed
     }
 
     fn parse_args(text: &str) -> Vec<String> {
@@ -16,6 +16,7 @@ use libadwaita as adw;
 use rustconn_core::export::{
     AnsibleExporter, AsbruExporter, ExportFormat, ExportOptions, ExportResult, ExportTarget,
     MobaXtermExporter, NativeExport, RemminaExporter, RoyalTsExporter, SshConfigExporter,
+    TermiusExporter,
 };
 use rustconn_core::models::{Connection, ConnectionGroup};
 use std::cell::RefCell;
@@ -198,6 +199,7 @@ impl ExportDialog {
             "RustConn Native (.rcn)",
             "Royal TS (.rtsz)",
             "MobaXterm (.mxtsessions)",
+            "Termius (.json)",
         ]);
         let format_dropdown = DropDown::new(Some(format_list), gtk4::Expression::NONE);
         format_dropdown.set_selected(0);
@@ -397,6 +399,7 @@ impl ExportDialog {
             4 => ExportFormat::Native,
             5 => ExportFormat::RoyalTs,
             6 => ExportFormat::MobaXterm,
+            7 => ExportFormat::Termius,
             _ => ExportFormat::Ansible,
         }
     }
@@ -493,6 +496,12 @@ impl ExportDialog {
                     .export(connections, groups, options)
                     .map_err(|e| e.to_string())
             }
+            ExportFormat::Termius => {
+                let exporter = TermiusExporter;
+                exporter
+                    .export(connections, groups, options)
+                    .map_err(|e| e.to_string())
+            }
         }
     }
 
@@ -582,6 +591,7 @@ impl ExportDialog {
                 4 => ExportFormat::Native,
                 5 => ExportFormat::RoyalTs,
                 6 => ExportFormat::MobaXterm,
+                7 => ExportFormat::Termius,
                 _ => ExportFormat::Ansible,
             };
 
@@ -647,6 +657,10 @@ impl ExportDialog {
                         filter.add_pattern("*.mxtsessions");
                         filter.set_name(Some("MobaXterm Sessions (*.mxtsessions)"));
                     }
+                    ExportFormat::Termius => {
+                        filter.add_pattern("*.json");
+                        filter.set_name(Some("Termius JSON (*.json)"));
+                    }
                 }
 
                 let filters = gtk4::gio::ListStore::new::<gtk4::FileFilter>();
@@ -678,6 +692,7 @@ impl ExportDialog {
                     4 => ExportFormat::Native,
                     5 => ExportFormat::RoyalTs,
                     6 => ExportFormat::MobaXterm,
+                    7 => ExportFormat::Termius,
                     _ => ExportFormat::Ansible,
                 };
 
@@ -745,6 +760,7 @@ impl ExportDialog {
                 4 => ExportFormat::Native,
                 5 => ExportFormat::RoyalTs,
                 6 => ExportFormat::MobaXterm,
+                7 => ExportFormat::Termius,
                 _ => ExportFormat::Ansible,
             };
 
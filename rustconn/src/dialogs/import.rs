@@ -7,6 +7,7 @@
 //! Updated for GTK 4.10+ compatibility using Window instead of Dialog.
 
 use adw::prelude::*;
+use gtk4::glib::thread_guard::ThreadGuard;
 use gtk4::prelude::*;
 use gtk4::{
     Box as GtkBox, Button, Frame, Label, ListBox, ListBoxRow, Orientation, ProgressBar,
@@ -15,11 +16,13 @@ use gtk4::{
 use libadwaita as adw;
 use rustconn_core::export::NativeExport;
 use rustconn_core::import::{
-    AnsibleInventoryImporter, AsbruImporter, ImportResult, ImportSource, MobaXtermImporter,
-    RdmImporter, RemminaImporter, RoyalTsImporter, SshConfigImporter,
+    AnsibleInventoryImporter, AsbruImporter, BatchImporter, ImportResult, ImportSource,
+    ImportStream, MobaXtermImporter, PuttyImporter, RdmImporter, RemminaImporter, RoyalTsImporter,
+    SshConfigImporter,
 };
-use rustconn_core::progress::LocalProgressReporter;
+use rustconn_core::progress::{CallbackProgressReporter, LocalProgressReporter};
 use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
 use std::rc::Rc;
 
 /// Import dialog for importing connections from external sources
@@ -29,6 +32,7 @@ pub struct ImportDialog {
     source_list: ListBox,
     progress_bar: ProgressBar,
     progress_label: Label,
+    cancel_button: Button,
     result_label: Label,
     result_details: Label,
     import_button: Button,
@@ -98,7 +102,8 @@ impl ImportDialog {
         stack.add_named(&source_page.0, Some("source"));
 
         // === Progress Page ===
-        let (progress_page, progress_bar, progress_label) = Self::create_progress_page();
+        let (progress_page, progress_bar, progress_label, cancel_button) =
+            Self::create_progress_page();
         stack.add_named(&progress_page, Some("progress"));
 
         // === Result Page ===
@@ -118,6 +123,7 @@ impl ImportDialog {
             source_list: source_page.1,
             progress_bar,
             progress_label,
+            cancel_button,
             result_label,
             result_details,
             import_button,
@@ -250,6 +256,18 @@ impl ImportDialog {
                 "Import from a MobaXterm session export file",
                 true,
             ),
+            (
+                "putty",
+                "PuTTY",
+                "Import from PuTTY saved sessions (~/.putty/sessions)",
+                PuttyImporter::new().is_available(),
+            ),
+            (
+                "putty_file",
+                "PuTTY Registry Export (.reg)",
+                "Import from a PuTTY sessions registry export",
+                true,
+            ),
         ];
 
         for (id, name, desc, available) in sources {
@@ -322,7 +340,7 @@ impl ImportDialog {
             .build()
     }
 
-    fn create_progress_page() -> (GtkBox, ProgressBar, Label) {
+    fn create_progress_page() -> (GtkBox, ProgressBar, Label, Button) {
         let vbox = GtkBox::new(Orientation::Vertical, 12);
         vbox.set_valign(gtk4::Align::Center);
 
@@ -345,7 +363,16 @@ impl ImportDialog {
             .build();
         vbox.append(&progress_label);
 
-        (vbox, progress_bar, progress_label)
+        // Only shown while a large file is streamed
+        let cancel_button = Button::builder()
+            .label("Cancel")
+            .halign(gtk4::Align::Center)
+            .margin_top(12)
+            .visible(false)
+            .build();
+        vbox.append(&cancel_button);
+
+        (vbox, progress_bar, progress_label, cancel_button)
     }
 
     fn create_result_page() -> (GtkBox, Label, Label) {
@@ -424,6 +451,8 @@ impl ImportDialog {
             "royalts_file" => "Royal TS",
             "rdm_file" => "Remote Desktop Manager",
             "mobaxterm_file" => "MobaXterm",
+            "putty" => "PuTTY",
+            "putty_file" => "PuTTY File",
             _ => "Unknown",
         }
     }
@@ -451,6 +480,10 @@ impl ImportDialog {
                 let importer = AnsibleInventoryImporter::new();
                 importer.import().unwrap_or_default()
             }
+            "putty" => {
+                let importer = PuttyImporter::new();
+                importer.import().unwrap_or_default()
+            }
             _ => ImportResult::default(),
         }
     }
@@ -498,6 +531,14 @@ impl ImportDialog {
             details.push('\n');
         }
 
+        if result.merged_duplicates > 0 {
+            let _ = writeln!(
+                details,
+                "Merged {} duplicate connection(s) listed in several groups.\n",
+                result.merged_duplicates
+            );
+        }
+
         // List skipped entries (Requirement 5.2)
         if !result.skipped.is_empty() {
             let _ = writeln!(details, "Skipped {} entries:", result.skipped.len());
@@ -620,6 +661,7 @@ impl ImportDialog {
         let source_list = self.source_list.clone();
         let progress_bar = self.progress_bar.clone();
         let progress_label = self.progress_label.clone();
+        let cancel_button = self.cancel_button.clone();
         let result_label = self.result_label.clone();
         let result_details = self.result_details.clone();
         let import_button = self.import_button.clone();
@@ -669,6 +711,7 @@ impl ImportDialog {
                         &stack,
                         &progress_bar,
                         &progress_label,
+                        &cancel_button,
                         &result_label,
                         &result_details,
                         &result_cell,
@@ -699,6 +742,7 @@ impl ImportDialog {
                         &stack,
                         &progress_bar,
                         &progress_label,
+                        &cancel_button,
                         &result_label,
                         &result_details,
                         &result_cell,
@@ -768,6 +812,21 @@ impl ImportDialog {
                     return;
                 }
 
+                if source_id == "putty_file" {
+                    Self::handle_putty_file_import(
+                        &window,
+                        &stack,
+                        &progress_bar,
+                        &progress_label,
+                        &result_label,
+                        &result_details,
+                        &result_cell,
+                        &source_name_cell,
+                        btn,
+                    );
+                    return;
+                }
+
                 // Perform import with progress reporting (Requirements 3.1, 3.6)
                 let result = Self::do_import_with_progress(
                     &source_id,
@@ -834,6 +893,7 @@ impl ImportDialog {
         stack: &Stack,
         progress_bar: &ProgressBar,
         progress_label: &Label,
+        cancel_button: &Button,
         result_label: &Label,
         result_details: &Label,
         result_cell: &Rc<RefCell<Option<ImportResult>>>,
@@ -859,6 +919,7 @@ impl ImportDialog {
         let stack_clone = stack.clone();
         let progress_bar_clone = progress_bar.clone();
         let progress_label_clone = progress_label.clone();
+        let cancel_button_clone = cancel_button.clone();
         let result_label_clone = result_label.clone();
         let result_details_clone = result_details.clone();
         let result_cell_clone = result_cell.clone();
@@ -879,7 +940,26 @@ impl ImportDialog {
 
                         // Parse SSH config file using import_from_path (Requirement 1.2, 1.3)
                         let importer = SshConfigImporter::new();
-                        let result = importer.import_from_path(&path).unwrap_or_default();
+                        // Large files are parsed incrementally
+                        let result = if BatchImporter::should_stream(&path) {
+                            match importer.stream_from_path(&path) {
+                                Ok(stream) => Self::stream_with_progress(
+                                    stream,
+                                    &progress_bar_clone,
+                                    &progress_label_clone,
+                                    &cancel_button_clone,
+                                ),
+                                Err(_) => Some(ImportResult::default()),
+                            }
+                        } else {
+                            Some(importer.import_from_path(&path).unwrap_or_default())
+                        };
+                        let Some(result) = result else {
+                            // Import cancelled - return to source page
+                            stack_clone.set_visible_child_name("source");
+                            btn_clone.set_sensitive(true);
+                            return;
+                        };
 
                         // Extract filename for display
                         let filename = path
@@ -950,6 +1030,7 @@ impl ImportDialog {
         let result_cell_clone = result_cell.clone();
         let source_name_cell_clone = source_name_cell.clone();
         let btn_clone = btn.clone();
+        let window_clone = window.clone();
 
         file_dialog.open(
             Some(window),
@@ -957,38 +1038,47 @@ impl ImportDialog {
             move |file_result| {
                 if let Ok(file) = file_result {
                     if let Some(path) = file.path() {
-                        stack_clone.set_visible_child_name("progress");
-                        btn_clone.set_sensitive(false);
-                        progress_bar_clone.set_fraction(0.5);
-                        progress_label_clone
-                            .set_text(&format!("Importing from {}...", path.display()));
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            false,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                let importer = AsbruImporter::new();
+                                let result = importer.import_from_path(&path).unwrap_or_default();
 
-                        let importer = AsbruImporter::new();
-                        let result = importer.import_from_path(&path).unwrap_or_default();
-
-                        // Extract filename for display
-                        let filename = path
-                            .file_name().map_or_else(|| "Asbru-CM File".to_string(), |n| n.to_string_lossy().to_string());
+                                // Extract filename for display
+                                let filename = path
+                                    .file_name().map_or_else(|| "Asbru-CM File".to_string(), |n| n.to_string_lossy().to_string());
 
-                        source_name_cell_clone.borrow_mut().clone_from(&filename);
+                                source_name_cell_clone.borrow_mut().clone_from(&filename);
 
-                        progress_bar_clone.set_fraction(1.0);
+                                progress_bar_clone.set_fraction(1.0);
 
-                        // Show results using format_import_details() (Requirements 5.2, 5.3)
-                        let conn_count = result.connections.len();
-                        let group_count = result.groups.len();
-                        let summary = format!(
-                            "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
-                        );
-                        result_label_clone.set_text(&summary);
+                                // Show results using format_import_details() (Requirements 5.2, 5.3)
+                                let conn_count = result.connections.len();
+                                let group_count = result.groups.len();
+                                let summary = format!(
+                                    "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
+                                );
+                                result_label_clone.set_text(&summary);
 
-                        let details = Self::format_import_details(&result);
-                        result_details_clone.set_text(&details);
+                                let details = Self::format_import_details(&result);
+                                result_details_clone.set_text(&details);
 
-                        *result_cell_clone.borrow_mut() = Some(result);
-                        stack_clone.set_visible_child_name("result");
-                        btn_clone.set_label("Done");
-                        btn_clone.set_sensitive(true);
+                                *result_cell_clone.borrow_mut() = Some(result);
+                                stack_clone.set_visible_child_name("result");
+                                btn_clone.set_label("Done");
+                                btn_clone.set_sensitive(true);
+                            },
+                        );
                     }
                 } else {
                     // User cancelled file selection - return to source page
@@ -1006,6 +1096,7 @@ impl ImportDialog {
         stack: &Stack,
         progress_bar: &ProgressBar,
         progress_label: &Label,
+        cancel_button: &Button,
         result_label: &Label,
         result_details: &Label,
         result_cell: &Rc<RefCell<Option<ImportResult>>>,
@@ -1034,11 +1125,13 @@ impl ImportDialog {
         let stack_clone = stack.clone();
         let progress_bar_clone = progress_bar.clone();
         let progress_label_clone = progress_label.clone();
+        let cancel_button_clone = cancel_button.clone();
         let result_label_clone = result_label.clone();
         let result_details_clone = result_details.clone();
         let result_cell_clone = result_cell.clone();
         let source_name_cell_clone = source_name_cell.clone();
         let btn_clone = btn.clone();
+        let window_clone = window.clone();
 
         file_dialog.open(
             Some(window),
@@ -1046,38 +1139,68 @@ impl ImportDialog {
             move |file_result| {
                 if let Ok(file) = file_result {
                     if let Some(path) = file.path() {
-                        stack_clone.set_visible_child_name("progress");
-                        btn_clone.set_sensitive(false);
-                        progress_bar_clone.set_fraction(0.5);
-                        progress_label_clone
-                            .set_text(&format!("Importing from {}...", path.display()));
-
-                        let importer = AnsibleInventoryImporter::new();
-                        let result = importer.import_from_path(&path).unwrap_or_default();
+                        // Large INI files are parsed incrementally
+                        let streamable = BatchImporter::should_stream(&path)
+                            && AnsibleInventoryImporter::is_streamable(&path).unwrap_or(false);
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            streamable,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                let importer = AnsibleInventoryImporter::new();
+                                let result = if streamable {
+                                    match importer.stream_from_path(&path) {
+                                        Ok(stream) => Self::stream_with_progress(
+                                            stream,
+                                            &progress_bar_clone,
+                                            &progress_label_clone,
+                                            &cancel_button_clone,
+                                        ),
+                                        Err(_) => Some(ImportResult::default()),
+                                    }
+                                } else {
+                                    Some(importer.import_from_path(&path).unwrap_or_default())
+                                };
+                                let Some(result) = result else {
+                                    // Import cancelled - return to source page
+                                    stack_clone.set_visible_child_name("source");
+                                    btn_clone.set_sensitive(true);
+                                    return;
+                                };
 
-                        // Extract filename for display
-                        let filename = path
-                            .file_name().map_or_else(|| "Ansible Inventory".to_string(), |n| n.to_string_lossy().to_string());
+                                // Extract filename for display
+                                let filename = path
+                                    .file_name().map_or_else(|| "Ansible Inventory".to_string(), |n| n.to_string_lossy().to_string());
 
-                        source_name_cell_clone.borrow_mut().clone_from(&filename);
+                                source_name_cell_clone.borrow_mut().clone_from(&filename);
 
-                        progress_bar_clone.set_fraction(1.0);
+                                progress_bar_clone.set_fraction(1.0);
 
-                        // Show results using format_import_details() (Requirements 5.2, 5.3)
-                        let conn_count = result.connections.len();
-                        let group_count = result.groups.len();
-                        let summary = format!(
-                            "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
-                        );
-                        result_label_clone.set_text(&summary);
+                                // Show results using format_import_details() (Requirements 5.2, 5.3)
+                                let conn_count = result.connections.len();
+                                let group_count = result.groups.len();
+                                let summary = format!(
+                                    "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
+                                );
+                                result_label_clone.set_text(&summary);
 
-                        let details = Self::format_import_details(&result);
-                        result_details_clone.set_text(&details);
+                                let details = Self::format_import_details(&result);
+                                result_details_clone.set_text(&details);
 
-                        *result_cell_clone.borrow_mut() = Some(result);
-                        stack_clone.set_visible_child_name("result");
-                        btn_clone.set_label("Done");
-                        btn_clone.set_sensitive(true);
+                                *result_cell_clone.borrow_mut() = Some(result);
+                                stack_clone.set_visible_child_name("result");
+                                btn_clone.set_label("Done");
+                                btn_clone.set_sensitive(true);
+                            },
+                        );
                     }
                 } else {
                     // User cancelled file selection - return to source page
@@ -1088,6 +1211,109 @@ impl ImportDialog {
         );
     }
 
+    /// Asks before importing a file above the streaming threshold that is
+    /// read into memory whole
+    ///
+    /// Calls `import` right away for smaller files and for files that are
+    /// `streamable`; otherwise only if the user continues, and returns to
+    /// the source page if they do not.
+    fn confirm_large_import(
+        window: &adw::Window,
+        stack: Stack,
+        btn: Button,
+        path: PathBuf,
+        streamable: bool,
+        import: impl FnOnce(PathBuf) + 'static,
+    ) {
+        if streamable || !BatchImporter::should_stream(&path) {
+            import(path);
+            return;
+        }
+
+        let size_mib = std::fs::metadata(&path).map_or(0, |meta| meta.len() / (1024 * 1024));
+        let body = format!(
+            "{} is {size_mib} MiB and will be read into memory as a whole, \
+             which may take a while and use a lot of memory.",
+            path.display()
+        );
+        let pending = Cell::new(Some((path, import)));
+        crate::alert::show_confirm(
+            window,
+            "Large Import File",
+            &body,
+            "Import",
+            false,
+            move |confirmed| {
+                let Some((path, import)) = pending.take() else {
+                    return;
+                };
+                if confirmed {
+                    import(path);
+                } else {
+                    stack.set_visible_child_name("source");
+                    btn.set_sensitive(true);
+                }
+            },
+        );
+    }
+
+    /// Streams a large file into an import result, updating the progress
+    /// page after every batch
+    ///
+    /// The Cancel button on the progress page is shown while streaming and
+    /// stops the import after the current batch. Returns `None` if the
+    /// import was cancelled.
+    fn stream_with_progress<S: ImportStream>(
+        stream: S,
+        progress_bar: &ProgressBar,
+        progress_label: &Label,
+        cancel_button: &Button,
+    ) -> Option<ImportResult> {
+        let importer = BatchImporter::with_default_batch_size();
+        let cancel = importer.cancel_handle();
+        let handler = cancel_button.connect_clicked(move |button| {
+            button.set_sensitive(false);
+            cancel.cancel();
+        });
+        cancel_button.set_sensitive(true);
+        cancel_button.set_visible(true);
+
+        // Progress is reported on the main thread that owns the widgets
+        let widgets = ThreadGuard::new((progress_bar.clone(), progress_label.clone()));
+        let reporter = CallbackProgressReporter::new(move |current, total, message| {
+            let (bar, label) = widgets.get_ref();
+            // Cast is safe: byte offsets are small enough that f64 precision is sufficient
+            #[allow(clippy::cast_precision_loss)]
+            let fraction = if total > 0 {
+                current as f64 / total as f64
+            } else {
+                0.0
+            };
+            bar.set_fraction(fraction);
+            label.set_text(message);
+
+            // Process pending GTK events so the Cancel button stays responsive
+            while gtk4::glib::MainContext::default().iteration(false) {}
+        });
+
+        let mut connections = Vec::new();
+        let streamed = importer.process_stream(stream, Some(&reporter), |batch| {
+            connections.extend(batch);
+        });
+
+        cancel_button.disconnect(handler);
+        cancel_button.set_visible(false);
+        if streamed.was_cancelled {
+            return None;
+        }
+
+        let mut result = streamed.result;
+        result.apply_group_memberships(&mut connections);
+        result.group_memberships.clear();
+        result.connections = connections;
+        Some(result)
+    }
+
     /// Returns a reference to the underlying window
     #[must_use]
     pub const fn window(&self) -> &adw::Window {
@@ -1221,6 +1447,15 @@ impl ImportDialog {
 
                 importer.import().unwrap_or_default()
             }
+            "putty" => {
+                let importer = PuttyImporter::new();
+                reporter.report(0, 1, "Importing PuTTY sessions...");
+                if reporter.is_cancelled() {
+                    return ImportResult::default();
+                }
+
+                importer.import().unwrap_or_default()
+            }
             _ => ImportResult::default(),
         };
 
@@ -1270,6 +1505,7 @@ impl ImportDialog {
         let result_cell_clone = result_cell.clone();
         let source_name_cell_clone = source_name_cell.clone();
         let btn_clone = btn.clone();
+        let window_clone = window.clone();
 
         file_dialog.open(
             Some(window),
@@ -1277,26 +1513,138 @@ impl ImportDialog {
             move |file_result| {
                 if let Ok(file) = file_result {
                     if let Some(path) = file.path() {
-                        stack_clone.set_visible_child_name("progress");
-                        btn_clone.set_sensitive(false);
-                        progress_bar_clone.set_fraction(0.5);
-                        progress_label_clone
-                            .set_text(&format!("Importing from {}...", path.display()));
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            false,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                // Parse native file
+                                match NativeExport::from_file(&path) {
+                                    Ok(native_export) => {
+                                        // Convert NativeExport to ImportResult
+                                        let result = ImportResult {
+                                            connections: native_export.connections,
+                                            groups: native_export.groups,
+                                            ..ImportResult::default()
+                                        };
+
+                                        // Extract filename for display
+                                        let filename = path.file_name().map_or_else(
+                                            || "RustConn Native".to_string(),
+                                            |n| n.to_string_lossy().to_string(),
+                                        );
+
+                                        source_name_cell_clone.borrow_mut().clone_from(&filename);
+
+                                        progress_bar_clone.set_fraction(1.0);
+
+                                        // Show results
+                                        let conn_count = result.connections.len();
+                                        let group_count = result.groups.len();
+                                        let summary = format!(
+                                            "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
+                                        );
+                                        result_label_clone.set_text(&summary);
+
+                                        let details = Self::format_import_details(&result);
+                                        result_details_clone.set_text(&details);
+
+                                        *result_cell_clone.borrow_mut() = Some(result);
+                                        stack_clone.set_visible_child_name("result");
+                                        btn_clone.set_label("Done");
+                                        btn_clone.set_sensitive(true);
+                                    }
+                                    Err(e) => {
+                                        // Show error
+                                        progress_bar_clone.set_fraction(1.0);
+                                        result_label_clone.set_text("Import Failed");
+                                        result_details_clone.set_text(&format!("Error: {e}"));
+
+                                        stack_clone.set_visible_child_name("result");
+                                        btn_clone.set_label("Close");
+                                        btn_clone.set_sensitive(true);
+                                    }
+                                }
+                            },
+                        );
+                    }
+                } else {
+                    // User cancelled file selection - return to source page
+                    stack_clone.set_visible_child_name("source");
+                    btn_clone.set_sensitive(true);
+                }
+            },
+        );
+    }
 
-                        // Parse native file
-                        match NativeExport::from_file(&path) {
-                            Ok(native_export) => {
-                                // Convert NativeExport to ImportResult
-                                let result = ImportResult {
-                                    connections: native_export.connections,
-                                    groups: native_export.groups,
-                                    skipped: Vec::new(),
-                                    errors: Vec::new(),
-                                };
+    /// Handles the special case of importing from a Royal TS file (.rtsz)
+    #[allow(clippy::too_many_arguments)]
+    fn handle_royalts_file_import(
+        window: &adw::Window,
+        stack: &Stack,
+        progress_bar: &ProgressBar,
+        progress_label: &Label,
+        result_label: &Label,
+        result_details: &Label,
+        result_cell: &Rc<RefCell<Option<ImportResult>>>,
+        source_name_cell: &Rc<RefCell<String>>,
+        btn: &Button,
+    ) {
+        let file_dialog = gtk4::FileDialog::builder()
+            .title("Select Royal TS File")
+            .modal(true)
+            .build();
+
+        let filter = gtk4::FileFilter::new();
+        filter.add_pattern("*.rtsz");
+        filter.add_pattern("*.json");
+        filter.set_name(Some("Royal TS files (*.rtsz, *.json)"));
+        let filters = gtk4::gio::ListStore::new::<gtk4::FileFilter>();
+        filters.append(&filter);
+        file_dialog.set_filters(Some(&filters));
+
+        let stack_clone = stack.clone();
+        let progress_bar_clone = progress_bar.clone();
+        let progress_label_clone = progress_label.clone();
+        let result_label_clone = result_label.clone();
+        let result_details_clone = result_details.clone();
+        let result_cell_clone = result_cell.clone();
+        let source_name_cell_clone = source_name_cell.clone();
+        let btn_clone = btn.clone();
+        let window_clone = window.clone();
+
+        file_dialog.open(
+            Some(window),
+            gtk4::gio::Cancellable::NONE,
+            move |file_result| {
+                if let Ok(file) = file_result {
+                    if let Some(path) = file.path() {
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            false,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                let importer = RoyalTsImporter::new();
+                                let result = importer.import_from_path(&path).unwrap_or_default();
 
-                                // Extract filename for display
                                 let filename = path.file_name().map_or_else(
-                                    || "RustConn Native".to_string(),
+                                    || "Royal TS".to_string(),
                                     |n| n.to_string_lossy().to_string(),
                                 );
 
@@ -1304,7 +1652,6 @@ impl ImportDialog {
 
                                 progress_bar_clone.set_fraction(1.0);
 
-                                // Show results
                                 let conn_count = result.connections.len();
                                 let group_count = result.groups.len();
                                 let summary = format!(
@@ -1319,18 +1666,8 @@ impl ImportDialog {
                                 stack_clone.set_visible_child_name("result");
                                 btn_clone.set_label("Done");
                                 btn_clone.set_sensitive(true);
-                            }
-                            Err(e) => {
-                                // Show error
-                                progress_bar_clone.set_fraction(1.0);
-                                result_label_clone.set_text("Import Failed");
-                                result_details_clone.set_text(&format!("Error: {e}"));
-
-                                stack_clone.set_visible_child_name("result");
-                                btn_clone.set_label("Close");
-                                btn_clone.set_sensitive(true);
-                            }
-                        }
+                            },
+                        );
                     }
                 } else {
                     // User cancelled file selection - return to source page
@@ -1341,9 +1678,9 @@ impl ImportDialog {
         );
     }
 
-    /// Handles the special case of importing from a Royal TS file (.rtsz)
+    /// Handles the special case of importing from a Remote Desktop Manager JSON file
     #[allow(clippy::too_many_arguments)]
-    fn handle_royalts_file_import(
+    fn handle_rdm_file_import(
         window: &adw::Window,
         stack: &Stack,
         progress_bar: &ProgressBar,
@@ -1355,14 +1692,13 @@ impl ImportDialog {
         btn: &Button,
     ) {
         let file_dialog = gtk4::FileDialog::builder()
-            .title("Select Royal TS File")
+            .title("Select RDM JSON File")
             .modal(true)
             .build();
 
         let filter = gtk4::FileFilter::new();
-        filter.add_pattern("*.rtsz");
         filter.add_pattern("*.json");
-        filter.set_name(Some("Royal TS files (*.rtsz, *.json)"));
+        filter.set_name(Some("JSON files"));
         let filters = gtk4::gio::ListStore::new::<gtk4::FileFilter>();
         filters.append(&filter);
         file_dialog.set_filters(Some(&filters));
@@ -1375,6 +1711,7 @@ impl ImportDialog {
         let result_cell_clone = result_cell.clone();
         let source_name_cell_clone = source_name_cell.clone();
         let btn_clone = btn.clone();
+        let window_clone = window.clone();
 
         file_dialog.open(
             Some(window),
@@ -1382,38 +1719,49 @@ impl ImportDialog {
             move |file_result| {
                 if let Ok(file) = file_result {
                     if let Some(path) = file.path() {
-                        stack_clone.set_visible_child_name("progress");
-                        btn_clone.set_sensitive(false);
-                        progress_bar_clone.set_fraction(0.5);
-                        progress_label_clone
-                            .set_text(&format!("Importing from {}...", path.display()));
-
-                        let importer = RoyalTsImporter::new();
-                        let result = importer.import_from_path(&path).unwrap_or_default();
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            false,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                let importer = RdmImporter::new();
+                                let result = importer.import_from_path(&path).unwrap_or_default();
 
-                        let filename = path.file_name().map_or_else(
-                            || "Royal TS".to_string(),
-                            |n| n.to_string_lossy().to_string(),
-                        );
+                                // Extract filename for display
+                                let filename = path.file_name().map_or_else(
+                                    || "RDM JSON".to_string(),
+                                    |n| n.to_string_lossy().to_string(),
+                                );
 
-                        source_name_cell_clone.borrow_mut().clone_from(&filename);
+                                source_name_cell_clone.borrow_mut().clone_from(&filename);
 
-                        progress_bar_clone.set_fraction(1.0);
+                                progress_bar_clone.set_fraction(1.0);
 
-                        let conn_count = result.connections.len();
-                        let group_count = result.groups.len();
-                        let summary = format!(
-                            "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
-                        );
-                        result_label_clone.set_text(&summary);
+                                // Show results
+                                let conn_count = result.connections.len();
+                                let group_count = result.groups.len();
+                                let summary = format!(
+                                    "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
+                                );
+                                result_label_clone.set_text(&summary);
 
-                        let details = Self::format_import_details(&result);
-                        result_details_clone.set_text(&details);
+                                let details = Self::format_import_details(&result);
+                                result_details_clone.set_text(&details);
 
-                        *result_cell_clone.borrow_mut() = Some(result);
-                        stack_clone.set_visible_child_name("result");
-                        btn_clone.set_label("Done");
-                        btn_clone.set_sensitive(true);
+                                *result_cell_clone.borrow_mut() = Some(result);
+                                stack_clone.set_visible_child_name("result");
+                                btn_clone.set_label("Done");
+                                btn_clone.set_sensitive(true);
+                            },
+                        );
                     }
                 } else {
                     // User cancelled file selection - return to source page
@@ -1424,9 +1772,9 @@ impl ImportDialog {
         );
     }
 
-    /// Handles the special case of importing from a Remote Desktop Manager JSON file
+    /// Handles the special case of importing from a MobaXterm session file
     #[allow(clippy::too_many_arguments)]
-    fn handle_rdm_file_import(
+    fn handle_mobaxterm_file_import(
         window: &adw::Window,
         stack: &Stack,
         progress_bar: &ProgressBar,
@@ -1438,13 +1786,13 @@ impl ImportDialog {
         btn: &Button,
     ) {
         let file_dialog = gtk4::FileDialog::builder()
-            .title("Select RDM JSON File")
+            .title("Select MobaXterm Session File")
             .modal(true)
             .build();
 
         let filter = gtk4::FileFilter::new();
-        filter.add_pattern("*.json");
-        filter.set_name(Some("JSON files"));
+        filter.add_pattern("*.mxtsessions");
+        filter.set_name(Some("MobaXterm Sessions (*.mxtsessions)"));
         let filters = gtk4::gio::ListStore::new::<gtk4::FileFilter>();
         filters.append(&filter);
         file_dialog.set_filters(Some(&filters));
@@ -1457,6 +1805,7 @@ impl ImportDialog {
         let result_cell_clone = result_cell.clone();
         let source_name_cell_clone = source_name_cell.clone();
         let btn_clone = btn.clone();
+        let window_clone = window.clone();
 
         file_dialog.open(
             Some(window),
@@ -1464,40 +1813,49 @@ impl ImportDialog {
             move |file_result| {
                 if let Ok(file) = file_result {
                     if let Some(path) = file.path() {
-                        stack_clone.set_visible_child_name("progress");
-                        btn_clone.set_sensitive(false);
-                        progress_bar_clone.set_fraction(0.5);
-                        progress_label_clone
-                            .set_text(&format!("Importing from {}...", path.display()));
-
-                        let importer = RdmImporter::new();
-                        let result = importer.import_from_path(&path).unwrap_or_default();
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            false,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                let importer = MobaXtermImporter::with_path(path.clone());
+                                let result = importer.import_from_path(&path).unwrap_or_default();
 
-                        // Extract filename for display
-                        let filename = path.file_name().map_or_else(
-                            || "RDM JSON".to_string(),
-                            |n| n.to_string_lossy().to_string(),
-                        );
+                                // Extract filename for display
+                                let filename = path.file_name().map_or_else(
+                                    || "MobaXterm".to_string(),
+                                    |n| n.to_string_lossy().to_string(),
+                                );
 
-                        source_name_cell_clone.borrow_mut().clone_from(&filename);
+                                source_name_cell_clone.borrow_mut().clone_from(&filename);
 
-                        progress_bar_clone.set_fraction(1.0);
+                                progress_bar_clone.set_fraction(1.0);
 
-                        // Show results
-                        let conn_count = result.connections.len();
-                        let group_count = result.groups.len();
-                        let summary = format!(
-                            "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
-                        );
-                        result_label_clone.set_text(&summary);
+                                // Show results
+                                let conn_count = result.connections.len();
+                                let group_count = result.groups.len();
+                                let summary = format!(
+                                    "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
+                                );
+                                result_label_clone.set_text(&summary);
 
-                        let details = Self::format_import_details(&result);
-                        result_details_clone.set_text(&details);
+                                let details = Self::format_import_details(&result);
+                                result_details_clone.set_text(&details);
 
-                        *result_cell_clone.borrow_mut() = Some(result);
-                        stack_clone.set_visible_child_name("result");
-                        btn_clone.set_label("Done");
-                        btn_clone.set_sensitive(true);
+                                *result_cell_clone.borrow_mut() = Some(result);
+                                stack_clone.set_visible_child_name("result");
+                                btn_clone.set_label("Done");
+                                btn_clone.set_sensitive(true);
+                            },
+                        );
                     }
                 } else {
                     // User cancelled file selection - return to source page
@@ -1508,9 +1866,9 @@ impl ImportDialog {
         );
     }
 
-    /// Handles the special case of importing from a MobaXterm session file
+    /// Handles the special case of importing from a PuTTY registry export
     #[allow(clippy::too_many_arguments)]
-    fn handle_mobaxterm_file_import(
+    fn handle_putty_file_import(
         window: &adw::Window,
         stack: &Stack,
         progress_bar: &ProgressBar,
@@ -1522,13 +1880,13 @@ impl ImportDialog {
         btn: &Button,
     ) {
         let file_dialog = gtk4::FileDialog::builder()
-            .title("Select MobaXterm Session File")
+            .title("Select PuTTY Registry Export")
             .modal(true)
             .build();
 
         let filter = gtk4::FileFilter::new();
-        filter.add_pattern("*.mxtsessions");
-        filter.set_name(Some("MobaXterm Sessions (*.mxtsessions)"));
+        filter.add_pattern("*.reg");
+        filter.set_name(Some("Registry Export (*.reg)"));
         let filters = gtk4::gio::ListStore::new::<gtk4::FileFilter>();
         filters.append(&filter);
         file_dialog.set_filters(Some(&filters));
@@ -1541,6 +1899,7 @@ impl ImportDialog {
         let result_cell_clone = result_cell.clone();
         let source_name_cell_clone = source_name_cell.clone();
         let btn_clone = btn.clone();
+        let window_clone = window.clone();
 
         file_dialog.open(
             Some(window),
@@ -1548,40 +1907,49 @@ impl ImportDialog {
             move |file_result| {
                 if let Ok(file) = file_result {
                     if let Some(path) = file.path() {
-                        stack_clone.set_visible_child_name("progress");
-                        btn_clone.set_sensitive(false);
-                        progress_bar_clone.set_fraction(0.5);
-                        progress_label_clone
-                            .set_text(&format!("Importing from {}...", path.display()));
-
-                        let importer = MobaXtermImporter::with_path(path.clone());
-                        let result = importer.import_from_path(&path).unwrap_or_default();
+                        Self::confirm_large_import(
+                            &window_clone,
+                            stack_clone.clone(),
+                            btn_clone.clone(),
+                            path,
+                            false,
+                            move |path| {
+                                stack_clone.set_visible_child_name("progress");
+                                btn_clone.set_sensitive(false);
+                                progress_bar_clone.set_fraction(0.5);
+                                progress_label_clone
+                                    .set_text(&format!("Importing from {}...", path.display()));
+
+                                let importer = PuttyImporter::with_path(path.clone());
+                                let result = importer.import_from_path(&path).unwrap_or_default();
 
-                        // Extract filename for display
-                        let filename = path.file_name().map_or_else(
-                            || "MobaXterm".to_string(),
-                            |n| n.to_string_lossy().to_string(),
-                        );
+                                // Extract filename for display
+                                let filename = path.file_name().map_or_else(
+                                    || "PuTTY".to_string(),
+                                    |n| n.to_string_lossy().to_string(),
+                                );
 
-                        source_name_cell_clone.borrow_mut().clone_from(&filename);
+                                source_name_cell_clone.borrow_mut().clone_from(&filename);
 
-                        progress_bar_clone.set_fraction(1.0);
+                                progress_bar_clone.set_fraction(1.0);
 
-                        // Show results
-                        let conn_count = result.connections.len();
-                        let group_count = result.groups.len();
-                        let summary = format!(
-                            "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
-                        );
-                        result_label_clone.set_text(&summary);
+                                // Show results
+                                let conn_count = result.connections.len();
+                                let group_count = result.groups.len();
+                                let summary = format!(
+                                    "Successfully imported {conn_count} connection(s) and {group_count} group(s).\nConnections will be added to '{filename} Import' group."
+                                );
+                                result_label_clone.set_text(&summary);
 
-                        let details = Self::format_import_details(&result);
-                        result_details_clone.set_text(&details);
+                                let details = Self::format_import_details(&result);
+                                result_details_clone.set_text(&details);
 
-                        *result_cell_clone.borrow_mut() = Some(result);
-                        stack_clone.set_visible_child_name("result");
-                        btn_clone.set_label("Done");
-                        btn_clone.set_sensitive(true);
+                                *result_cell_clone.borrow_mut() = Some(result);
+                                stack_clone.set_visible_child_name("result");
+                                btn_clone.set_label("Done");
+                                btn_clone.set_sensitive(true);
+                            },
+                        );
                     }
                 } else {
                     // User cancelled file selection - return to source page
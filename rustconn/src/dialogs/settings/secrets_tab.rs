@@ -60,7 +60,7 @@ pub fn create_secrets_page() -> SecretsPageWidgets {
         .build();
 
     // Simplified: KeePassXC, libsecret, Bitwarden
-    let backend_strings = StringList::new(&["KeePassXC", "libsecret", "Bitwarden"]);
+    let backend_strings = StringList::new(&["KeePassXC", "libsecret", "Bitwarden", "pass"]);
     let secret_backend_dropdown = DropDown::builder()
         .model(&backend_strings)
         .selected(0)
@@ -768,11 +768,12 @@ fn update_status_label(label: &Label, text: &str, css_class: &str) {
 /// Loads secret settings into UI controls
 #[allow(clippy::too_many_arguments)]
 pub fn load_secret_settings(widgets: &SecretsPageWidgets, settings: &SecretSettings) {
-    // Indices: 0=KeePassXC, 1=libsecret, 2=Bitwarden
+    // Indices: 0=KeePassXC, 1=libsecret, 2=Bitwarden, 3=pass
     let backend_index = match settings.preferred_backend {
         SecretBackendType::KeePassXc | SecretBackendType::KdbxFile => 0,
         SecretBackendType::LibSecret => 1,
         SecretBackendType::Bitwarden => 2,
+        SecretBackendType::Pass => 3,
     };
     widgets.secret_backend_dropdown.set_selected(backend_index);
     widgets.enable_fallback.set_active(settings.enable_fallback);
@@ -859,11 +860,12 @@ pub fn collect_secret_settings(
     widgets: &SecretsPageWidgets,
     settings: &Rc<RefCell<rustconn_core::config::AppSettings>>,
 ) -> SecretSettings {
-    // Indices: 0=KeePassXC, 1=libsecret, 2=Bitwarden
+    // Indices: 0=KeePassXC, 1=libsecret, 2=Bitwarden, 3=pass
     let preferred_backend = match widgets.secret_backend_dropdown.selected() {
         0 => SecretBackendType::KeePassXc,
         1 => SecretBackendType::LibSecret,
         2 => SecretBackendType::Bitwarden,
+        3 => SecretBackendType::Pass,
         _ => SecretBackendType::default(),
     };
 
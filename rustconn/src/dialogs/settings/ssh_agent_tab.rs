@@ -7,7 +7,7 @@ use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::{Box as GtkBox, Button, Label, ListBox, Orientation, Spinner};
 use libadwaita as adw;
-use rustconn_core::ssh_agent::SshAgentManager;
+use rustconn_core::ssh_agent::{AddKeyOptions, SshAgentManager};
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
@@ -175,81 +175,81 @@ pub fn load_ssh_agent_settings(
     let keys_list = ssh_agent_keys_list.clone();
     let manager = ssh_agent_manager.clone();
 
-    // Run status check asynchronously
-    glib::spawn_future_local(async move {
-        // Get status in background
-        let status_result = {
-            let mgr = manager.borrow();
-            let socket = mgr.socket_path().map(String::from);
-            let status = mgr.get_status();
+    // Run `ssh-add -l` off the GTK main loop so a slow agent does not freeze the dialog
+    let agent = manager.borrow().clone();
+    crate::utils::spawn_blocking_with_callback(
+        move || {
+            let socket = agent.socket_path().map(String::from);
+            let status = crate::utils::shared_runtime().and_then(|rt| {
+                rt.block_on(agent.get_status_async())
+                    .map_err(|e| e.to_string())
+            });
             (socket, status)
-        };
-
-        // Update UI on main thread
-        let (socket, status) = status_result;
-
-        if let Some(socket_path) = socket {
-            socket_label.set_text(&socket_path);
-        } else {
-            socket_label.set_text("Not available");
-        }
-
-        // Clear loading row
-        while let Some(child) = keys_list.first_child() {
-            keys_list.remove(&child);
-        }
-
-        if let Ok(agent_status) = status {
-            let status_text = if agent_status.running {
-                "Running"
+        },
+        move |(socket, status)| {
+            if let Some(socket_path) = socket {
+                socket_label.set_text(&socket_path);
             } else {
-                "Not running"
-            };
-            status_label.set_text(status_text);
-            status_label.remove_css_class("error");
-            status_label.remove_css_class("dim-label");
+                socket_label.set_text("Not available");
+            }
 
-            if agent_status.running {
-                status_label.add_css_class("success");
+            // Clear loading row
+            while let Some(child) = keys_list.first_child() {
+                keys_list.remove(&child);
+            }
 
-                if agent_status.keys.is_empty() {
+            if let Ok(agent_status) = status {
+                let status_text = if agent_status.running {
+                    "Running"
+                } else {
+                    "Not running"
+                };
+                status_label.set_text(status_text);
+                status_label.remove_css_class("error");
+                status_label.remove_css_class("dim-label");
+
+                if agent_status.running {
+                    status_label.add_css_class("success");
+
+                    if agent_status.keys.is_empty() {
+                        let empty_row = adw::ActionRow::builder()
+                            .title("No keys loaded")
+                            .subtitle("Add keys using ssh-add or the button above")
+                            .build();
+                        keys_list.append(&empty_row);
+                    } else {
+                        for key in &agent_status.keys {
+                            let key_row = create_loaded_key_row(
+                                key,
+                                &manager,
+                                &keys_list,
+                                &status_label,
+                                &socket_label,
+                            );
+                            keys_list.append(&key_row);
+                        }
+                    }
+                } else {
+                    status_label.add_css_class("dim-label");
                     let empty_row = adw::ActionRow::builder()
-                        .title("No keys loaded")
-                        .subtitle("Add keys using ssh-add or the button above")
+                        .title("Agent not running")
+                        .subtitle("Start the agent to manage keys")
                         .build();
                     keys_list.append(&empty_row);
-                } else {
-                    for key in &agent_status.keys {
-                        let key_row = create_loaded_key_row(
-                            key,
-                            &manager,
-                            &keys_list,
-                            &status_label,
-                            &socket_label,
-                        );
-                        keys_list.append(&key_row);
-                    }
                 }
             } else {
-                status_label.add_css_class("dim-label");
+                status_label.set_text("Error");
+                status_label.remove_css_class("dim-label");
+                status_label.add_css_class("error");
+
                 let empty_row = adw::ActionRow::builder()
                     .title("Agent not running")
                     .subtitle("Start the agent to manage keys")
                     .build();
                 keys_list.append(&empty_row);
             }
-        } else {
-            status_label.set_text("Error");
-            status_label.remove_css_class("dim-label");
-            status_label.add_css_class("error");
-
-            let empty_row = adw::ActionRow::builder()
-                .title("Agent not running")
-                .subtitle("Start the agent to manage keys")
-                .build();
-            keys_list.append(&empty_row);
-        }
-    });
+        },
+    );
 }
 
 /// Populates the available keys list with load buttons asynchronously
@@ -455,7 +455,7 @@ fn add_key_with_passphrase_dialog(
         };
 
         let manager = manager_clone.borrow();
-        match manager.add_key(&key_path_clone, passphrase) {
+        match manager.add_key(&key_path_clone, passphrase, &AddKeyOptions::default()) {
             Ok(()) => {
                 tracing::info!("Key added successfully: {}", key_path_clone.display());
                 dialog_clone2.close();
@@ -468,6 +468,11 @@ fn add_key_with_passphrase_dialog(
                     &manager_clone,
                 );
             }
+            Err(rustconn_core::ssh_agent::AgentError::BadPassphrase(_)) => {
+                // Keep the dialog open so the passphrase can be retyped
+                passphrase_entry.add_css_class("error");
+                passphrase_entry.grab_focus();
+            }
             Err(e) => {
                 tracing::error!("Failed to add key: {e}");
                 // Log the error - toast notifications are handled by the parent dialog
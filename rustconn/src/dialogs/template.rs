@@ -18,11 +18,11 @@ use gtk4::{
 use libadwaita as adw;
 use rustconn_core::models::{
     AwsSsmConfig, AzureBastionConfig, AzureSshConfig, BoundaryConfig, CloudflareAccessConfig,
-    ConnectionTemplate, GcpIapConfig, GenericZeroTrustConfig, OciBastionConfig, ProtocolConfig,
-    ProtocolType, RdpClientMode, RdpConfig, RdpPerformanceMode, Resolution, SpiceConfig,
-    SpiceImageCompression, SshAuthMethod, SshConfig, SshKeySource, TailscaleSshConfig,
-    TeleportConfig, VncClientMode, VncConfig, VncPerformanceMode, ZeroTrustConfig,
-    ZeroTrustProvider, ZeroTrustProviderConfig,
+    ConnectionTemplate, GcpIapConfig, GenericProxyConfig, GenericZeroTrustConfig, OciBastionConfig,
+    ProtocolConfig, ProtocolType, RdpClientMode, RdpConfig, RdpPerformanceMode, Resolution,
+    SpiceConfig, SpiceImageCompression, SshAlgorithms, SshAuthMethod, SshConfig, SshForward,
+    SshKeySource, TailscaleSshConfig, TeleportConfig, VncClientMode, VncConfig, VncPerformanceMode,
+    ZeroTrustConfig, ZeroTrustProvider, ZeroTrustProviderConfig,
 };
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -105,6 +105,8 @@ pub struct TemplateDialog {
     zt_boundary_target_entry: Entry,
     zt_boundary_addr_entry: Entry,
     zt_generic_command_entry: Entry,
+    zt_proxy_host_entry: Entry,
+    zt_proxy_command_entry: Entry,
     zt_custom_args_entry: Entry,
     // State
     editing_id: Rc<RefCell<Option<Uuid>>>,
@@ -273,6 +275,8 @@ impl TemplateDialog {
             zt_boundary_target_entry,
             zt_boundary_addr_entry,
             zt_generic_command_entry,
+            zt_proxy_host_entry,
+            zt_proxy_command_entry,
             zt_custom_args_entry,
         ) = Self::create_zerotrust_options();
         protocol_stack.add_named(&zt_box, Some("zerotrust"));
@@ -372,6 +376,8 @@ impl TemplateDialog {
             &zt_boundary_target_entry,
             &zt_boundary_addr_entry,
             &zt_generic_command_entry,
+            &zt_proxy_host_entry,
+            &zt_proxy_command_entry,
             &zt_custom_args_entry,
         );
 
@@ -440,6 +446,8 @@ impl TemplateDialog {
             zt_boundary_target_entry,
             zt_boundary_addr_entry,
             zt_generic_command_entry,
+            zt_proxy_host_entry,
+            zt_proxy_command_entry,
             zt_custom_args_entry,
             editing_id,
             on_save,
@@ -1259,6 +1267,8 @@ impl TemplateDialog {
         Entry,
         Entry,
         Entry,
+        Entry,
+        Entry,
     ) {
         let scrolled = ScrolledWindow::builder()
             .hscrollbar_policy(gtk4::PolicyType::Never)
@@ -1291,6 +1301,7 @@ impl TemplateDialog {
             "Tailscale SSH",
             "HashiCorp Boundary",
             "Generic Command",
+            "SSH via ProxyCommand",
         ]);
         let provider_dropdown = DropDown::new(Some(provider_list), gtk4::Expression::NONE);
         provider_dropdown.set_selected(0);
@@ -1350,6 +1361,10 @@ impl TemplateDialog {
         let (generic_box, generic_command) = Self::create_generic_fields();
         provider_stack.add_named(&generic_box, Some("generic"));
 
+        // Generic ProxyCommand
+        let (proxy_box, proxy_host, proxy_command) = Self::create_generic_proxy_fields();
+        provider_stack.add_named(&proxy_box, Some("generic_proxy"));
+
         provider_stack.set_visible_child_name("aws_ssm");
         content.append(&provider_stack);
 
@@ -1366,6 +1381,7 @@ impl TemplateDialog {
                 "tailscale",
                 "boundary",
                 "generic",
+                "generic_proxy",
             ];
             let selected = dropdown.selected() as usize;
             if selected < providers.len() {
@@ -1422,6 +1438,8 @@ impl TemplateDialog {
             boundary_target,
             boundary_addr,
             generic_command,
+            proxy_host,
+            proxy_command,
             custom_args_entry,
         )
     }
@@ -1798,6 +1816,42 @@ impl TemplateDialog {
         (content, command_entry)
     }
 
+    fn create_generic_proxy_fields() -> (GtkBox, Entry, Entry) {
+        let content = GtkBox::new(Orientation::Vertical, 12);
+
+        let group = adw::PreferencesGroup::builder()
+            .title("SSH via ProxyCommand")
+            .build();
+
+        let host_entry = Entry::builder()
+            .hexpand(true)
+            .placeholder_text("host or host:port")
+            .valign(gtk4::Align::Center)
+            .build();
+        let host_row = adw::ActionRow::builder()
+            .title("Target Host")
+            .subtitle("Host as seen by the proxy")
+            .build();
+        host_row.add_suffix(&host_entry);
+        group.add(&host_row);
+
+        let command_entry = Entry::builder()
+            .hexpand(true)
+            .placeholder_text("iap-tunnel --target {host}:{port}")
+            .valign(gtk4::Align::Center)
+            .build();
+        let command_row = adw::ActionRow::builder()
+            .title("Proxy Command")
+            .subtitle("Use {host} and {port} for the target")
+            .build();
+        command_row.add_suffix(&command_entry);
+        group.add(&command_row);
+
+        content.append(&group);
+
+        (content, host_entry, command_entry)
+    }
+
     #[allow(
         clippy::too_many_arguments,
         clippy::too_many_lines,
@@ -1868,6 +1922,8 @@ impl TemplateDialog {
         zt_boundary_target: &Entry,
         zt_boundary_addr: &Entry,
         zt_generic_command: &Entry,
+        zt_proxy_host: &Entry,
+        zt_proxy_command: &Entry,
         zt_custom_args: &Entry,
     ) {
         let window = window.clone();
@@ -1933,6 +1989,8 @@ impl TemplateDialog {
         let zt_boundary_target = zt_boundary_target.clone();
         let zt_boundary_addr = zt_boundary_addr.clone();
         let zt_generic_command = zt_generic_command.clone();
+        let zt_proxy_host = zt_proxy_host.clone();
+        let zt_proxy_command = zt_proxy_command.clone();
         let zt_custom_args = zt_custom_args.clone();
 
         save_btn.connect_clicked(move |_| {
@@ -2001,6 +2059,8 @@ impl TemplateDialog {
                 &zt_boundary_target,
                 &zt_boundary_addr,
                 &zt_generic_command,
+                &zt_proxy_host,
+                &zt_proxy_command,
                 &zt_custom_args,
             );
 
@@ -2109,6 +2169,8 @@ impl TemplateDialog {
         zt_boundary_target: &Entry,
         zt_boundary_addr: &Entry,
         zt_generic_command: &Entry,
+        zt_proxy_host: &Entry,
+        zt_proxy_command: &Entry,
         zt_custom_args: &Entry,
     ) -> ProtocolConfig {
         match protocol_idx {
@@ -2162,6 +2224,8 @@ impl TemplateDialog {
                 zt_boundary_target,
                 zt_boundary_addr,
                 zt_generic_command,
+                zt_proxy_host,
+                zt_proxy_command,
                 zt_custom_args,
             ),
             _ => Self::build_ssh_config(
@@ -2236,14 +2300,20 @@ impl TemplateDialog {
                 Some(startup_command.into())
             },
             custom_options: std::collections::HashMap::new(),
+            forwards: Vec::new(),
+            environment: Vec::new(),
+            algorithms: SshAlgorithms::default(),
         };
 
         if !custom_options_text.is_empty() {
             for pair in custom_options_text.split(',') {
                 if let Some((k, v)) = pair.split_once('=') {
-                    config
-                        .custom_options
-                        .insert(k.trim().to_string(), v.trim().to_string());
+                    let (k, v) = (k.trim(), v.trim());
+                    if let Some(forward) = SshForward::from_config(k, v) {
+                        config.forwards.push(forward);
+                    } else {
+                        config.custom_options.insert(k.to_string(), v.to_string());
+                    }
                 }
             }
         }
@@ -2298,6 +2368,7 @@ impl TemplateDialog {
             resolution: Some(resolution),
             color_depth: Some(color_depth),
             audio_redirect: audio_check.is_active(),
+            microphone_redirect: false,
             gateway: if gateway_text.is_empty() {
                 None
             } else {
@@ -2384,6 +2455,7 @@ impl TemplateDialog {
                 Some(std::path::PathBuf::from(ca_cert.as_str()))
             },
             skip_cert_verify: skip_verify_check.is_active(),
+            cert_fingerprint: None,
             usb_redirection: usb_check.is_active(),
             shared_folders: Vec::new(),
             clipboard_enabled: clipboard_check.is_active(),
@@ -2415,6 +2487,8 @@ impl TemplateDialog {
         boundary_target: &Entry,
         boundary_addr: &Entry,
         generic_command: &Entry,
+        proxy_host: &Entry,
+        proxy_command: &Entry,
         custom_args: &Entry,
     ) -> ProtocolConfig {
         let custom_args_text = custom_args.text();
@@ -2487,6 +2561,10 @@ impl TemplateDialog {
                     Some(boundary_addr.text().to_string())
                 },
             }),
+            10 => ZeroTrustProviderConfig::GenericProxy(GenericProxyConfig::from_target(
+                &proxy_host.text(),
+                proxy_command.text().to_string(),
+            )),
             _ => ZeroTrustProviderConfig::Generic(GenericZeroTrustConfig {
                 command_template: generic_command.text().to_string(),
             }),
@@ -2502,6 +2580,7 @@ impl TemplateDialog {
             6 => ZeroTrustProvider::Teleport,
             7 => ZeroTrustProvider::TailscaleSsh,
             8 => ZeroTrustProvider::Boundary,
+            10 => ZeroTrustProvider::GenericProxy,
             _ => ZeroTrustProvider::Generic,
         };
 
@@ -2598,11 +2677,17 @@ impl TemplateDialog {
         if let Some(ref cmd) = config.startup_command {
             self.ssh_startup_entry.set_text(cmd);
         }
-        if !config.custom_options.is_empty() {
+        if !config.custom_options.is_empty() || !config.forwards.is_empty() {
             let opts: Vec<String> = config
                 .custom_options
                 .iter()
                 .map(|(k, v)| format!("{k}={v}"))
+                .chain(
+                    config
+                        .forwards
+                        .iter()
+                        .map(|f| format!("{}={}", f.config_keyword(), f.config_value())),
+                )
                 .collect();
             self.ssh_options_entry.set_text(&opts.join(", "));
         }
@@ -2694,6 +2779,7 @@ impl TemplateDialog {
             ZeroTrustProvider::TailscaleSsh => 7,
             ZeroTrustProvider::Boundary => 8,
             ZeroTrustProvider::Generic => 9,
+            ZeroTrustProvider::GenericProxy => 10,
         };
         self.zt_provider_dropdown.set_selected(provider_idx);
 
@@ -2708,6 +2794,7 @@ impl TemplateDialog {
             ZeroTrustProvider::TailscaleSsh => "tailscale",
             ZeroTrustProvider::Boundary => "boundary",
             ZeroTrustProvider::Generic => "generic",
+            ZeroTrustProvider::GenericProxy => "generic_proxy",
         };
         self.zt_provider_stack.set_visible_child_name(stack_name);
 
@@ -2762,6 +2849,10 @@ impl TemplateDialog {
             ZeroTrustProviderConfig::Generic(c) => {
                 self.zt_generic_command_entry.set_text(&c.command_template);
             }
+            ZeroTrustProviderConfig::GenericProxy(c) => {
+                self.zt_proxy_host_entry.set_text(&c.target());
+                self.zt_proxy_command_entry.set_text(&c.proxy_command);
+            }
         }
 
         if !config.custom_args.is_empty() {
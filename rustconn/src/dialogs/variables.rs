@@ -13,7 +13,7 @@ use gtk4::{
     PasswordEntry, ScrolledWindow,
 };
 use libadwaita as adw;
-use rustconn_core::variables::Variable;
+use rustconn_core::variables::{Variable, VariableType};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -44,6 +44,8 @@ struct VariableRow {
     description_entry: Entry,
     /// Delete button
     delete_button: Button,
+    /// Type carried over from the edited variable
+    value_type: VariableType,
 }
 
 impl VariablesDialog {
@@ -108,7 +110,9 @@ impl VariablesDialog {
         let on_save_clone = on_save.clone();
         let variables_clone = variables.clone();
         save_btn.connect_clicked(move |_| {
-            let vars = Self::collect_variables(&variables_clone);
+            let Some(vars) = Self::collect_variables(&variables_clone) else {
+                return;
+            };
             if let Some(ref cb) = *on_save_clone.borrow() {
                 cb(Some(vars));
             }
@@ -293,13 +297,21 @@ impl VariablesDialog {
             is_secret_check,
             description_entry,
             delete_button,
+            value_type: variable
+                .map(|var| var.value_type.clone())
+                .unwrap_or_default(),
         }
     }
 
     /// Collects all variables from the dialog
-    fn collect_variables(variables: &Rc<RefCell<Vec<VariableRow>>>) -> Vec<Variable> {
+    ///
+    /// Returns `None` after flagging the value entries of variables whose
+    /// value does not match their type.
+    fn collect_variables(variables: &Rc<RefCell<Vec<VariableRow>>>) -> Option<Vec<Variable>> {
         let vars = variables.borrow();
-        vars.iter()
+        let mut all_valid = true;
+        let collected = vars
+            .iter()
             .filter_map(|row| {
                 let name = row.name_entry.text().trim().to_string();
                 if name.is_empty() {
@@ -320,12 +332,27 @@ impl VariablesDialog {
                     Some(desc.trim().to_string())
                 };
 
-                let mut var = Variable::new(name, value);
+                let mut var = Variable::new(name, value).with_type(row.value_type.clone());
                 var.is_secret = is_secret;
                 var.description = description;
+
+                let error = var.validate().err().map(|e| e.to_string());
+                for entry in [
+                    row.value_entry.upcast_ref::<gtk4::Widget>(),
+                    row.secret_entry.upcast_ref(),
+                ] {
+                    if error.is_some() {
+                        entry.add_css_class("error");
+                    } else {
+                        entry.remove_css_class("error");
+                    }
+                    entry.set_tooltip_text(error.as_deref());
+                }
+                all_valid &= error.is_none();
                 Some(var)
             })
-            .collect()
+            .collect();
+        all_valid.then_some(collected)
     }
 
     /// Sets the initial variables to display
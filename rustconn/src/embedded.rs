@@ -320,20 +320,6 @@ impl EmbeddedSessionTab {
 pub struct RdpLauncher;
 
 impl RdpLauncher {
-    fn find_freerdp_binary() -> Option<String> {
-        let candidates = ["xfreerdp3", "xfreerdp", "freerdp"];
-        for candidate in candidates {
-            if std::process::Command::new("which")
-                .arg(candidate)
-                .output()
-                .is_ok_and(|o| o.status.success())
-            {
-                return Some(candidate.to_string());
-            }
-        }
-        None
-    }
-
     /// Starts an RDP session
     ///
     /// # Errors
@@ -398,11 +384,13 @@ impl RdpLauncher {
     ) -> Result<(), EmbeddingError> {
         use std::process::Command;
 
-        let binary = Self::find_freerdp_binary().ok_or_else(|| {
-            EmbeddingError::ProcessStartFailed(
-                "FreeRDP client not found. Install xfreerdp or xfreerdp3.".to_string(),
-            )
-        })?;
+        let (binary, wayland_native) = rustconn_core::detect_freerdp_client()
+            .and_then(|client| client.path.map(|path| (path, client.wayland_native)))
+            .ok_or_else(|| {
+                EmbeddingError::ProcessStartFailed(
+                    "FreeRDP client not found. Install xfreerdp or xfreerdp3.".to_string(),
+                )
+            })?;
 
         let mut cmd = Command::new(&binary);
 
@@ -436,11 +424,15 @@ impl RdpLauncher {
         // Enable dynamic resolution for better display
         cmd.arg("/dynamic-resolution");
 
-        // Add decorations flag for window controls (Requirement 6.1)
-        cmd.arg("/decorations");
+        // Wayland-native clients are decorated by the compositor and cannot
+        // position their own windows
+        if !wayland_native {
+            // Add decorations flag for window controls (Requirement 6.1)
+            cmd.arg("/decorations");
+        }
 
         // Add window geometry if saved and remember_window_position is enabled (Requirements 6.2, 6.3, 6.4)
-        if remember_window_position {
+        if remember_window_position && !wayland_native {
             if let Some((x, y, _width, _height)) = window_geometry {
                 cmd.arg(format!("/x:{x}"));
                 cmd.arg(format!("/y:{y}"));
@@ -458,10 +450,11 @@ impl RdpLauncher {
             cmd.arg(arg);
         }
 
+        let server = rustconn_core::host_for_uri(host);
         if port == 3389 {
-            cmd.arg(format!("/v:{host}"));
+            cmd.arg(format!("/v:{server}"));
         } else {
-            cmd.arg(format!("/v:{host}:{port}"));
+            cmd.arg(format!("/v:{server}:{port}"));
         }
 
         match cmd.spawn() {
@@ -509,13 +502,14 @@ impl VncLauncher {
             cmd.arg(arg);
         }
 
+        let address = rustconn_core::host_for_uri(host);
         let server = if port == 5900 {
-            format!("{host}:0")
+            format!("{address}:0")
         } else if port > 5900 && port < 6000 {
             let display = port - 5900;
-            format!("{host}:{display}")
+            format!("{address}:{display}")
         } else {
-            format!("{host}::{port}")
+            format!("{address}::{port}")
         };
         cmd.arg(&server);
 
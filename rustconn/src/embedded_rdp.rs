@@ -161,11 +161,16 @@ pub struct EmbeddedRdpWidget {
     reconnect_timer: Rc<RefCell<Option<glib::SourceId>>>,
     /// Remote clipboard text (received from server via CLIPRDR)
     remote_clipboard_text: Rc<RefCell<Option<String>>>,
+    /// Remote clipboard image (received from server via CLIPRDR)
+    remote_clipboard_image: Rc<RefCell<Option<gdk::Texture>>>,
     /// Available clipboard formats from server
     remote_clipboard_formats: Rc<RefCell<Vec<rustconn_core::ClipboardFormatInfo>>>,
     /// Audio player for RDP audio redirection
     #[cfg(feature = "rdp-audio")]
     audio_player: Rc<RefCell<Option<crate::audio::RdpAudioPlayer>>>,
+    /// Microphone recorder for RDP audio input redirection
+    #[cfg(feature = "rdp-audio")]
+    audio_recorder: Rc<RefCell<crate::audio::RdpAudioRecorder>>,
     /// Clipboard file transfer state
     #[cfg(feature = "rdp-embedded")]
     file_transfer: Rc<RefCell<ClipboardFileTransfer>>,
@@ -308,9 +313,12 @@ impl EmbeddedRdpWidget {
             reconnect_button,
             reconnect_timer: Rc::new(RefCell::new(None)),
             remote_clipboard_text: Rc::new(RefCell::new(None)),
+            remote_clipboard_image: Rc::new(RefCell::new(None)),
             remote_clipboard_formats: Rc::new(RefCell::new(Vec::new())),
             #[cfg(feature = "rdp-audio")]
             audio_player: Rc::new(RefCell::new(None)),
+            #[cfg(feature = "rdp-audio")]
+            audio_recorder: Rc::new(RefCell::new(crate::audio::RdpAudioRecorder::new())),
             #[cfg(feature = "rdp-embedded")]
             file_transfer: Rc::new(RefCell::new(ClipboardFileTransfer::new())),
             #[cfg(feature = "rdp-embedded")]
@@ -410,36 +418,12 @@ impl EmbeddedRdpWidget {
                             save_btn_clone.set_sensitive(false);
                             save_btn_clone.set_label("Downloading...");
 
-                            // Request file contents for each file
+                            // The client streams the files into the target directory
+                            // and reports progress through clipboard transfer events
                             if let Some(ref sender) = *ironrdp_tx_clone.borrow() {
-                                for (idx, file) in files_clone.iter().enumerate() {
-                                    let stream_id = {
-                                        let mut transfer = file_transfer_clone.borrow_mut();
-                                        transfer.start_download(idx as u32)
-                                    };
-
-                                    if let Some(sid) = stream_id {
-                                        // First request size, then data
-                                        let _ =
-                                            sender.send(RdpClientCommand::RequestFileContents {
-                                                stream_id: sid,
-                                                file_index: file.index,
-                                                request_size: true,
-                                                offset: 0,
-                                                length: 0,
-                                            });
-
-                                        // Then request actual data
-                                        let _ =
-                                            sender.send(RdpClientCommand::RequestFileContents {
-                                                stream_id: sid,
-                                                file_index: file.index,
-                                                request_size: false,
-                                                offset: 0,
-                                                length: u32::MAX, // Request all data
-                                            });
-                                    }
-                                }
+                                let _ = sender.send(RdpClientCommand::SaveClipboardFiles {
+                                    target_dir: path.clone(),
+                                });
                             }
 
                             // Show progress
@@ -492,6 +476,7 @@ impl EmbeddedRdpWidget {
             let state = self.state.clone();
             let is_embedded = self.is_embedded.clone();
             let remote_clipboard_text = self.remote_clipboard_text.clone();
+            let remote_clipboard_image = self.remote_clipboard_image.clone();
             let drawing_area = self.drawing_area.clone();
             let status_label = self.status_label.clone();
 
@@ -503,8 +488,22 @@ impl EmbeddedRdpWidget {
                     return;
                 }
 
-                // Check if we have remote clipboard text
-                if let Some(ref text) = *remote_clipboard_text.borrow() {
+                // Check if we have a remote clipboard image or text
+                if let Some(ref texture) = *remote_clipboard_image.borrow() {
+                    let clipboard = drawing_area.display().clipboard();
+                    clipboard.set_texture(texture);
+
+                    status_label.set_text(&format!(
+                        "Copied {}x{} image",
+                        texture.width(),
+                        texture.height()
+                    ));
+                    status_label.set_visible(true);
+                    let status_hide = status_label.clone();
+                    glib::timeout_add_local_once(std::time::Duration::from_secs(2), move || {
+                        status_hide.set_visible(false);
+                    });
+                } else if let Some(ref text) = *remote_clipboard_text.borrow() {
                     let char_count = text.len();
 
                     // Copy to local clipboard
@@ -555,6 +554,75 @@ impl EmbeddedRdpWidget {
 
                 #[cfg(feature = "rdp-embedded")]
                 let using_ironrdp = *is_ironrdp.borrow();
+
+                // Files are offered as a file group descriptor; the server then
+                // pulls their contents over the clipboard channel
+                #[cfg(feature = "rdp-embedded")]
+                if using_ironrdp
+                    && clipboard
+                        .formats()
+                        .contains_type(gdk::FileList::static_type())
+                {
+                    let tx = ironrdp_tx.clone();
+                    let status = status_label.clone();
+                    clipboard.read_value_async(
+                        gdk::FileList::static_type(),
+                        glib::Priority::DEFAULT,
+                        None::<&gtk4::gio::Cancellable>,
+                        move |result| {
+                            let Some(files) = result
+                                .ok()
+                                .and_then(|value| value.get::<gdk::FileList>().ok())
+                            else {
+                                return;
+                            };
+                            let paths: Vec<_> =
+                                files.files().iter().filter_map(|f| f.path()).collect();
+                            if paths.is_empty() {
+                                return;
+                            }
+                            if let Some(ref sender) = *tx.borrow() {
+                                let count = paths.len();
+                                let _ = sender.send(RdpClientCommand::ClipboardCopyFiles(paths));
+                                status.set_text(&format!("{count} files offered to remote"));
+                                status.set_visible(true);
+                                let status_hide = status.clone();
+                                glib::timeout_add_local_once(
+                                    std::time::Duration::from_secs(2),
+                                    move || {
+                                        status_hide.set_visible(false);
+                                    },
+                                );
+                            }
+                        },
+                    );
+                    return;
+                }
+                // Images are offered to the server, which then requests the
+                // data in the format it prefers
+                #[cfg(feature = "rdp-embedded")]
+                if using_ironrdp
+                    && clipboard
+                        .formats()
+                        .contains_type(gdk::Texture::static_type())
+                {
+                    if let Some(ref sender) = *ironrdp_tx.borrow() {
+                        let _ = sender.send(RdpClientCommand::ClipboardCopy(vec![
+                            rustconn_core::ClipboardFormatInfo::png(),
+                            rustconn_core::ClipboardFormatInfo::dib(),
+                        ]));
+                        status_label.set_text("Image offered to remote clipboard");
+                        status_label.set_visible(true);
+                        let status_hide = status_label.clone();
+                        glib::timeout_add_local_once(
+                            std::time::Duration::from_secs(2),
+                            move || {
+                                status_hide.set_visible(false);
+                            },
+                        );
+                    }
+                    return;
+                }
                 #[cfg(feature = "rdp-embedded")]
                 let tx = ironrdp_tx.clone();
                 let status = status_label.clone();
@@ -1500,7 +1568,9 @@ impl EmbeddedRdpWidget {
     /// - Requirement 1.5: Fallback to FreeRDP if IronRDP fails
     #[cfg(feature = "rdp-embedded")]
     fn connect_ironrdp(&self, config: &RdpConfig) -> Result<(), EmbeddedRdpError> {
-        use rustconn_core::{RdpClient, RdpClientConfig, RdpClientEvent};
+        use rustconn_core::{
+            ClipboardTransferDirection, RdpClient, RdpClientConfig, RdpClientEvent,
+        };
 
         // Increment connection generation to invalidate any stale polling loops
         let generation = {
@@ -1571,7 +1641,10 @@ impl EmbeddedRdpWidget {
         let shared_folders: Vec<rustconn_core::rdp_client::SharedFolder> = config
             .shared_folders
             .iter()
-            .map(|f| rustconn_core::rdp_client::SharedFolder::new(&f.share_name, &f.local_path))
+            .map(|f| {
+                rustconn_core::rdp_client::SharedFolder::new(&f.share_name, &f.local_path)
+                    .with_read_only(f.read_only)
+            })
             .collect();
 
         // Convert GUI config to RdpClientConfig using actual widget size
@@ -1582,6 +1655,7 @@ impl EmbeddedRdpWidget {
                 crate::utils::dimension_to_u16(actual_height),
             )
             .with_clipboard(config.clipboard_enabled)
+            .with_microphone(config.microphone_enabled)
             .with_shared_folders(shared_folders)
             .with_performance_mode(config.performance_mode)
             .with_color_depth(config.performance_mode.color_depth());
@@ -1640,6 +1714,7 @@ impl EmbeddedRdpWidget {
         let is_ironrdp = self.is_ironrdp.clone();
         let ironrdp_tx = self.ironrdp_command_tx.clone();
         let remote_clipboard_text = self.remote_clipboard_text.clone();
+        let remote_clipboard_image = self.remote_clipboard_image.clone();
         let remote_clipboard_formats = self.remote_clipboard_formats.clone();
         let copy_button = self.copy_button.clone();
         let file_transfer = self.file_transfer.clone();
@@ -1650,6 +1725,8 @@ impl EmbeddedRdpWidget {
         let connection_generation = self.connection_generation.clone();
         #[cfg(feature = "rdp-audio")]
         let audio_player = self.audio_player.clone();
+        #[cfg(feature = "rdp-audio")]
+        let audio_recorder = self.audio_recorder.clone();
 
         // Store client in a shared reference for the polling closure
         let client = std::rc::Rc::new(std::cell::RefCell::new(Some(client)));
@@ -1692,9 +1769,19 @@ impl EmbeddedRdpWidget {
                 if let Some(ref client) = *client_ref.borrow() {
                     while let Some(event) = client.try_recv_event() {
                         match event {
-                            RdpClientEvent::Connected { width, height } => {
-                                tracing::debug!("[IronRDP] Connected: {}x{}", width, height);
+                            RdpClientEvent::Connected {
+                                width,
+                                height,
+                                monitors,
+                            } => {
+                                tracing::debug!(
+                                    "[IronRDP] Connected: {}x{} across {} monitor(s)",
+                                    width,
+                                    height,
+                                    monitors.len()
+                                );
                                 *state.borrow_mut() = RdpConnectionState::Connected;
+                                status_label.set_visible(false);
 
                                 // Use server's resolution for the buffer
                                 // The draw function will scale to fit the widget
@@ -1718,6 +1805,8 @@ impl EmbeddedRdpWidget {
                                     generation
                                 );
                                 // Check if this polling loop is still current before firing callback
+                                #[cfg(feature = "rdp-audio")]
+                                audio_recorder.borrow_mut().stop();
                                 if *connection_generation.borrow() == generation {
                                     *state.borrow_mut() = RdpConnectionState::Disconnected;
                                     toolbar.set_visible(false);
@@ -1734,6 +1823,16 @@ impl EmbeddedRdpWidget {
                                     should_break = true;
                                 }
                             }
+                            RdpClientEvent::Reconnecting { attempt } => {
+                                tracing::info!("[IronRDP] Reconnecting, attempt {}", attempt);
+                                *state.borrow_mut() = RdpConnectionState::Connecting;
+                                if let Some(ref callback) = *on_state_changed.borrow() {
+                                    callback(RdpConnectionState::Connecting);
+                                }
+                                status_label
+                                    .set_text(&format!("Reconnecting (attempt {attempt})…"));
+                                status_label.set_visible(true);
+                            }
                             RdpClientEvent::Error(msg) => {
                                 tracing::error!("[IronRDP] Error: {}", msg);
                                 *state.borrow_mut() = RdpConnectionState::Error;
@@ -1813,10 +1912,24 @@ impl EmbeddedRdpWidget {
                                 // Server sent clipboard text - store it and enable Copy button
                                 tracing::debug!("[Clipboard] Received text from server");
                                 *remote_clipboard_text.borrow_mut() = Some(text);
+                                *remote_clipboard_image.borrow_mut() = None;
                                 copy_button.set_sensitive(true);
                                 copy_button
                                     .set_tooltip_text(Some("Copy remote clipboard to local"));
                             }
+                            RdpClientEvent::ClipboardImage(image) => {
+                                // Server sent a clipboard image - store it and enable Copy button
+                                tracing::debug!("[Clipboard] Received image from server");
+                                if let Some(texture) = clipboard_image_to_texture(&image) {
+                                    *remote_clipboard_image.borrow_mut() = Some(texture);
+                                    *remote_clipboard_text.borrow_mut() = None;
+                                    copy_button.set_sensitive(true);
+                                    copy_button
+                                        .set_tooltip_text(Some("Copy remote clipboard to local"));
+                                } else {
+                                    tracing::warn!("[Clipboard] Failed to decode server image");
+                                }
+                            }
                             RdpClientEvent::ClipboardFormatsAvailable(formats) => {
                                 // Server has clipboard data available
                                 tracing::debug!(
@@ -1843,44 +1956,64 @@ impl EmbeddedRdpWidget {
                                 let tx = ironrdp_tx.clone();
                                 let format_id = format.id;
 
-                                clipboard.read_text_async(
-                                    None::<&gtk4::gio::Cancellable>,
-                                    move |result| {
-                                        if let Ok(Some(text)) = result {
-                                            eprintln!(
-                                                "[Clipboard] Sending {} chars to server",
-                                                text.len()
-                                            );
-                                            if let Some(ref sender) = *tx.borrow() {
-                                                // Send as UTF-16 for CF_UNICODETEXT
-                                                if format_id == 13 {
-                                                    // CF_UNICODETEXT
-                                                    let data: Vec<u8> = text
-                                                        .encode_utf16()
-                                                        .flat_map(u16::to_le_bytes)
-                                                        .chain([0, 0]) // null terminator
-                                                        .collect();
+                                if format.is_image() {
+                                    clipboard.read_texture_async(
+                                        None::<&gtk4::gio::Cancellable>,
+                                        move |result| {
+                                            if let Ok(Some(texture)) = result {
+                                                let image =
+                                                    texture_to_clipboard_image(&texture, &format);
+                                                if let Some(ref sender) = *tx.borrow() {
                                                     let _ = sender.send(
-                                                        RdpClientCommand::ClipboardData {
+                                                        RdpClientCommand::ClipboardImage {
                                                             format_id,
-                                                            data,
-                                                        },
-                                                    );
-                                                } else {
-                                                    // CF_TEXT - send as bytes
-                                                    let mut data = text.as_bytes().to_vec();
-                                                    data.push(0); // null terminator
-                                                    let _ = sender.send(
-                                                        RdpClientCommand::ClipboardData {
-                                                            format_id,
-                                                            data,
+                                                            image,
                                                         },
                                                     );
                                                 }
                                             }
-                                        }
-                                    },
-                                );
+                                        },
+                                    );
+                                } else {
+                                    clipboard.read_text_async(
+                                        None::<&gtk4::gio::Cancellable>,
+                                        move |result| {
+                                            if let Ok(Some(text)) = result {
+                                                eprintln!(
+                                                    "[Clipboard] Sending {} chars to server",
+                                                    text.len()
+                                                );
+                                                if let Some(ref sender) = *tx.borrow() {
+                                                    // Send as UTF-16 for CF_UNICODETEXT
+                                                    if format_id == 13 {
+                                                        // CF_UNICODETEXT
+                                                        let data: Vec<u8> = text
+                                                            .encode_utf16()
+                                                            .flat_map(u16::to_le_bytes)
+                                                            .chain([0, 0]) // null terminator
+                                                            .collect();
+                                                        let _ = sender.send(
+                                                            RdpClientCommand::ClipboardData {
+                                                                format_id,
+                                                                data,
+                                                            },
+                                                        );
+                                                    } else {
+                                                        // CF_TEXT - send as bytes
+                                                        let mut data = text.as_bytes().to_vec();
+                                                        data.push(0); // null terminator
+                                                        let _ = sender.send(
+                                                            RdpClientCommand::ClipboardData {
+                                                                format_id,
+                                                                data,
+                                                            },
+                                                        );
+                                                    }
+                                                }
+                                            }
+                                        },
+                                    );
+                                }
                             }
                             RdpClientEvent::ClipboardPasteRequest(format) => {
                                 // Backend requests to fetch data from server
@@ -1928,6 +2061,12 @@ impl EmbeddedRdpWidget {
                             RdpClientEvent::ServerMessage(msg) => {
                                 tracing::debug!("[IronRDP] Server message: {}", msg);
                             }
+                            RdpClientEvent::FrameCaptured(png) => {
+                                tracing::debug!("[IronRDP] Captured frame: {} bytes", png.len());
+                            }
+                            RdpClientEvent::CaptureFailed(msg) => {
+                                tracing::warn!("[IronRDP] Frame capture failed: {}", msg);
+                            }
                             #[cfg(feature = "rdp-audio")]
                             RdpClientEvent::AudioFormatChanged(format) => {
                                 // Audio format negotiated - configure audio player
@@ -1975,13 +2114,63 @@ impl EmbeddedRdpWidget {
                                     }
                                 }
                             }
+                            #[cfg(feature = "rdp-audio")]
+                            RdpClientEvent::AudioInputOpened {
+                                format,
+                                frames_per_packet,
+                            } => {
+                                // Server opened the microphone stream - start recording
+                                if let Some(ref sender) = *ironrdp_tx.borrow() {
+                                    if let Err(e) = audio_recorder.borrow_mut().start(
+                                        &format,
+                                        frames_per_packet,
+                                        sender.clone(),
+                                    ) {
+                                        tracing::warn!("[Audio] Failed to record: {}", e);
+                                    }
+                                }
+                            }
+                            #[cfg(feature = "rdp-audio")]
+                            RdpClientEvent::AudioInputClosed => {
+                                tracing::debug!("[Audio] Microphone channel closed");
+                                audio_recorder.borrow_mut().stop();
+                            }
                             #[cfg(not(feature = "rdp-audio"))]
                             RdpClientEvent::AudioFormatChanged(_)
                             | RdpClientEvent::AudioData { .. }
                             | RdpClientEvent::AudioVolume { .. }
-                            | RdpClientEvent::AudioClose => {
+                            | RdpClientEvent::AudioClose
+                            | RdpClientEvent::AudioInputOpened { .. }
+                            | RdpClientEvent::AudioInputClosed => {
                                 // Audio not enabled - ignore
                             }
+                            RdpClientEvent::AudioInputDeclined(reason) => {
+                                tracing::warn!("[Audio] Microphone declined: {}", reason);
+                                status_label.set_text(&reason);
+                                status_label.set_visible(true);
+                                let status_hide = status_label.clone();
+                                glib::timeout_add_local_once(
+                                    std::time::Duration::from_secs(4),
+                                    move || {
+                                        status_hide.set_visible(false);
+                                    },
+                                );
+                            }
+                            RdpClientEvent::SharedFolderMounted { name } => {
+                                tracing::info!("[RDPDR] Shared folder '{}' mounted", name);
+                            }
+                            RdpClientEvent::SharedFolderError { name, message } => {
+                                tracing::warn!("[RDPDR] Shared folder '{}': {}", name, message);
+                                status_label.set_text(&format!("{name}: {message}"));
+                                status_label.set_visible(true);
+                                let status_hide = status_label.clone();
+                                glib::timeout_add_local_once(
+                                    std::time::Duration::from_secs(4),
+                                    move || {
+                                        status_hide.set_visible(false);
+                                    },
+                                );
+                            }
                             RdpClientEvent::ClipboardDataReady { format_id, data } => {
                                 // Clipboard data ready to send to server
                                 tracing::debug!(
@@ -2117,6 +2306,74 @@ impl EmbeddedRdpWidget {
                                 // Update size for progress indication
                                 file_transfer.borrow_mut().update_size(stream_id, size);
                             }
+                            RdpClientEvent::ClipboardTransferProgress {
+                                direction,
+                                file_name,
+                                transferred,
+                                total,
+                            } => {
+                                let fraction = crate::utils::progress_fraction(transferred, total);
+                                let verb = match direction {
+                                    ClipboardTransferDirection::Download => "Downloading",
+                                    ClipboardTransferDirection::Upload => "Uploading",
+                                };
+                                if let Some(ref callback) = *on_file_progress.borrow() {
+                                    callback(fraction, &format!("{verb} {file_name}"));
+                                }
+                            }
+                            RdpClientEvent::ClipboardTransferComplete { direction, files } => {
+                                tracing::info!(
+                                    "[Clipboard] {:?} transfer complete: {} files",
+                                    direction,
+                                    files
+                                );
+                                let message = match direction {
+                                    ClipboardTransferDirection::Download => {
+                                        let file_count =
+                                            file_transfer.borrow().available_files.len();
+                                        save_files_button.set_sensitive(true);
+                                        save_files_button
+                                            .set_label(&format!("Save {} Files", file_count));
+                                        let target = file_transfer
+                                            .borrow()
+                                            .target_directory
+                                            .as_ref()
+                                            .map(|p| p.display().to_string())
+                                            .unwrap_or_default();
+                                        if let Some(ref callback) = *on_file_complete.borrow() {
+                                            callback(files, &target);
+                                        }
+                                        format!("Saved {files} files")
+                                    }
+                                    ClipboardTransferDirection::Upload => {
+                                        format!("Sent {files} files to remote")
+                                    }
+                                };
+                                status_label.set_text(&message);
+                                status_label.set_visible(true);
+                                let status_hide = status_label.clone();
+                                glib::timeout_add_local_once(
+                                    std::time::Duration::from_secs(3),
+                                    move || {
+                                        status_hide.set_visible(false);
+                                    },
+                                );
+                            }
+                            RdpClientEvent::ClipboardTransferFailed { direction, message } => {
+                                tracing::warn!(
+                                    "[Clipboard] {:?} transfer failed: {}",
+                                    direction,
+                                    message
+                                );
+                                if direction == ClipboardTransferDirection::Download {
+                                    let file_count = file_transfer.borrow().available_files.len();
+                                    save_files_button.set_sensitive(true);
+                                    save_files_button
+                                        .set_label(&format!("Save {} Files", file_count));
+                                }
+                                status_label.set_text(&format!("File transfer failed: {message}"));
+                                status_label.set_visible(true);
+                            }
                         }
                     }
                 }
@@ -2354,6 +2611,10 @@ impl EmbeddedRdpWidget {
         // Clear pixel buffer
         self.pixel_buffer.borrow_mut().clear();
 
+        // Release the microphone
+        #[cfg(feature = "rdp-audio")]
+        self.audio_recorder.borrow_mut().stop();
+
         // Reset state (but keep config for potential reconnect)
         *self.is_embedded.borrow_mut() = false;
         self.set_state(RdpConnectionState::Disconnected);
@@ -2701,4 +2962,59 @@ impl crate::embedded_trait::EmbeddedWidget for EmbeddedRdpWidget {
     }
 }
 
+/// Converts a clipboard image received from the server into a texture
+#[cfg(feature = "rdp-embedded")]
+fn clipboard_image_to_texture(image: &rustconn_core::ClipboardImage) -> Option<gdk::Texture> {
+    match image {
+        rustconn_core::ClipboardImage::Png(data) => {
+            gdk::Texture::from_bytes(&glib::Bytes::from(data)).ok()
+        }
+        rustconn_core::ClipboardImage::Rgba {
+            width,
+            height,
+            pixels,
+        } => {
+            let texture = gdk::MemoryTexture::new(
+                i32::try_from(*width).ok()?,
+                i32::try_from(*height).ok()?,
+                gdk::MemoryFormat::R8g8b8a8,
+                &glib::Bytes::from(pixels),
+                *width as usize * 4,
+            );
+            Some(texture.upcast())
+        }
+    }
+}
+
+/// Converts a local clipboard texture into the payload for a requested format
+#[cfg(feature = "rdp-embedded")]
+fn texture_to_clipboard_image(
+    texture: &gdk::Texture,
+    format: &rustconn_core::ClipboardFormatInfo,
+) -> rustconn_core::ClipboardImage {
+    if format.is_png() {
+        return rustconn_core::ClipboardImage::Png(texture.save_to_png_bytes().to_vec());
+    }
+
+    let mut downloader = gdk::TextureDownloader::new(texture);
+    downloader.set_format(gdk::MemoryFormat::R8g8b8a8);
+    let (bytes, stride) = downloader.download_bytes();
+
+    let width = texture.width().unsigned_abs();
+    let height = texture.height().unsigned_abs();
+    let row_len = width as usize * 4;
+    let pixels = bytes
+        .chunks(stride)
+        .take(height as usize)
+        .flat_map(|row| &row[..row_len.min(row.len())])
+        .copied()
+        .collect();
+
+    rustconn_core::ClipboardImage::Rgba {
+        width,
+        height,
+        pixels,
+    }
+}
+
 // Tests moved to embedded_rdp_types.rs, embedded_rdp_buffer.rs, and embedded_rdp_launcher.rs
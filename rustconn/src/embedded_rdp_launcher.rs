@@ -83,11 +83,13 @@ impl SafeFreeRdpLauncher {
     ///
     /// Returns error if FreeRDP cannot be launched.
     pub fn launch(&self, config: &RdpConfig) -> Result<Child, EmbeddedRdpError> {
-        let binary = Self::detect_freerdp().ok_or_else(|| {
-            EmbeddedRdpError::FreeRdpInit(
-                "No FreeRDP client found. Install xfreerdp or wlfreerdp.".to_string(),
-            )
-        })?;
+        let (binary, wayland_native) = Self::detect_freerdp()
+            .and_then(|client| client.path.map(|path| (path, client.wayland_native)))
+            .ok_or_else(|| {
+                EmbeddedRdpError::FreeRdpInit(
+                    "No FreeRDP client found. Install xfreerdp or wlfreerdp.".to_string(),
+                )
+            })?;
 
         let mut cmd = Command::new(&binary);
 
@@ -97,7 +99,7 @@ impl SafeFreeRdpLauncher {
         }
 
         // Build connection arguments
-        Self::add_connection_args(&mut cmd, config);
+        Self::add_connection_args(&mut cmd, config, wayland_native);
 
         // Redirect stderr to suppress warnings
         cmd.stderr(Stdio::null());
@@ -106,25 +108,20 @@ impl SafeFreeRdpLauncher {
             .map_err(|e| EmbeddedRdpError::FreeRdpInit(e.to_string()))
     }
 
-    /// Detects available FreeRDP binary
-    pub fn detect_freerdp() -> Option<String> {
-        let candidates = ["xfreerdp3", "xfreerdp", "freerdp"];
-        for candidate in candidates {
-            if Command::new("which")
-                .arg(candidate)
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .status()
-                .is_ok_and(|s| s.success())
-            {
-                return Some(candidate.to_string());
-            }
-        }
-        None
+    /// Detects the preferred FreeRDP client for the current display server
+    ///
+    /// Wayland-native clients are preferred in a Wayland session.
+    #[must_use]
+    pub fn detect_freerdp() -> Option<rustconn_core::ClientInfo> {
+        rustconn_core::detect_freerdp_client()
     }
 
     /// Adds connection arguments to the command
-    pub fn add_connection_args(cmd: &mut Command, config: &RdpConfig) {
+    ///
+    /// Wayland-native clients get decorations from the compositor and cannot
+    /// position their own windows, so `/decorations` and `/x`/`/y` are only
+    /// passed to X11 clients.
+    pub fn add_connection_args(cmd: &mut Command, config: &RdpConfig, wayland_native: bool) {
         if let Some(ref domain) = config.domain {
             if !domain.is_empty() {
                 cmd.arg(format!("/d:{domain}"));
@@ -146,11 +143,13 @@ impl SafeFreeRdpLauncher {
         cmd.arg("/cert:ignore");
         cmd.arg("/dynamic-resolution");
 
-        // Add decorations flag for window controls (Requirement 6.1)
-        cmd.arg("/decorations");
+        if !wayland_native {
+            // Add decorations flag for window controls (Requirement 6.1)
+            cmd.arg("/decorations");
+        }
 
         // Add window geometry if saved and remember_window_position is enabled
-        if config.remember_window_position {
+        if config.remember_window_position && !wayland_native {
             if let Some((x, y, _width, _height)) = config.window_geometry {
                 cmd.arg(format!("/x:{x}"));
                 cmd.arg(format!("/y:{y}"));
@@ -171,10 +170,11 @@ impl SafeFreeRdpLauncher {
             cmd.arg(arg);
         }
 
+        let host = rustconn_core::host_for_uri(&config.host);
         if config.port == 3389 {
-            cmd.arg(format!("/v:{}", config.host));
+            cmd.arg(format!("/v:{host}"));
         } else {
-            cmd.arg(format!("/v:{}:{}", config.host, config.port));
+            cmd.arg(format!("/v:{host}:{}", config.port));
         }
     }
 }
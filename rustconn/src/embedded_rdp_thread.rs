@@ -114,6 +114,7 @@ impl ClipboardFileTransfer {
     }
 
     /// Starts download for a file, returns stream_id
+    #[allow(dead_code)]
     pub fn start_download(&mut self, file_index: u32) -> Option<u32> {
         let file_info = self.available_files.get(file_index as usize)?.clone();
         let stream_id = self.next_stream_id;
@@ -403,10 +404,11 @@ impl FreeRdpThread {
             cmd.arg(arg);
         }
 
+        let host = rustconn_core::host_for_uri(&config.host);
         if config.port == 3389 {
-            cmd.arg(format!("/v:{}", config.host));
+            cmd.arg(format!("/v:{host}"));
         } else {
-            cmd.arg(format!("/v:{}:{}", config.host, config.port));
+            cmd.arg(format!("/v:{host}:{}", config.port));
         }
 
         // Redirect stderr to suppress Qt warnings
@@ -83,6 +83,8 @@ pub struct EmbeddedSharedFolder {
     pub local_path: PathBuf,
     /// Share name visible in the remote session
     pub share_name: String,
+    /// Expose the folder to the remote session as read-only
+    pub read_only: bool,
 }
 
 /// RDP connection configuration
@@ -104,6 +106,8 @@ pub struct RdpConfig {
     pub height: u32,
     /// Enable clipboard sharing
     pub clipboard_enabled: bool,
+    /// Redirect the local microphone to the remote session
+    pub microphone_enabled: bool,
     /// Performance mode (Quality/Balanced/Speed)
     pub performance_mode: RdpPerformanceMode,
     /// Shared folders for drive redirection
@@ -131,6 +135,7 @@ impl Default for RdpConfig {
             width: 1920,
             height: 1080,
             clipboard_enabled: true,
+            microphone_enabled: false,
             performance_mode: RdpPerformanceMode::default(),
             shared_folders: Vec::new(),
             extra_args: Vec::new(),
@@ -194,6 +199,13 @@ impl RdpConfig {
         self
     }
 
+    /// Enables or disables microphone redirection
+    #[must_use]
+    pub const fn with_microphone(mut self, enabled: bool) -> Self {
+        self.microphone_enabled = enabled;
+        self
+    }
+
     /// Sets the performance mode (Quality/Balanced/Speed)
     #[must_use]
     pub const fn with_performance_mode(mut self, mode: RdpPerformanceMode) -> Self {
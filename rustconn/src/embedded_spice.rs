@@ -27,7 +27,11 @@ use std::rc::Rc;
 #[cfg(feature = "spice-embedded")]
 use gtk4::glib;
 #[cfg(feature = "spice-embedded")]
-use rustconn_core::{SpiceClient, SpiceClientCommand, SpiceClientEvent};
+use libadwaita as adw;
+#[cfg(feature = "spice-embedded")]
+use rustconn_core::{
+    SpiceCertDecision, SpiceCertificateMismatch, SpiceClient, SpiceClientCommand, SpiceClientEvent,
+};
 use rustconn_core::{SpiceClientConfig, SpiceClientError};
 
 /// Connection state for embedded SPICE widget
@@ -155,6 +159,9 @@ type StateCallback = Box<dyn Fn(SpiceConnectionState) + 'static>;
 /// Callback type for error notifications
 type ErrorCallback = Box<dyn Fn(&str) + 'static>;
 
+/// Callback type for newly pinned certificate fingerprints
+type CertificatePinnedCallback = Box<dyn Fn(&str) + 'static>;
+
 /// Embedded SPICE widget using native spice-client
 ///
 /// This widget provides native SPICE session embedding within GTK4 applications.
@@ -202,6 +209,8 @@ pub struct EmbeddedSpiceWidget {
     on_error: Rc<RefCell<Option<ErrorCallback>>>,
     /// Reconnect callback
     on_reconnect: Rc<RefCell<Option<Box<dyn Fn() + 'static>>>>,
+    /// Callback storing a certificate the user chose to always trust
+    on_certificate_pinned: Rc<RefCell<Option<CertificatePinnedCallback>>>,
     /// Reconnect button (shown when disconnected)
     reconnect_button: Button,
     /// Native SPICE client (when spice-embedded feature is enabled)
@@ -305,6 +314,7 @@ impl EmbeddedSpiceWidget {
             on_state_changed: Rc::new(RefCell::new(None)),
             on_error: Rc::new(RefCell::new(None)),
             on_reconnect: Rc::new(RefCell::new(None)),
+            on_certificate_pinned: Rc::new(RefCell::new(None)),
             reconnect_button,
             #[cfg(feature = "spice-embedded")]
             spice_client: Rc::new(RefCell::new(None)),
@@ -755,6 +765,17 @@ impl EmbeddedSpiceWidget {
         }));
     }
 
+    /// Connects a callback for certificates the user chose to always trust
+    ///
+    /// The callback receives the fingerprint to store as the connection's
+    /// pinned certificate.
+    pub fn connect_certificate_pinned<F>(&self, callback: F)
+    where
+        F: Fn(&str) + 'static,
+    {
+        *self.on_certificate_pinned.borrow_mut() = Some(Box::new(callback));
+    }
+
     /// Connects a callback for errors
     pub fn connect_error<F>(&self, callback: F)
     where
@@ -810,6 +831,8 @@ impl EmbeddedSpiceWidget {
     #[cfg(feature = "spice-embedded")]
     fn connect_native(&self, config: &SpiceClientConfig) -> Result<(), SpiceClientError> {
         let mut client = SpiceClient::new(config.clone());
+        // Certificate mismatches are answered through `show_certificate_prompt`
+        client.set_certificate_prompt(true);
 
         // Connect and get channels
         client.connect()?;
@@ -846,6 +869,8 @@ impl EmbeddedSpiceWidget {
         let spice_height = self.spice_height.clone();
         let on_state_changed = self.on_state_changed.clone();
         let on_error = self.on_error.clone();
+        let on_certificate_pinned = self.on_certificate_pinned.clone();
+        let config = self.config.clone();
 
         glib::timeout_add_local(std::time::Duration::from_millis(16), move || {
             // Poll for events
@@ -926,6 +951,20 @@ impl EmbeddedSpiceWidget {
                         }
                         drawing_area.queue_draw();
                     }
+                    SpiceClientEvent::CertificateMismatch(mismatch) => {
+                        if let Some(sender) = command_sender.borrow().clone() {
+                            show_certificate_prompt(&drawing_area, &mismatch, sender);
+                        }
+                    }
+                    SpiceClientEvent::CertificatePinned { fingerprint } => {
+                        // Reconnects must not ask again
+                        if let Some(ref mut config) = *config.borrow_mut() {
+                            config.cert_fingerprint = Some(fingerprint.clone());
+                        }
+                        if let Some(ref callback) = *on_certificate_pinned.borrow() {
+                            callback(&fingerprint);
+                        }
+                    }
                     SpiceClientEvent::ClipboardText(text) => {
                         // Copy to local clipboard
                         if let Some(display) = gtk4::gdk::Display::default() {
@@ -1033,6 +1072,42 @@ impl EmbeddedSpiceWidget {
     }
 }
 
+/// Asks whether to trust a server certificate that does not match the pin
+/// and sends the answer to the SPICE client
+#[cfg(feature = "spice-embedded")]
+fn show_certificate_prompt(
+    parent: &impl IsA<gtk4::Widget>,
+    mismatch: &SpiceCertificateMismatch,
+    sender: rustconn_core::SpiceCommandSender,
+) {
+    use adw::prelude::*;
+
+    let body = format!(
+        "The certificate presented by {}:{} does not match the pinned certificate.\n\n\
+         Presented: {}\nPinned: {}\n\n\
+         The certificate may have been renewed, or someone may be intercepting the connection.",
+        mismatch.host, mismatch.port, mismatch.fingerprint, mismatch.pinned
+    );
+    let dialog = adw::AlertDialog::new(Some("Trust This Certificate?"), Some(&body));
+    dialog.add_response("reject", "Reject");
+    dialog.add_response("once", "Trust Once");
+    dialog.add_response("always", "Always Trust");
+    dialog.set_response_appearance("always", adw::ResponseAppearance::Destructive);
+    dialog.set_default_response(Some("reject"));
+    dialog.set_close_response("reject");
+
+    dialog.connect_response(None, move |_, response| {
+        let decision = match response {
+            "once" => SpiceCertDecision::TrustOnce,
+            "always" => SpiceCertDecision::TrustAlways,
+            _ => SpiceCertDecision::Reject,
+        };
+        let _ = sender.send(SpiceClientCommand::CertificateDecision(decision));
+    });
+
+    dialog.present(Some(parent));
+}
+
 impl Default for EmbeddedSpiceWidget {
     fn default() -> Self {
         Self::new()
@@ -759,7 +759,7 @@ impl EmbeddedVncWidget {
                             tracing::debug!("[VNC] Pasting {char_count} chars to remote");
 
                             // Send text as key presses via VNC client
-                            // (ClipboardText only syncs clipboard, doesn't paste)
+                            // (SetClipboard only syncs clipboard, doesn't paste)
                             if let Some(ref sender) = *tx.borrow() {
                                 // Use try_send to avoid blocking GTK main thread
                                 let _ =
@@ -843,6 +843,20 @@ impl EmbeddedVncWidget {
         *self.is_embedded.borrow()
     }
 
+    /// Returns the size of the remote framebuffer in pixels
+    #[must_use]
+    pub fn framebuffer_size(&self) -> (u32, u32) {
+        let buffer = self.pixel_buffer.borrow();
+        (buffer.width(), buffer.height())
+    }
+
+    /// Returns a copy of the current frame as `(width, height, bgra_pixels)`
+    #[must_use]
+    pub fn framebuffer_snapshot(&self) -> (u32, u32, Vec<u8>) {
+        let buffer = self.pixel_buffer.borrow();
+        (buffer.width(), buffer.height(), buffer.data().to_vec())
+    }
+
     /// Returns the current width
     #[must_use]
     pub fn width(&self) -> u32 {
@@ -1037,13 +1051,22 @@ impl EmbeddedVncWidget {
         let vnc_config = VncClientConfig::new(&config.host)
             .with_port(config.port)
             .with_shared(true)
-            .with_view_only(config.view_only);
+            .with_view_only(config.view_only)
+            .with_clipboard_enabled(config.clipboard_enabled);
 
         let vnc_config = if let Some(ref password) = config.password {
             vnc_config.with_password(password)
         } else {
             vnc_config
         };
+        let vnc_config = match config.compression {
+            Some(level) => vnc_config.with_compression(level),
+            None => vnc_config,
+        };
+        let vnc_config = match config.quality {
+            Some(level) => vnc_config.with_quality(level),
+            None => vnc_config,
+        };
 
         // Create the VNC client and connect (spawns background thread)
         let mut client = VncClient::new(vnc_config);
@@ -1206,8 +1229,11 @@ impl EmbeddedVncWidget {
                     VncClientEvent::Bell => {
                         // Could play a sound or show notification
                     }
-                    VncClientEvent::ClipboardText(_text) => {
-                        // Could sync with system clipboard
+                    #[allow(deprecated)]
+                    VncClientEvent::ServerCutText(text) | VncClientEvent::ClipboardText(text) => {
+                        // Mirror the remote clipboard locally
+                        tracing::debug!("[VNC] Server clipboard has {} chars", text.len());
+                        drawing_area.display().clipboard().set_text(&text);
                     }
                     VncClientEvent::CursorUpdate { .. } => {
                         // Could update cursor shape
@@ -1215,6 +1241,20 @@ impl EmbeddedVncWidget {
                     VncClientEvent::AuthRequired => {
                         // Authentication is handled during connection
                     }
+                    VncClientEvent::QualityChanged {
+                        compression,
+                        quality,
+                    } => {
+                        tracing::debug!(
+                            "[VNC] Quality changed: compression={compression}, quality={quality}"
+                        );
+                    }
+                    VncClientEvent::FrameCaptured(png) => {
+                        tracing::debug!("[VNC] Captured frame: {} bytes", png.len());
+                    }
+                    VncClientEvent::CaptureFailed(msg) => {
+                        tracing::warn!("[VNC] Frame capture failed: {}", msg);
+                    }
                 }
             }
 
@@ -1244,13 +1284,14 @@ impl EmbeddedVncWidget {
         let mut cmd = Command::new(&binary);
 
         // Build server address based on port
+        let host = rustconn_core::host_for_uri(&config.host);
         let server = if config.port == 5900 {
-            format!("{}:0", config.host)
+            format!("{host}:0")
         } else if config.port > 5900 && config.port < 6000 {
             let display = config.port - 5900;
-            format!("{}:{display}", config.host)
+            format!("{host}:{display}")
         } else {
-            format!("{}::{}", config.host, config.port)
+            format!("{host}::{}", config.port)
         };
 
         // Add viewer-specific arguments based on detected binary
@@ -1595,11 +1636,14 @@ impl EmbeddedVncWidget {
             return;
         }
 
-        // In a real implementation, this would:
-        // 1. Send ClientCutText message to VNC server
-        // rfb_send_client_cut_text(text)
+        #[cfg(feature = "vnc-embedded")]
+        if let Some(ref sender) = *self.command_sender.borrow() {
+            // Use try_send to avoid blocking GTK main thread
+            let _ = sender.try_send(VncClientCommand::SetClipboard(text.to_string()));
+        }
 
-        let _text = text;
+        #[cfg(not(feature = "vnc-embedded"))]
+        let _ = text;
     }
 
     /// Requests a full frame buffer update from the server
@@ -36,6 +36,7 @@ pub mod embedded_vnc_ui;
 pub mod empty_state;
 pub mod external_window;
 pub mod floating_controls;
+pub mod notifier;
 pub mod session;
 mod sidebar;
 mod sidebar_types;
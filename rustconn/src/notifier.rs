@@ -0,0 +1,52 @@
+//! Desktop notifications for core events
+//!
+//! Implements the core `Notifier` trait with `gio::Notification`, sent
+//! through the running application so that session events (idle
+//! disconnects, reconnects, alerts) reach the user under RustConn's own
+//! identity, even when the main window is hidden in the tray. Clicking a
+//! notification activates the application.
+
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use rustconn_core::{Notification, NotificationUrgency, Notifier};
+
+/// Delivers notifications through the desktop notification server
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DesktopNotifier;
+
+impl DesktopNotifier {
+    /// Creates a new desktop notifier
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, notification: &Notification) {
+        let notification = notification.clone();
+        // The application lives on the main thread; this runs right away
+        // when called from it and is queued otherwise
+        glib::MainContext::default().invoke(move || {
+            let Some(app) = gio::Application::default() else {
+                tracing::debug!(
+                    title = %notification.title,
+                    "No application to send the desktop notification"
+                );
+                return;
+            };
+
+            let desktop = gio::Notification::new(&notification.title);
+            desktop.set_body(Some(&notification.body));
+            desktop.set_priority(match notification.urgency {
+                NotificationUrgency::Low => gio::NotificationPriority::Low,
+                NotificationUrgency::Normal => gio::NotificationPriority::Normal,
+                NotificationUrgency::Critical => gio::NotificationPriority::Urgent,
+            });
+
+            // A newer notification about the same connection replaces the older one
+            let id = notification.connection_id.map(|id| id.to_string());
+            app.send_notification(id.as_deref(), &desktop);
+        });
+    }
+}
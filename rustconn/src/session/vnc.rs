@@ -16,7 +16,9 @@ use super::{SessionError, SessionState};
 use crate::embedded_vnc::{EmbeddedVncWidget, VncConfig as EmbeddedVncConfig, VncConnectionState};
 use gtk4::prelude::*;
 use gtk4::{Align, Box as GtkBox, Label, Orientation, Overlay, Spinner};
-use rustconn_core::ffi::{VncCredentialType, VncDisplay};
+use rustconn_core::ffi::{
+    ConnectionState, FfiDisplay, FfiError, VncCredentialType, VncDisplay, VncScalingMode,
+};
 use rustconn_core::models::{VncClientMode, VncConfig};
 use rustconn_core::protocol::{detect_vnc_client, detect_vnc_viewer_name};
 use std::cell::RefCell;
@@ -105,6 +107,12 @@ impl VncSessionWidget {
         embedded_container.set_vexpand(true);
         embedded_container.add_css_class("vnc-display");
 
+        // Framebuffer queries fail cleanly until the display is realized
+        let display_for_realize = display.clone();
+        embedded_container.connect_realize(move |_| display_for_realize.set_realized(true));
+        let display_for_unrealize = display.clone();
+        embedded_container.connect_unrealize(move |_| display_for_unrealize.set_realized(false));
+
         // Create status container for connection feedback
         let status_container = GtkBox::new(Orientation::Vertical, 12);
         status_container.set_halign(Align::Center);
@@ -248,6 +256,7 @@ impl VncSessionWidget {
         let status_label = self.status_label.clone();
         let spinner = self.spinner.clone();
         let state_callback = self.state_callback.clone();
+        let display_for_state = self.display.clone();
 
         // State change callback
         self.embedded_widget
@@ -262,6 +271,12 @@ impl VncSessionWidget {
                 };
 
                 *state.borrow_mut() = session_state.clone();
+                display_for_state.set_connection_state(match vnc_state {
+                    VncConnectionState::Disconnected => ConnectionState::Disconnected,
+                    VncConnectionState::Connecting => ConnectionState::Connecting,
+                    VncConnectionState::Connected => ConnectionState::Connected,
+                    VncConnectionState::Error => ConnectionState::Error,
+                });
 
                 // Hide status label when connected to avoid obstructing the view
                 if vnc_state == VncConnectionState::Connected {
@@ -304,6 +319,17 @@ impl VncSessionWidget {
                 callback(SessionState::Error(error));
             }
         });
+
+        // Keep the reported framebuffer size in step with the server
+        let display = self.display.clone();
+        let embedded_widget = Rc::downgrade(&self.embedded_widget);
+        self.embedded_widget
+            .connect_frame_update(move |_, _, _, _| {
+                if let Some(widget) = embedded_widget.upgrade() {
+                    let (width, height) = widget.framebuffer_size();
+                    display.update_framebuffer_size(width, height);
+                }
+            });
     }
 
     /// Connects to a VNC server
@@ -686,6 +712,7 @@ impl VncSessionWidget {
 
     /// Builds the server address string based on viewer type and port
     fn build_server_address(viewer: &str, host: &str, port: u16) -> String {
+        let host = rustconn_core::host_for_uri(host);
         match viewer {
             "vncviewer" | "tigervnc" | "xvnc4viewer" | "gvncviewer" => {
                 // These viewers use display number format for standard ports
@@ -818,9 +845,9 @@ impl VncSessionWidget {
         Ok(())
     }
 
-    /// Enables or disables display scaling
-    pub fn set_scaling(&self, enabled: bool) {
-        self.display.set_scaling(enabled);
+    /// Sets how the remote display is scaled into the widget
+    pub fn set_scaling(&self, mode: VncScalingMode) {
+        self.display.set_scaling(mode);
     }
 
     /// Returns whether scaling is enabled
@@ -829,6 +856,34 @@ impl VncSessionWidget {
         self.display.scaling_enabled()
     }
 
+    /// Returns the remote framebuffer size, if known
+    ///
+    /// # Errors
+    ///
+    /// Returns an `FfiError` if the display widget is not realized yet.
+    pub fn framebuffer_size(&self) -> Result<Option<(u32, u32)>, FfiError> {
+        FfiDisplay::framebuffer_size(self.display.as_ref())
+    }
+
+    /// Captures the current remote frame as a PNG image
+    ///
+    /// Copies the embedded viewer's latest frame into the display before
+    /// encoding it. External viewers cannot be captured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `FfiError` if the display is not realized, not connected,
+    /// or has not received a frame yet.
+    pub fn capture_frame(&self) -> Result<Vec<u8>, FfiError> {
+        if *self.is_embedded_native.borrow() {
+            let (width, height, pixels) = self.embedded_widget.framebuffer_snapshot();
+            if width > 0 && height > 0 {
+                self.display.update_framebuffer(width, height, pixels);
+            }
+        }
+        FfiDisplay::capture_frame(self.display.as_ref())
+    }
+
     /// Connects a callback for authentication requests
     ///
     /// The callback receives a list of credential types that the server requires.
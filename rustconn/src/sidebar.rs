@@ -28,7 +28,9 @@ use gtk4::{
     ScrolledWindow, SearchEntry, SignalListItemFactory, SingleSelection, TreeExpander,
     TreeListModel, TreeListRow, Widget,
 };
-use rustconn_core::{Debouncer, LazyGroupLoader, SelectionState as CoreSelectionState};
+use rustconn_core::models::{Connection, ConnectionGroup};
+use rustconn_core::search::DebouncedSearchEngine;
+use rustconn_core::{LazyGroupLoader, SelectionState as CoreSelectionState};
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::rc::Rc;
@@ -69,8 +71,8 @@ pub struct ConnectionSidebar {
     /// Lazy group loader for on-demand loading of connection groups
     lazy_loader: Rc<RefCell<LazyGroupLoader>>,
     selection_state: Rc<RefCell<CoreSelectionState>>,
-    /// Debouncer for rate-limiting search operations (100ms delay)
-    search_debouncer: Rc<Debouncer>,
+    /// Debounced, cached search engine (100ms delay)
+    search_engine: Rc<DebouncedSearchEngine>,
     /// Spinner widget to show search is pending during debounce
     search_spinner: gtk4::Spinner,
     /// Pending search query during debounce period
@@ -375,6 +377,37 @@ impl ConnectionSidebar {
                 .and_then(ConnectionItem::children)
         });
 
+        // Prefetch the neighbouring groups whenever a group is expanded; the
+        // tree model inserts the children right after the expanded row
+        let lazy_loader = Rc::new(RefCell::new(LazyGroupLoader::new()));
+        let loader_for_expand = lazy_loader.clone();
+        tree_model.connect_items_changed(move |model, position, removed, added| {
+            let Some(row) = position.checked_sub(1).and_then(|p| model.row(p)) else {
+                return;
+            };
+            let Some(item) = row.item().and_then(|o| o.downcast::<ConnectionItem>().ok()) else {
+                return;
+            };
+            if !item.is_group() {
+                return;
+            }
+            let (Ok(group_id), Ok(mut loader)) = (
+                Uuid::parse_str(&item.id()),
+                loader_for_expand.try_borrow_mut(),
+            ) else {
+                return;
+            };
+            if added > 0 && row.is_expanded() {
+                // The tree is built eagerly, so the group's own prefetched
+                // children are already shown
+                let _ = loader.take_prefetched(group_id);
+                loader.mark_group_loaded(group_id);
+                loader.prefetch(group_id);
+            } else if removed > 0 && !row.is_expanded() {
+                loader.mark_group_unloaded(group_id);
+            }
+        });
+
         // Create selection model (starts in single selection mode)
         let selection_wrapper = SelectionModelWrapper::new_single(tree_model.clone());
         let selection_model = Rc::new(RefCell::new(selection_wrapper));
@@ -592,8 +625,8 @@ impl ConnectionSidebar {
         let (bottom_toolbar, keepass_button) = sidebar_ui::create_sidebar_bottom_toolbar();
         container.append(&bottom_toolbar);
 
-        // Create debouncer for search with 100ms delay
-        let search_debouncer = Rc::new(Debouncer::for_search());
+        // Create debounced search engine with 100ms delay
+        let search_engine = Rc::new(DebouncedSearchEngine::for_search());
 
         Self {
             container,
@@ -610,9 +643,9 @@ impl ConnectionSidebar {
             drop_indicator,
             scrolled_window,
             connection_statuses: Rc::new(RefCell::new(std::collections::HashMap::new())),
-            lazy_loader: Rc::new(RefCell::new(LazyGroupLoader::new())),
+            lazy_loader,
             selection_state: Rc::new(RefCell::new(CoreSelectionState::new())),
-            search_debouncer,
+            search_engine,
             search_spinner,
             pending_search_query: Rc::new(RefCell::new(None)),
             pre_search_state: Rc::new(RefCell::new(None)),
@@ -764,6 +797,12 @@ impl ConnectionSidebar {
                     }
                 }
 
+                let is_archived = list_item_weak
+                    .upgrade()
+                    .and_then(|list_item| list_item.item().and_downcast::<TreeListRow>())
+                    .and_then(|row| row.item().and_downcast::<ConnectionItem>())
+                    .is_some_and(|item| item.archived());
+
                 // Check if this is a group by looking at the icon
                 let is_group = widget
                     .first_child()
@@ -773,7 +812,7 @@ impl ConnectionSidebar {
                         img.icon_name()
                             .is_some_and(|n| n.as_str() == "folder-symbolic")
                     });
-                Self::show_context_menu_for_item(&widget, x, y, is_group);
+                Self::show_context_menu_for_item(&widget, x, y, is_group, is_archived);
             }
         });
         expander.add_controller(gesture);
@@ -834,9 +873,13 @@ impl ConnectionSidebar {
             } else if item.is_group() {
                 icon.set_icon_name(Some("folder-symbolic"));
             } else {
-                let protocol = item.protocol();
-                let icon_name = Self::get_protocol_icon(&protocol);
-                icon.set_icon_name(Some(icon_name));
+                let custom_icon = item.icon();
+                if custom_icon.is_empty() {
+                    let protocol = item.protocol();
+                    icon.set_icon_name(Some(Self::get_protocol_icon(&protocol)));
+                } else {
+                    icon.set_icon_name(Some(&custom_icon));
+                }
             }
         }
 
@@ -884,6 +927,12 @@ impl ConnectionSidebar {
 
         // Update label with dirty indicator for documents
         if let Some(label) = content_box.last_child().and_downcast::<Label>() {
+            // Archived connections are only listed when "Show Archived" is on
+            if item.archived() {
+                label.add_css_class("dim-label");
+            } else {
+                label.remove_css_class("dim-label");
+            }
             let name = item.name();
             if item.is_document() && item.is_dirty() {
                 label.set_text(&format!("• {name}"));
@@ -930,8 +979,14 @@ impl ConnectionSidebar {
     }
 
     /// Shows the context menu for a connection item with group awareness
-    fn show_context_menu_for_item(widget: &impl IsA<Widget>, x: f64, y: f64, is_group: bool) {
-        sidebar_ui::show_context_menu_for_item(widget, x, y, is_group);
+    fn show_context_menu_for_item(
+        widget: &impl IsA<Widget>,
+        x: f64,
+        y: f64,
+        is_group: bool,
+        is_archived: bool,
+    ) {
+        sidebar_ui::show_context_menu_for_item(widget, x, y, is_group, is_archived);
     }
 
     /// Returns the main widget for this sidebar
@@ -946,10 +1001,10 @@ impl ConnectionSidebar {
         &self.search_entry
     }
 
-    /// Returns the search debouncer
+    /// Returns the debounced search engine
     #[must_use]
-    pub fn search_debouncer(&self) -> Rc<Debouncer> {
-        Rc::clone(&self.search_debouncer)
+    pub fn search_engine(&self) -> Rc<DebouncedSearchEngine> {
+        Rc::clone(&self.search_engine)
     }
 
     /// Returns the search spinner widget
@@ -1069,6 +1124,16 @@ impl ConnectionSidebar {
         self.lazy_loader.borrow_mut().reset();
     }
 
+    /// Resets the lazy loading state and sets the groups and connections
+    /// that sibling prefetches read from
+    ///
+    /// Call this whenever the tree is rebuilt.
+    pub fn set_prefetch_source(&self, groups: Vec<ConnectionGroup>, connections: Vec<Connection>) {
+        let mut loader = self.lazy_loader.borrow_mut();
+        loader.reset();
+        loader.set_source(groups.into(), connections.into());
+    }
+
     // ========== Virtual Scrolling Methods ==========
 
     /// Initializes virtual scrolling if the item count exceeds the threshold
@@ -2163,6 +2228,12 @@ mod imp {
         host: RefCell<String>,
         #[property(get, set)]
         status: RefCell<String>,
+        /// Custom icon name; empty means the protocol icon is used
+        #[property(get, set)]
+        icon: RefCell<String>,
+        /// Whether the connection is archived
+        #[property(get, set)]
+        archived: RefCell<bool>,
         pub(super) children: RefCell<Option<gio::ListStore>>,
     }
 
@@ -194,7 +194,13 @@ pub fn create_history_popover(
 }
 
 /// Shows the context menu for a connection item with group awareness
-pub fn show_context_menu_for_item(widget: &impl IsA<gtk4::Widget>, x: f64, y: f64, is_group: bool) {
+pub fn show_context_menu_for_item(
+    widget: &impl IsA<gtk4::Widget>,
+    x: f64,
+    y: f64,
+    is_group: bool,
+    is_archived: bool,
+) {
     // Get the root window to access actions
     let Some(root) = widget.root() else { return };
     let Some(window) = root.downcast_ref::<gtk4::ApplicationWindow>() else {
@@ -310,6 +316,19 @@ pub fn show_context_menu_for_item(widget: &impl IsA<gtk4::Widget>, x: f64, y: f6
         });
         menu_box.append(&move_btn);
 
+        let archive_btn = create_menu_button(if is_archived { "Unarchive" } else { "Archive" });
+        let win = window_clone.clone();
+        let popover_c = popover_ref.clone();
+        archive_btn.connect_clicked(move |_| {
+            if let Some(p) = popover_c.upgrade() {
+                p.popdown();
+            }
+            if let Some(action) = win.lookup_action("toggle-archive-connection") {
+                action.activate(None);
+            }
+        });
+        menu_box.append(&archive_btn);
+
         // Run Snippet option - opens snippet picker for the selected connection
         let snippet_btn = create_menu_button("Run Snippet...");
         let win = window_clone.clone();
@@ -371,7 +390,7 @@ pub fn show_context_menu_for_item(widget: &impl IsA<gtk4::Widget>, x: f64, y: f6
 /// Wrapper around `show_context_menu_for_item` for backward compatibility.
 /// Called from `ConnectionSidebar::show_context_menu`.
 pub fn show_context_menu(widget: &impl IsA<gtk4::Widget>, x: f64, y: f64) {
-    show_context_menu_for_item(widget, x, y, false);
+    show_context_menu_for_item(widget, x, y, false, false);
 }
 
 /// Returns the appropriate icon name for a protocol string
@@ -400,6 +419,7 @@ pub fn get_protocol_icon(protocol: &str) -> &'static str {
             "tailscale" | "tailscale_ssh" => "network-vpn-symbolic", // Tailscale - VPN
             "boundary" => "dialog-password-symbolic",          // Boundary - password/lock
             "generic" => "system-run-symbolic",                // Generic - run command
+            "generic_proxy" => "network-wired-symbolic",       // ProxyCommand - wired link
             _ => "folder-remote-symbolic",                     // Unknown - remote folder
         };
     }
@@ -473,7 +493,7 @@ pub fn create_bulk_actions_bar() -> GtkBox {
 
 /// Creates the sidebar bottom toolbar with secondary actions
 ///
-/// Layout: [Group Ops] [History] [A-Z Sort] [Recent] [Import] [Export] [KeePass]
+/// Layout: [Group Ops] [History] [A-Z Sort] [Recent] [Archived] [Import] [Export] [KeePass]
 #[must_use]
 pub fn create_sidebar_bottom_toolbar() -> (GtkBox, Button) {
     let toolbar = GtkBox::new(Orientation::Horizontal, 4);
@@ -517,6 +537,16 @@ pub fn create_sidebar_bottom_toolbar() -> (GtkBox, Button) {
     )]);
     toolbar.append(&sort_recent_button);
 
+    // Show archived connections toggle
+    let archived_button = gtk4::ToggleButton::new();
+    archived_button.set_icon_name("folder-saved-search-symbolic");
+    archived_button.set_tooltip_text(Some("Show Archived Connections"));
+    archived_button.set_action_name(Some("win.show-archived"));
+    archived_button.update_property(&[gtk4::accessible::Property::Label(
+        "Show archived connections in the list",
+    )]);
+    toolbar.append(&archived_button);
+
     // Import button
     let import_button = Button::from_icon_name("document-open-symbolic");
     import_button.set_tooltip_text(Some("Import Connections (Ctrl+I)"));
@@ -9,8 +9,9 @@ use rustconn_core::models::{ConnectionHistoryEntry, ConnectionStatistics};
 use rustconn_core::{
     AppSettings, AsyncCredentialResolver, AsyncCredentialResult, CancellationToken, Cluster,
     ClusterManager, ConfigManager, Connection, ConnectionGroup, ConnectionManager,
-    CredentialResolver, CredentialVerificationManager, Credentials, Document, DocumentManager,
-    ImportResult, SecretManager, Session, SessionManager, Snippet, SnippetManager,
+    CredentialLookup, CredentialResolver, CredentialVerificationManager, Credentials, Document,
+    DocumentManager, ImportResult, KeePassXcBackend, SecretBackendEvent, SecretBackendType,
+    SecretManager, Session, SessionManager, Snippet, SnippetManager,
 };
 use secrecy::SecretString;
 use std::cell::RefCell;
@@ -95,6 +96,7 @@ impl ConnectionClipboard {
             new_conn.created_at = now;
             new_conn.updated_at = now;
             new_conn.last_connected = None;
+            new_conn.connect_count = 0;
             new_conn
         })
     }
@@ -130,6 +132,26 @@ pub struct CachedCredentials {
     pub domain: String,
 }
 
+/// Why the stored credentials of a connection could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialResolveError {
+    /// The password vault holding the credentials is locked
+    VaultLocked,
+    /// Resolution failed for another reason
+    Failed(String),
+}
+
+impl std::fmt::Display for CredentialResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VaultLocked => {
+                f.write_str("The password vault is locked. Unlock it to use the stored password.")
+            }
+            Self::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
 /// Application state holding all managers
 ///
 /// This struct provides centralized access to all core functionality
@@ -161,8 +183,17 @@ pub struct AppState {
     clipboard: ConnectionClipboard,
     /// Connection history entries
     history_entries: Vec<ConnectionHistoryEntry>,
+    /// Whether the sidebar lists archived connections
+    show_archived: bool,
+    /// Stops the secret backend lock watcher, if one is running
+    lock_watcher: Option<CancellationToken>,
+    /// Connection whose connect waits for the password vault to be unlocked
+    pending_unlock_connect: Option<Uuid>,
 }
 
+/// How often the `KeePassXC` lock state is polled
+const LOCK_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
 impl AppState {
     /// Creates a new application state
     ///
@@ -221,7 +252,7 @@ impl AppState {
             .map_err(|e| format!("Failed to initialize connection manager: {e}"))?;
 
         // Initialize session manager with logging if enabled
-        let session_manager = if settings.logging.enabled {
+        let mut session_manager = if settings.logging.enabled {
             let log_dir = if settings.logging.log_directory.is_absolute() {
                 settings.logging.log_directory.clone()
             } else {
@@ -233,13 +264,14 @@ impl AppState {
         } else {
             SessionManager::new()
         };
+        session_manager.set_notifier(Arc::new(crate::notifier::DesktopNotifier::new()));
 
         // Initialize snippet manager
         let snippet_manager = SnippetManager::new(config_manager.clone())
             .map_err(|e| format!("Failed to initialize snippet manager: {e}"))?;
 
-        // Initialize secret manager (empty for now, backends added later)
-        let secret_manager = SecretManager::empty();
+        // Initialize secret manager from the configured backends
+        let secret_manager = SecretManager::from_settings(&settings.secrets);
 
         // Initialize document manager
         let document_manager = DocumentManager::new();
@@ -267,6 +299,9 @@ impl AppState {
             password_cache: HashMap::new(),
             clipboard: ConnectionClipboard::new(),
             history_entries,
+            show_archived: false,
+            lock_watcher: None,
+            pending_unlock_connect: None,
         })
     }
 
@@ -319,6 +354,72 @@ impl AppState {
         self.password_cache.clear();
     }
 
+    // ========== Secret Backend Lock State ==========
+
+    /// Starts watching the `KeePassXC` database lock state
+    ///
+    /// Only runs when `KeePassXC` is the preferred backend. Lock state changes
+    /// arrive on the returned receiver and should be passed to
+    /// [`Self::handle_secret_backend_event`] on the GTK main thread.
+    pub fn start_lock_watcher(&mut self) -> Option<std::sync::mpsc::Receiver<SecretBackendEvent>> {
+        if self.settings.secrets.preferred_backend != SecretBackendType::KeePassXc {
+            return None;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let backend = Arc::new(KeePassXcBackend::new("rustconn"));
+        let token = with_runtime(|rt| {
+            let _guard = rt.enter();
+            backend.watch_lock_state(LOCK_WATCH_INTERVAL, move |event| {
+                let _ = tx.send(event);
+            })
+        })
+        .map_err(|e| tracing::warn!("Cannot watch KeePassXC lock state: {e}"))
+        .ok()?;
+
+        if let Some(previous) = self.lock_watcher.replace(token) {
+            previous.cancel();
+        }
+        Some(rx)
+    }
+
+    /// Applies a lock state change reported by the secret backend
+    ///
+    /// When the vault locks, the secret manager cache and the session
+    /// password cache are cleared so no credential outlives the vault
+    /// session it was read in. Once the vault is unlocked again, the
+    /// connection deferred by [`Self::defer_connect_until_unlock`] is
+    /// returned so the caller can resume connecting to it.
+    pub fn handle_secret_backend_event(&mut self, event: SecretBackendEvent) -> Option<Uuid> {
+        let secret_manager = self.secret_manager.clone();
+        if let Err(e) = with_runtime(|rt| rt.block_on(secret_manager.handle_backend_event(event))) {
+            tracing::warn!("Failed to apply secret backend event: {e}");
+        }
+        match event {
+            SecretBackendEvent::Locked => {
+                self.password_cache.clear();
+                None
+            }
+            SecretBackendEvent::Relocked => {
+                self.password_cache.clear();
+                self.pending_unlock_connect.take()
+            }
+            SecretBackendEvent::Unlocked => self.pending_unlock_connect.take(),
+        }
+    }
+
+    /// Returns true if lock state changes of the password vault are watched
+    pub const fn is_watching_lock_state(&self) -> bool {
+        self.lock_watcher.is_some()
+    }
+
+    /// Remembers a connection to resume once the password vault is unlocked
+    ///
+    /// Only the most recent connection is kept.
+    pub fn defer_connect_until_unlock(&mut self, connection_id: Uuid) {
+        self.pending_unlock_connect = Some(connection_id);
+    }
+
     // ========== Credential Verification Operations ==========
 
     /// Marks credentials as verified for a connection after successful authentication
@@ -486,6 +587,11 @@ impl AppState {
         self.connection_manager.list_connections()
     }
 
+    /// Returns the search index over all connections
+    pub const fn search_index(&self) -> &rustconn_core::search::SearchIndex {
+        self.connection_manager.search_index()
+    }
+
     /// Searches connections
     ///
     /// Note: Part of connection search API.
@@ -495,13 +601,42 @@ impl AppState {
     }
 
     /// Gets connections by group
+    ///
+    /// Archived connections are left out unless [`Self::show_archived`] is set.
     pub fn get_connections_by_group(&self, group_id: Uuid) -> Vec<&Connection> {
-        self.connection_manager.get_by_group(group_id)
+        let mut connections = self.connection_manager.get_by_group(group_id);
+        connections.retain(|c| self.show_archived || !c.archived);
+        connections
     }
 
     /// Gets ungrouped connections
+    ///
+    /// Archived connections are left out unless [`Self::show_archived`] is set.
     pub fn get_ungrouped_connections(&self) -> Vec<&Connection> {
-        self.connection_manager.get_ungrouped()
+        let mut connections = self.connection_manager.get_ungrouped();
+        connections.retain(|c| self.show_archived || !c.archived);
+        connections
+    }
+
+    /// Returns whether the sidebar lists archived connections
+    #[must_use]
+    pub const fn show_archived(&self) -> bool {
+        self.show_archived
+    }
+
+    /// Sets whether the sidebar lists archived connections
+    pub fn set_show_archived(&mut self, show: bool) {
+        self.show_archived = show;
+    }
+
+    /// Archives or restores a connection
+    pub fn set_connection_archived(&mut self, id: Uuid, archived: bool) -> Result<(), String> {
+        let result = if archived {
+            self.connection_manager.archive(id)
+        } else {
+            self.connection_manager.unarchive(id)
+        };
+        result.map_err(|e| format!("Failed to archive connection: {e}"))
     }
 
     // ========== Group Operations ==========
@@ -590,6 +725,17 @@ impl AppState {
             .map_err(|e| format!("Failed to move connection: {e}"))
     }
 
+    /// Moves several connections to a group in one save
+    pub fn move_connections_to_group(
+        &mut self,
+        connection_ids: &[Uuid],
+        group_id: Option<Uuid>,
+    ) -> Result<(), String> {
+        self.connection_manager
+            .move_to_group(connection_ids, group_id)
+            .map_err(|e| format!("Failed to move connections: {e}"))
+    }
+
     /// Gets the group path
     pub fn get_group_path(&self, group_id: Uuid) -> Option<String> {
         self.connection_manager.get_group_path(group_id)
@@ -679,11 +825,22 @@ impl AppState {
             .map_err(|e| format!("Failed to reorder group: {e}"))
     }
 
-    /// Updates the `last_connected` timestamp for a connection
-    pub fn update_last_connected(&mut self, connection_id: Uuid) -> Result<(), String> {
+    /// Records a successful connection (`last_connected` and `connect_count`)
+    pub fn record_connect(&mut self, connection_id: Uuid) -> Result<(), String> {
+        self.connection_manager
+            .record_connect(connection_id)
+            .map_err(|e| format!("Failed to record connection: {e}"))
+    }
+
+    /// Records a successful connection established at `connected_at`
+    pub fn record_connect_at(
+        &mut self,
+        connection_id: Uuid,
+        connected_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), String> {
         self.connection_manager
-            .update_last_connected(connection_id)
-            .map_err(|e| format!("Failed to update last connected: {e}"))
+            .record_connect_at(connection_id, connected_at)
+            .map_err(|e| format!("Failed to record connection: {e}"))
     }
 
     /// Sorts all connections by `last_connected` timestamp (most recent first)
@@ -744,9 +901,6 @@ impl AppState {
     }
 
     /// Gets mutable session manager
-    ///
-    /// Note: Part of session management API.
-    #[allow(dead_code)]
     pub fn session_manager_mut(&mut self) -> &mut SessionManager {
         &mut self.session_manager
     }
@@ -1093,9 +1247,10 @@ impl AppState {
     #[must_use]
     pub fn create_async_resolver(&self) -> AsyncCredentialResolver {
         AsyncCredentialResolver::new(
-            Arc::new(SecretManager::empty()),
+            Arc::new(self.secret_manager.clone()),
             self.settings.secrets.clone(),
         )
+        .with_groups(self.list_groups().into_iter().cloned().collect())
     }
 
     /// Resolves credentials asynchronously without blocking the UI
@@ -1251,13 +1406,15 @@ impl AppState {
     /// ```
     pub fn resolve_credentials_gtk<F>(&self, connection_id: Uuid, callback: F)
     where
-        F: FnOnce(Result<Option<Credentials>, String>) + 'static,
+        F: FnOnce(Result<Option<Credentials>, CredentialResolveError>) + 'static,
     {
         // Get connection and settings needed for resolution
         let connection = if let Some(conn) = self.get_connection(connection_id) {
             conn.clone()
         } else {
-            callback(Err(format!("Connection not found: {connection_id}")));
+            callback(Err(CredentialResolveError::Failed(format!(
+                "Connection not found: {connection_id}"
+            ))));
             return;
         };
 
@@ -1268,12 +1425,14 @@ impl AppState {
         let kdbx_key_file = self.settings.secrets.kdbx_key_file.clone();
         let secret_settings = self.settings.secrets.clone();
         let secret_manager = self.secret_manager.clone();
+        let groups: Vec<ConnectionGroup> = self.list_groups().into_iter().cloned().collect();
 
         // Spawn blocking operation in background thread
         crate::utils::spawn_blocking_with_callback(
             move || {
                 Self::resolve_credentials_blocking(
                     &connection,
+                    &groups,
                     kdbx_enabled,
                     kdbx_path,
                     kdbx_password,
@@ -1290,15 +1449,17 @@ impl AppState {
     ///
     /// This is extracted from `resolve_credentials` to be callable from a background
     /// thread without needing `&self`.
+    #[allow(clippy::too_many_arguments)]
     fn resolve_credentials_blocking(
         connection: &Connection,
+        groups: &[ConnectionGroup],
         kdbx_enabled: bool,
         kdbx_path: Option<std::path::PathBuf>,
         kdbx_password: Option<SecretString>,
         kdbx_key_file: Option<std::path::PathBuf>,
         secret_settings: rustconn_core::config::SecretSettings,
         secret_manager: SecretManager,
-    ) -> Result<Option<Credentials>, String> {
+    ) -> Result<Option<Credentials>, CredentialResolveError> {
         use rustconn_core::secret::KeePassStatus;
         use secrecy::ExposeSecret;
 
@@ -1366,14 +1527,27 @@ impl AppState {
         let connection = connection.clone();
 
         // Create a new runtime for this thread (background thread doesn't have one)
-        let rt =
-            tokio::runtime::Runtime::new().map_err(|e| format!("Failed to create runtime: {e}"))?;
+        let rt = tokio::runtime::Runtime::new().map_err(|e| {
+            CredentialResolveError::Failed(format!("Failed to create runtime: {e}"))
+        })?;
 
         rt.block_on(async {
-            resolver
-                .resolve(&connection)
-                .await
-                .map_err(|e| format!("Failed to resolve credentials: {e}"))
+            // Check the stored entry first, so a locked vault is reported
+            // instead of silently falling through to the password prompt
+            match resolver.check(&connection, groups).await {
+                Some(CredentialLookup::BackendLocked) => {
+                    return Err(CredentialResolveError::VaultLocked);
+                }
+                Some(CredentialLookup::Failed(e)) => {
+                    return Err(CredentialResolveError::Failed(format!(
+                        "Stored password unavailable: {e}"
+                    )));
+                }
+                lookup => tracing::debug!(?lookup, "Stored credential check"),
+            }
+            resolver.resolve(&connection).await.map_err(|e| {
+                CredentialResolveError::Failed(format!("Failed to resolve credentials: {e}"))
+            })
         })
     }
 
@@ -1427,6 +1601,13 @@ impl AppState {
                 .set_logging_enabled(settings.logging.enabled);
         }
 
+        // Rebuild the secret manager when the backend selection changes
+        if settings.secrets.preferred_backend != self.settings.secrets.preferred_backend
+            || settings.secrets.enable_fallback != self.settings.secrets.enable_fallback
+        {
+            self.secret_manager = SecretManager::from_settings(&settings.secrets);
+        }
+
         self.settings = settings;
         Ok(())
     }
@@ -1595,6 +1776,13 @@ impl AppState {
 
             // Set the group
             connection.group_id = target_group_id;
+            connection.additional_group_ids = conn
+                .additional_group_ids
+                .iter()
+                .filter_map(|id| group_uuid_map.get(id).copied())
+                .filter(|&id| Some(id) != target_group_id)
+                .collect();
+            connection.additional_group_ids.dedup();
 
             // Auto-resolve name conflicts using protocol-aware naming
             if self.connection_exists_by_name(&connection.name) {
@@ -18,8 +18,9 @@ pub use types::{SessionWidgetStorage, TerminalSession};
 use gtk4::prelude::*;
 use gtk4::{gio, glib, Box as GtkBox, Orientation, Widget};
 use libadwaita as adw;
-use regex::Regex;
+use rustconn_core::automation::CompiledRule;
 use rustconn_core::models::AutomationConfig;
+use rustconn_core::session::SecretWindow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -53,6 +54,9 @@ pub struct TerminalNotebook {
     session_widgets: Rc<RefCell<HashMap<Uuid, SessionWidgetStorage>>>,
     /// Map of session IDs to automation sessions
     automation_sessions: Rc<RefCell<HashMap<Uuid, AutomationSession>>>,
+    /// Map of session IDs to the windows that pause their logs while
+    /// automation sends a secret
+    secret_windows: Rc<RefCell<HashMap<Uuid, SecretWindow>>>,
     /// Session metadata
     session_info: Rc<RefCell<HashMap<Uuid, TerminalSession>>>,
     /// Tab split manager for managing split layouts per tab
@@ -105,6 +109,7 @@ impl TerminalNotebook {
             terminals: Rc::new(RefCell::new(HashMap::new())),
             session_widgets: Rc::new(RefCell::new(HashMap::new())),
             automation_sessions: Rc::new(RefCell::new(HashMap::new())),
+            secret_windows: Rc::new(RefCell::new(HashMap::new())),
             session_info: Rc::new(RefCell::new(HashMap::new())),
             split_manager: Rc::new(RefCell::new(TabSplitManager::new())),
             session_tab_ids: Rc::new(RefCell::new(HashMap::new())),
@@ -119,6 +124,7 @@ impl TerminalNotebook {
         let sessions = self.sessions.clone();
         let terminals = self.terminals.clone();
         let session_widgets = self.session_widgets.clone();
+        let secret_windows = self.secret_windows.clone();
         let session_info = self.session_info.clone();
         let tab_view = self.tab_view.clone();
         let split_manager = self.split_manager.clone();
@@ -157,6 +163,7 @@ impl TerminalNotebook {
                 // Clean up session data
                 sessions.borrow_mut().remove(&session_id);
                 terminals.borrow_mut().remove(&session_id);
+                secret_windows.borrow_mut().remove(&session_id);
 
                 // Disconnect embedded widgets before removing
                 if let Some(widget_storage) = session_widgets.borrow_mut().remove(&session_id) {
@@ -212,6 +219,7 @@ impl TerminalNotebook {
                 "tailscale" | "tailscale_ssh" => "network-vpn-symbolic",
                 "boundary" => "dialog-password-symbolic",
                 "generic" => "system-run-symbolic",
+                "generic_proxy" => "network-wired-symbolic",
                 _ => "folder-remote-symbolic",
             };
         }
@@ -258,6 +266,21 @@ impl TerminalNotebook {
         )
     }
 
+    /// Tells the user which expect rules of a session could not be used
+    fn warn_skipped_rules(&self, title: &str, errors: &[String]) {
+        let Some(root) = self.widget().root() else {
+            return;
+        };
+        if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+            let message = format!(
+                "{title}: skipped {} expect rule(s): {}",
+                errors.len(),
+                errors.join("; ")
+            );
+            crate::toast::show_toast_on_window(window, &message, crate::toast::ToastType::Warning);
+        }
+    }
+
     /// Creates a new terminal tab with specific settings
     pub fn create_terminal_tab_with_settings(
         &self,
@@ -274,25 +297,45 @@ impl TerminalNotebook {
         terminal.set_hexpand(true);
         terminal.set_vexpand(true);
 
+        let secret_window = SecretWindow::new();
+        self.secret_windows
+            .borrow_mut()
+            .insert(session_id, secret_window.clone());
+
         // Setup automation if configured
         if let Some(cfg) = automation {
             if !cfg.expect_rules.is_empty() {
                 let mut triggers = Vec::new();
+                let mut prompt_steps = Vec::new();
+                let mut skipped = Vec::new();
                 for rule in &cfg.expect_rules {
                     if !rule.enabled {
                         continue;
                     }
-                    if let Ok(regex) = Regex::new(&rule.pattern) {
-                        triggers.push(Trigger {
-                            pattern: regex,
-                            response: rule.response.clone(),
+                    match CompiledRule::new(rule.clone()) {
+                        // Wait-for-prompt rules run as ordered steps instead
+                        Ok(_) if rule.is_wait_for_prompt() => prompt_steps.push(rule.clone()),
+                        Ok(compiled) => triggers.push(Trigger {
+                            rule: compiled,
                             one_shot: true,
-                        });
+                        }),
+                        Err(e) => {
+                            tracing::warn!("Skipping expect rule: {e}");
+                            skipped.push(e.to_string());
+                        }
                     }
                 }
+                if !skipped.is_empty() {
+                    self.warn_skipped_rules(title, &skipped);
+                }
 
-                if !triggers.is_empty() {
-                    let session = AutomationSession::new(terminal.clone(), triggers);
+                if !triggers.is_empty() || !prompt_steps.is_empty() {
+                    let session = AutomationSession::new(
+                        terminal.clone(),
+                        triggers,
+                        prompt_steps,
+                        secret_window,
+                    );
                     self.automation_sessions
                         .borrow_mut()
                         .insert(session_id, session);
@@ -663,6 +706,9 @@ impl TerminalNotebook {
     }
 
     /// Spawns an SSH command in the terminal
+    ///
+    /// `envv` holds `NAME=value` entries added to the environment of `ssh`.
+    #[allow(clippy::too_many_arguments)]
     pub fn spawn_ssh(
         &self,
         session_id: Uuid,
@@ -671,6 +717,7 @@ impl TerminalNotebook {
         username: Option<&str>,
         identity_file: Option<&str>,
         extra_args: &[&str],
+        envv: Option<&[&str]>,
     ) -> bool {
         let mut argv = vec!["ssh"];
 
@@ -688,6 +735,8 @@ impl TerminalNotebook {
 
         argv.extend(extra_args);
 
+        // ssh takes IPv6 literals unbracketed
+        let host = rustconn_core::unbracketed_host(host);
         let destination = if let Some(user) = username {
             format!("{user}@{host}")
         } else {
@@ -695,7 +744,7 @@ impl TerminalNotebook {
         };
         argv.push(&destination);
 
-        self.spawn_command(session_id, &argv, None, None)
+        self.spawn_command(session_id, &argv, envv, None)
     }
 
     /// Closes a terminal tab by session ID
@@ -705,6 +754,23 @@ impl TerminalNotebook {
         }
     }
 
+    /// Ends a terminal's child process by closing its PTY
+    ///
+    /// The process gets `SIGHUP` as if the tab were closed, but the tab and
+    /// its scrollback stay open.
+    pub fn hang_up(&self, session_id: Uuid) {
+        if let Some(terminal) = self.get_terminal(session_id) {
+            terminal.set_pty(None::<&vte4::Pty>);
+        }
+    }
+
+    /// Replaces the protocol icon of a tab with a custom icon name
+    pub fn set_tab_icon(&self, session_id: Uuid, icon_name: &str) {
+        if let Some(page) = self.sessions.borrow().get(&session_id) {
+            page.set_icon(Some(&gio::ThemedIcon::new(icon_name)));
+        }
+    }
+
     /// Marks a tab as disconnected (changes indicator)
     pub fn mark_tab_disconnected(&self, session_id: Uuid) {
         if let Some(page) = self.sessions.borrow().get(&session_id) {
@@ -845,6 +911,13 @@ impl TerminalNotebook {
         }
     }
 
+    /// Returns the window that pauses a session's log while automation
+    /// sends a secret
+    #[must_use]
+    pub fn secret_window(&self, session_id: Uuid) -> Option<SecretWindow> {
+        self.secret_windows.borrow().get(&session_id).cloned()
+    }
+
     /// Sets the history entry ID for a session
     pub fn set_history_entry_id(&self, session_id: Uuid, history_entry_id: Uuid) {
         if let Some(info) = self.session_info.borrow_mut().get_mut(&session_id) {
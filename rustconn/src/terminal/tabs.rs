@@ -31,6 +31,7 @@ pub fn get_protocol_icon(protocol: &str) -> &'static str {
             "tailscale" | "tailscale_ssh" => "network-vpn-symbolic",
             "boundary" => "dialog-password-symbolic",
             "generic" => "system-run-symbolic",
+            "generic_proxy" => "network-wired-symbolic",
             _ => "folder-remote-symbolic",
         };
     }
@@ -6,6 +6,23 @@
 use gtk4::gdk;
 use std::sync::LazyLock;
 
+/// Multi-threaded tokio runtime shared by background operations
+static SHARED_RUNTIME: LazyLock<Result<tokio::runtime::Runtime, String>> = LazyLock::new(|| {
+    tokio::runtime::Runtime::new().map_err(|e| format!("Failed to create runtime: {e}"))
+});
+
+/// Returns the process-wide tokio runtime for background operations
+///
+/// Building a runtime spawns worker threads, so background closures should
+/// use this instead of creating a runtime per call.
+///
+/// # Errors
+///
+/// Returns an error if the runtime could not be created.
+pub fn shared_runtime() -> Result<&'static tokio::runtime::Runtime, String> {
+    SHARED_RUNTIME.as_ref().map_err(Clone::clone)
+}
+
 /// Gets the default GDK display, returning None if unavailable
 ///
 /// This is safer than using `gdk::Display::default().expect(...)` which
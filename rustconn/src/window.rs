@@ -34,7 +34,7 @@ use crate::dialogs::{ExportDialog, SettingsDialog};
 use crate::external_window::ExternalWindowManager;
 use crate::sidebar::{ConnectionItem, ConnectionSidebar};
 use crate::split_view::{SplitDirection, SplitViewBridge, TabSplitManager};
-use crate::state::SharedAppState;
+use crate::state::{CredentialResolveError, SharedAppState};
 use crate::terminal::TerminalNotebook;
 use rustconn_core::split::ColorPool;
 
@@ -44,6 +44,9 @@ type SharedColorPool = Rc<RefCell<ColorPool>>;
 /// Shared toast overlay reference
 pub type SharedToastOverlay = Rc<ToastOverlay>;
 
+/// Seconds between checks for idle terminal sessions
+const IDLE_REAP_INTERVAL_SECS: u32 = 30;
+
 /// Main application window wrapper
 ///
 /// Provides access to the main window and its components.
@@ -229,6 +232,8 @@ impl MainWindow {
         // Connect signals
         main_window.connect_signals();
 
+        main_window.start_idle_reaper();
+
         main_window
     }
 
@@ -345,7 +350,8 @@ impl MainWindow {
         let settings = state.borrow().settings().clone();
         let action_enabled = match settings.secrets.preferred_backend {
             rustconn_core::config::SecretBackendType::LibSecret
-            | rustconn_core::config::SecretBackendType::Bitwarden => true,
+            | rustconn_core::config::SecretBackendType::Bitwarden
+            | rustconn_core::config::SecretBackendType::Pass => true,
             rustconn_core::config::SecretBackendType::KeePassXc
             | rustconn_core::config::SecretBackendType::KdbxFile => {
                 settings.secrets.kdbx_enabled
@@ -388,6 +394,30 @@ impl MainWindow {
         });
         window.add_action(&connect_action);
 
+        // Connect by ID action - resolves credentials, then connects
+        let connect_by_id_action =
+            gio::SimpleAction::new("connect-by-id", Some(glib::VariantTy::STRING));
+        let state_clone = state.clone();
+        let sidebar_clone = sidebar.clone();
+        let notebook_clone = self.terminal_notebook.clone();
+        let split_view_clone = self.split_view.clone();
+        connect_by_id_action.connect_activate(move |_, param| {
+            let Some(connection_id) = param
+                .and_then(glib::Variant::get::<String>)
+                .and_then(|id| Uuid::parse_str(&id).ok())
+            else {
+                return;
+            };
+            Self::start_connection_with_credential_resolution(
+                state_clone.clone(),
+                notebook_clone.clone(),
+                split_view_clone.clone(),
+                sidebar_clone.clone(),
+                connection_id,
+            );
+        });
+        window.add_action(&connect_by_id_action);
+
         // Edit connection action
         let edit_action = gio::SimpleAction::new("edit-connection", None);
         let window_weak = window.downgrade();
@@ -424,6 +454,38 @@ impl MainWindow {
         });
         window.add_action(&duplicate_action);
 
+        // Archive/unarchive connection action
+        let archive_action = gio::SimpleAction::new("toggle-archive-connection", None);
+        let window_weak = window.downgrade();
+        let state_clone = state.clone();
+        let sidebar_clone = sidebar.clone();
+        archive_action.connect_activate(move |_, _| {
+            if let Some(win) = window_weak.upgrade() {
+                Self::toggle_archive_selected_connection(
+                    win.upcast_ref(),
+                    &state_clone,
+                    &sidebar_clone,
+                );
+            }
+        });
+        window.add_action(&archive_action);
+
+        // Show archived connections in the sidebar (toggle)
+        let show_archived_action =
+            gio::SimpleAction::new_stateful("show-archived", None, &false.to_variant());
+        let state_clone = state.clone();
+        let sidebar_clone = sidebar.clone();
+        show_archived_action.connect_activate(move |action, _| {
+            let show = !action
+                .state()
+                .and_then(|v| v.get::<bool>())
+                .unwrap_or(false);
+            action.set_state(&show.to_variant());
+            state_clone.borrow_mut().set_show_archived(show);
+            Self::reload_sidebar(&state_clone, &sidebar_clone);
+        });
+        window.add_action(&show_archived_action);
+
         // Move to group action
         let move_to_group_action = gio::SimpleAction::new("move-to-group", None);
         let window_weak = window.downgrade();
@@ -2049,17 +2111,10 @@ impl MainWindow {
                 sidebar_clone.save_pre_search_state();
             }
 
-            // Check if we should debounce
-            let debouncer = sidebar_clone.search_debouncer();
-            if debouncer.should_proceed() {
-                // Immediate search - hide spinner and filter
+            if Self::filter_connections(&state_clone, &sidebar_clone, &query, false) {
+                // Searched immediately - hide spinner
                 sidebar_clone.hide_search_pending();
-                Self::filter_connections(&state_clone, &sidebar_clone, &query);
-
-                // Restore state if search cleared
-                if query.is_empty() {
-                    sidebar_clone.restore_pre_search_state();
-                }
+                sidebar_clone.set_pending_search_query(None);
             } else {
                 // Debounced - show spinner and schedule search
                 sidebar_clone.show_search_pending();
@@ -2068,7 +2123,7 @@ impl MainWindow {
                 // Schedule delayed search using glib timeout
                 let state_for_timeout = state_clone.clone();
                 let sidebar_for_timeout = sidebar_clone.clone();
-                let delay_ms = debouncer.delay().as_millis() as u32;
+                let delay_ms = sidebar_clone.search_engine().delay().as_millis() as u32;
 
                 glib::timeout_add_local_once(
                     std::time::Duration::from_millis(u64::from(delay_ms)),
@@ -2082,12 +2137,8 @@ impl MainWindow {
                                     &state_for_timeout,
                                     &sidebar_for_timeout,
                                     &pending,
+                                    true,
                                 );
-
-                                // Restore state if search cleared
-                                if pending.is_empty() {
-                                    sidebar_for_timeout.restore_pre_search_state();
-                                }
                             }
                         }
                     },
@@ -2304,7 +2355,8 @@ impl MainWindow {
         // For KeePassXC/KdbxFile, check if enabled and database exists
         let (enabled, database_exists) = match backend {
             rustconn_core::config::SecretBackendType::LibSecret
-            | rustconn_core::config::SecretBackendType::Bitwarden => (true, true),
+            | rustconn_core::config::SecretBackendType::Bitwarden
+            | rustconn_core::config::SecretBackendType::Pass => (true, true),
             rustconn_core::config::SecretBackendType::KeePassXc
             | rustconn_core::config::SecretBackendType::KdbxFile => {
                 let kdbx_enabled = settings.secrets.kdbx_enabled;
@@ -2405,7 +2457,17 @@ impl MainWindow {
     }
 
     /// Filters connections based on search query
-    fn filter_connections(state: &SharedAppState, sidebar: &SharedSidebar, query: &str) {
+    ///
+    /// Text queries go through the sidebar's debounced search engine and the
+    /// connection search index. Unless `immediate` is set, a query arriving
+    /// too soon after the previous one is debounced: nothing changes and
+    /// `false` is returned, so the caller should retry after the delay.
+    fn filter_connections(
+        state: &SharedAppState,
+        sidebar: &SharedSidebar,
+        query: &str,
+        immediate: bool,
+    ) -> bool {
         use rustconn_core::search::SearchEngine;
 
         if query.is_empty() {
@@ -2413,14 +2475,13 @@ impl MainWindow {
             Self::reload_sidebar(state, sidebar);
             // Restore the tree state that was saved before search started
             sidebar.restore_pre_search_state();
-            return;
+            return true;
         }
 
         // Save tree state before first search keystroke
         sidebar.save_pre_search_state();
 
         let store = sidebar.store();
-        store.remove_all();
 
         let state_ref = state.borrow();
 
@@ -2432,14 +2493,16 @@ impl MainWindow {
             .cloned()
             .collect();
         let groups: Vec<_> = state_ref.list_groups().iter().cloned().cloned().collect();
+        let show_archived = state_ref.show_archived();
 
         // Check for special multiple protocol filter syntax
         if let Some(protocols_str) = query.strip_prefix("protocols:") {
             // Handle multiple protocol filters with OR logic
             let protocol_names: Vec<&str> = protocols_str.split(',').collect();
             let mut filtered_connections = Vec::new();
+            store.remove_all();
 
-            for conn in &connections {
+            for conn in connections.iter().filter(|c| show_archived || !c.archived) {
                 let protocol = get_protocol_string(&conn.protocol_config);
                 let protocol_lower = protocol.to_lowercase();
 
@@ -2461,11 +2524,15 @@ impl MainWindow {
                     &protocol,
                     &conn.host,
                 );
+                if let Some(icon) = &conn.icon {
+                    item.set_icon(icon.as_str());
+                }
+                item.set_archived(conn.archived);
                 store.append(&item);
             }
         } else {
-            // Use standard search engine for other queries
-            let search_engine = SearchEngine::new();
+            // Use the debounced search engine for other queries
+            let search_engine = sidebar.search_engine();
             let parsed_query = match SearchEngine::parse_query(query) {
                 Ok(q) => q,
                 Err(_) => {
@@ -2474,8 +2541,19 @@ impl MainWindow {
                 }
             };
 
-            // Perform search with ranking
-            let results = search_engine.search(&parsed_query, &connections, &groups);
+            // Perform search with ranking, narrowed by the search index
+            let index = state_ref.search_index();
+            let lookup = |id| state_ref.get_connection(id);
+            let results = if immediate {
+                search_engine.search_indexed(&parsed_query, lookup, &groups, index)
+            } else {
+                match search_engine.search_debounced_indexed(&parsed_query, lookup, &groups, index)
+                {
+                    Some(results) => results,
+                    None => return false,
+                }
+            };
+            store.remove_all();
 
             // Display results sorted by relevance
             for result in results {
@@ -2499,10 +2577,15 @@ impl MainWindow {
                         &protocol,
                         &conn.host,
                     );
+                    if let Some(icon) = &conn.icon {
+                        item.set_icon(icon.as_str());
+                    }
+                    item.set_archived(conn.archived);
                     store.append(&item);
                 }
             }
         }
+        true
     }
 
     /// Connects to the selected connection
@@ -2632,10 +2715,34 @@ impl MainWindow {
             };
 
             state_ref.resolve_credentials_gtk(connection_id, move |result| {
+                let show_warning = |message: &str| {
+                    if let Some(root) = notebook_clone.widget().root() {
+                        if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                            crate::toast::show_toast_on_window(
+                                window,
+                                message,
+                                crate::toast::ToastType::Warning,
+                            );
+                        }
+                    }
+                };
                 let resolved_credentials = match result {
                     Ok(creds) => creds,
+                    Err(CredentialResolveError::VaultLocked)
+                        if state_clone.borrow().is_watching_lock_state() =>
+                    {
+                        // Resumed by the lock watcher once the vault is unlocked
+                        state_clone
+                            .borrow_mut()
+                            .defer_connect_until_unlock(connection_id);
+                        show_warning(
+                            "The password vault is locked. Unlock it to continue connecting.",
+                        );
+                        return;
+                    }
                     Err(e) => {
                         tracing::warn!("Failed to resolve credentials: {e}");
+                        show_warning(&e.to_string());
                         None
                     }
                 };
@@ -3234,7 +3341,7 @@ impl MainWindow {
         connection_name: &str,
     ) {
         // Get the log directory and logging modes from settings
-        let (log_dir, log_activity, log_input, log_output) =
+        let (log_dir, log_activity, log_input, log_output, redactor) =
             if let Ok(state_ref) = state.try_borrow() {
                 let settings = state_ref.settings();
                 let dir = if settings.logging.log_directory.is_absolute() {
@@ -3245,11 +3352,13 @@ impl MainWindow {
                         .config_dir()
                         .join(&settings.logging.log_directory)
                 };
+                let connection = state_ref.get_connection(connection_id);
                 (
                     dir,
                     settings.logging.log_activity,
                     settings.logging.log_input,
                     settings.logging.log_output,
+                    Self::session_log_redactor(connection, settings.logging.log_input),
                 )
             } else {
                 return;
@@ -3339,6 +3448,7 @@ impl MainWindow {
                             log_activity,
                             log_input,
                             log_output,
+                            redactor,
                         );
                     }
                     Err(e) => {
@@ -3349,6 +3459,30 @@ impl MainWindow {
         );
     }
 
+    /// Creates the secret redactor for a session log
+    ///
+    /// Uses the connection's redaction settings. Invalid patterns fall back
+    /// to the built-in ones rather than turning redaction off.
+    fn session_log_redactor(
+        connection: Option<&rustconn_core::Connection>,
+        log_input: bool,
+    ) -> Option<rustconn_core::session::SecretRedactor> {
+        use rustconn_core::session::{LogConfig, SecretRedactor};
+
+        let config = LogConfig {
+            log_input,
+            ..connection
+                .and_then(|c| c.log_config.clone())
+                .unwrap_or_default()
+        };
+        let sensitive = connection
+            .is_some_and(|c| c.password_source != rustconn_core::models::PasswordSource::None);
+        config.redactor(sensitive).unwrap_or_else(|e| {
+            tracing::warn!("Invalid session log redaction pattern: {e}");
+            SecretRedactor::new(&[]).ok()
+        })
+    }
+
     /// Sets up the child exited handler for session cleanup
     pub fn setup_child_exited_handler(
         state: &SharedAppState,
@@ -3429,12 +3563,107 @@ impl MainWindow {
         });
     }
 
+    /// Records a connect for a terminal session once its `ssh` process exits
+    /// without `ssh`'s own error status
+    ///
+    /// Unreachable hosts and failed authentication are not counted. The
+    /// outcome is only known when the process exits, so the connect is
+    /// recorded then, timestamped with the session start.
+    pub fn setup_connect_recorder(
+        state: &SharedAppState,
+        notebook: &SharedNotebook,
+        session_id: Uuid,
+        connection_id: Uuid,
+    ) {
+        let state_clone = state.clone();
+        let started_at = chrono::Utc::now();
+
+        notebook.connect_child_exited(session_id, move |exit_status| {
+            if !rustconn_core::ssh_session_established(exit_status) {
+                return;
+            }
+            if let Ok(mut state_mut) = state_clone.try_borrow_mut() {
+                let _ = state_mut.record_connect_at(connection_id, started_at);
+            }
+        });
+    }
+
+    /// Tracks input and output of a terminal session so it is disconnected
+    /// once idle past the configured idle timeout
+    ///
+    /// Does nothing when no idle timeout is configured.
+    pub fn setup_idle_tracking(
+        state: &SharedAppState,
+        notebook: &SharedNotebook,
+        session_id: Uuid,
+        connection: &rustconn_core::Connection,
+    ) {
+        let Ok(mut state_mut) = state.try_borrow_mut() else {
+            return;
+        };
+        let minutes = state_mut.settings().connection.idle_timeout_minutes;
+        if minutes == 0 {
+            return;
+        }
+        let timeout = std::time::Duration::from_secs(u64::from(minutes) * 60);
+        state_mut
+            .session_manager_mut()
+            .track_session(session_id, connection, Some(timeout));
+        drop(state_mut);
+
+        let state_clone = state.clone();
+        let touch = move || {
+            if let Ok(mut state_mut) = state_clone.try_borrow_mut() {
+                state_mut.session_manager_mut().touch(session_id);
+            }
+        };
+        let touch_on_input = touch.clone();
+        notebook.connect_commit(session_id, move |_| touch_on_input());
+        notebook.connect_contents_changed(session_id, touch);
+
+        let state_clone = state.clone();
+        notebook.connect_child_exited(session_id, move |_| {
+            if let Ok(mut state_mut) = state_clone.try_borrow_mut() {
+                state_mut.session_manager_mut().remove_session(session_id);
+            }
+        });
+    }
+
+    /// Disconnects idle terminal sessions every [`IDLE_REAP_INTERVAL_SECS`]
+    ///
+    /// Only sessions registered by [`Self::setup_idle_tracking`] are reaped;
+    /// their tabs stay open and are marked disconnected.
+    fn start_idle_reaper(&self) {
+        let state = self.state.clone();
+        let notebook = self.terminal_notebook.clone();
+        glib::timeout_add_seconds_local(IDLE_REAP_INTERVAL_SECS, move || {
+            let reaped = match state.try_borrow_mut() {
+                Ok(mut state_mut) => state_mut
+                    .session_manager_mut()
+                    .reap_idle(chrono::Utc::now()),
+                Err(_) => return glib::ControlFlow::Continue,
+            };
+            for session_id in reaped {
+                notebook.display_output(
+                    session_id,
+                    "\r\n\r\nDisconnected after a period of inactivity.\r\n",
+                );
+                notebook.hang_up(session_id);
+                notebook.mark_tab_disconnected(session_id);
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
     /// Sets up logging handlers for a terminal session based on settings
     ///
     /// Supports three logging modes:
     /// - Activity: logs change counts (default, lightweight)
     /// - Input: logs user commands sent to terminal
     /// - Output: logs full terminal transcript
+    ///
+    /// Input and output are passed through `redactor`, when set, before
+    /// they are written.
     #[allow(clippy::too_many_arguments)]
     fn setup_logging_handlers(
         notebook: &SharedNotebook,
@@ -3443,7 +3672,9 @@ impl MainWindow {
         log_activity: bool,
         log_input: bool,
         log_output: bool,
+        redactor: Option<rustconn_core::session::SecretRedactor>,
     ) {
+        use rustconn_core::session::LogDirection;
         use std::cell::RefCell;
         use std::fs::OpenOptions;
         use std::io::Write;
@@ -3512,11 +3743,22 @@ impl MainWindow {
         // Set up input logging (user commands)
         if log_input {
             let log_writer_clone = log_writer.clone();
+            let notebook_clone = notebook.clone();
             let input_buffer: Rc<RefCell<String>> = Rc::new(RefCell::new(String::new()));
             let last_flush: Rc<RefCell<std::time::Instant>> =
                 Rc::new(RefCell::new(std::time::Instant::now()));
+            let input_redactor = RefCell::new(redactor.clone());
+            let secret_window = notebook.secret_window(session_id);
 
             notebook.connect_commit(session_id, move |text| {
+                // Automation is answering a password prompt
+                if secret_window
+                    .as_ref()
+                    .is_some_and(rustconn_core::session::SecretWindow::is_open)
+                {
+                    return;
+                }
+
                 let mut buffer = input_buffer.borrow_mut();
 
                 // Handle special characters
@@ -3525,12 +3767,16 @@ impl MainWindow {
                         '\r' | '\n' => {
                             // End of command - log it
                             if !buffer.is_empty() {
+                                let line = Self::redact_input_line(
+                                    &mut input_redactor.borrow_mut(),
+                                    &buffer,
+                                    true,
+                                );
                                 if let Ok(mut writer_opt) = log_writer_clone.try_borrow_mut() {
                                     if let Some(ref mut writer) = *writer_opt {
                                         let timestamp =
                                             chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-                                        let _ =
-                                            writeln!(writer, "[{}] INPUT: {}", timestamp, *buffer);
+                                        let _ = writeln!(writer, "[{}] INPUT: {}", timestamp, line);
                                         let _ = writer.flush();
                                     }
                                 }
@@ -3568,6 +3814,19 @@ impl MainWindow {
                             // Skip other control characters
                         }
                         _ => {
+                            if buffer.is_empty() {
+                                // A line typed after a password prompt is a secret
+                                if let Some(redactor) = input_redactor.borrow_mut().as_mut() {
+                                    let text = notebook_clone.get_terminal_text(session_id);
+                                    let prompt = text
+                                        .as_deref()
+                                        .and_then(|t| {
+                                            t.lines().rev().find(|l| !l.trim().is_empty())
+                                        })
+                                        .unwrap_or_default();
+                                    redactor.redact(LogDirection::Output, prompt);
+                                }
+                            }
                             buffer.push(ch);
                         }
                     }
@@ -3576,11 +3835,12 @@ impl MainWindow {
                 // Periodic flush for long-running commands
                 let now = std::time::Instant::now();
                 if now.duration_since(*last_flush.borrow()).as_secs() >= 30 && !buffer.is_empty() {
+                    let line =
+                        Self::redact_input_line(&mut input_redactor.borrow_mut(), &buffer, false);
                     if let Ok(mut writer_opt) = log_writer_clone.try_borrow_mut() {
                         if let Some(ref mut writer) = *writer_opt {
                             let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-                            let _ =
-                                writeln!(writer, "[{}] INPUT (partial): {}", timestamp, *buffer);
+                            let _ = writeln!(writer, "[{}] INPUT (partial): {}", timestamp, line);
                             let _ = writer.flush();
                         }
                     }
@@ -3592,6 +3852,7 @@ impl MainWindow {
         // Set up output logging (full transcript)
         if log_output {
             let log_writer_clone = log_writer.clone();
+            let output_redactor = RefCell::new(redactor);
             let notebook_clone = notebook.clone();
             let last_content: Rc<RefCell<String>> = Rc::new(RefCell::new(String::new()));
             let last_log_time: Rc<RefCell<std::time::Instant>> =
@@ -3618,7 +3879,12 @@ impl MainWindow {
                                         let timestamp =
                                             chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
                                         let _ = writeln!(writer, "[{}] OUTPUT:", timestamp);
+                                        let mut redactor = output_redactor.borrow_mut();
                                         for line in new_lines {
+                                            let line = redactor.as_mut().map_or_else(
+                                                || line.to_string(),
+                                                |r| r.redact(LogDirection::Output, line),
+                                            );
                                             let _ = writeln!(writer, "  {}", line);
                                         }
                                         let _ = writer.flush();
@@ -3636,6 +3902,28 @@ impl MainWindow {
         }
     }
 
+    /// Masks secrets in a logged input line
+    ///
+    /// `complete` marks the end of the line, which ends a secret started
+    /// after a password prompt.
+    fn redact_input_line(
+        redactor: &mut Option<rustconn_core::session::SecretRedactor>,
+        line: &str,
+        complete: bool,
+    ) -> String {
+        use rustconn_core::session::LogDirection;
+
+        let Some(redactor) = redactor.as_mut() else {
+            return line.to_string();
+        };
+        if complete {
+            let redacted = redactor.redact(LogDirection::Input, &format!("{line}\n"));
+            redacted.trim_end_matches('\n').to_string()
+        } else {
+            redactor.redact(LogDirection::Input, line)
+        }
+    }
+
     /// Shows the new connection dialog with optional template selection
     fn show_new_connection_dialog(
         window: &adw::ApplicationWindow,
@@ -3710,7 +3998,8 @@ impl MainWindow {
                             {
                                 let action_enabled = match backend {
                                     rustconn_core::config::SecretBackendType::LibSecret
-                                    | rustconn_core::config::SecretBackendType::Bitwarden => true,
+                                    | rustconn_core::config::SecretBackendType::Bitwarden
+                                    | rustconn_core::config::SecretBackendType::Pass => true,
                                     rustconn_core::config::SecretBackendType::KeePassXc
                                     | rustconn_core::config::SecretBackendType::KdbxFile => {
                                         keepass_enabled && kdbx_path_exists
@@ -3812,6 +4101,15 @@ impl MainWindow {
         operations::duplicate_selected_connection(window.upcast_ref(), state, sidebar);
     }
 
+    /// Archives or restores the selected connection
+    fn toggle_archive_selected_connection(
+        window: &adw::ApplicationWindow,
+        state: &SharedAppState,
+        sidebar: &SharedSidebar,
+    ) {
+        operations::toggle_archive_selected_connection(window.upcast_ref(), state, sidebar);
+    }
+
     /// Copies the selected connection to the internal clipboard
     fn copy_selected_connection(
         window: &adw::ApplicationWindow,
@@ -3851,6 +4149,20 @@ impl MainWindow {
         sidebar.restore_state(&tree_state);
     }
 
+    /// Reloads the sidebar after connections were edited or deleted
+    ///
+    /// Like [`Self::reload_sidebar_preserving_state`], but keeps cached
+    /// searches the changed connections cannot affect.
+    pub fn reload_sidebar_after_connection_change(
+        state: &SharedAppState,
+        sidebar: &SharedSidebar,
+        changed: &[Uuid],
+    ) {
+        let tree_state = sidebar.save_state();
+        operations::reload_sidebar_after_connection_change(state, sidebar, changed);
+        sidebar.restore_state(&tree_state);
+    }
+
     /// Presents the window to the user
     pub fn present(&self) {
         self.window.present();
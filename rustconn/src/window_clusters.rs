@@ -4,7 +4,15 @@
 //! including cluster dialogs and related functionality.
 
 use crate::alert;
+use adw::prelude::*;
 use gtk4::prelude::*;
+use libadwaita as adw;
+use rustconn_core::{
+    ClusterCommandResult, ClusterCommandStatus, ClusterSession, ClusterSessionStatus,
+    SshCommandRunner,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
 use std::rc::Rc;
 use uuid::Uuid;
 
@@ -133,6 +141,19 @@ fn setup_cluster_dialog_callbacks(
         );
     });
 
+    // Run command callback
+    let state_clone = state.clone();
+    let notebook_clone = notebook.clone();
+    let dialog_window = dialog_ref.window().clone();
+    dialog_ref.set_on_run_command(move |cluster_id| {
+        run_cluster_command(
+            dialog_window.upcast_ref(),
+            &state_clone,
+            &notebook_clone,
+            cluster_id,
+        );
+    });
+
     // Edit callback
     let state_clone = state.clone();
     let notebook_clone = notebook.clone();
@@ -249,6 +270,138 @@ fn connect_cluster(
     }
 }
 
+/// Asks for a command and runs it on the cluster's connected members
+///
+/// Members with an open tab count as connected. The command runs over
+/// separate non-interactive SSH connections in the background, and the
+/// per-host results are shown once every member has finished.
+fn run_cluster_command(
+    parent: &gtk4::Window,
+    state: &SharedAppState,
+    notebook: &SharedNotebook,
+    cluster_id: Uuid,
+) {
+    let (cluster, connections) = if let Ok(state_ref) = state.try_borrow() {
+        let Some(cluster) = state_ref.get_cluster(cluster_id).cloned() else {
+            return;
+        };
+        let connections: Vec<_> = state_ref
+            .list_connections()
+            .iter()
+            .cloned()
+            .cloned()
+            .collect();
+        (cluster, connections)
+    } else {
+        return;
+    };
+    let open: HashSet<Uuid> = notebook
+        .get_all_sessions()
+        .iter()
+        .map(|session| session.connection_id)
+        .collect();
+
+    let entry = gtk4::Entry::builder()
+        .placeholder_text("uptime")
+        .activates_default(true)
+        .build();
+    let dialog = adw::AlertDialog::new(
+        Some(&format!("Run Command on {}", cluster.name)),
+        Some("The command runs on every connected member over a separate SSH connection and is stopped if it takes longer than a minute."),
+    );
+    dialog.set_extra_child(Some(&entry));
+    dialog.add_response("cancel", "Cancel");
+    dialog.add_response("run", "Run");
+    dialog.set_response_appearance("run", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("run"));
+    dialog.set_close_response("cancel");
+
+    let parent_clone = parent.clone();
+    dialog.connect_response(None, move |_, response| {
+        let command = entry.text().trim().to_string();
+        if response != "run" || command.is_empty() {
+            return;
+        }
+
+        let mut session = ClusterSession::new(&cluster);
+        for id in cluster.connection_ids.iter().filter(|id| open.contains(id)) {
+            session.update_session_status(*id, ClusterSessionStatus::Connected);
+        }
+        let runner = SshCommandRunner::new(&connections);
+        let names: HashMap<Uuid, String> =
+            connections.iter().map(|c| (c.id, c.name.clone())).collect();
+        let parent = parent_clone.clone();
+        let cluster_name = cluster.name.clone();
+        crate::utils::spawn_blocking_with_callback(
+            move || session.run_command(&command, &runner),
+            move |results| {
+                show_cluster_command_results(&parent, &cluster_name, &results, &names);
+            },
+        );
+    });
+    dialog.present(Some(parent));
+}
+
+/// Shows the per-host output of a cluster command
+fn show_cluster_command_results(
+    parent: &gtk4::Window,
+    cluster_name: &str,
+    results: &[(Uuid, ClusterCommandResult)],
+    names: &HashMap<Uuid, String>,
+) {
+    let (mut succeeded, mut failed, mut skipped) = (0, 0, 0);
+    let mut text = String::new();
+    for (id, result) in results {
+        let status = match &result.status {
+            ClusterCommandStatus::Exited(code) => {
+                if *code == 0 {
+                    succeeded += 1;
+                } else {
+                    failed += 1;
+                }
+                format!("exit {code}")
+            }
+            ClusterCommandStatus::Failed(message) => {
+                failed += 1;
+                format!("failed: {message}")
+            }
+            ClusterCommandStatus::Skipped => {
+                skipped += 1;
+                "skipped, not connected".to_string()
+            }
+        };
+        let name = names.get(id).map_or("Unknown connection", String::as_str);
+        let _ = writeln!(text, "== {name} ({status})");
+        text.push_str(&result.output);
+        if !result.output.is_empty() && !result.output.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+
+    let view = gtk4::TextView::builder()
+        .editable(false)
+        .monospace(true)
+        .wrap_mode(gtk4::WrapMode::WordChar)
+        .build();
+    view.buffer().set_text(&text);
+    let scrolled = gtk4::ScrolledWindow::builder()
+        .min_content_height(300)
+        .min_content_width(500)
+        .child(&view)
+        .build();
+
+    let dialog = adw::AlertDialog::new(
+        Some(&format!("{cluster_name}: Command Results")),
+        Some(&format!(
+            "{succeeded} succeeded, {failed} failed, {skipped} skipped"
+        )),
+    );
+    dialog.set_extra_child(Some(&scrolled));
+    dialog.add_response("ok", "OK");
+    dialog.set_default_response(Some("ok"));
+    dialog.present(Some(parent));
+}
+
 /// Edits a cluster
 fn edit_cluster(
     parent: &gtk4::Window,
@@ -12,13 +12,42 @@ use adw::prelude::*;
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
-use rustconn_core::models::{Credentials, PasswordSource};
+use rustconn_core::config::SecretBackendType;
+use rustconn_core::models::{Connection, ConnectionGroup, Credentials, PasswordSource};
 use std::rc::Rc;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Type alias for shared sidebar reference
 pub type SharedSidebar = Rc<ConnectionSidebar>;
 
+/// Creates the `KeePassXC` entry holding a new connection's password
+///
+/// The entry is created in the background under the connection's group
+/// path; failures are logged.
+fn create_keepassxc_entry(connection: Connection, groups: Vec<ConnectionGroup>, password: String) {
+    crate::utils::spawn_blocking_with_callback(
+        move || {
+            use rustconn_core::secret::{KeePassXcBackend, SecretManager};
+            let manager = SecretManager::new(vec![Arc::new(KeePassXcBackend::new("rustconn"))]);
+            let rt = tokio::runtime::Runtime::new().map_err(|e| format!("Runtime error: {e}"))?;
+            rt.block_on(manager.create_connection_entry(
+                &connection,
+                &groups,
+                &secrecy::SecretString::from(password),
+            ))
+            .map_err(|e| format!("{e}"))
+        },
+        |result| match result {
+            Ok(Some(entry)) => {
+                tracing::info!("Created KeePassXC entry {}", entry.path);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to create KeePassXC entry: {}", e),
+        },
+    );
+}
+
 /// Shows the new connection dialog (always creates blank connection)
 pub fn show_new_connection_dialog(
     window: &gtk4::Window,
@@ -101,6 +130,7 @@ pub fn show_new_connection_dialog_internal(
                 let conn_username = conn.username.clone();
                 let password_source = conn.password_source;
                 let protocol = conn.protocol;
+                let entry_connection = conn.clone();
 
                 match state_mut.create_connection(conn) {
                     Ok(conn_id) => {
@@ -110,7 +140,13 @@ pub fn show_new_connection_dialog_internal(
                             if let Some(pwd) = password.clone() {
                                 // Get KeePass settings
                                 let settings = state_mut.settings().clone();
-                                if settings.secrets.kdbx_enabled {
+                                if settings.secrets.preferred_backend
+                                    == SecretBackendType::KeePassXc
+                                {
+                                    let groups =
+                                        state_mut.list_groups().into_iter().cloned().collect();
+                                    create_keepassxc_entry(entry_connection, groups, pwd);
+                                } else if settings.secrets.kdbx_enabled {
                                     if let Some(kdbx_path) = settings.secrets.kdbx_path.clone() {
                                         let key_file = settings.secrets.kdbx_key_file.clone();
                                         let entry_name = format!(
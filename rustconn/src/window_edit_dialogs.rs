@@ -109,10 +109,13 @@ pub fn edit_selected_connection(
         let window_clone = window.clone();
         dialog.run(move |result| {
             if let Some(dialog_result) = result {
-                let updated_conn = dialog_result.connection;
+                let mut updated_conn = dialog_result.connection;
                 let password = dialog_result.password;
 
                 if let Ok(mut state_mut) = state_clone.try_borrow_mut() {
+                    // The dialog does not edit the archive state, keep it
+                    updated_conn.archived =
+                        state_mut.get_connection(id).is_some_and(|c| c.archived);
                     // Clone values needed for password saving
                     let conn_name = updated_conn.name.clone();
                     let conn_host = updated_conn.host.clone();
@@ -274,7 +277,11 @@ pub fn edit_selected_connection(
                             let state = state_clone.clone();
                             let sidebar = sidebar_clone.clone();
                             glib::idle_add_local_once(move || {
-                                MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+                                MainWindow::reload_sidebar_after_connection_change(
+                                    &state,
+                                    &sidebar,
+                                    &[id],
+                                );
                             });
                         }
                         Err(e) => {
@@ -420,7 +427,11 @@ pub fn rename_selected_item(
                             let sidebar = sidebar_clone.clone();
                             let window = window_clone.clone();
                             glib::idle_add_local_once(move || {
-                                MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+                                MainWindow::reload_sidebar_after_connection_change(
+                                    &state,
+                                    &sidebar,
+                                    &[id],
+                                );
                                 window.close();
                             });
                         }
@@ -858,6 +869,7 @@ fn start_quick_ssh(
         params.username.as_deref(),
         None,
         &[],
+        None,
     );
 }
 
@@ -83,7 +83,15 @@ pub fn delete_selected_connection(
                             let state = state_clone.clone();
                             let sidebar = sidebar_clone.clone();
                             glib::idle_add_local_once(move || {
-                                MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+                                if is_group {
+                                    MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+                                } else {
+                                    MainWindow::reload_sidebar_after_connection_change(
+                                        &state,
+                                        &sidebar,
+                                        &[id],
+                                    );
+                                }
                             });
                         }
                         Err(e) => {
@@ -96,6 +104,63 @@ pub fn delete_selected_connection(
     );
 }
 
+/// Archives the selected connection, or restores it if it is archived
+///
+/// Archived connections are hidden from the sidebar tree unless "Show
+/// Archived" is on; they can also be found by searching for `status:archived`.
+pub fn toggle_archive_selected_connection(
+    window: &gtk4::Window,
+    state: &SharedAppState,
+    sidebar: &SharedSidebar,
+) {
+    let Some(conn_item) = sidebar.get_selected_item() else {
+        return;
+    };
+    if conn_item.is_group() {
+        return;
+    }
+    let Ok(id) = Uuid::parse_str(&conn_item.id()) else {
+        return;
+    };
+
+    let Ok(mut state_mut) = state.try_borrow_mut() else {
+        return;
+    };
+    let Some(archived) = state_mut.get_connection(id).map(|c| !c.archived) else {
+        return;
+    };
+    match state_mut.set_connection_archived(id, archived) {
+        Ok(()) => {
+            drop(state_mut);
+            // Defer sidebar reload to prevent UI freeze
+            let state = state.clone();
+            let sidebar = sidebar.clone();
+            let window = window.clone();
+            glib::idle_add_local_once(move || {
+                MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+                let message = if archived {
+                    "Connection archived"
+                } else {
+                    "Connection restored"
+                };
+                crate::toast::show_toast_on_window(
+                    &window,
+                    message,
+                    crate::toast::ToastType::Success,
+                );
+            });
+        }
+        Err(e) => {
+            tracing::error!("{e}");
+            crate::toast::show_toast_on_window(
+                window,
+                "Failed to archive connection",
+                crate::toast::ToastType::Error,
+            );
+        }
+    }
+}
+
 /// Duplicates the selected connection
 pub fn duplicate_selected_connection(
     window: &gtk4::Window,
@@ -258,6 +323,36 @@ pub fn paste_connection(window: &gtk4::Window, state: &SharedAppState, sidebar:
 
 /// Reloads the sidebar with current data (preserving hierarchy)
 pub fn reload_sidebar(state: &SharedAppState, sidebar: &SharedSidebar) {
+    // Connections may have changed, so cached search results are stale
+    sidebar.search_engine().invalidate_cache();
+    rebuild_sidebar(state, sidebar);
+}
+
+/// Reloads the sidebar after the given connections were edited or deleted
+///
+/// Only cached searches the changed connections can affect are dropped.
+pub fn reload_sidebar_after_connection_change(
+    state: &SharedAppState,
+    sidebar: &SharedSidebar,
+    changed: &[Uuid],
+) {
+    let search_engine = sidebar.search_engine();
+    if let Ok(state_ref) = state.try_borrow() {
+        let groups: Vec<_> = state_ref.list_groups().into_iter().cloned().collect();
+        for id in changed {
+            match state_ref.get_connection(*id) {
+                Some(connection) => search_engine.invalidate_connection(connection, &groups),
+                None => search_engine.invalidate_removed_connection(*id),
+            }
+        }
+    } else {
+        search_engine.invalidate_cache();
+    }
+    rebuild_sidebar(state, sidebar);
+}
+
+/// Repopulates the sidebar tree from the current state
+fn rebuild_sidebar(state: &SharedAppState, sidebar: &SharedSidebar) {
     let store = sidebar.store();
     store.remove_all();
 
@@ -265,6 +360,10 @@ pub fn reload_sidebar(state: &SharedAppState, sidebar: &SharedSidebar) {
         tracing::warn!("Could not borrow state for sidebar reload");
         return;
     };
+    sidebar.set_prefetch_source(
+        state_ref.list_groups().into_iter().cloned().collect(),
+        state_ref.list_connections().into_iter().cloned().collect(),
+    );
 
     // Add root groups with their children
     for group in state_ref.get_root_groups() {
@@ -286,6 +385,10 @@ pub fn reload_sidebar(state: &SharedAppState, sidebar: &SharedSidebar) {
             &conn.host,
             &status,
         );
+        if let Some(icon) = &conn.icon {
+            item.set_icon(icon.as_str());
+        }
+        item.set_archived(conn.archived);
         store.append(&item);
     }
 }
@@ -317,6 +420,10 @@ pub fn add_group_children_static(
             &conn.host,
             &status,
         );
+        if let Some(icon) = &conn.icon {
+            item.set_icon(icon.as_str());
+        }
+        item.set_archived(conn.archived);
         parent_item.add_child(&item);
     }
 }
@@ -429,13 +536,23 @@ fn perform_bulk_delete(
 ) {
     let mut success_count = 0;
     let mut failures: Vec<String> = Vec::new();
+    let mut deleted_connections = Vec::new();
+    let mut deleted_groups = false;
 
     if let Ok(mut state_mut) = state.try_borrow_mut() {
         for id in &selected_ids {
             // Try to delete as connection first, then as group
-            let delete_result = state_mut
-                .delete_connection(*id)
-                .or_else(|_| state_mut.delete_group(*id));
+            let delete_result = match state_mut.delete_connection(*id) {
+                Ok(()) => {
+                    deleted_connections.push(*id);
+                    Ok(())
+                }
+                Err(_) => {
+                    let result = state_mut.delete_group(*id);
+                    deleted_groups |= result.is_ok();
+                    result
+                }
+            };
 
             match delete_result {
                 Ok(()) => success_count += 1,
@@ -449,7 +566,16 @@ fn perform_bulk_delete(
     let sidebar = sidebar.clone();
     let window = window.clone();
     glib::idle_add_local_once(move || {
-        MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+        // Deleted groups can change which group names connections match
+        if deleted_groups {
+            MainWindow::reload_sidebar_preserving_state(&state, &sidebar);
+        } else {
+            MainWindow::reload_sidebar_after_connection_change(
+                &state,
+                &sidebar,
+                &deleted_connections,
+            );
+        }
 
         // Show results
         if failures.is_empty() {
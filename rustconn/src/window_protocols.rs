@@ -35,6 +35,22 @@ pub fn start_ssh_connection(
 
     let conn_name = conn.name.clone();
 
+    // Resolve saved jump hosts first so a broken chain fails instead of
+    // silently connecting directly
+    let jump_arg = match resolve_jump_arg(state, conn) {
+        Ok(jump_arg) => jump_arg,
+        Err(e) => {
+            tracing::warn!("Cannot resolve jump hosts for '{conn_name}': {e}");
+            sidebar.update_connection_status(&connection_id.to_string(), "failed");
+            if let Some(root) = notebook.widget().root() {
+                if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                    crate::alert::show_error(window, "Connection Failed", &e);
+                }
+            }
+            return None;
+        }
+    };
+
     // Get terminal settings from state
     let terminal_settings = state
         .try_borrow()
@@ -51,6 +67,10 @@ pub fn start_ssh_connection(
         &terminal_settings,
     );
 
+    if let Some(icon) = &conn.icon {
+        notebook.set_tab_icon(session_id, icon);
+    }
+
     // Record connection start in history
     let history_entry_id = if let Ok(mut state_mut) = state.try_borrow_mut() {
         Some(state_mut.record_connection_start(conn, conn.username.as_deref()))
@@ -69,7 +89,7 @@ pub fn start_ssh_connection(
     let username = conn.username.clone();
 
     // Get SSH-specific options
-    let (identity_file, extra_args) =
+    let (identity_file, extra_args, environment) =
         if let rustconn_core::ProtocolConfig::Ssh(ssh_config) = &conn.protocol_config {
             let key = ssh_config
                 .key_path
@@ -77,70 +97,10 @@ pub fn start_ssh_connection(
                 .map(|p| p.to_string_lossy().to_string());
             let mut args = Vec::new();
 
-            let mut jump_hosts = Vec::new();
-
-            // Handle string-based proxy jump (legacy/manual)
-            if let Some(proxy) = &ssh_config.proxy_jump {
-                jump_hosts.push(proxy.clone());
-            }
-
-            // Handle reference-based jump host (recursive resolution)
-            if let Some(jump_id) = ssh_config.jump_host_id {
-                if let Ok(state_ref) = state.try_borrow() {
-                    let mut current_id = Some(jump_id);
-                    let mut visited = std::collections::HashSet::new();
-                    visited.insert(connection_id); // Avoid self-reference loop
-
-                    // Limit recursion depth to avoid infinite loops
-                    for _ in 0..10 {
-                        if let Some(jid) = current_id {
-                            if visited.contains(&jid) {
-                                break;
-                            }
-                            visited.insert(jid);
-
-                            if let Some(jump_conn) = state_ref.get_connection(jid) {
-                                // Format: [user@]host[:port]
-                                let mut host_str = jump_conn.host.clone();
-                                if let Some(user) = &jump_conn.username {
-                                    host_str = format!("{}@{}", user, host_str);
-                                }
-                                if jump_conn.port != 22 {
-                                    host_str = format!("{}:{}", host_str, jump_conn.port);
-                                }
-                                jump_hosts.push(host_str);
-
-                                // Check if this jump host has its own jumper
-                                if let rustconn_core::ProtocolConfig::Ssh(jump_config) =
-                                    &jump_conn.protocol_config
-                                {
-                                    // Prepend manual proxy if exists on jump host (unlikely but possible)
-                                    if let Some(p) = &jump_config.proxy_jump {
-                                        jump_hosts.insert(jump_hosts.len() - 1, p.clone());
-                                    }
-                                    current_id = jump_config.jump_host_id;
-                                } else {
-                                    current_id = None;
-                                }
-                            } else {
-                                current_id = None;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                }
-            }
-
-            if !jump_hosts.is_empty() {
+            // Saved jump hosts take precedence over a manual proxy jump
+            if let Some(jump) = jump_arg.or_else(|| ssh_config.proxy_jump.clone()) {
                 args.push("-J".to_string());
-                // SSH expects comma-separated list: jump1,jump2,dest
-                // But -J option takes jumps to reach dest.
-                // If we chain: A -> B -> C -> Dest.
-                // We need `ssh -J B,C Dest` (from A).
-                // My recursion pushed B then C. So `jump_hosts` is [B, C].
-                // Join with comma.
-                args.push(jump_hosts.join(","));
+                args.push(jump);
             }
 
             if ssh_config.use_control_master {
@@ -152,21 +112,25 @@ pub fn start_ssh_connection(
                 args.push("-A".to_string());
             }
 
+            for forward in &ssh_config.forwards {
+                args.extend(forward.command_args());
+            }
+
+            // Algorithm preferences go first so they win over custom options
+            args.extend(ssh_config.algorithms.command_args());
+
             for (k, v) in &ssh_config.custom_options {
                 args.push("-o".to_string());
                 args.push(format!("{k}={v}"));
             }
 
-            (key, args)
+            args.extend(ssh_config.send_env_args());
+
+            (key, args, ssh_config.environment_strings())
         } else {
-            (None, Vec::new())
+            (None, Vec::new(), Vec::new())
         };
 
-    // Update last_connected timestamp
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
-
     // Set up session logging if enabled
     if logging_enabled {
         MainWindow::setup_session_logging(state, notebook, session_id, connection_id, &conn_name);
@@ -186,10 +150,11 @@ pub fn start_ssh_connection(
         ssh_cmd_parts.push(key.clone());
     }
     ssh_cmd_parts.extend(extra_args.clone());
+    let destination = rustconn_core::unbracketed_host(&host);
     let destination = if let Some(ref user) = username {
-        format!("{user}@{host}")
+        format!("{user}@{destination}")
     } else {
-        host.clone()
+        destination.to_string()
     };
     ssh_cmd_parts.push(destination);
     let ssh_command = ssh_cmd_parts.join(" ");
@@ -202,6 +167,10 @@ pub fn start_ssh_connection(
 
     // Spawn SSH
     let extra_refs: Vec<&str> = extra_args.iter().map(std::string::String::as_str).collect();
+    let env_refs: Vec<&str> = environment
+        .iter()
+        .map(std::string::String::as_str)
+        .collect();
     notebook.spawn_ssh(
         session_id,
         &host,
@@ -209,14 +178,43 @@ pub fn start_ssh_connection(
         username.as_deref(),
         identity_file.as_deref(),
         &extra_refs,
+        Some(&env_refs),
     );
 
+    // Count the session only if ssh got past connecting and authentication
+    MainWindow::setup_connect_recorder(state, notebook, session_id, connection_id);
+    MainWindow::setup_idle_tracking(state, notebook, session_id, conn);
+
     // Wire up child exited callback for session cleanup (second call for terminal monitoring)
     MainWindow::setup_child_exited_handler(state, notebook, sidebar, session_id, connection_id);
 
     Some(session_id)
 }
 
+/// Builds the `-J` value for the saved jump hosts of an SSH connection
+///
+/// Returns `Ok(None)` when the connection has no jump host and an error
+/// message when a jump host is missing or the chain loops.
+fn resolve_jump_arg(
+    state: &SharedAppState,
+    conn: &rustconn_core::Connection,
+) -> Result<Option<String>, String> {
+    let state_ref = state
+        .try_borrow()
+        .map_err(|_| "Application state is busy".to_string())?;
+    // The connection being started may be an unsaved copy
+    let lookup = |id: Uuid| {
+        if id == conn.id {
+            Some(conn)
+        } else {
+            state_ref.get_connection(id)
+        }
+    };
+    let chain = rustconn_core::resolve_jump_chain(conn.id, lookup).map_err(|e| e.to_string())?;
+    let hops: Vec<&rustconn_core::Connection> = chain.into_iter().filter_map(lookup).collect();
+    Ok(rustconn_core::proxy_jump_arg(&hops))
+}
+
 /// Starts a VNC connection
 ///
 /// Creates a VNC session tab with native widget and initiates connection.
@@ -319,6 +317,10 @@ fn start_vnc_connection_internal(
     // Create VNC session tab with native widget
     let session_id = notebook.create_vnc_session_tab(connection_id, &conn_name);
 
+    if let Some(icon) = &conn.icon {
+        notebook.set_tab_icon(session_id, icon);
+    }
+
     // Record connection start in history
     let history_entry_id = if let Ok(mut state_mut) = state.try_borrow_mut() {
         Some(state_mut.record_connection_start(conn, conn.username.as_deref()))
@@ -352,6 +354,9 @@ fn start_vnc_connection_internal(
             } else if vnc_state == crate::session::SessionState::Connected {
                 notebook_for_state.mark_tab_connected(session_id);
                 sidebar_for_state.increment_session_count(&connection_id.to_string());
+                if let Ok(mut state_mut) = state_for_callback.try_borrow_mut() {
+                    let _ = state_mut.record_connect(connection_id);
+                }
             }
         });
 
@@ -374,11 +379,6 @@ fn start_vnc_connection_internal(
         }
     }
 
-    // Update last_connected timestamp
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
-
     Some(session_id)
 }
 
@@ -470,6 +470,10 @@ fn start_spice_connection_internal(
     // Create SPICE session tab with native widget
     let session_id = notebook.create_spice_session_tab(connection_id, &conn_name);
 
+    if let Some(icon) = &conn.icon {
+        notebook.set_tab_icon(session_id, icon);
+    }
+
     // Record connection start in history
     let history_entry_id = if let Ok(mut state_mut) = state.try_borrow_mut() {
         Some(state_mut.record_connection_start(conn, conn.username.as_deref()))
@@ -496,6 +500,9 @@ fn start_spice_connection_internal(
                 config = config.with_ca_cert(ca_path);
             }
             config = config.with_skip_cert_verify(opts.skip_cert_verify);
+            if let Some(fingerprint) = &opts.cert_fingerprint {
+                config = config.with_cert_fingerprint(fingerprint);
+            }
 
             // Configure USB redirection
             config = config.with_usb_redirection(opts.usb_redirection);
@@ -534,6 +541,26 @@ fn start_spice_connection_internal(
             } else if spice_state == SpiceConnectionState::Connected {
                 notebook_for_state.mark_tab_connected(session_id);
                 sidebar_for_state.increment_session_count(&connection_id.to_string());
+                if let Ok(mut state_mut) = state_for_callback.try_borrow_mut() {
+                    let _ = state_mut.record_connect(connection_id);
+                }
+            }
+        });
+
+        // Store certificates the user chose to always trust as the new pin
+        let state_for_pin = state.clone();
+        spice_widget.connect_certificate_pinned(move |fingerprint| {
+            let Ok(mut state_mut) = state_for_pin.try_borrow_mut() else {
+                return;
+            };
+            let Some(mut updated) = state_mut.get_connection(connection_id).cloned() else {
+                return;
+            };
+            if let rustconn_core::ProtocolConfig::Spice(ref mut spice) = updated.protocol_config {
+                spice.cert_fingerprint = Some(fingerprint.to_string());
+            }
+            if let Err(e) = state_mut.update_connection(connection_id, updated) {
+                tracing::warn!("Failed to store pinned SPICE certificate: {e}");
             }
         });
 
@@ -551,11 +578,6 @@ fn start_spice_connection_internal(
         }
     }
 
-    // Update last_connected timestamp
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
-
     Some(session_id)
 }
 
@@ -593,6 +615,7 @@ pub fn start_zerotrust_connection(
                 rustconn_core::models::ZeroTrustProvider::TailscaleSsh => "tailscale",
                 rustconn_core::models::ZeroTrustProvider::Boundary => "boundary",
                 rustconn_core::models::ZeroTrustProvider::Generic => "generic",
+                rustconn_core::models::ZeroTrustProvider::GenericProxy => "generic_proxy",
             };
             (prog, args, provider, key)
         } else {
@@ -618,6 +641,10 @@ pub fn start_zerotrust_connection(
         &terminal_settings,
     );
 
+    if let Some(icon) = &conn.icon {
+        notebook.set_tab_icon(session_id, icon);
+    }
+
     // Record connection start in history
     let history_entry_id = if let Ok(mut state_mut) = state.try_borrow_mut() {
         Some(state_mut.record_connection_start(conn, conn.username.as_deref()))
@@ -630,11 +657,6 @@ pub fn start_zerotrust_connection(
         notebook.set_history_entry_id(session_id, entry_id);
     }
 
-    // Update last_connected timestamp
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
-
     // Set up session logging if enabled
     if logging_enabled {
         MainWindow::setup_session_logging(state, notebook, session_id, connection_id, &conn_name);
@@ -660,5 +682,9 @@ pub fn start_zerotrust_connection(
     let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string());
     notebook.spawn_command(session_id, &[&shell, "-c", &full_command], None, None);
 
+    // Providers that wrap ssh report its error status when connecting fails
+    MainWindow::setup_connect_recorder(state, notebook, session_id, connection_id);
+    MainWindow::setup_idle_tracking(state, notebook, session_id, conn);
+
     Some(session_id)
 }
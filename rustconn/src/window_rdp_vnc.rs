@@ -249,6 +249,7 @@ fn start_embedded_rdp_session(
         .with_port(port)
         .with_resolution(1280, 720) // Placeholder, will be updated
         .with_clipboard(true)
+        .with_microphone(rdp_config.microphone_redirect)
         .with_performance_mode(rdp_config.performance_mode);
 
     if !username.is_empty() {
@@ -275,6 +276,7 @@ fn start_embedded_rdp_session(
             .map(|f| EmbeddedSharedFolder {
                 local_path: f.local_path.clone(),
                 share_name: f.share_name.clone(),
+                read_only: f.read_only,
             })
             .collect();
         embedded_config = embedded_config.with_shared_folders(folders);
@@ -308,6 +310,9 @@ fn start_embedded_rdp_session(
         crate::embedded_rdp::RdpConnectionState::Connected => {
             notebook_for_state.mark_tab_connected(session_id);
             sidebar_for_state.increment_session_count(&connection_id.to_string());
+            if let Ok(mut state_mut) = state_for_callback.try_borrow_mut() {
+                let _ = state_mut.record_connect(connection_id);
+            }
         }
         crate::embedded_rdp::RdpConnectionState::Error => {
             // Record connection failure in history
@@ -337,6 +342,13 @@ fn start_embedded_rdp_session(
         conn_name,
         embedded_widget.clone(),
     );
+    let custom_icon = state
+        .try_borrow()
+        .ok()
+        .and_then(|s| s.get_connection(connection_id).and_then(|c| c.icon.clone()));
+    if let Some(icon) = custom_icon {
+        notebook.set_tab_icon(session_id, &icon);
+    }
 
     // Store history entry ID in session for later use
     if let Some(entry_id) = history_entry_id {
@@ -361,11 +373,6 @@ fn start_embedded_rdp_session(
         }
     }
 
-    // Update last_connected timestamp
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
-
     // Connect after a short delay to let GTK layout the widget
     // This ensures we get the actual widget size for RDP resolution
     let widget_for_connect = embedded_widget.clone();
@@ -468,6 +475,10 @@ fn start_external_rdp_session(
         true
     } else {
         sidebar.increment_session_count(&connection_id.to_string());
+        // The external client launched; count it as a successful connect
+        if let Ok(mut state_mut) = state.try_borrow_mut() {
+            let _ = state_mut.record_connect(connection_id);
+        }
         // Record connection end when external process exits (we can't track this easily)
         // For external sessions, we record end immediately as we don't have state tracking
         if let Some(entry_id) = history_entry_id {
@@ -489,11 +500,6 @@ fn start_external_rdp_session(
     if let Some(info) = notebook.get_session_info(session_id) {
         split_view.add_session(info, None);
     }
-
-    // Update last_connected
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
 }
 
 /// Starts a VNC connection with password dialog
@@ -683,6 +689,9 @@ fn start_vnc_session_internal(
 
     // Create VNC session tab with native widget
     let session_id = notebook.create_vnc_session_tab(connection_id, &conn_name);
+    if let Some(icon) = &conn_for_history.icon {
+        notebook.set_tab_icon(session_id, icon);
+    }
 
     // Store history entry ID in session for later use
     if let Some(entry_id) = history_entry_id {
@@ -710,6 +719,9 @@ fn start_vnc_session_internal(
             } else if vnc_state == crate::session::SessionState::Connected {
                 notebook_for_state.mark_tab_connected(session_id);
                 sidebar_for_state.increment_session_count(&connection_id.to_string());
+                if let Ok(mut state_mut) = state_for_callback.try_borrow_mut() {
+                    let _ = state_mut.record_connect(connection_id);
+                }
             }
         });
 
@@ -735,9 +747,4 @@ fn start_vnc_session_internal(
     split_view.widget().set_vexpand(false);
     notebook.widget().set_vexpand(true);
     notebook.show_tab_view_content();
-
-    // Update last_connected timestamp
-    if let Ok(mut state_mut) = state.try_borrow_mut() {
-        let _ = state_mut.update_last_connected(connection_id);
-    }
 }
@@ -94,6 +94,10 @@ pub fn rebuild_sidebar_sorted(state: &SharedAppState, sidebar: &SharedSidebar) {
 
     // Check if there are any connections at all
     let total_connections = state_ref.list_connections().len();
+    sidebar.set_prefetch_source(
+        state_ref.list_groups().into_iter().cloned().collect(),
+        state_ref.list_connections().into_iter().cloned().collect(),
+    );
 
     drop(state_ref);
 
@@ -130,6 +134,10 @@ pub fn rebuild_sidebar_sorted(state: &SharedAppState, sidebar: &SharedSidebar) {
             &conn.host,
             &status,
         );
+        if let Some(icon) = &conn.icon {
+            item.set_icon(icon.as_str());
+        }
+        item.set_archived(conn.archived);
         store.append(&item);
     }
 }
@@ -181,6 +189,10 @@ pub fn add_sorted_group_children(
             &conn.host,
             &status,
         );
+        if let Some(icon) = &conn.icon {
+            item.set_icon(icon.as_str());
+        }
+        item.set_archived(conn.archived);
         parent_item.add_child(&item);
     }
 }
@@ -219,7 +231,7 @@ pub fn handle_drag_drop(state: &SharedAppState, sidebar: &SharedSidebar, data: &
                     // Drop INTO group - move to the group
                     if let Ok(mut state_mut) = state.try_borrow_mut() {
                         if let Err(e) =
-                            state_mut.move_connection_to_group(item_uuid, Some(target_uuid))
+                            state_mut.move_connections_to_group(&[item_uuid], Some(target_uuid))
                         {
                             eprintln!("Failed to move connection to group: {e}");
                             return;
@@ -267,11 +279,38 @@ pub fn handle_drag_drop(state: &SharedAppState, sidebar: &SharedSidebar, data: &
             }
         }
         "group" => {
-            // Moving a group - reorder among groups
+            // Moving a group - reparent it first if the drop changes its parent.
+            // The manager rejects moves that would place a group inside itself.
+            let (current_parent, new_parent) = {
+                let state_ref = state.borrow();
+                let current_parent = state_ref.get_group(item_uuid).and_then(|g| g.parent_id);
+                let new_parent = if target_is_group {
+                    if position == "into" {
+                        Some(target_uuid)
+                    } else {
+                        state_ref.get_group(target_uuid).and_then(|g| g.parent_id)
+                    }
+                } else {
+                    state_ref
+                        .get_connection(target_uuid)
+                        .and_then(|c| c.group_id)
+                };
+                (current_parent, new_parent)
+            };
+
             if let Ok(mut state_mut) = state.try_borrow_mut() {
-                if let Err(e) = state_mut.reorder_group(item_uuid, target_uuid) {
-                    eprintln!("Failed to reorder group: {e}");
-                    return;
+                if new_parent != current_parent {
+                    if let Err(e) = state_mut.move_group_to_parent(item_uuid, new_parent) {
+                        eprintln!("Failed to move group: {e}");
+                        return;
+                    }
+                }
+
+                if target_is_group && position != "into" {
+                    if let Err(e) = state_mut.reorder_group(item_uuid, target_uuid) {
+                        eprintln!("Failed to reorder group: {e}");
+                        return;
+                    }
                 }
             }
         }
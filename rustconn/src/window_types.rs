@@ -55,6 +55,7 @@ pub fn get_protocol_string(config: &rustconn_core::ProtocolConfig) -> String {
                 rustconn_core::models::ZeroTrustProvider::TailscaleSsh => "tailscale",
                 rustconn_core::models::ZeroTrustProvider::Boundary => "boundary",
                 rustconn_core::models::ZeroTrustProvider::Generic => "generic",
+                rustconn_core::models::ZeroTrustProvider::GenericProxy => "generic_proxy",
             };
             format!("zerotrust:{provider}")
         }